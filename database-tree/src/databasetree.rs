@@ -2,7 +2,7 @@ use crate::{
     databasetreeitems::DatabaseTreeItems, error::Result, item::DatabaseTreeItemKind,
     tree_iter::TreeIterator,
 };
-use crate::{Database, Table};
+use crate::{Database, Routine, Table};
 use std::{collections::BTreeSet, usize};
 
 ///
@@ -86,10 +86,75 @@ impl DatabaseTree {
                     Some((database.clone(), table.clone()))
                 }
                 DatabaseTreeItemKind::Schema { .. } => None,
+                DatabaseTreeItemKind::Routine { .. } => None,
             }
         })
     }
 
+    pub fn selected_routine(&self) -> Option<(Database, Routine)> {
+        match self.selected_item()?.kind() {
+            DatabaseTreeItemKind::Routine { database, routine } => {
+                Some((database.clone(), routine.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// returns the name of the database which contains the selected item, or which is selected
+    pub fn selected_database(&self) -> Option<String> {
+        self.selected_item().map(|item| match item.kind() {
+            DatabaseTreeItemKind::Database { name, .. } => name.clone(),
+            DatabaseTreeItemKind::Table { database, .. }
+            | DatabaseTreeItemKind::Schema { database, .. }
+            | DatabaseTreeItemKind::Routine { database, .. } => database.name.clone(),
+        })
+    }
+
+    /// selects the table, expanding the database and the schema which contain it
+    pub fn select_table(&mut self, database: &str, schema: Option<&str>, table: &str) -> bool {
+        let found = self
+            .items
+            .tree_items
+            .iter()
+            .enumerate()
+            .find_map(|(index, item)| match item.kind() {
+                DatabaseTreeItemKind::Table {
+                    database: d,
+                    table: t,
+                } if d.name == database
+                    && t.name == table
+                    && (schema.is_none() || t.schema.as_deref() == schema) =>
+                {
+                    Some((index, t.schema.clone()))
+                }
+                _ => None,
+            });
+        let (index, schema) = match found {
+            Some(found) => found,
+            None => return false,
+        };
+
+        for i in 0..index {
+            let kind = self.items.tree_items[i].kind();
+            let is_parent = if kind.is_database() {
+                kind.name() == database && kind.is_database_collapsed()
+            } else if kind.is_schema() {
+                kind.database_name().as_deref() == Some(database)
+                    && Some(kind.name()) == schema
+                    && kind.is_schema_collapsed()
+            } else {
+                false
+            };
+            if is_parent {
+                self.items.expand(i, false);
+            }
+        }
+
+        self.selection = Some(index);
+        self.visual_selection = self.calc_visual_selection();
+        true
+    }
+
     pub fn collapse_recursive(&mut self) {
         if let Some(selection) = self.selection {
             self.items.collapse(selection, true);
@@ -127,6 +192,18 @@ impl DatabaseTree {
         })
     }
 
+    /// selects the item shown at `visual_index` among the visible ones
+    pub fn select_visual(&mut self, visual_index: usize) -> bool {
+        match self.visual_index_to_absolute(visual_index) {
+            Some(index) => {
+                self.selection = Some(index);
+                self.visual_selection = self.calc_visual_selection();
+                true
+            }
+            None => false,
+        }
+    }
+
     fn visual_index_to_absolute(&self, visual_index: usize) -> Option<usize> {
         self.items
             .iterate(0, self.items.len())
@@ -377,6 +454,7 @@ mod test {
                 update_time: None,
                 engine: None,
                 schema: None,
+                view: false,
             }
         }
 
@@ -387,6 +465,7 @@ mod test {
                 update_time: None,
                 engine: None,
                 schema: Some(schema),
+                view: false,
             }
         }
     }
@@ -433,6 +512,40 @@ mod test {
         assert_eq!(tree.selection, Some(2));
     }
 
+    #[test]
+    fn test_select_table() {
+        let items = vec![
+            Database::new("a".to_string(), vec![Table::new("b".to_string()).into()]),
+            Database::new(
+                "c".to_string(),
+                vec![Schema {
+                    name: "d".to_string(),
+                    tables: vec![Table::new_with_schema("b".to_string(), "d".to_string()).into()],
+                }
+                .into()],
+            ),
+        ];
+
+        // a
+        //   b
+        // c
+        //   d
+        //     b
+
+        let mut tree = DatabaseTree::new(&items, &BTreeSet::new()).unwrap();
+
+        assert!(tree.select_table("c", Some("d"), "b"));
+        assert_eq!(tree.selection, Some(4));
+        assert_eq!(tree.selected_table().unwrap().0.name, "c".to_string());
+        assert!(tree.is_visible_index(4));
+        assert!(!tree.is_visible_index(1));
+
+        assert!(tree.select_table("a", None, "b"));
+        assert_eq!(tree.selection, Some(1));
+        assert!(!tree.select_table("a", None, "x"));
+        assert_eq!(tree.selection, Some(1));
+    }
+
     #[test]
     fn test_expand() {
         let items = vec![Database::new(
@@ -838,6 +951,28 @@ mod test {
         assert_eq!(s.index, 1);
     }
 
+    #[test]
+    fn test_select_visual() {
+        let items = vec![
+            Database::new("a".to_string(), vec![Table::new("b".to_string()).into()]),
+            Database::new("c".to_string(), vec![Table::new("d".to_string()).into()]),
+        ];
+
+        // a
+        //   b
+        // c
+        //   d
+
+        let mut tree = DatabaseTree::new(&items, &BTreeSet::new()).unwrap();
+        tree.items.expand(0, false);
+
+        assert!(tree.select_visual(2));
+        assert_eq!(tree.selection, Some(2));
+        assert_eq!(tree.visual_selection().unwrap().index, 2);
+        assert!(!tree.select_visual(3));
+        assert_eq!(tree.selection, Some(2));
+    }
+
     #[test]
     fn test_selection_top() {
         let items = vec![Database::new(
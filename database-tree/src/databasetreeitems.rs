@@ -62,6 +62,9 @@ impl DatabaseTreeItems {
                             items.push(DatabaseTreeItem::new_table(e, table))
                         }
                     }
+                    Child::Routine(routine) => {
+                        items.push(DatabaseTreeItem::new_routine(e, routine))
+                    }
                 }
             }
         }
@@ -217,7 +220,8 @@ impl DatabaseTreeItems {
                                 continue;
                             }
                         }
-                        if let DatabaseTreeItemKind::Table { database, .. } =
+                        if let DatabaseTreeItemKind::Table { database, .. }
+                        | DatabaseTreeItemKind::Routine { database, .. } =
                             self.tree_items[i].kind().clone()
                         {
                             if database.name == *name {
@@ -256,7 +260,9 @@ impl DatabaseTreeItems {
                         }
                     }
 
-                    if let DatabaseTreeItemKind::Table { database, .. } = item_kind {
+                    if let DatabaseTreeItemKind::Table { database, .. }
+                    | DatabaseTreeItemKind::Routine { database, .. } = item_kind
+                    {
                         if *name == database.name {
                             self.tree_items[i].info_mut().set_visible(true);
                         }
@@ -1,4 +1,4 @@
-use crate::{Database, Schema, Table};
+use crate::{Database, Routine, Schema, Table};
 
 #[derive(Debug, Clone)]
 pub struct TreeItemInfo {
@@ -44,6 +44,10 @@ pub enum DatabaseTreeItemKind {
         schema: Schema,
         collapsed: bool,
     },
+    Routine {
+        database: Database,
+        routine: Routine,
+    },
 }
 
 impl DatabaseTreeItemKind {
@@ -55,15 +59,24 @@ impl DatabaseTreeItemKind {
         matches!(self, Self::Table { .. })
     }
 
+    pub fn is_view(&self) -> bool {
+        matches!(self, Self::Table { table, .. } if table.view)
+    }
+
     pub const fn is_schema(&self) -> bool {
         matches!(self, Self::Schema { .. })
     }
 
+    pub const fn is_routine(&self) -> bool {
+        matches!(self, Self::Routine { .. })
+    }
+
     pub const fn is_database_collapsed(&self) -> bool {
         match self {
             Self::Database { collapsed, .. } => *collapsed,
             Self::Table { .. } => false,
             Self::Schema { .. } => false,
+            Self::Routine { .. } => false,
         }
     }
 
@@ -72,6 +85,7 @@ impl DatabaseTreeItemKind {
             Self::Database { .. } => false,
             Self::Table { .. } => false,
             Self::Schema { collapsed, .. } => *collapsed,
+            Self::Routine { .. } => false,
         }
     }
 
@@ -80,6 +94,7 @@ impl DatabaseTreeItemKind {
             Self::Database { name, .. } => name.to_string(),
             Self::Table { table, .. } => table.name.clone(),
             Self::Schema { schema, .. } => schema.name.clone(),
+            Self::Routine { routine, .. } => routine.qualified_name(),
         }
     }
 
@@ -88,6 +103,7 @@ impl DatabaseTreeItemKind {
             Self::Database { .. } => None,
             Self::Table { database, .. } => Some(database.name.clone()),
             Self::Schema { database, .. } => Some(database.name.clone()),
+            Self::Routine { database, .. } => Some(database.name.clone()),
         }
     }
 
@@ -96,6 +112,8 @@ impl DatabaseTreeItemKind {
             Self::Database { .. } => None,
             Self::Table { table, .. } => table.schema.clone(),
             Self::Schema { .. } => None,
+            // listed after the schemas, even when it belongs to one
+            Self::Routine { .. } => None,
         }
     }
 }
@@ -118,6 +136,16 @@ impl DatabaseTreeItem {
         }
     }
 
+    pub fn new_routine(database: &Database, routine: &Routine) -> Self {
+        Self {
+            info: TreeItemInfo::new(1, false),
+            kind: DatabaseTreeItemKind::Routine {
+                database: database.clone(),
+                routine: routine.clone(),
+            },
+        }
+    }
+
     pub fn new_schema(database: &Database, schema: &Schema, _collapsed: bool) -> Self {
         Self {
             info: TreeItemInfo::new(1, false),
@@ -217,6 +245,7 @@ impl DatabaseTreeItem {
             DatabaseTreeItemKind::Database { name, .. } => name.contains(filter_text),
             DatabaseTreeItemKind::Table { table, .. } => table.name.contains(filter_text),
             DatabaseTreeItemKind::Schema { schema, .. } => schema.name.contains(filter_text),
+            DatabaseTreeItemKind::Routine { routine, .. } => routine.name.contains(filter_text),
         }
     }
 
@@ -21,6 +21,7 @@ pub struct Database {
 pub enum Child {
     Table(Table),
     Schema(Schema),
+    Routine(Routine),
 }
 
 impl From<Table> for Child {
@@ -35,6 +36,12 @@ impl From<Schema> for Child {
     }
 }
 
+impl From<Routine> for Child {
+    fn from(r: Routine) -> Self {
+        Child::Routine(r)
+    }
+}
+
 impl Database {
     pub fn new(database: String, children: Vec<Child>) -> Self {
         Self {
@@ -57,4 +64,44 @@ pub struct Table {
     pub update_time: Option<chrono::DateTime<chrono::Utc>>,
     pub engine: Option<String>,
     pub schema: Option<String>,
+    /// whether the rows come from a query saved in the database, such as a view or a materialized view
+    pub view: bool,
+}
+
+/// a stored procedure or function, listed after the tables of its database
+#[derive(Debug, Clone, PartialEq)]
+pub struct Routine {
+    pub name: String,
+    pub schema: Option<String>,
+    /// the name the server tells the overloads of a routine apart by, such as the oid of a PostgreSQL function
+    pub specific_name: String,
+    /// whether it returns a value, rather than being run with CALL
+    pub function: bool,
+    /// the parameters with their modes and types, such as `IN id int, OUT total decimal(10,2)`
+    pub arguments: String,
+    /// the type of the value a function returns
+    pub returns: Option<String>,
+}
+
+impl Routine {
+    /// returns the name qualified with the schema, as it is listed in the tree
+    pub fn qualified_name(&self) -> String {
+        match self.schema.as_ref() {
+            Some(schema) => format!("{}.{}", schema, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// returns the name with the parameters and the type of the returned value, such as `total(id int) returns int`
+    pub fn signature(&self) -> String {
+        match self.returns.as_ref() {
+            Some(returns) => format!(
+                "{}({}) returns {}",
+                self.qualified_name(),
+                self.arguments,
+                returns
+            ),
+            None => format!("{}({})", self.qualified_name(), self.arguments),
+        }
+    }
 }
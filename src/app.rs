@@ -1,22 +1,215 @@
 use crate::clipboard::copy_to_clipboard;
 use crate::components::{CommandInfo, Component as _, DrawableComponent as _, EventState};
-use crate::database::{MySqlPool, Pool, PostgresPool, SqlitePool, RECORDS_LIMIT_PER_PAGE};
-use crate::event::Key;
+use crate::database::{
+    bulk_edit_statements, column_stats_statement, delete_statement, dialect_hints, display_value,
+    format_sql, insert_batches, insert_statements, is_connection_error, is_nullable,
+    is_numeric_type, is_page_error, is_read_only_statement, is_transient_error, jitter,
+    join_conditions, lock_statement, page_statement, qualified_columns, quality_statement,
+    referenced_tables, reinsert_statement, restore_statements, retry_delay, split_statements,
+    transaction_change, type_badge, unknown_names, unsupported_constructs, update_statement,
+    BulkEdit, CancelToken, ColumnKind, Engine, ExecuteResult, Feature, FormatOptions, KeysetPage,
+    MySqlPool, PluginPool, Pool, PostgresPool, Session, SqlitePool, StatementMode, TableStatus,
+    RECORDS_LIMIT_PER_PAGE,
+};
+use crate::dump::{dump_order, find_program, run_dump_command, write_dump, DumpProgress};
+use crate::erd::Erd;
+use crate::event::{Key, Mouse, MouseKind};
+use crate::history::QueryHistory;
+use crate::i18n::Msg;
+use crate::keyring;
+use crate::pipe::pipe_to_command;
+use crate::snapshot::SchemaSnapshot;
+use crate::snippets::SnippetLibrary;
 use crate::{
     components::tab::Tab,
+    components::table::RowRange,
     components::{
-        command, ConnectionsComponent, DatabasesComponent, ErrorComponent, HelpComponent,
-        RecordTableComponent, TabComponent, TableComponent,
+        command, file_statements, CellValueComponent, ChildRows, ChildRowsComponent,
+        ColumnPickerComponent, ColumnQualityComponent, ColumnStatsComponent,
+        ConfirmStatementComponent, ConnectionFormComponent, ConnectionsComponent,
+        CopyRecordsComponent, CsvImportComponent, CsvRows, DataGeneratorComponent,
+        DatabasesComponent, DumpComponent, ErdComponent, ErrorComponent, ExecutionLogComponent,
+        ExportViewComponent, FilePickerComponent, FooterComponent, HelpComponent,
+        ImportConnectionsComponent, InputMode, InsertRowComponent, PasswordPromptComponent,
+        PipeCommandComponent, Popup, PopupStack, QueueComponent, RecentDatabasesComponent,
+        RecordDetailComponent, RecordTableComponent, ReferencedTablesComponent,
+        RoutineFormComponent, RunFileComponent, ScheduledJobsComponent, SchemaSnapshotComponent,
+        SearchMatchesComponent, SessionSettingsComponent, SnapshotAction, SnippetsComponent,
+        SqlEditorComponent, StatementLog, TabComponent, TableComponent, TableDefinitionComponent,
+        TableFinderComponent,
     },
-    config::Config,
+    config::{save_connections, Action, Config, Connection, FetchMode},
 };
-use database_tree::Database;
+use database_tree::{Database, Routine, Table};
+use futures::FutureExt as _;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+use tokio::{sync::oneshot, task::JoinHandle};
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
     Frame,
 };
 
+/// statements running in the background on a connection taken out of the pool
+struct QueryTask<T> {
+    handle: JoinHandle<anyhow::Result<T>>,
+    cancel_token: oneshot::Receiver<CancelToken>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<T> QueryTask<T> {
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// the connection the statements of the SQL editor run on, which rolls back the transaction open on it when it is
+/// dropped rather than handed back, such as when the task running the statements fails or is aborted, so that the
+/// pool never gets the connection back in the middle of a transaction
+struct EditorSession {
+    session: Option<Box<dyn Session>>,
+    in_transaction: bool,
+}
+
+impl EditorSession {
+    fn session(&mut self) -> &mut Box<dyn Session> {
+        self.session
+            .as_mut()
+            .expect("the session is only taken when it is handed back")
+    }
+
+    /// returns the connection when a transaction is open on it, and lets it go back to the pool otherwise
+    fn into_transaction(mut self) -> Option<Box<dyn Session>> {
+        self.session.take().filter(|_| self.in_transaction)
+    }
+}
+
+impl Drop for EditorSession {
+    fn drop(&mut self) {
+        if !self.in_transaction {
+            return;
+        }
+        if let (Some(mut session), Ok(runtime)) =
+            (self.session.take(), tokio::runtime::Handle::try_current())
+        {
+            runtime.spawn(async move {
+                let _ = session.run("ROLLBACK", StatementMode::Execute).await;
+            });
+        }
+    }
+}
+
+/// how each statement of the SQL editor ended, how many times they were retried, the connection they ran on when
+/// they left a transaction open, whether each one fetched a page of its rows with more left to fetch, and how long
+/// each one took with the warnings it raised
+type StatementsResult = (
+    Vec<(String, anyhow::Result<ExecuteResult>)>,
+    u32,
+    Option<Box<dyn Session>>,
+    Vec<bool>,
+    Vec<(Duration, Vec<String>)>,
+);
+
+/// the first page of a watched table with the table it was fetched from and the key of its last record
+type WatchedRecords = (
+    Database,
+    Table,
+    Vec<String>,
+    Vec<Vec<Option<String>>>,
+    Option<Vec<String>>,
+);
+
+/// a file running in the background, sending how each statement ended as soon as it does
+struct BatchTask {
+    task: QueryTask<()>,
+    logs: mpsc::Receiver<StatementLog>,
+    /// whether the statements insert the rows of a CSV file, whose progress the import popup shows
+    import: bool,
+}
+
+/// every page of the records of a table fetched in the background to be copied, sending how many records are fetched
+struct CopyTask {
+    task: QueryTask<String>,
+    progress: mpsc::Receiver<usize>,
+}
+
+/// a database or a table dumped into a file in the background, sending how far SELECT has got
+struct DumpTask {
+    task: QueryTask<()>,
+    progress: mpsc::Receiver<DumpProgress>,
+    /// the file the program of the server writes, whose size tells how far it has got
+    written: Option<String>,
+}
+
+/// what watch mode runs again every `watch_interval_secs` seconds
+enum Watch {
+    /// the first page of the records of the selected table
+    Records,
+    /// the statements which were in the SQL editor when watching started
+    Statements(Vec<String>),
+}
+
+/// the statement of the queue running in the background
+struct QueuedTask {
+    id: usize,
+    statement: String,
+    started: Instant,
+    task: QueryTask<ExecuteResult>,
+}
+
+/// what runs once the user confirmed it in the confirmation popup
+enum PendingAction {
+    /// a statement changing the records of the selected table, after locking them with `lock` when the connection locks rows,
+    /// and the statements undoing it
+    Change {
+        lock: Option<String>,
+        undo: Vec<String>,
+    },
+    Statements {
+        mode: StatementMode,
+        overrides: Vec<(String, String)>,
+        statements: Vec<String>,
+        fetch: FetchMode,
+    },
+    RunFile {
+        statements: Vec<String>,
+        stop_on_error: bool,
+    },
+    InsertRecords {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    /// the INSERT statements of the batches of the rows of a CSV file
+    ImportCsv {
+        statements: Vec<String>,
+        rows: usize,
+    },
+    ExportView {
+        path: String,
+        all_rows: bool,
+        ansi: bool,
+    },
+    SaveSnapshot {
+        path: String,
+    },
+    Dump {
+        path: String,
+        native: bool,
+    },
+    Queue {
+        statements: Vec<String>,
+    },
+    /// a statement enabling or disabling a scheduled job, after which the jobs are listed again
+    EnableJob,
+    /// statements changing the records of the selected table, which run in one transaction, and the statements undoing
+    /// them
+    Changes {
+        undo: Vec<String>,
+    },
+}
+
 pub enum Focus {
     DabataseList,
     Table,
@@ -28,12 +221,76 @@ pub struct App {
     constraint_table: TableComponent,
     foreign_key_table: TableComponent,
     index_table: TableComponent,
+    trigger_table: TableComponent,
+    table_definition: TableDefinitionComponent,
+    sql_editor: SqlEditorComponent,
     focus: Focus,
     tab: TabComponent,
     help: HelpComponent,
+    footer: FooterComponent,
+    record_detail: RecordDetailComponent,
+    data_generator: DataGeneratorComponent,
     databases: DatabasesComponent,
     connections: ConnectionsComponent,
-    pool: Option<Box<dyn Pool>>,
+    connection_form: ConnectionFormComponent,
+    pipe_command: PipeCommandComponent,
+    recent_databases: RecentDatabasesComponent,
+    referenced_tables: ReferencedTablesComponent,
+    table_finder: TableFinderComponent,
+    scheduled_jobs: ScheduledJobsComponent,
+    password_prompt: PasswordPromptComponent,
+    copy_records: CopyRecordsComponent,
+    import_connections: ImportConnectionsComponent,
+    column_picker: ColumnPickerComponent,
+    child_rows: ChildRowsComponent,
+    routine_form: RoutineFormComponent,
+    run_file: RunFileComponent,
+    file_picker: FilePickerComponent,
+    schema_snapshot: SchemaSnapshotComponent,
+    csv_import: CsvImportComponent,
+    session_settings: SessionSettingsComponent,
+    export_view: ExportViewComponent,
+    insert_row: InsertRowComponent,
+    confirm_statement: ConfirmStatementComponent,
+    /// what runs once the confirmation popup is confirmed
+    pending: Option<PendingAction>,
+    column_stats: ColumnStatsComponent,
+    column_quality: ColumnQualityComponent,
+    search_matches: SearchMatchesComponent,
+    snippets: SnippetsComponent,
+    cell_value: CellValueComponent,
+    queue: QueueComponent,
+    execution_log: ExecutionLogComponent,
+    erd: ErdComponent,
+    dump: DumpComponent,
+    popups: PopupStack,
+    /// the results of the statements and how many times they were retried after transient errors
+    query_task: Option<QueryTask<StatementsResult>>,
+    /// the connection of the SQL editor while a transaction started there is open, which its next statements run on
+    transaction: Option<Box<dyn Session>>,
+    /// whether quitting was warned about the open transaction, so that quitting again does quit
+    quit_warned: bool,
+    batch_task: Option<BatchTask>,
+    stats_task: Option<QueryTask<ExecuteResult>>,
+    /// computes the data quality checks of a column
+    quality_task: Option<QueryTask<ExecuteResult>>,
+    copy_task: Option<CopyTask>,
+    dump_task: Option<DumpTask>,
+    /// what is watched and when it last ran
+    watch: Option<(Watch, Instant)>,
+    /// the first page of the watched table fetched in the background
+    watch_task: Option<QueryTask<WatchedRecords>>,
+    queued_task: Option<QueuedTask>,
+    /// how many rows the opened table has and how much space it takes, fetched after its records
+    table_status_task: Option<QueryTask<(Database, Table, TableStatus)>>,
+    /// the statements undoing the last change made from the records table, and when it was made
+    undo: Option<(Vec<String>, Instant)>,
+    pool: Option<Arc<dyn Pool>>,
+    /// the server of the open connection as it is named in the title of the terminal
+    connection_title: Option<String>,
+    /// the open connection with the password read from the keyring or entered, which the program dumping a database
+    /// connects with
+    connection: Option<Connection>,
     pub config: Config,
     pub error: ErrorComponent,
 }
@@ -43,38 +300,135 @@ impl App {
         Self {
             config: config.clone(),
             connections: ConnectionsComponent::new(config.key_config.clone(), config.conn),
-            record_table: RecordTableComponent::new(config.key_config.clone()),
-            column_table: TableComponent::new(config.key_config.clone()),
-            constraint_table: TableComponent::new(config.key_config.clone()),
-            foreign_key_table: TableComponent::new(config.key_config.clone()),
-            index_table: TableComponent::new(config.key_config.clone()),
+            connection_form: ConnectionFormComponent::new(config.key_config.clone()),
+            pipe_command: PipeCommandComponent::new(config.key_config.clone()),
+            recent_databases: RecentDatabasesComponent::new(config.key_config.clone()),
+            referenced_tables: ReferencedTablesComponent::new(config.key_config.clone()),
+            table_finder: TableFinderComponent::new(config.key_config.clone()),
+            scheduled_jobs: ScheduledJobsComponent::new(config.key_config.clone()),
+            password_prompt: PasswordPromptComponent::new(config.key_config.clone()),
+            copy_records: CopyRecordsComponent::new(config.key_config.clone()),
+            import_connections: ImportConnectionsComponent::new(config.key_config.clone()),
+            column_picker: ColumnPickerComponent::new(config.key_config.clone()),
+            child_rows: ChildRowsComponent::new(config.key_config.clone()),
+            routine_form: RoutineFormComponent::new(config.key_config.clone()),
+            run_file: RunFileComponent::new(config.key_config.clone()),
+            file_picker: FilePickerComponent::new(config.key_config.clone()),
+            schema_snapshot: SchemaSnapshotComponent::new(config.key_config.clone()),
+            csv_import: CsvImportComponent::new(config.key_config.clone()),
+            session_settings: SessionSettingsComponent::new(config.key_config.clone()),
+            export_view: ExportViewComponent::new(config.key_config.clone()),
+            insert_row: InsertRowComponent::new(config.key_config.clone()),
+            confirm_statement: ConfirmStatementComponent::new(config.key_config.clone()),
+            pending: None,
+            column_stats: ColumnStatsComponent::new(config.key_config.clone()),
+            column_quality: ColumnQualityComponent::new(config.key_config.clone()),
+            search_matches: SearchMatchesComponent::new(config.key_config.clone()),
+            snippets: SnippetsComponent::new(config.key_config.clone()),
+            cell_value: CellValueComponent::new(config.key_config.clone()),
+            queue: QueueComponent::new(config.key_config.clone()),
+            execution_log: ExecutionLogComponent::new(config.key_config.clone()),
+            erd: ErdComponent::new(config.key_config.clone()),
+            dump: DumpComponent::new(config.key_config.clone()),
+            popups: PopupStack::default(),
+            record_table: RecordTableComponent::new(
+                config.key_config.clone(),
+                config.table_config.clone(),
+            ),
+            column_table: TableComponent::new(
+                config.key_config.clone(),
+                config.table_config.clone(),
+            ),
+            constraint_table: TableComponent::new(
+                config.key_config.clone(),
+                config.table_config.clone(),
+            ),
+            foreign_key_table: TableComponent::new(
+                config.key_config.clone(),
+                config.table_config.clone(),
+            ),
+            index_table: TableComponent::new(
+                config.key_config.clone(),
+                config.table_config.clone(),
+            ),
+            trigger_table: TableComponent::new(
+                config.key_config.clone(),
+                config.table_config.clone(),
+            ),
+            table_definition: TableDefinitionComponent::new(config.key_config.clone()),
+            sql_editor: SqlEditorComponent::new(
+                config.key_config.clone(),
+                config.table_config.clone(),
+            ),
             tab: TabComponent::new(config.key_config.clone()),
             help: HelpComponent::new(config.key_config.clone()),
+            footer: FooterComponent::new(config.key_config.clone()),
+            record_detail: RecordDetailComponent::new(config.key_config.clone()),
+            data_generator: DataGeneratorComponent::new(config.key_config.clone()),
             databases: DatabasesComponent::new(config.key_config.clone()),
             error: ErrorComponent::new(config.key_config),
             focus: Focus::ConnectionList,
+            query_task: None,
+            transaction: None,
+            quit_warned: false,
+            batch_task: None,
+            stats_task: None,
+            quality_task: None,
+            copy_task: None,
+            dump_task: None,
+            watch: None,
+            watch_task: None,
+            queued_task: None,
+            table_status_task: None,
+            undo: None,
             pool: None,
+            connection_title: None,
+            connection: None,
+        }
+    }
+
+    /// returns the title of the terminal, such as `gobang: root@localhost:3306/shop/orders`, naming the connection,
+    /// the database and the table selected in the tree
+    pub fn terminal_title(&self) -> String {
+        let connection = match self.connection_title.as_ref() {
+            Some(connection) => connection,
+            None => return "gobang".to_string(),
+        };
+        let mut parts = vec![connection.clone()];
+        if let Some((database, table)) = self.databases.tree().selected_table() {
+            parts.push(database.name);
+            parts.push(table.name);
+        } else if let Some(database) = self.databases.tree().selected_database() {
+            parts.push(database);
         }
+        format!("gobang: {}", parts.join("/"))
     }
 
     pub fn draw<B: Backend>(&mut self, f: &mut Frame<'_, B>) -> anyhow::Result<()> {
+        self.sync_popups();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(f.size());
+        self.footer.set_cmds(self.focused_commands());
+        let mode = self.input_mode();
+        self.footer.set_mode(mode);
+        self.footer.set_watching(
+            self.watch
+                .as_ref()
+                .map(|_| self.config.table_config.watch_interval_secs),
+        );
+        self.footer.draw(f, chunks[1], false)?;
+
         if let Focus::ConnectionList = self.focus {
-            self.connections.draw(
-                f,
-                Layout::default()
-                    .constraints([Constraint::Percentage(100)])
-                    .split(f.size())[0],
-                false,
-            )?;
-            self.error.draw(f, Rect::default(), false)?;
-            self.help.draw(f, Rect::default(), false)?;
-            return Ok(());
+            self.connections.draw(f, chunks[0], false)?;
+            return self.draw_popups(f);
         }
 
         let main_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(15), Constraint::Percentage(85)])
-            .split(f.size());
+            .split(chunks[0]);
 
         self.databases
             .draw(f, main_chunks[0], matches!(self.focus, Focus::DabataseList))
@@ -110,16 +464,537 @@ impl App {
                 self.index_table
                     .draw(f, right_chunks[1], matches!(self.focus, Focus::Table))?
             }
+            Tab::Sql => {
+                if let Some(pool) = self.pool.as_ref() {
+                    let engine = pool.engine();
+                    self.sql_editor.set_unknown_names(unknown_names(
+                        self.sql_editor.query().as_str(),
+                        engine,
+                        &self.record_table.filter.metadata,
+                    ));
+                    self.sql_editor.set_join_conditions(join_conditions(
+                        self.sql_editor.query_before_cursor().as_str(),
+                        engine,
+                        &self.record_table.filter.metadata,
+                    ));
+                    self.sql_editor.set_column_candidates(qualified_columns(
+                        self.sql_editor.query().as_str(),
+                        engine,
+                        self.sql_editor.query_before_cursor().chars().count(),
+                        &self.record_table.filter.metadata,
+                    ));
+                    self.sql_editor.set_dialect_hints(
+                        engine,
+                        dialect_hints(self.sql_editor.query().as_str(), engine),
+                    );
+                }
+                self.sql_editor
+                    .draw(f, right_chunks[1], matches!(self.focus, Focus::Table))?
+            }
+            Tab::Definition => self.table_definition.draw(
+                f,
+                right_chunks[1],
+                matches!(self.focus, Focus::Table),
+            )?,
+            Tab::Triggers => {
+                self.trigger_table
+                    .draw(f, right_chunks[1], matches!(self.focus, Focus::Table))?
+            }
+        }
+        self.draw_popups(f)
+    }
+
+    fn draw_popups<B: Backend>(&mut self, f: &mut Frame<'_, B>) -> anyhow::Result<()> {
+        for popup in self.popups.popups() {
+            match popup {
+                Popup::Error => self.error.draw(f, Rect::default(), false)?,
+                Popup::Help => self.help.draw(f, Rect::default(), false)?,
+                Popup::RecordDetail => self.record_detail.draw(f, Rect::default(), false)?,
+                Popup::DataGenerator => self.data_generator.draw(f, Rect::default(), false)?,
+                Popup::ConnectionForm => self.connection_form.draw(f, Rect::default(), false)?,
+                Popup::PipeCommand => self.pipe_command.draw(f, Rect::default(), false)?,
+                Popup::RecentDatabases => self.recent_databases.draw(f, Rect::default(), false)?,
+                Popup::RunFile => self.run_file.draw(f, Rect::default(), false)?,
+                Popup::FilePicker => self.file_picker.draw(f, Rect::default(), false)?,
+                Popup::SchemaSnapshot => self.schema_snapshot.draw(f, Rect::default(), false)?,
+                Popup::CsvImport => self.csv_import.draw(f, Rect::default(), false)?,
+                Popup::SessionSettings => self.session_settings.draw(f, Rect::default(), false)?,
+                Popup::ExportView => self.export_view.draw(f, Rect::default(), false)?,
+                Popup::InsertRow => self.insert_row.draw(f, Rect::default(), false)?,
+                Popup::ConfirmStatement => {
+                    self.confirm_statement.draw(f, Rect::default(), false)?
+                }
+                Popup::ColumnStats => self.column_stats.draw(f, Rect::default(), false)?,
+                Popup::Snippets => self.snippets.draw(f, Rect::default(), false)?,
+                Popup::CellValue => self.cell_value.draw(f, Rect::default(), false)?,
+                Popup::Queue => self.queue.draw(f, Rect::default(), false)?,
+                Popup::ReferencedTables => {
+                    self.referenced_tables.draw(f, Rect::default(), false)?
+                }
+                Popup::TableFinder => self.table_finder.draw(f, Rect::default(), false)?,
+                Popup::ScheduledJobs => self.scheduled_jobs.draw(f, Rect::default(), false)?,
+                Popup::PasswordPrompt => self.password_prompt.draw(f, Rect::default(), false)?,
+                Popup::CopyRecords => self.copy_records.draw(f, Rect::default(), false)?,
+                Popup::ImportConnections => {
+                    self.import_connections.draw(f, Rect::default(), false)?
+                }
+                Popup::ColumnPicker => self.column_picker.draw(f, Rect::default(), false)?,
+                Popup::ChildRows => self.child_rows.draw(f, Rect::default(), false)?,
+                Popup::RoutineForm => self.routine_form.draw(f, Rect::default(), false)?,
+                Popup::ColumnQuality => self.column_quality.draw(f, Rect::default(), false)?,
+                Popup::SearchMatches => self.search_matches.draw(f, Rect::default(), false)?,
+                Popup::ExecutionLog => self.execution_log.draw(f, Rect::default(), false)?,
+                Popup::Erd => self.erd.draw(f, Rect::default(), false)?,
+                Popup::Dump => self.dump.draw(f, Rect::default(), false)?,
+            }
         }
-        self.error.draw(f, Rect::default(), false)?;
-        self.help.draw(f, Rect::default(), false)?;
         Ok(())
     }
 
+    fn sync_popups(&mut self) {
+        self.popups.sync(Popup::Error, self.error.is_visible());
+        self.popups.sync(Popup::Help, self.help.is_visible());
+        self.popups
+            .sync(Popup::RecordDetail, self.record_detail.is_visible());
+        self.popups
+            .sync(Popup::DataGenerator, self.data_generator.is_visible());
+        self.popups
+            .sync(Popup::ConnectionForm, self.connection_form.is_visible());
+        self.popups
+            .sync(Popup::PipeCommand, self.pipe_command.is_visible());
+        self.popups
+            .sync(Popup::RecentDatabases, self.recent_databases.is_visible());
+        self.popups.sync(Popup::RunFile, self.run_file.is_visible());
+        self.popups
+            .sync(Popup::FilePicker, self.file_picker.is_visible());
+        self.popups
+            .sync(Popup::SchemaSnapshot, self.schema_snapshot.is_visible());
+        self.popups
+            .sync(Popup::CsvImport, self.csv_import.is_visible());
+        self.popups
+            .sync(Popup::SessionSettings, self.session_settings.is_visible());
+        self.popups
+            .sync(Popup::ExportView, self.export_view.is_visible());
+        self.popups
+            .sync(Popup::InsertRow, self.insert_row.is_visible());
+        self.popups
+            .sync(Popup::ConfirmStatement, self.confirm_statement.is_visible());
+        self.popups
+            .sync(Popup::ColumnStats, self.column_stats.is_visible());
+        self.popups
+            .sync(Popup::Snippets, self.snippets.is_visible());
+        self.popups
+            .sync(Popup::CellValue, self.cell_value.is_visible());
+        self.popups.sync(Popup::Queue, self.queue.is_visible());
+        self.popups
+            .sync(Popup::ReferencedTables, self.referenced_tables.is_visible());
+        self.popups
+            .sync(Popup::TableFinder, self.table_finder.is_visible());
+        self.popups
+            .sync(Popup::ScheduledJobs, self.scheduled_jobs.is_visible());
+        self.popups
+            .sync(Popup::PasswordPrompt, self.password_prompt.is_visible());
+        self.popups
+            .sync(Popup::CopyRecords, self.copy_records.is_visible());
+        self.popups.sync(
+            Popup::ImportConnections,
+            self.import_connections.is_visible(),
+        );
+        self.popups
+            .sync(Popup::ColumnPicker, self.column_picker.is_visible());
+        self.popups
+            .sync(Popup::ChildRows, self.child_rows.is_visible());
+        self.popups
+            .sync(Popup::RoutineForm, self.routine_form.is_visible());
+        self.popups
+            .sync(Popup::ColumnQuality, self.column_quality.is_visible());
+        self.popups
+            .sync(Popup::SearchMatches, self.search_matches.is_visible());
+        self.popups
+            .sync(Popup::ExecutionLog, self.execution_log.is_visible());
+        self.popups.sync(Popup::Erd, self.erd.is_visible());
+        self.popups.sync(Popup::Dump, self.dump.is_visible());
+    }
+
+    async fn popup_event(&mut self, popup: Popup, key: Key) -> anyhow::Result<EventState> {
+        match popup {
+            Popup::Error => self.error.event(key),
+            Popup::Help => self.help.event(key),
+            Popup::RecordDetail => self.record_detail.event(key),
+            Popup::ChildRows => self.child_rows.event(key),
+            Popup::CellValue => {
+                let state = self.cell_value.event(key)?;
+                if let Some(text) = self.cell_value.take_copied() {
+                    copy_to_clipboard(text.as_str(), &self.config.clipboard)?;
+                }
+                Ok(state)
+            }
+            Popup::DataGenerator => {
+                let state = self.data_generator.event(key)?;
+                if let Some((headers, rows)) = self.data_generator.take_records() {
+                    self.check_writable()?;
+                    if self.confirms(Action::Write) {
+                        self.ask(
+                            Msg::ConfirmWrite.text().to_string(),
+                            format!("INSERT ({} {})", rows.len(), Msg::Rows.text()),
+                            PendingAction::InsertRecords { headers, rows },
+                        )?;
+                    } else {
+                        self.insert_records(&headers, &rows).await?;
+                    }
+                }
+                Ok(state)
+            }
+            Popup::ConnectionForm => {
+                let state = self.connection_form.event(key)?;
+                if let Some((index, conn)) = self.connection_form.take_connection() {
+                    let mut connections = self.connections.connections().to_vec();
+                    let index = match index {
+                        Some(index) => {
+                            connections[index] = conn;
+                            index
+                        }
+                        None => {
+                            connections.push(conn);
+                            connections.len() - 1
+                        }
+                    };
+                    self.update_connections(connections, Some(index))?;
+                }
+                Ok(state)
+            }
+            Popup::PipeCommand => {
+                let state = self.pipe_command.event(key)?;
+                if let Some((command, text)) = self.pipe_command.take_command() {
+                    self.pipe_command
+                        .set_output(pipe_to_command(command.as_str(), text.as_str())?);
+                }
+                Ok(state)
+            }
+            Popup::RecentDatabases => {
+                let state = self.recent_databases.event(key)?;
+                if let Some(recent) = self.recent_databases.take_chosen() {
+                    if self.databases.select_table(
+                        recent.database.as_str(),
+                        recent.schema.as_deref(),
+                        recent.table.as_str(),
+                    ) {
+                        self.update_table().await?;
+                    }
+                }
+                Ok(state)
+            }
+            Popup::TableFinder => {
+                let state = self.table_finder.event(key)?;
+                if let Some((database, schema, table)) = self.table_finder.take_chosen() {
+                    if self.databases.select_table(
+                        database.as_str(),
+                        schema.as_deref(),
+                        table.as_str(),
+                    ) {
+                        self.tab.selected_tab = Tab::Records;
+                        self.update_table().await?;
+                    }
+                }
+                Ok(state)
+            }
+            Popup::ScheduledJobs => {
+                let state = self.scheduled_jobs.event(key)?;
+                if let Some((job, enable)) = self.scheduled_jobs.take_toggle() {
+                    self.check_writable()?;
+                    if let Some(statement) = self
+                        .pool
+                        .as_ref()
+                        .and_then(|pool| pool.enable_job_statement(&job, enable))
+                    {
+                        if self.confirms(Action::Write) {
+                            self.ask(
+                                Msg::ConfirmWrite.text().to_string(),
+                                statement,
+                                PendingAction::EnableJob,
+                            )?;
+                        } else {
+                            self.enable_job(statement.as_str()).await?;
+                        }
+                    }
+                }
+                Ok(state)
+            }
+            Popup::ReferencedTables => {
+                let state = self.referenced_tables.event(key)?;
+                if let Some((qualifier, table)) = self.referenced_tables.take_chosen() {
+                    self.open_referenced_table(qualifier.as_deref(), table.as_str())
+                        .await?;
+                }
+                Ok(state)
+            }
+            Popup::RunFile => {
+                if self.batch_task.is_some()
+                    && (key == self.config.key_config.exit_popup
+                        || key == self.config.key_config.exit)
+                {
+                    self.cancel_file().await?;
+                    return Ok(EventState::Consumed);
+                }
+                let state = self.run_file.event(key)?;
+                if let Some((path, stop_on_error)) = self.run_file.take_file() {
+                    self.run_statements_in_file(path.as_str(), stop_on_error)?;
+                }
+                Ok(state)
+            }
+            Popup::PasswordPrompt => {
+                let state = self.password_prompt.event(key)?;
+                if let Some((conn, password)) = self.password_prompt.take_password() {
+                    self.connect_with_password(conn, password, true).await?;
+                }
+                Ok(state)
+            }
+            Popup::FilePicker => {
+                let state = self.file_picker.event(key)?;
+                if let Some(path) = self.file_picker.take_chosen() {
+                    if let Some(mut conn) = self.connections.selected_connection().cloned() {
+                        conn.set_path(path);
+                        self.update_databases(&conn).await?;
+                    }
+                }
+                Ok(state)
+            }
+            Popup::SchemaSnapshot => {
+                let state = self.schema_snapshot.event(key)?;
+                if let Some((path, action)) = self.schema_snapshot.take_request() {
+                    if matches!(action, SnapshotAction::Save) && self.confirms(Action::Export) {
+                        self.ask(
+                            Msg::ConfirmExport.text().to_string(),
+                            path.clone(),
+                            PendingAction::SaveSnapshot { path },
+                        )?;
+                    } else {
+                        self.run_schema_snapshot(path.as_str(), action).await?;
+                    }
+                }
+                Ok(state)
+            }
+            Popup::SessionSettings => {
+                let state = self.session_settings.event(key)?;
+                if let Some(overrides) = self.session_settings.take_overrides() {
+                    self.run_statements(
+                        StatementMode::Execute,
+                        overrides,
+                        self.config.table_config.fetch,
+                    )?;
+                }
+                Ok(state)
+            }
+            Popup::ExportView => {
+                let state = self.export_view.event(key)?;
+                if let Some((path, all_rows, ansi)) = self.export_view.take_request() {
+                    if self.confirms(Action::Export) {
+                        self.ask(
+                            Msg::ConfirmExport.text().to_string(),
+                            path.clone(),
+                            PendingAction::ExportView {
+                                path,
+                                all_rows,
+                                ansi,
+                            },
+                        )?;
+                    } else {
+                        self.export_view_to(path.as_str(), all_rows, ansi)?;
+                    }
+                }
+                Ok(state)
+            }
+            Popup::RoutineForm => {
+                let state = self.routine_form.event(key)?;
+                if let Some((database, routine, arguments)) = self.routine_form.take_call() {
+                    self.execute_routine(&database, &routine, &arguments)?;
+                }
+                Ok(state)
+            }
+            Popup::InsertRow => {
+                let state = self.insert_row.event(key)?;
+                if let Some(statement) = self.insert_row.take_statement() {
+                    self.run_and_reload(statement.as_str(), vec![]).await?;
+                }
+                Ok(state)
+            }
+            Popup::ConfirmStatement => {
+                let state = self.confirm_statement.event(key)?;
+                if let Some(statement) = self.confirm_statement.take_statement() {
+                    if let Some(pending) = self.pending.take() {
+                        self.run_pending(pending, statement.as_str()).await?;
+                    }
+                }
+                Ok(state)
+            }
+            Popup::ColumnStats => {
+                let state = self.column_stats.event(key)?;
+                if !self.column_stats.is_visible() {
+                    if let (Some(pool), Some(task)) = (self.pool.as_ref(), self.stats_task.as_mut())
+                    {
+                        Self::cancel_task(pool.as_ref(), task).await?;
+                    }
+                }
+                Ok(state)
+            }
+            Popup::SearchMatches => {
+                let state = self.search_matches.event(key)?;
+                if let Some((row, column)) = self.search_matches.take_jump() {
+                    if let Some(table) = self.selected_tab_table() {
+                        table.select_cell(row, column)?;
+                    }
+                }
+                Ok(state)
+            }
+            Popup::ColumnQuality => {
+                let state = self.column_quality.event(key)?;
+                if !self.column_quality.is_visible() {
+                    if let (Some(pool), Some(task)) =
+                        (self.pool.as_ref(), self.quality_task.as_mut())
+                    {
+                        Self::cancel_task(pool.as_ref(), task).await?;
+                    }
+                }
+                Ok(state)
+            }
+            Popup::CopyRecords => {
+                let state = self.copy_records.event(key)?;
+                if !self.copy_records.is_visible() {
+                    if let (Some(pool), Some(copy)) = (self.pool.as_ref(), self.copy_task.as_mut())
+                    {
+                        Self::cancel_task(pool.as_ref(), &mut copy.task).await?;
+                    }
+                }
+                Ok(state)
+            }
+            Popup::ImportConnections => {
+                let state = self.import_connections.event(key)?;
+                if let Some(imported) = self.import_connections.take_imported() {
+                    if !imported.is_empty() {
+                        let mut connections = self.connections.connections().to_vec();
+                        let selected = connections.len();
+                        connections.extend(imported);
+                        self.update_connections(connections, Some(selected))?;
+                    }
+                }
+                Ok(state)
+            }
+            Popup::ColumnPicker => {
+                let state = self.column_picker.event(key)?;
+                if let Some(hidden) = self.column_picker.take_hidden() {
+                    if let Some(table) = self.selected_tab_table() {
+                        table.set_hidden_columns(hidden);
+                    }
+                }
+                Ok(state)
+            }
+            Popup::Snippets => {
+                let state = self.snippets.event(key)?;
+                if let Some(query) = self.snippets.take_chosen() {
+                    self.sql_editor.insert_text(query.as_str());
+                }
+                Ok(state)
+            }
+            Popup::Queue => {
+                let state = self.queue.event(key)?;
+                if let Some(id) = self.queue.take_cancel() {
+                    if let (Some(pool), Some(queued)) =
+                        (self.pool.as_ref(), self.queued_task.as_mut())
+                    {
+                        if queued.id == id {
+                            Self::cancel_task(pool.as_ref(), &mut queued.task).await?;
+                        }
+                    }
+                }
+                Ok(state)
+            }
+            Popup::ExecutionLog => self.execution_log.event(key),
+            Popup::Erd => {
+                let state = self.erd.event(key)?;
+                if let Some(text) = self.erd.take_copied() {
+                    copy_to_clipboard(text.as_str(), &self.config.clipboard)?;
+                }
+                Ok(state)
+            }
+            Popup::CsvImport => {
+                if self.batch_task.is_some()
+                    && self.csv_import.is_importing()
+                    && (key == self.config.key_config.exit_popup
+                        || key == self.config.key_config.exit)
+                {
+                    self.cancel_file().await?;
+                    return Ok(EventState::Consumed);
+                }
+                let state = self.csv_import.event(key)?;
+                if let Some(path) = self.csv_import.take_path() {
+                    let text = std::fs::read_to_string(path.as_str())
+                        .map_err(|e| anyhow::anyhow!("{}: {}", path, e))?;
+                    self.csv_import.load(text.as_str());
+                }
+                if let Some(request) = self.csv_import.take_request() {
+                    self.import_csv(request)?;
+                }
+                Ok(state)
+            }
+            Popup::Dump => {
+                if self.dump_task.is_some()
+                    && (key == self.config.key_config.exit_popup
+                        || key == self.config.key_config.exit)
+                {
+                    if let (Some(pool), Some(dump)) = (self.pool.as_ref(), self.dump_task.as_mut())
+                    {
+                        Self::cancel_task(pool.as_ref(), &mut dump.task).await?;
+                    }
+                    return Ok(EventState::Consumed);
+                }
+                let state = self.dump.event(key)?;
+                if let Some((path, native)) = self.dump.take_request() {
+                    if self.confirms(Action::Export) {
+                        self.ask(
+                            Msg::ConfirmExport.text().to_string(),
+                            path.clone(),
+                            PendingAction::Dump { path, native },
+                        )?;
+                    } else {
+                        self.start_dump(path, native)?;
+                    }
+                }
+                Ok(state)
+            }
+        }
+    }
+
     fn update_commands(&mut self) {
         self.help.set_cmds(self.commands());
     }
 
+    /// returns the commands of the focused component followed by the general ones to move around, for the footer
+    fn focused_commands(&self) -> Vec<CommandInfo> {
+        let mut res = vec![];
+        match self.focus {
+            Focus::ConnectionList => self.connections.commands(&mut res),
+            Focus::DabataseList => self.databases.commands(&mut res),
+            Focus::Table => match self.tab.selected_tab {
+                Tab::Records => self.record_table.commands(&mut res),
+                Tab::Columns => self.column_table.commands(&mut res),
+                Tab::Constraints => self.constraint_table.commands(&mut res),
+                Tab::ForeignKeys => self.foreign_key_table.commands(&mut res),
+                Tab::Indexes => self.index_table.commands(&mut res),
+                Tab::Sql => self.sql_editor.commands(&mut res),
+                Tab::Definition => self.table_definition.commands(&mut res),
+                Tab::Triggers => self.trigger_table.commands(&mut res),
+            },
+        }
+        res.push(CommandInfo::new(command::move_focus(
+            &self.config.key_config,
+        )));
+        res.push(CommandInfo::new(command::toggle_tabs(
+            &self.config.key_config,
+        )));
+        res.push(CommandInfo::new(command::help(&self.config.key_config)));
+        res
+    }
+
     fn commands(&self) -> Vec<CommandInfo> {
         let mut res = vec![
             CommandInfo::new(command::scroll(&self.config.key_config)),
@@ -131,102 +1006,612 @@ impl App {
             CommandInfo::new(command::filter(&self.config.key_config)),
             CommandInfo::new(command::help(&self.config.key_config)),
             CommandInfo::new(command::toggle_tabs(&self.config.key_config)),
+            CommandInfo::new(command::run_file(&self.config.key_config)),
+            CommandInfo::new(command::open_queue(&self.config.key_config)),
+            CommandInfo::new(command::open_execution_log(&self.config.key_config)),
+            CommandInfo::new(command::schema_snapshot(&self.config.key_config)),
+            CommandInfo::new(command::open_erd(&self.config.key_config)),
+            CommandInfo::new(command::import_csv(&self.config.key_config)),
+            CommandInfo::new(command::dump(&self.config.key_config)),
         ];
 
         self.databases.commands(&mut res);
         self.record_table.commands(&mut res);
+        self.sql_editor.commands(&mut res);
 
         res
     }
 
-    async fn update_databases(&mut self) -> anyhow::Result<()> {
-        if let Some(conn) = self.connections.selected_connection() {
-            if let Some(pool) = self.pool.as_ref() {
-                pool.close().await;
+    async fn connect(conn: &Connection) -> anyhow::Result<Box<dyn Pool>> {
+        Ok(if conn.is_mysql() {
+            Box::new(
+                MySqlPool::new(
+                    conn.database_url()?.as_str(),
+                    &conn.settings,
+                    conn.read_only,
+                )
+                .await?,
+            )
+        } else if conn.is_postgres() {
+            Box::new(
+                PostgresPool::new(
+                    conn.database_url()?.as_str(),
+                    &conn.settings,
+                    conn.read_only,
+                )
+                .await?,
+            )
+        } else if conn.is_duckdb() {
+            Self::connect_duckdb(conn).await?
+        } else if conn.is_plugin() {
+            Box::new(PluginPool::new(conn.database_url()?.as_str(), &conn.settings).await?)
+        } else {
+            Box::new(
+                SqlitePool::new(
+                    conn.database_url()?.as_str(),
+                    &conn.settings,
+                    conn.read_only,
+                )
+                .await?,
+            )
+        })
+    }
+
+    #[cfg(feature = "duckdb")]
+    async fn connect_duckdb(conn: &Connection) -> anyhow::Result<Box<dyn Pool>> {
+        Ok(Box::new(
+            crate::database::DuckDbPool::new(conn.database_url()?.as_str(), &conn.settings).await?,
+        ))
+    }
+
+    #[cfg(not(feature = "duckdb"))]
+    async fn connect_duckdb(_conn: &Connection) -> anyhow::Result<Box<dyn Pool>> {
+        Err(anyhow::anyhow!(
+            "gobang was built without the duckdb feature, install it with `cargo install gobang --features duckdb`"
+        ))
+    }
+
+    /// saves the schema of the selected database to the snapshot file, or reports how it differs from the file
+    async fn run_schema_snapshot(
+        &mut self,
+        path: &str,
+        action: SnapshotAction,
+    ) -> anyhow::Result<()> {
+        let (pool, database) = match (
+            self.pool.as_ref(),
+            self.databases.tree().selected_database(),
+        ) {
+            (Some(pool), Some(database)) => (pool, database),
+            _ => return Ok(()),
+        };
+        let database = Database::new(database.clone(), pool.get_tables(database).await?);
+        let live = SchemaSnapshot::capture(pool.as_ref(), &database).await?;
+        let path = std::path::Path::new(path);
+        let report = match action {
+            SnapshotAction::Save => {
+                live.save(path)?;
+                vec![format!(
+                    "{}: {} {}",
+                    Msg::SnapshotSaved.text(),
+                    live.tables.len(),
+                    Msg::Tables.text()
+                )]
             }
-            self.pool = if conn.is_mysql() {
-                Some(Box::new(
-                    MySqlPool::new(conn.database_url()?.as_str()).await?,
-                ))
-            } else if conn.is_postgres() {
-                Some(Box::new(
-                    PostgresPool::new(conn.database_url()?.as_str()).await?,
-                ))
-            } else {
-                Some(Box::new(
-                    SqlitePool::new(conn.database_url()?.as_str()).await?,
-                ))
-            };
-            let databases = match &conn.database {
-                Some(database) => vec![Database::new(
-                    database.clone(),
-                    self.pool
-                        .as_ref()
-                        .unwrap()
-                        .get_tables(database.clone())
-                        .await?,
-                )],
-                None => self.pool.as_ref().unwrap().get_databases().await?,
+            SnapshotAction::Compare => {
+                let changes = SchemaSnapshot::load(path)?.diff(&live);
+                if changes.is_empty() {
+                    vec![Msg::NoSchemaChanges.text().to_string()]
+                } else {
+                    changes
+                }
+            }
+        };
+        self.schema_snapshot.set_report(report);
+        Ok(())
+    }
+
+    /// reads the keys of the tables of the selected database to show them as a diagram
+    async fn open_erd(&mut self) -> anyhow::Result<()> {
+        let (pool, database) = match (
+            self.pool.as_ref(),
+            self.databases.tree().selected_database(),
+        ) {
+            (Some(pool), Some(database)) => (pool, database),
+            _ => return Ok(()),
+        };
+        let database = Database::new(database.clone(), pool.get_tables(database).await?);
+        let erd = Erd::capture(pool.as_ref(), &database).await?;
+        self.erd.open(erd)
+    }
+
+    /// reads the columns of the selected table to map the columns of a CSV file to them
+    async fn open_csv_import(&mut self) -> anyhow::Result<()> {
+        let (pool, (database, table)) =
+            match (self.pool.as_ref(), self.databases.tree().selected_table()) {
+                (Some(pool), Some(selected)) => (pool, selected),
+                _ => return Ok(()),
             };
-            self.databases.update(databases.as_slice()).unwrap();
-            self.focus = Focus::DabataseList;
-            self.record_table.reset();
-            self.tab.reset();
+        let columns = pool
+            .get_columns(&database, &table)
+            .await?
+            .iter()
+            .map(|row| {
+                let value = |header: &str| {
+                    row.fields()
+                        .iter()
+                        .position(|field| field == header)
+                        .and_then(|index| row.columns().get(index).cloned())
+                        .unwrap_or_default()
+                };
+                (value("name"), value("type"), is_nullable(&value("null")))
+            })
+            .collect();
+        self.csv_import.open(table.name.clone(), columns)
+    }
+
+    /// asks for the file to dump the selected database or table into, offering the program of the server when it is
+    /// installed
+    fn open_dump(&mut self) -> anyhow::Result<()> {
+        let target = match self.databases.tree().selected_table() {
+            Some((database, table)) => format!("{}.{}", database.name, table.name),
+            None => match self.databases.tree().selected_database() {
+                Some(database) => database,
+                None => return Ok(()),
+            },
+        };
+        let program = self
+            .connection
+            .as_ref()
+            .and_then(Connection::dump_program)
+            .filter(|program| find_program(program).is_some());
+        self.dump.open(target, program)
+    }
+
+    /// dumps the selected database or table into the file at `path` in the background, with the program of the server
+    /// when `native` or with SELECT otherwise
+    fn start_dump(&mut self, path: String, native: bool) -> anyhow::Result<()> {
+        if self.dump_task.is_some() {
+            return Ok(());
         }
+        let pool = match self.pool.as_ref() {
+            Some(pool) => Arc::clone(pool),
+            None => return Ok(()),
+        };
+        let selected = self.databases.tree().selected_table();
+        let name = match (&selected, self.databases.tree().selected_database()) {
+            (Some((database, _)), _) => database.name.clone(),
+            (None, Some(database)) => database,
+            _ => return Ok(()),
+        };
+        let command = match self.connection.as_ref().filter(|_| native) {
+            Some(conn) => conn.dump_command(
+                name.as_str(),
+                selected.as_ref().map(|(_, table)| table),
+                path.as_str(),
+            )?,
+            None => None,
+        };
+        let written = command.as_ref().map(|_| path.clone());
+        let (progress, progress_receiver) = mpsc::channel();
+        let handle = match command {
+            Some(command) => tokio::spawn(run_dump_command(command)),
+            None => tokio::spawn(async move {
+                let (database, tables) = match selected {
+                    Some((database, table)) => (database, vec![table]),
+                    None => {
+                        let database = Database::new(name.clone(), pool.get_tables(name).await?);
+                        let tables = dump_order(&database);
+                        (database, tables)
+                    }
+                };
+                let file = std::fs::File::create(path.as_str())
+                    .map_err(|e| anyhow::anyhow!("{}: {}", path, e))?;
+                let mut out = std::io::BufWriter::new(file);
+                write_dump(pool.as_ref(), &database, &tables, &mut out, progress).await?;
+                std::io::Write::flush(&mut out)?;
+                Ok(())
+            }),
+        };
+        // the dump runs apart from any session, so cancelling aborts the task, which kills the program
+        let (_, receiver) = oneshot::channel();
+        self.dump_task = Some(DumpTask {
+            task: QueryTask {
+                handle,
+                cancel_token: receiver,
+                cancelled: Arc::new(AtomicBool::new(false)),
+            },
+            progress: progress_receiver,
+            written,
+        });
+        self.dump.start();
         Ok(())
     }
 
-    async fn update_table(&mut self) -> anyhow::Result<()> {
-        if let Some((database, table)) = self.databases.tree().selected_table() {
-            self.focus = Focus::Table;
-            self.record_table.reset();
-            let (headers, records) = self
-                .pool
-                .as_ref()
-                .unwrap()
-                .get_records(&database, &table, 0, None)
-                .await?;
-            self.record_table
-                .update(records, headers, database.clone(), table.clone());
+    fn update_connections(
+        &mut self,
+        connections: Vec<Connection>,
+        selected: Option<usize>,
+    ) -> anyhow::Result<()> {
+        save_connections(&self.config.path, &connections)?;
+        self.connections.update(connections.clone(), selected);
+        self.config.conn = connections;
+        Ok(())
+    }
 
-            self.column_table.reset();
-            let columns = self
-                .pool
-                .as_ref()
-                .unwrap()
-                .get_columns(&database, &table)
-                .await?;
-            if !columns.is_empty() {
-                self.column_table.update(
-                    columns
-                        .iter()
-                        .map(|c| c.columns())
-                        .collect::<Vec<Vec<String>>>(),
-                    columns.get(0).unwrap().fields(),
-                    database.clone(),
-                    table.clone(),
-                );
-            }
-            self.constraint_table.reset();
-            let constraints = self
-                .pool
-                .as_ref()
-                .unwrap()
-                .get_constraints(&database, &table)
-                .await?;
-            if !constraints.is_empty() {
-                self.constraint_table.update(
-                    constraints
-                        .iter()
-                        .map(|c| c.columns())
-                        .collect::<Vec<Vec<String>>>(),
-                    constraints.get(0).unwrap().fields(),
-                    database.clone(),
-                    table.clone(),
-                );
+    pub async fn open_selected_connection(&mut self) -> anyhow::Result<()> {
+        if let Some(conn) = self.connections.selected_connection().cloned() {
+            // a sqlite connection without a path gets its file chosen each time it is opened
+            if conn.needs_path() {
+                self.file_picker.open()?;
+            } else if conn.needs_keyring_password() {
+                match keyring::get_password(conn.keyring_account().as_str())? {
+                    Some(password) => self.connect_with_password(conn, password, false).await?,
+                    None => self.password_prompt.open(conn, None)?,
+                }
+            } else {
+                self.update_databases(&conn).await?;
             }
-            self.foreign_key_table.reset();
-            let foreign_keys = self
+        }
+        Ok(())
+    }
+
+    /// connects with a password read from the system keyring or entered by the user, which is saved to the keyring
+    /// when `save` is set and the connection succeeds, asking for the password again when it fails
+    async fn connect_with_password(
+        &mut self,
+        conn: Connection,
+        password: String,
+        save: bool,
+    ) -> anyhow::Result<()> {
+        let mut with_password = conn.clone();
+        with_password.set_password(password.clone());
+        if let Err(err) = self.update_databases(&with_password).await {
+            return self.password_prompt.open(conn, Some(err.to_string()));
+        }
+        if save {
+            keyring::set_password(conn.keyring_account().as_str(), password.as_str())?;
+        }
+        Ok(())
+    }
+
+    async fn update_databases(&mut self, conn: &Connection) -> anyhow::Result<()> {
+        if let Some(pool) = self.pool.as_ref() {
+            pool.close().await;
+        }
+        if let Some(task) = self.query_task.take() {
+            task.handle.abort();
+            self.sql_editor.finish_running();
+        }
+        // the connection goes back to the pool, which must not keep the transaction of the one being left
+        if let Some(mut session) = self.transaction.take() {
+            let _ = session.run("ROLLBACK", StatementMode::Execute).await;
+            self.set_transaction(None);
+        }
+        if let Some(batch) = self.batch_task.take() {
+            batch.task.handle.abort();
+            if batch.import {
+                self.csv_import.finish(true);
+            } else {
+                self.run_file.finish(true);
+            }
+        }
+        if let Some(task) = self.stats_task.take() {
+            task.handle.abort();
+            self.column_stats.hide();
+        }
+        if let Some(task) = self.quality_task.take() {
+            task.handle.abort();
+            self.column_quality.hide();
+        }
+        if let Some(copy) = self.copy_task.take() {
+            copy.task.handle.abort();
+            self.copy_records.hide();
+        }
+        if let Some(dump) = self.dump_task.take() {
+            dump.task.handle.abort();
+            self.dump.finish(None, true);
+        }
+        if let Some(task) = self.table_status_task.take() {
+            task.handle.abort();
+        }
+        self.stop_watching();
+        // the queued statements were meant for the connection being left
+        if let Some(queued) = self.queued_task.take() {
+            queued.task.handle.abort();
+        }
+        self.queue.clear();
+        self.execution_log.clear();
+        // the change was made on the connection being left
+        self.undo = None;
+        self.pool = Some(Arc::from(Self::connect(conn).await?));
+        let databases = match &conn.database {
+            Some(database) => vec![Database::new(
+                database.clone(),
+                self.pool
+                    .as_ref()
+                    .unwrap()
+                    .get_tables(database.clone())
+                    .await?,
+            )],
+            None => self.pool.as_ref().unwrap().get_databases().await?,
+        };
+        self.databases.update(databases.as_slice()).unwrap();
+        self.record_table.filter.metadata.clear();
+        self.record_table
+            .filter
+            .set_engine(self.pool.as_ref().unwrap().engine());
+        for database in &databases {
+            self.record_table.filter.metadata.add_database(database);
+        }
+        self.connection_title = Some(conn.title());
+        self.connection = Some(conn.clone());
+        self.sql_editor.set_history(QueryHistory::load(conn)?);
+        self.snippets.set_library(SnippetLibrary::load(conn)?);
+        self.record_table
+            .table
+            .set_reveal_invisible(conn.reveal_invisible);
+        self.sql_editor.set_reveal_invisible(conn.reveal_invisible);
+        self.cell_value.set_reveal_invisible(conn.reveal_invisible);
+        self.focus = Focus::DabataseList;
+        self.record_table.reset();
+        self.record_table.forget_filters();
+        self.recent_databases.clear();
+        self.tab.reset();
+        Ok(())
+    }
+
+    /// fetches the columns of the table named before the dot in the filter so that they can be completed
+    async fn cache_qualified_columns(&mut self) -> anyhow::Result<()> {
+        if let (Some(pool), Some((database, table))) = (
+            self.pool.as_ref(),
+            self.record_table.filter.qualified_table(),
+        ) {
+            let columns = pool.get_columns(&database, &table).await?;
+            self.record_table.filter.metadata.set_columns(
+                &table,
+                columns
+                    .iter()
+                    .filter_map(|c| c.columns().into_iter().next())
+                    .collect(),
+            );
+        }
+        Ok(())
+    }
+
+    /// fetches the columns and the foreign keys of the tables which the query in the SQL editor uses and which are not
+    /// cached yet, so that the names qualified with them can be checked and their joins completed
+    async fn cache_referenced_columns(&mut self) -> anyhow::Result<()> {
+        if let Some(pool) = self.pool.as_ref() {
+            for (_, table) in referenced_tables(self.sql_editor.query().as_str(), pool.engine()) {
+                let metadata = &mut self.record_table.filter.metadata;
+                if let Some((database, table)) = metadata.uncached_table(table.as_str()) {
+                    let columns = pool.get_columns(&database, &table).await?;
+                    metadata.set_columns(
+                        &table,
+                        columns
+                            .iter()
+                            .filter_map(|c| c.columns().into_iter().next())
+                            .collect(),
+                    );
+                }
+                if let Some((database, table)) = metadata.unreferenced_table(table.as_str()) {
+                    let references = pool.get_foreign_key_references(&database, &table).await?;
+                    metadata.set_references(&table, references);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// fetches the page of records starting at `index`, following the primary key when the table has one, with the key
+    /// of its last record
+    async fn get_records(
+        &self,
+        database: &Database,
+        table: &Table,
+        index: usize,
+    ) -> anyhow::Result<KeysetPage<Option<String>>> {
+        let pool = self.pool.as_ref().unwrap();
+        let filter = if self.record_table.filter.input.is_empty() {
+            None
+        } else {
+            Some(self.record_table.filter.input_str())
+        };
+        if !self.record_table.primary_key.is_empty() {
+            if index == 0 {
+                return pool
+                    .get_records_after(
+                        database,
+                        table,
+                        &self.record_table.primary_key,
+                        None,
+                        filter,
+                    )
+                    .await;
+            }
+            if let Some(key) = self.record_table.next_key.as_ref() {
+                return pool
+                    .get_records_after(
+                        database,
+                        table,
+                        &self.record_table.primary_key,
+                        Some(key),
+                        filter,
+                    )
+                    .await;
+            }
+        }
+        let (headers, records) = pool.get_records(database, table, index, filter).await?;
+        Ok((headers, records, None))
+    }
+
+    /// fetches the next page of records once the last fetched one is selected
+    async fn fetch_more_records(&mut self) -> anyhow::Result<()> {
+        if self.record_table.table.eod {
+            return Ok(());
+        }
+        if let Some(index) = self.record_table.table.selected_row.selected() {
+            let loaded = self.record_table.table.row_offset + self.record_table.table.rows.len();
+            if index + 1 == self.record_table.table.rows.len()
+                && !self.record_table.table.has_rows_below()
+                && loaded % RECORDS_LIMIT_PER_PAGE as usize == 0
+            {
+                if let Some((database, table)) = self.databases.tree().selected_table() {
+                    let (_, records, key) = self.get_records(&database, &table, loaded).await?;
+                    if !records.is_empty() {
+                        self.record_table.next_key = key;
+                        self.record_table.table.append_rows(records)?;
+                    } else {
+                        self.record_table.table.end()
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// fetches the next page of the selected result of the SQL editor once its last fetched row is selected
+    async fn fetch_more_results(&mut self) -> anyhow::Result<()> {
+        let pool = match self.pool.as_ref() {
+            Some(pool) => Arc::clone(pool),
+            None => return Ok(()),
+        };
+        let (statement, table) = match self.sql_editor.selected_result() {
+            Some(result) => result,
+            None => return Ok(()),
+        };
+        if table.eod {
+            return Ok(());
+        }
+        if let Some(index) = table.selected_row.selected() {
+            if index + 1 == table.rows.len() && !table.has_rows_below() {
+                let loaded = table.row_offset + table.rows.len();
+                let limit = RECORDS_LIMIT_PER_PAGE as usize;
+                let statement = match page_statement(statement, pool.engine(), loaded, limit) {
+                    Some(statement) => statement,
+                    None => {
+                        table.end();
+                        return Ok(());
+                    }
+                };
+                let mut session = pool.session().await?;
+                if let ExecuteResult::Read { rows, .. } = session
+                    .run(statement.as_str(), StatementMode::Execute)
+                    .await?
+                {
+                    let last = rows.len() < limit;
+                    if !rows.is_empty() {
+                        table.append_rows(rows)?;
+                    }
+                    if last {
+                        table.end();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// fetches pages of records until the row at `index` is fetched or the last page is, and selects it
+    async fn fetch_records_until(&mut self, index: usize) -> anyhow::Result<()> {
+        loop {
+            let table = &mut self.record_table.table;
+            let loaded = table.row_offset + table.rows.len();
+            if index < loaded || table.eod || table.rows.is_empty() {
+                break;
+            }
+            table.go_to_row(loaded - 1)?;
+            self.fetch_more_records().await?;
+            let table = &self.record_table.table;
+            if table.row_offset + table.rows.len() == loaded {
+                break;
+            }
+        }
+        self.record_table.table.go_to_row(index)?;
+        self.record_table.table.take_wanted_row();
+        Ok(())
+    }
+
+    async fn update_table(&mut self) -> anyhow::Result<()> {
+        if let Some((database, table)) = self.databases.tree().selected_table() {
+            self.focus = Focus::Table;
+            self.recent_databases.push(&database, &table);
+            self.record_table.reset();
+            self.record_table.restore_filter(&database, &table);
+            self.record_table.primary_key = self
+                .pool
+                .as_ref()
+                .unwrap()
+                .get_primary_key(&database, &table)
+                .await?;
+            self.record_table.foreign_keys = self
+                .pool
+                .as_ref()
+                .unwrap()
+                .get_foreign_key_references(&database, &table)
+                .await?;
+            let (headers, records, key) = self.get_records(&database, &table, 0).await?;
+            self.record_table
+                .update(records, headers, database.clone(), table.clone());
+            self.record_table.next_key = key;
+
+            self.column_table.reset();
+            let columns = self
+                .pool
+                .as_ref()
+                .unwrap()
+                .get_columns(&database, &table)
+                .await?;
+            let names = columns
+                .iter()
+                .filter_map(|c| c.columns().into_iter().next())
+                .collect::<Vec<String>>();
+            self.record_table
+                .filter
+                .metadata
+                .set_columns(&table, names.clone());
+            self.record_table.filter.set_columns(names);
+            self.record_table.table.set_badges(
+                columns
+                    .iter()
+                    .filter_map(|c| {
+                        let column = c.columns();
+                        let badge = type_badge(column.get(1)?)?;
+                        Some((column.get(0)?.clone(), badge))
+                    })
+                    .collect(),
+            );
+            if !columns.is_empty() {
+                self.column_table.update(
+                    columns
+                        .iter()
+                        .map(|c| c.columns().into_iter().map(Some).collect())
+                        .collect::<Vec<Vec<Option<String>>>>(),
+                    columns.get(0).unwrap().fields(),
+                    database.clone(),
+                    table.clone(),
+                );
+            }
+            self.constraint_table.reset();
+            let constraints = self
+                .pool
+                .as_ref()
+                .unwrap()
+                .get_constraints(&database, &table)
+                .await?;
+            if !constraints.is_empty() {
+                self.constraint_table.update(
+                    constraints
+                        .iter()
+                        .map(|c| c.columns().into_iter().map(Some).collect())
+                        .collect::<Vec<Vec<Option<String>>>>(),
+                    constraints.get(0).unwrap().fields(),
+                    database.clone(),
+                    table.clone(),
+                );
+            }
+            self.foreign_key_table.reset();
+            let foreign_keys = self
                 .pool
                 .as_ref()
                 .unwrap()
@@ -236,8 +1621,8 @@ impl App {
                 self.foreign_key_table.update(
                     foreign_keys
                         .iter()
-                        .map(|c| c.columns())
-                        .collect::<Vec<Vec<String>>>(),
+                        .map(|c| c.columns().into_iter().map(Some).collect())
+                        .collect::<Vec<Vec<Option<String>>>>(),
                     foreign_keys.get(0).unwrap().fields(),
                     database.clone(),
                     table.clone(),
@@ -254,40 +1639,1615 @@ impl App {
                 self.index_table.update(
                     indexes
                         .iter()
-                        .map(|c| c.columns())
-                        .collect::<Vec<Vec<String>>>(),
+                        .map(|c| c.columns().into_iter().map(Some).collect())
+                        .collect::<Vec<Vec<Option<String>>>>(),
                     indexes.get(0).unwrap().fields(),
                     database.clone(),
                     table.clone(),
                 );
             }
+            self.trigger_table.reset();
+            let triggers = self
+                .pool
+                .as_ref()
+                .unwrap()
+                .get_triggers(&database, &table)
+                .await?;
+            if !triggers.is_empty() {
+                self.trigger_table.update(
+                    triggers
+                        .iter()
+                        .map(|c| c.columns().into_iter().map(Some).collect())
+                        .collect::<Vec<Vec<Option<String>>>>(),
+                    triggers.get(0).unwrap().fields(),
+                    database.clone(),
+                    table.clone(),
+                );
+            }
+            self.table_definition.update(
+                self.pool
+                    .as_ref()
+                    .unwrap()
+                    .get_definition(&database, &table)
+                    .await?,
+            );
         }
         Ok(())
     }
 
-    async fn update_record_table(&mut self) -> anyhow::Result<()> {
-        if let Some((database, table)) = self.databases.tree().selected_table() {
-            let (headers, records) = self
-                .pool
+    /// opens the table referenced by the selected cell filtered to the referenced record
+    /// opens the records of a table named in the query of the SQL editor, which may be qualified with its database or
+    /// its schema, looking for it in the selected database otherwise
+    async fn open_referenced_table(
+        &mut self,
+        qualifier: Option<&str>,
+        table: &str,
+    ) -> anyhow::Result<()> {
+        let database = self
+            .databases
+            .tree()
+            .selected_database()
+            .unwrap_or_default();
+        let candidates = match qualifier {
+            Some(qualifier) => vec![(qualifier, None), (database.as_str(), Some(qualifier))],
+            None => vec![(database.as_str(), None)],
+        };
+        // unquoted names are folded to lower case by PostgreSQL
+        let lower = table.to_lowercase();
+        let found = candidates.into_iter().any(|(database, schema)| {
+            self.databases.select_table(database, schema, table)
+                || self
+                    .databases
+                    .select_table(database, schema, lower.as_str())
+        });
+        if !found {
+            return Err(anyhow::anyhow!(
+                "{}: {}",
+                Msg::ReferencedTableNotFound.text(),
+                table
+            ));
+        }
+        self.tab.selected_tab = Tab::Records;
+        self.update_table().await
+    }
+
+    async fn follow_foreign_key(&mut self) -> anyhow::Result<()> {
+        let (reference, value) = match self.record_table.selected_foreign_key() {
+            Some((reference, value)) => (reference.clone(), value),
+            None => return Ok(()),
+        };
+        let database = match self.databases.tree().selected_table() {
+            Some((database, _)) => database,
+            None => return Ok(()),
+        };
+        if !self.databases.select_table(
+            reference
+                .ref_database
                 .as_ref()
-                .unwrap()
+                .unwrap_or(&database.name)
+                .as_str(),
+            reference.ref_schema.as_deref(),
+            reference.ref_table.as_str(),
+        ) {
+            return Err(anyhow::anyhow!(
+                "{}: {}",
+                Msg::ReferencedTableNotFound.text(),
+                reference.ref_table
+            ));
+        }
+        if let Some((database, table)) = self.databases.tree().selected_table() {
+            let pool = self.pool.as_ref().unwrap();
+            self.record_table.filter.set_input(
+                reference
+                    .filter(pool.engine(), value.as_str(), pool.identifier_quote())
+                    .as_str(),
+            );
+            self.record_table.remember_filter(&database, &table);
+            self.update_table().await?;
+        }
+        Ok(())
+    }
+
+    /// writes a DELETE, or an UPDATE of the selected column, of the selected rows into the SQL editor to be reviewed
+    /// before it runs
+    fn write_change_template(&mut self, update: bool) -> anyhow::Result<()> {
+        let (pool, (database, table)) =
+            match (self.pool.as_ref(), self.databases.tree().selected_table()) {
+                (Some(pool), Some(selected)) => (pool, selected),
+                _ => return Ok(()),
+            };
+        let keys = self.record_table.selected_keys();
+        let table_name = pool.table_name(&database, &table);
+        let statement = match (update, self.record_table.table.selected_header()) {
+            (true, Some(column)) => update_statement(
+                pool.engine(),
+                table_name.as_str(),
+                pool.identifier_quote(),
+                &self.record_table.primary_key,
+                &keys,
+                column,
+            )?,
+            (true, None) => return Ok(()),
+            (false, _) => delete_statement(
+                pool.engine(),
+                table_name.as_str(),
+                pool.identifier_quote(),
+                &self.record_table.primary_key,
+                &keys,
+            )?,
+        };
+        if keys.is_empty() {
+            return Ok(());
+        }
+        self.sql_editor
+            .set_query(format!("{};", statement).as_str());
+        self.tab.selected_tab = Tab::Sql;
+        Ok(())
+    }
+
+    /// shows the signature and the source of the routine selected in the tree in the definition tab
+    async fn open_routine(&mut self) -> anyhow::Result<()> {
+        let (database, routine) = match self.databases.tree().selected_routine() {
+            Some(selected) => selected,
+            None => return Ok(()),
+        };
+        let definition = self
+            .pool
+            .as_ref()
+            .unwrap()
+            .get_routine_definition(&database, &routine)
+            .await?;
+        self.focus = Focus::Table;
+        self.record_table.reset();
+        self.column_table.reset();
+        self.constraint_table.reset();
+        self.foreign_key_table.reset();
+        self.index_table.reset();
+        self.trigger_table.reset();
+        self.table_definition
+            .update(format!("-- {}\n{}", routine.signature(), definition));
+        self.tab.selected_tab = Tab::Definition;
+        Ok(())
+    }
+
+    /// writes the statements running a routine with the arguments of the routine form into the SQL editor and runs
+    /// them, so that its results are shown as those of any query
+    fn execute_routine(
+        &mut self,
+        database: &Database,
+        routine: &Routine,
+        arguments: &[Option<String>],
+    ) -> anyhow::Result<()> {
+        let statement = match self.pool.as_ref() {
+            Some(pool) => pool.call_statement(database, routine, arguments),
+            None => return Ok(()),
+        };
+        // the CALL replaces the query being written, which is kept in the history so that a search brings it back
+        self.sql_editor.push_history()?;
+        self.sql_editor.set_query(statement.as_str());
+        self.focus = Focus::Table;
+        self.tab.selected_tab = Tab::Sql;
+        self.run_statements(
+            StatementMode::Execute,
+            vec![],
+            self.config.table_config.fetch,
+        )
+    }
+
+    /// lists the rows of the tables referencing the selected record through their foreign keys
+    async fn open_child_rows(&mut self) -> anyhow::Result<()> {
+        let (database, table) = match self.databases.tree().selected_table() {
+            Some(selected) => selected,
+            None => return Ok(()),
+        };
+        let row = match self.record_table.table.selected_record() {
+            Some(row) => row,
+            None => return Ok(()),
+        };
+        let pool = self.pool.as_ref().unwrap();
+        let quote = pool.identifier_quote();
+        let mut groups = vec![];
+        for reference in pool.get_child_references(&database, &table).await? {
+            let value = self
+                .record_table
+                .table
+                .headers
+                .iter()
+                .position(|header| header == &reference.ref_column)
+                .and_then(|index| row.get(index)?.as_ref());
+            let value = match value {
+                Some(value) => value,
+                None => continue,
+            };
+            let (headers, rows) = pool
                 .get_records(
                     &database,
-                    &table,
+                    &reference.table,
                     0,
-                    if self.record_table.filter.input.is_empty() {
-                        None
-                    } else {
-                        Some(self.record_table.filter.input_str())
-                    },
+                    Some(reference.filter(pool.engine(), value.as_str(), quote)),
                 )
                 .await?;
+            groups.push(ChildRows {
+                title: format!("{}.{} = {}", reference.table.name, reference.column, value),
+                headers,
+                rows: rows
+                    .iter()
+                    .map(|row| row.iter().map(|v| display_value(v).to_string()).collect())
+                    .collect(),
+            });
+        }
+        self.child_rows.open(groups)
+    }
+
+    /// returns whether the policy of the group of the selected connection asks to confirm `action`
+    fn confirms(&self, action: Action) -> bool {
+        self.config.policy.confirms(
+            self.connections
+                .selected_connection()
+                .and_then(|conn| conn.group.as_deref()),
+            action,
+        )
+    }
+
+    /// shows `text` in the confirmation popup and keeps what runs once it is confirmed
+    fn ask(&mut self, title: String, text: String, pending: PendingAction) -> anyhow::Result<()> {
+        self.pending = Some(pending);
+        self.confirm_statement.open(title, text)
+    }
+
+    /// runs what the user confirmed, where `statement` is the text shown in the confirmation popup
+    async fn run_pending(&mut self, pending: PendingAction, statement: &str) -> anyhow::Result<()> {
+        match pending {
+            PendingAction::Change {
+                lock: Some(lock),
+                undo,
+            } => {
+                self.run_locked_and_reload(lock.as_str(), statement, undo)
+                    .await
+            }
+            PendingAction::Change { lock: None, undo } => {
+                self.run_and_reload(statement, undo).await
+            }
+            PendingAction::Statements {
+                mode,
+                overrides,
+                statements,
+                fetch,
+            } => self.start_statements(mode, overrides, statements, fetch),
+            PendingAction::RunFile {
+                statements,
+                stop_on_error,
+            } => self.start_file(statements, stop_on_error),
+            PendingAction::ImportCsv { statements, rows } => self.start_import(statements, rows),
+            PendingAction::InsertRecords { headers, rows } => {
+                self.insert_records(&headers, &rows).await
+            }
+            PendingAction::ExportView {
+                path,
+                all_rows,
+                ansi,
+            } => self.export_view_to(path.as_str(), all_rows, ansi),
+            PendingAction::Dump { path, native } => self.start_dump(path, native),
+            PendingAction::SaveSnapshot { path } => {
+                self.run_schema_snapshot(path.as_str(), SnapshotAction::Save)
+                    .await
+            }
+            PendingAction::Queue { statements } => {
+                for statement in statements {
+                    self.queue.push(statement);
+                }
+                Ok(())
+            }
+            PendingAction::EnableJob => self.enable_job(statement).await,
+            PendingAction::Changes { undo } => self.run_all_and_reload(statement, undo).await,
+        }
+    }
+
+    /// runs the statements in one transaction, so that either all of them change the records or none does
+    async fn run_all_and_reload(
+        &mut self,
+        statements: &str,
+        undo: Vec<String>,
+    ) -> anyhow::Result<()> {
+        let statements = split_statements(statements, self.pool.as_ref().unwrap().engine());
+        for statement in &statements {
+            self.check_read_only(statement)?;
+        }
+        let mut session = self.pool.as_ref().unwrap().session().await?;
+        session.run("BEGIN", StatementMode::Execute).await?;
+        for statement in &statements {
+            if let Err(err) = session.run(statement, StatementMode::Execute).await {
+                session.run("ROLLBACK", StatementMode::Execute).await?;
+                return Err(err);
+            }
+        }
+        session.run("COMMIT", StatementMode::Execute).await?;
+        self.keep_undo(undo);
+        self.update_record_table().await
+    }
+
+    /// keeps the statements undoing the change which just ran in place of those of the change before it, which could
+    /// not be undone safely any more
+    fn keep_undo(&mut self, undo: Vec<String>) {
+        self.undo = if undo.is_empty() {
+            None
+        } else {
+            Some((undo, Instant::now()))
+        };
+    }
+
+    /// asks to run the statements undoing the last change, as long as it was made within `undo_window_secs` seconds
+    fn confirm_undo(&mut self) -> anyhow::Result<()> {
+        let window = Duration::from_secs(self.config.table_config.undo_window_secs);
+        let statements = match self.undo.as_ref() {
+            Some((statements, made)) if made.elapsed() <= window => statements,
+            _ => return Err(anyhow::anyhow!(Msg::NothingToUndo.text())),
+        };
+        let text = statements
+            .iter()
+            .map(|statement| format!("{};", statement))
+            .collect::<Vec<String>>()
+            .join("\n");
+        self.ask(
+            Msg::UndoChange.text().to_string(),
+            text,
+            PendingAction::Changes { undo: vec![] },
+        )
+    }
+
+    /// asks to run the UPDATEs making `edit` to the selected cells, showing them to be reviewed first
+    fn confirm_bulk_edit(&mut self, edit: BulkEdit) -> anyhow::Result<()> {
+        self.check_writable()?;
+        let (pool, (database, table)) =
+            match (self.pool.as_ref(), self.databases.tree().selected_table()) {
+                (Some(pool), Some(selected)) => (pool, selected),
+                _ => return Ok(()),
+            };
+        let (columns, rows) = self.record_table.selected_area();
+        let kinds = columns
+            .iter()
+            .map(|column| self.record_table.table.column_kind(column))
+            .collect::<Vec<ColumnKind>>();
+        let keys = self.record_table.selected_keys();
+        let table_name = pool.table_name(&database, &table);
+        let statements = bulk_edit_statements(
+            pool.engine(),
+            table_name.as_str(),
+            pool.identifier_quote(),
+            &self.record_table.primary_key,
+            &keys,
+            &columns,
+            &kinds,
+            &rows,
+            edit,
+        )?;
+        let undo = restore_statements(
+            pool.engine(),
+            table_name.as_str(),
+            pool.identifier_quote(),
+            &self.record_table.primary_key,
+            &keys,
+            &columns,
+            &kinds,
+            &rows,
+        )?;
+        if statements.is_empty() {
+            return Ok(());
+        }
+        let msg = match edit {
+            BulkEdit::FillDown => Msg::FillDown,
+            BulkEdit::Clear => Msg::SetNull,
+            BulkEdit::Increment => Msg::IncrementCells,
+        };
+        self.ask(
+            format!("{} ({} {})", msg.text(), rows.len(), Msg::Rows.text()),
+            statements
+                .iter()
+                .map(|statement| format!("{};", statement))
+                .collect::<Vec<String>>()
+                .join("\n"),
+            PendingAction::Changes { undo },
+        )
+    }
+
+    /// enables or disables a scheduled job, then lists the jobs again
+    async fn enable_job(&mut self, statement: &str) -> anyhow::Result<()> {
+        let pool = self.pool.as_ref().unwrap();
+        let mut session = pool.session().await?;
+        session.run(statement, StatementMode::Execute).await?;
+        self.scheduled_jobs.update(pool.get_scheduled_jobs().await?);
+        Ok(())
+    }
+
+    async fn insert_records(
+        &mut self,
+        headers: &[String],
+        rows: &[Vec<String>],
+    ) -> anyhow::Result<()> {
+        if let Some((database, table)) = self.databases.tree().selected_table() {
+            self.pool
+                .as_ref()
+                .unwrap()
+                .insert_records(&database, &table, headers, rows)
+                .await?;
+            self.update_record_table().await?;
+        }
+        Ok(())
+    }
+
+    /// writes the table of the selected tab to `path` as the export popup asked
+    fn export_view_to(&mut self, path: &str, all_rows: bool, ansi: bool) -> anyhow::Result<()> {
+        if path.ends_with(".sql") {
+            let range = if all_rows {
+                RowRange::All
+            } else {
+                RowRange::Visible
+            };
+            if let Some(text) = self.insert_statements(range)? {
+                std::fs::write(path, text.as_str())
+                    .map_err(|e| anyhow::anyhow!("{}: {}", path, e))?;
+                self.export_view.set_exported(text.lines().count());
+            }
+        } else if let Some(table) = self.selected_tab_table() {
+            let text = table.export(all_rows, ansi)?;
+            std::fs::write(path, text.as_str()).map_err(|e| anyhow::anyhow!("{}: {}", path, e))?;
+            self.export_view.set_exported(text.lines().count());
+        }
+        Ok(())
+    }
+
+    /// writes the rows in `range` of the table of the selected tab as INSERT statements into the table they were read
+    /// from, which is the first table its statement names for a result of the SQL editor
+    fn insert_statements(&mut self, range: RowRange) -> anyhow::Result<Option<String>> {
+        let pool = match self.pool.as_ref() {
+            Some(pool) => pool,
+            None => return Ok(None),
+        };
+        let table = match self.tab.selected_tab {
+            Tab::Records => match self.databases.tree().selected_table() {
+                Some((database, table)) => pool.table_name(&database, &table),
+                None => return Ok(None),
+            },
+            Tab::Sql => match self.sql_editor.selected_statement().and_then(|statement| {
+                referenced_tables(statement, pool.engine())
+                    .into_iter()
+                    .next()
+            }) {
+                Some((Some(qualifier), table)) => format!("{}.{}", qualifier, table),
+                Some((None, table)) => table,
+                None => return Err(anyhow::anyhow!(Msg::NoInsertTable.text())),
+            },
+            _ => return Ok(None),
+        };
+        let (engine, quote) = (pool.engine(), pool.identifier_quote());
+        Ok(self.selected_tab_table().map(|component| {
+            let (headers, kinds, rows) = component.shown_values(range);
+            insert_statements(engine, table.as_str(), quote, &headers, &kinds, &rows)
+        }))
+    }
+
+    /// runs a statement changing the records of the selected table, then fetches them again
+    async fn run_and_reload(&mut self, statement: &str, undo: Vec<String>) -> anyhow::Result<()> {
+        self.check_read_only(statement)?;
+        let mut session = self.pool.as_ref().unwrap().session().await?;
+        session.run(statement, StatementMode::Execute).await?;
+        self.keep_undo(undo);
+        self.update_record_table().await
+    }
+
+    /// runs a statement changing the rows locked by `lock` in a transaction, offering to retry it when another transaction holds them
+    async fn run_locked_and_reload(
+        &mut self,
+        lock: &str,
+        statement: &str,
+        undo: Vec<String>,
+    ) -> anyhow::Result<()> {
+        self.check_read_only(statement)?;
+        let mut session = self.pool.as_ref().unwrap().session().await?;
+        session.run("BEGIN", StatementMode::Execute).await?;
+        let result = match session.lock_rows(lock).await {
+            Ok(true) => session
+                .run(statement, StatementMode::Execute)
+                .await
+                .map(|_| true),
+            result => result,
+        };
+        match result {
+            Ok(true) => {
+                session.run("COMMIT", StatementMode::Execute).await?;
+                self.keep_undo(undo);
+                self.update_record_table().await
+            }
+            Ok(false) => {
+                session.run("ROLLBACK", StatementMode::Execute).await?;
+                self.pending = Some(PendingAction::Change {
+                    lock: Some(lock.to_string()),
+                    undo,
+                });
+                self.confirm_statement.open_retry(statement.to_string())
+            }
+            Err(err) => {
+                session.run("ROLLBACK", StatementMode::Execute).await?;
+                Err(err)
+            }
+        }
+    }
+
+    async fn update_record_table(&mut self) -> anyhow::Result<()> {
+        if let Some((database, table)) = self.databases.tree().selected_table() {
+            self.record_table.remember_filter(&database, &table);
+            let (headers, records, key) = self.get_records(&database, &table, 0).await?;
             self.record_table
                 .update(records, headers, database.clone(), table.clone());
+            self.record_table.next_key = key;
+            self.start_table_status(database, table);
+        }
+        Ok(())
+    }
+
+    /// starts fetching how many rows the table has and how much space it takes, which can take a while on some
+    /// servers, so that its records show first
+    fn start_table_status(&mut self, database: Database, table: Table) {
+        if let Some(task) = self.table_status_task.take() {
+            task.handle.abort();
+        }
+        if let Some(pool) = self.pool.as_ref() {
+            let pool = Arc::clone(pool);
+            let (_, receiver) = oneshot::channel();
+            self.table_status_task = Some(QueryTask {
+                handle: tokio::spawn(async move {
+                    let status = pool.get_table_status(&database, &table).await?;
+                    Ok((database, table, status))
+                }),
+                cancel_token: receiver,
+                cancelled: Arc::new(AtomicBool::new(false)),
+            });
+        }
+    }
+
+    /// shows the size of the table once it is fetched
+    fn tick_table_status(&mut self) {
+        let result = match self.table_status_task.as_mut() {
+            Some(task) => match (&mut task.handle).now_or_never() {
+                Some(result) => result,
+                None => return,
+            },
+            None => return,
+        };
+        self.table_status_task = None;
+        // the status bar keeps its dashes when the server does not tell, which is no reason to stop browsing
+        if let Ok(Ok((database, table, status))) = result {
+            self.record_table
+                .table
+                .set_table_status(&database, &table, status);
+        }
+    }
+
+    /// starts running the statements in the SQL editor in the background, fetching their rows as `fetch` tells unless
+    /// a comment in the editor tells otherwise
+    fn run_statements(
+        &mut self,
+        mode: StatementMode,
+        overrides: Vec<(String, String)>,
+        fetch: FetchMode,
+    ) -> anyhow::Result<()> {
+        if self.query_task.is_some() {
+            return Ok(());
+        }
+        self.sql_editor.push_history()?;
+        if let Some(pool) = self.pool.as_ref() {
+            let query = self.sql_editor.query();
+            let fetch = FetchMode::directive(query.as_str()).unwrap_or(fetch);
+            let engine = pool.engine();
+            let statements = split_statements(query.as_str(), engine);
+            if mode == (StatementMode::Explain { analyze: true }) {
+                Self::check_supported(pool.as_ref(), Feature::ExplainAnalyze)?;
+            }
+            // a plain EXPLAIN does not run the statements it explains
+            if mode != (StatementMode::Explain { analyze: false }) {
+                for statement in &statements {
+                    self.check_read_only(statement)?;
+                }
+            }
+            // checks every statement first so that none of them runs when the server lacks a feature
+            if mode == StatementMode::Execute {
+                for statement in &statements {
+                    if let Some(feature) = Feature::required_by(statement, engine) {
+                        Self::check_supported(pool.as_ref(), feature)?;
+                    }
+                }
+            }
+            // asks before running what another engine would have, which the server would reject
+            let unsupported = match mode {
+                StatementMode::Execute => {
+                    unsupported_constructs(&dialect_hints(query.as_str(), engine))
+                }
+                _ => vec![],
+            };
+            let action = match mode {
+                StatementMode::Explain { analyze: true } => Some(Action::ExplainAnalyze),
+                StatementMode::Execute
+                    if !statements
+                        .iter()
+                        .all(|statement| is_read_only_statement(statement, engine)) =>
+                {
+                    Some(Action::Write)
+                }
+                _ => None,
+            };
+            match action {
+                _ if !unsupported.is_empty() => {
+                    self.ask(
+                        Msg::ConfirmDialect.text().to_string(),
+                        format!(
+                            "{} {}: {}",
+                            Msg::NotSupportedBy.text(),
+                            engine,
+                            unsupported.join(", ")
+                        ),
+                        PendingAction::Statements {
+                            mode,
+                            overrides,
+                            statements,
+                            fetch,
+                        },
+                    )?;
+                }
+                Some(action) if self.confirms(action) => {
+                    let title = if action == Action::ExplainAnalyze {
+                        Msg::ConfirmExplainAnalyze
+                    } else {
+                        Msg::ConfirmWrite
+                    };
+                    self.ask(
+                        title.text().to_string(),
+                        format!("{} {}", statements.len(), Msg::Statements.text()),
+                        PendingAction::Statements {
+                            mode,
+                            overrides,
+                            statements,
+                            fetch,
+                        },
+                    )?;
+                }
+                _ => self.start_statements(mode, overrides, statements, fetch)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn start_statements(
+        &mut self,
+        mode: StatementMode,
+        overrides: Vec<(String, String)>,
+        statements: Vec<String>,
+        fetch: FetchMode,
+    ) -> anyhow::Result<()> {
+        if let Some(pool) = self.pool.as_ref() {
+            let pool = Arc::clone(pool);
+            let engine = pool.engine();
+            let retry = self.config.retry_config.clone();
+            let (cancel_token, receiver) = oneshot::channel();
+            let cancelled = Arc::new(AtomicBool::new(false));
+            let stopped = Arc::clone(&cancelled);
+            let transaction = self.transaction.take();
+            self.query_task = Some(QueryTask {
+                handle: tokio::spawn(async move {
+                    // however the task ends from here on, the transaction is either handed back or rolled back
+                    let mut editor = EditorSession {
+                        in_transaction: transaction.is_some(),
+                        session: Some(match transaction {
+                            Some(session) => session,
+                            None => pool.session().await?,
+                        }),
+                    };
+                    let _ = cancel_token.send(editor.session().cancel_token());
+                    let previous = editor.session().apply_settings(&overrides).await?;
+                    let mut results = vec![];
+                    let mut retries = 0;
+                    let mut more_rows = vec![];
+                    let mut executions = vec![];
+                    // whether the connection was lost and no other one could be taken in its place
+                    let mut disconnected = false;
+                    for statement in statements {
+                        let started = Instant::now();
+                        // the later pages are fetched on another connection, which cannot see a transaction
+                        let mut page = Some(fetch)
+                            .filter(|fetch| {
+                                *fetch == FetchMode::Lazy
+                                    && mode == StatementMode::Execute
+                                    && !editor.in_transaction
+                            })
+                            .and_then(|_| {
+                                page_statement(
+                                    statement.as_str(),
+                                    engine,
+                                    0,
+                                    RECORDS_LIMIT_PER_PAGE as usize,
+                                )
+                            });
+                        let mut attempt = 0;
+                        let result = loop {
+                            let run = page.as_deref().unwrap_or(statement.as_str());
+                            match editor.session().run(run, mode).await {
+                                // some queries cannot be wrapped, such as those returning two columns of the same
+                                // name on MySQL, so they fetch every row instead, unless the run was cancelled
+                                Err(err)
+                                    if page.is_some()
+                                        && !stopped.load(Ordering::Relaxed)
+                                        && is_page_error(&err) =>
+                                {
+                                    page = None
+                                }
+                                // only statements which read can run twice without changing anything twice, and
+                                // not in a transaction which the error may have aborted or another connection lacks
+                                Err(err)
+                                    if attempt < retry.max_retries
+                                        && !editor.in_transaction
+                                        && !stopped.load(Ordering::Relaxed)
+                                        && is_read_only_statement(statement.as_str(), engine)
+                                        && is_transient_error(&err) =>
+                                {
+                                    tokio::time::sleep(retry_delay(
+                                        Duration::from_millis(retry.backoff_ms),
+                                        attempt,
+                                        jitter(),
+                                    ))
+                                    .await;
+                                    if is_connection_error(&err) {
+                                        // the statements which ran before keep their results, and this one fails
+                                        // with why it could not run again
+                                        match Self::reconnect(pool.as_ref(), &overrides).await {
+                                            Ok(session) => editor.session = Some(session),
+                                            Err(err) => {
+                                                disconnected = true;
+                                                break Err(err);
+                                            }
+                                        }
+                                    }
+                                    attempt += 1;
+                                }
+                                result => break result,
+                            }
+                        };
+                        retries += attempt;
+                        let elapsed = started.elapsed();
+                        let warnings = match (&result, mode) {
+                            (Ok(_), StatementMode::Execute) => {
+                                editor.session().warnings().await.unwrap_or_default()
+                            }
+                            _ => vec![],
+                        };
+                        executions.push((elapsed, warnings));
+                        more_rows.push(
+                            page.is_some()
+                                && matches!(
+                                    &result,
+                                    Ok(ExecuteResult::Read { rows, .. })
+                                        if rows.len() == RECORDS_LIMIT_PER_PAGE as usize
+                                ),
+                        );
+                        let failed = result.is_err();
+                        if !failed && mode == StatementMode::Execute {
+                            if let Some(open) = transaction_change(statement.as_str(), engine) {
+                                editor.in_transaction = open;
+                            }
+                        }
+                        results.push((statement, result));
+                        // the statements after a failed one are left, since they may depend on it
+                        if failed {
+                            break;
+                        }
+                    }
+                    // the connection goes back to the pool, so the overrides must not outlive this run
+                    if !disconnected {
+                        editor.session().apply_settings(&previous).await?;
+                    }
+                    Ok((
+                        results,
+                        retries,
+                        editor.into_transaction(),
+                        more_rows,
+                        executions,
+                    ))
+                }),
+                cancel_token: receiver,
+                cancelled,
+            });
+            self.sql_editor.start_running();
+        }
+        Ok(())
+    }
+
+    /// starts computing the statistics of the selected column of the records in the background
+    fn compute_column_stats(&mut self) -> anyhow::Result<()> {
+        if self.stats_task.is_some() {
+            return Ok(());
+        }
+        let column = match self.record_table.table.selected_header() {
+            Some(column) => column.clone(),
+            None => return Ok(()),
+        };
+        if let (Some(pool), Some((database, table))) =
+            (self.pool.as_ref(), self.databases.tree().selected_table())
+        {
+            let numeric = self
+                .column_table
+                .rows
+                .iter()
+                .find(|row| row.get(0).and_then(Option::as_ref) == Some(&column))
+                .and_then(|row| row.get(1)?.as_deref())
+                .map_or(false, is_numeric_type);
+            let filter = self.record_table.filter.input_str();
+            let statement = column_stats_statement(
+                pool.table_name(&database, &table).as_str(),
+                pool.identifier_quote(),
+                column.as_str(),
+                numeric,
+                Some(filter.as_str()).filter(|filter| !filter.is_empty()),
+            );
+            let pool = Arc::clone(pool);
+            let (cancel_token, receiver) = oneshot::channel();
+            self.stats_task = Some(QueryTask {
+                handle: tokio::spawn(async move {
+                    let mut session = pool.session().await?;
+                    let _ = cancel_token.send(session.cancel_token());
+                    session
+                        .run(statement.as_str(), StatementMode::Execute)
+                        .await
+                }),
+                cancel_token: receiver,
+                cancelled: Arc::new(AtomicBool::new(false)),
+            });
+            self.column_stats.open(column)?;
+        }
+        Ok(())
+    }
+
+    /// starts running the data quality checks of the selected column of the records in the background
+    fn compute_column_quality(&mut self) -> anyhow::Result<()> {
+        if self.quality_task.is_some() {
+            return Ok(());
+        }
+        let column = match self.record_table.table.selected_header() {
+            Some(column) => column.clone(),
+            None => return Ok(()),
+        };
+        if let (Some(pool), Some((database, table))) =
+            (self.pool.as_ref(), self.databases.tree().selected_table())
+        {
+            let filter = self.record_table.filter.input_str();
+            let statement = quality_statement(
+                pool.table_name(&database, &table).as_str(),
+                pool.identifier_quote(),
+                column.as_str(),
+                &pool.text_syntax(),
+                Some(filter.as_str()).filter(|filter| !filter.is_empty()),
+            );
+            let pool = Arc::clone(pool);
+            let (cancel_token, receiver) = oneshot::channel();
+            self.quality_task = Some(QueryTask {
+                handle: tokio::spawn(async move {
+                    let mut session = pool.session().await?;
+                    let _ = cancel_token.send(session.cancel_token());
+                    session
+                        .run(statement.as_str(), StatementMode::Execute)
+                        .await
+                }),
+                cancel_token: receiver,
+                cancelled: Arc::new(AtomicBool::new(false)),
+            });
+            self.column_quality.open(column)?;
+        }
+        Ok(())
+    }
+
+    /// starts fetching every record of the selected table matching the filter in the background, to copy them all
+    /// with their headers as TSV rather than only the pages loaded into the table
+    fn copy_all_records(&mut self) -> anyhow::Result<()> {
+        if self.copy_task.is_some() {
+            return Ok(());
+        }
+        if let (Some(pool), Some((database, table))) =
+            (self.pool.as_ref(), self.databases.tree().selected_table())
+        {
+            let pool = Arc::clone(pool);
+            let primary_key = self.record_table.primary_key.clone();
+            let filter =
+                Some(self.record_table.filter.input_str()).filter(|filter| !filter.is_empty());
+            let hidden = self.record_table.table.hidden_headers();
+            let (progress, progress_receiver) = mpsc::channel();
+            // the pages are fetched from the pool rather than on a session, so cancelling aborts the task
+            let (_, receiver) = oneshot::channel();
+            self.copy_task = Some(CopyTask {
+                task: QueryTask {
+                    handle: tokio::spawn(async move {
+                        let mut lines = vec![];
+                        let mut fetched = 0;
+                        let mut key = None;
+                        loop {
+                            // falls back to offsets like the table when the key cannot be read from the last record
+                            let (headers, records, next_key) =
+                                if primary_key.is_empty() || (fetched > 0 && key.is_none()) {
+                                    let (headers, records) = pool
+                                        .get_records(&database, &table, fetched, filter.clone())
+                                        .await?;
+                                    (headers, records, None)
+                                } else {
+                                    pool.get_records_after(
+                                        &database,
+                                        &table,
+                                        &primary_key,
+                                        key.as_deref(),
+                                        filter.clone(),
+                                    )
+                                    .await?
+                                };
+                            // the hidden columns are left out as they are in the table
+                            let tsv = |row: Vec<&str>| {
+                                row.into_iter()
+                                    .zip(headers.iter())
+                                    .filter(|(_, header)| !hidden.contains(header))
+                                    .map(|(cell, _)| cell)
+                                    .collect::<Vec<&str>>()
+                                    .join("\t")
+                            };
+                            if lines.is_empty() && !headers.is_empty() {
+                                lines.push(tsv(headers.iter().map(String::as_str).collect()));
+                            }
+                            fetched += records.len();
+                            let _ = progress.send(fetched);
+                            key = next_key;
+                            lines.extend(
+                                records
+                                    .iter()
+                                    .map(|row| tsv(row.iter().map(display_value).collect())),
+                            );
+                            if records.len() < RECORDS_LIMIT_PER_PAGE as usize {
+                                break;
+                            }
+                        }
+                        Ok(lines.join("\n"))
+                    }),
+                    cancel_token: receiver,
+                    cancelled: Arc::new(AtomicBool::new(false)),
+                },
+                progress: progress_receiver,
+            });
+            self.copy_records.open()?;
+        }
+        Ok(())
+    }
+
+    /// starts or stops running the table query or the statements of the SQL editor again every few seconds, refreshing
+    /// the results in place
+    fn toggle_watch(&mut self, watch: Watch) -> anyhow::Result<()> {
+        if self.watch.is_some() {
+            self.stop_watching();
+            return Ok(());
+        }
+        // statements which change something must not run again and again without being asked to
+        if let (Watch::Statements(statements), Some(pool)) = (&watch, self.pool.as_ref()) {
+            if statements.is_empty()
+                || !statements
+                    .iter()
+                    .all(|statement| is_read_only_statement(statement, pool.engine()))
+            {
+                return Err(anyhow::anyhow!(Msg::WatchReadOnly.text()));
+            }
+        }
+        self.watch = Some((watch, Instant::now()));
+        Ok(())
+    }
+
+    fn stop_watching(&mut self) {
+        self.watch = None;
+        if let Some(task) = self.watch_task.take() {
+            task.handle.abort();
+        }
+    }
+
+    /// starts fetching the first page of the selected table in the background, as long as no further page is loaded
+    /// which refreshing would drop
+    fn start_watch_records(&mut self) {
+        let table = &self.record_table.table;
+        if table.row_offset + table.rows.len() > RECORDS_LIMIT_PER_PAGE as usize {
+            return;
+        }
+        if let (Some(pool), Some((database, table))) =
+            (self.pool.as_ref(), self.databases.tree().selected_table())
+        {
+            let pool = Arc::clone(pool);
+            let primary_key = self.record_table.primary_key.clone();
+            let filter =
+                Some(self.record_table.filter.input_str()).filter(|filter| !filter.is_empty());
+            let (_, receiver) = oneshot::channel();
+            self.watch_task = Some(QueryTask {
+                handle: tokio::spawn(async move {
+                    let (headers, records, key) = if primary_key.is_empty() {
+                        let (headers, records) =
+                            pool.get_records(&database, &table, 0, filter).await?;
+                        (headers, records, None)
+                    } else {
+                        pool.get_records_after(&database, &table, &primary_key, None, filter)
+                            .await?
+                    };
+                    Ok((database, table, headers, records, key))
+                }),
+                cancel_token: receiver,
+                cancelled: Arc::new(AtomicBool::new(false)),
+            });
+        }
+    }
+
+    /// runs the watched query again once the interval has passed, and refreshes the table once its records are fetched
+    fn tick_watch(&mut self) -> anyhow::Result<()> {
+        if let Some(task) = self.watch_task.as_mut() {
+            let result = match (&mut task.handle).now_or_never() {
+                Some(result) => result,
+                None => return Ok(()),
+            };
+            self.watch_task = None;
+            let result = result
+                .map_err(anyhow::Error::from)
+                .and_then(|result| result);
+            match result {
+                // another table may have been opened while the records were fetched
+                Ok((database, table, headers, records, key)) => {
+                    if self.databases.tree().selected_table() == Some((database, table)) {
+                        self.record_table.table.refresh_rows(records, headers);
+                        self.record_table.next_key = key;
+                    }
+                }
+                Err(err) => {
+                    // the same error would show again every few seconds
+                    self.stop_watching();
+                    return Err(err);
+                }
+            }
+            return Ok(());
+        }
+        let interval = Duration::from_secs(self.config.table_config.watch_interval_secs);
+        let statements = match self.watch.as_mut() {
+            Some((watch, last_run)) if last_run.elapsed() >= interval => {
+                *last_run = Instant::now();
+                match watch {
+                    Watch::Records => None,
+                    Watch::Statements(statements) => Some(statements.clone()),
+                }
+            }
+            _ => return Ok(()),
+        };
+        match statements {
+            // statements of the user running or a transaction waiting to end are left alone
+            Some(statements) => {
+                if self.query_task.is_none() && self.transaction.is_none() {
+                    self.start_statements(
+                        StatementMode::Execute,
+                        vec![],
+                        statements,
+                        self.config.table_config.fetch,
+                    )?;
+                }
+            }
+            None => self.start_watch_records(),
+        }
+        Ok(())
+    }
+
+    /// starts running the statements in a file in the background, logging each one as it ends
+    fn run_statements_in_file(&mut self, path: &str, stop_on_error: bool) -> anyhow::Result<()> {
+        if self.batch_task.is_some() {
+            return Ok(());
+        }
+        if let Some(engine) = self.pool.as_ref().map(|pool| pool.engine()) {
+            let statements = file_statements(path, engine)?;
+            for statement in &statements {
+                self.check_read_only(statement)?;
+            }
+            if !statements
+                .iter()
+                .all(|statement| is_read_only_statement(statement, engine))
+                && self.confirms(Action::Write)
+            {
+                return self.ask(
+                    Msg::ConfirmWrite.text().to_string(),
+                    format!("{} ({} {})", path, statements.len(), Msg::Statements.text()),
+                    PendingAction::RunFile {
+                        statements,
+                        stop_on_error,
+                    },
+                );
+            }
+            self.start_file(statements, stop_on_error)?;
+        }
+        Ok(())
+    }
+
+    fn start_file(&mut self, statements: Vec<String>, stop_on_error: bool) -> anyhow::Result<()> {
+        if self.pool.is_some() {
+            self.run_file.start(statements.len());
+        }
+        self.start_batch(statements, stop_on_error, false)
+    }
+
+    /// inserts the rows of a CSV file into the selected table in batches, asking first when writes are confirmed
+    fn import_csv(&mut self, request: CsvRows) -> anyhow::Result<()> {
+        if self.batch_task.is_some() {
+            return Ok(());
+        }
+        let (pool, (database, table)) =
+            match (self.pool.as_ref(), self.databases.tree().selected_table()) {
+                (Some(pool), Some(selected)) => (pool, selected),
+                _ => return Ok(()),
+            };
+        self.check_writable()?;
+        let table = pool.table_name(&database, &table);
+        let statements = insert_batches(
+            pool.engine(),
+            table.as_str(),
+            pool.identifier_quote(),
+            &request.headers,
+            &request.kinds,
+            &request.rows,
+            request.batch_size,
+        );
+        let rows = request.rows.len();
+        if self.confirms(Action::Write) {
+            return self.ask(
+                Msg::ConfirmWrite.text().to_string(),
+                format!("INSERT INTO {} ({} {})", table, rows, Msg::Rows.text()),
+                PendingAction::ImportCsv { statements, rows },
+            );
+        }
+        self.start_import(statements, rows)
+    }
+
+    fn start_import(&mut self, statements: Vec<String>, rows: usize) -> anyhow::Result<()> {
+        if self.pool.is_some() {
+            self.csv_import.start(statements.len(), rows);
+        }
+        self.start_batch(statements, true, true)
+    }
+
+    /// runs statements one after another on a session in the background, sending how each one ended
+    fn start_batch(
+        &mut self,
+        statements: Vec<String>,
+        stop_on_error: bool,
+        import: bool,
+    ) -> anyhow::Result<()> {
+        if let Some(pool) = self.pool.as_ref() {
+            let pool = Arc::clone(pool);
+            let engine = pool.engine();
+            let (cancel_token, receiver) = oneshot::channel();
+            let (logs, log_receiver) = mpsc::channel();
+            let cancelled = Arc::new(AtomicBool::new(false));
+            let stopped = Arc::clone(&cancelled);
+            self.batch_task = Some(BatchTask {
+                task: QueryTask {
+                    handle: tokio::spawn(async move {
+                        let mut session = pool.session().await?;
+                        let _ = cancel_token.send(session.cancel_token());
+                        for statement in statements {
+                            if stopped.load(Ordering::Relaxed) {
+                                break;
+                            }
+                            let started = Instant::now();
+                            let result = match Feature::required_by(statement.as_str(), engine) {
+                                Some(feature) => Self::check_supported(pool.as_ref(), feature),
+                                None => Ok(()),
+                            };
+                            let result = match result {
+                                Ok(()) => {
+                                    session
+                                        .run(statement.as_str(), StatementMode::Execute)
+                                        .await
+                                }
+                                Err(err) => Err(err),
+                            };
+                            let elapsed = started.elapsed();
+                            let warnings = match &result {
+                                Ok(_) => session.warnings().await.unwrap_or_default(),
+                                Err(_) => vec![],
+                            };
+                            let _ = logs.send(
+                                StatementLog::new(statement, elapsed, &result)
+                                    .with_warnings(warnings),
+                            );
+                            let failed = result.is_err();
+                            if failed && stop_on_error {
+                                break;
+                            }
+                        }
+                        Ok(())
+                    }),
+                    cancel_token: receiver,
+                    cancelled,
+                },
+                logs: log_receiver,
+                import,
+            });
+        }
+        Ok(())
+    }
+
+    /// adds statements to the queue, which runs them one after another after those queued before
+    fn queue_statements(&mut self, statements: Vec<String>) -> anyhow::Result<()> {
+        if let Some(pool) = self.pool.as_ref() {
+            let engine = pool.engine();
+            for statement in &statements {
+                self.check_read_only(statement)?;
+                if let Some(feature) = Feature::required_by(statement, engine) {
+                    Self::check_supported(pool.as_ref(), feature)?;
+                }
+            }
+            if !statements
+                .iter()
+                .all(|statement| is_read_only_statement(statement, engine))
+                && self.confirms(Action::Write)
+            {
+                return self.ask(
+                    Msg::ConfirmWrite.text().to_string(),
+                    format!("{} {}", statements.len(), Msg::Statements.text()),
+                    PendingAction::Queue { statements },
+                );
+            }
+            for statement in statements {
+                self.queue.push(statement);
+            }
+        }
+        Ok(())
+    }
+
+    /// logs how the running statement of the queue ended, and starts the next one
+    fn tick_queue(&mut self) -> anyhow::Result<()> {
+        if let Some(queued) = self.queued_task.as_mut() {
+            let result = match (&mut queued.task.handle).now_or_never() {
+                Some(result) => result,
+                None => return Ok(()),
+            };
+            let queued = self.queued_task.take().unwrap();
+            let id = queued.id;
+            let log = match result {
+                Ok(_) | Err(_) if queued.task.is_cancelled() => None,
+                Ok(result) => Some(result),
+                Err(err) => Some(Err(err.into())),
+            };
+            let log = log.map(|result| {
+                StatementLog::new(queued.statement, queued.started.elapsed(), &result)
+            });
+            if let Some(log) = log.as_ref() {
+                self.execution_log.push(log.clone());
+            }
+            self.queue.finish(id, log);
+        }
+        if let (Some(pool), Some((id, statement))) = (self.pool.as_ref(), self.queue.start_next()) {
+            let pool = Arc::clone(pool);
+            let (cancel_token, receiver) = oneshot::channel();
+            let running = statement.clone();
+            self.queued_task = Some(QueuedTask {
+                id,
+                statement,
+                started: Instant::now(),
+                task: QueryTask {
+                    handle: tokio::spawn(async move {
+                        let mut session = pool.session().await?;
+                        let _ = cancel_token.send(session.cancel_token());
+                        session.run(running.as_str(), StatementMode::Execute).await
+                    }),
+                    cancel_token: receiver,
+                    cancelled: Arc::new(AtomicBool::new(false)),
+                },
+            });
         }
         Ok(())
     }
 
+    /// takes another connection in place of a lost one, with the settings the statements run with
+    async fn reconnect(
+        pool: &dyn Pool,
+        overrides: &[(String, String)],
+    ) -> anyhow::Result<Box<dyn Session>> {
+        let mut session = pool.session().await?;
+        session.apply_settings(overrides).await?;
+        Ok(session)
+    }
+
+    /// cancels the running statements on the server, or drops them when the token has not arrived or was already used
+    async fn cancel_task<T>(pool: &dyn Pool, task: &mut QueryTask<T>) -> anyhow::Result<()> {
+        task.cancelled.store(true, Ordering::Relaxed);
+        match task.cancel_token.try_recv() {
+            Ok(token) => pool.cancel(token).await?,
+            Err(_) => task.handle.abort(),
+        }
+        Ok(())
+    }
+
+    async fn cancel_statements(&mut self) -> anyhow::Result<()> {
+        if let (Some(pool), Some(task)) = (self.pool.as_ref(), self.query_task.as_mut()) {
+            Self::cancel_task(pool.as_ref(), task).await?;
+        }
+        Ok(())
+    }
+
+    /// cancels the statement of the file running on the server and skips the rest of them
+    async fn cancel_file(&mut self) -> anyhow::Result<()> {
+        if let (Some(pool), Some(batch)) = (self.pool.as_ref(), self.batch_task.as_mut()) {
+            Self::cancel_task(pool.as_ref(), &mut batch.task).await?;
+        }
+        Ok(())
+    }
+
+    /// moves the statements of the file which have ended into the log, and finishes it once all of them have
+    fn tick_file(&mut self) -> anyhow::Result<()> {
+        let result = match self.batch_task.as_mut() {
+            Some(batch) => {
+                let result = (&mut batch.task.handle).now_or_never();
+                while let Ok(log) = batch.logs.try_recv() {
+                    self.execution_log.push(log.clone());
+                    if batch.import {
+                        self.csv_import.push_log(&log);
+                    } else {
+                        self.run_file.push_log(log);
+                    }
+                }
+                match result {
+                    Some(result) => result,
+                    None => return Ok(()),
+                }
+            }
+            None => return Ok(()),
+        };
+        let (cancelled, import) = match self.batch_task.take() {
+            Some(batch) => (batch.task.is_cancelled(), batch.import),
+            None => (false, false),
+        };
+        if import {
+            self.csv_import.finish(cancelled);
+        } else {
+            self.run_file.finish(cancelled);
+        }
+        match result {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(_)) | Err(_) if cancelled => Ok(()),
+            Ok(Err(err)) => Err(err),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// shows the statistics of the column once they are computed
+    fn tick_stats(&mut self) -> anyhow::Result<()> {
+        let result = match self.stats_task.as_mut() {
+            Some(task) => match (&mut task.handle).now_or_never() {
+                Some(result) => result,
+                None => return Ok(()),
+            },
+            None => return Ok(()),
+        };
+        let cancelled = matches!(self.stats_task.take(), Some(task) if task.is_cancelled());
+        match result {
+            Ok(Ok(ExecuteResult::Read { mut rows, .. })) if !rows.is_empty() => {
+                self.column_stats.set_values(
+                    rows.remove(0)
+                        .iter()
+                        .map(|value| display_value(value).to_string())
+                        .collect(),
+                );
+                Ok(())
+            }
+            Ok(_) | Err(_) if cancelled => Ok(()),
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(err)) => {
+                self.column_stats.hide();
+                Err(err)
+            }
+            Err(err) => {
+                self.column_stats.hide();
+                Err(err.into())
+            }
+        }
+    }
+
+    /// shows the data quality checks of the column once they ran
+    fn tick_quality(&mut self) -> anyhow::Result<()> {
+        let result = match self.quality_task.as_mut() {
+            Some(task) => match (&mut task.handle).now_or_never() {
+                Some(result) => result,
+                None => return Ok(()),
+            },
+            None => return Ok(()),
+        };
+        let cancelled = matches!(self.quality_task.take(), Some(task) if task.is_cancelled());
+        match result {
+            Ok(Ok(ExecuteResult::Read { mut rows, .. })) if !rows.is_empty() => {
+                self.column_quality.set_values(
+                    rows.remove(0)
+                        .iter()
+                        .map(|value| display_value(value).to_string())
+                        .collect(),
+                );
+                Ok(())
+            }
+            Ok(_) | Err(_) if cancelled => Ok(()),
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(err)) => {
+                self.column_quality.hide();
+                Err(err)
+            }
+            Err(err) => {
+                self.column_quality.hide();
+                Err(err.into())
+            }
+        }
+    }
+
+    /// updates the number of fetched records, and copies them once every page is fetched
+    fn tick_copy(&mut self) -> anyhow::Result<()> {
+        let result = match self.copy_task.as_mut() {
+            Some(copy) => {
+                if let Some(fetched) = copy.progress.try_iter().last() {
+                    self.copy_records.set_fetched(fetched);
+                }
+                match (&mut copy.task.handle).now_or_never() {
+                    Some(result) => result,
+                    None => return Ok(()),
+                }
+            }
+            None => return Ok(()),
+        };
+        let cancelled = matches!(self.copy_task.take(), Some(copy) if copy.task.is_cancelled());
+        self.copy_records.hide();
+        match result {
+            Ok(_) | Err(_) if cancelled => Ok(()),
+            Ok(Ok(text)) => copy_to_clipboard(text.as_str(), &self.config.clipboard),
+            Ok(Err(err)) => Err(err),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// updates how far the dump has got, and shows how it ended once it has
+    fn tick_dump(&mut self) {
+        let result = match self.dump_task.as_mut() {
+            Some(dump) => {
+                if let Some(progress) = dump.progress.try_iter().last() {
+                    self.dump.set_progress(progress);
+                }
+                if let Some(metadata) = dump
+                    .written
+                    .as_ref()
+                    .and_then(|path| std::fs::metadata(path).ok())
+                {
+                    self.dump.set_written(metadata.len());
+                }
+                match (&mut dump.task.handle).now_or_never() {
+                    Some(result) => result,
+                    None => return,
+                }
+            }
+            None => return,
+        };
+        let cancelled = matches!(self.dump_task.take(), Some(dump) if dump.task.is_cancelled());
+        let error = match result {
+            Ok(_) | Err(_) if cancelled => None,
+            Ok(Ok(())) => None,
+            Ok(Err(err)) => Some(err.to_string()),
+            Err(err) => Some(err.to_string()),
+        };
+        self.dump.finish(error, cancelled);
+    }
+
+    /// shows the results once the statements running in the background finish
+    pub fn tick(&mut self) -> anyhow::Result<()> {
+        self.tick_file()?;
+        self.tick_stats()?;
+        self.tick_quality()?;
+        self.tick_copy()?;
+        self.tick_dump();
+        self.tick_queue()?;
+        self.tick_watch()?;
+        self.tick_table_status();
+        let result = match self.query_task.as_mut() {
+            Some(task) => match (&mut task.handle).now_or_never() {
+                Some(result) => result,
+                None => return Ok(()),
+            },
+            None => return Ok(()),
+        };
+        let cancelled = matches!(self.query_task.take(), Some(task) if task.is_cancelled());
+        self.sql_editor.finish_running();
+        match result {
+            Ok(Ok((mut results, retries, transaction, more_rows, executions))) => {
+                if let Some((_, result)) = results.last_mut() {
+                    if cancelled && result.is_err() {
+                        *result = Err(anyhow::anyhow!(Msg::QueryCancelled.text()));
+                    }
+                }
+                let logs = results
+                    .iter()
+                    .zip(executions)
+                    .map(|((statement, result), (elapsed, warnings))| {
+                        StatementLog::new(statement.clone(), elapsed, result)
+                            .with_warnings(warnings)
+                    })
+                    .collect::<Vec<StatementLog>>();
+                let statuses = logs.iter().map(StatementLog::status).collect();
+                // the statements run again and again while they are watched are not logged
+                if matches!(self.watch, Some((Watch::Statements(_), _))) {
+                    self.sql_editor.refresh_results(results);
+                } else {
+                    self.sql_editor.set_results(results);
+                    for log in logs {
+                        self.execution_log.push(log);
+                    }
+                }
+                self.sql_editor.set_statuses(statuses);
+                self.sql_editor.set_more_rows(&more_rows);
+                self.sql_editor.set_retries(retries);
+                self.set_transaction(transaction);
+            }
+            Ok(Err(_)) | Err(_) if cancelled => {
+                self.set_transaction(None);
+                return Err(anyhow::anyhow!(Msg::QueryCancelled.text()));
+            }
+            Ok(Err(err)) => {
+                self.set_transaction(None);
+                return Err(err);
+            }
+            Err(err) => {
+                self.set_transaction(None);
+                return Err(err.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// keeps the connection of the SQL editor while a transaction is open on it
+    fn set_transaction(&mut self, transaction: Option<Box<dyn Session>>) {
+        self.sql_editor.set_transaction(transaction.is_some());
+        if transaction.is_none() {
+            self.quit_warned = false;
+        }
+        self.transaction = transaction;
+    }
+
+    fn check_supported(pool: &dyn Pool, feature: Feature) -> anyhow::Result<()> {
+        if pool.supports(feature) {
+            return Ok(());
+        }
+        Err(anyhow::anyhow!(
+            "{}: {} ({})",
+            Msg::NotSupportedByServer.text(),
+            feature.name(),
+            pool.server_version()
+        ))
+    }
+
+    /// fails when the selected connection is read-only
+    fn check_writable(&self) -> anyhow::Result<()> {
+        if self
+            .connections
+            .selected_connection()
+            .map_or(false, |conn| conn.read_only)
+        {
+            return Err(anyhow::anyhow!("{}", Msg::ReadOnlyConnection.text()));
+        }
+        Ok(())
+    }
+
+    /// fails when the selected connection is read-only and `statement` does more than read
+    fn check_read_only(&self, statement: &str) -> anyhow::Result<()> {
+        let engine = match self.pool.as_ref() {
+            Some(pool) => pool.engine(),
+            None => return Ok(()),
+        };
+        if is_read_only_statement(statement, engine) {
+            return Ok(());
+        }
+        self.check_writable()
+            .map_err(|err| anyhow::anyhow!("{}: {}", err, statement))
+    }
+
     pub async fn event(&mut self, key: Key) -> anyhow::Result<EventState> {
         self.update_commands();
 
@@ -295,18 +3255,81 @@ impl App {
             return Ok(EventState::Consumed);
         };
 
-        if self.move_focus(key)?.is_consumed() {
+        if self.move_focus(key).await?.is_consumed() {
             return Ok(EventState::Consumed);
         };
+
+        if (key == self.config.key_config.quit || key == self.config.key_config.exit)
+            && self.transaction.is_some()
+            && !std::mem::replace(&mut self.quit_warned, true)
+        {
+            return Err(anyhow::anyhow!(Msg::UncommittedTransaction.text()));
+        }
         Ok(EventState::NotConsumed)
     }
 
-    pub async fn components_event(&mut self, key: Key) -> anyhow::Result<EventState> {
-        if self.error.event(key)?.is_consumed() {
+    /// focuses and selects what was clicked, or scrolls what is under the wheel, unless a popup is open
+    pub async fn mouse_event(&mut self, mouse: Mouse) -> anyhow::Result<()> {
+        self.sync_popups();
+        if self.popups.top().is_some() || matches!(self.focus, Focus::ConnectionList) {
+            return Ok(());
+        }
+        if self.databases.mouse_event(mouse)?.is_consumed() {
+            if mouse.kind == MouseKind::Click {
+                self.focus = Focus::DabataseList;
+            }
+            return Ok(());
+        }
+        if self.tab.mouse_event(mouse)?.is_consumed() {
+            return Ok(());
+        }
+        let state = match self.tab.selected_tab {
+            Tab::Records => self.record_table.mouse_event(mouse)?,
+            Tab::Columns => self.column_table.mouse_event(mouse)?,
+            Tab::Constraints => self.constraint_table.mouse_event(mouse)?,
+            Tab::ForeignKeys => self.foreign_key_table.mouse_event(mouse)?,
+            Tab::Indexes => self.index_table.mouse_event(mouse)?,
+            Tab::Sql => self.sql_editor.mouse_event(mouse)?,
+            Tab::Definition => self.table_definition.mouse_event(mouse)?,
+            Tab::Triggers => self.trigger_table.mouse_event(mouse)?,
+        };
+        if state.is_consumed() {
+            if mouse.kind == MouseKind::Click {
+                self.focus = Focus::Table;
+            }
+            match self.tab.selected_tab {
+                Tab::Records => self.fetch_more_records().await?,
+                Tab::Sql => self.fetch_more_results().await?,
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn components_event(&mut self, key: Key) -> anyhow::Result<EventState> {
+        // popups are modal, so the newest one takes every key except the one to exit the app
+        self.sync_popups();
+        if let Some(popup) = self.popups.top() {
+            let state = self.popup_event(popup, key).await;
+            self.sync_popups();
+            if !state?.is_consumed() && key == self.config.key_config.exit {
+                return Ok(EventState::NotConsumed);
+            }
+            return Ok(EventState::Consumed);
+        }
+
+        if self.query_task.is_some()
+            && (key == self.config.key_config.exit_popup || key == self.config.key_config.exit)
+        {
+            self.cancel_statements().await?;
             return Ok(EventState::Consumed);
         }
 
-        if !matches!(self.focus, Focus::ConnectionList) && self.help.event(key)?.is_consumed() {
+        if !matches!(self.focus, Focus::ConnectionList)
+            && !self.sql_editor_focused()
+            && !self.table_searching()
+            && self.help.event(key)?.is_consumed()
+        {
             return Ok(EventState::Consumed);
         }
 
@@ -316,34 +3339,376 @@ impl App {
                     return Ok(EventState::Consumed);
                 }
 
+                if key == self.config.key_config.add_connection {
+                    self.connection_form.open(None)?;
+                    return Ok(EventState::Consumed);
+                }
+
+                if key == self.config.key_config.edit_connection {
+                    if let Some(index) = self.connections.selected_index() {
+                        self.connection_form
+                            .open(Some((index, &self.connections.connections()[index])))?;
+                    }
+                    return Ok(EventState::Consumed);
+                }
+
+                if key == self.config.key_config.delete_connection {
+                    if let Some(index) = self.connections.selected_index() {
+                        if self.connections.confirm_delete() {
+                            let mut connections = self.connections.connections().to_vec();
+                            connections.remove(index);
+                            let selected = if connections.is_empty() {
+                                None
+                            } else {
+                                Some(index.min(connections.len() - 1))
+                            };
+                            self.update_connections(connections, selected)?;
+                        }
+                    }
+                    return Ok(EventState::Consumed);
+                }
+
+                if key == self.config.key_config.test_connection {
+                    if let Some(conn) = self.connections.selected_connection().cloned() {
+                        let status = match Self::connect(&conn).await {
+                            Ok(pool) => {
+                                pool.close().await;
+                                Msg::ConnectionSucceeded.text().to_string()
+                            }
+                            Err(err) => format!("{}: {}", Msg::ConnectionFailed.text(), err),
+                        };
+                        self.connections.set_status(status);
+                    }
+                    return Ok(EventState::Consumed);
+                }
+
+                if key == self.config.key_config.import_connections {
+                    let connections = self.connections.connections();
+                    let found = crate::import::find_connections()
+                        .into_iter()
+                        .filter(|(_, found)| {
+                            !connections
+                                .iter()
+                                .any(|connection| connection.fields() == found.fields())
+                        })
+                        .collect::<Vec<_>>();
+                    if found.is_empty() {
+                        self.connections
+                            .set_status(Msg::NoConnectionsToImport.text().to_string());
+                    } else {
+                        self.import_connections.open(found)?;
+                    }
+                    return Ok(EventState::Consumed);
+                }
+
                 if key == self.config.key_config.enter {
-                    self.update_databases().await?;
+                    self.open_selected_connection().await?;
                     return Ok(EventState::Consumed);
                 }
             }
             Focus::DabataseList => {
+                if key == self.config.key_config.scheduled_jobs && self.databases.tree_focused() {
+                    if let Some(pool) = self.pool.as_ref() {
+                        let jobs = pool.get_scheduled_jobs().await?;
+                        self.scheduled_jobs.open(jobs)?;
+                    }
+                    return Ok(EventState::Consumed);
+                }
+
+                if key == self.config.key_config.queue_analyze && self.databases.tree_focused() {
+                    if let (Some(pool), Some((database, table))) =
+                        (self.pool.as_ref(), self.databases.tree().selected_table())
+                    {
+                        let statement = pool.analyze_statement(&database, &table);
+                        self.queue_statements(vec![statement])?;
+                    }
+                    return Ok(EventState::Consumed);
+                }
+
+                if key == self.config.key_config.execute_routine && self.databases.tree_focused() {
+                    if let Some((database, routine)) = self.databases.tree().selected_routine() {
+                        self.routine_form.open(database, routine)?;
+                    }
+                    return Ok(EventState::Consumed);
+                }
+
                 let state = self.databases.event(key)?;
 
                 if key == self.config.key_config.enter && self.databases.tree_focused() {
-                    self.update_table().await?;
+                    if self.databases.tree().selected_routine().is_some() {
+                        self.open_routine().await?;
+                    } else {
+                        self.update_table().await?;
+                    }
                     return Ok(EventState::Consumed);
                 }
 
                 return Ok(state);
             }
             Focus::Table => {
+                if key == self.config.key_config.enter
+                    && !self.table_searching()
+                    && !self.sql_editor_focused()
+                    && (!matches!(self.tab.selected_tab, Tab::Records)
+                        || !self.record_table.filter_focused())
+                {
+                    let cell = self.selected_tab_table().and_then(|table| {
+                        table.selected_column_value().map(|(column, value)| {
+                            let json = table.column_badge(column) == Some("json");
+                            (column.clone(), display_value(value).to_string(), json)
+                        })
+                    });
+                    if let Some((column, value, json)) = cell {
+                        self.cell_value.open(column, value, json)?;
+                        return Ok(EventState::Consumed);
+                    }
+                }
+
                 match self.tab.selected_tab {
                     Tab::Records => {
+                        if key == Key::Tab && self.record_table.filter_focused() {
+                            self.cache_qualified_columns().await?;
+                        }
+
                         if self.record_table.event(key)?.is_consumed() {
+                            if let Some(row) = self.record_table.table.take_wanted_row() {
+                                self.fetch_records_until(row).await?;
+                            }
+                            if let Some(edit) = self.record_table.take_bulk_edit() {
+                                self.confirm_bulk_edit(edit)?;
+                            }
                             return Ok(EventState::Consumed);
                         };
 
                         if key == self.config.key_config.copy {
                             if let Some(text) = self.record_table.table.selected_cells() {
-                                copy_to_clipboard(text.as_str())?
+                                copy_to_clipboard(text.as_str(), &self.config.clipboard)?
+                            }
+                        }
+
+                        if key == self.config.key_config.copy_as_insert
+                            && !self.record_table.filter_focused()
+                        {
+                            if let Some(text) = self.insert_statements(RowRange::Selected)? {
+                                copy_to_clipboard(text.as_str(), &self.config.clipboard)?
+                            }
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.copy_all_records
+                            && !self.record_table.filter_focused()
+                        {
+                            self.copy_all_records()?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.toggle_watch
+                            && !self.record_table.filter_focused()
+                        {
+                            self.toggle_watch(Watch::Records)?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.pipe_to_command {
+                            if let Some(text) = self.record_table.table.selected_tsv() {
+                                self.pipe_command.open(text)?;
+                                return Ok(EventState::Consumed);
+                            }
+                        }
+
+                        if key == self.config.key_config.generate_fake_data
+                            && !self.record_table.filter_focused()
+                        {
+                            self.check_writable()?;
+                            self.data_generator.open(
+                                self.column_table
+                                    .rows
+                                    .iter()
+                                    .map(|column| {
+                                        (
+                                            column.get(0).cloned().flatten().unwrap_or_default(),
+                                            column.get(1).cloned().flatten().unwrap_or_default(),
+                                            column.get(3).cloned().flatten(),
+                                        )
+                                    })
+                                    .collect(),
+                            )?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.insert_row
+                            && !self.record_table.filter_focused()
+                        {
+                            self.check_writable()?;
+                            if let (Some(pool), Some((database, table))) =
+                                (self.pool.as_ref(), self.databases.tree().selected_table())
+                            {
+                                self.insert_row.open(
+                                    pool.engine(),
+                                    pool.table_name(&database, &table),
+                                    pool.identifier_quote(),
+                                    self.column_table
+                                        .rows
+                                        .iter()
+                                        .map(|column| {
+                                            (
+                                                column
+                                                    .get(0)
+                                                    .cloned()
+                                                    .flatten()
+                                                    .unwrap_or_default(),
+                                                column
+                                                    .get(1)
+                                                    .cloned()
+                                                    .flatten()
+                                                    .unwrap_or_default(),
+                                                column
+                                                    .get(2)
+                                                    .cloned()
+                                                    .flatten()
+                                                    .unwrap_or_default(),
+                                                column
+                                                    .get(3)
+                                                    .cloned()
+                                                    .flatten()
+                                                    .unwrap_or_default(),
+                                            )
+                                        })
+                                        .collect(),
+                                )?;
+                            }
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.column_stats
+                            && !self.record_table.filter_focused()
+                        {
+                            self.compute_column_stats()?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.column_quality
+                            && !self.record_table.filter_focused()
+                        {
+                            self.compute_column_quality()?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if (key == self.config.key_config.delete_template
+                            || key == self.config.key_config.update_template)
+                            && !self.record_table.filter_focused()
+                        {
+                            self.write_change_template(
+                                key == self.config.key_config.update_template,
+                            )?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.undo_change
+                            && !self.record_table.filter_focused()
+                        {
+                            self.confirm_undo()?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.delete_rows
+                            && !self.record_table.filter_focused()
+                        {
+                            self.check_writable()?;
+                            if let (Some(pool), Some((database, table))) =
+                                (self.pool.as_ref(), self.databases.tree().selected_table())
+                            {
+                                let keys = self.record_table.selected_keys();
+                                let statement = delete_statement(
+                                    pool.engine(),
+                                    pool.table_name(&database, &table).as_str(),
+                                    pool.identifier_quote(),
+                                    &self.record_table.primary_key,
+                                    &keys,
+                                )?;
+                                let lock = if self
+                                    .connections
+                                    .selected_connection()
+                                    .map_or(false, |conn| conn.lock_rows)
+                                    && pool.supports(Feature::LockNowait)
+                                {
+                                    Some(lock_statement(
+                                        pool.engine(),
+                                        pool.table_name(&database, &table).as_str(),
+                                        pool.identifier_quote(),
+                                        &self.record_table.primary_key,
+                                        &keys,
+                                    )?)
+                                } else {
+                                    None
+                                };
+                                if keys.is_empty() {
+                                    return Ok(EventState::Consumed);
+                                }
+                                let headers = &self.record_table.table.headers;
+                                let undo = vec![reinsert_statement(
+                                    pool.engine(),
+                                    pool.table_name(&database, &table).as_str(),
+                                    pool.identifier_quote(),
+                                    headers,
+                                    &headers
+                                        .iter()
+                                        .map(|header| self.record_table.table.column_kind(header))
+                                        .collect::<Vec<ColumnKind>>(),
+                                    &pool.get_generated_columns(&database, &table).await?,
+                                    self.record_table.table.selected_rows(),
+                                )];
+                                if self.confirms(Action::Delete) {
+                                    self.ask(
+                                        format!(
+                                            "{} ({} {})",
+                                            Msg::DeleteRows.text(),
+                                            keys.len(),
+                                            Msg::Rows.text()
+                                        ),
+                                        statement,
+                                        PendingAction::Change { lock, undo },
+                                    )?;
+                                } else {
+                                    self.run_pending(
+                                        PendingAction::Change { lock, undo },
+                                        statement.as_str(),
+                                    )
+                                    .await?;
+                                }
+                            }
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.open_record_detail
+                            && !self.record_table.filter_focused()
+                        {
+                            if let Some(row) = self.record_table.table.selected_record() {
+                                self.record_detail.set(
+                                    self.record_table.table.headers.clone(),
+                                    row.iter()
+                                        .map(|value| display_value(value).to_string())
+                                        .collect(),
+                                )?;
+                                return Ok(EventState::Consumed);
                             }
                         }
 
+                        if key == self.config.key_config.open_child_rows
+                            && !self.record_table.filter_focused()
+                        {
+                            self.open_child_rows().await?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.follow_foreign_key
+                            && !self.record_table.filter_focused()
+                            && self.record_table.selected_foreign_key().is_some()
+                        {
+                            self.follow_foreign_key().await?;
+                            return Ok(EventState::Consumed);
+                        }
+
                         if key == self.config.key_config.enter && self.record_table.filter_focused()
                         {
                             self.record_table.focus = crate::components::record_table::Focus::Table;
@@ -354,34 +3719,7 @@ impl App {
                             return Ok(EventState::Consumed);
                         }
 
-                        if let Some(index) = self.record_table.table.selected_row.selected() {
-                            if index.saturating_add(1) % RECORDS_LIMIT_PER_PAGE as usize == 0 {
-                                if let Some((database, table)) =
-                                    self.databases.tree().selected_table()
-                                {
-                                    let (_, records) = self
-                                        .pool
-                                        .as_ref()
-                                        .unwrap()
-                                        .get_records(
-                                            &database,
-                                            &table,
-                                            index as u16,
-                                            if self.record_table.filter.input.is_empty() {
-                                                None
-                                            } else {
-                                                Some(self.record_table.filter.input_str())
-                                            },
-                                        )
-                                        .await?;
-                                    if !records.is_empty() {
-                                        self.record_table.table.rows.extend(records);
-                                    } else {
-                                        self.record_table.table.end()
-                                    }
-                                }
-                            }
-                        };
+                        self.fetch_more_records().await?;
                     }
                     Tab::Columns => {
                         if self.column_table.event(key)?.is_consumed() {
@@ -390,9 +3728,16 @@ impl App {
 
                         if key == self.config.key_config.copy {
                             if let Some(text) = self.column_table.selected_cells() {
-                                copy_to_clipboard(text.as_str())?
+                                copy_to_clipboard(text.as_str(), &self.config.clipboard)?
                             }
                         };
+
+                        if key == self.config.key_config.pipe_to_command {
+                            if let Some(text) = self.column_table.selected_tsv() {
+                                self.pipe_command.open(text)?;
+                                return Ok(EventState::Consumed);
+                            }
+                        }
                     }
                     Tab::Constraints => {
                         if self.constraint_table.event(key)?.is_consumed() {
@@ -401,9 +3746,16 @@ impl App {
 
                         if key == self.config.key_config.copy {
                             if let Some(text) = self.constraint_table.selected_cells() {
-                                copy_to_clipboard(text.as_str())?
+                                copy_to_clipboard(text.as_str(), &self.config.clipboard)?
                             }
                         };
+
+                        if key == self.config.key_config.pipe_to_command {
+                            if let Some(text) = self.constraint_table.selected_tsv() {
+                                self.pipe_command.open(text)?;
+                                return Ok(EventState::Consumed);
+                            }
+                        }
                     }
                     Tab::ForeignKeys => {
                         if self.foreign_key_table.event(key)?.is_consumed() {
@@ -412,9 +3764,16 @@ impl App {
 
                         if key == self.config.key_config.copy {
                             if let Some(text) = self.foreign_key_table.selected_cells() {
-                                copy_to_clipboard(text.as_str())?
+                                copy_to_clipboard(text.as_str(), &self.config.clipboard)?
                             }
                         };
+
+                        if key == self.config.key_config.pipe_to_command {
+                            if let Some(text) = self.foreign_key_table.selected_tsv() {
+                                self.pipe_command.open(text)?;
+                                return Ok(EventState::Consumed);
+                            }
+                        }
                     }
                     Tab::Indexes => {
                         if self.index_table.event(key)?.is_consumed() {
@@ -423,17 +3782,310 @@ impl App {
 
                         if key == self.config.key_config.copy {
                             if let Some(text) = self.index_table.selected_cells() {
-                                copy_to_clipboard(text.as_str())?
+                                copy_to_clipboard(text.as_str(), &self.config.clipboard)?
+                            }
+                        };
+
+                        if key == self.config.key_config.pipe_to_command {
+                            if let Some(text) = self.index_table.selected_tsv() {
+                                self.pipe_command.open(text)?;
+                                return Ok(EventState::Consumed);
+                            }
+                        }
+                    }
+                    Tab::Sql => {
+                        if key == self.config.key_config.enter
+                            && self.sql_editor.editor_focused()
+                            && !self.sql_editor.searching_history()
+                        {
+                            self.run_statements(
+                                StatementMode::Execute,
+                                vec![],
+                                self.config.table_config.fetch,
+                            )?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.execute_other_fetch
+                            && self.sql_editor.editor_focused()
+                            && !self.sql_editor.searching_history()
+                        {
+                            self.run_statements(
+                                StatementMode::Execute,
+                                vec![],
+                                self.config.table_config.fetch.other(),
+                            )?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if (key == self.config.key_config.commit_transaction
+                            || key == self.config.key_config.rollback_transaction)
+                            && self.sql_editor.editor_focused()
+                            && !self.sql_editor.searching_history()
+                            && self.transaction.is_some()
+                        {
+                            let statement = if key == self.config.key_config.commit_transaction {
+                                "COMMIT"
+                            } else {
+                                "ROLLBACK"
+                            };
+                            self.start_statements(
+                                StatementMode::Execute,
+                                vec![],
+                                vec![statement.to_string()],
+                                FetchMode::All,
+                            )?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.toggle_watch
+                            && !self.sql_editor.searching_history()
+                        {
+                            let engine = self
+                                .pool
+                                .as_ref()
+                                .map_or(Engine::MySql, |pool| pool.engine());
+                            let statements =
+                                split_statements(self.sql_editor.query().as_str(), engine);
+                            self.toggle_watch(Watch::Statements(statements))?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.run_with_settings
+                            && self.sql_editor.editor_focused()
+                            && !self.sql_editor.searching_history()
+                        {
+                            if let Some(conn) = self.connections.selected_connection() {
+                                self.session_settings.open(&conn.settings)?;
+                            }
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.explain_query
+                            && self.sql_editor.editor_focused()
+                            && !self.sql_editor.searching_history()
+                        {
+                            self.run_statements(
+                                StatementMode::Explain { analyze: false },
+                                vec![],
+                                FetchMode::All,
+                            )?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.format_query
+                            && self.sql_editor.editor_focused()
+                            && !self.sql_editor.searching_history()
+                        {
+                            let format_config = &self.config.format_config;
+                            let options = FormatOptions {
+                                engine: format_config
+                                    .dialect
+                                    .or_else(|| self.pool.as_ref().map(|pool| pool.engine()))
+                                    .unwrap_or(Engine::MySql),
+                                keyword_case: format_config.keyword_case,
+                                indent_width: format_config.indent_width,
+                                max_line_width: format_config.max_line_width,
+                            };
+                            let formatted = format_sql(self.sql_editor.query().as_str(), &options);
+                            self.sql_editor.set_query(&formatted);
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.queue_query
+                            && self.sql_editor.editor_focused()
+                            && !self.sql_editor.searching_history()
+                        {
+                            self.sql_editor.push_history()?;
+                            let engine = self
+                                .pool
+                                .as_ref()
+                                .map_or(Engine::MySql, |pool| pool.engine());
+                            self.queue_statements(split_statements(
+                                self.sql_editor.query().as_str(),
+                                engine,
+                            ))?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.open_referenced_table
+                            && self.sql_editor.editor_focused()
+                            && !self.sql_editor.searching_history()
+                        {
+                            let engine = self
+                                .pool
+                                .as_ref()
+                                .map_or(Engine::MySql, |pool| pool.engine());
+                            let tables =
+                                referenced_tables(self.sql_editor.query().as_str(), engine)
+                                    .into_iter()
+                                    .filter(|(_, table)| self.databases.has_table(table))
+                                    .collect::<Vec<_>>();
+                            if tables.is_empty() {
+                                return Err(anyhow::anyhow!(Msg::NoReferencedTables.text()));
+                            }
+                            self.referenced_tables.open(tables)?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.save_snippet
+                            && self.sql_editor.editor_focused()
+                            && !self.sql_editor.searching_history()
+                        {
+                            self.snippets.open_save(self.sql_editor.query())?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.open_snippets
+                            && self.sql_editor.editor_focused()
+                            && !self.sql_editor.searching_history()
+                        {
+                            self.snippets.open()?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.explain_analyze_query
+                            && self.sql_editor.editor_focused()
+                            && !self.sql_editor.searching_history()
+                        {
+                            self.run_statements(
+                                StatementMode::Explain { analyze: true },
+                                vec![],
+                                FetchMode::All,
+                            )?;
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if self.sql_editor.event(key)?.is_consumed() {
+                            if self.sql_editor.editor_focused() {
+                                self.cache_referenced_columns().await?;
+                            } else {
+                                self.fetch_more_results().await?;
+                            }
+                            return Ok(EventState::Consumed);
+                        };
+
+                        if key == self.config.key_config.copy {
+                            if let Some(text) = self.sql_editor.selected_cells() {
+                                copy_to_clipboard(text.as_str(), &self.config.clipboard)?
+                            }
+                        };
+
+                        if key == self.config.key_config.copy_as_insert
+                            && !self.sql_editor.editor_focused()
+                            && self.sql_editor.result_table().is_some()
+                        {
+                            if let Some(text) = self.insert_statements(RowRange::Selected)? {
+                                copy_to_clipboard(text.as_str(), &self.config.clipboard)?
+                            }
+                            return Ok(EventState::Consumed);
+                        }
+
+                        if key == self.config.key_config.pipe_to_command {
+                            if let Some(text) = self.sql_editor.selected_tsv() {
+                                self.pipe_command.open(text)?;
+                                return Ok(EventState::Consumed);
+                            }
+                        }
+                    }
+                    Tab::Definition => {
+                        if self.table_definition.event(key)?.is_consumed() {
+                            return Ok(EventState::Consumed);
+                        };
+
+                        if key == self.config.key_config.copy {
+                            if let Some(text) = self.table_definition.definition() {
+                                copy_to_clipboard(text, &self.config.clipboard)?
                             }
                         };
                     }
+                    Tab::Triggers => {
+                        if self.trigger_table.event(key)?.is_consumed() {
+                            return Ok(EventState::Consumed);
+                        };
+
+                        if key == self.config.key_config.copy {
+                            if let Some(text) = self.trigger_table.selected_cells() {
+                                copy_to_clipboard(text.as_str(), &self.config.clipboard)?
+                            }
+                        };
+
+                        if key == self.config.key_config.pipe_to_command {
+                            if let Some(text) = self.trigger_table.selected_tsv() {
+                                self.pipe_command.open(text)?;
+                                return Ok(EventState::Consumed);
+                            }
+                        }
+                    }
                 };
             }
         }
         Ok(EventState::NotConsumed)
     }
 
-    pub fn move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
+    fn sql_editor_focused(&self) -> bool {
+        matches!(self.focus, Focus::Table)
+            && matches!(self.tab.selected_tab, Tab::Sql)
+            && self.sql_editor.editor_focused()
+    }
+
+    /// returns whether the keys type text into the focused component, extend the selection of a table, or move around
+    fn input_mode(&mut self) -> InputMode {
+        let typing = match self.focus {
+            Focus::ConnectionList => false,
+            Focus::DabataseList => !self.databases.tree_focused(),
+            Focus::Table => {
+                self.sql_editor_focused()
+                    || self.table_searching()
+                    || (matches!(self.tab.selected_tab, Tab::Records)
+                        && self.record_table.filter_focused())
+            }
+        };
+        if typing {
+            return InputMode::Insert;
+        }
+        if matches!(self.focus, Focus::Table)
+            && self
+                .selected_tab_table()
+                .map_or(false, |table| table.selecting())
+        {
+            return InputMode::Visual;
+        }
+        InputMode::Normal
+    }
+
+    /// returns whether the pattern of a search in the table of the selected tab is being typed
+    fn table_searching(&self) -> bool {
+        if !matches!(self.focus, Focus::Table) {
+            return false;
+        }
+        match self.tab.selected_tab {
+            Tab::Records => self.record_table.table.searching(),
+            Tab::Columns => self.column_table.searching(),
+            Tab::Constraints => self.constraint_table.searching(),
+            Tab::ForeignKeys => self.foreign_key_table.searching(),
+            Tab::Indexes => self.index_table.searching(),
+            Tab::Sql => self.sql_editor.searching_result(),
+            Tab::Definition => false,
+            Tab::Triggers => self.trigger_table.searching(),
+        }
+    }
+
+    /// returns the table shown in the selected tab
+    fn selected_tab_table(&mut self) -> Option<&mut TableComponent> {
+        match self.tab.selected_tab {
+            Tab::Records => Some(&mut self.record_table.table),
+            Tab::Columns => Some(&mut self.column_table),
+            Tab::Constraints => Some(&mut self.constraint_table),
+            Tab::ForeignKeys => Some(&mut self.foreign_key_table),
+            Tab::Indexes => Some(&mut self.index_table),
+            Tab::Sql => self.sql_editor.result_table(),
+            Tab::Definition => None,
+            Tab::Triggers => Some(&mut self.trigger_table),
+        }
+    }
+
+    pub async fn move_focus(&mut self, key: Key) -> anyhow::Result<EventState> {
         if key == self.config.key_config.focus_connections {
             self.focus = Focus::ConnectionList;
             return Ok(EventState::Consumed);
@@ -441,6 +4093,86 @@ impl App {
         if self.tab.event(key)?.is_consumed() {
             return Ok(EventState::Consumed);
         }
+        if key == self.config.key_config.recent_databases
+            && !matches!(self.focus, Focus::ConnectionList)
+        {
+            self.recent_databases.open()?;
+            return Ok(EventState::Consumed);
+        }
+        if key == self.config.key_config.find_table && !matches!(self.focus, Focus::ConnectionList)
+        {
+            self.table_finder.open(self.databases.table_paths())?;
+            return Ok(EventState::Consumed);
+        }
+        if key == self.config.key_config.run_file && !matches!(self.focus, Focus::ConnectionList) {
+            self.run_file.open()?;
+            return Ok(EventState::Consumed);
+        }
+        if key == self.config.key_config.open_queue && !matches!(self.focus, Focus::ConnectionList)
+        {
+            self.queue.show()?;
+            return Ok(EventState::Consumed);
+        }
+        if key == self.config.key_config.open_execution_log
+            && !matches!(self.focus, Focus::ConnectionList)
+        {
+            self.execution_log.open()?;
+            return Ok(EventState::Consumed);
+        }
+        if key == self.config.key_config.schema_snapshot
+            && !matches!(self.focus, Focus::ConnectionList)
+            && self.databases.tree().selected_database().is_some()
+        {
+            self.schema_snapshot.open()?;
+            return Ok(EventState::Consumed);
+        }
+        if key == self.config.key_config.open_erd
+            && !matches!(self.focus, Focus::ConnectionList)
+            && self.databases.tree().selected_database().is_some()
+        {
+            self.open_erd().await?;
+            return Ok(EventState::Consumed);
+        }
+        if key == self.config.key_config.import_csv
+            && !matches!(self.focus, Focus::ConnectionList)
+            && self.databases.tree().selected_table().is_some()
+        {
+            self.open_csv_import().await?;
+            return Ok(EventState::Consumed);
+        }
+        if key == self.config.key_config.dump
+            && matches!(self.focus, Focus::DabataseList)
+            && self.databases.tree().selected_database().is_some()
+        {
+            self.open_dump()?;
+            return Ok(EventState::Consumed);
+        }
+        if key == self.config.key_config.export_view
+            && matches!(self.focus, Focus::Table)
+            && self.selected_tab_table().is_some()
+        {
+            self.export_view.open()?;
+            return Ok(EventState::Consumed);
+        }
+        if key == self.config.key_config.search_matches && matches!(self.focus, Focus::Table) {
+            if let Some(table) = self.selected_tab_table() {
+                if let Some((pattern, matches)) = table.search_matches() {
+                    let (headers, current) = (table.headers.clone(), table.selected_cell());
+                    self.search_matches
+                        .open(pattern, &headers, matches, current)?;
+                    return Ok(EventState::Consumed);
+                }
+            }
+        }
+        if key == self.config.key_config.column_picker && matches!(self.focus, Focus::Table) {
+            if let Some(table) = self.selected_tab_table() {
+                let (headers, hidden) = (table.headers.clone(), table.hidden_columns().clone());
+                if !headers.is_empty() {
+                    self.column_picker.open(&headers, &hidden)?;
+                    return Ok(EventState::Consumed);
+                }
+            }
+        }
         match self.focus {
             Focus::ConnectionList => {
                 if key == self.config.key_config.enter {
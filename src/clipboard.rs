@@ -1,68 +1,193 @@
 use anyhow::{anyhow, Result};
-#[cfg(all(target_family = "unix", not(target_os = "macos")))]
-use std::ffi::OsStr;
+use serde::Deserialize;
 use std::io::Write;
 use std::process::{Command, Stdio};
 
+/// a way to put text on the clipboard
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClipboardBackend {
+    /// the OSC 52 escape sequence, which the terminal handles even over SSH
+    Osc52,
+    WlCopy,
+    Xclip,
+    Xsel,
+    Pbcopy,
+    Clip,
+}
+
+impl ClipboardBackend {
+    fn command(self) -> Option<Command> {
+        let (program, args): (&str, &[&str]) = match self {
+            Self::Osc52 => return None,
+            Self::WlCopy => ("wl-copy", &[]),
+            Self::Xclip => ("xclip", &["-selection", "clipboard"]),
+            Self::Xsel => ("xsel", &["--clipboard"]),
+            Self::Pbcopy => ("pbcopy", &[]),
+            Self::Clip => ("clip", &[]),
+        };
+        if !is_installed(program) {
+            return None;
+        }
+        let mut command = Command::new(program);
+        command.args(args);
+        Some(command)
+    }
+}
+
+/// the backends tried in order until one of them copies the text
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ClipboardConfig {
+    /// chosen by the platform and by whether gobang runs over SSH when empty
+    pub backends: Vec<ClipboardBackend>,
+}
+
+impl ClipboardConfig {
+    fn backends(&self) -> Vec<ClipboardBackend> {
+        if !self.backends.is_empty() {
+            return self.backends.clone();
+        }
+        // the commands would copy to the clipboard of the remote machine
+        if std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some() {
+            return vec![ClipboardBackend::Osc52];
+        }
+        let mut backends = if cfg!(target_os = "macos") {
+            vec![ClipboardBackend::Pbcopy]
+        } else if cfg!(windows) {
+            vec![ClipboardBackend::Clip]
+        } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            vec![
+                ClipboardBackend::WlCopy,
+                ClipboardBackend::Xclip,
+                ClipboardBackend::Xsel,
+            ]
+        } else {
+            vec![ClipboardBackend::Xclip, ClipboardBackend::Xsel]
+        };
+        backends.push(ClipboardBackend::Osc52);
+        backends
+    }
+}
+
+#[cfg(all(target_family = "unix", not(target_os = "macos")))]
+fn is_installed(program: &str) -> bool {
+    which::which(program).is_ok()
+}
+
+#[cfg(not(all(target_family = "unix", not(target_os = "macos"))))]
+fn is_installed(_program: &str) -> bool {
+    true
+}
+
 fn execute_copy_command(command: Command, text: &str) -> Result<()> {
     let mut command = command;
 
     let mut process = command
         .stdin(Stdio::piped())
         .stdout(Stdio::null())
+        .stderr(Stdio::null())
         .spawn()
         .map_err(|e| anyhow!("`{:?}`: {}", command, e))?;
 
     process
         .stdin
-        .as_mut()
+        .take()
         .ok_or_else(|| anyhow!("`{:?}`", command))?
         .write_all(text.as_bytes())
         .map_err(|e| anyhow!("`{:?}`: {}", command, e))?;
 
-    process
+    let status = process
         .wait()
         .map_err(|e| anyhow!("`{:?}`: {}", command, e))?;
+    if !status.success() {
+        return Err(anyhow!("`{:?}`: {}", command, status));
+    }
 
     Ok(())
 }
 
-#[cfg(all(target_family = "unix", not(target_os = "macos")))]
-fn gen_command(path: impl AsRef<OsStr>, xclip_syntax: bool) -> Command {
-    let mut c = Command::new(path);
-    if xclip_syntax {
-        c.arg("-selection");
-        c.arg("clipboard");
+/// builds the escape sequence asking the terminal to copy `text`, passed through to the outer terminal inside tmux
+fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    if tmux {
+        format!("\x1bPtmux;\x1b{}\x1b\\", sequence)
     } else {
-        c.arg("--clipboard");
+        sequence
     }
-    c
 }
 
-#[cfg(all(target_family = "unix", not(target_os = "macos")))]
-pub fn copy_to_clipboard(string: &str) -> Result<()> {
-    use std::path::PathBuf;
-    use which::which;
-    let (path, xclip_syntax) = which("xclip").ok().map_or_else(
-        || {
-            (
-                which("xsel").ok().unwrap_or_else(|| PathBuf::from("xsel")),
-                false,
-            )
-        },
-        |path| (path, true),
-    );
-
-    let cmd = gen_command(path, xclip_syntax);
-    execute_copy_command(cmd, string)
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
 }
 
-#[cfg(target_os = "macos")]
-pub fn copy_to_clipboard(string: &str) -> Result<()> {
-    execute_copy_command(Command::new("pbcopy"), string)
+pub fn copy_to_clipboard(string: &str, config: &ClipboardConfig) -> Result<()> {
+    let mut error = anyhow!("no clipboard backend is available");
+    for backend in config.backends() {
+        if backend == ClipboardBackend::Osc52 {
+            let mut stdout = std::io::stdout();
+            stdout
+                .write_all(osc52_sequence(string, std::env::var_os("TMUX").is_some()).as_bytes())?;
+            stdout.flush()?;
+            return Ok(());
+        }
+        if let Some(command) = backend.command() {
+            match execute_copy_command(command, string) {
+                Ok(()) => return Ok(()),
+                Err(e) => error = e,
+            }
+        }
+    }
+    Err(error)
 }
 
-#[cfg(windows)]
-pub fn copy_to_clipboard(string: &str) -> Result<()> {
-    execute_copy_command(Command::new("clip"), string)
+#[cfg(test)]
+mod test {
+    use super::{base64, osc52_sequence, ClipboardBackend, ClipboardConfig};
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64("foobar ü".as_bytes()), "Zm9vYmFyIMO8");
+    }
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("foo", false), "\x1b]52;c;Zm9v\x07");
+        assert_eq!(
+            osc52_sequence("foo", true),
+            "\x1bPtmux;\x1b\x1b]52;c;Zm9v\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_backends() {
+        let config: ClipboardConfig =
+            toml::from_str(r#"backends = ["osc52", "wl-copy", "xclip"]"#).unwrap();
+        assert_eq!(
+            config.backends(),
+            vec![
+                ClipboardBackend::Osc52,
+                ClipboardBackend::WlCopy,
+                ClipboardBackend::Xclip
+            ]
+        );
+    }
 }
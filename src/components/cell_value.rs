@@ -0,0 +1,449 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::components::utils::{binary::hex_dump, invisible::reveal_invisible, json::JsonView};
+use crate::config::KeyConfig;
+use crate::database::decode_binary;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// shows the whole value of a cell with its lines wrapped, pretty-printing JSON with its objects and arrays
+/// collapsible and dumping binary values in hex
+pub struct CellValueComponent {
+    column: String,
+    value: String,
+    json: Option<JsonView>,
+    /// the position of the selected line among the shown lines of a JSON value
+    cursor: usize,
+    /// the JSON value to copy once it was copied pretty or minified
+    copied: Option<String>,
+    /// the bytes of a binary value, which can be saved to a file
+    bytes: Option<Vec<u8>>,
+    /// the path being typed to save the bytes to
+    path: Option<String>,
+    /// the path the bytes were last saved to
+    saved: Option<String>,
+    scroll: u16,
+    /// the width the value was last wrapped at
+    width: u16,
+    /// the height the value was last drawn in
+    height: u16,
+    reveal_invisible: bool,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl CellValueComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            column: String::new(),
+            value: String::new(),
+            json: None,
+            cursor: 0,
+            copied: None,
+            bytes: None,
+            path: None,
+            saved: None,
+            scroll: 0,
+            width: 0,
+            height: 0,
+            reveal_invisible: false,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn set_reveal_invisible(&mut self, reveal_invisible: bool) {
+        self.reveal_invisible = reveal_invisible;
+    }
+
+    /// opens the value of `column`, which is parsed as any JSON value when the column is typed as JSON, and only
+    /// when it looks like an object or array otherwise, as SQLite stores JSON as text
+    pub fn open(&mut self, column: String, value: String, json_column: bool) -> Result<()> {
+        self.column = column;
+        self.bytes = decode_binary(value.as_str());
+        self.path = None;
+        self.saved = None;
+        self.copied = None;
+        self.json = None;
+        match self.bytes.as_ref() {
+            Some(bytes) => self.value = hex_dump(bytes),
+            None => {
+                self.json = JsonView::parse(value.as_str(), json_column);
+                self.value = value;
+            }
+        }
+        if self.reveal_invisible && self.bytes.is_none() {
+            self.value = reveal_invisible(self.value.as_str());
+        }
+        self.cursor = 0;
+        self.scroll = 0;
+        self.show()
+    }
+
+    /// returns the JSON value to copy once it was copied pretty or minified
+    pub fn take_copied(&mut self) -> Option<String> {
+        self.copied.take()
+    }
+
+    /// returns how many lines each shown line takes once wrapped at the last width
+    fn line_heights(&self) -> Vec<u16> {
+        let width = self.width.max(1) as usize;
+        let height = |line: &str| ((line.width() + width - 1) / width).max(1) as u16;
+        match self.json.as_ref() {
+            Some(json) => json
+                .visible_lines()
+                .into_iter()
+                .map(|index| height(json.text(index).as_str()))
+                .collect(),
+            None => self.value.lines().map(height).collect(),
+        }
+    }
+
+    /// returns how many lines the value takes once wrapped at the last width
+    fn line_count(&self) -> u16 {
+        self.line_heights().iter().sum()
+    }
+
+    fn scroll_down(&mut self, lines: u16) {
+        if let Some(json) = self.json.as_ref() {
+            self.cursor =
+                (self.cursor + lines as usize).min(json.visible_lines().len().saturating_sub(1));
+            return;
+        }
+        self.scroll = self
+            .scroll
+            .saturating_add(lines)
+            .min(self.line_count().saturating_sub(1));
+    }
+
+    fn scroll_up(&mut self, lines: u16) {
+        if self.json.is_some() {
+            self.cursor = self.cursor.saturating_sub(lines as usize);
+            return;
+        }
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// scrolls the least for the selected line of a JSON value to be shown whole
+    fn scroll_to_cursor(&mut self) {
+        let heights = self.line_heights();
+        let top = heights.iter().take(self.cursor).sum::<u16>();
+        let bottom = top + heights.get(self.cursor).copied().unwrap_or(1);
+        if top < self.scroll {
+            self.scroll = top;
+        } else if bottom > self.scroll + self.height {
+            self.scroll = bottom.saturating_sub(self.height);
+        }
+    }
+
+    /// collapses or expands the object or array opened at the selected line of a JSON value
+    fn toggle_fold(&mut self) {
+        if let Some(json) = self.json.as_mut() {
+            if let Some(index) = json.visible_lines().get(self.cursor).copied() {
+                json.toggle(index);
+            }
+        }
+    }
+
+    /// writes the bytes of a binary value to the path typed
+    fn save(&mut self) -> Result<()> {
+        let path = match self.path.take() {
+            Some(path) if !path.trim().is_empty() => path.trim().to_string(),
+            _ => return Ok(()),
+        };
+        if let Some(bytes) = self.bytes.as_ref() {
+            std::fs::write(path.as_str(), bytes).map_err(|e| anyhow::anyhow!("{}: {}", path, e))?;
+            self.saved = Some(path);
+        }
+        Ok(())
+    }
+
+    /// types the path to save the bytes to, returning whether a path is being typed
+    fn path_event(&mut self, key: Key) -> Result<bool> {
+        let path = match self.path.as_mut() {
+            Some(path) => path,
+            None => return Ok(false),
+        };
+        if key == self.key_config.exit_popup {
+            self.path = None;
+        } else if key == self.key_config.enter {
+            self.save()?;
+        } else {
+            match key {
+                Key::Char(c) => path.push(c),
+                Key::Backspace | Key::Delete => {
+                    path.pop();
+                }
+                _ => (),
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl DrawableComponent for CellValueComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let area = popup_area(
+            f,
+            f.size().width.saturating_sub(10),
+            f.size().height.saturating_sub(6),
+        );
+        self.width = area.width.saturating_sub(2);
+        self.height = area.height.saturating_sub(2);
+        let title = match (self.json.is_some(), self.bytes.as_ref()) {
+            (_, Some(bytes)) => format!(
+                "{}: {} (BLOB {} bytes)",
+                Msg::CellValue.text(),
+                self.column,
+                bytes.len()
+            ),
+            (true, None) => format!("{}: {} (JSON)", Msg::CellValue.text(), self.column),
+            (false, None) => format!("{}: {}", Msg::CellValue.text(), self.column),
+        };
+        if self.json.is_some() {
+            self.scroll_to_cursor();
+        }
+        let text = match self.json.as_ref() {
+            Some(json) => Text::from(
+                json.visible_lines()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(position, index)| {
+                        let mut spans = json.spans(index);
+                        if position == self.cursor {
+                            for span in spans.0.iter_mut() {
+                                span.style = span.style.patch(Theme::current().selected());
+                            }
+                        }
+                        spans
+                    })
+                    .collect::<Vec<Spans>>(),
+            ),
+            None => Text::raw(self.value.as_str()),
+        };
+        f.render_widget(
+            Paragraph::new(text)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Thick),
+                )
+                .wrap(Wrap { trim: false })
+                .scroll((self.scroll, 0)),
+            area,
+        );
+        if self.json.is_some() && area.height > 2 {
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    format!(
+                        " [Space] {} [{}] {} [{}] {} ",
+                        Msg::ToggleFold.text(),
+                        self.key_config.copy,
+                        Msg::CopyPretty.text(),
+                        self.key_config.copy_minified,
+                        Msg::CopyMinified.text()
+                    ),
+                    Theme::current().hint(),
+                )),
+                Rect::new(
+                    area.x + 1,
+                    area.y + area.height - 1,
+                    area.width.saturating_sub(2),
+                    1,
+                ),
+            );
+        }
+        if self.bytes.is_some() && area.height > 2 {
+            let line = Rect::new(
+                area.x + 1,
+                area.y + area.height - 1,
+                area.width.saturating_sub(2),
+                1,
+            );
+            let text = match (self.path.as_ref(), self.saved.as_ref()) {
+                (Some(path), _) => {
+                    let text = format!(" {}: {}", Msg::SaveToFile.text(), path);
+                    f.set_cursor(line.x + text.width() as u16, line.y);
+                    Span::raw(text)
+                }
+                (None, Some(saved)) => Span::styled(
+                    format!(" {} {} ", Msg::SavedTo.text(), saved),
+                    Theme::current().success(),
+                ),
+                (None, None) => Span::styled(
+                    format!(
+                        " [{}] {} ",
+                        self.key_config.save_cell_value,
+                        Msg::SaveToFile.text()
+                    ),
+                    Theme::current().hint(),
+                ),
+            };
+            f.render_widget(Paragraph::new(text), line);
+        }
+        Ok(())
+    }
+}
+
+impl Component for CellValueComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if self.path_event(key)? {
+            return Ok(EventState::Consumed);
+        }
+        if key == self.key_config.save_cell_value && self.bytes.is_some() {
+            self.path = Some(String::new());
+            self.saved = None;
+        } else if key == Key::Char(' ') {
+            self.toggle_fold();
+        } else if key == self.key_config.copy && self.json.is_some() {
+            self.copied = self.json.as_ref().map(JsonView::pretty);
+        } else if key == self.key_config.copy_minified && self.json.is_some() {
+            self.copied = self.json.as_ref().map(JsonView::minified);
+        } else if key == self.key_config.exit_popup || key == self.key_config.enter {
+            self.hide();
+        } else if key == self.key_config.scroll_down {
+            self.scroll_down(1);
+        } else if key == self.key_config.scroll_up {
+            self.scroll_up(1);
+        } else if key == self.key_config.scroll_down_multiple_lines {
+            self.scroll_down(10);
+        } else if key == self.key_config.scroll_up_multiple_lines {
+            self.scroll_up(10);
+        } else if key == self.key_config.scroll_to_top {
+            self.cursor = 0;
+            self.scroll = 0;
+        } else if key == self.key_config.scroll_to_bottom {
+            match self.json.as_ref() {
+                Some(json) => self.cursor = json.visible_lines().len().saturating_sub(1),
+                None => self.scroll = self.line_count().saturating_sub(1),
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CellValueComponent, Component as _, KeyConfig};
+    use crate::event::Key;
+
+    #[test]
+    fn test_json_value() {
+        let mut component = CellValueComponent::new(KeyConfig::default());
+        component
+            .open(
+                "doc".to_string(),
+                r#"{"b":1,"a":[true,null]}"#.to_string(),
+                false,
+            )
+            .unwrap();
+        component.event(KeyConfig::default().copy).unwrap();
+        assert_eq!(
+            component.take_copied(),
+            Some("{\n  \"b\": 1,\n  \"a\": [\n    true,\n    null\n  ]\n}".to_string())
+        );
+        component.width = 40;
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(Key::Char(' ')).unwrap();
+        assert_eq!(component.line_count(), 4);
+        component.event(KeyConfig::default().copy_minified).unwrap();
+        assert_eq!(
+            component.take_copied(),
+            Some(r#"{"b":1,"a":[true,null]}"#.to_string())
+        );
+
+        for (value, json_column, json) in [
+            ("[]", false, true),
+            ("{a,b}", true, false),
+            ("42", false, false),
+            ("42", true, true),
+            ("\"text\"", false, false),
+        ] {
+            component
+                .open("doc".to_string(), value.to_string(), json_column)
+                .unwrap();
+            assert_eq!(component.json.is_some(), json, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_line_count() {
+        let mut component = CellValueComponent::new(KeyConfig::default());
+        component
+            .open("note".to_string(), "abcdefghij\n\nabc".to_string(), false)
+            .unwrap();
+        component.width = 4;
+        assert_eq!(component.line_count(), 5);
+        component.scroll_down(10);
+        assert_eq!(component.scroll, 4);
+        component.scroll_up(1);
+        assert_eq!(component.scroll, 3);
+    }
+
+    #[test]
+    fn test_save_binary_value() {
+        let mut component = CellValueComponent::new(KeyConfig::default());
+        component
+            .open("avatar".to_string(), "\\x89504e47".to_string(), false)
+            .unwrap();
+        assert_eq!(
+            component.value,
+            "00000000  89 50 4e 47".to_string() + &" ".repeat(38) + ".PNG"
+        );
+
+        let path = std::env::temp_dir().join(format!("gobang-blob-{}.bin", std::process::id()));
+        component
+            .event(KeyConfig::default().save_cell_value)
+            .unwrap();
+        for c in path.to_string_lossy().chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(component.visible);
+        assert_eq!(std::fs::read(&path).unwrap(), vec![0x89, 0x50, 0x4e, 0x47]);
+        std::fs::remove_file(&path).unwrap();
+
+        // a text value has no bytes to save
+        component
+            .open("note".to_string(), "text".to_string(), false)
+            .unwrap();
+        component
+            .event(KeyConfig::default().save_cell_value)
+            .unwrap();
+        assert!(component.path.is_none());
+    }
+}
@@ -0,0 +1,297 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// the widest a column of the child rows is drawn, cutting longer values
+const MAX_COLUMN_WIDTH: usize = 24;
+
+/// the rows of a table referencing the selected record through one of its foreign keys
+pub struct ChildRows {
+    /// the table with the column referencing the record, such as `order_items.order_id = 5`
+    pub title: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// shows the rows referencing the selected record grouped by their table, each group expanded or collapsed
+pub struct ChildRowsComponent {
+    groups: Vec<(ChildRows, bool)>,
+    selected: usize,
+    scroll: u16,
+    /// the height the groups were last drawn in
+    height: u16,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ChildRowsComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            groups: vec![],
+            selected: 0,
+            scroll: 0,
+            height: 0,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens the groups with those which have rows expanded
+    pub fn open(&mut self, groups: Vec<ChildRows>) -> Result<()> {
+        self.groups = groups
+            .into_iter()
+            .map(|group| {
+                let expanded = !group.rows.is_empty();
+                (group, expanded)
+            })
+            .collect();
+        self.selected = 0;
+        self.scroll = 0;
+        self.show()
+    }
+
+    /// returns the lines of the groups with the index of the line of each group title
+    fn lines(&self) -> (Vec<Spans<'static>>, Vec<usize>) {
+        let mut lines = vec![];
+        let mut titles = vec![];
+        for (index, (group, expanded)) in self.groups.iter().enumerate() {
+            titles.push(lines.len());
+            let title = format!(
+                "{} {} ({} {})",
+                if *expanded { "▾" } else { "▸" },
+                group.title,
+                group.rows.len(),
+                Msg::Rows.text()
+            );
+            lines.push(Spans::from(if index == self.selected {
+                Span::styled(title, Theme::current().selected())
+            } else {
+                Span::styled(title, Theme::current().accent())
+            }));
+            if !*expanded || group.rows.is_empty() {
+                continue;
+            }
+            let widths = group
+                .headers
+                .iter()
+                .enumerate()
+                .map(|(column, header)| {
+                    group
+                        .rows
+                        .iter()
+                        .filter_map(|row| row.get(column))
+                        .chain(std::iter::once(header))
+                        .map(|value| value.lines().next().unwrap_or_default().width())
+                        .max()
+                        .unwrap_or(0)
+                        .min(MAX_COLUMN_WIDTH)
+                })
+                .collect::<Vec<usize>>();
+            lines.push(Spans::from(Span::styled(
+                format!("  {}", layout_row(&group.headers, &widths)),
+                Theme::current().header(),
+            )));
+            for row in &group.rows {
+                lines.push(Spans::from(format!("  {}", layout_row(row, &widths))));
+            }
+        }
+        (lines, titles)
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.groups.len().saturating_sub(1));
+        let (_, titles) = self.lines();
+        if let Some(title) = titles.get(self.selected).map(|line| *line as u16) {
+            if title < self.scroll || title >= self.scroll + self.height.max(1) {
+                self.scroll = title;
+            }
+        }
+    }
+
+    fn toggle_selected(&mut self) {
+        if let Some((_, expanded)) = self.groups.get_mut(self.selected) {
+            *expanded = !*expanded;
+        }
+    }
+
+    fn scroll_lines(&mut self, down: bool, lines: u16) {
+        let count = self.lines().0.len() as u16;
+        self.scroll = if down {
+            self.scroll
+                .saturating_add(lines)
+                .min(count.saturating_sub(1))
+        } else {
+            self.scroll.saturating_sub(lines)
+        };
+    }
+}
+
+/// lays out the first line of each value in its column, cutting those wider than it with `…`
+fn layout_row(values: &[String], widths: &[usize]) -> String {
+    values
+        .iter()
+        .zip(widths.iter())
+        .map(|(value, width)| {
+            let line = value.lines().next().unwrap_or_default();
+            let mut cell = String::new();
+            if line.width() <= *width {
+                cell.push_str(line);
+            } else {
+                for c in line.chars() {
+                    if cell.width() + c.width().unwrap_or(0) >= *width {
+                        break;
+                    }
+                    cell.push(c);
+                }
+                cell.push('…');
+            }
+            let padding = width.saturating_sub(cell.width());
+            cell + &" ".repeat(padding)
+        })
+        .collect::<Vec<String>>()
+        .join("  ")
+        .trim_end()
+        .to_string()
+}
+
+impl DrawableComponent for ChildRowsComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let area = popup_area(
+            f,
+            f.size().width.saturating_sub(10),
+            f.size().height.saturating_sub(6),
+        );
+        self.height = area.height.saturating_sub(2);
+        let (lines, _) = self.lines();
+        let lines = if lines.is_empty() {
+            vec![Spans::from(Span::styled(
+                Msg::NoChildRows.text(),
+                Theme::current().hint(),
+            ))]
+        } else {
+            lines
+        };
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(Msg::ChildRows.text())
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Thick),
+                )
+                .scroll((self.scroll, 0)),
+            area,
+        );
+        if area.height > 2 {
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    format!(" [Space] {} ", Msg::ToggleFold.text()),
+                    Theme::current().hint(),
+                )),
+                Rect::new(
+                    area.x + 1,
+                    area.y + area.height - 1,
+                    area.width.saturating_sub(2),
+                    1,
+                ),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for ChildRowsComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == Key::Char(' ') || key == self.key_config.enter {
+            self.toggle_selected();
+        } else if key == self.key_config.scroll_down || key == Key::Down {
+            self.select(self.selected + 1);
+        } else if key == self.key_config.scroll_up || key == Key::Up {
+            self.select(self.selected.saturating_sub(1));
+        } else if key == self.key_config.scroll_down_multiple_lines {
+            self.scroll_lines(true, 10);
+        } else if key == self.key_config.scroll_up_multiple_lines {
+            self.scroll_lines(false, 10);
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{layout_row, ChildRows, ChildRowsComponent, Component as _, KeyConfig};
+    use crate::event::Key;
+
+    fn fields(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_layout_row() {
+        assert_eq!(
+            layout_row(&fields(&["1", "Widget", "x"]), &[2, 4, 3]),
+            "1   Wid…  x"
+        );
+    }
+
+    #[test]
+    fn test_toggle_group() {
+        let mut component = ChildRowsComponent::new(KeyConfig::default());
+        component
+            .open(vec![
+                ChildRows {
+                    title: "order_items.order_id = 5".to_string(),
+                    headers: fields(&["id", "product"]),
+                    rows: vec![fields(&["1", "pen"]), fields(&["2", "ink"])],
+                },
+                ChildRows {
+                    title: "payments.order_id = 5".to_string(),
+                    headers: fields(&["id"]),
+                    rows: vec![],
+                },
+            ])
+            .unwrap();
+        assert_eq!(component.lines().1, vec![0, 4]);
+        component.event(Key::Char(' ')).unwrap();
+        assert_eq!(component.lines().1, vec![0, 1]);
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        assert_eq!(component.selected, 1);
+    }
+}
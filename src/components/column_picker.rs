@@ -0,0 +1,195 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use std::collections::HashSet;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::Span,
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// lists the headers of a table with whether each column is shown, to hide or show them again
+pub struct ColumnPickerComponent {
+    /// each header with whether its column is shown
+    columns: Vec<(String, bool)>,
+    state: ListState,
+    hidden: Option<HashSet<usize>>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ColumnPickerComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            columns: vec![],
+            state: ListState::default(),
+            hidden: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn open(&mut self, headers: &[String], hidden: &HashSet<usize>) -> Result<()> {
+        self.columns = headers
+            .iter()
+            .enumerate()
+            .map(|(column, header)| (header.clone(), !hidden.contains(&column)))
+            .collect();
+        self.state.select(Some(0));
+        self.hidden = None;
+        self.show()
+    }
+
+    /// returns the columns to hide once the user applied them
+    pub fn take_hidden(&mut self) -> Option<HashSet<usize>> {
+        self.hidden.take()
+    }
+
+    /// shows or hides the selected column, keeping at least one column shown
+    fn toggle_selected(&mut self) {
+        let shown = self.columns.iter().filter(|(_, shown)| *shown).count();
+        if let Some((_, checked)) = self.state.selected().and_then(|i| self.columns.get_mut(i)) {
+            if !*checked || shown > 1 {
+                *checked = !*checked;
+            }
+        }
+    }
+
+    fn select_next(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some((i + 1) % self.columns.len()));
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state
+                .select(Some((i + self.columns.len() - 1) % self.columns.len()));
+        }
+    }
+}
+
+impl DrawableComponent for ColumnPickerComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let items = self
+            .columns
+            .iter()
+            .map(|(header, shown)| {
+                ListItem::new(format!("[{}] {}", if *shown { "x" } else { " " }, header))
+            })
+            .collect::<Vec<ListItem>>();
+        let area = popup_area(f, 40, (self.columns.len() as u16).saturating_add(3).min(20));
+        f.render_stateful_widget(
+            List::new(items)
+                .block(
+                    Block::default()
+                        .title(Msg::ShowHideColumns.text())
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Thick),
+                )
+                .highlight_style(Theme::current().selected()),
+            area,
+            &mut self.state,
+        );
+        if area.height > 2 {
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    format!(
+                        " [Space] {} [{}] {} ",
+                        Msg::ToggleColumn.text(),
+                        self.key_config.enter,
+                        Msg::ApplyColumns.text()
+                    ),
+                    Theme::current().hint(),
+                )),
+                Rect::new(
+                    area.x + 1,
+                    area.y + area.height - 1,
+                    area.width.saturating_sub(2),
+                    1,
+                ),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for ColumnPickerComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            self.hidden = Some(
+                self.columns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, shown))| !*shown)
+                    .map(|(column, _)| column)
+                    .collect(),
+            );
+            self.hide();
+        } else if key == Key::Char(' ') {
+            self.toggle_selected();
+        } else if key == self.key_config.scroll_down || key == Key::Down {
+            self.select_next();
+        } else if key == self.key_config.scroll_up || key == Key::Up {
+            self.select_previous();
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ColumnPickerComponent, Component as _, KeyConfig};
+    use crate::event::Key;
+
+    #[test]
+    fn test_take_hidden() {
+        let mut component = ColumnPickerComponent::new(KeyConfig::default());
+        let headers = vec!["id".to_string(), "payload".to_string()];
+        component
+            .open(&headers, &vec![0].into_iter().collect())
+            .unwrap();
+        // the last shown column cannot be hidden
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(Key::Char(' ')).unwrap();
+        assert!(component.columns[1].1);
+        component.event(KeyConfig::default().scroll_up).unwrap();
+        component.event(Key::Char(' ')).unwrap();
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(Key::Char(' ')).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(!component.visible);
+        assert_eq!(component.take_hidden(), Some(vec![1].into_iter().collect()));
+        assert!(component.take_hidden().is_none());
+    }
+}
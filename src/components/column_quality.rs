@@ -0,0 +1,218 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::database::VALUE_FORMATS;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// shows the data quality checks of a column once the query running them in the background ends
+pub struct ColumnQualityComponent {
+    column: String,
+    values: Option<Vec<String>>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ColumnQualityComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            column: String::new(),
+            values: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens the popup for `column` while its checks run
+    pub fn open(&mut self, column: String) -> Result<()> {
+        self.column = column;
+        self.values = None;
+        self.show()
+    }
+
+    /// shows the values of the checks in the order `quality_statement` selects them
+    pub fn set_values(&mut self, values: Vec<String>) {
+        self.values = Some(values);
+    }
+
+    fn lines(&self) -> Vec<Spans<'static>> {
+        let values = match &self.values {
+            Some(values) => values,
+            None => {
+                return vec![Spans::from(Span::styled(
+                    format!("{}…", Msg::RunningQuery.text()),
+                    Theme::current().hint(),
+                ))]
+            }
+        };
+        let report = report(values);
+        let width = report
+            .iter()
+            .map(|(label, _)| label.width())
+            .max()
+            .unwrap_or(0);
+        report
+            .into_iter()
+            .map(|(label, value)| {
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{:w$}  ", label, w = width),
+                        Theme::current().accent(),
+                    ),
+                    Span::raw(value),
+                ])
+            })
+            .collect()
+    }
+}
+
+/// parses a count, which is NULL for a check the server cannot run, or a decimal for the sums of MySQL
+fn count(values: &[String], index: usize) -> Option<u64> {
+    values
+        .get(index)
+        .and_then(|value| value.parse::<f64>().ok())
+        .map(|value| value as u64)
+}
+
+/// writes `count` with the share of `total` it makes
+fn share(count: Option<u64>, total: u64) -> String {
+    match count {
+        Some(count) if total > 0 => {
+            format!("{} ({:.1}%)", count, count as f64 * 100.0 / total as f64)
+        }
+        Some(count) => count.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+/// lays out the checks as labels with their values, where NULL values are shares of the rows, duplicates of the
+/// non-null values and formats of the non-empty values
+fn report(values: &[String]) -> Vec<(String, String)> {
+    let rows = count(values, 0).unwrap_or(0);
+    let non_null = count(values, 1).unwrap_or(0);
+    let empty = count(values, 2);
+    let distinct = count(values, 3).unwrap_or(0);
+    let filled = non_null.saturating_sub(empty.unwrap_or(0));
+    let length = match (count(values, 4), count(values, 5)) {
+        (Some(min), Some(max)) => format!("{}–{}", min, max),
+        _ => "-".to_string(),
+    };
+    let mut report = vec![
+        (Msg::StatsRows.text().to_string(), rows.to_string()),
+        (
+            Msg::QualityNull.text().to_string(),
+            share(Some(rows.saturating_sub(non_null)), rows),
+        ),
+        (Msg::QualityEmpty.text().to_string(), share(empty, rows)),
+        (
+            Msg::QualityDuplicates.text().to_string(),
+            share(Some(non_null.saturating_sub(distinct)), non_null),
+        ),
+        (Msg::QualityLength.text().to_string(), length),
+    ];
+    for (index, (name, _)) in VALUE_FORMATS.iter().enumerate() {
+        report.push((
+            format!("{} {}", Msg::QualityMatches.text(), name),
+            share(count(values, 6 + index), filled),
+        ));
+    }
+    report
+}
+
+impl DrawableComponent for ColumnQualityComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let mut text = self.lines();
+        text.push(Spans::from(""));
+        text.push(Spans::from(Span::styled(
+            format!("[{}] {}", self.key_config.exit_popup, Msg::Cancel.text()),
+            Theme::current().hint(),
+        )));
+        let area = popup_area(f, 60, text.len() as u16 + 2);
+        f.render_widget(
+            Paragraph::new(text).block(
+                Block::default()
+                    .title(format!("{}: {}", Msg::ColumnQuality.text(), self.column))
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick),
+            ),
+            area,
+        );
+        Ok(())
+    }
+}
+
+impl Component for ColumnQualityComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::report;
+
+    #[test]
+    fn test_report() {
+        let values = vec!["200", "190", "10", "171", "3", "40", "178.0", "NULL"]
+            .into_iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            report(&values)
+                .into_iter()
+                .map(|(_, value)| value)
+                .collect::<Vec<String>>(),
+            vec![
+                "200",
+                "10 (5.0%)",
+                "10 (5.0%)",
+                "19 (10.0%)",
+                "3–40",
+                "178 (98.9%)",
+                "-"
+            ]
+        );
+
+        let values = vec!["0", "0", "NULL", "0", "NULL", "NULL", "NULL", "NULL"]
+            .into_iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(report(&values)[1].1, "0");
+        assert_eq!(report(&values)[4].1, "-");
+    }
+}
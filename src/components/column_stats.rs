@@ -0,0 +1,149 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+/// shows the statistics of a column once the query computing them in the background ends
+pub struct ColumnStatsComponent {
+    column: String,
+    values: Option<Vec<String>>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ColumnStatsComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            column: String::new(),
+            values: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens the popup for `column` while its statistics are computed
+    pub fn open(&mut self, column: String) -> Result<()> {
+        self.column = column;
+        self.values = None;
+        self.show()
+    }
+
+    /// shows the values of the statistics in the order `column_stats_statement` selects them
+    pub fn set_values(&mut self, values: Vec<String>) {
+        self.values = Some(values);
+    }
+
+    fn lines(&self) -> Vec<Spans<'static>> {
+        let values = match &self.values {
+            Some(values) => values,
+            None => {
+                return vec![Spans::from(Span::styled(
+                    format!("{}…", Msg::RunningQuery.text()),
+                    Theme::current().hint(),
+                ))]
+            }
+        };
+        [
+            Msg::StatsRows,
+            Msg::StatsNonNull,
+            Msg::StatsDistinct,
+            Msg::StatsMin,
+            Msg::StatsMax,
+            Msg::StatsAverage,
+        ]
+        .iter()
+        .zip(values)
+        .map(|(label, value)| {
+            Spans::from(vec![
+                Span::styled(format!("{:9}", label.text()), Theme::current().accent()),
+                Span::raw(value.clone()),
+            ])
+        })
+        .collect()
+    }
+}
+
+impl DrawableComponent for ColumnStatsComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let mut text = self.lines();
+        text.push(Spans::from(""));
+        text.push(Spans::from(Span::styled(
+            format!("[{}] {}", self.key_config.exit_popup, Msg::Cancel.text()),
+            Theme::current().hint(),
+        )));
+        let area = popup_area(f, 60, text.len() as u16 + 2);
+        f.render_widget(
+            Paragraph::new(text).block(
+                Block::default()
+                    .title(format!("{}: {}", Msg::ColumnStats.text(), self.column))
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick),
+            ),
+            area,
+        );
+        Ok(())
+    }
+}
+
+impl Component for ColumnStatsComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ColumnStatsComponent, KeyConfig};
+
+    #[test]
+    fn test_lines() {
+        let mut component = ColumnStatsComponent::new(KeyConfig::default());
+        component.open("name".to_string()).unwrap();
+        assert_eq!(component.lines().len(), 1);
+
+        component.set_values(
+            vec!["10", "9", "7", "Alice", "Zoe"]
+                .into_iter()
+                .map(|value| value.to_string())
+                .collect(),
+        );
+        let lines = component.lines();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[3].0[1].content, "Alice");
+    }
+}
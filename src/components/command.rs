@@ -1,8 +1,5 @@
 use crate::config::KeyConfig;
-
-static CMD_GROUP_GENERAL: &str = "-- General --";
-static CMD_GROUP_TABLE: &str = "-- Table --";
-static CMD_GROUP_DATABASES: &str = "-- Databases --";
+use crate::i18n::Msg;
 
 #[derive(Clone, PartialEq, PartialOrd, Ord, Eq)]
 pub struct CommandText {
@@ -34,110 +31,637 @@ impl CommandInfo {
 pub fn scroll(key: &KeyConfig) -> CommandText {
     CommandText::new(
         format!(
-            "Scroll up/down/left/right [{},{},{},{}]",
-            key.scroll_up, key.scroll_down, key.scroll_left, key.scroll_right
+            "{} [{},{},{},{}]",
+            Msg::Scroll.text(),
+            key.scroll_up,
+            key.scroll_down,
+            key.scroll_left,
+            key.scroll_right
         ),
-        CMD_GROUP_GENERAL,
+        Msg::GroupGeneral.text(),
     )
 }
 
 pub fn scroll_up_down_multiple_lines(key: &KeyConfig) -> CommandText {
     CommandText::new(
         format!(
-            "Scroll up/down multiple lines [{},{}]",
-            key.scroll_up_multiple_lines, key.scroll_down_multiple_lines,
+            "{} [{},{}]",
+            Msg::ScrollMultipleLines.text(),
+            key.scroll_up_multiple_lines,
+            key.scroll_down_multiple_lines,
         ),
-        CMD_GROUP_GENERAL,
+        Msg::GroupGeneral.text(),
     )
 }
 
 pub fn scroll_to_top_bottom(key: &KeyConfig) -> CommandText {
     CommandText::new(
         format!(
-            "Scroll to top/bottom [{},{}]",
-            key.scroll_to_top, key.scroll_to_bottom,
+            "{} [{},{}]",
+            Msg::ScrollToTopBottom.text(),
+            key.scroll_to_top,
+            key.scroll_to_bottom,
         ),
-        CMD_GROUP_GENERAL,
+        Msg::GroupGeneral.text(),
+    )
+}
+
+pub fn recent_databases(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::RecentDatabases.text(), key.recent_databases),
+        Msg::GroupDatabases.text(),
+    )
+}
+
+pub fn find_table(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::FindTable.text(), key.find_table),
+        Msg::GroupDatabases.text(),
     )
 }
 
 pub fn expand_collapse(key: &KeyConfig) -> CommandText {
     CommandText::new(
-        format!("Expand/Collapse [{},{}]", key.scroll_right, key.scroll_left,),
-        CMD_GROUP_DATABASES,
+        format!(
+            "{} [{},{}]",
+            Msg::ExpandCollapse.text(),
+            key.scroll_right,
+            key.scroll_left,
+        ),
+        Msg::GroupDatabases.text(),
     )
 }
 
 pub fn filter(key: &KeyConfig) -> CommandText {
-    CommandText::new(format!("Filter [{}]", key.filter), CMD_GROUP_GENERAL)
+    CommandText::new(
+        format!("{} [{}]", Msg::Filter.text(), key.filter),
+        Msg::GroupGeneral.text(),
+    )
 }
 
 pub fn move_focus(key: &KeyConfig) -> CommandText {
     CommandText::new(
         format!(
-            "Move focus to left/right [{},{}]",
-            key.focus_left, key.focus_right
+            "{} [{},{}]",
+            Msg::MoveFocus.text(),
+            key.focus_left,
+            key.focus_right
         ),
-        CMD_GROUP_GENERAL,
+        Msg::GroupGeneral.text(),
     )
 }
 
 pub fn extend_selection_by_one_cell(key: &KeyConfig) -> CommandText {
     CommandText::new(
         format!(
-            "Extend selection by one cell up/down/left/right [{},{},{},{}]",
+            "{} [{},{},{},{}]",
+            Msg::ExtendSelection.text(),
             key.extend_selection_by_one_cell_up,
             key.extend_selection_by_one_cell_down,
             key.extend_selection_by_one_cell_left,
             key.extend_selection_by_one_cell_right
         ),
-        CMD_GROUP_TABLE,
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn recalculate_widths(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "{} [{}]",
+            Msg::RecalculateWidths.text(),
+            key.recalculate_widths
+        ),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn toggle_wrap(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::ToggleWrap.text(), key.toggle_wrap),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn scroll_value(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "{} [{},{}]",
+            Msg::ScrollValue.text(),
+            key.scroll_value_left,
+            key.scroll_value_right
+        ),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn search_in_table(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::SearchInTable.text(), key.search_in_table),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn go_to(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::GoTo.text(), key.go_to),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn jump_to_match(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "{} [{},{}]",
+            Msg::JumpToMatch.text(),
+            key.next_match,
+            key.previous_match
+        ),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn search_matches(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::SearchMatches.text(), key.search_matches),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn pin_columns(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::PinColumns.text(), key.pin_columns),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn show_hide_columns(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::ShowHideColumns.text(), key.column_picker),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn freeze_rows(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::FreezeRows.text(), key.freeze_rows),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn pin_row(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::PinRow.text(), key.pin_row),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn open_cell_value(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::OpenCellValue.text(), key.enter),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn open_record_detail(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "{} [{}]",
+            Msg::OpenRecordDetail.text(),
+            key.open_record_detail
+        ),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn generate_fake_data(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "{} [{}]",
+            Msg::GenerateFakeData.text(),
+            key.generate_fake_data
+        ),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn insert_row(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::InsertRow.text(), key.insert_row),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn column_quality(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::ColumnQuality.text(), key.column_quality),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn delete_template(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::DeleteTemplate.text(), key.delete_template),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn update_template(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::UpdateTemplate.text(), key.update_template),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn bulk_edit(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::BulkEdit.text(), key.bulk_edit),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn fill_down(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::FillDown.text(), key.fill_down),
+        Msg::BulkEdit.text(),
+    )
+}
+
+pub fn set_null(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::SetNull.text(), key.set_null),
+        Msg::BulkEdit.text(),
+    )
+}
+
+pub fn increment_cells(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::IncrementCells.text(), key.increment_cells),
+        Msg::BulkEdit.text(),
+    )
+}
+
+pub fn delete_rows(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::DeleteRows.text(), key.delete_rows),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn undo_change(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::UndoChange.text(), key.undo_change),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn column_stats(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::ColumnStats.text(), key.column_stats),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn copy_all_records(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::CopyAllRecords.text(), key.copy_all_records),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn copy_as_insert(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::CopyAsInsert.text(), key.copy_as_insert),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn toggle_watch(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::ToggleWatch.text(), key.toggle_watch),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn open_child_rows(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::OpenChildRows.text(), key.open_child_rows),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn follow_foreign_key(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "{} [{}]",
+            Msg::FollowForeignKey.text(),
+            key.follow_foreign_key
+        ),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn pipe_to_command(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::PipeToCommand.text(), key.pipe_to_command),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn execute_query(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::ExecuteQuery.text(), key.enter),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn execute_other_fetch(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "{} [{}]",
+            Msg::ExecuteOtherFetch.text(),
+            key.execute_other_fetch
+        ),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn end_transaction(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "{} [{},{}]",
+            Msg::EndTransaction.text(),
+            key.commit_transaction,
+            key.rollback_transaction
+        ),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn focus_sql_editor(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::FocusSqlEditor.text(), key.focus_sql_editor),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn switch_result_tab(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "{} [{},{}]",
+            Msg::SwitchResultTab.text(),
+            key.previous_result_tab,
+            key.next_result_tab
+        ),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn search_history(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::SearchHistory.text(), key.search_history),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn explain_query(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::ExplainQuery.text(), key.explain_query),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn format_query(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::FormatQuery.text(), key.format_query),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn save_snippet(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::SaveSnippet.text(), key.save_snippet),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn open_snippets(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::InsertSnippet.text(), key.open_snippets),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn explain_analyze_query(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "{} [{}]",
+            Msg::ExplainAnalyzeQuery.text(),
+            key.explain_analyze_query
+        ),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn run_file(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::RunFile.text(), key.run_file),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn open_referenced_table(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "{} [{}]",
+            Msg::OpenReferencedTable.text(),
+            key.open_referenced_table
+        ),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn queue_query(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::QueueQuery.text(), key.queue_query),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn queue_analyze(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::QueueAnalyze.text(), key.queue_analyze),
+        Msg::GroupDatabases.text(),
+    )
+}
+
+pub fn execute_routine(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::ExecuteRoutine.text(), key.execute_routine),
+        Msg::GroupDatabases.text(),
+    )
+}
+
+pub fn open_queue(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::OpenQueue.text(), key.open_queue),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn open_execution_log(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "{} [{}]",
+            Msg::OpenExecutionLog.text(),
+            key.open_execution_log
+        ),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn export_view(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::ExportView.text(), key.export_view),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn run_with_settings(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "{} [{}]",
+            Msg::RunWithSettings.text(),
+            key.run_with_settings
+        ),
+        Msg::GroupSql.text(),
+    )
+}
+
+pub fn scheduled_jobs(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::OpenScheduledJobs.text(), key.scheduled_jobs),
+        Msg::GroupDatabases.text(),
+    )
+}
+
+pub fn schema_snapshot(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::SchemaSnapshot.text(), key.schema_snapshot),
+        Msg::GroupDatabases.text(),
+    )
+}
+
+pub fn open_erd(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::Erd.text(), key.open_erd),
+        Msg::GroupDatabases.text(),
+    )
+}
+
+pub fn import_csv(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::ImportCsv.text(), key.import_csv),
+        Msg::GroupDatabases.text(),
+    )
+}
+
+pub fn dump(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::Dump.text(), key.dump),
+        Msg::GroupDatabases.text(),
+    )
+}
+
+pub fn cancel_query(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!(
+            "{} [{},{}]",
+            Msg::CancelQuery.text(),
+            key.exit_popup,
+            key.exit
+        ),
+        Msg::GroupSql.text(),
     )
 }
 
 pub fn tab_records(key: &KeyConfig) -> CommandText {
-    CommandText::new(format!("Records [{}]", key.tab_records), CMD_GROUP_TABLE)
+    CommandText::new(
+        format!("{} [{}]", Msg::Records.text(), key.tab_records),
+        Msg::GroupTable.text(),
+    )
 }
 
 pub fn tab_columns(key: &KeyConfig) -> CommandText {
-    CommandText::new(format!("Columns [{}]", key.tab_columns), CMD_GROUP_TABLE)
+    CommandText::new(
+        format!("{} [{}]", Msg::Columns.text(), key.tab_columns),
+        Msg::GroupTable.text(),
+    )
 }
 
 pub fn tab_constraints(key: &KeyConfig) -> CommandText {
     CommandText::new(
-        format!("Constraints [{}]", key.tab_constraints),
-        CMD_GROUP_TABLE,
+        format!("{} [{}]", Msg::Constraints.text(), key.tab_constraints),
+        Msg::GroupTable.text(),
     )
 }
 
 pub fn tab_foreign_keys(key: &KeyConfig) -> CommandText {
     CommandText::new(
-        format!("Foreign keys [{}]", key.tab_foreign_keys),
-        CMD_GROUP_TABLE,
+        format!("{} [{}]", Msg::ForeignKeys.text(), key.tab_foreign_keys),
+        Msg::GroupTable.text(),
     )
 }
 
 pub fn tab_indexes(key: &KeyConfig) -> CommandText {
-    CommandText::new(format!("Indexes [{}]", key.tab_indexes), CMD_GROUP_TABLE)
+    CommandText::new(
+        format!("{} [{}]", Msg::Indexes.text(), key.tab_indexes),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn tab_sql(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::Sql.text(), key.tab_sql),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn tab_definition(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::Definition.text(), key.tab_definition),
+        Msg::GroupTable.text(),
+    )
+}
+
+pub fn tab_triggers(key: &KeyConfig) -> CommandText {
+    CommandText::new(
+        format!("{} [{}]", Msg::Triggers.text(), key.tab_triggers),
+        Msg::GroupTable.text(),
+    )
 }
 
 pub fn toggle_tabs(key_config: &KeyConfig) -> CommandText {
     CommandText::new(
         format!(
-            "Tab [{},{},{},{},{}]",
+            "{} [{},{},{},{},{},{},{},{}]",
+            Msg::Tab.text(),
             key_config.tab_records,
             key_config.tab_columns,
             key_config.tab_constraints,
             key_config.tab_foreign_keys,
-            key_config.tab_indexes
+            key_config.tab_indexes,
+            key_config.tab_sql,
+            key_config.tab_definition,
+            key_config.tab_triggers
         ),
-        CMD_GROUP_GENERAL,
+        Msg::GroupGeneral.text(),
     )
 }
 
 pub fn help(key_config: &KeyConfig) -> CommandText {
     CommandText::new(
-        format!("Help [{}]", key_config.open_help),
-        CMD_GROUP_GENERAL,
+        format!("{} [{}]", Msg::Help.text(), key_config.open_help),
+        Msg::GroupGeneral.text(),
     )
 }
@@ -0,0 +1,157 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+/// shows a statement which changes data and runs it only once the user confirmed it
+pub struct ConfirmStatementComponent {
+    title: String,
+    statement: String,
+    action: &'static str,
+    confirmed: Option<String>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ConfirmStatementComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            title: String::new(),
+            statement: String::new(),
+            action: Msg::Run.text(),
+            confirmed: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn open(&mut self, title: String, statement: String) -> Result<()> {
+        self.title = title;
+        self.statement = statement;
+        self.action = Msg::Run.text();
+        self.confirmed = None;
+        self.show()
+    }
+
+    /// opens the statement again after it failed because another transaction locked its rows
+    pub fn open_retry(&mut self, statement: String) -> Result<()> {
+        self.open(Msg::RowsLocked.text().to_string(), statement)?;
+        self.action = Msg::Retry.text();
+        Ok(())
+    }
+
+    /// returns the statement once the user confirmed it
+    pub fn take_statement(&mut self) -> Option<String> {
+        self.confirmed.take()
+    }
+}
+
+impl DrawableComponent for ConfirmStatementComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let text = vec![
+            Spans::from(Span::raw(self.statement.as_str())),
+            Spans::from(""),
+            Spans::from(Span::styled(
+                format!(
+                    "[{}] {}  [{}] {}",
+                    self.key_config.enter,
+                    self.action,
+                    self.key_config.exit_popup,
+                    Msg::Cancel.text()
+                ),
+                Theme::current().hint(),
+            )),
+        ];
+        let area = popup_area(f, 80, 10);
+        f.render_widget(
+            Paragraph::new(text)
+                .block(
+                    Block::default()
+                        .title(Span::styled(self.title.as_str(), Theme::current().error()))
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Thick),
+                )
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+        Ok(())
+    }
+}
+
+impl Component for ConfirmStatementComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.enter {
+            self.confirmed = Some(std::mem::take(&mut self.statement));
+            self.hide();
+        } else if key == self.key_config.exit_popup {
+            self.hide();
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, ConfirmStatementComponent, KeyConfig, Msg};
+
+    #[test]
+    fn test_take_statement() {
+        let mut component = ConfirmStatementComponent::new(KeyConfig::default());
+        component
+            .open("Delete".to_string(), "DELETE FROM t".to_string())
+            .unwrap();
+        component.event(KeyConfig::default().exit_popup).unwrap();
+        assert!(!component.visible);
+        assert_eq!(component.take_statement(), None);
+
+        component
+            .open("Delete".to_string(), "DELETE FROM t".to_string())
+            .unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(
+            component.take_statement(),
+            Some("DELETE FROM t".to_string())
+        );
+        assert_eq!(component.take_statement(), None);
+
+        component.open_retry("DELETE FROM t".to_string()).unwrap();
+        assert_eq!(component.action, Msg::Retry.text());
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(
+            component.take_statement(),
+            Some("DELETE FROM t".to_string())
+        );
+    }
+}
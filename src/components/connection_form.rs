@@ -0,0 +1,270 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::{Connection, KeyConfig, SslMode, CONNECTION_FIELDS};
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+const PASSWORD_FIELD: usize = 4;
+
+pub struct ConnectionFormComponent {
+    values: Vec<String>,
+    selected: usize,
+    index: Option<usize>,
+    settings: BTreeMap<String, String>,
+    lock_rows: bool,
+    read_only: bool,
+    group: Option<String>,
+    reveal_invisible: bool,
+    keyring: bool,
+    ssl_mode: Option<SslMode>,
+    ssl_ca: Option<PathBuf>,
+    saved: Option<(Option<usize>, Connection)>,
+    error: Option<String>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ConnectionFormComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            values: vec![String::new(); CONNECTION_FIELDS.len()],
+            selected: 0,
+            index: None,
+            settings: BTreeMap::new(),
+            lock_rows: false,
+            read_only: false,
+            group: None,
+            reveal_invisible: false,
+            keyring: false,
+            ssl_mode: None,
+            ssl_ca: None,
+            saved: None,
+            error: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens the form to edit the connection at `index`, or to add a new one
+    pub fn open(&mut self, connection: Option<(usize, &Connection)>) -> Result<()> {
+        match connection {
+            Some((index, connection)) => {
+                self.values = connection.fields();
+                self.index = Some(index);
+                self.settings = connection.settings.clone();
+                self.lock_rows = connection.lock_rows;
+                self.read_only = connection.read_only;
+                self.group = connection.group.clone();
+                self.reveal_invisible = connection.reveal_invisible;
+                self.keyring = connection.keyring;
+                self.ssl_mode = connection.ssl_mode;
+                self.ssl_ca = connection.ssl_ca.clone();
+            }
+            None => {
+                self.values = vec![String::new(); CONNECTION_FIELDS.len()];
+                self.values[0] = "mysql".to_string();
+                self.index = None;
+                self.settings = BTreeMap::new();
+                self.lock_rows = false;
+                self.read_only = false;
+                self.group = None;
+                self.reveal_invisible = false;
+                self.keyring = false;
+                self.ssl_mode = None;
+                self.ssl_ca = None;
+            }
+        }
+        self.selected = 0;
+        self.saved = None;
+        self.error = None;
+        self.show()
+    }
+
+    /// returns the validated connection and the index it replaces once the user saved the form
+    pub fn take_connection(&mut self) -> Option<(Option<usize>, Connection)> {
+        self.saved.take()
+    }
+
+    fn save(&mut self) {
+        match Connection::from_fields(&self.values) {
+            Ok(mut connection) => {
+                connection.settings = self.settings.clone();
+                connection.lock_rows = self.lock_rows;
+                connection.read_only = self.read_only;
+                connection.group = self.group.clone();
+                connection.reveal_invisible = self.reveal_invisible;
+                connection.keyring = self.keyring;
+                connection.ssl_mode = self.ssl_mode;
+                connection.ssl_ca = self.ssl_ca.clone();
+                self.saved = Some((self.index, connection));
+                self.hide();
+            }
+            Err(err) => self.error = Some(err.to_string()),
+        }
+    }
+
+    fn get_text(&self) -> Vec<Spans<'static>> {
+        let label_width = CONNECTION_FIELDS
+            .iter()
+            .map(|label| label.width())
+            .max()
+            .unwrap_or(0);
+        let mut txt = CONNECTION_FIELDS
+            .iter()
+            .zip(self.values.iter())
+            .enumerate()
+            .map(|(i, (label, value))| {
+                let style = if i == self.selected {
+                    Style::default().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default()
+                };
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{:w$}", label, w = label_width),
+                        Theme::current().accent(),
+                    ),
+                    Span::raw(": "),
+                    Span::styled(
+                        if i == PASSWORD_FIELD {
+                            "*".repeat(value.chars().count())
+                        } else {
+                            value.to_string()
+                        },
+                        style,
+                    ),
+                    Span::styled(" ", style),
+                ])
+            })
+            .collect::<Vec<Spans>>();
+        txt.push(Spans::from(Span::raw("")));
+        if let Some(error) = self.error.as_ref() {
+            txt.push(Spans::from(Span::styled(
+                error.to_string(),
+                Theme::current().error(),
+            )));
+        }
+        txt.push(Spans::from(Span::styled(
+            format!(
+                "[{}] {}  [{}] {}",
+                self.key_config.enter,
+                Msg::Save.text(),
+                self.key_config.exit_popup,
+                Msg::Cancel.text()
+            ),
+            Theme::current().hint(),
+        )));
+        txt
+    }
+}
+
+impl DrawableComponent for ConnectionFormComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            const SIZE: (u16, u16) = (60, 13);
+            let area = popup_area(f, SIZE.0, SIZE.1);
+            f.render_widget(
+                Paragraph::new(self.get_text()).block(
+                    Block::default()
+                        .title(if self.index.is_some() {
+                            Msg::EditConnection.text()
+                        } else {
+                            Msg::AddConnection.text()
+                        })
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Thick),
+                ),
+                area,
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for ConnectionFormComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            self.save();
+        } else {
+            match key {
+                Key::Tab | Key::Down => {
+                    self.selected = (self.selected + 1) % CONNECTION_FIELDS.len()
+                }
+                Key::Up => {
+                    self.selected =
+                        (self.selected + CONNECTION_FIELDS.len() - 1) % CONNECTION_FIELDS.len()
+                }
+                Key::Char(c) => self.values[self.selected].push(c),
+                Key::Backspace | Key::Delete => {
+                    self.values[self.selected].pop();
+                }
+                _ => (),
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, ConnectionFormComponent, KeyConfig};
+    use crate::event::Key;
+
+    #[test]
+    fn test_save() {
+        let mut form = ConnectionFormComponent::new(KeyConfig::default());
+        form.open(None).unwrap();
+        form.event(KeyConfig::default().enter).unwrap();
+        assert!(form.error.is_some());
+        assert!(form.take_connection().is_none());
+
+        for (field, value) in [(1, "root"), (2, "localhost"), (3, "3306")].iter() {
+            form.selected = *field;
+            for c in value.chars() {
+                form.event(Key::Char(c)).unwrap();
+            }
+        }
+        form.event(KeyConfig::default().enter).unwrap();
+        let (index, connection) = form.take_connection().unwrap();
+        assert_eq!(index, None);
+        assert_eq!(
+            connection.database_url().unwrap(),
+            "mysql://root:@localhost:3306"
+        );
+        assert!(!form.visible);
+    }
+}
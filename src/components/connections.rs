@@ -1,20 +1,24 @@
-use super::{Component, DrawableComponent, EventState};
+use super::{popup_area, Component, DrawableComponent, EventState};
 use crate::components::command::CommandInfo;
 use crate::config::{Connection, KeyConfig};
 use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
 use anyhow::Result;
 use tui::{
     backend::Backend,
     layout::Rect,
-    style::{Color, Style},
+    style::Style,
     text::{Span, Spans},
-    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
 pub struct ConnectionsComponent {
     connections: Vec<Connection>,
     state: ListState,
+    delete_confirming: bool,
+    status: Option<String>,
     key_config: KeyConfig,
 }
 
@@ -28,9 +32,36 @@ impl ConnectionsComponent {
             connections,
             key_config,
             state,
+            delete_confirming: false,
+            status: None,
         }
     }
 
+    pub fn connections(&self) -> &[Connection] {
+        &self.connections
+    }
+
+    pub fn update(&mut self, connections: Vec<Connection>, selected: Option<usize>) {
+        self.connections = connections;
+        self.state
+            .select(selected.filter(|i| *i < self.connections.len()));
+        self.delete_confirming = false;
+    }
+
+    pub fn selected_index(&self) -> Option<usize> {
+        self.state.selected()
+    }
+
+    /// returns true when the deletion is requested twice in a row
+    pub fn confirm_delete(&mut self) -> bool {
+        self.delete_confirming = !self.delete_confirming;
+        !self.delete_confirming
+    }
+
+    pub fn set_status(&mut self, status: String) {
+        self.status = Some(status);
+    }
+
     fn next_connection(&mut self, lines: usize) {
         let i = match self.state.selected() {
             Some(i) => {
@@ -88,24 +119,79 @@ impl DrawableComponent for ConnectionsComponent {
         let conns = &self.connections;
         let mut connections: Vec<ListItem> = Vec::new();
         for c in conns {
-            connections.push(
-                ListItem::new(vec![Spans::from(Span::raw(c.database_url()?))])
-                    .style(Style::default()),
-            )
+            let mut spans = if c.needs_path() {
+                Spans::from(vec![
+                    Span::raw(if c.is_duckdb() {
+                        "duckdb://"
+                    } else {
+                        "sqlite://"
+                    }),
+                    Span::styled(
+                        format!(" <{}>", Msg::ChooseSqliteFile.text()),
+                        Theme::current().hint(),
+                    ),
+                ])
+            } else {
+                Spans::from(Span::raw(c.database_url()?))
+            };
+            if c.read_only {
+                spans.0.push(Span::styled(
+                    format!(" ({})", Msg::ReadOnly.text()),
+                    Theme::current().hint(),
+                ));
+            }
+            if let Some(group) = &c.group {
+                spans.0.push(Span::styled(
+                    format!(" [{}]", group),
+                    Theme::current().hint(),
+                ));
+            }
+            connections.push(ListItem::new(vec![spans]).style(Style::default()))
         }
+        let title = if self.delete_confirming {
+            format!(
+                "{} [{}]",
+                Msg::ConfirmDeleteConnection.text(),
+                self.key_config.delete_connection
+            )
+        } else if let Some(status) = self.status.as_ref() {
+            format!("{} - {}", Msg::Connections.text(), status)
+        } else {
+            Msg::Connections.text().to_string()
+        };
         let tasks = List::new(connections)
-            .block(Block::default().borders(Borders::ALL).title("Connections"))
-            .highlight_style(Style::default().bg(Color::Blue))
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Theme::current().selected())
             .style(Style::default());
 
-        let area = Rect::new(
-            (f.size().width.saturating_sub(width)) / 2,
-            (f.size().height.saturating_sub(height)) / 2,
-            width.min(f.size().width),
-            height.min(f.size().height),
-        );
-        f.render_widget(Clear, area);
+        let area = popup_area(f, width, height);
         f.render_stateful_widget(tasks, area, &mut self.state);
+        if area.height > 2 {
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    format!(
+                        " [{}] {} [{}] {} [{}] {} [{}] {} [{}] {} ",
+                        self.key_config.add_connection,
+                        Msg::AddConnection.text(),
+                        self.key_config.edit_connection,
+                        Msg::EditConnection.text(),
+                        self.key_config.delete_connection,
+                        Msg::DeleteConnection.text(),
+                        self.key_config.test_connection,
+                        Msg::TestConnection.text(),
+                        self.key_config.import_connections,
+                        Msg::ImportConnections.text()
+                    ),
+                    Theme::current().hint(),
+                )),
+                Rect::new(
+                    area.x + 1,
+                    area.y + area.height - 1,
+                    area.width.saturating_sub(2),
+                    1,
+                ),
+            );
+        }
         Ok(())
     }
 }
@@ -114,6 +200,9 @@ impl Component for ConnectionsComponent {
     fn commands(&self, _out: &mut Vec<CommandInfo>) {}
 
     fn event(&mut self, key: Key) -> Result<EventState> {
+        if key != self.key_config.delete_connection {
+            self.delete_confirming = false;
+        }
         if key == self.key_config.scroll_down {
             self.next_connection(1);
             return Ok(EventState::Consumed);
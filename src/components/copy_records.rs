@@ -0,0 +1,123 @@
+use super::{
+    popup_area, table_value::separate_thousands, Component, DrawableComponent, EventState,
+};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+/// shows how many records are fetched while every page of the table is fetched in the background to be copied
+pub struct CopyRecordsComponent {
+    fetched: usize,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl CopyRecordsComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            fetched: 0,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn open(&mut self) -> Result<()> {
+        self.fetched = 0;
+        self.show()
+    }
+
+    pub fn set_fetched(&mut self, fetched: usize) {
+        self.fetched = fetched;
+    }
+
+    fn lines(&self) -> Vec<Spans<'static>> {
+        vec![
+            Spans::from(format!(
+                "{} {}…",
+                separate_thousands(self.fetched),
+                Msg::RecordsFetched.text()
+            )),
+            Spans::from(""),
+            Spans::from(Span::styled(
+                format!("[{}] {}", self.key_config.exit_popup, Msg::Cancel.text()),
+                Theme::current().hint(),
+            )),
+        ]
+    }
+}
+
+impl DrawableComponent for CopyRecordsComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let text = self.lines();
+        let area = popup_area(f, 50, text.len() as u16 + 2);
+        f.render_widget(
+            Paragraph::new(text).block(
+                Block::default()
+                    .title(Msg::CopyAllRecords.text())
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick),
+            ),
+            area,
+        );
+        Ok(())
+    }
+}
+
+impl Component for CopyRecordsComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, CopyRecordsComponent, KeyConfig};
+
+    #[test]
+    fn test_fetched() {
+        let mut component = CopyRecordsComponent::new(KeyConfig::default());
+        component.open().unwrap();
+        component.set_fetched(12000);
+        assert_eq!(component.lines()[0].0[0].content, "12,000 records fetched…");
+        component.event(KeyConfig::default().exit_popup).unwrap();
+        assert!(!component.visible);
+
+        component.open().unwrap();
+        assert_eq!(component.fetched, 0);
+    }
+}
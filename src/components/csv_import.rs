@@ -0,0 +1,808 @@
+use super::{
+    popup_area, table_value::separate_thousands, Component, DrawableComponent, EventState,
+};
+use crate::components::command::CommandInfo;
+use crate::components::StatementLog;
+use crate::config::KeyConfig;
+use crate::csv::{detect_delimiter, parse_csv, valid_value};
+use crate::database::{type_badge, type_length, ColumnKind};
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// how many rows of the file are previewed
+const PREVIEW_ROWS: usize = 5;
+/// the widest a column of the preview is drawn
+const PREVIEW_WIDTH: usize = 16;
+/// how many rows one INSERT statement may insert, to choose from
+const BATCH_SIZES: [usize; 7] = [1, 10, 50, 100, 500, 1000, 5000];
+
+/// the values of the rows of a CSV file to insert into the columns of a table
+pub struct CsvRows {
+    pub headers: Vec<String>,
+    /// the kind of values each column holds as its type tells, which decides how the values are written
+    pub kinds: Vec<ColumnKind>,
+    /// the values, with `None` for NULL
+    pub rows: Vec<Vec<Option<String>>>,
+    pub batch_size: usize,
+}
+
+/// how many of the batches of rows were inserted
+struct ImportProgress {
+    rows: usize,
+    batch_size: usize,
+    batches: usize,
+    inserted: usize,
+    error: Option<String>,
+    finished: bool,
+    cancelled: bool,
+}
+
+/// asks for a CSV file, maps its columns to the columns of the selected table, checking their values against the types
+/// of the columns, and inserts its rows in batches while showing how many were inserted
+pub struct CsvImportComponent {
+    table: String,
+    /// the names, the types and the nullabilities of the columns of the table
+    columns: Vec<(String, String, bool)>,
+    input: String,
+    /// whether the first record of the file names its columns
+    header: bool,
+    /// whether an empty value is inserted as NULL rather than as an empty string
+    empty_as_null: bool,
+    submitted: bool,
+    records: Option<Vec<Vec<String>>>,
+    /// the column of the file each column of the table takes its values from
+    mapping: Vec<Option<usize>>,
+    selected: usize,
+    /// the position of the batch size among `BATCH_SIZES`
+    batch_size: usize,
+    request: Option<CsvRows>,
+    message: Option<String>,
+    /// the values which do not fit their columns, found when the rows were last checked
+    invalid: Vec<String>,
+    /// how many rows were checked without inserting them, when every value fit its column
+    validated: Option<usize>,
+    progress: Option<ImportProgress>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl CsvImportComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            table: String::new(),
+            columns: vec![],
+            input: String::new(),
+            header: true,
+            empty_as_null: true,
+            submitted: false,
+            records: None,
+            mapping: vec![],
+            selected: 0,
+            batch_size: 4,
+            request: None,
+            message: None,
+            invalid: vec![],
+            validated: None,
+            progress: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens the prompt for the file to import into `table`, keeping the last path, header mode, empty value mode and
+    /// batch size, where `columns` holds the name, the type and whether NULL is allowed of each column
+    pub fn open(&mut self, table: String, columns: Vec<(String, String, bool)>) -> Result<()> {
+        if self.is_importing() {
+            return self.show();
+        }
+        self.table = table;
+        self.columns = columns;
+        self.submitted = false;
+        self.records = None;
+        self.request = None;
+        self.message = None;
+        self.invalid = vec![];
+        self.validated = None;
+        self.progress = None;
+        self.show()
+    }
+
+    /// returns the path of the file once the user entered it
+    pub fn take_path(&mut self) -> Option<String> {
+        if !std::mem::take(&mut self.submitted) {
+            return None;
+        }
+        Some(self.input.trim().to_string())
+    }
+
+    /// reads the records of the file, mapping the columns of the table to the columns of the file with the same
+    /// name, or in order when the file has no header
+    pub fn load(&mut self, text: &str) {
+        let records = parse_csv(text, detect_delimiter(text));
+        if records.len() <= self.header as usize {
+            self.message = Some(Msg::EmptyCsv.text().to_string());
+            return;
+        }
+        let width = records.iter().map(Vec::len).max().unwrap_or(0);
+        self.mapping = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, (name, _, _))| {
+                if self.header {
+                    records[0]
+                        .iter()
+                        .position(|header| header.trim().eq_ignore_ascii_case(name))
+                } else {
+                    Some(index).filter(|index| *index < width)
+                }
+            })
+            .collect();
+        self.records = Some(records);
+        self.selected = 0;
+        self.message = None;
+        self.invalid = vec![];
+        self.validated = None;
+    }
+
+    /// returns the rows to insert once the user confirmed the mapping and every value fits its column
+    pub fn take_request(&mut self) -> Option<CsvRows> {
+        self.request.take()
+    }
+
+    /// switches to the progress of the import, which inserts `rows` rows in `batches` statements
+    pub fn start(&mut self, batches: usize, rows: usize) {
+        self.progress = Some(ImportProgress {
+            rows,
+            batch_size: BATCH_SIZES[self.batch_size],
+            batches,
+            inserted: 0,
+            error: None,
+            finished: false,
+            cancelled: false,
+        });
+    }
+
+    pub fn push_log(&mut self, log: &StatementLog) {
+        if let Some(progress) = self.progress.as_mut() {
+            match log.error() {
+                Some(error) => progress.error = Some(error.to_string()),
+                None => progress.inserted += 1,
+            }
+        }
+    }
+
+    pub fn finish(&mut self, cancelled: bool) {
+        if let Some(progress) = self.progress.as_mut() {
+            progress.finished = true;
+            progress.cancelled = cancelled;
+        }
+    }
+
+    pub fn is_importing(&self) -> bool {
+        matches!(&self.progress, Some(progress) if !progress.finished)
+    }
+
+    /// returns the names of the columns of the file, or their positions when it has no header
+    fn file_columns(&self) -> Vec<String> {
+        let records = match self.records.as_ref() {
+            Some(records) => records,
+            None => return vec![],
+        };
+        let width = records.iter().map(Vec::len).max().unwrap_or(0);
+        (0..width)
+            .map(|index| match records[0].get(index) {
+                Some(name) if self.header => format!("{}: {}", index + 1, name),
+                _ => format!("{} {}", Msg::CsvColumn.text(), index + 1),
+            })
+            .collect()
+    }
+
+    /// returns the records of the file after its header
+    fn data(&self) -> &[Vec<String>] {
+        match self.records.as_ref() {
+            Some(records) => &records[(self.header as usize).min(records.len())..],
+            None => &[],
+        }
+    }
+
+    /// returns the value of a record for the column of the table at `column`
+    fn value<'a>(&self, record: &'a [String], column: usize) -> Option<&'a str> {
+        self.mapping[column]
+            .and_then(|index| record.get(index))
+            .map(String::as_str)
+    }
+
+    /// returns the value to insert into the column of the table at `column`, with `None` for NULL
+    fn insert_value(&self, record: &[String], column: usize) -> Option<String> {
+        match self.value(record, column).unwrap_or_default() {
+            "" if self.empty_as_null => None,
+            value => Some(value.to_string()),
+        }
+    }
+
+    fn valid(&self, column: usize, value: &str) -> bool {
+        let badge = type_badge(self.columns[column].1.as_str());
+        // an empty string is only text, while NULL fits every type
+        if value.is_empty() && !self.empty_as_null {
+            return matches!(badge, None | Some("txt"));
+        }
+        valid_value(badge, value)
+    }
+
+    /// returns the row, the column and the value of every value of `columns` which does not fit its column, with why:
+    /// its type, NULL in a NOT NULL column or more characters than the type allows
+    fn invalid_values(&self, columns: &[usize]) -> Vec<String> {
+        let mut invalid = vec![];
+        for (row, record) in self.data().iter().enumerate() {
+            for column in columns {
+                let (name, r#type, nullable) = &self.columns[*column];
+                let value = self.value(record, *column).unwrap_or_default();
+                let reason = match self.insert_value(record, *column) {
+                    None if !nullable => Some(format!("NULL {} NOT NULL", Msg::DoesNotFit.text())),
+                    None => None,
+                    Some(_) if !self.valid(*column, value) => {
+                        Some(format!("'{}' {} {}", value, Msg::DoesNotFit.text(), r#type))
+                    }
+                    Some(_) => type_length(r#type)
+                        .filter(|length| value.chars().count() > *length)
+                        .map(|_| {
+                            format!(
+                                "'{}' {} {} ({} {})",
+                                value,
+                                Msg::DoesNotFit.text(),
+                                r#type,
+                                value.chars().count(),
+                                Msg::Characters.text()
+                            )
+                        }),
+                };
+                if let Some(reason) = reason {
+                    invalid.push(format!(
+                        "{} {}, {}: {}",
+                        Msg::Row.text(),
+                        row + 1,
+                        name,
+                        reason
+                    ));
+                }
+            }
+        }
+        invalid
+    }
+
+    fn cycle_source(&mut self, forward: bool) {
+        self.validated = None;
+        let count = self.file_columns().len();
+        if let Some(source) = self.mapping.get_mut(self.selected) {
+            // no column comes before the first one and after the last one
+            *source = match (*source, forward) {
+                (None, true) if count > 0 => Some(0),
+                (None, false) if count > 0 => Some(count - 1),
+                (Some(index), true) if index + 1 < count => Some(index + 1),
+                (Some(index), false) if index > 0 => Some(index - 1),
+                _ => None,
+            };
+        }
+    }
+
+    /// checks every value of the mapped columns against its column, listing every one which does not fit, and returns
+    /// the mapped columns when they all fit
+    fn check(&mut self) -> Option<Vec<usize>> {
+        let columns = (0..self.columns.len())
+            .filter(|column| self.mapping[*column].is_some())
+            .collect::<Vec<usize>>();
+        self.invalid = vec![];
+        self.validated = None;
+        if columns.is_empty() {
+            self.message = Some(Msg::NoColumnMapped.text().to_string());
+            return None;
+        }
+        self.invalid = self.invalid_values(&columns);
+        if !self.invalid.is_empty() {
+            self.message = Some(format!(
+                "{} {}",
+                separate_thousands(self.invalid.len()),
+                Msg::InvalidValues.text()
+            ));
+            return None;
+        }
+        self.message = None;
+        Some(columns)
+    }
+
+    /// checks every row as importing it would without inserting anything
+    fn validate(&mut self) {
+        if self.check().is_some() {
+            self.validated = Some(self.data().len());
+        }
+    }
+
+    /// checks every value against its column, then asks for the mapped columns to be inserted
+    fn submit(&mut self) {
+        let columns = match self.check() {
+            Some(columns) => columns,
+            None => return,
+        };
+        self.request = Some(CsvRows {
+            headers: columns
+                .iter()
+                .map(|column| self.columns[*column].0.clone())
+                .collect(),
+            kinds: columns
+                .iter()
+                .map(|column| ColumnKind::from_badge(type_badge(self.columns[*column].1.as_str())))
+                .collect(),
+            rows: self
+                .data()
+                .iter()
+                .map(|record| {
+                    columns
+                        .iter()
+                        .map(|column| self.insert_value(record, *column))
+                        .collect()
+                })
+                .collect(),
+            batch_size: BATCH_SIZES[self.batch_size],
+        });
+    }
+
+    fn mapping_lines(&self) -> Vec<Spans<'static>> {
+        let file_columns = self.file_columns();
+        let name_width = self.columns.iter().map(|(name, _, _)| name.width()).max();
+        let type_width = self
+            .columns
+            .iter()
+            .map(|(_, r#type, _)| r#type.width())
+            .max();
+        let mut lines = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, (name, r#type, _))| {
+                let source = match self.mapping[index] {
+                    Some(source) => file_columns[source].clone(),
+                    None => Msg::SkipColumn.text().to_string(),
+                };
+                let line = format!(
+                    "{:<name_width$}  {:<type_width$}  ← {}",
+                    name,
+                    r#type,
+                    source,
+                    name_width = name_width.unwrap_or(0),
+                    type_width = type_width.unwrap_or(0)
+                );
+                Spans::from(Span::styled(
+                    line,
+                    if index == self.selected {
+                        Theme::current().selected()
+                    } else {
+                        Theme::current().text(true)
+                    },
+                ))
+            })
+            .collect::<Vec<Spans>>();
+        lines.push(Spans::from(""));
+        lines.push(Spans::from(format!(
+            "{}: {}  ({} {})  {}",
+            Msg::BatchSize.text(),
+            separate_thousands(BATCH_SIZES[self.batch_size]),
+            separate_thousands(self.data().len()),
+            Msg::Rows.text(),
+            self.empty_mode()
+        )));
+        lines.push(Spans::from(""));
+        lines.push(Spans::from(Span::styled(
+            Msg::Preview.text(),
+            Theme::current().hint(),
+        )));
+        let cell = |text: &str| {
+            let text = text.replace('\n', " ");
+            let text = text.chars().take(PREVIEW_WIDTH).collect::<String>();
+            format!("{:<width$} ", text, width = PREVIEW_WIDTH)
+        };
+        let mapped = (0..self.columns.len())
+            .filter(|column| self.mapping[*column].is_some())
+            .collect::<Vec<usize>>();
+        lines.push(Spans::from(Span::styled(
+            mapped
+                .iter()
+                .map(|column| cell(self.columns[*column].0.as_str()))
+                .collect::<String>(),
+            Theme::current().hint(),
+        )));
+        for record in self.data().iter().take(PREVIEW_ROWS) {
+            lines.push(Spans::from(
+                mapped
+                    .iter()
+                    .map(|column| {
+                        let value = self.value(record, *column).unwrap_or_default();
+                        match value {
+                            "" if self.empty_as_null => {
+                                Span::styled(cell("NULL"), Theme::current().hint())
+                            }
+                            value if !self.valid(*column, value) => {
+                                Span::styled(cell(value), Theme::current().error())
+                            }
+                            value => Span::raw(cell(value)),
+                        }
+                    })
+                    .collect::<Vec<Span>>(),
+            ));
+        }
+        lines.push(Spans::from(""));
+        lines.extend(
+            self.invalid.iter().map(|invalid| {
+                Spans::from(Span::styled(invalid.clone(), Theme::current().error()))
+            }),
+        );
+        if let Some(rows) = self.validated {
+            lines.push(Spans::from(format!(
+                "{} {}",
+                separate_thousands(rows),
+                Msg::RowsFit.text()
+            )));
+        }
+        lines.push(match self.message.as_ref() {
+            Some(message) => Spans::from(Span::styled(message.clone(), Theme::current().error())),
+            None => Spans::from(Span::styled(
+                format!(
+                    "[{},{}] {}  [{},{}] {}  [+,-] {}  [{}] {}  [v] {}  [{}] {}  [{}] {}",
+                    self.key_config.scroll_up,
+                    self.key_config.scroll_down,
+                    Msg::Columns.text(),
+                    self.key_config.scroll_left,
+                    self.key_config.scroll_right,
+                    Msg::CsvColumn.text(),
+                    Msg::BatchSize.text(),
+                    Key::Tab,
+                    self.empty_mode(),
+                    Msg::Validate.text(),
+                    self.key_config.enter,
+                    Msg::Import.text(),
+                    self.key_config.exit_popup,
+                    Msg::Cancel.text()
+                ),
+                Theme::current().hint(),
+            )),
+        });
+        lines
+    }
+
+    fn progress_lines(&self, progress: &ImportProgress, width: usize) -> Vec<Spans<'static>> {
+        let rows = (progress.inserted * progress.batch_size).min(progress.rows);
+        let ratio = if progress.batches == 0 {
+            1.0
+        } else {
+            progress.inserted as f64 / progress.batches as f64
+        };
+        let bar_width = width.saturating_sub(7);
+        let filled = (bar_width as f64 * ratio).round() as usize;
+        let mut lines = vec![
+            Spans::from(format!(
+                "{}{} {:>3}%",
+                "█".repeat(filled),
+                "░".repeat(bar_width - filled),
+                (ratio * 100.0).round()
+            )),
+            Spans::from(format!(
+                "{} / {} {}",
+                separate_thousands(rows),
+                separate_thousands(progress.rows),
+                Msg::RowsImported.text()
+            )),
+        ];
+        if let Some(error) = progress.error.as_ref() {
+            lines.push(Spans::from(Span::styled(
+                error.clone(),
+                Theme::current().error(),
+            )));
+        } else if progress.cancelled {
+            lines.push(Spans::from(Span::styled(
+                Msg::QueryCancelled.text(),
+                Theme::current().error(),
+            )));
+        }
+        lines.push(Spans::from(Span::styled(
+            if progress.finished {
+                format!("[{}] {}", self.key_config.exit_popup, Msg::Close.text())
+            } else {
+                format!(
+                    "[{},{}] {}",
+                    self.key_config.exit_popup,
+                    self.key_config.exit,
+                    Msg::Cancel.text()
+                )
+            },
+            Theme::current().hint(),
+        )));
+        lines
+    }
+
+    fn mapping_event(&mut self, key: Key) {
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            self.submit();
+        } else if key == self.key_config.scroll_down || key == Key::Down {
+            self.selected = (self.selected + 1).min(self.columns.len().saturating_sub(1));
+        } else if key == self.key_config.scroll_up || key == Key::Up {
+            self.selected = self.selected.saturating_sub(1);
+        } else if key == self.key_config.scroll_right || key == Key::Right {
+            self.cycle_source(true);
+        } else if key == self.key_config.scroll_left || key == Key::Left {
+            self.cycle_source(false);
+        } else if key == Key::Char('v') {
+            self.validate();
+        } else if key == Key::Char('+') {
+            self.batch_size = (self.batch_size + 1).min(BATCH_SIZES.len() - 1);
+        } else if key == Key::Char('-') {
+            self.batch_size = self.batch_size.saturating_sub(1);
+        } else if key == Key::Tab {
+            self.empty_as_null = !self.empty_as_null;
+            self.invalid = vec![];
+            self.validated = None;
+            self.message = None;
+        }
+    }
+
+    fn input_event(&mut self, key: Key) {
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            self.submitted = !self.input.trim().is_empty();
+        } else {
+            match key {
+                Key::Tab => self.header = !self.header,
+                Key::Char(c) => self.input.push(c),
+                Key::Backspace | Key::Delete => {
+                    self.input.pop();
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn header_mode(&self) -> &'static str {
+        if self.header {
+            Msg::FirstRowIsHeader.text()
+        } else {
+            Msg::NoHeaderRow.text()
+        }
+    }
+
+    fn empty_mode(&self) -> &'static str {
+        if self.empty_as_null {
+            Msg::EmptyIsNull.text()
+        } else {
+            Msg::EmptyIsEmptyString.text()
+        }
+    }
+}
+
+impl DrawableComponent for CsvImportComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let block = Block::default()
+            .title(format!(
+                "{}: {} ← {}",
+                Msg::ImportCsv.text(),
+                self.table,
+                self.input.trim()
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick);
+        if let Some(progress) = self.progress.as_ref() {
+            const SIZE: (u16, u16) = (60, 6);
+            let area = popup_area(f, SIZE.0, SIZE.1);
+            let lines = self.progress_lines(progress, area.width.saturating_sub(2) as usize);
+            f.render_widget(Paragraph::new(lines).block(block), area);
+        } else if self.records.is_some() {
+            let lines = self.mapping_lines();
+            let area = popup_area(
+                f,
+                f.size().width.saturating_sub(10),
+                (lines.len() as u16 + 2).min(f.size().height.saturating_sub(6)),
+            );
+            f.render_widget(Paragraph::new(lines).block(block), area);
+        } else {
+            const SIZE: (u16, u16) = (60, 4);
+            let area = popup_area(f, SIZE.0, SIZE.1);
+            let text = vec![
+                if self.input.is_empty() {
+                    Spans::from(Span::styled(
+                        Msg::ImportCsvPlaceholder.text(),
+                        Theme::current().hint(),
+                    ))
+                } else {
+                    Spans::from(Span::raw(self.input.as_str()))
+                },
+                match self.message.as_ref() {
+                    Some(message) => {
+                        Spans::from(Span::styled(message.clone(), Theme::current().error()))
+                    }
+                    None => Spans::from(Span::styled(
+                        format!(
+                            "[{}] {}  [{}] {}  [{}] {}",
+                            Key::Tab,
+                            self.header_mode(),
+                            self.key_config.enter,
+                            Msg::Preview.text(),
+                            self.key_config.exit_popup,
+                            Msg::Cancel.text()
+                        ),
+                        Theme::current().hint(),
+                    )),
+                },
+            ];
+            f.render_widget(
+                Paragraph::new(text).block(block.title(Msg::ImportCsv.text())),
+                area,
+            );
+            f.set_cursor(area.x + 1 + self.input.width() as u16, area.y + 1);
+        }
+        Ok(())
+    }
+}
+
+impl Component for CsvImportComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if self.progress.is_some() {
+            if key == self.key_config.exit_popup && !self.is_importing() {
+                self.hide();
+            }
+        } else if self.records.is_some() {
+            self.mapping_event(key);
+        } else {
+            self.message = None;
+            self.input_event(key);
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ColumnKind, Component as _, CsvImportComponent, KeyConfig};
+    use crate::event::Key;
+
+    fn component() -> CsvImportComponent {
+        let mut component = CsvImportComponent::new(KeyConfig::default());
+        component
+            .open(
+                "users".to_string(),
+                vec![
+                    ("id".to_string(), "int".to_string(), true),
+                    ("name".to_string(), "varchar(5)".to_string(), true),
+                    ("born".to_string(), "date".to_string(), false),
+                ],
+            )
+            .unwrap();
+        component
+    }
+
+    #[test]
+    fn test_map_and_import() {
+        let mut component = component();
+        for c in "users.csv".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(component.take_path(), Some("users.csv".to_string()));
+        component.load("Name;ID\nalice;1\nbob;\n");
+        assert_eq!(component.mapping, vec![Some(1), Some(0), None]);
+
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(KeyConfig::default().scroll_left).unwrap();
+        assert_eq!(component.mapping[1], None);
+        component.event(KeyConfig::default().scroll_left).unwrap();
+        assert_eq!(component.mapping[1], Some(1));
+        component.event(KeyConfig::default().scroll_left).unwrap();
+        component.event(Key::Char('-')).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        let request = component.take_request().unwrap();
+        assert_eq!(request.headers, vec!["id", "name"]);
+        assert_eq!(request.kinds, vec![ColumnKind::Number, ColumnKind::Text]);
+        assert_eq!(
+            request.rows,
+            vec![
+                vec![Some("1".to_string()), Some("alice".to_string())],
+                vec![None, Some("bob".to_string())]
+            ]
+        );
+        assert_eq!(request.batch_size, 100);
+
+        // an empty string does not fit a number
+        component.event(Key::Tab).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(component.take_request().is_none());
+        assert_eq!(component.invalid.len(), 1);
+        component.event(KeyConfig::default().scroll_up).unwrap();
+        component.event(KeyConfig::default().scroll_left).unwrap();
+        component.event(KeyConfig::default().scroll_left).unwrap();
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(KeyConfig::default().scroll_right).unwrap();
+        assert_eq!(component.mapping, vec![None, Some(1), None]);
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(
+            component.take_request().unwrap().rows,
+            vec![vec![Some("1".to_string())], vec![Some("".to_string())]]
+        );
+
+        component.start(1, 2);
+        assert!(component.is_importing());
+        component.event(KeyConfig::default().exit_popup).unwrap();
+        assert!(component.visible);
+        component.finish(false);
+        component.event(KeyConfig::default().exit_popup).unwrap();
+        assert!(!component.visible);
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let mut component = component();
+        component.event(Key::Tab).unwrap();
+        component.load("1,alice,1990-01-01\nx,bob,1990-02-30\n");
+        assert_eq!(component.mapping, vec![Some(0), Some(1), Some(2)]);
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(component.take_request().is_none());
+        assert!(component.message.as_ref().unwrap().starts_with("2 "));
+        assert_eq!(component.invalid.len(), 2);
+        assert!(component.invalid[0].contains("'x'"));
+        assert!(component.invalid[1].contains("'1990-02-30'"));
+
+        // checking the rows without importing them tells every value which does not fit with why
+        component.load("1,alice,1990-01-01\n,bob,\n3,charlotte,1990-03-01\n");
+        component.event(Key::Char('v')).unwrap();
+        assert!(component.take_request().is_none());
+        assert_eq!(component.validated, None);
+        assert_eq!(component.invalid.len(), 2);
+        assert!(component.invalid[0].starts_with("row 2, born: NULL"));
+        assert!(component.invalid[1].starts_with("row 3, name: 'charlotte'"));
+        assert!(component.invalid[1].ends_with("(9 chars)"));
+        component.load("1,alice,1990-01-01\n2,bob,1990-02-01\n");
+        component.event(Key::Char('v')).unwrap();
+        assert!(component.take_request().is_none());
+        assert!(component.invalid.is_empty());
+        assert_eq!(component.validated, Some(2));
+
+        component.load("");
+        assert!(component.message.is_some());
+        let mut component = super::CsvImportComponent::new(KeyConfig::default());
+        component.open("users".to_string(), vec![]).unwrap();
+        component.load("a\n1\n");
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(component.take_request().is_none());
+    }
+}
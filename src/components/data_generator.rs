@@ -0,0 +1,246 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::fake_data::FakeDataGenerator;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+const MAX_ROWS: usize = 10_000;
+const PREVIEW_ROWS: usize = 20;
+
+enum Step {
+    Count,
+    Preview,
+}
+
+pub struct DataGeneratorComponent {
+    columns: Vec<(String, String, Option<String>)>,
+    input: String,
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    step: Step,
+    visible: bool,
+    confirmed: bool,
+    key_config: KeyConfig,
+}
+
+impl DataGeneratorComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            columns: vec![],
+            input: String::new(),
+            headers: vec![],
+            rows: vec![],
+            step: Step::Count,
+            visible: false,
+            confirmed: false,
+            key_config,
+        }
+    }
+
+    /// `columns` is a list of column names, types and defaults
+    pub fn open(&mut self, columns: Vec<(String, String, Option<String>)>) -> Result<()> {
+        self.columns = columns;
+        self.input = String::new();
+        self.headers = vec![];
+        self.rows = vec![];
+        self.step = Step::Count;
+        self.confirmed = false;
+        self.show()
+    }
+
+    /// returns the generated records once the user confirmed the preview
+    pub fn take_records(&mut self) -> Option<(Vec<String>, Vec<Vec<String>>)> {
+        if !self.confirmed {
+            return None;
+        }
+        self.confirmed = false;
+        Some((
+            std::mem::take(&mut self.headers),
+            std::mem::take(&mut self.rows),
+        ))
+    }
+
+    fn count(&self) -> usize {
+        self.input.parse::<usize>().unwrap_or(10).clamp(1, MAX_ROWS)
+    }
+
+    fn generate(&mut self, generator: &mut FakeDataGenerator) {
+        self.headers = self
+            .columns
+            .iter()
+            .filter(|(name, r#type, default)| {
+                generator.value(name, r#type, default.as_deref()).is_some()
+            })
+            .map(|(name, _, _)| name.to_string())
+            .collect();
+        self.rows = (0..self.count())
+            .map(|_| {
+                self.columns
+                    .iter()
+                    .filter_map(|(name, r#type, default)| {
+                        generator.value(name, r#type, default.as_deref())
+                    })
+                    .collect()
+            })
+            .collect();
+    }
+
+    fn get_text(&self) -> Vec<Spans<'static>> {
+        match self.step {
+            Step::Count => vec![
+                Spans::from(Span::raw(format!(
+                    "{}: {}",
+                    Msg::NumberOfRows.text(),
+                    self.input
+                ))),
+                Spans::from(Span::raw("")),
+                Spans::from(Span::styled(
+                    format!(
+                        "[{}] {}  [{}] {}",
+                        self.key_config.enter,
+                        Msg::Preview.text(),
+                        self.key_config.exit_popup,
+                        Msg::Cancel.text()
+                    ),
+                    Theme::current().hint(),
+                )),
+            ],
+            Step::Preview => {
+                let mut txt = vec![Spans::from(Span::styled(
+                    self.headers.join(" | "),
+                    Theme::current().header(),
+                ))];
+                for row in self.rows.iter().take(PREVIEW_ROWS) {
+                    txt.push(Spans::from(Span::raw(row.join(" | "))));
+                }
+                if self.rows.len() > PREVIEW_ROWS {
+                    txt.push(Spans::from(Span::raw("...")));
+                }
+                txt.push(Spans::from(Span::raw("")));
+                txt.push(Spans::from(Span::styled(
+                    format!(
+                        "[{}] {} {}  [{}] {}",
+                        self.key_config.enter,
+                        Msg::Insert.text(),
+                        self.rows.len(),
+                        self.key_config.exit_popup,
+                        Msg::Cancel.text()
+                    ),
+                    Theme::current().hint(),
+                )));
+                txt
+            }
+        }
+    }
+}
+
+impl DrawableComponent for DataGeneratorComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let size = match self.step {
+                Step::Count => (50, 6),
+                Step::Preview => (100, PREVIEW_ROWS as u16 + 6),
+            };
+            let area = popup_area(f, size.0, size.1);
+            f.render_widget(
+                Block::default()
+                    .title(Msg::GenerateFakeData.text())
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick),
+                area,
+            );
+
+            let chunks = Layout::default()
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1)].as_ref())
+                .split(area);
+            f.render_widget(Paragraph::new(self.get_text()), chunks[0]);
+        }
+        Ok(())
+    }
+}
+
+impl Component for DataGeneratorComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+            return Ok(EventState::Consumed);
+        }
+        match self.step {
+            Step::Count => match key {
+                Key::Char(c) if c.is_ascii_digit() => self.input.push(c),
+                Key::Backspace | Key::Delete => {
+                    self.input.pop();
+                }
+                key if key == self.key_config.enter => {
+                    self.generate(&mut FakeDataGenerator::default());
+                    self.step = Step::Preview;
+                }
+                _ => (),
+            },
+            Step::Preview => {
+                if key == self.key_config.enter {
+                    self.confirmed = true;
+                    self.hide();
+                }
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DataGeneratorComponent, FakeDataGenerator, KeyConfig};
+
+    #[test]
+    fn test_generate() {
+        let mut component = DataGeneratorComponent::new(KeyConfig::default());
+        component
+            .open(vec![
+                ("id".to_string(), "int(11)".to_string(), None),
+                ("email".to_string(), "varchar(255)".to_string(), None),
+            ])
+            .unwrap();
+        component.input = "3".to_string();
+        component.generate(&mut FakeDataGenerator::new(1));
+        assert_eq!(component.headers, vec!["email".to_string()]);
+        assert_eq!(component.rows.len(), 3);
+        assert!(component.take_records().is_none());
+        component.confirmed = true;
+        let (headers, rows) = component.take_records().unwrap();
+        assert_eq!(headers.len(), 1);
+        assert_eq!(rows.len(), 3);
+    }
+}
@@ -1,20 +1,24 @@
 use super::{
-    compute_character_width, utils::scroll_vertical::VerticalScroll, Component, DrawableComponent,
-    EventState,
+    compute_character_width,
+    utils::{completion::common_prefix, scroll_vertical::VerticalScroll},
+    Component, DrawableComponent, EventState,
 };
 use crate::components::command::{self, CommandInfo};
+use crate::components::table_finder::TablePath;
 use crate::config::KeyConfig;
-use crate::event::Key;
+use crate::event::{Key, Mouse, MouseKind, WHEEL_LINES};
+use crate::i18n::Msg;
+use crate::theme::Theme;
 use crate::ui::common_nav;
 use crate::ui::scrolllist::draw_list_block;
 use anyhow::Result;
-use database_tree::{Database, DatabaseTree, DatabaseTreeItem};
+use database_tree::{Child, Database, DatabaseTree, DatabaseTreeItem, MoveSelection};
 use std::collections::BTreeSet;
 use std::convert::From;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::Style,
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
     Frame,
@@ -25,6 +29,10 @@ use unicode_width::UnicodeWidthStr;
 const FOLDER_ICON_COLLAPSED: &str = "\u{25b8}";
 // ▾
 const FOLDER_ICON_EXPANDED: &str = "\u{25be}";
+// ◇
+const VIEW_ICON: &str = "\u{25c7}";
+// ƒ
+const ROUTINE_ICON: &str = "\u{192}";
 const EMPTY_STR: &str = "";
 
 #[derive(PartialEq)]
@@ -36,11 +44,16 @@ pub enum Focus {
 pub struct DatabasesComponent {
     tree: DatabaseTree,
     filterd_tree: Option<DatabaseTree>,
+    /// the names of the tables in the tree, sorted and without duplicates, which the filter completes
+    table_names: Vec<String>,
+    /// every table in the tree with its database and its schema, in the order of the tree
+    table_paths: Vec<TablePath>,
     scroll: VerticalScroll,
     input: Vec<char>,
     input_idx: usize,
     input_cursor_position: u16,
     focus: Focus,
+    area: Rect,
     key_config: KeyConfig,
 }
 
@@ -49,11 +62,14 @@ impl DatabasesComponent {
         Self {
             tree: DatabaseTree::default(),
             filterd_tree: None,
+            table_names: Vec::new(),
+            table_paths: Vec::new(),
             scroll: VerticalScroll::new(false, false),
             input: Vec::new(),
             input_idx: 0,
             input_cursor_position: 0,
             focus: Focus::Tree,
+            area: Rect::default(),
             key_config,
         }
     }
@@ -64,6 +80,41 @@ impl DatabasesComponent {
 
     pub fn update(&mut self, list: &[Database]) -> Result<()> {
         self.tree = DatabaseTree::new(list, &BTreeSet::new())?;
+        self.table_names = list
+            .iter()
+            .flat_map(|database| &database.children)
+            .flat_map(|child| match child {
+                Child::Table(table) => vec![table.name.clone()],
+                Child::Schema(schema) => schema
+                    .tables
+                    .iter()
+                    .map(|table| table.name.clone())
+                    .collect(),
+                Child::Routine(_) => vec![],
+            })
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect();
+        self.table_paths = list
+            .iter()
+            .flat_map(|database| {
+                database.children.iter().flat_map(move |child| match child {
+                    Child::Table(table) => vec![(database.name.clone(), None, table.name.clone())],
+                    Child::Schema(schema) => schema
+                        .tables
+                        .iter()
+                        .map(|table| {
+                            (
+                                database.name.clone(),
+                                Some(schema.name.clone()),
+                                table.name.clone(),
+                            )
+                        })
+                        .collect(),
+                    Child::Routine(_) => vec![],
+                })
+            })
+            .collect();
         self.filterd_tree = None;
         self.input = Vec::new();
         self.input_idx = 0;
@@ -71,6 +122,46 @@ impl DatabasesComponent {
         Ok(())
     }
 
+    /// selects the table in the whole tree, clearing the filter
+    pub fn select_table(&mut self, database: &str, schema: Option<&str>, table: &str) -> bool {
+        self.filterd_tree = None;
+        self.input = Vec::new();
+        self.input_idx = 0;
+        self.input_cursor_position = 0;
+        self.focus = Focus::Tree;
+        self.tree.select_table(database, schema, table)
+    }
+
+    /// completes the filter to the longest prefix shared by the table names starting with it, as the filter of the records does
+    fn complete(&mut self) {
+        let input = self.input_str().to_lowercase();
+        let candidates = self
+            .table_names
+            .iter()
+            .filter(|name| name.to_lowercase().starts_with(input.as_str()))
+            .collect::<Vec<&String>>();
+        match common_prefix(&candidates) {
+            Some(completion) if completion.len() >= self.input.len() => {
+                self.input = completion;
+                self.input_idx = self.input.len();
+                self.input_cursor_position = self.input_str().width() as u16;
+                self.filterd_tree = Some(self.tree.filter(self.input_str()));
+            }
+            _ => (),
+        }
+    }
+
+    pub fn table_paths(&self) -> Vec<TablePath> {
+        self.table_paths.clone()
+    }
+
+    /// tells whether a database of the connection has a table of the name, ignoring the case of ASCII letters
+    pub fn has_table(&self, name: &str) -> bool {
+        self.table_names
+            .iter()
+            .any(|table| table.eq_ignore_ascii_case(name))
+    }
+
     pub fn tree_focused(&self) -> bool {
         matches!(self.focus, Focus::Tree)
     }
@@ -100,19 +191,23 @@ impl DatabasesComponent {
             } else {
                 FOLDER_ICON_EXPANDED
             }
+        } else if item.kind().is_view() {
+            VIEW_ICON
+        } else if item.kind().is_routine() {
+            ROUTINE_ICON
         } else {
             EMPTY_STR
         };
 
         if let Some(filter) = filter {
-            if item.kind().is_table() && name.contains(&filter) {
+            if (item.kind().is_table() || item.kind().is_routine()) && name.contains(&filter) {
                 let (first, rest) = &name.split_at(name.find(filter.as_str()).unwrap_or(0));
                 let (middle, last) = &rest.split_at(filter.len().clamp(0, rest.len()));
                 return Spans::from(vec![
                     Span::styled(
                         format!("{}{}{}", indent_str, arrow, first),
                         if selected {
-                            Style::default().bg(Color::Blue)
+                            Theme::current().selected()
                         } else {
                             Style::default()
                         },
@@ -120,15 +215,15 @@ impl DatabasesComponent {
                     Span::styled(
                         middle.to_string(),
                         if selected {
-                            Style::default().bg(Color::Blue).fg(Color::Blue)
+                            Theme::current().selected().patch(Theme::current().accent())
                         } else {
-                            Style::default().fg(Color::Blue)
+                            Theme::current().accent()
                         },
                     ),
                     Span::styled(
                         format!("{:w$}", last.to_string(), w = width as usize),
                         if selected {
-                            Style::default().bg(Color::Blue)
+                            Theme::current().selected()
                         } else {
                             Style::default()
                         },
@@ -140,7 +235,7 @@ impl DatabasesComponent {
         Spans::from(Span::styled(
             format!("{}{}{:w$}", indent_str, arrow, name, w = width as usize),
             if selected {
-                Style::default().bg(Color::Blue)
+                Theme::current().selected()
             } else {
                 Style::default()
             },
@@ -150,13 +245,9 @@ impl DatabasesComponent {
     fn draw_tree<B: Backend>(&self, f: &mut Frame<B>, area: Rect, focused: bool) {
         f.render_widget(
             Block::default()
-                .title("Databases")
+                .title(Msg::Databases.text())
                 .borders(Borders::ALL)
-                .style(if focused {
-                    Style::default()
-                } else {
-                    Style::default().fg(Color::DarkGray)
-                }),
+                .style(Theme::current().text(focused)),
             area,
         );
 
@@ -171,17 +262,13 @@ impl DatabasesComponent {
             format!(
                 "{}{:w$}",
                 if self.input.is_empty() && matches!(self.focus, Focus::Tree) {
-                    "Filter tables".to_string()
+                    Msg::FilterTables.text().to_string()
                 } else {
                     self.input_str()
                 },
                 w = area.width as usize
             ),
-            if let Focus::Filter = self.focus {
-                Style::default()
-            } else {
-                Style::default().fg(Color::DarkGray)
-            },
+            Theme::current().text(matches!(self.focus, Focus::Filter)),
         ))
         .block(Block::default().borders(Borders::BOTTOM));
         f.render_widget(filter, chunks[0]);
@@ -228,6 +315,7 @@ impl DatabasesComponent {
 
 impl DrawableComponent for DatabasesComponent {
     fn draw<B: Backend>(&mut self, f: &mut Frame<B>, area: Rect, focused: bool) -> Result<()> {
+        self.area = area;
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(100)].as_ref())
@@ -240,7 +328,14 @@ impl DrawableComponent for DatabasesComponent {
 
 impl Component for DatabasesComponent {
     fn commands(&self, out: &mut Vec<CommandInfo>) {
-        out.push(CommandInfo::new(command::expand_collapse(&self.key_config)))
+        out.push(CommandInfo::new(command::expand_collapse(&self.key_config)));
+        out.push(CommandInfo::new(command::recent_databases(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::queue_analyze(&self.key_config)));
+        out.push(CommandInfo::new(command::execute_routine(&self.key_config)));
+        out.push(CommandInfo::new(command::find_table(&self.key_config)));
+        out.push(CommandInfo::new(command::scheduled_jobs(&self.key_config)));
     }
 
     fn event(&mut self, key: Key) -> Result<EventState> {
@@ -304,6 +399,10 @@ impl Component for DatabasesComponent {
                 }
                 return Ok(EventState::Consumed);
             }
+            Key::Tab if matches!(self.focus, Focus::Filter) => {
+                self.complete();
+                return Ok(EventState::Consumed);
+            }
             Key::Enter if matches!(self.focus, Focus::Filter) => {
                 self.focus = Focus::Tree;
                 return Ok(EventState::Consumed);
@@ -324,6 +423,36 @@ impl Component for DatabasesComponent {
         }
         Ok(EventState::NotConsumed)
     }
+
+    fn mouse_event(&mut self, mouse: Mouse) -> Result<EventState> {
+        if !mouse.is_in(self.area) {
+            return Ok(EventState::NotConsumed);
+        }
+        let tree = if let Some(tree) = self.filterd_tree.as_mut() {
+            tree
+        } else {
+            &mut self.tree
+        };
+        // the filter takes the two lines under the top border and the tree the rest
+        let tree_top = self.area.y + 3;
+        match mouse.kind {
+            MouseKind::Click if mouse.row < tree_top => self.focus = Focus::Filter,
+            MouseKind::Click => {
+                self.focus = Focus::Tree;
+                tree.select_visual(self.scroll.get_top() + (mouse.row - tree_top) as usize);
+            }
+            MouseKind::ScrollUp | MouseKind::ScrollDown => {
+                for _ in 0..WHEEL_LINES {
+                    tree.move_selection(if mouse.kind == MouseKind::ScrollUp {
+                        MoveSelection::Up
+                    } else {
+                        MoveSelection::Down
+                    });
+                }
+            }
+        }
+        Ok(EventState::Consumed)
+    }
 }
 
 fn tree_nav(tree: &mut DatabaseTree, key: Key, key_config: &KeyConfig) -> bool {
@@ -336,8 +465,12 @@ fn tree_nav(tree: &mut DatabaseTree, key: Key, key_config: &KeyConfig) -> bool {
 
 #[cfg(test)]
 mod test {
-    use super::{Color, Database, DatabaseTreeItem, DatabasesComponent, Span, Spans, Style};
+    use super::{
+        Child, Component as _, Database, DatabaseTreeItem, DatabasesComponent, Focus, Key,
+        KeyConfig, Span, Spans, Style,
+    };
     use database_tree::Table;
+    use tui::style::Color;
 
     #[test]
     fn test_tree_database_tree_item_to_span() {
@@ -397,7 +530,8 @@ mod test {
                         create_time: None,
                         update_time: None,
                         engine: None,
-                        schema: None
+                        schema: None,
+                        view: false,
                     },
                 ),
                 false,
@@ -423,7 +557,8 @@ mod test {
                         create_time: None,
                         update_time: None,
                         engine: None,
-                        schema: None
+                        schema: None,
+                        view: false,
                     },
                 ),
                 true,
@@ -452,7 +587,8 @@ mod test {
                         create_time: None,
                         update_time: None,
                         engine: None,
-                        schema: None
+                        schema: None,
+                        view: false,
                     },
                 ),
                 false,
@@ -478,7 +614,8 @@ mod test {
                         create_time: None,
                         update_time: None,
                         engine: None,
-                        schema: None
+                        schema: None,
+                        view: false,
                     },
                 ),
                 true,
@@ -495,4 +632,58 @@ mod test {
             ])
         );
     }
+
+    #[test]
+    fn test_tree_view_item_to_span() {
+        let database = Database::new("foo".to_string(), vec![]);
+        let view = Table {
+            name: "bar".to_string(),
+            create_time: None,
+            update_time: None,
+            engine: None,
+            schema: None,
+            view: true,
+        };
+        assert_eq!(
+            DatabasesComponent::tree_item_to_span(
+                DatabaseTreeItem::new_table(&database, &view),
+                false,
+                10,
+                None,
+            ),
+            Spans::from(vec![Span::raw(format!("  \u{25c7}{:w$}", "bar", w = 10))])
+        );
+    }
+
+    #[test]
+    fn test_complete() {
+        let table = |name: &str| {
+            Child::Table(Table {
+                name: name.to_string(),
+                create_time: None,
+                update_time: None,
+                engine: None,
+                schema: None,
+                view: false,
+            })
+        };
+        let mut component = DatabasesComponent::new(KeyConfig::default());
+        component
+            .update(&[Database::new(
+                "shop".to_string(),
+                vec![table("orders"), table("order_items"), table("users")],
+            )])
+            .unwrap();
+        component.focus = Focus::Filter;
+        component.event(Key::Char('o')).unwrap();
+        component.event(Key::Tab).unwrap();
+        assert_eq!(component.input_str(), "order");
+        component.event(Key::Char('_')).unwrap();
+        component.event(Key::Tab).unwrap();
+        assert_eq!(component.input_str(), "order_items");
+        assert_eq!(component.input_idx, 11);
+        component.event(Key::Char('x')).unwrap();
+        component.event(Key::Tab).unwrap();
+        assert_eq!(component.input_str(), "order_itemsx");
+    }
 }
@@ -0,0 +1,332 @@
+use super::{
+    popup_area, table_status::format_size, table_value::separate_thousands, Component,
+    DrawableComponent, EventState,
+};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::dump::DumpProgress;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use std::time::{Duration, Instant};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// how a dump is running and how far it has got
+struct DumpState {
+    started: Instant,
+    finished: Option<Duration>,
+    progress: DumpProgress,
+    /// how large the file the program of the server writes has grown
+    written: u64,
+    error: Option<String>,
+    cancelled: bool,
+}
+
+/// asks for the file to dump the selected database or table into, with the program of the server such as `pg_dump`
+/// when it is installed or with SELECT otherwise, and shows how far the dump running in the background has got
+pub struct DumpComponent {
+    target: String,
+    /// the program of the server which is found in `PATH`
+    program: Option<&'static str>,
+    /// whether the program of the server dumps rather than SELECT
+    native: bool,
+    input: String,
+    request: Option<(String, bool)>,
+    state: Option<DumpState>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl DumpComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            target: String::new(),
+            program: None,
+            native: true,
+            input: String::new(),
+            request: None,
+            state: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens the prompt for the file to dump `target` into, suggesting a file named after it
+    pub fn open(&mut self, target: String, program: Option<&'static str>) -> Result<()> {
+        if self.is_dumping() {
+            return self.show();
+        }
+        if self.input.is_empty() || self.target != target {
+            self.input = format!("{}.sql", target);
+        }
+        self.target = target;
+        self.program = program;
+        self.request = None;
+        self.state = None;
+        self.show()
+    }
+
+    /// returns the path of the file and whether the program of the server dumps into it once the user entered it
+    pub fn take_request(&mut self) -> Option<(String, bool)> {
+        self.request.take()
+    }
+
+    pub fn start(&mut self) {
+        self.state = Some(DumpState {
+            started: Instant::now(),
+            finished: None,
+            progress: DumpProgress::default(),
+            written: 0,
+            error: None,
+            cancelled: false,
+        });
+    }
+
+    pub fn set_progress(&mut self, progress: DumpProgress) {
+        if let Some(state) = self.state.as_mut() {
+            state.progress = progress;
+        }
+    }
+
+    pub fn set_written(&mut self, written: u64) {
+        if let Some(state) = self.state.as_mut() {
+            state.written = written;
+        }
+    }
+
+    pub fn finish(&mut self, error: Option<String>, cancelled: bool) {
+        if let Some(state) = self.state.as_mut() {
+            state.finished = Some(state.started.elapsed());
+            state.error = error;
+            state.cancelled = cancelled;
+        }
+    }
+
+    pub fn is_dumping(&self) -> bool {
+        matches!(&self.state, Some(state) if state.finished.is_none())
+    }
+
+    fn method(&self) -> &'static str {
+        match self.program {
+            Some(program) if self.native => program,
+            _ => "SELECT",
+        }
+    }
+
+    fn progress_lines(&self, state: &DumpState) -> Vec<Spans<'static>> {
+        let progress = if self.native && self.program.is_some() {
+            format!("{} {}", format_size(state.written), Msg::Written.text())
+        } else {
+            format!(
+                "{} / {} {}, {} {}",
+                state.progress.tables,
+                state.progress.total,
+                Msg::Tables.text(),
+                separate_thousands(state.progress.rows),
+                Msg::Rows.text()
+            )
+        };
+        let elapsed = state
+            .finished
+            .unwrap_or_else(|| state.started.elapsed())
+            .as_secs();
+        let mut lines = vec![
+            Spans::from(format!("{}: {}", self.method(), self.input.trim())),
+            Spans::from(format!(
+                "{}{}  {}s",
+                progress,
+                if state.finished.is_none() { "…" } else { "" },
+                elapsed
+            )),
+        ];
+        if let Some(error) = state.error.as_ref() {
+            lines.push(Spans::from(Span::styled(
+                error.clone(),
+                Theme::current().error(),
+            )));
+        } else if state.cancelled {
+            lines.push(Spans::from(Span::styled(
+                Msg::QueryCancelled.text(),
+                Theme::current().error(),
+            )));
+        } else if state.finished.is_some() {
+            lines.push(Spans::from(Msg::DumpFinished.text()));
+        }
+        lines.push(Spans::from(Span::styled(
+            if state.finished.is_some() {
+                format!("[{}] {}", self.key_config.exit_popup, Msg::Close.text())
+            } else {
+                format!(
+                    "[{},{}] {}",
+                    self.key_config.exit_popup,
+                    self.key_config.exit,
+                    Msg::Cancel.text()
+                )
+            },
+            Theme::current().hint(),
+        )));
+        lines
+    }
+}
+
+impl DrawableComponent for DumpComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let block = Block::default()
+            .title(format!("{}: {}", Msg::Dump.text(), self.target))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick);
+        if let Some(state) = self.state.as_ref() {
+            let lines = self.progress_lines(state);
+            let area = popup_area(f, 60, lines.len() as u16 + 2);
+            f.render_widget(Paragraph::new(lines).block(block), area);
+            return Ok(());
+        }
+        const SIZE: (u16, u16) = (60, 4);
+        let area = popup_area(f, SIZE.0, SIZE.1);
+        let hint = match self.program {
+            Some(program) => format!(
+                "[{}] {}/SELECT ({})  [{}] {}  [{}] {}",
+                Key::Tab,
+                program,
+                self.method(),
+                self.key_config.enter,
+                Msg::Dump.text(),
+                self.key_config.exit_popup,
+                Msg::Cancel.text()
+            ),
+            None => format!(
+                "SELECT  [{}] {}  [{}] {}",
+                self.key_config.enter,
+                Msg::Dump.text(),
+                self.key_config.exit_popup,
+                Msg::Cancel.text()
+            ),
+        };
+        let text = vec![
+            if self.input.is_empty() {
+                Spans::from(Span::styled(
+                    Msg::DumpPlaceholder.text(),
+                    Theme::current().hint(),
+                ))
+            } else {
+                Spans::from(Span::raw(self.input.as_str()))
+            },
+            Spans::from(Span::styled(hint, Theme::current().hint())),
+        ];
+        f.render_widget(Paragraph::new(text).block(block), area);
+        f.set_cursor(area.x + 1 + self.input.width() as u16, area.y + 1);
+        Ok(())
+    }
+}
+
+impl Component for DumpComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if self.state.is_some() {
+            if key == self.key_config.exit_popup && !self.is_dumping() {
+                self.hide();
+            }
+            return Ok(EventState::Consumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            if !self.input.trim().is_empty() {
+                self.request = Some((
+                    self.input.trim().to_string(),
+                    self.native && self.program.is_some(),
+                ));
+            }
+        } else {
+            match key {
+                Key::Tab => self.native = !self.native,
+                Key::Char(c) => self.input.push(c),
+                Key::Backspace | Key::Delete => {
+                    self.input.pop();
+                }
+                _ => (),
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, DumpComponent, KeyConfig};
+    use crate::dump::DumpProgress;
+    use crate::event::Key;
+
+    #[test]
+    fn test_dump() {
+        let mut component = DumpComponent::new(KeyConfig::default());
+        component.open("shop".to_string(), Some("pg_dump")).unwrap();
+        assert_eq!(component.input, "shop.sql");
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(
+            component.take_request(),
+            Some(("shop.sql".to_string(), true))
+        );
+        component.event(Key::Tab).unwrap();
+        component.event(Key::Backspace).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(
+            component.take_request(),
+            Some(("shop.sq".to_string(), false))
+        );
+
+        component.start();
+        component.set_progress(DumpProgress {
+            tables: 1,
+            total: 3,
+            rows: 1200,
+        });
+        let lines = component.progress_lines(component.state.as_ref().unwrap());
+        assert!(lines[1].0[0]
+            .content
+            .starts_with("1 / 3 tables, 1,200 rows…"));
+        component.event(KeyConfig::default().exit_popup).unwrap();
+        assert!(component.visible);
+        component.finish(None, false);
+        assert!(!component.is_dumping());
+        component.event(KeyConfig::default().exit_popup).unwrap();
+        assert!(!component.visible);
+
+        component.open("shop.users".to_string(), None).unwrap();
+        assert_eq!(component.input, "shop.users.sql");
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(
+            component.take_request(),
+            Some(("shop.users.sql".to_string(), false))
+        );
+    }
+}
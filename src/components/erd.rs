@@ -0,0 +1,239 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::erd::{Erd, Mark};
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+/// the text the diagram is copied as
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErdFormat {
+    Dot,
+    Mermaid,
+}
+
+impl ErdFormat {
+    fn text(self) -> &'static str {
+        match self {
+            Self::Dot => "DOT",
+            Self::Mermaid => "Mermaid",
+        }
+    }
+}
+
+/// shows the tables of the selected database as boxes with their keys joined by their foreign keys, highlighting
+/// the relations of the selected table
+pub struct ErdComponent {
+    erd: Erd,
+    selected: usize,
+    /// the first line and the first column of the diagram which are shown
+    scroll: (usize, usize),
+    format: ErdFormat,
+    copied: Option<String>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ErdComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            erd: Erd::default(),
+            selected: 0,
+            scroll: (0, 0),
+            format: ErdFormat::Mermaid,
+            copied: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens the diagram of the tables, keeping the format it was last copied as
+    pub fn open(&mut self, erd: Erd) -> Result<()> {
+        self.erd = erd;
+        self.selected = 0;
+        self.scroll = (0, 0);
+        self.copied = None;
+        self.show()
+    }
+
+    /// returns the diagram in the chosen format once the user copied it
+    pub fn take_copied(&mut self) -> Option<String> {
+        self.copied.take()
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.erd.entities.len().saturating_sub(1));
+    }
+}
+
+/// splits a line of the diagram into spans of the characters highlighted the same way
+fn diagram_line(line: &[(char, Mark)]) -> Spans<'static> {
+    let mut spans = vec![];
+    let mut text = String::new();
+    let mut mark = line.first().map_or(Mark::Plain, |(_, mark)| *mark);
+    let style = |mark: Mark| match mark {
+        Mark::Plain => Theme::current().text(true),
+        Mark::Related => Theme::current().accent(),
+        Mark::Selected => Theme::current().selected(),
+    };
+    for (c, next) in line {
+        if *next != mark {
+            spans.push(Span::styled(std::mem::take(&mut text), style(mark)));
+            mark = *next;
+        }
+        text.push(*c);
+    }
+    spans.push(Span::styled(text, style(mark)));
+    Spans::from(spans)
+}
+
+impl DrawableComponent for ErdComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let area = popup_area(
+            f,
+            f.size().width.saturating_sub(10),
+            f.size().height.saturating_sub(6),
+        );
+        let diagram = self.erd.diagram(Some(self.selected));
+        let height = area.height.saturating_sub(2) as usize;
+        // keeps the box of the selected table in view, from its name when it is taller than the view
+        if let Some((top, lines)) = diagram.boxes.get(self.selected) {
+            if top + lines > self.scroll.0 + height {
+                self.scroll.0 = (top + lines).saturating_sub(height);
+            }
+            if *top < self.scroll.0 {
+                self.scroll.0 = *top;
+            }
+        }
+        let lines = diagram
+            .lines
+            .iter()
+            .skip(self.scroll.0)
+            .take(height)
+            .map(|line| diagram_line(&line[self.scroll.1.min(line.len())..]))
+            .collect::<Vec<Spans>>();
+        let title = format!(
+            "{}: {} [{}] {} [{}] {}",
+            Msg::Erd.text(),
+            self.erd.database,
+            Key::Tab,
+            self.format.text(),
+            self.key_config.copy,
+            Msg::CopyDiagram.text()
+        );
+        f.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick),
+            ),
+            area,
+        );
+        Ok(())
+    }
+}
+
+impl Component for ErdComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.scroll_down || key == Key::Down {
+            self.select(self.selected + 1);
+        } else if key == self.key_config.scroll_up || key == Key::Up {
+            self.select(self.selected.saturating_sub(1));
+        } else if key == self.key_config.scroll_to_top {
+            self.select(0);
+        } else if key == self.key_config.scroll_to_bottom {
+            self.select(usize::MAX);
+        } else if key == self.key_config.scroll_right || key == Key::Right {
+            self.scroll.1 += 2;
+        } else if key == self.key_config.scroll_left || key == Key::Left {
+            self.scroll.1 = self.scroll.1.saturating_sub(2);
+        } else if key == self.key_config.copy {
+            self.copied = Some(match self.format {
+                ErdFormat::Dot => self.erd.to_dot(),
+                ErdFormat::Mermaid => self.erd.to_mermaid(),
+            });
+        } else if key == Key::Tab {
+            self.format = match self.format {
+                ErdFormat::Dot => ErdFormat::Mermaid,
+                ErdFormat::Mermaid => ErdFormat::Dot,
+            };
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, ErdComponent, ErdFormat, KeyConfig};
+    use crate::erd::{Entity, Erd};
+    use crate::event::Key;
+
+    #[test]
+    fn test_navigate_and_copy() {
+        let entity = |name: &str| Entity {
+            schema: None,
+            name: name.to_string(),
+            columns: vec![],
+            primary_key: vec![],
+            foreign_keys: vec![],
+        };
+        let erd = Erd {
+            database: "shop".to_string(),
+            entities: vec![entity("orders"), entity("users")],
+        };
+        let mut component = ErdComponent::new(KeyConfig::default());
+        component.open(erd.clone()).unwrap();
+        component.event(KeyConfig::default().scroll_up).unwrap();
+        assert_eq!(component.selected, 0);
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        assert_eq!(component.selected, 1);
+        component.event(KeyConfig::default().scroll_to_top).unwrap();
+        assert_eq!(component.selected, 0);
+
+        assert_eq!(component.take_copied(), None);
+        component.event(KeyConfig::default().copy).unwrap();
+        assert_eq!(component.take_copied(), Some(erd.to_mermaid()));
+        component.event(Key::Tab).unwrap();
+        assert_eq!(component.format, ErdFormat::Dot);
+        component.event(KeyConfig::default().copy).unwrap();
+        assert_eq!(component.take_copied(), Some(erd.to_dot()));
+
+        component.event(KeyConfig::default().exit_popup).unwrap();
+        assert!(!component.visible);
+    }
+}
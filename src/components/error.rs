@@ -1,13 +1,14 @@
-use super::{Component, DrawableComponent, EventState};
+use super::{popup_area, Component, DrawableComponent, EventState};
 use crate::components::command::CommandInfo;
 use crate::config::KeyConfig;
 use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
 use anyhow::Result;
 use tui::{
     backend::Backend,
     layout::{Alignment, Rect},
-    style::{Color, Style},
-    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
 
@@ -40,17 +41,15 @@ impl DrawableComponent for ErrorComponent {
             let width = 65;
             let height = 10;
             let error = Paragraph::new(self.error.to_string())
-                .block(Block::default().title("Error").borders(Borders::ALL))
-                .style(Style::default().fg(Color::Red))
+                .block(
+                    Block::default()
+                        .title(Msg::Error.text())
+                        .borders(Borders::ALL),
+                )
+                .style(Theme::current().error())
                 .alignment(Alignment::Left)
                 .wrap(Wrap { trim: true });
-            let area = Rect::new(
-                (f.size().width.saturating_sub(width)) / 2,
-                (f.size().height.saturating_sub(height)) / 2,
-                width.min(f.size().width),
-                height.min(f.size().height),
-            );
-            f.render_widget(Clear, area);
+            let area = popup_area(f, width, height);
             f.render_widget(error, area);
         }
         Ok(())
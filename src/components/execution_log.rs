@@ -0,0 +1,217 @@
+use super::{popup_area, Component, DrawableComponent, EventState, StatementLog};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// the oldest statements are forgotten beyond this count
+const MAX_LOGS: usize = 1000;
+
+/// the statements run on the connection so far, with when each of them ran and how it ended
+pub struct ExecutionLogComponent {
+    /// the time each statement ran, such as 14:03:21, with how it ended
+    logs: Vec<(String, StatementLog)>,
+    state: ListState,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ExecutionLogComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            logs: vec![],
+            state: ListState::default(),
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn push(&mut self, log: StatementLog) {
+        self.logs
+            .push((chrono::Local::now().format("%H:%M:%S").to_string(), log));
+        if self.logs.len() > MAX_LOGS {
+            self.logs.remove(0);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.logs.clear();
+        self.state.select(None);
+    }
+
+    /// shows the log with the newest statement selected
+    pub fn open(&mut self) -> Result<()> {
+        self.state.select(self.logs.len().checked_sub(1));
+        self.show()
+    }
+
+    fn select_next(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state
+                .select(Some((i + 1).min(self.logs.len().saturating_sub(1))));
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(i.saturating_sub(1)));
+        }
+    }
+}
+
+impl DrawableComponent for ExecutionLogComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let area = popup_area(
+            f,
+            f.size().width.saturating_sub(10),
+            f.size().height.saturating_sub(6),
+        );
+        f.render_widget(
+            Block::default()
+                .title(format!(
+                    "{} ({})",
+                    Msg::ExecutionLog.text(),
+                    self.logs.len()
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick),
+            area,
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(area);
+        // the warnings of a statement are listed under it
+        let items = self
+            .logs
+            .iter()
+            .map(|(time, log)| {
+                let mut spans = log.spans();
+                spans.0.insert(
+                    0,
+                    Span::styled(format!("{} ", time), Theme::current().hint()),
+                );
+                let mut lines = vec![spans];
+                lines.extend(log.warnings().iter().map(|warning| {
+                    Spans::from(Span::styled(
+                        format!("{:>20}{}", "", warning),
+                        Theme::current().accent(),
+                    ))
+                }));
+                ListItem::new(lines)
+            })
+            .collect::<Vec<ListItem>>();
+        f.render_stateful_widget(
+            List::new(items).highlight_style(Theme::current().selected()),
+            chunks[0],
+            &mut self.state,
+        );
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                format!("[{}] {}", self.key_config.exit_popup, Msg::Close.text()),
+                Theme::current().hint(),
+            )),
+            chunks[1],
+        );
+        Ok(())
+    }
+}
+
+impl Component for ExecutionLogComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.scroll_down || key == Key::Down {
+            self.select_next();
+        } else if key == self.key_config.scroll_up || key == Key::Up {
+            self.select_previous();
+        } else if key == self.key_config.scroll_to_top {
+            self.state.select(Some(0).filter(|_| !self.logs.is_empty()));
+        } else if key == self.key_config.scroll_to_bottom {
+            self.state.select(self.logs.len().checked_sub(1));
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, ExecutionLogComponent, KeyConfig, StatementLog, MAX_LOGS};
+    use crate::database::ExecuteResult;
+    use std::time::Duration;
+
+    fn log(statement: &str) -> StatementLog {
+        StatementLog::new(
+            statement.to_string(),
+            Duration::from_millis(1),
+            &Ok(ExecuteResult::Write { updated_rows: 1 }),
+        )
+    }
+
+    #[test]
+    fn test_execution_log() {
+        let mut component = ExecutionLogComponent::new(KeyConfig::default());
+        component.open().unwrap();
+        assert_eq!(component.state.selected(), None);
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        assert_eq!(component.state.selected(), None);
+
+        for _ in 0..MAX_LOGS {
+            component.push(log("DELETE FROM t"));
+        }
+        component.push(StatementLog::new(
+            "SELECT * FROM t".to_string(),
+            Duration::from_millis(2),
+            &Ok(ExecuteResult::Read {
+                headers: vec![],
+                rows: vec![],
+            }),
+        ));
+        assert_eq!(component.logs.len(), MAX_LOGS);
+        assert_eq!(component.logs[MAX_LOGS - 1].1.status(), "0 rows  2ms");
+
+        component.open().unwrap();
+        assert_eq!(component.state.selected(), Some(MAX_LOGS - 1));
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        assert_eq!(component.state.selected(), Some(MAX_LOGS - 1));
+        component.event(KeyConfig::default().scroll_to_top).unwrap();
+        assert_eq!(component.state.selected(), Some(0));
+
+        component.clear();
+        assert!(component.logs.is_empty());
+        component.event(KeyConfig::default().exit_popup).unwrap();
+        assert!(!component.visible);
+    }
+}
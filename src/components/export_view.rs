@@ -0,0 +1,199 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// asks for the file to write the table of the selected tab to, as it is drawn on the screen
+pub struct ExportViewComponent {
+    input: String,
+    all_rows: bool,
+    submitted: bool,
+    exported: Option<usize>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ExportViewComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            input: String::new(),
+            all_rows: false,
+            submitted: false,
+            exported: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens the prompt, keeping the last path and range of rows
+    pub fn open(&mut self) -> Result<()> {
+        self.submitted = false;
+        self.exported = None;
+        self.show()
+    }
+
+    /// returns the path, whether to export every loaded row and whether to keep the colors as ANSI escape codes once the user entered the path
+    pub fn take_request(&mut self) -> Option<(String, bool, bool)> {
+        if !std::mem::take(&mut self.submitted) {
+            return None;
+        }
+        let path = self.input.trim().to_string();
+        let ansi = path.ends_with(".ansi");
+        Some((path, self.all_rows, ansi))
+    }
+
+    /// tells that the view was written as `lines` lines
+    pub fn set_exported(&mut self, lines: usize) {
+        self.exported = Some(lines);
+    }
+
+    fn range(&self) -> &'static str {
+        if self.all_rows {
+            Msg::AllLoadedRows.text()
+        } else {
+            Msg::VisibleRows.text()
+        }
+    }
+}
+
+impl DrawableComponent for ExportViewComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        const SIZE: (u16, u16) = (60, 4);
+        let area = popup_area(f, SIZE.0, SIZE.1);
+        let text = vec![
+            if self.input.is_empty() {
+                Spans::from(Span::styled(
+                    Msg::ExportViewPlaceholder.text(),
+                    Theme::current().hint(),
+                ))
+            } else {
+                Spans::from(Span::raw(self.input.as_str()))
+            },
+            match self.exported {
+                Some(lines) => Spans::from(Span::styled(
+                    format!(
+                        "{} ({} {})",
+                        Msg::ViewExported.text(),
+                        lines,
+                        Msg::Lines.text()
+                    ),
+                    Theme::current().success(),
+                )),
+                None => Spans::from(Span::styled(
+                    format!(
+                        "[{}] {}  [{}] {}  [{}] {}",
+                        Key::Tab,
+                        self.range(),
+                        self.key_config.enter,
+                        Msg::Save.text(),
+                        self.key_config.exit_popup,
+                        Msg::Cancel.text()
+                    ),
+                    Theme::current().hint(),
+                )),
+            },
+        ];
+        f.render_widget(
+            Paragraph::new(text).block(
+                Block::default()
+                    .title(Msg::ExportView.text())
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick),
+            ),
+            area,
+        );
+        f.set_cursor(area.x + 1 + self.input.width() as u16, area.y + 1);
+        Ok(())
+    }
+}
+
+impl Component for ExportViewComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.enter {
+            self.submitted = !self.input.trim().is_empty();
+            return Ok(EventState::Consumed);
+        }
+        match key {
+            Key::Tab => self.all_rows = !self.all_rows,
+            Key::Char(c) => self.input.push(c),
+            Key::Backspace | Key::Delete => {
+                self.input.pop();
+            }
+            _ => (),
+        }
+        self.exported = None;
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, ExportViewComponent, KeyConfig};
+    use crate::event::Key;
+
+    #[test]
+    fn test_take_request() {
+        let mut component = ExportViewComponent::new(KeyConfig::default());
+        component.open().unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(component.take_request(), None);
+
+        for c in "view.ansi".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(
+            component.take_request(),
+            Some(("view.ansi".to_string(), false, true))
+        );
+        assert_eq!(component.take_request(), None);
+
+        component.set_exported(12);
+        component.event(Key::Tab).unwrap();
+        assert_eq!(component.exported, None);
+        for _ in 0..5 {
+            component.event(Key::Backspace).unwrap();
+        }
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(
+            component.take_request(),
+            Some(("view".to_string(), true, false))
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState},
+    Frame,
+};
+
+const DATABASE_EXTENSIONS: [&str; 6] = ["db", "sqlite", "sqlite3", "db3", "duckdb", "ddb"];
+
+/// a directory or a SQLite or DuckDB file listed in the picker
+#[derive(Debug, PartialEq)]
+struct Entry {
+    name: String,
+    is_dir: bool,
+}
+
+/// browses the filesystem to choose the file of a sqlite connection which has no path
+pub struct FilePickerComponent {
+    dir: PathBuf,
+    entries: Vec<Entry>,
+    state: ListState,
+    chosen: Option<PathBuf>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl FilePickerComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            dir: PathBuf::new(),
+            entries: vec![],
+            state: ListState::default(),
+            chosen: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens the directory last browsed, or the current directory the first time
+    pub fn open(&mut self) -> Result<()> {
+        if self.dir.as_os_str().is_empty() {
+            self.dir = std::env::current_dir()?;
+        }
+        self.change_dir(self.dir.clone(), None)?;
+        self.chosen = None;
+        self.show()
+    }
+
+    /// returns the file the user chose
+    pub fn take_chosen(&mut self) -> Option<PathBuf> {
+        self.chosen.take()
+    }
+
+    /// lists `dir`, selecting the entry named `selected` if there is one, and keeps the current listing when it cannot be read
+    fn change_dir(&mut self, dir: PathBuf, selected: Option<&str>) -> Result<()> {
+        self.entries = read_entries(dir.as_path())?;
+        self.dir = dir;
+        let index = selected
+            .and_then(|name| self.entries.iter().position(|entry| entry.name == name))
+            .unwrap_or(0);
+        self.state.select(if self.entries.is_empty() {
+            None
+        } else {
+            Some(index)
+        });
+        Ok(())
+    }
+
+    fn parent_dir(&mut self) -> Result<()> {
+        let name = self
+            .dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string());
+        if let Some(parent) = self.dir.parent().map(Path::to_path_buf) {
+            self.change_dir(parent, name.as_deref())?;
+        }
+        Ok(())
+    }
+
+    fn enter(&mut self) -> Result<()> {
+        let entry = match self.state.selected().and_then(|i| self.entries.get(i)) {
+            Some(entry) => entry,
+            None => return Ok(()),
+        };
+        if entry.name == ".." {
+            return self.parent_dir();
+        }
+        let path = self.dir.join(entry.name.as_str());
+        if entry.is_dir {
+            self.change_dir(path, None)
+        } else {
+            self.chosen = Some(path);
+            self.hide();
+            Ok(())
+        }
+    }
+
+    fn select_next(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some((i + 1).min(self.entries.len() - 1)));
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(i.saturating_sub(1)));
+        }
+    }
+}
+
+/// lists the directories and the SQLite and DuckDB files in `dir`, skipping hidden ones, with directories first
+fn read_entries(dir: &Path) -> Result<Vec<Entry>> {
+    let mut entries = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        let is_dir = entry.path().is_dir();
+        let is_database = entry.path().extension().map_or(false, |extension| {
+            DATABASE_EXTENSIONS.contains(&extension.to_string_lossy().to_lowercase().as_str())
+        });
+        if is_dir || is_database {
+            entries.push(Entry { name, is_dir });
+        }
+    }
+    entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+    if dir.parent().is_some() {
+        entries.insert(
+            0,
+            Entry {
+                name: "..".to_string(),
+                is_dir: true,
+            },
+        );
+    }
+    Ok(entries)
+}
+
+impl DrawableComponent for FilePickerComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let items = self
+                .entries
+                .iter()
+                .map(|entry| {
+                    ListItem::new(Spans::from(if entry.is_dir {
+                        Span::styled(format!("{}/", entry.name), Theme::current().accent())
+                    } else {
+                        Span::raw(entry.name.clone())
+                    }))
+                })
+                .collect::<Vec<ListItem>>();
+            let area = popup_area(f, 60, f.size().height.saturating_sub(10).min(20));
+            f.render_stateful_widget(
+                List::new(items)
+                    .block(
+                        Block::default()
+                            .title(format!(
+                                "{}: {}",
+                                Msg::ChooseSqliteFile.text(),
+                                self.dir.display()
+                            ))
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick),
+                    )
+                    .highlight_style(Theme::current().selected()),
+                area,
+                &mut self.state,
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for FilePickerComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter || key == self.key_config.scroll_right {
+            self.enter()?;
+        } else if key == self.key_config.scroll_left || key == Key::Backspace {
+            self.parent_dir()?;
+        } else if key == self.key_config.scroll_down {
+            self.select_next();
+        } else if key == self.key_config.scroll_up {
+            self.select_previous();
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, Entry, FilePickerComponent, KeyConfig};
+
+    #[test]
+    fn test_choose_file() {
+        let root = std::env::temp_dir().join(format!("gobang-file-picker-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("data")).unwrap();
+        for file in &["b.sqlite3", "a.db", "notes.txt", ".hidden.db", "data/c.DB"] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+
+        let mut component = FilePickerComponent::new(KeyConfig::default());
+        component.dir = root.clone();
+        component.open().unwrap();
+        let names = component
+            .entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(names, vec!["..", "data", "a.db", "b.sqlite3"]);
+
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(component.dir, root.join("data"));
+        assert_eq!(
+            component.entries[1],
+            Entry {
+                name: "c.DB".to_string(),
+                is_dir: false,
+            }
+        );
+
+        component.event(KeyConfig::default().scroll_left).unwrap();
+        assert_eq!(component.dir, root);
+        assert_eq!(component.state.selected(), Some(1));
+
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+        assert!(!component.visible);
+        assert_eq!(component.take_chosen(), Some(root.join("a.db")));
+        assert_eq!(component.take_chosen(), None);
+    }
+}
@@ -0,0 +1,192 @@
+use super::{Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use itertools::Itertools;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::Paragraph,
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+const SEPARATOR: &str = "  ";
+const GROUP_SEPARATOR: &str = " │ ";
+
+/// what the keys act upon: moving around, typing text, or extending the selection of a table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputMode {
+    Normal,
+    Insert,
+    Visual,
+}
+
+impl InputMode {
+    fn badge(self) -> Span<'static> {
+        let theme = Theme::current();
+        let (msg, bg) = match self {
+            Self::Normal => (Msg::NormalMode, theme.normal_mode_bg),
+            Self::Insert => (Msg::InsertMode, theme.insert_mode_bg),
+            Self::Visual => (Msg::VisualMode, theme.visual_mode_bg),
+        };
+        Span::styled(format!(" {} ", msg.text()), theme.mode(bg))
+    }
+}
+
+/// a line at the bottom of the screen with the input mode and the commands of the focused component, grouped as in the help
+pub struct FooterComponent {
+    cmds: Vec<CommandInfo>,
+    mode: InputMode,
+    /// the seconds between the runs of the watched query
+    watching: Option<u64>,
+    key_config: KeyConfig,
+}
+
+impl FooterComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            cmds: vec![],
+            mode: InputMode::Normal,
+            watching: None,
+            key_config,
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: InputMode) {
+        self.mode = mode;
+    }
+
+    pub fn set_watching(&mut self, watching: Option<u64>) {
+        self.watching = watching;
+    }
+
+    pub fn set_cmds(&mut self, cmds: Vec<CommandInfo>) {
+        self.cmds = cmds
+            .into_iter()
+            .filter(|e| !e.text.hide_help)
+            .collect::<Vec<_>>();
+    }
+
+    /// returns the groups and commands which fit in `width`, followed by a pointer to the help when some of them do not
+    fn hints(&self, width: usize) -> Vec<Span<'static>> {
+        let more = format!("{}… [{}]", Msg::More.text(), self.key_config.open_help);
+        let mut spans = vec![];
+        let mut used = 0;
+        let mut shown = 0;
+        for (group, cmds) in &self.cmds.iter().group_by(|e| e.text.group) {
+            let mut label = Some(if spans.is_empty() {
+                format!("{} ", group)
+            } else {
+                format!("{}{} ", GROUP_SEPARATOR, group)
+            });
+            for (i, cmd) in cmds.enumerate() {
+                let name = if i == 0 {
+                    cmd.text.name.clone()
+                } else {
+                    format!("{}{}", SEPARATOR, cmd.text.name)
+                };
+                let needed = label.as_ref().map_or(0, |label| label.width()) + name.width();
+                // keeps room for the pointer to the help unless this is the last command
+                let reserved = if shown + 1 < self.cmds.len() {
+                    SEPARATOR.width() + more.width()
+                } else {
+                    0
+                };
+                if used + needed + reserved > width {
+                    if shown > 0 {
+                        spans.push(Span::raw(SEPARATOR));
+                    }
+                    spans.push(Span::styled(more, Theme::current().hint()));
+                    return spans;
+                }
+                if let Some(label) = label.take() {
+                    spans.push(Span::styled(label, Theme::current().accent()));
+                }
+                spans.push(Span::raw(name));
+                used += needed;
+                shown += 1;
+            }
+        }
+        spans
+    }
+}
+
+impl DrawableComponent for FooterComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, area: Rect, _focused: bool) -> Result<()> {
+        let mut spans = vec![self.mode.badge(), Span::raw(SEPARATOR)];
+        if let Some(interval) = self.watching {
+            spans.push(Span::styled(
+                format!("{} {}s", Msg::Watching.text(), interval),
+                Theme::current().accent(),
+            ));
+            spans.push(Span::raw(SEPARATOR));
+        }
+        let used = spans.iter().map(|span| span.width()).sum::<usize>();
+        let width = (area.width as usize).saturating_sub(used);
+        spans.extend(self.hints(width));
+        f.render_widget(Paragraph::new(Spans::from(spans)), area);
+        Ok(())
+    }
+}
+
+impl Component for FooterComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, _key: Key) -> Result<EventState> {
+        Ok(EventState::NotConsumed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CommandInfo, FooterComponent, InputMode, KeyConfig};
+    use crate::components::CommandText;
+
+    fn footer() -> FooterComponent {
+        let mut footer = FooterComponent::new(KeyConfig::default());
+        footer.set_cmds(vec![
+            CommandInfo::new(CommandText::new("Copy [y]".to_string(), "Table")),
+            CommandInfo::new(CommandText::new("Filter [/]".to_string(), "Table")),
+            CommandInfo::new(CommandText::new("Help [?]".to_string(), "General")),
+        ]);
+        footer
+    }
+
+    fn text(footer: &FooterComponent, width: usize) -> String {
+        footer
+            .hints(width)
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_hints() {
+        let footer = footer();
+        assert_eq!(
+            text(&footer, 80),
+            "Table Copy [y]  Filter [/] │ General Help [?]"
+        );
+        assert_eq!(
+            text(&footer, 45),
+            "Table Copy [y]  Filter [/] │ General Help [?]"
+        );
+        assert_eq!(text(&footer, 44), "Table Copy [y]  Filter [/]  More… [?]");
+        assert_eq!(text(&footer, 20), "More… [?]");
+    }
+
+    #[test]
+    fn test_badge() {
+        assert_eq!(InputMode::Normal.badge().content, " NORMAL ");
+        assert_eq!(InputMode::Insert.badge().content, " INSERT ");
+        assert_ne!(
+            InputMode::Insert.badge().style,
+            InputMode::Visual.badge().style
+        );
+    }
+}
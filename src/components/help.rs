@@ -1,7 +1,9 @@
-use super::{Component, DrawableComponent, EventState};
+use super::{popup_area, Component, DrawableComponent, EventState};
 use crate::components::command::CommandInfo;
 use crate::config::KeyConfig;
 use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
 use crate::version::Version;
 use anyhow::Result;
 use itertools::Itertools;
@@ -9,9 +11,9 @@ use std::convert::From;
 use tui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Span, Spans},
-    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    widgets::{Block, BorderType, Borders, Paragraph},
     Frame,
 };
 
@@ -29,17 +31,10 @@ impl DrawableComponent for HelpComponent {
             let scroll_threshold = SIZE.1 / 3;
             let scroll = self.selection.saturating_sub(scroll_threshold);
 
-            let area = Rect::new(
-                (f.size().width.saturating_sub(SIZE.0)) / 2,
-                (f.size().height.saturating_sub(SIZE.1)) / 2,
-                SIZE.0.min(f.size().width),
-                SIZE.1.min(f.size().height),
-            );
-
-            f.render_widget(Clear, area);
+            let area = popup_area(f, SIZE.0, SIZE.1);
             f.render_widget(
                 Block::default()
-                    .title("Help")
+                    .title(Msg::Help.text())
                     .borders(Borders::ALL)
                     .border_type(BorderType::Thick),
                 area,
@@ -153,7 +148,7 @@ impl HelpComponent {
                 txt.push(Spans::from(Span::styled(
                     format!(" {}{:w$}", command_info.text.name, w = width),
                     if is_selected {
-                        Style::default().bg(Color::Blue)
+                        Theme::current().selected()
                     } else {
                         Style::default()
                     },
@@ -167,7 +162,8 @@ impl HelpComponent {
 
 #[cfg(test)]
 mod test {
-    use super::{Color, CommandInfo, HelpComponent, KeyConfig, Modifier, Span, Spans, Style};
+    use super::{CommandInfo, HelpComponent, KeyConfig, Modifier, Span, Spans, Style};
+    use tui::style::Color;
 
     #[test]
     fn test_get_text() {
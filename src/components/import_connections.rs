@@ -0,0 +1,205 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::{Connection, KeyConfig};
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// lists the connections found in the files of other tools to review which of them are added
+pub struct ImportConnectionsComponent {
+    /// each connection with where it was found and whether it is checked to be added
+    connections: Vec<(String, Connection, bool)>,
+    state: ListState,
+    imported: Option<Vec<Connection>>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ImportConnectionsComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            connections: vec![],
+            state: ListState::default(),
+            imported: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn open(&mut self, connections: Vec<(String, Connection)>) -> Result<()> {
+        self.connections = connections
+            .into_iter()
+            .map(|(source, connection)| (source, connection, true))
+            .collect();
+        self.state.select(Some(0));
+        self.imported = None;
+        self.show()
+    }
+
+    /// returns the checked connections once the user added them
+    pub fn take_imported(&mut self) -> Option<Vec<Connection>> {
+        self.imported.take()
+    }
+
+    fn select_next(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some((i + 1) % self.connections.len()));
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(
+                (i + self.connections.len() - 1) % self.connections.len(),
+            ));
+        }
+    }
+}
+
+impl DrawableComponent for ImportConnectionsComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let items = self
+            .connections
+            .iter()
+            .map(|(source, connection, checked)| {
+                ListItem::new(Spans::from(vec![
+                    Span::raw(format!(
+                        "[{}] {}",
+                        if *checked { "x" } else { " " },
+                        connection.database_url().unwrap_or_default()
+                    )),
+                    Span::styled(format!("  {}", source), Theme::current().hint()),
+                ]))
+            })
+            .collect::<Vec<ListItem>>();
+        let area = popup_area(
+            f,
+            80,
+            (self.connections.len() as u16).saturating_add(3).min(20),
+        );
+        f.render_stateful_widget(
+            List::new(items)
+                .block(
+                    Block::default()
+                        .title(Msg::ImportConnections.text())
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Thick),
+                )
+                .highlight_style(Theme::current().selected()),
+            area,
+            &mut self.state,
+        );
+        if area.height > 2 {
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    format!(
+                        " [Space] {} [{}] {} ",
+                        Msg::ToggleConnection.text(),
+                        self.key_config.enter,
+                        Msg::AddCheckedConnections.text()
+                    ),
+                    Theme::current().hint(),
+                )),
+                Rect::new(
+                    area.x + 1,
+                    area.y + area.height - 1,
+                    area.width.saturating_sub(2),
+                    1,
+                ),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for ImportConnectionsComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            self.imported = Some(
+                self.connections
+                    .iter()
+                    .filter(|(_, _, checked)| *checked)
+                    .map(|(_, connection, _)| connection.clone())
+                    .collect(),
+            );
+            self.hide();
+        } else if key == Key::Char(' ') {
+            if let Some(i) = self.state.selected() {
+                if let Some((_, _, checked)) = self.connections.get_mut(i) {
+                    *checked = !*checked;
+                }
+            }
+        } else if key == self.key_config.scroll_down || key == Key::Down {
+            self.select_next();
+        } else if key == self.key_config.scroll_up || key == Key::Up {
+            self.select_previous();
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, Connection, ImportConnectionsComponent, KeyConfig};
+    use crate::event::Key;
+
+    #[test]
+    fn test_take_imported() {
+        let mut component = ImportConnectionsComponent::new(KeyConfig::default());
+        component
+            .open(vec![
+                (
+                    "~/.pgpass".to_string(),
+                    Connection::from_url("postgres://app@localhost:5432/app").unwrap(),
+                ),
+                (
+                    "DATABASE_URL".to_string(),
+                    Connection::from_url("mysql://root@localhost:3306").unwrap(),
+                ),
+            ])
+            .unwrap();
+        component.event(Key::Char(' ')).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(!component.visible);
+        assert_eq!(
+            component.take_imported().map(|connections| connections
+                .iter()
+                .map(|connection| connection.keyring_account())
+                .collect::<Vec<String>>()),
+            Some(vec!["mysql://root@localhost:3306".to_string()])
+        );
+        assert!(component.take_imported().is_none());
+    }
+}
@@ -0,0 +1,353 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::database::{insert_row_statement, is_nullable, Engine};
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// what is inserted into a column
+#[derive(Clone, Debug, PartialEq)]
+enum FieldValue {
+    /// the column is left out so that the server uses its default
+    Default,
+    Null,
+    Text(String),
+}
+
+/// a column of the table with the value entered for it
+struct Field {
+    name: String,
+    r#type: String,
+    nullable: bool,
+    default: Option<String>,
+    value: FieldValue,
+}
+
+enum Step {
+    Edit,
+    Preview(String),
+}
+
+/// a form with a field for each column of the table, which builds an INSERT and shows it before it runs
+pub struct InsertRowComponent {
+    engine: Engine,
+    table: String,
+    quote: char,
+    fields: Vec<Field>,
+    selected: usize,
+    step: Step,
+    error: Option<String>,
+    confirmed: Option<String>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl InsertRowComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            engine: Engine::MySql,
+            table: String::new(),
+            quote: '`',
+            fields: vec![],
+            selected: 0,
+            step: Step::Edit,
+            error: None,
+            confirmed: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens an empty form for `table`, where `columns` is a list of column names, types, nullabilities and defaults as the columns tab shows them
+    pub fn open(
+        &mut self,
+        engine: Engine,
+        table: String,
+        quote: char,
+        columns: Vec<(String, String, String, String)>,
+    ) -> Result<()> {
+        self.engine = engine;
+        self.table = table;
+        self.quote = quote;
+        self.fields = columns
+            .into_iter()
+            .map(|(name, r#type, null, default)| Field {
+                name,
+                r#type,
+                nullable: is_nullable(null.as_str()),
+                default: Some(default).filter(|default| !default.is_empty()),
+                value: FieldValue::Default,
+            })
+            .collect();
+        self.selected = 0;
+        self.step = Step::Edit;
+        self.error = None;
+        self.confirmed = None;
+        self.show()
+    }
+
+    /// returns the statement once the user confirmed its preview
+    pub fn take_statement(&mut self) -> Option<String> {
+        self.confirmed.take()
+    }
+
+    fn statement(&self) -> Result<String> {
+        let values = self
+            .fields
+            .iter()
+            .filter_map(|field| match &field.value {
+                FieldValue::Default => None,
+                FieldValue::Null => Some((field.name.clone(), None)),
+                FieldValue::Text(text) => Some((field.name.clone(), Some(text.clone()))),
+            })
+            .collect::<Vec<(String, Option<String>)>>();
+        insert_row_statement(self.engine, self.table.as_str(), self.quote, &values)
+    }
+
+    fn edit_event(&mut self, key: Key) {
+        if key == self.key_config.exit_popup {
+            self.hide();
+            return;
+        } else if key == self.key_config.enter {
+            match self.statement() {
+                Ok(statement) => self.step = Step::Preview(statement),
+                Err(err) => self.error = Some(err.to_string()),
+            }
+            return;
+        }
+        let len = self.fields.len();
+        let field = match self.fields.get_mut(self.selected) {
+            Some(field) => field,
+            None => return,
+        };
+        match key {
+            Key::Tab | Key::Down => self.selected = (self.selected + 1) % len,
+            Key::Up => self.selected = (self.selected + len - 1) % len,
+            Key::Ctrl('n') => field.value = FieldValue::Null,
+            Key::Ctrl('d') => field.value = FieldValue::Default,
+            Key::Char(c) => match &mut field.value {
+                FieldValue::Text(text) => text.push(c),
+                value => *value = FieldValue::Text(c.to_string()),
+            },
+            Key::Backspace | Key::Delete => match &mut field.value {
+                FieldValue::Text(text) if !text.is_empty() => {
+                    text.pop();
+                }
+                value => *value = FieldValue::Default,
+            },
+            _ => (),
+        }
+        self.error = None;
+    }
+
+    fn field_spans(&self, index: usize, label_width: usize) -> Spans<'static> {
+        let field = &self.fields[index];
+        let style = if index == self.selected {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        let value = match &field.value {
+            FieldValue::Default => Span::styled(
+                match &field.default {
+                    Some(default) => format!("DEFAULT ({})", default),
+                    None => "DEFAULT".to_string(),
+                },
+                Theme::current().hint().patch(style),
+            ),
+            FieldValue::Null => Span::styled("NULL", Theme::current().hint().patch(style)),
+            FieldValue::Text(text) => Span::styled(text.clone(), style),
+        };
+        Spans::from(vec![
+            Span::styled(
+                format!("{:w$}", field.name, w = label_width),
+                Theme::current().accent(),
+            ),
+            Span::styled(
+                format!(
+                    " {}{}: ",
+                    field.r#type,
+                    if field.nullable { "" } else { " NOT NULL" }
+                ),
+                Theme::current().hint(),
+            ),
+            value,
+        ])
+    }
+}
+
+impl DrawableComponent for InsertRowComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let block = Block::default()
+            .title(format!("{}: {}", Msg::InsertRow.text(), self.table))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick);
+        match &self.step {
+            Step::Edit => {
+                let label_width = self
+                    .fields
+                    .iter()
+                    .map(|field| field.name.width())
+                    .max()
+                    .unwrap_or(0);
+                let mut text = (0..self.fields.len())
+                    .map(|index| self.field_spans(index, label_width))
+                    .collect::<Vec<Spans>>();
+                text.push(Spans::from(""));
+                text.push(match &self.error {
+                    Some(error) => {
+                        Spans::from(Span::styled(error.clone(), Theme::current().error()))
+                    }
+                    None => Spans::from(Span::styled(
+                        format!(
+                            "[{}] NULL  [{}] DEFAULT  [{}] {}  [{}] {}",
+                            Key::Ctrl('n'),
+                            Key::Ctrl('d'),
+                            self.key_config.enter,
+                            Msg::Preview.text(),
+                            self.key_config.exit_popup,
+                            Msg::Cancel.text()
+                        ),
+                        Theme::current().hint(),
+                    )),
+                });
+                let area = popup_area(
+                    f,
+                    80,
+                    (text.len() as u16 + 2).min(f.size().height.saturating_sub(4)),
+                );
+                // keeps the selected field in view when there are more columns than lines
+                let scroll = (self.selected as u16).saturating_sub(area.height.saturating_sub(5));
+                f.render_widget(Paragraph::new(text).block(block).scroll((scroll, 0)), area);
+            }
+            Step::Preview(statement) => {
+                let text = vec![
+                    Spans::from(Span::raw(statement.clone())),
+                    Spans::from(""),
+                    Spans::from(Span::styled(
+                        format!(
+                            "[{}] {}  [{}] {}",
+                            self.key_config.enter,
+                            Msg::Insert.text(),
+                            self.key_config.exit_popup,
+                            Msg::Back.text()
+                        ),
+                        Theme::current().hint(),
+                    )),
+                ];
+                let area = popup_area(f, 80, 10);
+                f.render_widget(
+                    Paragraph::new(text).block(block).wrap(Wrap { trim: false }),
+                    area,
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Component for InsertRowComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        match &self.step {
+            Step::Edit => self.edit_event(key),
+            Step::Preview(statement) => {
+                if key == self.key_config.enter {
+                    self.confirmed = Some(statement.clone());
+                    self.hide();
+                } else if key == self.key_config.exit_popup {
+                    self.step = Step::Edit;
+                }
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, Engine, InsertRowComponent, KeyConfig};
+    use crate::event::Key;
+
+    fn columns() -> Vec<(String, String, String, String)> {
+        vec![
+            ("id", "int", "NO", ""),
+            ("name", "varchar(255)", "NO", ""),
+            ("note", "text", "YES", ""),
+            ("created_at", "datetime", "NO", "CURRENT_TIMESTAMP"),
+        ]
+        .into_iter()
+        .map(|(name, r#type, null, default)| {
+            (
+                name.to_string(),
+                r#type.to_string(),
+                null.to_string(),
+                default.to_string(),
+            )
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_take_statement() {
+        let mut component = InsertRowComponent::new(KeyConfig::default());
+        component
+            .open(Engine::MySql, "`shop`.`users`".to_string(), '`', columns())
+            .unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(component.error.is_some());
+
+        component.event(Key::Down).unwrap();
+        for c in "Bob".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        component.event(Key::Backspace).unwrap();
+        component.event(Key::Down).unwrap();
+        component.event(Key::Ctrl('n')).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(component.take_statement(), None);
+
+        component.event(KeyConfig::default().exit_popup).unwrap();
+        assert!(component.visible);
+        component.event(Key::Ctrl('d')).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(!component.visible);
+        assert_eq!(
+            component.take_statement(),
+            Some("INSERT INTO `shop`.`users` (`name`) VALUES ('Bo')".to_string())
+        );
+    }
+}
@@ -1,25 +1,97 @@
+pub mod cell_value;
+pub mod child_rows;
+pub mod column_picker;
+pub mod column_quality;
+pub mod column_stats;
 pub mod command;
+pub mod confirm_statement;
+pub mod connection_form;
 pub mod connections;
+pub mod copy_records;
+pub mod csv_import;
+pub mod data_generator;
 pub mod databases;
+pub mod dump;
+pub mod erd;
 pub mod error;
+pub mod execution_log;
+pub mod export_view;
+pub mod file_picker;
+pub mod footer;
 pub mod help;
+pub mod import_connections;
+pub mod insert_row;
+pub mod password_prompt;
+pub mod pipe_command;
+pub mod popup_stack;
+pub mod query_plan;
+pub mod queue;
+pub mod recent_databases;
+pub mod record_detail;
 pub mod record_table;
+pub mod referenced_tables;
+pub mod routine_form;
+pub mod run_file;
+pub mod scheduled_jobs;
+pub mod schema_snapshot;
+pub mod search_matches;
+pub mod session_settings;
+pub mod snippets;
+pub mod sql_editor;
 pub mod tab;
 pub mod table;
+pub mod table_definition;
 pub mod table_filter;
+pub mod table_finder;
 pub mod table_status;
 pub mod table_value;
 pub mod utils;
 
+pub use cell_value::CellValueComponent;
+pub use child_rows::{ChildRows, ChildRowsComponent};
+pub use column_picker::ColumnPickerComponent;
+pub use column_quality::ColumnQualityComponent;
+pub use column_stats::ColumnStatsComponent;
 pub use command::{CommandInfo, CommandText};
+pub use confirm_statement::ConfirmStatementComponent;
+pub use connection_form::ConnectionFormComponent;
 pub use connections::ConnectionsComponent;
+pub use copy_records::CopyRecordsComponent;
+pub use csv_import::{CsvImportComponent, CsvRows};
+pub use data_generator::DataGeneratorComponent;
 pub use databases::DatabasesComponent;
+pub use dump::DumpComponent;
+pub use erd::ErdComponent;
 pub use error::ErrorComponent;
+pub use execution_log::ExecutionLogComponent;
+pub use export_view::ExportViewComponent;
+pub use file_picker::FilePickerComponent;
+pub use footer::{FooterComponent, InputMode};
 pub use help::HelpComponent;
+pub use import_connections::ImportConnectionsComponent;
+pub use insert_row::InsertRowComponent;
+pub use password_prompt::PasswordPromptComponent;
+pub use pipe_command::PipeCommandComponent;
+pub use popup_stack::{popup_area, Popup, PopupStack};
+pub use query_plan::QueryPlanComponent;
+pub use queue::QueueComponent;
+pub use recent_databases::RecentDatabasesComponent;
+pub use record_detail::RecordDetailComponent;
 pub use record_table::RecordTableComponent;
+pub use referenced_tables::ReferencedTablesComponent;
+pub use routine_form::RoutineFormComponent;
+pub use run_file::{file_statements, RunFileComponent, StatementLog};
+pub use scheduled_jobs::ScheduledJobsComponent;
+pub use schema_snapshot::{SchemaSnapshotComponent, SnapshotAction};
+pub use search_matches::SearchMatchesComponent;
+pub use session_settings::SessionSettingsComponent;
+pub use snippets::SnippetsComponent;
+pub use sql_editor::SqlEditorComponent;
 pub use tab::TabComponent;
 pub use table::TableComponent;
+pub use table_definition::TableDefinitionComponent;
 pub use table_filter::TableFilterComponent;
+pub use table_finder::TableFinderComponent;
 pub use table_status::TableStatusComponent;
 pub use table_value::TableValueComponent;
 
@@ -62,6 +134,11 @@ pub trait Component {
 
     fn event(&mut self, key: crate::event::Key) -> Result<EventState>;
 
+    /// handles a click or a turn of the wheel, which is consumed when it happened on the component
+    fn mouse_event(&mut self, _mouse: crate::event::Mouse) -> Result<EventState> {
+        Ok(EventState::NotConsumed)
+    }
+
     fn focused(&self) -> bool {
         false
     }
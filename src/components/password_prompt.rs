@@ -0,0 +1,164 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::{Connection, KeyConfig};
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+/// asks for the password of a connection which reads it from the system keyring but has none stored there yet
+pub struct PasswordPromptComponent {
+    connection: Option<Connection>,
+    input: String,
+    /// why connecting with the last password failed
+    error: Option<String>,
+    submitted: bool,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl PasswordPromptComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            connection: None,
+            input: String::new(),
+            error: None,
+            submitted: false,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn open(&mut self, connection: Connection, error: Option<String>) -> Result<()> {
+        self.connection = Some(connection);
+        self.input.clear();
+        self.error = error;
+        self.submitted = false;
+        self.show()
+    }
+
+    /// returns the connection and the password once the user entered it
+    pub fn take_password(&mut self) -> Option<(Connection, String)> {
+        if !std::mem::take(&mut self.submitted) {
+            return None;
+        }
+        self.connection
+            .take()
+            .map(|connection| (connection, std::mem::take(&mut self.input)))
+    }
+}
+
+impl DrawableComponent for PasswordPromptComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let area = popup_area(f, 60, 4);
+        let message = match self.error.as_ref() {
+            Some(error) => Span::styled(error.clone(), Theme::current().error()),
+            None => Span::styled(Msg::SaveToKeyring.text(), Theme::current().hint()),
+        };
+        f.render_widget(
+            Paragraph::new(vec![
+                Spans::from(Span::raw("*".repeat(self.input.chars().count()))),
+                Spans::from(message),
+            ])
+            .block(
+                Block::default()
+                    .title(format!(
+                        "{}: {}",
+                        Msg::EnterPassword.text(),
+                        self.connection
+                            .as_ref()
+                            .map_or(String::new(), |connection| connection.keyring_account())
+                    ))
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick),
+            ),
+            area,
+        );
+        f.set_cursor(area.x + 1 + self.input.chars().count() as u16, area.y + 1);
+        Ok(())
+    }
+}
+
+impl Component for PasswordPromptComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            self.submitted = !self.input.is_empty();
+            if self.submitted {
+                self.hide();
+            }
+        } else {
+            match key {
+                Key::Char(c) => self.input.push(c),
+                Key::Backspace | Key::Delete => {
+                    self.input.pop();
+                }
+                _ => (),
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, Connection, KeyConfig, PasswordPromptComponent};
+    use crate::event::Key;
+
+    #[test]
+    fn test_take_password() {
+        let mut component = PasswordPromptComponent::new(KeyConfig::default());
+        let connection = Connection::from_url("postgres://app@localhost:5432/app").unwrap();
+        component.open(connection, None).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(component.visible);
+        assert!(component.take_password().is_none());
+
+        for c in "s3cret".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        component.event(Key::Backspace).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(!component.visible);
+        assert_eq!(
+            component
+                .take_password()
+                .map(|(connection, password)| (connection.keyring_account(), password)),
+            Some((
+                "postgres://app@localhost:5432".to_string(),
+                "s3cre".to_string()
+            ))
+        );
+        assert!(component.take_password().is_none());
+    }
+}
@@ -0,0 +1,237 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+pub struct PipeCommandComponent {
+    input: String,
+    text: String,
+    submitted: bool,
+    output: Option<String>,
+    scroll: u16,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl PipeCommandComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            input: String::new(),
+            text: String::new(),
+            submitted: false,
+            output: None,
+            scroll: 0,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens the prompt for a command to pipe `text` to, keeping the last command
+    pub fn open(&mut self, text: String) -> Result<()> {
+        self.text = text;
+        self.submitted = false;
+        self.output = None;
+        self.show()
+    }
+
+    /// returns the command and the text to pipe once the user entered the command
+    pub fn take_command(&mut self) -> Option<(String, String)> {
+        if !std::mem::take(&mut self.submitted) {
+            return None;
+        }
+        Some((self.input.clone(), self.text.clone()))
+    }
+
+    /// shows the output of the command, or closes the popup when it printed nothing
+    pub fn set_output(&mut self, output: String) {
+        if output.trim().is_empty() {
+            self.hide();
+        } else {
+            self.output = Some(output);
+            self.scroll = 0;
+        }
+    }
+
+    fn line_count(&self) -> u16 {
+        self.output
+            .as_ref()
+            .map_or(0, |output| output.lines().count()) as u16
+    }
+
+    fn scroll_down(&mut self, lines: u16) {
+        self.scroll = self
+            .scroll
+            .saturating_add(lines)
+            .min(self.line_count().saturating_sub(1));
+    }
+
+    fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    fn output_event(&mut self, key: Key) {
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.scroll_down {
+            self.scroll_down(1);
+        } else if key == self.key_config.scroll_up {
+            self.scroll_up(1);
+        } else if key == self.key_config.scroll_down_multiple_lines {
+            self.scroll_down(10);
+        } else if key == self.key_config.scroll_up_multiple_lines {
+            self.scroll_up(10);
+        } else if key == self.key_config.scroll_to_top {
+            self.scroll = 0;
+        } else if key == self.key_config.scroll_to_bottom {
+            self.scroll = self.line_count().saturating_sub(1);
+        }
+    }
+
+    fn input_event(&mut self, key: Key) {
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            self.submitted = !self.input.trim().is_empty();
+        } else {
+            match key {
+                Key::Char(c) => self.input.push(c),
+                Key::Backspace | Key::Delete => {
+                    self.input.pop();
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+impl DrawableComponent for PipeCommandComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick);
+        match self.output.as_ref() {
+            Some(output) => {
+                let area = popup_area(
+                    f,
+                    f.size().width.saturating_sub(10),
+                    f.size().height.saturating_sub(6),
+                );
+                f.render_widget(
+                    Paragraph::new(output.as_str())
+                        .block(block.title(format!(
+                            "{}: {}",
+                            Msg::CommandOutput.text(),
+                            self.input
+                        )))
+                        .scroll((self.scroll, 0)),
+                    area,
+                );
+            }
+            None => {
+                const SIZE: (u16, u16) = (60, 3);
+                let area = popup_area(f, SIZE.0, SIZE.1);
+                let text = if self.input.is_empty() {
+                    Spans::from(Span::styled(
+                        Msg::PipeCommandPlaceholder.text(),
+                        Theme::current().hint(),
+                    ))
+                } else {
+                    Spans::from(Span::raw(format!("| {}", self.input)))
+                };
+                f.render_widget(
+                    Paragraph::new(text).block(block.title(Msg::PipeToCommand.text())),
+                    area,
+                );
+                f.set_cursor(
+                    area.x
+                        + 1
+                        + if self.input.is_empty() {
+                            0
+                        } else {
+                            2 + self.input.width() as u16
+                        },
+                    area.y + 1,
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Component for PipeCommandComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if self.output.is_some() {
+            self.output_event(key);
+        } else {
+            self.input_event(key);
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, KeyConfig, PipeCommandComponent};
+    use crate::event::Key;
+
+    #[test]
+    fn test_take_command() {
+        let mut component = PipeCommandComponent::new(KeyConfig::default());
+        component.open("a\tb".to_string()).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(component.take_command(), None);
+
+        for c in "wc -l".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(
+            component.take_command(),
+            Some(("wc -l".to_string(), "a\tb".to_string()))
+        );
+        assert_eq!(component.take_command(), None);
+
+        component.set_output("1\n".to_string());
+        assert!(component.visible);
+        component.event(KeyConfig::default().exit_popup).unwrap();
+        assert!(!component.visible);
+
+        component.open("c".to_string()).unwrap();
+        assert_eq!(component.input, "wc -l");
+        component.set_output(String::new());
+        assert!(!component.visible);
+    }
+}
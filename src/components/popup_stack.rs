@@ -0,0 +1,99 @@
+use tui::{backend::Backend, layout::Rect, widgets::Clear, Frame};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Popup {
+    Error,
+    Help,
+    RecordDetail,
+    DataGenerator,
+    ConnectionForm,
+    PipeCommand,
+    RecentDatabases,
+    RunFile,
+    FilePicker,
+    SchemaSnapshot,
+    CsvImport,
+    SessionSettings,
+    ExportView,
+    InsertRow,
+    ConfirmStatement,
+    ColumnStats,
+    Snippets,
+    CellValue,
+    Queue,
+    ReferencedTables,
+    TableFinder,
+    ScheduledJobs,
+    PasswordPrompt,
+    CopyRecords,
+    ImportConnections,
+    ColumnPicker,
+    ChildRows,
+    RoutineForm,
+    ColumnQuality,
+    SearchMatches,
+    ExecutionLog,
+    Erd,
+    Dump,
+}
+
+/// keeps visible popups in the order they were opened, so the newest one is drawn last and receives keys first
+#[derive(Default)]
+pub struct PopupStack {
+    popups: Vec<Popup>,
+}
+
+impl PopupStack {
+    /// pushes a popup which has become visible and removes one which has been hidden
+    pub fn sync(&mut self, popup: Popup, visible: bool) {
+        match (visible, self.popups.iter().position(|p| *p == popup)) {
+            (true, None) => self.popups.push(popup),
+            (false, Some(index)) => {
+                self.popups.remove(index);
+            }
+            _ => (),
+        }
+    }
+
+    pub fn top(&self) -> Option<Popup> {
+        self.popups.last().copied()
+    }
+
+    /// returns the popups from the bottom to the top
+    pub fn popups(&self) -> Vec<Popup> {
+        self.popups.clone()
+    }
+}
+
+/// clears a centered area of the given size for a popup and returns it
+pub fn popup_area<B: Backend>(f: &mut Frame<B>, width: u16, height: u16) -> Rect {
+    let area = Rect::new(
+        (f.size().width.saturating_sub(width)) / 2,
+        (f.size().height.saturating_sub(height)) / 2,
+        width.min(f.size().width),
+        height.min(f.size().height),
+    );
+    f.render_widget(Clear, area);
+    area
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Popup, PopupStack};
+
+    #[test]
+    fn test_sync() {
+        let mut stack = PopupStack::default();
+        assert_eq!(stack.top(), None);
+        stack.sync(Popup::Help, true);
+        stack.sync(Popup::RecordDetail, false);
+        stack.sync(Popup::Error, true);
+        stack.sync(Popup::Help, true);
+        assert_eq!(stack.top(), Some(Popup::Error));
+        assert_eq!(stack.popups(), vec![Popup::Help, Popup::Error]);
+        stack.sync(Popup::Error, false);
+        assert_eq!(stack.top(), Some(Popup::Help));
+        stack.sync(Popup::Help, false);
+        assert_eq!(stack.top(), None);
+    }
+}
@@ -0,0 +1,212 @@
+use super::{Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::database::PlanNode;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use std::collections::HashSet;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, Borders, List, ListItem, ListState},
+    Frame,
+};
+
+// ▸
+const COLLAPSED_ICON: &str = "\u{25b8}";
+// ▾
+const EXPANDED_ICON: &str = "\u{25be}";
+
+/// a step of the plan with the lines connecting it to its ancestors
+struct PlanLine {
+    prefix: String,
+    label: String,
+    descendants: usize,
+}
+
+/// shows a query plan as a tree whose steps can be collapsed
+pub struct QueryPlanComponent {
+    lines: Vec<PlanLine>,
+    collapsed: HashSet<usize>,
+    selected: usize,
+    state: ListState,
+    key_config: KeyConfig,
+}
+
+impl QueryPlanComponent {
+    pub fn new(key_config: KeyConfig, plan: &[PlanNode]) -> Self {
+        let mut lines = vec![];
+        for node in plan {
+            flatten(node, String::new(), String::new(), &mut lines);
+        }
+        Self {
+            lines,
+            collapsed: HashSet::new(),
+            selected: 0,
+            state: ListState::default(),
+            key_config,
+        }
+    }
+
+    pub fn selected_label(&self) -> Option<String> {
+        self.lines.get(self.selected).map(|line| line.label.clone())
+    }
+
+    /// returns the indexes of the lines outside of collapsed steps
+    fn visible_lines(&self) -> Vec<usize> {
+        let mut visible = vec![];
+        let mut index = 0;
+        while index < self.lines.len() {
+            visible.push(index);
+            if self.collapsed.contains(&index) {
+                index += self.lines[index].descendants;
+            }
+            index += 1;
+        }
+        visible
+    }
+
+    fn move_selection(&mut self, lines: i64) {
+        let visible = self.visible_lines();
+        if let Some(position) = visible.iter().position(|index| *index == self.selected) {
+            let position = (position as i64 + lines).clamp(0, visible.len() as i64 - 1);
+            self.selected = visible[position as usize];
+        }
+    }
+
+    fn toggle_collapsed(&mut self) {
+        if matches!(self.lines.get(self.selected), Some(line) if line.descendants > 0)
+            && !self.collapsed.remove(&self.selected)
+        {
+            self.collapsed.insert(self.selected);
+        }
+    }
+}
+
+fn flatten(node: &PlanNode, prefix: String, children_prefix: String, lines: &mut Vec<PlanLine>) {
+    let index = lines.len();
+    lines.push(PlanLine {
+        prefix,
+        label: node.label.clone(),
+        descendants: 0,
+    });
+    for (i, child) in node.children.iter().enumerate() {
+        let last = i + 1 == node.children.len();
+        flatten(
+            child,
+            format!("{}{}", children_prefix, if last { "└─ " } else { "├─ " }),
+            format!("{}{}", children_prefix, if last { "   " } else { "│  " }),
+            lines,
+        );
+    }
+    lines[index].descendants = lines.len() - index - 1;
+}
+
+impl DrawableComponent for QueryPlanComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, area: Rect, focused: bool) -> Result<()> {
+        let visible = self.visible_lines();
+        let items = visible
+            .iter()
+            .map(|index| {
+                let line = &self.lines[*index];
+                let icon = if line.descendants == 0 {
+                    " "
+                } else if self.collapsed.contains(index) {
+                    COLLAPSED_ICON
+                } else {
+                    EXPANDED_ICON
+                };
+                ListItem::new(Spans::from(vec![
+                    Span::styled(line.prefix.clone(), Theme::current().hint()),
+                    Span::raw(format!("{} {}", icon, line.label)),
+                ]))
+            })
+            .collect::<Vec<ListItem>>();
+        self.state
+            .select(visible.iter().position(|index| *index == self.selected));
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(Msg::QueryPlan.text())
+                    .borders(Borders::ALL),
+            )
+            .style(Theme::current().text(focused))
+            .highlight_style(Theme::current().selected());
+        f.render_stateful_widget(list, area, &mut self.state);
+        Ok(())
+    }
+}
+
+impl Component for QueryPlanComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if key == self.key_config.scroll_down {
+            self.move_selection(1);
+        } else if key == self.key_config.scroll_up {
+            self.move_selection(-1);
+        } else if key == self.key_config.scroll_down_multiple_lines {
+            self.move_selection(10);
+        } else if key == self.key_config.scroll_up_multiple_lines {
+            self.move_selection(-10);
+        } else if key == self.key_config.scroll_to_top {
+            self.move_selection(-(self.lines.len() as i64));
+        } else if key == self.key_config.scroll_to_bottom {
+            self.move_selection(self.lines.len() as i64);
+        } else if key == self.key_config.enter {
+            self.toggle_collapsed();
+        } else {
+            return Ok(EventState::NotConsumed);
+        }
+        Ok(EventState::Consumed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, KeyConfig, PlanNode, QueryPlanComponent};
+
+    #[test]
+    fn test_tree() {
+        let plan = vec![PlanNode {
+            label: "Hash Join".to_string(),
+            children: vec![
+                PlanNode {
+                    label: "Seq Scan on a".to_string(),
+                    children: vec![PlanNode::new("Filter".to_string())],
+                },
+                PlanNode::new("Seq Scan on b".to_string()),
+            ],
+        }];
+        let mut component = QueryPlanComponent::new(KeyConfig::default(), &plan);
+        assert_eq!(
+            component
+                .lines
+                .iter()
+                .map(|line| format!("{}{}", line.prefix, line.label))
+                .collect::<Vec<String>>(),
+            vec![
+                "Hash Join",
+                "├─ Seq Scan on a",
+                "│  └─ Filter",
+                "└─ Seq Scan on b"
+            ]
+        );
+
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(component.visible_lines(), vec![0, 1, 3]);
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        assert_eq!(
+            component.selected_label(),
+            Some("Seq Scan on b".to_string())
+        );
+
+        component.event(KeyConfig::default().scroll_to_top).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(component.visible_lines(), vec![0]);
+    }
+}
@@ -0,0 +1,299 @@
+use super::{popup_area, Component, DrawableComponent, EventState, StatementLog};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use itertools::Itertools;
+use std::time::Instant;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+enum QueueStatus {
+    Pending,
+    Running(Instant),
+    Finished(StatementLog),
+    Cancelled,
+}
+
+struct QueueItem {
+    id: usize,
+    statement: String,
+    status: QueueStatus,
+}
+
+impl QueueItem {
+    fn spans(&self) -> Spans<'static> {
+        let statement = self.statement.split_whitespace().join(" ");
+        match &self.status {
+            QueueStatus::Pending => Spans::from(vec![
+                Span::styled("·", Theme::current().hint()),
+                Span::raw(format!(" {:>9}  {}", Msg::Pending.text(), statement)),
+            ]),
+            QueueStatus::Running(started) => Spans::from(vec![
+                Span::styled("▶", Theme::current().accent()),
+                Span::raw(format!(
+                    " {:>7}ms  {}",
+                    started.elapsed().as_millis(),
+                    statement
+                )),
+            ]),
+            QueueStatus::Finished(log) => log.spans(),
+            QueueStatus::Cancelled => Spans::from(vec![
+                Span::styled("✘", Theme::current().hint()),
+                Span::raw(format!(" {:>9}  {}", Msg::Cancelled.text(), statement)),
+            ]),
+        }
+    }
+}
+
+/// the statements queued to run one after another in the background, with how each of them ended
+pub struct QueueComponent {
+    items: Vec<QueueItem>,
+    next_id: usize,
+    state: ListState,
+    /// the running item whose cancellation was asked for
+    cancel: Option<usize>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl QueueComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            items: vec![],
+            next_id: 0,
+            state: ListState::default(),
+            cancel: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn push(&mut self, statement: String) {
+        self.items.push(QueueItem {
+            id: self.next_id,
+            statement,
+            status: QueueStatus::Pending,
+        });
+        self.next_id += 1;
+        if self.state.selected().is_none() {
+            self.state.select(Some(0));
+        }
+    }
+
+    /// marks the first pending item as running and returns its id and statement
+    pub fn start_next(&mut self) -> Option<(usize, String)> {
+        let item = self
+            .items
+            .iter_mut()
+            .find(|item| matches!(item.status, QueueStatus::Pending))?;
+        item.status = QueueStatus::Running(Instant::now());
+        Some((item.id, item.statement.clone()))
+    }
+
+    pub fn finish(&mut self, id: usize, log: Option<StatementLog>) {
+        if let Some(item) = self.items.iter_mut().find(|item| item.id == id) {
+            item.status = match log {
+                Some(log) => QueueStatus::Finished(log),
+                None => QueueStatus::Cancelled,
+            };
+        }
+    }
+
+    /// returns the id of the running item which the user asked to cancel
+    pub fn take_cancel(&mut self) -> Option<usize> {
+        self.cancel.take()
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.state.select(None);
+        self.cancel = None;
+    }
+
+    fn pending_count(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| matches!(item.status, QueueStatus::Pending))
+            .count()
+    }
+
+    /// cancels the selected item, or removes it from the list once it is no longer pending nor running
+    fn cancel_selected(&mut self) {
+        let index = match self.state.selected() {
+            Some(index) if index < self.items.len() => index,
+            _ => return,
+        };
+        let item = &mut self.items[index];
+        match item.status {
+            QueueStatus::Pending => item.status = QueueStatus::Cancelled,
+            QueueStatus::Running(_) => self.cancel = Some(item.id),
+            QueueStatus::Finished(_) | QueueStatus::Cancelled => {
+                self.items.remove(index);
+                self.state.select(if self.items.is_empty() {
+                    None
+                } else {
+                    Some(index.min(self.items.len() - 1))
+                });
+            }
+        }
+    }
+
+    fn select_next(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state
+                .select(Some((i + 1).min(self.items.len().saturating_sub(1))));
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(i.saturating_sub(1)));
+        }
+    }
+}
+
+impl DrawableComponent for QueueComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let area = popup_area(
+            f,
+            f.size().width.saturating_sub(10),
+            f.size().height.saturating_sub(6),
+        );
+        f.render_widget(
+            Block::default()
+                .title(format!(
+                    "{} ({} {})",
+                    Msg::Queue.text(),
+                    self.pending_count(),
+                    Msg::Pending.text()
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick),
+            area,
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(area);
+        let items = self
+            .items
+            .iter()
+            .map(|item| ListItem::new(item.spans()))
+            .collect::<Vec<ListItem>>();
+        f.render_stateful_widget(
+            List::new(items).highlight_style(Theme::current().selected()),
+            chunks[0],
+            &mut self.state,
+        );
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                format!(
+                    "[{}] {}  [{}] {}",
+                    self.key_config.cancel_queued,
+                    Msg::CancelQueued.text(),
+                    self.key_config.exit_popup,
+                    Msg::Close.text()
+                ),
+                Theme::current().hint(),
+            )),
+            chunks[1],
+        );
+        Ok(())
+    }
+}
+
+impl Component for QueueComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.cancel_queued {
+            self.cancel_selected();
+        } else if key == self.key_config.scroll_down || key == Key::Down {
+            self.select_next();
+        } else if key == self.key_config.scroll_up || key == Key::Up {
+            self.select_previous();
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, KeyConfig, QueueComponent, QueueStatus, StatementLog};
+    use crate::database::ExecuteResult;
+    use std::time::Duration;
+
+    #[test]
+    fn test_queue() {
+        let mut component = QueueComponent::new(KeyConfig::default());
+        component.show().unwrap();
+        component.push("ANALYZE users".to_string());
+        component.push("ANALYZE posts".to_string());
+        component.push("ANALYZE tags".to_string());
+        assert_eq!(component.pending_count(), 3);
+
+        assert_eq!(
+            component.start_next(),
+            Some((0, "ANALYZE users".to_string()))
+        );
+        component.event(KeyConfig::default().cancel_queued).unwrap();
+        assert_eq!(component.take_cancel(), Some(0));
+        assert_eq!(component.take_cancel(), None);
+        component.finish(0, None);
+        assert!(matches!(component.items[0].status, QueueStatus::Cancelled));
+
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(KeyConfig::default().cancel_queued).unwrap();
+        assert_eq!(component.pending_count(), 1);
+
+        assert_eq!(
+            component.start_next(),
+            Some((2, "ANALYZE tags".to_string()))
+        );
+        component.finish(
+            2,
+            Some(StatementLog::new(
+                "ANALYZE tags".to_string(),
+                Duration::from_millis(2),
+                &Ok(ExecuteResult::Write { updated_rows: 0 }),
+            )),
+        );
+        assert_eq!(component.start_next(), None);
+
+        component.event(KeyConfig::default().scroll_up).unwrap();
+        component.event(KeyConfig::default().cancel_queued).unwrap();
+        assert_eq!(component.items.len(), 2);
+        assert_eq!(component.state.selected(), Some(0));
+    }
+}
@@ -0,0 +1,218 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use database_tree::{Database, Table};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState},
+    Frame,
+};
+
+/// a database and the table last opened in it
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecentDatabase {
+    pub database: String,
+    pub schema: Option<String>,
+    pub table: String,
+}
+
+/// lists the databases of the connection from the most recently used one
+pub struct RecentDatabasesComponent {
+    recent: Vec<RecentDatabase>,
+    state: ListState,
+    chosen: Option<RecentDatabase>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl RecentDatabasesComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            recent: vec![],
+            state: ListState::default(),
+            chosen: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// moves the database to the top, remembering the table opened in it
+    pub fn push(&mut self, database: &Database, table: &Table) {
+        self.recent
+            .retain(|recent| recent.database != database.name);
+        self.recent.insert(
+            0,
+            RecentDatabase {
+                database: database.name.clone(),
+                schema: table.schema.clone(),
+                table: table.name.clone(),
+            },
+        );
+    }
+
+    pub fn clear(&mut self) {
+        self.recent.clear();
+    }
+
+    /// opens the list with the previous database selected, so that choosing it flips between the last two
+    pub fn open(&mut self) -> Result<()> {
+        if self.recent.is_empty() {
+            return Ok(());
+        }
+        self.state.select(Some(1.min(self.recent.len() - 1)));
+        self.chosen = None;
+        self.show()
+    }
+
+    /// returns the database the user chose
+    pub fn take_chosen(&mut self) -> Option<RecentDatabase> {
+        self.chosen.take()
+    }
+
+    fn select_next(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some((i + 1) % self.recent.len()));
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state
+                .select(Some((i + self.recent.len() - 1) % self.recent.len()));
+        }
+    }
+}
+
+impl DrawableComponent for RecentDatabasesComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let items = self
+                .recent
+                .iter()
+                .map(|recent| {
+                    ListItem::new(Spans::from(vec![
+                        Span::raw(recent.database.clone()),
+                        Span::styled(
+                            format!(
+                                " {}",
+                                recent.schema.as_ref().map_or_else(
+                                    || recent.table.clone(),
+                                    |schema| format!("{}.{}", schema, recent.table)
+                                )
+                            ),
+                            Theme::current().hint(),
+                        ),
+                    ]))
+                })
+                .collect::<Vec<ListItem>>();
+            let area = popup_area(f, 50, (self.recent.len() as u16).saturating_add(2).min(20));
+            f.render_stateful_widget(
+                List::new(items)
+                    .block(
+                        Block::default()
+                            .title(Msg::RecentDatabases.text())
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick),
+                    )
+                    .highlight_style(Theme::current().selected()),
+                area,
+                &mut self.state,
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for RecentDatabasesComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            self.chosen = self
+                .state
+                .selected()
+                .and_then(|i| self.recent.get(i))
+                .cloned();
+            self.hide();
+        } else if key == self.key_config.scroll_down || key == self.key_config.recent_databases {
+            self.select_next();
+        } else if key == self.key_config.scroll_up {
+            self.select_previous();
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, KeyConfig, RecentDatabase, RecentDatabasesComponent};
+    use database_tree::{Database, Table};
+
+    fn table(name: &str) -> Table {
+        Table {
+            name: name.to_string(),
+            create_time: None,
+            update_time: None,
+            engine: None,
+            schema: None,
+            view: false,
+        }
+    }
+
+    #[test]
+    fn test_choose_previous_database() {
+        let mut component = RecentDatabasesComponent::new(KeyConfig::default());
+        component.open().unwrap();
+        assert!(!component.visible);
+
+        component.push(&Database::new("a".to_string(), vec![]), &table("users"));
+        component.push(&Database::new("b".to_string(), vec![]), &table("posts"));
+        component.push(&Database::new("a".to_string(), vec![]), &table("items"));
+        component.open().unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(
+            component.take_chosen(),
+            Some(RecentDatabase {
+                database: "b".to_string(),
+                schema: None,
+                table: "posts".to_string(),
+            })
+        );
+        assert!(!component.visible);
+
+        component.open().unwrap();
+        component
+            .event(KeyConfig::default().recent_databases)
+            .unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(
+            component.take_chosen().map(|recent| recent.table),
+            Some("items".to_string())
+        );
+    }
+}
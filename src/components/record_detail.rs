@@ -0,0 +1,237 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+pub struct RecordDetailComponent {
+    headers: Vec<String>,
+    row: Vec<String>,
+    visible: bool,
+    scroll: u16,
+    key_config: KeyConfig,
+}
+
+impl RecordDetailComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            headers: vec![],
+            row: vec![],
+            visible: false,
+            scroll: 0,
+            key_config,
+        }
+    }
+
+    pub fn set(&mut self, headers: Vec<String>, row: Vec<String>) -> Result<()> {
+        self.headers = headers;
+        self.row = row;
+        self.scroll = 0;
+        self.show()
+    }
+
+    fn get_text(&self) -> Vec<Spans<'static>> {
+        let name_width = self
+            .headers
+            .iter()
+            .map(|header| header.width())
+            .max()
+            .unwrap_or(0);
+        let mut txt = Vec::new();
+        for (header, value) in self.headers.iter().zip(self.row.iter()) {
+            let mut lines = value.lines();
+            txt.push(Spans::from(vec![
+                Span::styled(
+                    format!("{:w$}", header, w = name_width),
+                    Theme::current().accent().patch(Theme::current().header()),
+                ),
+                Span::raw(format!(": {}", lines.next().unwrap_or_default())),
+            ]));
+            for line in lines {
+                txt.push(Spans::from(Span::raw(format!(
+                    "{:w$}  {}",
+                    "",
+                    line,
+                    w = name_width
+                ))));
+            }
+            for (index, element) in array_elements(value).unwrap_or_default().iter().enumerate() {
+                txt.push(Spans::from(vec![
+                    Span::raw(format!("{:w$}  ", "", w = name_width)),
+                    Span::styled(format!("[{}] ", index + 1), Theme::current().hint()),
+                    Span::raw(element.to_string()),
+                ]));
+            }
+        }
+        txt
+    }
+
+    fn line_count(&self) -> u16 {
+        self.row
+            .iter()
+            .map(|value| {
+                value.lines().count().max(1) + array_elements(value).map_or(0, |e| e.len())
+            })
+            .sum::<usize>() as u16
+    }
+
+    fn scroll_down(&mut self, lines: u16) {
+        self.scroll = self
+            .scroll
+            .saturating_add(lines)
+            .min(self.line_count().saturating_sub(1));
+    }
+
+    fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+}
+
+impl DrawableComponent for RecordDetailComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            const SIZE: (u16, u16) = (80, 30);
+            let area = popup_area(f, SIZE.0, SIZE.1);
+            f.render_widget(
+                Paragraph::new(self.get_text())
+                    .block(
+                        Block::default()
+                            .title(Msg::RecordDetail.text())
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick),
+                    )
+                    .scroll((self.scroll, 0)),
+                area,
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for RecordDetailComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.visible {
+            if key == self.key_config.exit_popup {
+                self.hide();
+            } else if key == self.key_config.scroll_down {
+                self.scroll_down(1);
+            } else if key == self.key_config.scroll_up {
+                self.scroll_up(1);
+            } else if key == self.key_config.scroll_down_multiple_lines {
+                self.scroll_down(10);
+            } else if key == self.key_config.scroll_up_multiple_lines {
+                self.scroll_up(10);
+            } else if key == self.key_config.scroll_to_top {
+                self.scroll = 0;
+            } else if key == self.key_config.scroll_to_bottom {
+                self.scroll = self.line_count().saturating_sub(1);
+            }
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+/// splits a postgres array literal such as `{a,"b c",{d,e}}` into its top-level elements
+fn array_elements(value: &str) -> Option<Vec<String>> {
+    if !value.starts_with('{')
+        || !value.ends_with('}')
+        || serde_json::from_str::<serde_json::Value>(value).is_ok()
+    {
+        return None;
+    }
+    let inner = &value[1..value.len() - 1];
+    if inner.is_empty() {
+        return None;
+    }
+    let mut elements = vec![];
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for c in inner.chars() {
+        if escaped {
+            current.push(c);
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '{' | '(' if !quoted => {
+                depth += 1;
+                current.push(c);
+            }
+            '}' | ')' if !quoted => {
+                depth -= 1;
+                current.push(c);
+            }
+            ',' if !quoted && depth == 0 => elements.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    elements.push(current);
+    Some(elements)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{array_elements, KeyConfig, RecordDetailComponent};
+
+    #[test]
+    fn test_array_elements() {
+        assert_eq!(
+            array_elements(r#"{a,"b c",{d,e}}"#),
+            Some(vec![
+                "a".to_string(),
+                "b c".to_string(),
+                "{d,e}".to_string()
+            ])
+        );
+        assert_eq!(array_elements(r#"{"a": 1}"#), None);
+        assert_eq!(array_elements("{}"), None);
+        assert_eq!(array_elements("abc"), None);
+    }
+
+    #[test]
+    fn test_scroll() {
+        let mut component = RecordDetailComponent::new(KeyConfig::default());
+        component
+            .set(
+                vec!["id".to_string(), "note".to_string()],
+                vec!["1".to_string(), "a\nb\nc".to_string()],
+            )
+            .unwrap();
+        assert_eq!(component.line_count(), 4);
+        component.scroll_down(10);
+        assert_eq!(component.scroll, 3);
+        component.scroll_up(1);
+        assert_eq!(component.scroll, 2);
+    }
+}
@@ -1,10 +1,12 @@
 use super::{Component, DrawableComponent, EventState};
-use crate::components::command::CommandInfo;
+use crate::components::command::{self, CommandInfo};
 use crate::components::{TableComponent, TableFilterComponent};
-use crate::config::KeyConfig;
-use crate::event::Key;
+use crate::config::{KeyConfig, TableConfig};
+use crate::database::{BulkEdit, ForeignKeyReference};
+use crate::event::{Key, Mouse, MouseKind};
 use anyhow::Result;
 use database_tree::{Database, Table as DTable};
+use std::collections::HashMap;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
@@ -19,23 +21,40 @@ pub enum Focus {
 pub struct RecordTableComponent {
     pub filter: TableFilterComponent,
     pub table: TableComponent,
+    pub primary_key: Vec<String>,
+    /// the key of the last fetched record as the server writes it, which the next page is fetched after
+    pub next_key: Option<Vec<String>>,
+    pub foreign_keys: Vec<ForeignKeyReference>,
     pub focus: Focus,
+    filters: HashMap<String, String>,
+    filter_area: Rect,
+    /// whether the next key picks an edit of the selected cells rather than doing what it does in the table
+    bulk_edit_layer: bool,
+    /// the edit picked, which the app builds the statements of
+    bulk_edit: Option<BulkEdit>,
     key_config: KeyConfig,
 }
 
 impl RecordTableComponent {
-    pub fn new(key_config: KeyConfig) -> Self {
+    pub fn new(key_config: KeyConfig, table_config: TableConfig) -> Self {
         Self {
             filter: TableFilterComponent::default(),
-            table: TableComponent::new(key_config.clone()),
+            table: TableComponent::new(key_config.clone(), table_config),
+            primary_key: Vec::new(),
+            next_key: None,
+            foreign_keys: Vec::new(),
+            filters: HashMap::new(),
             focus: Focus::Table,
+            filter_area: Rect::default(),
+            bulk_edit_layer: false,
+            bulk_edit: None,
             key_config,
         }
     }
 
     pub fn update(
         &mut self,
-        rows: Vec<Vec<String>>,
+        rows: Vec<Vec<Option<String>>>,
         headers: Vec<String>,
         database: Database,
         table: DTable,
@@ -47,6 +66,108 @@ impl RecordTableComponent {
     pub fn reset(&mut self) {
         self.table.reset();
         self.filter.reset();
+        self.primary_key = Vec::new();
+        self.next_key = None;
+        self.foreign_keys = Vec::new();
+        self.bulk_edit_layer = false;
+    }
+
+    /// returns the edit of the selected cells picked after the bulk edit key
+    pub fn take_bulk_edit(&mut self) -> Option<BulkEdit> {
+        self.bulk_edit.take()
+    }
+
+    /// returns the names of the selected columns, and their values in each selected row with `None` for NULL
+    pub fn selected_area(&self) -> (Vec<String>, Vec<Vec<Option<String>>>) {
+        let columns = self.table.selected_columns();
+        (
+            columns
+                .iter()
+                .filter_map(|column| self.table.headers.get(*column).cloned())
+                .collect(),
+            self.table
+                .selected_rows()
+                .iter()
+                .map(|row| columns.iter().map(|column| row[*column].clone()).collect())
+                .collect(),
+        )
+    }
+
+    /// picks the edit bound to `key` in the bulk edit layer, which any other key leaves
+    fn bulk_edit_event(&mut self, key: Key) -> EventState {
+        self.bulk_edit_layer = false;
+        self.bulk_edit = if key == self.key_config.fill_down {
+            Some(BulkEdit::FillDown)
+        } else if key == self.key_config.set_null {
+            Some(BulkEdit::Clear)
+        } else if key == self.key_config.increment_cells {
+            Some(BulkEdit::Increment)
+        } else {
+            None
+        };
+        EventState::Consumed
+    }
+
+    /// returns the foreign key of the selected cell and the value it references
+    pub fn selected_foreign_key(&self) -> Option<(&ForeignKeyReference, String)> {
+        let (column, value) = self.table.selected_column_value()?;
+        let value = value.as_ref()?;
+        let reference = self
+            .foreign_keys
+            .iter()
+            .find(|reference| &reference.column == column)?;
+        Some((reference, value.clone()))
+    }
+
+    /// remembers the current filter of the table for the session
+    pub fn remember_filter(&mut self, database: &Database, table: &DTable) {
+        let key = format!("{}.{}", database.name, table.name);
+        if self.filter.input.is_empty() {
+            self.filters.remove(&key);
+        } else {
+            self.filters.insert(key, self.filter.input_str());
+        }
+    }
+
+    pub fn restore_filter(&mut self, database: &Database, table: &DTable) {
+        if let Some(filter) = self
+            .filters
+            .get(&format!("{}.{}", database.name, table.name))
+        {
+            self.filter.set_input(filter);
+        }
+    }
+
+    pub fn forget_filters(&mut self) {
+        self.filters.clear();
+    }
+
+    /// returns the primary key values of the selected rows, or nothing when the table has no primary key
+    pub fn selected_keys(&self) -> Vec<Vec<String>> {
+        let indices = self
+            .primary_key
+            .iter()
+            .map(|column| {
+                self.table
+                    .headers
+                    .iter()
+                    .position(|header| header == column)
+            })
+            .collect::<Option<Vec<usize>>>()
+            .unwrap_or_default();
+        if indices.is_empty() {
+            return vec![];
+        }
+        self.table
+            .selected_rows()
+            .iter()
+            .filter_map(|row| {
+                indices
+                    .iter()
+                    .map(|i| row.get(*i).cloned().flatten())
+                    .collect()
+            })
+            .collect()
     }
 
     pub fn filter_focused(&self) -> bool {
@@ -60,22 +181,64 @@ impl DrawableComponent for RecordTableComponent {
             .direction(Direction::Vertical)
             .constraints(vec![Constraint::Length(3), Constraint::Length(5)])
             .split(area);
+        self.filter_area = layout[0];
 
         self.filter
             .draw(f, layout[0], focused && matches!(self.focus, Focus::Filter))?;
 
         self.table
             .draw(f, layout[1], focused && matches!(self.focus, Focus::Table))?;
+        if focused && matches!(self.focus, Focus::Filter) {
+            self.filter.draw_candidates(f, layout[0]);
+        }
         Ok(())
     }
 }
 
 impl Component for RecordTableComponent {
     fn commands(&self, out: &mut Vec<CommandInfo>) {
-        self.table.commands(out)
+        if self.bulk_edit_layer {
+            out.push(CommandInfo::new(command::fill_down(&self.key_config)));
+            out.push(CommandInfo::new(command::set_null(&self.key_config)));
+            out.push(CommandInfo::new(command::increment_cells(&self.key_config)));
+            return;
+        }
+        self.table.commands(out);
+        out.push(CommandInfo::new(command::open_record_detail(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::generate_fake_data(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::insert_row(&self.key_config)));
+        out.push(CommandInfo::new(command::delete_rows(&self.key_config)));
+        out.push(CommandInfo::new(command::delete_template(&self.key_config)));
+        out.push(CommandInfo::new(command::update_template(&self.key_config)));
+        out.push(CommandInfo::new(command::bulk_edit(&self.key_config)));
+        out.push(CommandInfo::new(command::undo_change(&self.key_config)));
+        out.push(CommandInfo::new(command::column_stats(&self.key_config)));
+        out.push(CommandInfo::new(command::column_quality(&self.key_config)));
+        out.push(CommandInfo::new(command::copy_all_records(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::copy_as_insert(&self.key_config)));
+        out.push(CommandInfo::new(command::toggle_watch(&self.key_config)));
+        out.push(CommandInfo::new(command::follow_foreign_key(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::open_child_rows(&self.key_config)));
     }
 
     fn event(&mut self, key: Key) -> Result<EventState> {
+        if matches!(self.focus, Focus::Table) {
+            if self.bulk_edit_layer {
+                return Ok(self.bulk_edit_event(key));
+            }
+            if key == self.key_config.bulk_edit {
+                self.bulk_edit_layer = true;
+                return Ok(EventState::Consumed);
+            }
+        }
         if key == self.key_config.filter {
             self.focus = Focus::Filter;
             return Ok(EventState::Consumed);
@@ -87,4 +250,176 @@ impl Component for RecordTableComponent {
         }
         Ok(EventState::NotConsumed)
     }
+
+    fn mouse_event(&mut self, mouse: Mouse) -> Result<EventState> {
+        if self.table.mouse_event(mouse)?.is_consumed() {
+            if mouse.kind == MouseKind::Click {
+                self.focus = Focus::Table;
+            }
+            return Ok(EventState::Consumed);
+        }
+        if mouse.kind == MouseKind::Click && mouse.is_in(self.filter_area) {
+            self.focus = Focus::Filter;
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        BulkEdit, Component as _, ForeignKeyReference, KeyConfig, RecordTableComponent, TableConfig,
+    };
+    use crate::event::Key;
+
+    #[test]
+    fn test_bulk_edit() {
+        let key_config = KeyConfig::default();
+        let mut component = RecordTableComponent::new(key_config.clone(), TableConfig::default());
+        component.table.update_rows(
+            vec![
+                vec![
+                    Some("1".to_string()),
+                    Some("a".to_string()),
+                    Some("x".to_string()),
+                ],
+                vec![Some("2".to_string()), Some("b".to_string()), None],
+                vec![
+                    Some("3".to_string()),
+                    Some("c".to_string()),
+                    Some("z".to_string()),
+                ],
+            ],
+            vec!["id".to_string(), "name".to_string(), "note".to_string()],
+        );
+        component.event(Key::Char('l')).unwrap();
+        component
+            .event(key_config.extend_selection_by_one_cell_down)
+            .unwrap();
+        component
+            .event(key_config.extend_selection_by_one_cell_right)
+            .unwrap();
+        assert_eq!(
+            component.selected_area(),
+            (
+                vec!["name".to_string(), "note".to_string()],
+                vec![
+                    vec![Some("a".to_string()), Some("x".to_string())],
+                    vec![Some("b".to_string()), None],
+                ]
+            )
+        );
+
+        // the keys of the layer only pick an edit after the bulk edit key, and any other key leaves it
+        component.event(key_config.bulk_edit).unwrap();
+        let mut commands = vec![];
+        component.commands(&mut commands);
+        assert_eq!(commands.len(), 3);
+        component.event(key_config.fill_down).unwrap();
+        assert_eq!(component.take_bulk_edit(), Some(BulkEdit::FillDown));
+        component.event(key_config.bulk_edit).unwrap();
+        component.event(Key::Char('q')).unwrap();
+        assert_eq!(component.take_bulk_edit(), None);
+        component.event(key_config.set_null).unwrap();
+        assert_eq!(component.take_bulk_edit(), None);
+    }
+
+    #[test]
+    fn test_selected_foreign_key() {
+        let mut component = RecordTableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.table.headers = vec!["id", "user_id"]
+            .iter()
+            .map(|h| h.to_string())
+            .collect();
+        component.table.rows = vec![
+            vec!["1", "10"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec![Some("2".to_string()), None],
+            vec!["3", "NULL"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+        ];
+        component.foreign_keys = vec![ForeignKeyReference {
+            column: "user_id".to_string(),
+            ref_database: None,
+            ref_schema: None,
+            ref_table: "users".to_string(),
+            ref_column: "id".to_string(),
+        }];
+        component.table.selected_row.select(Some(0));
+        assert_eq!(component.selected_foreign_key(), None);
+
+        component
+            .table
+            .event(KeyConfig::default().scroll_right)
+            .unwrap();
+        let (reference, value) = component.selected_foreign_key().unwrap();
+        assert_eq!(reference.ref_table, "users".to_string());
+        assert_eq!(value, "10".to_string());
+
+        component.table.selected_row.select(Some(1));
+        assert_eq!(component.selected_foreign_key(), None);
+
+        // a text which reads NULL is a value like any other
+        component.table.selected_row.select(Some(2));
+        assert_eq!(component.selected_foreign_key().unwrap().1, "NULL");
+    }
+
+    #[test]
+    fn test_search_and_filter_keys() {
+        let key_config = KeyConfig::default();
+        let mut component = RecordTableComponent::new(key_config.clone(), TableConfig::default());
+        component.table.update_rows(
+            vec![vec![Some("apple".to_string())]],
+            vec!["name".to_string()],
+        );
+        assert!(component
+            .event(key_config.search_in_table)
+            .unwrap()
+            .is_consumed());
+        assert!(component.table.searching());
+        assert!(!component.filter_focused());
+
+        let mut component = RecordTableComponent::new(key_config.clone(), TableConfig::default());
+        component.event(key_config.filter).unwrap();
+        assert!(component.filter_focused());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut component = RecordTableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.primary_key = vec!["id".to_string()];
+        component.next_key = Some(vec!["2".to_string()]);
+        component.reset();
+        assert!(component.primary_key.is_empty());
+        assert_eq!(component.next_key, None);
+    }
+
+    #[test]
+    fn test_selected_keys() {
+        let mut component = RecordTableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.table.headers = vec!["name", "id"].iter().map(|h| h.to_string()).collect();
+        component.table.rows = vec![
+            vec!["a", "1"].iter().map(|h| Some(h.to_string())).collect(),
+            vec!["b", "2"].iter().map(|h| Some(h.to_string())).collect(),
+            vec!["c", "3"].iter().map(|h| Some(h.to_string())).collect(),
+        ];
+        component.table.selected_row.select(Some(1));
+        assert_eq!(component.selected_keys(), Vec::<Vec<String>>::new());
+
+        component.primary_key = vec!["id".to_string()];
+        assert_eq!(component.selected_keys(), vec![vec!["2".to_string()]]);
+        component
+            .table
+            .event(KeyConfig::default().extend_selection_by_one_cell_down)
+            .unwrap();
+        assert_eq!(
+            component.selected_keys(),
+            vec![vec!["2".to_string()], vec!["3".to_string()]]
+        );
+    }
 }
@@ -0,0 +1,164 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState},
+    Frame,
+};
+
+/// lists the tables referenced by the query in the SQL editor to open one of them
+pub struct ReferencedTablesComponent {
+    /// the tables with the schema or the database they are qualified with in the query
+    tables: Vec<(Option<String>, String)>,
+    state: ListState,
+    chosen: Option<(Option<String>, String)>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ReferencedTablesComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            tables: vec![],
+            state: ListState::default(),
+            chosen: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn open(&mut self, tables: Vec<(Option<String>, String)>) -> Result<()> {
+        if tables.is_empty() {
+            return Ok(());
+        }
+        self.tables = tables;
+        self.state.select(Some(0));
+        self.chosen = None;
+        self.show()
+    }
+
+    /// returns the table the user chose
+    pub fn take_chosen(&mut self) -> Option<(Option<String>, String)> {
+        self.chosen.take()
+    }
+
+    fn select_next(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some((i + 1) % self.tables.len()));
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state
+                .select(Some((i + self.tables.len() - 1) % self.tables.len()));
+        }
+    }
+}
+
+impl DrawableComponent for ReferencedTablesComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if self.visible {
+            let items = self
+                .tables
+                .iter()
+                .map(|(qualifier, table)| {
+                    ListItem::new(Spans::from(match qualifier {
+                        Some(qualifier) => vec![
+                            Span::styled(format!("{}.", qualifier), Theme::current().hint()),
+                            Span::raw(table.clone()),
+                        ],
+                        None => vec![Span::raw(table.clone())],
+                    }))
+                })
+                .collect::<Vec<ListItem>>();
+            let area = popup_area(f, 50, (self.tables.len() as u16).saturating_add(2).min(20));
+            f.render_stateful_widget(
+                List::new(items)
+                    .block(
+                        Block::default()
+                            .title(Msg::ReferencedTables.text())
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick),
+                    )
+                    .highlight_style(Theme::current().selected()),
+                area,
+                &mut self.state,
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for ReferencedTablesComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            self.chosen = self
+                .state
+                .selected()
+                .and_then(|i| self.tables.get(i))
+                .cloned();
+            self.hide();
+        } else if key == self.key_config.scroll_down || key == Key::Down {
+            self.select_next();
+        } else if key == self.key_config.scroll_up || key == Key::Up {
+            self.select_previous();
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, KeyConfig, ReferencedTablesComponent};
+
+    #[test]
+    fn test_choose_table() {
+        let mut component = ReferencedTablesComponent::new(KeyConfig::default());
+        component.open(vec![]).unwrap();
+        assert!(!component.visible);
+
+        component
+            .open(vec![
+                (None, "orders".to_string()),
+                (Some("public".to_string()), "users".to_string()),
+            ])
+            .unwrap();
+        component.event(KeyConfig::default().scroll_up).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(!component.visible);
+        assert_eq!(
+            component.take_chosen(),
+            Some((Some("public".to_string()), "users".to_string()))
+        );
+        assert_eq!(component.take_chosen(), None);
+    }
+}
@@ -0,0 +1,242 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::database::{routine_parameters, RoutineParameter};
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use database_tree::{Database, Routine};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// a form with a field for each parameter of a stored procedure or function, which runs it with the values entered
+pub struct RoutineFormComponent {
+    routine: Option<(Database, Routine)>,
+    /// each parameter with the value entered for it, where an empty value is passed as NULL
+    fields: Vec<(RoutineParameter, String)>,
+    selected: usize,
+    call: Option<(Database, Routine, Vec<Option<String>>)>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl RoutineFormComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            routine: None,
+            fields: vec![],
+            selected: 0,
+            call: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn open(&mut self, database: Database, routine: Routine) -> Result<()> {
+        self.fields = routine_parameters(&routine.arguments)
+            .into_iter()
+            .map(|parameter| (parameter, String::new()))
+            .collect();
+        self.routine = Some((database, routine));
+        self.selected = 0;
+        self.call = None;
+        self.show()
+    }
+
+    /// returns the routine with an argument for each parameter, `None` for the output ones, once the user ran it
+    pub fn take_call(&mut self) -> Option<(Database, Routine, Vec<Option<String>>)> {
+        self.call.take()
+    }
+
+    fn field_spans(&self, index: usize, label_width: usize) -> Spans<'static> {
+        let (parameter, value) = &self.fields[index];
+        let style = if index == self.selected {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        let value = if parameter.is_output() {
+            Span::styled(
+                Msg::OutputParameter.text(),
+                Theme::current().hint().patch(style),
+            )
+        } else if value.is_empty() {
+            Span::styled("NULL", Theme::current().hint().patch(style))
+        } else {
+            Span::styled(value.clone(), style)
+        };
+        Spans::from(vec![
+            Span::styled(
+                format!("{:w$}", parameter.name, w = label_width),
+                Theme::current().accent(),
+            ),
+            Span::styled(
+                format!(" {} {}: ", parameter.mode, parameter.r#type),
+                Theme::current().hint(),
+            ),
+            value,
+        ])
+    }
+}
+
+impl DrawableComponent for RoutineFormComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let title = match &self.routine {
+            Some((_, routine)) => {
+                format!("{}: {}", Msg::ExecuteRoutine.text(), routine.signature())
+            }
+            None => Msg::ExecuteRoutine.text().to_string(),
+        };
+        let label_width = self
+            .fields
+            .iter()
+            .map(|(parameter, _)| parameter.name.width())
+            .max()
+            .unwrap_or(0);
+        let mut text = (0..self.fields.len())
+            .map(|index| self.field_spans(index, label_width))
+            .collect::<Vec<Spans>>();
+        if text.is_empty() {
+            text.push(Spans::from(Span::styled(
+                Msg::NoParameters.text(),
+                Theme::current().hint(),
+            )));
+        }
+        text.push(Spans::from(""));
+        text.push(Spans::from(Span::styled(
+            format!(
+                "[{}] {}  [{}] {}",
+                self.key_config.enter,
+                Msg::ExecuteRoutine.text(),
+                self.key_config.exit_popup,
+                Msg::Cancel.text()
+            ),
+            Theme::current().hint(),
+        )));
+        let area = popup_area(
+            f,
+            80,
+            (text.len() as u16 + 2).min(f.size().height.saturating_sub(4)),
+        );
+        // keeps the selected field in view when there are more parameters than lines
+        let scroll = (self.selected as u16).saturating_sub(area.height.saturating_sub(5));
+        f.render_widget(
+            Paragraph::new(text)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Thick),
+                )
+                .scroll((scroll, 0)),
+            area,
+        );
+        Ok(())
+    }
+}
+
+impl Component for RoutineFormComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.enter {
+            if let Some((database, routine)) = self.routine.clone() {
+                let arguments = self
+                    .fields
+                    .iter()
+                    .map(|(parameter, value)| {
+                        Some(value.clone()).filter(|_| !parameter.is_output())
+                    })
+                    .collect();
+                self.call = Some((database, routine, arguments));
+            }
+            self.hide();
+            return Ok(EventState::Consumed);
+        }
+        let len = self.fields.len();
+        let (parameter, value) = match self.fields.get_mut(self.selected) {
+            Some(field) => field,
+            None => return Ok(EventState::Consumed),
+        };
+        match key {
+            Key::Tab | Key::Down => self.selected = (self.selected + 1) % len,
+            Key::Up => self.selected = (self.selected + len - 1) % len,
+            Key::Char(c) if !parameter.is_output() => value.push(c),
+            Key::Backspace | Key::Delete => {
+                value.pop();
+            }
+            _ => (),
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, KeyConfig, RoutineFormComponent};
+    use crate::event::Key;
+    use database_tree::{Database, Routine};
+
+    #[test]
+    fn test_take_call() {
+        let mut component = RoutineFormComponent::new(KeyConfig::default());
+        let routine = Routine {
+            name: "order_total".to_string(),
+            schema: None,
+            specific_name: "order_total".to_string(),
+            function: false,
+            arguments: "IN id int, OUT total decimal(10,2), IN note text".to_string(),
+            returns: None,
+        };
+        component
+            .open(Database::new("shop".to_string(), vec![]), routine.clone())
+            .unwrap();
+        for c in "42".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        component.event(Key::Down).unwrap();
+        component.event(Key::Char('x')).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(!component.visible);
+        assert_eq!(
+            component.take_call(),
+            Some((
+                Database::new("shop".to_string(), vec![]),
+                routine,
+                vec![Some("42".to_string()), None, Some(String::new())]
+            ))
+        );
+        assert!(component.take_call().is_none());
+    }
+}
@@ -0,0 +1,505 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::database::{split_statements, Engine, ExecuteResult};
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use crate::ui::scrollbar::draw_scrollbar;
+use anyhow::Result;
+use itertools::Itertools;
+use std::time::{Duration, Instant};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// how a statement of the file ended, with the number of rows or the error
+#[derive(Clone)]
+pub struct StatementLog {
+    statement: String,
+    elapsed: Duration,
+    result: std::result::Result<String, String>,
+    /// the warnings the server raised while running the statement
+    warnings: Vec<String>,
+}
+
+impl StatementLog {
+    pub fn new(statement: String, elapsed: Duration, result: &Result<ExecuteResult>) -> Self {
+        let result = match result {
+            Ok(ExecuteResult::Read { rows, .. }) => {
+                Ok(format!("{} {}", rows.len(), Msg::Rows.text()))
+            }
+            Ok(ExecuteResult::Write { updated_rows }) => {
+                Ok(format!("{} {}", updated_rows, Msg::RowsAffected.text()))
+            }
+            Ok(ExecuteResult::Plan(_)) => Ok(String::new()),
+            Err(err) => Err(err.to_string()),
+        };
+        Self {
+            statement,
+            elapsed,
+            result,
+            warnings: vec![],
+        }
+    }
+
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// returns the error the statement failed with
+    pub fn error(&self) -> Option<&str> {
+        self.result.as_ref().err().map(String::as_str)
+    }
+
+    /// returns the rows the statement returned or changed, how long it took and the first warning it raised, such as
+    /// `3 rows affected  12ms  Warning 1265: Data truncated for column 'name' at row 1 (+1)`
+    pub fn status(&self) -> String {
+        let mut status = format!(
+            "{}  {}ms",
+            match &self.result {
+                Ok(message) => message.as_str(),
+                Err(_) => Msg::Failed.text(),
+            },
+            self.elapsed.as_millis()
+        );
+        if let Some(warning) = self.warnings.first() {
+            status = format!("{}  {}", status, warning);
+            if self.warnings.len() > 1 {
+                status = format!("{} (+{})", status, self.warnings.len() - 1);
+            }
+        }
+        status
+    }
+
+    pub fn spans(&self) -> Spans<'static> {
+        let (mark, message, style) = match &self.result {
+            Ok(message) => ("✔", message, Theme::current().success()),
+            Err(message) => ("✘", message, Theme::current().error()),
+        };
+        let mut spans = vec![
+            Span::styled(mark, style),
+            Span::raw(format!(
+                " {:>7}ms  {}  ",
+                self.elapsed.as_millis(),
+                self.statement.split_whitespace().join(" ")
+            )),
+            Span::styled(message.clone(), style),
+        ];
+        if !self.warnings.is_empty() {
+            spans.push(Span::styled(
+                format!("  {} {}", self.warnings.len(), Msg::Warnings.text()),
+                Theme::current().accent(),
+            ));
+        }
+        Spans::from(spans)
+    }
+}
+
+/// the statements of the file run so far
+struct BatchLog {
+    total: usize,
+    logs: Vec<StatementLog>,
+    started: Instant,
+    finished: Option<Duration>,
+    cancelled: bool,
+}
+
+impl BatchLog {
+    fn summary(&self) -> String {
+        let failed = self.logs.iter().filter(|log| log.result.is_err()).count();
+        let elapsed = self.finished.unwrap_or_else(|| self.started.elapsed());
+        let mut summary = format!(
+            "{} {}, {} {}, {} {}  {:.1}s",
+            self.logs.len() - failed,
+            Msg::Succeeded.text(),
+            failed,
+            Msg::Failed.text(),
+            self.total - self.logs.len(),
+            if self.finished.is_some() {
+                Msg::Skipped.text()
+            } else {
+                Msg::Remaining.text()
+            },
+            elapsed.as_secs_f64()
+        );
+        if self.cancelled {
+            summary = format!("{}  {}", summary, Msg::QueryCancelled.text());
+        }
+        summary
+    }
+}
+
+/// reads the file at `path` and splits it into the statements which `engine` runs one after another
+pub fn file_statements(path: &str, engine: Engine) -> Result<Vec<String>> {
+    let sql = std::fs::read_to_string(path).map_err(|e| anyhow::anyhow!("{}: {}", path, e))?;
+    Ok(split_statements(sql.as_str(), engine))
+}
+
+pub struct RunFileComponent {
+    input: String,
+    stop_on_error: bool,
+    submitted: bool,
+    log: Option<BatchLog>,
+    scroll: u16,
+    follow: bool,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl RunFileComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            input: String::new(),
+            stop_on_error: true,
+            submitted: false,
+            log: None,
+            scroll: 0,
+            follow: true,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens the prompt for the file to run, keeping the last path and mode
+    pub fn open(&mut self) -> Result<()> {
+        if self.is_running() {
+            return self.show();
+        }
+        self.submitted = false;
+        self.log = None;
+        self.show()
+    }
+
+    /// returns the path and whether to stop at the first failed statement once the user entered the path
+    pub fn take_file(&mut self) -> Option<(String, bool)> {
+        if !std::mem::take(&mut self.submitted) {
+            return None;
+        }
+        Some((self.input.trim().to_string(), self.stop_on_error))
+    }
+
+    /// switches to the log of the file which is about to run `total` statements
+    pub fn start(&mut self, total: usize) {
+        self.log = Some(BatchLog {
+            total,
+            logs: Vec::new(),
+            started: Instant::now(),
+            finished: None,
+            cancelled: false,
+        });
+        self.scroll = 0;
+        self.follow = true;
+    }
+
+    pub fn push_log(&mut self, log: StatementLog) {
+        if let Some(batch) = self.log.as_mut() {
+            batch.logs.push(log);
+        }
+    }
+
+    pub fn finish(&mut self, cancelled: bool) {
+        if let Some(batch) = self.log.as_mut() {
+            batch.finished = Some(batch.started.elapsed());
+            batch.cancelled = cancelled;
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(&self.log, Some(batch) if batch.finished.is_none())
+    }
+
+    fn line_count(&self) -> u16 {
+        self.log.as_ref().map_or(0, |batch| batch.logs.len() + 2) as u16
+    }
+
+    fn scroll_down(&mut self, lines: u16) {
+        self.scroll = self
+            .scroll
+            .saturating_add(lines)
+            .min(self.line_count().saturating_sub(1));
+    }
+
+    fn scroll_up(&mut self, lines: u16) {
+        self.follow = false;
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    fn log_event(&mut self, key: Key) {
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.scroll_down {
+            self.scroll_down(1);
+        } else if key == self.key_config.scroll_up {
+            self.scroll_up(1);
+        } else if key == self.key_config.scroll_down_multiple_lines {
+            self.scroll_down(10);
+        } else if key == self.key_config.scroll_up_multiple_lines {
+            self.scroll_up(10);
+        } else if key == self.key_config.scroll_to_top {
+            self.scroll_up(self.scroll);
+        } else if key == self.key_config.scroll_to_bottom {
+            self.follow = true;
+        }
+    }
+
+    fn input_event(&mut self, key: Key) {
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            self.submitted = !self.input.trim().is_empty();
+        } else {
+            match key {
+                Key::Tab => self.stop_on_error = !self.stop_on_error,
+                Key::Char(c) => self.input.push(c),
+                Key::Backspace | Key::Delete => {
+                    self.input.pop();
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn mode(&self) -> &'static str {
+        if self.stop_on_error {
+            Msg::StopOnError.text()
+        } else {
+            Msg::ContinueOnError.text()
+        }
+    }
+}
+
+impl DrawableComponent for RunFileComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick);
+        match self.log.as_ref() {
+            Some(batch) => {
+                let area = popup_area(
+                    f,
+                    f.size().width.saturating_sub(10),
+                    f.size().height.saturating_sub(6),
+                );
+                let mut lines = batch.logs.iter().map(StatementLog::spans).collect_vec();
+                lines.push(Spans::from(""));
+                lines.push(Spans::from(Span::styled(
+                    if batch.finished.is_some() {
+                        batch.summary()
+                    } else {
+                        format!(
+                            "{}  [{},{}] {}",
+                            batch.summary(),
+                            self.key_config.exit_popup,
+                            self.key_config.exit,
+                            Msg::Cancel.text()
+                        )
+                    },
+                    Theme::current().hint(),
+                )));
+                let height = area.height.saturating_sub(2);
+                let max = (lines.len() as u16).saturating_sub(height);
+                if self.follow {
+                    self.scroll = max;
+                }
+                f.render_widget(
+                    Paragraph::new(lines)
+                        .block(block.title(format!(
+                            "{}: {} ({})",
+                            Msg::RunFile.text(),
+                            self.input.trim(),
+                            self.mode()
+                        )))
+                        .scroll((self.scroll, 0)),
+                    area,
+                );
+                draw_scrollbar(
+                    f,
+                    area,
+                    max as usize,
+                    self.scroll as usize,
+                    height as usize,
+                    true,
+                    true,
+                );
+            }
+            None => {
+                const SIZE: (u16, u16) = (60, 4);
+                let area = popup_area(f, SIZE.0, SIZE.1);
+                let text = vec![
+                    if self.input.is_empty() {
+                        Spans::from(Span::styled(
+                            Msg::RunFilePlaceholder.text(),
+                            Theme::current().hint(),
+                        ))
+                    } else {
+                        Spans::from(Span::raw(self.input.as_str()))
+                    },
+                    Spans::from(Span::styled(
+                        format!(
+                            "[{}] {}  [{}] {}  [{}] {}",
+                            Key::Tab,
+                            self.mode(),
+                            self.key_config.enter,
+                            Msg::Run.text(),
+                            self.key_config.exit_popup,
+                            Msg::Cancel.text()
+                        ),
+                        Theme::current().hint(),
+                    )),
+                ];
+                f.render_widget(
+                    Paragraph::new(text).block(block.title(Msg::RunFile.text())),
+                    area,
+                );
+                f.set_cursor(area.x + 1 + self.input.width() as u16, area.y + 1);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Component for RunFileComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if self.log.is_some() {
+            self.log_event(key);
+        } else {
+            self.input_event(key);
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{file_statements, Component as _, KeyConfig, RunFileComponent, StatementLog};
+    use crate::database::{Engine, ExecuteResult};
+    use crate::event::Key;
+    use std::time::Duration;
+
+    #[test]
+    fn test_take_file() {
+        let mut component = RunFileComponent::new(KeyConfig::default());
+        component.open().unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(component.take_file(), None);
+
+        for c in "seed.sql".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        component.event(Key::Tab).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(component.take_file(), Some(("seed.sql".to_string(), false)));
+        assert_eq!(component.take_file(), None);
+    }
+
+    #[test]
+    fn test_file_statements() {
+        let path = std::env::temp_dir().join(format!("gobang-run-file-{}.sql", std::process::id()));
+        std::fs::write(
+            &path,
+            "-- seed\nCREATE FUNCTION one() RETURNS int AS $$\nBEGIN\n  RETURN 1;\nEND;\n$$ LANGUAGE plpgsql;\n\nSELECT one();\n",
+        )
+        .unwrap();
+        let path = path.to_str().unwrap();
+        assert_eq!(
+            file_statements(path, Engine::Postgres).unwrap(),
+            vec![
+                "CREATE FUNCTION one() RETURNS int AS $$\nBEGIN\n  RETURN 1;\nEND;\n$$ LANGUAGE plpgsql"
+                    .to_string(),
+                "SELECT one()".to_string()
+            ]
+        );
+        std::fs::remove_file(path).unwrap();
+        assert!(file_statements(path, Engine::Postgres).is_err());
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut component = RunFileComponent::new(KeyConfig::default());
+        component.open().unwrap();
+        component.start(3);
+        assert!(component.is_running());
+        component.push_log(StatementLog::new(
+            "INSERT INTO t VALUES (1)".to_string(),
+            Duration::from_millis(3),
+            &Ok(ExecuteResult::Write { updated_rows: 1 }),
+        ));
+        component.push_log(StatementLog::new(
+            "INSERT INTO u VALUES (1)".to_string(),
+            Duration::from_millis(1),
+            &Err(anyhow::anyhow!("no such table: u")),
+        ));
+        component.finish(false);
+        assert!(!component.is_running());
+
+        let batch = component.log.as_ref().unwrap();
+        assert_eq!(batch.logs[0].result, Ok("1 rows affected".to_string()));
+        assert_eq!(batch.logs[1].result, Err("no such table: u".to_string()));
+        assert!(batch
+            .summary()
+            .starts_with("1 succeeded, 1 failed, 1 skipped"));
+
+        component.event(KeyConfig::default().exit_popup).unwrap();
+        assert!(!component.visible);
+        component.open().unwrap();
+        assert!(component.log.is_none());
+        assert_eq!(component.input, "");
+    }
+
+    #[test]
+    fn test_status() {
+        let log = StatementLog::new(
+            "INSERT INTO t VALUES ('long')".to_string(),
+            Duration::from_millis(12),
+            &Ok(ExecuteResult::Write { updated_rows: 1 }),
+        );
+        assert_eq!(log.status(), "1 rows affected  12ms");
+        let log = log.with_warnings(vec![
+            "Warning 1265: Data truncated for column 'a' at row 1".to_string(),
+            "Warning 1265: Data truncated for column 'b' at row 1".to_string(),
+        ]);
+        assert_eq!(
+            log.status(),
+            "1 rows affected  12ms  Warning 1265: Data truncated for column 'a' at row 1 (+1)"
+        );
+        let log = StatementLog::new(
+            "SELECT * FROM u".to_string(),
+            Duration::from_millis(1),
+            &Err(anyhow::anyhow!("no such table: u")),
+        );
+        assert_eq!(log.status(), "failed  1ms");
+    }
+}
@@ -0,0 +1,249 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::database::ScheduledJob;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+/// lists the jobs the server runs on a schedule with the definition of the selected one, to enable or disable them
+pub struct ScheduledJobsComponent {
+    jobs: Vec<ScheduledJob>,
+    state: ListState,
+    /// the job the user asked to enable or disable, with whether it should be enabled
+    toggle: Option<(ScheduledJob, bool)>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl ScheduledJobsComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            jobs: vec![],
+            state: ListState::default(),
+            toggle: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn open(&mut self, jobs: Vec<ScheduledJob>) -> Result<()> {
+        self.state.select(None);
+        self.update(jobs);
+        self.toggle = None;
+        self.show()
+    }
+
+    /// replaces the jobs, keeping the selected one when it is still there
+    pub fn update(&mut self, jobs: Vec<ScheduledJob>) {
+        let selected = self
+            .state
+            .selected()
+            .and_then(|i| self.jobs.get(i))
+            .map(|job| job.id.clone());
+        self.jobs = jobs;
+        self.state.select(if self.jobs.is_empty() {
+            None
+        } else {
+            Some(
+                selected
+                    .and_then(|id| self.jobs.iter().position(|job| job.id == id))
+                    .unwrap_or(0),
+            )
+        });
+    }
+
+    /// returns the job the user asked to enable or disable, with whether it should be enabled
+    pub fn take_toggle(&mut self) -> Option<(ScheduledJob, bool)> {
+        self.toggle.take()
+    }
+
+    fn selected_job(&self) -> Option<&ScheduledJob> {
+        self.state.selected().and_then(|i| self.jobs.get(i))
+    }
+
+    fn select_next(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state
+                .select(Some((i + 1).min(self.jobs.len().saturating_sub(1))));
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some(i.saturating_sub(1)));
+        }
+    }
+}
+
+impl DrawableComponent for ScheduledJobsComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let area = popup_area(
+            f,
+            f.size().width.saturating_sub(10),
+            f.size().height.saturating_sub(6),
+        );
+        f.render_widget(
+            Block::default()
+                .title(format!(
+                    "{} ({})",
+                    Msg::ScheduledJobs.text(),
+                    self.jobs.len()
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick),
+            area,
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Percentage(50),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(area);
+        let items = self
+            .jobs
+            .iter()
+            .map(|job| {
+                ListItem::new(Spans::from(vec![
+                    if job.enabled {
+                        Span::styled("✔", Theme::current().success())
+                    } else {
+                        Span::styled("✘", Theme::current().hint())
+                    },
+                    Span::raw(format!(" {}  {}  ", job.name, job.schedule)),
+                    Span::styled(
+                        format!(
+                            "{}: {}",
+                            Msg::LastRun.text(),
+                            job.last_run.as_deref().unwrap_or("-")
+                        ),
+                        Theme::current().hint(),
+                    ),
+                ]))
+            })
+            .collect::<Vec<ListItem>>();
+        if items.is_empty() {
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    Msg::NoScheduledJobs.text(),
+                    Theme::current().hint(),
+                )),
+                chunks[0],
+            );
+        } else {
+            f.render_stateful_widget(
+                List::new(items).highlight_style(Theme::current().selected()),
+                chunks[0],
+                &mut self.state,
+            );
+        }
+        f.render_widget(
+            Paragraph::new(Text::raw(
+                self.selected_job()
+                    .map_or(String::new(), |job| job.definition.clone()),
+            ))
+            .block(Block::default().borders(Borders::TOP))
+            .wrap(Wrap { trim: false }),
+            chunks[1],
+        );
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                format!(
+                    "[{}] {}  [{}] {}",
+                    self.key_config.toggle_job,
+                    Msg::ToggleJob.text(),
+                    self.key_config.exit_popup,
+                    Msg::Close.text()
+                ),
+                Theme::current().hint(),
+            )),
+            chunks[2],
+        );
+        Ok(())
+    }
+}
+
+impl Component for ScheduledJobsComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.toggle_job {
+            self.toggle = self.selected_job().map(|job| (job.clone(), !job.enabled));
+        } else if key == self.key_config.scroll_down || key == Key::Down {
+            self.select_next();
+        } else if key == self.key_config.scroll_up || key == Key::Up {
+            self.select_previous();
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, KeyConfig, ScheduledJob, ScheduledJobsComponent};
+
+    fn job(id: &str, enabled: bool) -> ScheduledJob {
+        ScheduledJob {
+            id: id.to_string(),
+            name: id.to_string(),
+            schedule: "EVERY 1 DAY".to_string(),
+            enabled,
+            last_run: None,
+            definition: "DELETE FROM sessions".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_toggle_job() {
+        let mut component = ScheduledJobsComponent::new(KeyConfig::default());
+        component
+            .open(vec![job("purge", true), job("report", false)])
+            .unwrap();
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(KeyConfig::default().toggle_job).unwrap();
+        assert_eq!(component.take_toggle(), Some((job("report", false), true)));
+        assert_eq!(component.take_toggle(), None);
+
+        component.update(vec![job("cleanup", true), job("report", true)]);
+        assert_eq!(component.state.selected(), Some(1));
+        component.update(vec![job("cleanup", true)]);
+        assert_eq!(component.state.selected(), Some(0));
+        component.update(vec![]);
+        assert_eq!(component.state.selected(), None);
+        component.event(KeyConfig::default().toggle_job).unwrap();
+        assert_eq!(component.take_toggle(), None);
+    }
+}
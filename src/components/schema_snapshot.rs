@@ -0,0 +1,257 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// what to do with the snapshot file
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SnapshotAction {
+    Save,
+    Compare,
+}
+
+impl SnapshotAction {
+    fn text(self) -> &'static str {
+        match self {
+            Self::Save => Msg::SaveSnapshot.text(),
+            Self::Compare => Msg::CompareSnapshot.text(),
+        }
+    }
+}
+
+/// asks for the path of a schema snapshot, then shows what was saved or what changed since it was saved
+pub struct SchemaSnapshotComponent {
+    input: String,
+    action: SnapshotAction,
+    submitted: bool,
+    report: Option<Vec<String>>,
+    scroll: u16,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl SchemaSnapshotComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            input: String::new(),
+            action: SnapshotAction::Compare,
+            submitted: false,
+            report: None,
+            scroll: 0,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens the prompt, keeping the last path and action
+    pub fn open(&mut self) -> Result<()> {
+        self.submitted = false;
+        self.report = None;
+        self.show()
+    }
+
+    /// returns the path and what to do with it once the user entered the path
+    pub fn take_request(&mut self) -> Option<(String, SnapshotAction)> {
+        if !std::mem::take(&mut self.submitted) {
+            return None;
+        }
+        Some((self.input.trim().to_string(), self.action))
+    }
+
+    /// shows the lines describing the result of the action
+    pub fn set_report(&mut self, report: Vec<String>) {
+        self.report = Some(report);
+        self.scroll = 0;
+    }
+
+    fn report_event(&mut self, key: Key) {
+        let lines = self.report.as_ref().map_or(0, Vec::len) as u16;
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.scroll_down {
+            self.scroll = self.scroll.saturating_add(1).min(lines.saturating_sub(1));
+        } else if key == self.key_config.scroll_up {
+            self.scroll = self.scroll.saturating_sub(1);
+        } else if key == self.key_config.scroll_to_top {
+            self.scroll = 0;
+        } else if key == self.key_config.scroll_to_bottom {
+            self.scroll = lines.saturating_sub(1);
+        }
+    }
+
+    fn input_event(&mut self, key: Key) {
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            self.submitted = !self.input.trim().is_empty();
+        } else {
+            match key {
+                Key::Tab => {
+                    self.action = match self.action {
+                        SnapshotAction::Save => SnapshotAction::Compare,
+                        SnapshotAction::Compare => SnapshotAction::Save,
+                    }
+                }
+                Key::Char(c) => self.input.push(c),
+                Key::Backspace | Key::Delete => {
+                    self.input.pop();
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// colors a line of the report by the kind of change it starts with
+fn report_line(line: &str) -> Spans<'static> {
+    let style = match line.chars().next() {
+        Some('+') => Theme::current().success(),
+        Some('-') => Theme::current().error(),
+        Some('~') => Theme::current().accent(),
+        _ => Theme::current().text(true),
+    };
+    Spans::from(Span::styled(line.to_string(), style))
+}
+
+impl DrawableComponent for SchemaSnapshotComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick);
+        match self.report.as_ref() {
+            Some(report) => {
+                let area = popup_area(
+                    f,
+                    f.size().width.saturating_sub(10),
+                    f.size().height.saturating_sub(6),
+                );
+                let lines = report
+                    .iter()
+                    .map(|line| report_line(line))
+                    .collect::<Vec<Spans>>();
+                f.render_widget(
+                    Paragraph::new(lines)
+                        .block(block.title(format!(
+                            "{}: {} ({})",
+                            Msg::SchemaSnapshot.text(),
+                            self.input.trim(),
+                            self.action.text()
+                        )))
+                        .scroll((self.scroll, 0)),
+                    area,
+                );
+            }
+            None => {
+                const SIZE: (u16, u16) = (60, 4);
+                let area = popup_area(f, SIZE.0, SIZE.1);
+                let text = vec![
+                    if self.input.is_empty() {
+                        Spans::from(Span::styled(
+                            Msg::SchemaSnapshotPlaceholder.text(),
+                            Theme::current().hint(),
+                        ))
+                    } else {
+                        Spans::from(Span::raw(self.input.as_str()))
+                    },
+                    Spans::from(Span::styled(
+                        format!(
+                            "[{}] {}  [{}] {}  [{}] {}",
+                            Key::Tab,
+                            self.action.text(),
+                            self.key_config.enter,
+                            Msg::Run.text(),
+                            self.key_config.exit_popup,
+                            Msg::Cancel.text()
+                        ),
+                        Theme::current().hint(),
+                    )),
+                ];
+                f.render_widget(
+                    Paragraph::new(text).block(block.title(Msg::SchemaSnapshot.text())),
+                    area,
+                );
+                f.set_cursor(area.x + 1 + self.input.width() as u16, area.y + 1);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Component for SchemaSnapshotComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if self.report.is_some() {
+            self.report_event(key);
+        } else {
+            self.input_event(key);
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, KeyConfig, SchemaSnapshotComponent, SnapshotAction};
+    use crate::event::Key;
+
+    #[test]
+    fn test_take_request() {
+        let mut component = SchemaSnapshotComponent::new(KeyConfig::default());
+        component.open().unwrap();
+        for c in "base.json".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(
+            component.take_request(),
+            Some(("base.json".to_string(), SnapshotAction::Compare))
+        );
+        assert_eq!(component.take_request(), None);
+
+        component.set_report(vec!["+ table orders".to_string()]);
+        component.event(Key::Char('x')).unwrap();
+        assert_eq!(component.input, "base.json");
+        component.event(KeyConfig::default().exit_popup).unwrap();
+        assert!(!component.visible);
+
+        component.open().unwrap();
+        component.event(Key::Tab).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(
+            component.take_request(),
+            Some(("base.json".to_string(), SnapshotAction::Save))
+        );
+    }
+}
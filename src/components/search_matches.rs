@@ -0,0 +1,308 @@
+use super::{popup_area, table::SearchMatch, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// how many characters before a match are kept in its excerpt
+const EXCERPT_CONTEXT: usize = 15;
+/// how many characters an excerpt is cut to
+const EXCERPT_WIDTH: usize = 60;
+
+/// lists every cell matching the search of a table like the quickfix list of vimgrep, to jump to one of them
+pub struct SearchMatchesComponent {
+    pattern: String,
+    /// the row counted from the first fetched row, the column and its header, and the excerpt of each matched cell
+    matches: Vec<(usize, usize, String, String)>,
+    state: ListState,
+    jump: Option<(usize, usize)>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl SearchMatchesComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            pattern: String::new(),
+            matches: vec![],
+            state: ListState::default(),
+            jump: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens the list of the cells at `matches` with their values, selecting the first one after `current`
+    pub fn open(
+        &mut self,
+        pattern: String,
+        headers: &[String],
+        matches: Vec<SearchMatch>,
+        current: (usize, usize),
+    ) -> Result<()> {
+        self.matches = matches
+            .into_iter()
+            .map(|(row, column, value)| {
+                let header = headers.get(column).cloned().unwrap_or_default();
+                (
+                    row,
+                    column,
+                    header,
+                    excerpt(value.as_str(), pattern.as_str()),
+                )
+            })
+            .collect();
+        self.pattern = pattern;
+        self.state.select(Some(
+            self.matches
+                .iter()
+                .position(|(row, column, _, _)| (*row, *column) >= current)
+                .unwrap_or(0),
+        ));
+        self.jump = None;
+        self.show()
+    }
+
+    /// returns the row and the column of the match to select once the user chose one
+    pub fn take_jump(&mut self) -> Option<(usize, usize)> {
+        self.jump.take()
+    }
+
+    fn select(&mut self, forward: bool, lines: usize) {
+        let last = self.matches.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        self.state.select(Some(if forward {
+            (selected + lines).min(last)
+        } else {
+            selected.saturating_sub(lines)
+        }));
+    }
+
+    /// returns the excerpt with the pattern highlighted, matching without case like the search
+    fn excerpt_spans(&self, excerpt: &str) -> Vec<Span<'static>> {
+        let start = match excerpt
+            .to_lowercase()
+            .find(self.pattern.to_lowercase().as_str())
+        {
+            Some(start)
+                if excerpt.is_char_boundary(start)
+                    && excerpt.is_char_boundary(start + self.pattern.len()) =>
+            {
+                start
+            }
+            _ => return vec![Span::raw(excerpt.to_string())],
+        };
+        let end = start + self.pattern.len();
+        vec![
+            Span::raw(excerpt[..start].to_string()),
+            Span::styled(excerpt[start..end].to_string(), Theme::current().matched()),
+            Span::raw(excerpt[end..].to_string()),
+        ]
+    }
+}
+
+/// returns the value on one line around the first match of `pattern`, cut with `…` where it goes on
+fn excerpt(value: &str, pattern: &str) -> String {
+    let chars = value
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect::<Vec<char>>();
+    let lower = value.to_lowercase();
+    // the lowercase value only gives the position of the match when lowercasing kept every character
+    let position = lower
+        .find(pattern.to_lowercase().as_str())
+        .filter(|_| lower.chars().count() == chars.len())
+        .map_or(0, |index| lower[..index].chars().count());
+    let start = position.saturating_sub(EXCERPT_CONTEXT);
+    let end = (start + EXCERPT_WIDTH).min(chars.len());
+    format!(
+        "{}{}{}",
+        if start > 0 { "…" } else { "" },
+        chars[start..end].iter().collect::<String>(),
+        if end < chars.len() { "…" } else { "" }
+    )
+}
+
+impl DrawableComponent for SearchMatchesComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let row_width = self
+            .matches
+            .iter()
+            .map(|(row, _, _, _)| (row + 1).to_string().len())
+            .max()
+            .unwrap_or(0);
+        let header_width = self
+            .matches
+            .iter()
+            .map(|(_, _, header, _)| header.width())
+            .max()
+            .unwrap_or(0)
+            .min(20);
+        let items = self
+            .matches
+            .iter()
+            .map(|(row, _, header, excerpt)| {
+                let mut spans = vec![
+                    Span::styled(
+                        format!("{:>w$} ", row + 1, w = row_width),
+                        Theme::current().hint(),
+                    ),
+                    Span::styled(
+                        format!("{:w$} ", header, w = header_width),
+                        Theme::current().accent(),
+                    ),
+                ];
+                spans.extend(self.excerpt_spans(excerpt));
+                ListItem::new(Spans::from(spans))
+            })
+            .collect::<Vec<ListItem>>();
+        let area = popup_area(
+            f,
+            90,
+            (self.matches.len() as u16)
+                .saturating_add(3)
+                .min(f.size().height.saturating_sub(4)),
+        );
+        f.render_stateful_widget(
+            List::new(items)
+                .block(
+                    Block::default()
+                        .title(format!(
+                            "{} `{}' ({})",
+                            Msg::SearchMatches.text(),
+                            self.pattern,
+                            self.matches.len()
+                        ))
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Thick),
+                )
+                .highlight_style(Theme::current().selected()),
+            area,
+            &mut self.state,
+        );
+        if area.height > 2 {
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    format!(
+                        " [{}] {} [{}] {} ",
+                        self.key_config.enter,
+                        Msg::JumpToCell.text(),
+                        self.key_config.exit_popup,
+                        Msg::Cancel.text()
+                    ),
+                    Theme::current().hint(),
+                )),
+                Rect::new(
+                    area.x + 1,
+                    area.y + area.height - 1,
+                    area.width.saturating_sub(2),
+                    1,
+                ),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Component for SearchMatchesComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            self.jump = self
+                .state
+                .selected()
+                .and_then(|index| self.matches.get(index))
+                .map(|(row, column, _, _)| (*row, *column));
+            self.hide();
+        } else if key == self.key_config.scroll_down || key == Key::Down {
+            self.select(true, 1);
+        } else if key == self.key_config.scroll_up || key == Key::Up {
+            self.select(false, 1);
+        } else if key == self.key_config.scroll_down_multiple_lines || key == Key::PageDown {
+            self.select(true, 10);
+        } else if key == self.key_config.scroll_up_multiple_lines || key == Key::PageUp {
+            self.select(false, 10);
+        } else if key == self.key_config.scroll_to_top || key == Key::Home {
+            self.state.select(Some(0));
+        } else if key == self.key_config.scroll_to_bottom || key == Key::End {
+            self.select(true, self.matches.len());
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{excerpt, Component as _, KeyConfig, SearchMatchesComponent};
+
+    #[test]
+    fn test_excerpt() {
+        assert_eq!(excerpt("alice@example.com", "EXAMPLE"), "alice@example.com");
+        assert_eq!(
+            excerpt(
+                "a long note\nwhich mentions the refund only after a while, and then goes on and on",
+                "refund"
+            ),
+            "…h mentions the refund only after a while, and then goes on a…"
+        );
+    }
+
+    #[test]
+    fn test_jump() {
+        let mut component = SearchMatchesComponent::new(KeyConfig::default());
+        let headers = vec!["id".to_string(), "email".to_string()];
+        component
+            .open(
+                "ex".to_string(),
+                &headers,
+                vec![
+                    (0, 1, "a@ex.com".to_string()),
+                    (3, 1, "b@ex.com".to_string()),
+                    (7, 0, "ex".to_string()),
+                ],
+                (2, 0),
+            )
+            .unwrap();
+        assert_eq!(component.state.selected(), Some(1));
+        assert_eq!(component.matches[1].2, "email");
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(!component.visible);
+        assert_eq!(component.take_jump(), Some((7, 0)));
+        assert_eq!(component.take_jump(), None);
+    }
+}
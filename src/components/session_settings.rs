@@ -0,0 +1,212 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// asks for session settings which override those of the connection while the query runs
+pub struct SessionSettingsComponent {
+    input: String,
+    connection_settings: String,
+    overrides: Option<Vec<(String, String)>>,
+    error: Option<String>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl SessionSettingsComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            input: String::new(),
+            connection_settings: String::new(),
+            overrides: None,
+            error: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    /// opens the prompt, keeping the last overrides and showing the settings of the connection
+    pub fn open(&mut self, settings: &BTreeMap<String, String>) -> Result<()> {
+        self.connection_settings = settings
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .join(" ");
+        self.overrides = None;
+        self.error = None;
+        self.show()
+    }
+
+    /// returns the settings to apply once the user entered them
+    pub fn take_overrides(&mut self) -> Option<Vec<(String, String)>> {
+        self.overrides.take()
+    }
+
+    fn submit(&mut self) {
+        match parse_settings(self.input.as_str()) {
+            Ok(overrides) => {
+                self.overrides = Some(overrides);
+                self.hide();
+            }
+            Err(err) => self.error = Some(err.to_string()),
+        }
+    }
+}
+
+/// parses whitespace separated `name=value` pairs
+fn parse_settings(input: &str) -> Result<Vec<(String, String)>> {
+    input
+        .split_whitespace()
+        .map(|pair| match pair.split_once('=') {
+            Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+            _ => Err(anyhow::anyhow!("expected name=value but got {}", pair)),
+        })
+        .collect()
+}
+
+impl DrawableComponent for SessionSettingsComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        const SIZE: (u16, u16) = (60, 5);
+        let area = popup_area(f, SIZE.0, SIZE.1);
+        let text = vec![
+            if self.input.is_empty() {
+                Spans::from(Span::styled(
+                    Msg::SessionSettingsPlaceholder.text(),
+                    Theme::current().hint(),
+                ))
+            } else {
+                Spans::from(Span::raw(self.input.as_str()))
+            },
+            match self.error.as_ref() {
+                Some(error) => Spans::from(Span::styled(error.as_str(), Theme::current().error())),
+                None => Spans::from(Span::styled(
+                    format!(
+                        "{}: {}",
+                        Msg::ConnectionSettings.text(),
+                        self.connection_settings
+                    ),
+                    Theme::current().hint(),
+                )),
+            },
+            Spans::from(Span::styled(
+                format!(
+                    "[{}] {}  [{}] {}",
+                    self.key_config.enter,
+                    Msg::Run.text(),
+                    self.key_config.exit_popup,
+                    Msg::Cancel.text()
+                ),
+                Theme::current().hint(),
+            )),
+        ];
+        f.render_widget(
+            Paragraph::new(text).block(
+                Block::default()
+                    .title(Msg::RunWithSettings.text())
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick),
+            ),
+            area,
+        );
+        f.set_cursor(area.x + 1 + self.input.width() as u16, area.y + 1);
+        Ok(())
+    }
+}
+
+impl Component for SessionSettingsComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            self.submit();
+        } else {
+            match key {
+                Key::Char(c) => self.input.push(c),
+                Key::Backspace | Key::Delete => {
+                    self.input.pop();
+                }
+                _ => (),
+            }
+            self.error = None;
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_settings, Component as _, KeyConfig, SessionSettingsComponent};
+    use crate::event::Key;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_parse_settings() {
+        assert_eq!(
+            parse_settings(" work_mem=256MB  statement_timeout=0 ").unwrap(),
+            vec![
+                ("work_mem".to_string(), "256MB".to_string()),
+                ("statement_timeout".to_string(), "0".to_string()),
+            ]
+        );
+        assert_eq!(parse_settings("").unwrap(), vec![]);
+        assert!(parse_settings("work_mem").is_err());
+        assert!(parse_settings("=1").is_err());
+    }
+
+    #[test]
+    fn test_take_overrides() {
+        let mut component = SessionSettingsComponent::new(KeyConfig::default());
+        component.open(&BTreeMap::new()).unwrap();
+        for c in "work_mem".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(component.error.is_some());
+        assert_eq!(component.take_overrides(), None);
+
+        for c in "=1GB".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(!component.visible);
+        assert_eq!(
+            component.take_overrides(),
+            Some(vec![("work_mem".to_string(), "1GB".to_string())])
+        );
+        assert_eq!(component.take_overrides(), None);
+    }
+}
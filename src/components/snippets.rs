@@ -0,0 +1,281 @@
+use super::{popup_area, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::snippets::{Snippet, SnippetLibrary};
+use crate::theme::Theme;
+use anyhow::Result;
+use itertools::Itertools;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+enum Mode {
+    /// asks for the name to save the query under
+    Save(String),
+    /// searches the snippets to insert one
+    Choose,
+}
+
+/// saves the query of the SQL editor as a snippet of the connection, or lists the snippets to insert one
+pub struct SnippetsComponent {
+    library: SnippetLibrary,
+    mode: Mode,
+    input: String,
+    state: ListState,
+    chosen: Option<String>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl SnippetsComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            library: SnippetLibrary::default(),
+            mode: Mode::Choose,
+            input: String::new(),
+            state: ListState::default(),
+            chosen: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn set_library(&mut self, library: SnippetLibrary) {
+        self.library = library;
+    }
+
+    /// asks for the name to save `query` under, unless it is empty
+    pub fn open_save(&mut self, query: String) -> Result<()> {
+        if query.trim().is_empty() {
+            return Ok(());
+        }
+        self.mode = Mode::Save(query);
+        self.input.clear();
+        self.show()
+    }
+
+    /// opens the list of the snippets with the first one selected
+    pub fn open(&mut self) -> Result<()> {
+        self.mode = Mode::Choose;
+        self.input.clear();
+        self.chosen = None;
+        self.select_first();
+        self.show()
+    }
+
+    /// returns the query of the snippet the user chose
+    pub fn take_chosen(&mut self) -> Option<String> {
+        self.chosen.take()
+    }
+
+    fn matches(&self) -> Vec<&Snippet> {
+        self.library.search(self.input.as_str())
+    }
+
+    fn select_first(&mut self) {
+        self.state.select(if self.matches().is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+    }
+
+    fn select_next(&mut self) {
+        let len = self.matches().len();
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some((i + 1) % len));
+        }
+    }
+
+    fn select_previous(&mut self) {
+        let len = self.matches().len();
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some((i + len - 1) % len));
+        }
+    }
+
+    fn hint(&self, action: &str) -> Spans<'static> {
+        Spans::from(Span::styled(
+            format!(
+                "[{}] {}  [{}] {}",
+                self.key_config.enter,
+                action,
+                self.key_config.exit_popup,
+                Msg::Cancel.text()
+            ),
+            Theme::current().hint(),
+        ))
+    }
+
+    fn input_line(&self, placeholder: Msg) -> Spans<'static> {
+        if self.input.is_empty() {
+            Spans::from(Span::styled(placeholder.text(), Theme::current().hint()))
+        } else {
+            Spans::from(Span::raw(self.input.clone()))
+        }
+    }
+}
+
+impl DrawableComponent for SnippetsComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick);
+        match self.mode {
+            Mode::Save(_) => {
+                let area = popup_area(f, 60, 4);
+                let text = vec![
+                    self.input_line(Msg::SnippetNamePlaceholder),
+                    self.hint(Msg::Save.text()),
+                ];
+                f.render_widget(
+                    Paragraph::new(text).block(block.title(Msg::SaveSnippet.text())),
+                    area,
+                );
+                f.set_cursor(area.x + 1 + self.input.width() as u16, area.y + 1);
+            }
+            Mode::Choose => {
+                let area = popup_area(f, 70, 20);
+                f.render_widget(block.title(Msg::Snippets.text()), area);
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(1)
+                    .constraints([
+                        Constraint::Length(1),
+                        Constraint::Min(1),
+                        Constraint::Length(1),
+                    ])
+                    .split(area);
+                f.render_widget(
+                    Paragraph::new(self.input_line(Msg::SearchSnippetsPlaceholder)),
+                    chunks[0],
+                );
+                let items = self
+                    .matches()
+                    .iter()
+                    .map(|snippet| {
+                        ListItem::new(Spans::from(vec![
+                            Span::raw(snippet.name.clone()),
+                            Span::styled(
+                                format!(" {}", snippet.query.split_whitespace().join(" ")),
+                                Theme::current().hint(),
+                            ),
+                        ]))
+                    })
+                    .collect::<Vec<ListItem>>();
+                f.render_stateful_widget(
+                    List::new(items).highlight_style(Theme::current().selected()),
+                    chunks[1],
+                    &mut self.state,
+                );
+                f.render_widget(Paragraph::new(self.hint(Msg::Insert.text())), chunks[2]);
+                f.set_cursor(chunks[0].x + self.input.width() as u16, chunks[0].y);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Component for SnippetsComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            match &self.mode {
+                Mode::Save(query) => {
+                    if self.input.trim().is_empty() {
+                        return Ok(EventState::Consumed);
+                    }
+                    self.library.save(self.input.as_str(), query)?;
+                }
+                Mode::Choose => {
+                    self.chosen = self
+                        .state
+                        .selected()
+                        .and_then(|i| self.matches().get(i).map(|snippet| snippet.query.clone()));
+                }
+            }
+            self.hide();
+        } else {
+            match key {
+                Key::Down => self.select_next(),
+                Key::Up => self.select_previous(),
+                Key::Char(c) => {
+                    self.input.push(c);
+                    self.select_first();
+                }
+                Key::Backspace | Key::Delete => {
+                    self.input.pop();
+                    self.select_first();
+                }
+                _ => (),
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, Key, KeyConfig, SnippetLibrary, SnippetsComponent};
+
+    #[test]
+    fn test_save_and_choose() {
+        let mut component = SnippetsComponent::new(KeyConfig::default());
+        component.set_library(SnippetLibrary::default());
+        component.open_save("  ".to_string()).unwrap();
+        assert!(!component.visible);
+
+        component
+            .open_save("SELECT * FROM users".to_string())
+            .unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(component.visible);
+        for c in "users".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(!component.visible);
+
+        component.open().unwrap();
+        component.event(Key::Char('x')).unwrap();
+        assert_eq!(component.state.selected(), None);
+        component.event(Key::Backspace).unwrap();
+        component.event(Key::Down).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(
+            component.take_chosen(),
+            Some("SELECT * FROM users".to_string())
+        );
+        assert_eq!(component.take_chosen(), None);
+    }
+}
@@ -0,0 +1,1177 @@
+use super::{
+    compute_character_width, utils::fuzzy::fuzzy_match, Component, DrawableComponent, EventState,
+    QueryPlanComponent, TableComponent,
+};
+use crate::components::command::{self, CommandInfo};
+use crate::config::{KeyConfig, TableConfig};
+use crate::database::{unsupported_constructs, DialectHint, Engine, ExecuteResult};
+use crate::event::{Key, Mouse, MouseKind, WHEEL_LINES};
+use crate::history::QueryHistory;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use crate::ui::tab_at;
+use anyhow::Result;
+use std::ops::Range;
+use std::time::Instant;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Tabs, Wrap},
+    Frame,
+};
+
+const SPINNER: [&str; 8] = ["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"];
+
+pub enum Focus {
+    Editor,
+    Table,
+}
+
+#[derive(Default)]
+struct HistorySearch {
+    pattern: String,
+    matched: Option<usize>,
+    failed: bool,
+}
+
+enum QueryResult {
+    Table(Box<TableComponent>),
+    Plan(Box<QueryPlanComponent>),
+    Message(String),
+    Error(String),
+}
+
+impl QueryResult {
+    /// returns what the statement returned in a few words, shown in its tab
+    fn summary(&self) -> String {
+        match self {
+            Self::Table(table) => format!("{} {}", table.rows.len(), Msg::Rows.text()),
+            Self::Plan(_) => Msg::QueryPlan.text().to_string(),
+            Self::Message(message) => message.clone(),
+            Self::Error(_) => Msg::Failed.text().to_string(),
+        }
+    }
+}
+
+pub struct SqlEditorComponent {
+    input: Vec<char>,
+    input_idx: usize,
+    history: QueryHistory,
+    search: Option<HistorySearch>,
+    running: Option<Instant>,
+    results: Vec<(String, QueryResult)>,
+    selected_result: usize,
+    /// how many times the statements of the results were retried after transient errors
+    retries: u32,
+    /// how long the statement of each result took, with the rows it returned or changed and the warnings it raised
+    statuses: Vec<String>,
+    /// whether a transaction started in the editor is still open
+    transaction: bool,
+    /// the characters of the tables and columns of the query which the schema does not have, which are underlined
+    unknown_names: Vec<Range<usize>>,
+    /// the engine of the connection with the characters of the functions and syntax of the query it does not have,
+    /// which are underlined and named in the title
+    dialect_hints: Option<(Engine, Vec<DialectHint>)>,
+    /// the conditions joining the table of the `JOIN ... ON` before the cursor, the first of which Tab inserts
+    join_conditions: Vec<String>,
+    /// the columns of the table or the alias before the dot at the cursor, which complete the word after the dot
+    column_candidates: Vec<String>,
+    /// the columns which Tab and Shift-Tab go through, replacing the word starting at the index with the selected one
+    column_cycle: Option<(usize, Vec<String>, usize)>,
+    /// the first line of the query shown in the editor
+    editor_scroll: u16,
+    /// whether the editor scrolls to the cursor, which it stops doing once the wheel scrolled it
+    follow_cursor: bool,
+    focus: Focus,
+    area: Rect,
+    /// whether the tables of the results draw invisible characters as placeholders
+    reveal_invisible: bool,
+    key_config: KeyConfig,
+    table_config: TableConfig,
+}
+
+impl SqlEditorComponent {
+    pub fn new(key_config: KeyConfig, table_config: TableConfig) -> Self {
+        Self {
+            input: Vec::new(),
+            input_idx: 0,
+            history: QueryHistory::default(),
+            search: None,
+            running: None,
+            results: Vec::new(),
+            selected_result: 0,
+            retries: 0,
+            statuses: Vec::new(),
+            transaction: false,
+            unknown_names: Vec::new(),
+            dialect_hints: None,
+            join_conditions: Vec::new(),
+            column_candidates: Vec::new(),
+            column_cycle: None,
+            editor_scroll: 0,
+            follow_cursor: true,
+            focus: Focus::Editor,
+            area: Rect::default(),
+            reveal_invisible: false,
+            key_config,
+            table_config,
+        }
+    }
+
+    pub fn query(&self) -> String {
+        self.input.iter().collect()
+    }
+
+    pub fn set_unknown_names(&mut self, unknown_names: Vec<Range<usize>>) {
+        self.unknown_names = unknown_names;
+    }
+
+    pub fn set_dialect_hints(&mut self, engine: Engine, hints: Vec<DialectHint>) {
+        self.dialect_hints = Some((engine, hints));
+    }
+
+    /// returns the query up to the cursor, whose end is completed
+    pub fn query_before_cursor(&self) -> String {
+        self.input[..self.input_idx].iter().collect()
+    }
+
+    pub fn set_join_conditions(&mut self, conditions: Vec<String>) {
+        self.join_conditions = conditions;
+    }
+
+    pub fn set_column_candidates(&mut self, columns: Vec<String>) {
+        self.column_candidates = columns;
+    }
+
+    fn word_start(&self) -> usize {
+        self.input[..self.input_idx]
+            .iter()
+            .rposition(|c| !c.is_alphanumeric() && *c != '_')
+            .map_or(0, |i| i + 1)
+    }
+
+    /// returns the columns which the word after the dot matches fuzzily, the best match first
+    fn matched_columns(&self) -> Vec<String> {
+        if let Some((_, columns, _)) = &self.column_cycle {
+            return columns.clone();
+        }
+        let word = self.input[self.word_start()..self.input_idx]
+            .iter()
+            .collect::<String>();
+        let mut matches = self
+            .column_candidates
+            .iter()
+            .filter_map(|column| {
+                fuzzy_match(word.as_str(), column.as_str()).map(|(score, _)| (score, column))
+            })
+            .collect::<Vec<(i64, &String)>>();
+        if !word.is_empty() {
+            matches.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.len().cmp(&b.1.len())));
+        }
+        matches
+            .into_iter()
+            .map(|(_, column)| column.clone())
+            .collect()
+    }
+
+    /// replaces the word after the dot with the next column it matches, or the previous one when not `forward`,
+    /// returning false when it matches none
+    fn complete_column(&mut self, forward: bool) -> bool {
+        let (start, columns, selected) = match self.column_cycle.take() {
+            Some((start, columns, selected)) => {
+                let len = columns.len();
+                let selected = if forward {
+                    (selected + 1) % len
+                } else {
+                    (selected + len - 1) % len
+                };
+                (start, columns, selected)
+            }
+            None => {
+                let columns = self.matched_columns();
+                if columns.is_empty() {
+                    return false;
+                }
+                let selected = if forward { 0 } else { columns.len() - 1 };
+                (self.word_start(), columns, selected)
+            }
+        };
+        self.input
+            .splice(start..self.input_idx, columns[selected].chars());
+        self.input_idx = start + columns[selected].chars().count();
+        self.column_cycle = Some((start, columns, selected));
+        true
+    }
+
+    /// inserts the most likely condition of the join before the cursor, returning false when there is none
+    fn complete_join(&mut self) -> bool {
+        let condition = match self.join_conditions.first() {
+            Some(condition) => condition.clone(),
+            None => return false,
+        };
+        if self.input_idx > 0 && !self.input[self.input_idx - 1].is_whitespace() {
+            self.insert_text(" ");
+        }
+        self.insert_text(condition.as_str());
+        self.join_conditions.clear();
+        true
+    }
+
+    /// returns the query with the unknown names and what the engine does not support underlined
+    fn query_spans(&self) -> Spans<'static> {
+        let mut underlined = self
+            .unknown_names
+            .iter()
+            .map(|range| (range.clone(), Theme::current().error()))
+            .collect::<Vec<_>>();
+        if let Some((_, hints)) = &self.dialect_hints {
+            underlined.extend(
+                hints
+                    .iter()
+                    .map(|(range, _)| (range.clone(), Theme::current().literal())),
+            );
+        }
+        underlined.sort_by_key(|(range, _)| range.start);
+        let mut spans = vec![];
+        let mut start = 0;
+        for (range, style) in underlined {
+            let range =
+                range.start.max(start).min(self.input.len())..range.end.min(self.input.len());
+            if range.is_empty() {
+                continue;
+            }
+            spans.push(Span::raw(
+                self.input[start..range.start].iter().collect::<String>(),
+            ));
+            spans.push(Span::styled(
+                self.input[range.clone()].iter().collect::<String>(),
+                style.add_modifier(Modifier::UNDERLINED),
+            ));
+            start = range.end;
+        }
+        spans.push(Span::raw(self.input[start..].iter().collect::<String>()));
+        Spans::from(spans)
+    }
+
+    pub fn editor_focused(&self) -> bool {
+        matches!(self.focus, Focus::Editor)
+    }
+
+    pub fn set_reveal_invisible(&mut self, reveal_invisible: bool) {
+        self.reveal_invisible = reveal_invisible;
+    }
+
+    pub fn set_history(&mut self, history: QueryHistory) {
+        self.history = history;
+        self.search = None;
+    }
+
+    /// replaces the query in the editor with `text`, putting the cursor at its end
+    pub fn set_query(&mut self, text: &str) {
+        self.input = text.chars().collect();
+        self.input_idx = self.input.len();
+        self.search = None;
+        self.focus = Focus::Editor;
+        self.follow_cursor = true;
+    }
+
+    /// inserts `text` at the cursor, leaving the cursor after it
+    pub fn insert_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.input.insert(self.input_idx, c);
+            self.input_idx += 1;
+        }
+        self.focus = Focus::Editor;
+        self.follow_cursor = true;
+    }
+
+    pub fn push_history(&mut self) -> Result<()> {
+        self.history.push(self.query().as_str())
+    }
+
+    pub fn start_running(&mut self) {
+        self.running = Some(Instant::now());
+    }
+
+    pub fn finish_running(&mut self) {
+        self.running = None;
+    }
+
+    pub fn searching_history(&self) -> bool {
+        self.search.is_some()
+    }
+
+    /// returns whether the pattern of a search in the selected result is being typed
+    pub fn searching_result(&self) -> bool {
+        matches!(
+            self.results.get(self.selected_result),
+            Some((_, QueryResult::Table(table))) if table.searching()
+        )
+    }
+
+    /// returns the table of the selected result tab if it shows rows
+    pub fn result_table(&mut self) -> Option<&mut TableComponent> {
+        match self.results.get_mut(self.selected_result) {
+            Some((_, QueryResult::Table(table))) => Some(table.as_mut()),
+            _ => None,
+        }
+    }
+
+    /// looks for the pattern starting at `before`, keeping the last match when nothing is found
+    fn search_history(&mut self, before: Option<usize>) {
+        if let Some(search) = self.search.as_mut() {
+            if search.pattern.is_empty() {
+                search.matched = None;
+                search.failed = false;
+                return;
+            }
+            match self.history.search(search.pattern.as_str(), before) {
+                Some(index) => {
+                    search.matched = Some(index);
+                    search.failed = false;
+                }
+                None => search.failed = true,
+            }
+        }
+    }
+
+    fn accept_search(&mut self) {
+        if let Some(search) = self.search.take() {
+            if let Some(entry) = search.matched.and_then(|index| self.history.get(index)) {
+                self.input = entry.chars().collect();
+                self.input_idx = self.input.len();
+            }
+        }
+    }
+
+    fn search_event(&mut self, key: Key) -> EventState {
+        let matched = self.search.as_ref().and_then(|search| search.matched);
+        if key == self.key_config.search_history {
+            self.search_history(matched);
+            return EventState::Consumed;
+        }
+        if key == self.key_config.exit_popup {
+            self.search = None;
+            return EventState::Consumed;
+        }
+        if key == self.key_config.enter {
+            self.accept_search();
+            return EventState::Consumed;
+        }
+        match key {
+            Key::Char(c) => {
+                if let Some(search) = self.search.as_mut() {
+                    search.pattern.push(c);
+                }
+                self.search_history(matched.map(|index| index + 1));
+                EventState::Consumed
+            }
+            Key::Delete | Key::Backspace => {
+                if let Some(search) = self.search.as_mut() {
+                    search.pattern.pop();
+                }
+                self.search_history(None);
+                EventState::Consumed
+            }
+            _ => {
+                self.accept_search();
+                EventState::NotConsumed
+            }
+        }
+    }
+
+    fn search_text(&self, search: &HistorySearch) -> Spans<'static> {
+        // the line breaks of the entry are shown as spaces, which are as long, so that it fits on the line
+        let entry = match search.matched.and_then(|index| self.history.get(index)) {
+            Some(entry) => entry.replace('\n', " "),
+            None => return Spans::default(),
+        };
+        match entry.find(search.pattern.as_str()) {
+            Some(start) if !search.pattern.is_empty() => {
+                let end = start + search.pattern.len();
+                Spans::from(vec![
+                    Span::raw(entry[..start].to_string()),
+                    Span::styled(entry[start..end].to_string(), Theme::current().matched()),
+                    Span::raw(entry[end..].to_string()),
+                ])
+            }
+            _ => Spans::from(entry.to_string()),
+        }
+    }
+
+    /// replaces the result tabs with one tab per executed statement, selecting the failed statement or the last one which returned rows
+    pub fn set_results(&mut self, results: Vec<(String, Result<ExecuteResult>)>) {
+        self.statuses.clear();
+        self.results = results
+            .into_iter()
+            .map(|(query, result)| {
+                let result = match result {
+                    Ok(ExecuteResult::Read { headers, rows }) => {
+                        let mut table =
+                            TableComponent::new(self.key_config.clone(), self.table_config.clone());
+                        table.update_rows(rows, headers);
+                        table.set_reveal_invisible(self.reveal_invisible);
+                        QueryResult::Table(Box::new(table))
+                    }
+                    Ok(ExecuteResult::Plan(plan)) => QueryResult::Plan(Box::new(
+                        QueryPlanComponent::new(self.key_config.clone(), &plan),
+                    )),
+                    Ok(ExecuteResult::Write { updated_rows }) => QueryResult::Message(format!(
+                        "{} {}",
+                        updated_rows,
+                        Msg::RowsAffected.text()
+                    )),
+                    Err(err) => QueryResult::Error(err.to_string()),
+                };
+                (query, result)
+            })
+            .collect();
+        self.selected_result = self
+            .results
+            .iter()
+            .rposition(|(_, result)| matches!(result, QueryResult::Error(_)))
+            .or_else(|| {
+                self.results
+                    .iter()
+                    .rposition(|(_, result)| matches!(result, QueryResult::Table(_)))
+            })
+            .unwrap_or_else(|| self.results.len().saturating_sub(1));
+        if !self.results.is_empty() {
+            self.focus = Focus::Table;
+        }
+    }
+
+    /// replaces the results of the statements run again, refreshing the tables in place so that their selected cells
+    /// stay when the same statements still return rows
+    pub fn refresh_results(&mut self, results: Vec<(String, Result<ExecuteResult>)>) {
+        let same = results.len() == self.results.len()
+            && results.iter().zip(&self.results).all(
+                |((query, result), (current_query, current))| {
+                    query == current_query
+                        && matches!(
+                            (result, current),
+                            (Ok(ExecuteResult::Read { .. }), QueryResult::Table(_))
+                        )
+                },
+            );
+        if !same {
+            return self.set_results(results);
+        }
+        for ((_, result), (_, current)) in results.into_iter().zip(self.results.iter_mut()) {
+            if let (Ok(ExecuteResult::Read { headers, rows }), QueryResult::Table(table)) =
+                (result, current)
+            {
+                table.refresh_rows(rows, headers);
+            }
+        }
+    }
+
+    /// marks the tables of the results whose rows were all fetched, `more` telling for each result whether a page of
+    /// its rows was fetched with more left to fetch
+    pub fn set_more_rows(&mut self, more: &[bool]) {
+        for (index, (_, result)) in self.results.iter_mut().enumerate() {
+            if let QueryResult::Table(table) = result {
+                if !more.get(index).copied().unwrap_or(false) {
+                    table.end();
+                }
+            }
+        }
+    }
+
+    /// returns the statement of the selected result with its table, when it returned rows
+    pub fn selected_result(&mut self) -> Option<(&str, &mut TableComponent)> {
+        match self.results.get_mut(self.selected_result) {
+            Some((statement, QueryResult::Table(table))) => {
+                Some((statement.as_str(), table.as_mut()))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn set_retries(&mut self, retries: u32) {
+        self.retries = retries;
+    }
+
+    /// sets the status of the statement of each result, shown above the selected one
+    pub fn set_statuses(&mut self, statuses: Vec<String>) {
+        self.statuses = statuses;
+    }
+
+    pub fn set_transaction(&mut self, transaction: bool) {
+        self.transaction = transaction;
+    }
+
+    pub fn selected_cells(&self) -> Option<String> {
+        match self.results.get(self.selected_result) {
+            Some((_, QueryResult::Table(table))) => table.selected_cells(),
+            Some((_, QueryResult::Plan(plan))) => plan.selected_label(),
+            _ => None,
+        }
+    }
+
+    /// returns the statement of the selected result
+    pub fn selected_statement(&self) -> Option<&str> {
+        self.results
+            .get(self.selected_result)
+            .map(|(statement, _)| statement.as_str())
+    }
+
+    pub fn selected_tsv(&self) -> Option<String> {
+        match self.results.get(self.selected_result) {
+            Some((_, QueryResult::Table(table))) => table.selected_tsv(),
+            _ => None,
+        }
+    }
+
+    fn result_titles(&self) -> Vec<String> {
+        self.results
+            .iter()
+            .enumerate()
+            .map(|(i, (_, result))| {
+                format!("{} {}: {}", Msg::Result.text(), i + 1, result.summary())
+            })
+            .collect()
+    }
+
+    /// splits the area into the editor, the tabs of the results and the selected result
+    fn layout(area: Rect) -> Vec<Rect> {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length(5),
+                    Constraint::Length(3),
+                    Constraint::Min(1),
+                ]
+                .as_ref(),
+            )
+            .split(area)
+    }
+
+    fn next_result(&mut self) {
+        if !self.results.is_empty() {
+            self.selected_result = (self.selected_result + 1) % self.results.len();
+        }
+    }
+
+    fn previous_result(&mut self) {
+        if !self.results.is_empty() {
+            self.selected_result =
+                (self.selected_result + self.results.len() - 1) % self.results.len();
+        }
+    }
+
+    /// returns the cursor position relative to the inside of the editor block
+    fn cursor_position(&self, width: u16) -> (u16, u16) {
+        self.position(self.input_idx, width)
+    }
+
+    /// returns the position of the character at `index` of the query relative to the inside of the editor block
+    fn position(&self, index: usize, width: u16) -> (u16, u16) {
+        let (mut x, mut y) = (0, 0);
+        for c in self.input.iter().take(index) {
+            let w = compute_character_width(*c);
+            if x + w > width {
+                x = 0;
+                y += 1;
+            }
+            x += w;
+        }
+        if x >= width {
+            return (0, y + 1);
+        }
+        (x, y)
+    }
+}
+
+impl DrawableComponent for SqlEditorComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, area: Rect, focused: bool) -> Result<()> {
+        self.area = area;
+        let chunks = Self::layout(area);
+
+        let editor_focused = focused && self.editor_focused();
+        let mut block = Block::default().borders(Borders::ALL);
+        if self.transaction {
+            block = block.title(Span::styled(
+                format!(
+                    "{} [{}] {} [{}] {}",
+                    Msg::TransactionOpen.text(),
+                    self.key_config.commit_transaction,
+                    Msg::Commit.text(),
+                    self.key_config.rollback_transaction,
+                    Msg::Rollback.text()
+                ),
+                Theme::current().error(),
+            ));
+        }
+        match &self.dialect_hints {
+            Some((engine, hints))
+                if !self.transaction && self.search.is_none() && !hints.is_empty() =>
+            {
+                block = block.title(Span::styled(
+                    format!(
+                        "{} {}: {}",
+                        Msg::NotSupportedBy.text(),
+                        engine,
+                        unsupported_constructs(hints).join(", ")
+                    ),
+                    Theme::current().literal(),
+                ));
+            }
+            _ => (),
+        }
+        if editor_focused && !self.transaction && self.search.is_none() {
+            if let Some(condition) = self.join_conditions.first() {
+                block = block.title(Span::styled(
+                    format!("{} [{}]: {}", Msg::JoinOn.text(), Key::Tab, condition),
+                    Theme::current().hint(),
+                ));
+            } else {
+                let columns = self.matched_columns();
+                if !columns.is_empty() {
+                    block = block.title(Span::styled(
+                        format!(
+                            "{} [{}]: {}",
+                            Msg::Columns.text(),
+                            Key::Tab,
+                            columns.join(", ")
+                        ),
+                        Theme::current().hint(),
+                    ));
+                }
+            }
+        }
+        let text = if let Some(search) = self.search.as_ref() {
+            block = block.title(format!(
+                "({}) `{}'",
+                if search.failed {
+                    Msg::HistorySearchFailed.text()
+                } else {
+                    Msg::HistorySearch.text()
+                },
+                search.pattern
+            ));
+            self.search_text(search)
+        } else if editor_focused || !self.input.is_empty() {
+            self.query_spans()
+        } else {
+            Spans::from(Msg::SqlEditorPlaceholder.text())
+        };
+        let (width, height) = (
+            chunks[0].width.saturating_sub(2),
+            chunks[0].height.saturating_sub(2),
+        );
+        let (x, y) = self.cursor_position(width);
+        if self.follow_cursor {
+            if y < self.editor_scroll {
+                self.editor_scroll = y;
+            } else if y >= self.editor_scroll + height {
+                self.editor_scroll = (y + 1).saturating_sub(height);
+            }
+        }
+        self.editor_scroll = self
+            .editor_scroll
+            .min(self.position(self.input.len(), width).1);
+        let editor = Paragraph::new(text)
+            .style(Theme::current().text(editor_focused))
+            .wrap(Wrap { trim: false })
+            .block(block)
+            .scroll((
+                if self.searching_history() {
+                    0
+                } else {
+                    self.editor_scroll
+                },
+                0,
+            ));
+        f.render_widget(editor, chunks[0]);
+        if editor_focused
+            && !self.searching_history()
+            && (self.editor_scroll..self.editor_scroll + height).contains(&y)
+        {
+            f.set_cursor(
+                chunks[0].x + 1 + x,
+                chunks[0].y + 1 + y - self.editor_scroll,
+            );
+        }
+
+        let titles = self.result_titles().into_iter().map(Spans::from).collect();
+        let mut title = self
+            .statuses
+            .get(self.selected_result)
+            .cloned()
+            .unwrap_or_default();
+        if self.retries > 0 {
+            title = format!(
+                "{}  {} ({})",
+                title,
+                Msg::RetriedTransientErrors.text(),
+                self.retries
+            );
+        }
+        let block = Block::default().borders(Borders::ALL).title(Span::styled(
+            title.trim_start().to_string(),
+            Theme::current().hint(),
+        ));
+        f.render_widget(
+            Tabs::new(titles)
+                .block(block)
+                .select(self.selected_result)
+                .style(Theme::current().hint())
+                .highlight_style(
+                    Style::default()
+                        .fg(Theme::current().focused_fg)
+                        .add_modifier(Modifier::UNDERLINED),
+                ),
+            chunks[1],
+        );
+
+        let result_focused = focused && !self.editor_focused();
+        if let Some(started) = self.running {
+            let elapsed = started.elapsed();
+            f.render_widget(
+                Paragraph::new(Spans::from(vec![
+                    Span::raw(format!(
+                        "{} {} {:.1}s  ",
+                        SPINNER[(elapsed.as_millis() / 250) as usize % SPINNER.len()],
+                        Msg::RunningQuery.text(),
+                        elapsed.as_secs_f64()
+                    )),
+                    Span::styled(
+                        format!(
+                            "[{},{}] {}",
+                            self.key_config.exit_popup,
+                            self.key_config.exit,
+                            Msg::Cancel.text()
+                        ),
+                        Theme::current().hint(),
+                    ),
+                ]))
+                .block(Block::default().borders(Borders::ALL)),
+                chunks[2],
+            );
+            return Ok(());
+        }
+        match self.results.get_mut(self.selected_result) {
+            Some((_, QueryResult::Table(table))) => table.draw(f, chunks[2], result_focused)?,
+            Some((_, QueryResult::Plan(plan))) => plan.draw(f, chunks[2], result_focused)?,
+            Some((query, QueryResult::Message(message))) => f.render_widget(
+                Paragraph::new(vec![
+                    Spans::from(query.as_str()),
+                    Spans::from(message.as_str()),
+                ])
+                .wrap(Wrap { trim: false })
+                .block(Block::default().borders(Borders::ALL)),
+                chunks[2],
+            ),
+            Some((query, QueryResult::Error(error))) => f.render_widget(
+                Paragraph::new(vec![
+                    Spans::from(query.as_str()),
+                    Spans::from(Span::styled(error.as_str(), Theme::current().error())),
+                ])
+                .wrap(Wrap { trim: false })
+                .block(Block::default().borders(Borders::ALL)),
+                chunks[2],
+            ),
+            None => f.render_widget(Block::default().borders(Borders::ALL), chunks[2]),
+        }
+        Ok(())
+    }
+}
+
+impl Component for SqlEditorComponent {
+    fn commands(&self, out: &mut Vec<CommandInfo>) {
+        out.push(CommandInfo::new(command::execute_query(&self.key_config)));
+        out.push(CommandInfo::new(command::execute_other_fetch(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::focus_sql_editor(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::switch_result_tab(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::search_history(&self.key_config)));
+        out.push(CommandInfo::new(command::save_snippet(&self.key_config)));
+        out.push(CommandInfo::new(command::open_snippets(&self.key_config)));
+        out.push(CommandInfo::new(command::explain_query(&self.key_config)));
+        out.push(CommandInfo::new(command::format_query(&self.key_config)));
+        out.push(CommandInfo::new(command::copy_as_insert(&self.key_config)));
+        out.push(CommandInfo::new(command::explain_analyze_query(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::run_with_settings(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::queue_query(&self.key_config)));
+        out.push(CommandInfo::new(command::open_referenced_table(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::cancel_query(&self.key_config)));
+        out.push(CommandInfo::new(command::end_transaction(&self.key_config)));
+        out.push(CommandInfo::new(command::toggle_watch(&self.key_config)));
+    }
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if self.editor_focused() && self.searching_history() && self.search_event(key).is_consumed()
+        {
+            return Ok(EventState::Consumed);
+        }
+        if self.editor_focused() {
+            self.follow_cursor = true;
+        }
+        if !matches!(key, Key::Tab | Key::BackTab) {
+            self.column_cycle = None;
+        }
+        match self.focus {
+            Focus::Editor => match key {
+                key if key == self.key_config.search_history => {
+                    self.search = Some(HistorySearch::default());
+                    return Ok(EventState::Consumed);
+                }
+                Key::Tab if self.complete_join() => return Ok(EventState::Consumed),
+                Key::Tab if self.complete_column(true) => return Ok(EventState::Consumed),
+                Key::BackTab if self.complete_column(false) => return Ok(EventState::Consumed),
+                Key::Char(c) => {
+                    self.input.insert(self.input_idx, c);
+                    self.input_idx += 1;
+                    return Ok(EventState::Consumed);
+                }
+                Key::Delete | Key::Backspace => {
+                    if self.input_idx > 0 {
+                        self.input.remove(self.input_idx - 1);
+                        self.input_idx -= 1;
+                    }
+                    return Ok(EventState::Consumed);
+                }
+                Key::Left => {
+                    self.input_idx = self.input_idx.saturating_sub(1);
+                    return Ok(EventState::Consumed);
+                }
+                Key::Right => {
+                    if self.input_idx < self.input.len() {
+                        self.input_idx += 1;
+                    }
+                    return Ok(EventState::Consumed);
+                }
+                Key::Ctrl('a') => {
+                    self.input_idx = 0;
+                    return Ok(EventState::Consumed);
+                }
+                Key::Ctrl('e') => {
+                    self.input_idx = self.input.len();
+                    return Ok(EventState::Consumed);
+                }
+                key if key == self.key_config.exit_popup => {
+                    self.focus = Focus::Table;
+                    return Ok(EventState::Consumed);
+                }
+                _ => (),
+            },
+            Focus::Table => {
+                if self.searching_result() {
+                    if let Some((_, QueryResult::Table(table))) =
+                        self.results.get_mut(self.selected_result)
+                    {
+                        return table.event(key);
+                    }
+                }
+                if key == self.key_config.focus_sql_editor {
+                    self.focus = Focus::Editor;
+                    return Ok(EventState::Consumed);
+                }
+                if key == self.key_config.next_result_tab {
+                    self.next_result();
+                    return Ok(EventState::Consumed);
+                }
+                if key == self.key_config.previous_result_tab {
+                    self.previous_result();
+                    return Ok(EventState::Consumed);
+                }
+                match self.results.get_mut(self.selected_result) {
+                    Some((_, QueryResult::Table(table))) => return table.event(key),
+                    Some((_, QueryResult::Plan(plan))) => return plan.event(key),
+                    _ => (),
+                }
+            }
+        }
+        Ok(EventState::NotConsumed)
+    }
+
+    fn mouse_event(&mut self, mouse: Mouse) -> Result<EventState> {
+        if !mouse.is_in(self.area) {
+            return Ok(EventState::NotConsumed);
+        }
+        let chunks = Self::layout(self.area);
+        if mouse.is_in(chunks[0]) {
+            match mouse.kind {
+                MouseKind::Click => self.focus = Focus::Editor,
+                MouseKind::ScrollUp => {
+                    self.follow_cursor = false;
+                    self.editor_scroll = self.editor_scroll.saturating_sub(WHEEL_LINES as u16);
+                }
+                MouseKind::ScrollDown => {
+                    self.follow_cursor = false;
+                    self.editor_scroll = self.editor_scroll.saturating_add(WHEEL_LINES as u16);
+                }
+            }
+        } else if mouse.is_in(chunks[1]) {
+            if mouse.kind == MouseKind::Click && mouse.row == chunks[1].y + 1 {
+                if let Some(index) = tab_at(
+                    &self.result_titles(),
+                    mouse.column.saturating_sub(chunks[1].x + 1),
+                ) {
+                    self.selected_result = index;
+                    self.focus = Focus::Table;
+                }
+            }
+        } else {
+            if mouse.kind == MouseKind::Click {
+                self.focus = Focus::Table;
+            }
+            if let Some(table) = self.result_table() {
+                table.mouse_event(mouse)?;
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Engine, ExecuteResult, KeyConfig, Modifier, QueryHistory, Rect, SqlEditorComponent,
+        TableConfig, Theme,
+    };
+    use crate::components::Component as _;
+    use crate::event::{Key, Mouse, MouseKind};
+
+    #[test]
+    fn test_input() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), TableConfig::default());
+        for c in "SELCT".chars() {
+            editor.event(Key::Char(c)).unwrap();
+        }
+        editor.event(Key::Left).unwrap();
+        editor.event(Key::Left).unwrap();
+        editor.event(Key::Char('E')).unwrap();
+        assert_eq!(editor.query(), "SELECT");
+        assert_eq!(editor.cursor_position(4), (0, 1));
+    }
+
+    #[test]
+    fn test_complete_join() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), TableConfig::default());
+        editor.insert_text("SELECT * FROM orders o JOIN customers c ON");
+        assert!(!editor.event(Key::Tab).unwrap().is_consumed());
+        editor.set_join_conditions(vec![
+            "c.id = o.customer_id".to_string(),
+            "c.region = o.region".to_string(),
+        ]);
+        assert!(editor.event(Key::Tab).unwrap().is_consumed());
+        assert_eq!(
+            editor.query(),
+            "SELECT * FROM orders o JOIN customers c ON c.id = o.customer_id"
+        );
+        assert_eq!(editor.query_before_cursor(), editor.query());
+    }
+
+    #[test]
+    fn test_complete_column() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), TableConfig::default());
+        editor.insert_text("SELECT u.nm");
+        assert!(!editor.event(Key::Tab).unwrap().is_consumed());
+        editor.set_column_candidates(vec![
+            "id".to_string(),
+            "name".to_string(),
+            "nickname".to_string(),
+        ]);
+        assert_eq!(editor.matched_columns(), vec!["name", "nickname"]);
+        assert!(editor.event(Key::Tab).unwrap().is_consumed());
+        assert_eq!(editor.query(), "SELECT u.name");
+        editor.event(Key::Tab).unwrap();
+        assert_eq!(editor.query(), "SELECT u.nickname");
+        editor.event(Key::BackTab).unwrap();
+        editor.event(Key::BackTab).unwrap();
+        assert_eq!(editor.query(), "SELECT u.nickname");
+
+        // typing ends the cycle, after which the columns are those of the new word
+        editor.event(Key::Char(',')).unwrap();
+        editor.insert_text(" u.");
+        assert_eq!(editor.matched_columns(), vec!["id", "name", "nickname"]);
+        editor.event(Key::BackTab).unwrap();
+        assert_eq!(editor.query(), "SELECT u.nickname, u.nickname");
+        assert_eq!(editor.query_before_cursor(), editor.query());
+    }
+
+    #[test]
+    fn test_query_spans() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), TableConfig::default());
+        editor.insert_text("SELECT * FROM ordrs o");
+        editor.set_unknown_names(vec![14..19, 30..40]);
+        let spans = editor.query_spans();
+        assert_eq!(
+            spans
+                .0
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<Vec<&str>>(),
+            vec!["SELECT * FROM ", "ordrs", " o"]
+        );
+        assert!(spans.0[1].style.add_modifier.contains(Modifier::UNDERLINED));
+
+        editor.set_dialect_hints(Engine::Postgres, vec![(7..8, "`name`".to_string())]);
+        let spans = editor.query_spans();
+        assert_eq!(
+            spans
+                .0
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<Vec<&str>>(),
+            vec!["SELECT ", "*", " FROM ", "ordrs", " o"]
+        );
+        assert_eq!(spans.0[1].style.fg, Theme::current().literal().fg);
+    }
+
+    #[test]
+    fn test_search_history() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), TableConfig::default());
+        let mut history = QueryHistory::default();
+        history.push("SELECT * FROM users").unwrap();
+        history.push("SELECT * FROM posts").unwrap();
+        history.push("DELETE FROM posts").unwrap();
+        editor.set_history(history);
+
+        editor.event(KeyConfig::default().search_history).unwrap();
+        for c in "SEL".chars() {
+            editor.event(Key::Char(c)).unwrap();
+        }
+        assert_eq!(editor.search.as_ref().unwrap().matched, Some(1));
+        editor.event(KeyConfig::default().search_history).unwrap();
+        assert_eq!(editor.search.as_ref().unwrap().matched, Some(0));
+        editor.event(KeyConfig::default().search_history).unwrap();
+        assert!(editor.search.as_ref().unwrap().failed);
+        assert_eq!(editor.search.as_ref().unwrap().matched, Some(0));
+        editor.event(KeyConfig::default().enter).unwrap();
+        assert!(!editor.searching_history());
+        assert_eq!(editor.query(), "SELECT * FROM users");
+
+        editor.event(KeyConfig::default().search_history).unwrap();
+        editor.event(Key::Char('x')).unwrap();
+        editor.event(KeyConfig::default().exit_popup).unwrap();
+        assert!(!editor.searching_history());
+        assert_eq!(editor.query(), "SELECT * FROM users");
+    }
+
+    #[test]
+    fn test_result_tabs() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), TableConfig::default());
+        editor.set_results(vec![
+            (
+                "SELECT 1".to_string(),
+                Ok(ExecuteResult::Read {
+                    headers: vec!["1".to_string()],
+                    rows: vec![vec![Some("1".to_string())]],
+                }),
+            ),
+            (
+                "DELETE FROM a".to_string(),
+                Ok(ExecuteResult::Write { updated_rows: 3 }),
+            ),
+        ]);
+        assert!(!editor.editor_focused());
+        assert_eq!(editor.selected_cells(), Some("1".to_string()));
+        editor.set_more_rows(&[true, false]);
+        assert_eq!(
+            editor
+                .selected_result()
+                .map(|(statement, table)| (statement.to_string(), table.eod)),
+            Some(("SELECT 1".to_string(), false))
+        );
+        editor.set_more_rows(&[]);
+        assert!(editor
+            .selected_result()
+            .map_or(false, |(_, table)| table.eod));
+        editor.event(KeyConfig::default().next_result_tab).unwrap();
+        assert_eq!(editor.selected_result, 1);
+        assert_eq!(editor.selected_cells(), None);
+        editor.event(KeyConfig::default().next_result_tab).unwrap();
+        assert_eq!(editor.selected_result, 0);
+        editor.event(KeyConfig::default().focus_sql_editor).unwrap();
+        assert!(editor.editor_focused());
+    }
+
+    #[test]
+    fn test_mouse_event() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), TableConfig::default());
+        editor.set_results(vec![
+            (
+                "SELECT 1".to_string(),
+                Ok(ExecuteResult::Read {
+                    headers: vec!["1".to_string()],
+                    rows: vec![vec![Some("1".to_string())]],
+                }),
+            ),
+            (
+                "DELETE FROM a".to_string(),
+                Ok(ExecuteResult::Write { updated_rows: 3 }),
+            ),
+        ]);
+        editor.area = Rect::new(0, 0, 60, 20);
+        let click = |column, row| Mouse {
+            kind: MouseKind::Click,
+            column,
+            row,
+        };
+
+        // the tabs of the results are drawn on the line under the editor and its border
+        editor.mouse_event(click(21, 6)).unwrap();
+        assert_eq!(editor.selected_result, 1);
+        editor.mouse_event(click(2, 1)).unwrap();
+        assert!(editor.editor_focused());
+        editor.mouse_event(click(2, 10)).unwrap();
+        assert!(!editor.editor_focused());
+        assert!(!editor.mouse_event(click(2, 20)).unwrap().is_consumed());
+    }
+
+    #[test]
+    fn test_result_summaries() {
+        let mut editor = SqlEditorComponent::new(KeyConfig::default(), TableConfig::default());
+        let read = |value: &str| {
+            Ok(ExecuteResult::Read {
+                headers: vec!["a".to_string()],
+                rows: vec![vec![Some(value.to_string())], vec![Some(value.to_string())]],
+            })
+        };
+        editor.set_results(vec![
+            ("SELECT 1".to_string(), read("1")),
+            ("SELECT 2".to_string(), read("2")),
+            (
+                "UPDATE a SET b = 1".to_string(),
+                Ok(ExecuteResult::Write { updated_rows: 3 }),
+            ),
+        ]);
+        assert_eq!(editor.selected_result, 1);
+        assert_eq!(editor.selected_cells(), Some("2".to_string()));
+        let summaries = editor
+            .results
+            .iter()
+            .map(|(_, result)| result.summary())
+            .collect::<Vec<String>>();
+        assert_eq!(summaries, vec!["2 rows", "2 rows", "3 rows affected"]);
+
+        editor.set_results(vec![
+            ("SELECT 1".to_string(), read("1")),
+            (
+                "DELETE FROM b".to_string(),
+                Err(anyhow::anyhow!("no such table: b")),
+            ),
+        ]);
+        assert_eq!(editor.selected_result, 1);
+        assert!(
+            matches!(&editor.results[1].1, super::QueryResult::Error(error) if error == "no such table: b")
+        );
+    }
+}
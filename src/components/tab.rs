@@ -1,13 +1,16 @@
 use super::{Component, DrawableComponent, EventState};
 use crate::components::command::{self, CommandInfo};
 use crate::config::KeyConfig;
-use crate::event::Key;
+use crate::event::{Key, Mouse, MouseKind};
+use crate::theme::Theme;
+use crate::ui::tab_at;
 use anyhow::Result;
+use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 use tui::{
     backend::Backend,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::Spans,
     widgets::{Block, Borders, Tabs},
     Frame,
@@ -20,6 +23,9 @@ pub enum Tab {
     Constraints,
     ForeignKeys,
     Indexes,
+    Sql,
+    Definition,
+    Triggers,
 }
 
 impl std::fmt::Display for Tab {
@@ -30,6 +36,7 @@ impl std::fmt::Display for Tab {
 
 pub struct TabComponent {
     pub selected_tab: Tab,
+    area: Rect,
     key_config: KeyConfig,
 }
 
@@ -37,6 +44,7 @@ impl TabComponent {
     pub fn new(key_config: KeyConfig) -> Self {
         Self {
             selected_tab: Tab::Records,
+            area: Rect::default(),
             key_config,
         }
     }
@@ -52,20 +60,24 @@ impl TabComponent {
             command::tab_constraints(&self.key_config).name,
             command::tab_foreign_keys(&self.key_config).name,
             command::tab_indexes(&self.key_config).name,
+            command::tab_sql(&self.key_config).name,
+            command::tab_definition(&self.key_config).name,
+            command::tab_triggers(&self.key_config).name,
         ]
     }
 }
 
 impl DrawableComponent for TabComponent {
     fn draw<B: Backend>(&mut self, f: &mut Frame<B>, area: Rect, _focused: bool) -> Result<()> {
+        self.area = area;
         let titles = self.names().iter().cloned().map(Spans::from).collect();
         let tabs = Tabs::new(titles)
             .block(Block::default().borders(Borders::ALL))
             .select(self.selected_tab as usize)
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Theme::current().hint())
             .highlight_style(
                 Style::default()
-                    .fg(Color::Reset)
+                    .fg(Theme::current().focused_fg)
                     .add_modifier(Modifier::UNDERLINED),
             );
         f.render_widget(tabs, area);
@@ -92,7 +104,30 @@ impl Component for TabComponent {
         } else if key == self.key_config.tab_indexes {
             self.selected_tab = Tab::Indexes;
             return Ok(EventState::Consumed);
+        } else if key == self.key_config.tab_sql {
+            self.selected_tab = Tab::Sql;
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.tab_definition {
+            self.selected_tab = Tab::Definition;
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.tab_triggers {
+            self.selected_tab = Tab::Triggers;
+            return Ok(EventState::Consumed);
         }
         Ok(EventState::NotConsumed)
     }
+
+    fn mouse_event(&mut self, mouse: Mouse) -> Result<EventState> {
+        if !mouse.is_in(self.area) {
+            return Ok(EventState::NotConsumed);
+        }
+        if mouse.kind == MouseKind::Click && mouse.row == self.area.y + 1 {
+            if let Some(tab) = tab_at(&self.names(), mouse.column.saturating_sub(self.area.x + 1))
+                .and_then(|index| Tab::iter().nth(index))
+            {
+                self.selected_tab = tab;
+            }
+        }
+        Ok(EventState::Consumed)
+    }
 }
@@ -1,98 +1,727 @@
 use super::{
-    utils::scroll_vertical::VerticalScroll, Component, DrawableComponent, EventState,
-    TableStatusComponent, TableValueComponent,
+    table_value::separate_thousands,
+    utils::{
+        binary::binary_placeholder, completion::common_prefix, invisible::reveal_invisible,
+        number_format::format_number, scroll_vertical::VerticalScroll,
+    },
+    Component, DrawableComponent, EventState, TableStatusComponent, TableValueComponent,
 };
 use crate::components::command::{self, CommandInfo};
-use crate::config::KeyConfig;
-use crate::event::Key;
+use crate::config::{KeyConfig, NumberFormat, TableConfig};
+use crate::database::{display_value, ColumnKind, TableStatus};
+use crate::event::{Key, Mouse, MouseKind, WHEEL_LINES};
+use crate::i18n::Msg;
+use crate::spill::SpillFile;
+use crate::theme::Theme;
+use crate::ui::column_separators::draw_column_separators;
+use crate::ui::screenshot::render_to_text;
+use crate::ui::scrollbar::draw_horizontal_scrollbar;
 use anyhow::Result;
 use database_tree::{Database, Table as DTable};
+use std::cell::RefCell;
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
 use std::convert::From;
+use std::hash::{BuildHasher, Hasher};
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::Style,
+    text::{Span, Spans},
     widgets::{Block, Borders, Cell, Row, Table, TableState},
     Frame,
 };
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// how many of the first rows are always measured for the widths of the columns
+const WIDTH_HEAD_ROWS: usize = 500;
+/// how many of the other rows are measured at random, so that the widths fit a large result without scanning all of it
+const WIDTH_SAMPLE_ROWS: usize = 500;
+
+/// which rows to take the values of
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RowRange {
+    /// the rows spanned by the selection area, or the selected row
+    Selected,
+    /// the rows drawn when the table was last drawn
+    Visible,
+    /// every loaded row
+    All,
+}
+
+/// the row counted from the first fetched row, the column and the value of a cell matching a search
+pub type SearchMatch = (usize, usize, String);
+
+/// a search over the rows in memory, highlighting the cells which contain the pattern
+#[derive(Default)]
+struct TableSearch {
+    pattern: String,
+    editing: bool,
+    /// the matched cells as row indices counted from the first fetched row and column indices, in order
+    matches: Vec<(usize, usize)>,
+}
 
 pub struct TableComponent {
     pub headers: Vec<String>,
-    pub rows: Vec<Vec<String>>,
+    /// the values of the fetched rows kept in memory, with `None` for NULL
+    pub rows: Vec<Vec<Option<String>>>,
     pub eod: bool,
     pub selected_row: TableState,
+    pub row_offset: usize,
     table: Option<(Database, DTable)>,
+    /// how many rows the table has and how much space it takes, which comes after its records
+    table_status: Option<TableStatus>,
     selected_column: usize,
     selection_area_corner: Option<(usize, usize)>,
     column_page_start: std::cell::Cell<usize>,
+    wrap_toggled_columns: HashSet<usize>,
+    frozen_rows: usize,
+    /// the number and the values of the row kept above the others to compare them with
+    pinned_row: Option<(usize, Vec<Option<String>>)>,
+    /// how many columns from the first one stay at the left while paging through the others
+    pinned_columns: usize,
+    /// the columns left out of the view, the copied cells and the export until they are shown again
+    hidden_columns: HashSet<usize>,
+    spill: Option<SpillFile>,
     scroll: VerticalScroll,
+    value: TableValueComponent,
+    search: Option<TableSearch>,
+    /// the kind of values of each column by its name, shown after the header
+    badges: HashMap<String, &'static str>,
+    /// the widest value of each column in the rows measured so far, measured on the first draw
+    data_widths: RefCell<Option<Vec<usize>>>,
+    area: Rect,
+    /// the first row drawn below the header, which tui keeps to itself in the state of the table
+    visible_row_start: usize,
+    /// the top, the height and the index of each row drawn, which clicks are matched against
+    row_hits: Vec<(u16, u16, usize)>,
+    /// the left, the width and the index of each column drawn
+    column_hits: Vec<(u16, u16, usize)>,
+    /// whether zero-width, control and trailing whitespace characters are drawn as placeholders
+    reveal_invisible: bool,
+    /// the count typed after `start_count` before a movement, which moves that many times, and is 0 until a digit
+    /// is typed
+    count: Option<usize>,
+    /// the row number or the column name being typed after `:`
+    go_to: Option<String>,
+    /// the row asked for with `:` which has not been fetched yet
+    wanted_row: Option<usize>,
     key_config: KeyConfig,
+    table_config: TableConfig,
 }
 
 impl TableComponent {
-    pub fn new(key_config: KeyConfig) -> Self {
+    pub fn new(key_config: KeyConfig, table_config: TableConfig) -> Self {
         Self {
             selected_row: TableState::default(),
+            row_offset: 0,
             headers: vec![],
             rows: vec![],
             table: None,
+            table_status: None,
             selected_column: 0,
             selection_area_corner: None,
             column_page_start: std::cell::Cell::new(0),
+            wrap_toggled_columns: HashSet::new(),
+            frozen_rows: table_config.frozen_rows,
+            pinned_row: None,
+            pinned_columns: table_config.pinned_columns,
+            hidden_columns: HashSet::new(),
+            spill: None,
             scroll: VerticalScroll::new(false, false),
             eod: false,
+            value: TableValueComponent::new(key_config.clone()),
+            search: None,
+            badges: HashMap::new(),
+            data_widths: RefCell::new(None),
+            area: Rect::default(),
+            visible_row_start: 0,
+            row_hits: vec![],
+            column_hits: vec![],
+            reveal_invisible: false,
+            count: None,
+            go_to: None,
+            wanted_row: None,
             key_config,
+            table_config,
         }
     }
 
     fn title(&self) -> String {
-        self.table.as_ref().map_or(" - ".to_string(), |table| {
+        let title = self.table.as_ref().map_or(" - ".to_string(), |table| {
             format!("{}.{}", table.0.name, table.1.name)
-        })
+        });
+        let title = match self.search.as_ref() {
+            Some(search) => format!(
+                "{}  /{} ({} {})",
+                title,
+                search.pattern,
+                search.matches.len(),
+                Msg::Matches.text()
+            ),
+            None => title,
+        };
+        match (self.go_to.as_ref(), self.count) {
+            (Some(target), _) => format!("{}  :{}", title, target),
+            (None, Some(0)) => format!("{}  {}", title, self.key_config.start_count),
+            (None, Some(count)) => format!("{}  {}{}", title, self.key_config.start_count, count),
+            (None, None) => title,
+        }
+    }
+
+    /// completes the column name being typed after `:` with the longest prefix shared by the matching headers
+    fn complete_go_to(&mut self) {
+        let target = match self.go_to.as_mut() {
+            Some(target) => target,
+            None => return,
+        };
+        let word = target.to_lowercase();
+        let hidden_columns = &self.hidden_columns;
+        let candidates = self
+            .headers
+            .iter()
+            .enumerate()
+            .filter(|(column, _)| !hidden_columns.contains(column))
+            .map(|(_, header)| header)
+            .filter(|header| header.to_lowercase().starts_with(word.as_str()))
+            .collect::<Vec<&String>>();
+        if let Some(completion) = common_prefix(&candidates) {
+            if completion.len() >= target.chars().count() {
+                *target = completion.into_iter().collect();
+            }
+        }
+    }
+
+    /// selects the row of the number typed after `:`, or the first column whose name is or starts with what was typed
+    fn go_to_target(&mut self, target: &str) -> Result<()> {
+        let target = target.trim();
+        if let Ok(number) = target.parse::<usize>() {
+            return self.go_to_row(number.saturating_sub(1));
+        }
+        let target = target.to_lowercase();
+        let shown = self.shown_columns();
+        let found = shown
+            .iter()
+            .find(|column| self.headers[**column].to_lowercase() == target)
+            .or_else(|| {
+                shown.iter().find(|column| {
+                    self.headers[**column]
+                        .to_lowercase()
+                        .starts_with(target.as_str())
+                })
+            })
+            .copied();
+        if let Some(column) = found {
+            self.reset_selection();
+            self.selected_column = column;
+        }
+        Ok(())
+    }
+
+    /// selects the row at `index` counted from the first fetched row, reading it back from the temporary file when
+    /// needed, and remembers it as wanted when it has not been fetched yet
+    pub fn go_to_row(&mut self, index: usize) -> Result<()> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+        self.reset_selection();
+        while index < self.row_offset && self.load_above()? {}
+        while index >= self.row_offset + self.rows.len() && self.load_below()? {}
+        let last = self.row_offset + self.rows.len() - 1;
+        self.wanted_row = Some(index).filter(|index| *index > last && !self.eod);
+        self.selected_row
+            .select(Some(index.min(last).saturating_sub(self.row_offset)));
+        Ok(())
+    }
+
+    /// returns the row asked for with `:` which has to be fetched before it can be selected
+    pub fn take_wanted_row(&mut self) -> Option<usize> {
+        self.wanted_row.take()
+    }
+
+    fn go_to_event(&mut self, key: Key) -> Result<EventState> {
+        let target = match self.go_to.as_mut() {
+            Some(target) => target,
+            None => return Ok(EventState::NotConsumed),
+        };
+        if key == self.key_config.exit_popup {
+            self.go_to = None;
+        } else if key == self.key_config.enter {
+            if let Some(target) = self.go_to.take() {
+                self.go_to_target(target.as_str())?;
+            }
+        } else if key == Key::Tab {
+            self.complete_go_to();
+        } else {
+            match key {
+                Key::Char(c) => target.push(c),
+                Key::Backspace | Key::Delete => {
+                    target.pop();
+                }
+                _ => (),
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+
+    /// starts the count of the next movement, or adds a digit to it once it is started
+    fn count_event(&mut self, key: Key) -> EventState {
+        if key == self.key_config.start_count {
+            self.count = Some(0);
+            return EventState::Consumed;
+        }
+        let digit = match key {
+            Key::Char(c) if self.count.is_some() => match c.to_digit(10) {
+                Some(digit) => digit as usize,
+                None => return EventState::NotConsumed,
+            },
+            _ => return EventState::NotConsumed,
+        };
+        self.count = Some(
+            self.count
+                .unwrap_or(0)
+                .saturating_mul(10)
+                .saturating_add(digit),
+        );
+        EventState::Consumed
+    }
+
+    /// returns whether the pattern of a search, or the row or the column to go to, is being typed
+    pub fn searching(&self) -> bool {
+        matches!(&self.search, Some(search) if search.editing) || self.go_to.is_some()
+    }
+
+    /// finds the cells containing the pattern, ignoring case, among the rows in memory
+    fn update_matches(&mut self) {
+        let search = match self.search.as_mut() {
+            Some(search) => search,
+            None => return,
+        };
+        search.matches = Vec::new();
+        let pattern = search.pattern.to_lowercase();
+        if pattern.is_empty() {
+            return;
+        }
+        for (row_index, row) in self.rows.iter().enumerate() {
+            for (column_index, cell) in row.iter().enumerate() {
+                if display_value(cell)
+                    .to_lowercase()
+                    .contains(pattern.as_str())
+                {
+                    search
+                        .matches
+                        .push((self.row_offset + row_index, column_index));
+                }
+            }
+        }
+    }
+
+    /// returns the pattern of the search with the row, counted from the first fetched row, the column and the value of
+    /// each matched cell, once the pattern was entered
+    pub fn search_matches(&self) -> Option<(String, Vec<SearchMatch>)> {
+        let search = self.search.as_ref().filter(|search| !search.editing)?;
+        let matches = search
+            .matches
+            .iter()
+            .filter_map(|(row, column)| {
+                let value = self
+                    .rows
+                    .get(row.checked_sub(self.row_offset)?)?
+                    .get(*column)?;
+                Some((*row, *column, display_value(value).to_string()))
+            })
+            .collect();
+        Some((search.pattern.clone(), matches))
+    }
+
+    /// returns the selected cell with its row counted from the first fetched row
+    pub fn selected_cell(&self) -> (usize, usize) {
+        (
+            self.row_offset + self.selected_row.selected().unwrap_or(0),
+            self.selected_column,
+        )
+    }
+
+    /// selects the cell at `row` counted from the first fetched row and `column`, such as a match chosen from a list
+    pub fn select_cell(&mut self, row: usize, column: usize) -> Result<()> {
+        self.go_to_row(row)?;
+        self.selected_column = column.min(self.headers.len().saturating_sub(1));
+        Ok(())
+    }
+
+    fn is_matched_cell(&self, row_index: usize, column_index: usize) -> bool {
+        if column_index < self.number_column_count() {
+            return false;
+        }
+        matches!(&self.search, Some(search) if search
+            .matches
+            .binary_search(&(
+                self.row_offset + row_index,
+                self.page_column(column_index - self.number_column_count()),
+            ))
+            .is_ok())
+    }
+
+    /// selects the next or the previous matched cell from the selected one, wrapping around the rows in memory
+    fn jump_to_match(&mut self, forward: bool) {
+        let current = (
+            self.row_offset + self.selected_row.selected().unwrap_or(0),
+            self.selected_column,
+        );
+        let matches = match self.search.as_ref() {
+            Some(search) if !search.matches.is_empty() => &search.matches,
+            _ => return,
+        };
+        let found = if forward {
+            matches
+                .iter()
+                .find(|cell| **cell > current)
+                .or_else(|| matches.first())
+        } else {
+            matches
+                .iter()
+                .rev()
+                .find(|cell| **cell < current)
+                .or_else(|| matches.last())
+        };
+        if let Some((row, column)) = found.copied() {
+            self.reset_selection();
+            self.selected_row.select(Some(row - self.row_offset));
+            self.selected_column = column;
+        }
+    }
+
+    fn search_event(&mut self, key: Key) -> EventState {
+        let search = match self.search.as_mut() {
+            Some(search) if search.editing => search,
+            _ => return EventState::NotConsumed,
+        };
+        if key == self.key_config.exit_popup {
+            self.search = None;
+            return EventState::Consumed;
+        }
+        if key == self.key_config.enter {
+            if search.pattern.is_empty() {
+                self.search = None;
+            } else {
+                search.editing = false;
+                // stays on the selected cell when it matches, and moves to the next match otherwise
+                let current = (
+                    self.row_offset + self.selected_row.selected().unwrap_or(0),
+                    self.selected_column,
+                );
+                if search.matches.binary_search(&current).is_err() {
+                    self.jump_to_match(true);
+                }
+            }
+            return EventState::Consumed;
+        }
+        match key {
+            Key::Char(c) => search.pattern.push(c),
+            Key::Backspace | Key::Delete => {
+                search.pattern.pop();
+            }
+            _ => return EventState::Consumed,
+        }
+        self.update_matches();
+        EventState::Consumed
     }
 
     pub fn update(
         &mut self,
-        rows: Vec<Vec<String>>,
+        rows: Vec<Vec<Option<String>>>,
         headers: Vec<String>,
         database: Database,
         table: DTable,
     ) {
+        // the size of the same table is kept until it is fetched again
+        let table_status = self
+            .table_status
+            .take()
+            .filter(|_| self.is_table(&database, &table));
+        self.update_rows(rows, headers);
+        self.table = Some((database, table));
+        self.table_status = table_status;
+    }
+
+    /// shows how many rows `table` has and how much space it takes, unless another table was opened since
+    pub fn set_table_status(&mut self, database: &Database, table: &DTable, status: TableStatus) {
+        if self.is_table(database, table) {
+            self.table_status = Some(status);
+        }
+    }
+
+    fn is_table(&self, database: &Database, table: &DTable) -> bool {
+        matches!(&self.table, Some((d, t)) if d == database && t == table)
+    }
+
+    /// updates rows which do not belong to a specific table, such as query results
+    pub fn update_rows(&mut self, rows: Vec<Vec<Option<String>>>, headers: Vec<String>) {
         if !rows.is_empty() {
             self.selected_row.select(None);
             self.selected_row.select(Some(0))
         }
         self.headers = headers;
         self.rows = rows;
+        self.row_offset = 0;
         self.selected_column = 0;
         self.selection_area_corner = None;
         self.column_page_start = std::cell::Cell::new(0);
+        self.wrap_toggled_columns = HashSet::new();
+        self.frozen_rows = self.table_config.frozen_rows;
+        self.pinned_row = None;
+        self.table_status = None;
+        self.pinned_columns = self.table_config.pinned_columns;
+        self.hidden_columns = HashSet::new();
+        self.spill = None;
         self.scroll = VerticalScroll::new(false, false);
         self.eod = false;
-        self.table = Some((database, table));
+        self.search = None;
+        self.go_to = None;
+        self.wanted_row = None;
+        self.table = None;
+        self.data_widths = RefCell::new(None);
+    }
+
+    /// replaces the rows with the first ones fetched again, keeping the selected cell, the scroll and the settings of
+    /// the columns when the headers are the same
+    pub fn refresh_rows(&mut self, rows: Vec<Vec<Option<String>>>, headers: Vec<String>) {
+        let selected_row = self
+            .selected_row
+            .selected()
+            .map_or(0, |index| self.row_offset + index);
+        let selected_header = self.headers.get(self.selected_column).cloned();
+        if headers != self.headers {
+            self.column_page_start = std::cell::Cell::new(0);
+            self.wrap_toggled_columns = HashSet::new();
+            self.hidden_columns = HashSet::new();
+            self.data_widths = RefCell::new(None);
+        }
+        self.selected_column = selected_header
+            .and_then(|header| headers.iter().position(|h| *h == header))
+            .unwrap_or(0);
+        self.headers = headers;
+        self.rows = rows;
+        self.row_offset = 0;
+        self.spill = None;
+        self.eod = false;
+        self.selection_area_corner = None;
+        self.selected_row.select(if self.rows.is_empty() {
+            None
+        } else {
+            Some(selected_row.min(self.rows.len() - 1))
+        });
+        self.visible_row_start = self
+            .visible_row_start
+            .min(self.selected_row.selected().unwrap_or(0));
+        self.update_matches();
+    }
+
+    /// sets the kinds of values shown after the headers, which are kept while the rows are fetched again
+    pub fn set_badges(&mut self, badges: HashMap<String, &'static str>) {
+        self.badges = badges;
+    }
+
+    /// returns the kind of values of the column named `header`, whether or not the badges are shown
+    pub fn column_badge(&self, header: &str) -> Option<&'static str> {
+        self.badges.get(header).copied()
+    }
+
+    /// returns the kind of values of the column named `header`, which tells how they are written as literals
+    pub fn column_kind(&self, header: &str) -> ColumnKind {
+        ColumnKind::from_badge(self.column_badge(header))
+    }
+
+    fn badge(&self, header: &str) -> Option<&'static str> {
+        if !self.table_config.show_type_badges {
+            return None;
+        }
+        self.badges.get(header).copied()
+    }
+
+    /// returns the format of the numbers of each column, or `None` for the columns shown as they are and those known not to hold numbers
+    fn number_formats(&self) -> Vec<Option<NumberFormat>> {
+        self.headers
+            .iter()
+            .map(|header| {
+                if matches!(self.badges.get(header), Some(badge) if !matches!(*badge, "int" | "num"))
+                {
+                    return None;
+                }
+                Some(self.table_config.number_format.column(header)).filter(|f| !f.is_plain())
+            })
+            .collect()
+    }
+
+    fn header_width(&self, header: &str) -> usize {
+        header.width() + self.badge(header).map_or(0, |badge| badge.width() + 1)
     }
 
     pub fn reset(&mut self) {
         self.selected_row.select(None);
         self.headers = Vec::new();
         self.rows = Vec::new();
+        self.row_offset = 0;
         self.selected_column = 0;
         self.selection_area_corner = None;
         self.column_page_start = std::cell::Cell::new(0);
+        self.wrap_toggled_columns = HashSet::new();
+        self.frozen_rows = self.table_config.frozen_rows;
+        self.pinned_row = None;
+        self.table_status = None;
+        self.pinned_columns = self.table_config.pinned_columns;
+        self.hidden_columns = HashSet::new();
+        self.spill = None;
         self.scroll = VerticalScroll::new(false, false);
         self.eod = false;
+        self.search = None;
+        self.go_to = None;
+        self.wanted_row = None;
         self.table = None;
+        self.badges = HashMap::new();
+        self.data_widths = RefCell::new(None);
     }
 
     fn reset_selection(&mut self) {
         self.selection_area_corner = None;
     }
 
+    /// returns whether the selection is being extended beyond the selected cell
+    pub fn selecting(&self) -> bool {
+        self.selection_area_corner.is_some()
+    }
+
     pub fn end(&mut self) {
         self.eod = true;
     }
 
+    /// appends fetched rows, moving the oldest ones to a temporary file when more rows than
+    /// `max_rows_in_memory` are held
+    pub fn append_rows(&mut self, rows: Vec<Vec<Option<String>>>) -> Result<()> {
+        let number_formats = self.number_formats();
+        if let Some(widths) = self.data_widths.get_mut() {
+            measure_widths(&rows, sample_indices(rows.len()), &number_formats, widths);
+        }
+        self.rows.extend(rows);
+        self.spill_above()?;
+        self.update_matches();
+        Ok(())
+    }
+
+    /// returns whether rows after the ones in memory have been moved to the temporary file
+    pub fn has_rows_below(&self) -> bool {
+        matches!(&self.spill, Some(spill) if spill.has_below())
+    }
+
+    fn excess_rows(&self) -> usize {
+        match self.table_config.max_rows_in_memory {
+            0 => 0,
+            max => self.rows.len().saturating_sub(max),
+        }
+    }
+
+    /// returns the first and the last row index of the selection
+    fn selected_row_range(&self) -> Option<(usize, usize)> {
+        let selected_row_index = self.selected_row.selected()?;
+        Some(
+            self.selection_area_corner
+                .map_or((selected_row_index, selected_row_index), |(_, y)| {
+                    (y.min(selected_row_index), y.max(selected_row_index))
+                }),
+        )
+    }
+
+    fn shift_selected_rows(&mut self, shift: impl Fn(usize) -> usize) {
+        self.selected_row
+            .select(self.selected_row.selected().map(&shift));
+        self.selection_area_corner = self.selection_area_corner.map(|(x, y)| (x, shift(y)));
+    }
+
+    fn spill_file(&mut self) -> Result<&mut SpillFile> {
+        if self.spill.is_none() {
+            self.spill = Some(SpillFile::new()?);
+        }
+        Ok(self.spill.as_mut().unwrap())
+    }
+
+    fn spill_above(&mut self) -> Result<()> {
+        let count = self
+            .excess_rows()
+            .min(self.selected_row_range().map_or(0, |(top, _)| top));
+        if count == 0 {
+            return Ok(());
+        }
+        let rows = self
+            .rows
+            .drain(..count)
+            .collect::<Vec<Vec<Option<String>>>>();
+        self.spill_file()?.push_above(&rows)?;
+        self.row_offset += count;
+        self.shift_selected_rows(|index| index - count);
+        Ok(())
+    }
+
+    fn spill_below(&mut self) -> Result<()> {
+        let count = self.excess_rows().min(
+            self.selected_row_range()
+                .map_or(0, |(_, bottom)| self.rows.len() - bottom - 1),
+        );
+        if count == 0 {
+            return Ok(());
+        }
+        let rows = self.rows.split_off(self.rows.len() - count);
+        self.spill_file()?.push_below(&rows)
+    }
+
+    fn load_above(&mut self) -> Result<bool> {
+        let rows = match self.spill.as_mut() {
+            Some(spill) => spill.pop_above()?,
+            None => None,
+        };
+        match rows {
+            Some(rows) => {
+                let count = rows.len();
+                self.rows.splice(..0, rows);
+                self.row_offset -= count;
+                self.shift_selected_rows(|index| index + count);
+                self.spill_below()?;
+                self.update_matches();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn load_below(&mut self) -> Result<bool> {
+        let rows = match self.spill.as_mut() {
+            Some(spill) => spill.pop_below()?,
+            None => None,
+        };
+        match rows {
+            Some(rows) => {
+                self.rows.extend(rows);
+                self.spill_above()?;
+                self.update_matches();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// reads rows back from the temporary file until `lines` rows above the selected one are in memory
+    fn load_rows_above(&mut self, lines: usize) -> Result<()> {
+        while matches!(self.selected_row.selected(), Some(i) if i < lines) && self.load_above()? {}
+        Ok(())
+    }
+
+    /// reads rows back from the temporary file until `lines` rows below the selected one are in memory
+    fn load_rows_below(&mut self, lines: usize) -> Result<()> {
+        while matches!(self.selected_row.selected(), Some(i) if i + lines >= self.rows.len())
+            && self.load_below()?
+        {}
+        Ok(())
+    }
+
     fn next_row(&mut self, lines: usize) {
         let i = match self.selected_row.selected() {
             Some(i) => {
@@ -144,10 +773,13 @@ impl TableComponent {
             return;
         }
         self.reset_selection();
-        if self.selected_column >= self.headers.len().saturating_sub(1) {
-            return;
+        if let Some(column) = self
+            .shown_columns()
+            .into_iter()
+            .find(|column| *column > self.selected_column)
+        {
+            self.selected_column = column;
         }
-        self.selected_column += 1;
     }
 
     fn previous_column(&mut self) {
@@ -155,10 +787,189 @@ impl TableComponent {
             return;
         }
         self.reset_selection();
-        if self.selected_column == 0 {
+        if let Some(column) = self
+            .shown_columns()
+            .into_iter()
+            .rev()
+            .find(|column| *column < self.selected_column)
+        {
+            self.selected_column = column;
+        }
+    }
+
+    fn toggle_wrap(&mut self) {
+        if !self.wrap_toggled_columns.remove(&self.selected_column) {
+            self.wrap_toggled_columns.insert(self.selected_column);
+        }
+    }
+
+    /// freezes the rows up to the selected one, or unfreezes them if they are already frozen
+    fn toggle_frozen_rows(&mut self) {
+        if let Some(selected_row_index) = self.selected_row.selected() {
+            self.frozen_rows = if self.frozen_rows == selected_row_index + 1 {
+                0
+            } else {
+                selected_row_index + 1
+            };
+        }
+    }
+
+    /// keeps the selected row above the others while they scroll, or lets it go if it is the one kept
+    fn toggle_pinned_row(&mut self) {
+        if let Some(selected_row_index) = self.selected_row.selected() {
+            let number = self.row_offset + selected_row_index;
+            self.pinned_row = match &self.pinned_row {
+                Some((pinned, _)) if *pinned == number => None,
+                _ => self
+                    .rows
+                    .get(selected_row_index)
+                    .map(|row| (number, row.clone())),
+            };
+        }
+    }
+
+    /// pins the columns up to the selected one to the left of every page, or unpins them if they are already pinned
+    fn toggle_pinned_columns(&mut self) {
+        self.pinned_columns = if self.pinned_columns == self.selected_column + 1 {
+            0
+        } else {
+            self.selected_column + 1
+        };
+    }
+
+    /// returns the number of shown columns kept at the left, leaving at least one column to page through
+    fn pinned_column_count(&self) -> usize {
+        self.shown_position(self.pinned_columns.min(self.headers.len()))
+            .min(self.shown_column_count().saturating_sub(1))
+    }
+
+    /// returns the column drawn at `index` after the row number, the pinned columns coming before the page
+    fn page_column(&self, index: usize) -> usize {
+        let pinned = self.pinned_column_count();
+        if index < pinned {
+            self.shown_column(index)
+        } else {
+            self.shown_column(self.column_page_start.get().max(pinned) + index - pinned)
+        }
+    }
+
+    /// hides the columns at `hidden` and shows the others, unless that would hide every column
+    pub fn set_hidden_columns(&mut self, hidden: HashSet<usize>) {
+        if (0..self.headers.len()).all(|column| hidden.contains(&column)) {
             return;
         }
-        self.selected_column -= 1;
+        self.hidden_columns = hidden;
+        self.reset_selection();
+        self.column_page_start.set(0);
+        if self.hidden_columns.contains(&self.selected_column) {
+            let shown = self.shown_columns();
+            self.selected_column = shown
+                .iter()
+                .find(|column| **column > self.selected_column)
+                .or_else(|| shown.last())
+                .copied()
+                .unwrap_or(0);
+        }
+    }
+
+    pub fn hidden_columns(&self) -> &HashSet<usize> {
+        &self.hidden_columns
+    }
+
+    /// returns the headers of the hidden columns
+    pub fn hidden_headers(&self) -> Vec<String> {
+        self.headers
+            .iter()
+            .enumerate()
+            .filter(|(column, _)| self.hidden_columns.contains(column))
+            .map(|(_, header)| header.clone())
+            .collect()
+    }
+
+    /// returns the columns which are not hidden, in order
+    fn shown_columns(&self) -> Vec<usize> {
+        (0..self.headers.len())
+            .filter(|column| !self.hidden_columns.contains(column))
+            .collect()
+    }
+
+    fn shown_column_count(&self) -> usize {
+        self.shown_position(self.headers.len())
+    }
+
+    /// returns the column which is the `index`th one shown
+    fn shown_column(&self, index: usize) -> usize {
+        if self.hidden_columns.is_empty() {
+            return index;
+        }
+        (0..self.headers.len())
+            .filter(|column| !self.hidden_columns.contains(column))
+            .nth(index)
+            .unwrap_or(index)
+    }
+
+    /// returns how many shown columns come before `column`
+    fn shown_position(&self, column: usize) -> usize {
+        (0..column)
+            .filter(|column| !self.hidden_columns.contains(column))
+            .count()
+    }
+
+    /// returns the number of rows kept at the top, leaving at least one row to scroll
+    fn frozen_row_count(&self) -> usize {
+        // the first rows are not in memory once they have been spilled to the temporary file
+        if self.row_offset > 0 {
+            return 0;
+        }
+        self.frozen_rows.min(self.rows.len().saturating_sub(1))
+    }
+
+    fn is_wrapped_column(&self, column_index: usize) -> bool {
+        self.table_config.wrap_text ^ self.wrap_toggled_columns.contains(&column_index)
+    }
+
+    pub fn set_reveal_invisible(&mut self, reveal_invisible: bool) {
+        self.reveal_invisible = reveal_invisible;
+        self.value.set_reveal_invisible(reveal_invisible);
+    }
+
+    /// wraps the cells of the wrapped columns and marks where the cells of the others are cut short
+    fn wrap_row(&self, row: &[String], constraints: &[Constraint]) -> Vec<String> {
+        row.iter()
+            .enumerate()
+            .map(|(index, cell)| {
+                if index < self.number_column_count() {
+                    return cell.to_string();
+                }
+                let cell = if self.reveal_invisible {
+                    reveal_invisible(cell)
+                } else {
+                    cell.to_string()
+                };
+                let wrapped =
+                    self.is_wrapped_column(self.page_column(index - self.number_column_count()));
+                match constraints.get(index) {
+                    Some(Constraint::Length(width)) | Some(Constraint::Min(width)) if wrapped => {
+                        wrap_text(&cell, *width as usize)
+                    }
+                    Some(Constraint::Length(width)) => truncate_text(&cell, *width as usize),
+                    _ => cell,
+                }
+            })
+            .collect()
+    }
+
+    /// returns whether the table shows only the start of the selected cell
+    fn is_selected_cell_truncated(&self) -> bool {
+        if self.selection_area_corner.is_some() || self.is_wrapped_column(self.selected_column) {
+            return false;
+        }
+        let width = self.column_width(self.selected_column);
+        self.selected_column_value().map_or(false, |(_, value)| {
+            display_value(value)
+                .lines()
+                .any(|line| line.width() > width)
+        })
     }
 
     fn expand_selected_area_x(&mut self, positive: bool) {
@@ -169,14 +980,15 @@ impl TableComponent {
             ));
         }
         if let Some((x, y)) = self.selection_area_corner {
-            self.selection_area_corner = Some((
-                if positive {
-                    (x + 1).min(self.headers.len().saturating_sub(1))
-                } else {
-                    x.saturating_sub(1)
-                },
-                y,
-            ));
+            let shown = self.shown_columns();
+            let x = if positive {
+                shown.iter().find(|column| **column > x)
+            } else {
+                shown.iter().rev().find(|column| **column < x)
+            }
+            .copied()
+            .unwrap_or(x);
+            self.selection_area_corner = Some((x, y));
         }
     }
 
@@ -200,13 +1012,26 @@ impl TableComponent {
     }
 
     pub fn selected_cells(&self) -> Option<String> {
+        self.join_selected_cells(",")
+    }
+
+    /// returns the selected cells as tab-separated values
+    pub fn selected_tsv(&self) -> Option<String> {
+        self.join_selected_cells("\t")
+    }
+
+    fn join_selected_cells(&self, separator: &str) -> Option<String> {
         if let Some((x, y)) = self.selection_area_corner {
             let selected_row_index = self.selected_row.selected()?;
             return Some(
                 self.rows[y.min(selected_row_index)..y.max(selected_row_index) + 1]
                     .iter()
                     .map(|row| {
-                        row[x.min(self.selected_column)..x.max(self.selected_column) + 1].join(",")
+                        (x.min(self.selected_column)..x.max(self.selected_column) + 1)
+                            .filter(|column| !self.hidden_columns.contains(column))
+                            .map(|column| display_value(&row[column]))
+                            .collect::<Vec<&str>>()
+                            .join(separator)
                     })
                     .collect::<Vec<String>>()
                     .join("\n"),
@@ -215,7 +1040,134 @@ impl TableComponent {
         self.rows
             .get(self.selected_row.selected()?)?
             .get(self.selected_column)
-            .map(|cell| cell.to_string())
+            .map(|cell| display_value(cell).to_string())
+    }
+
+    /// returns the header of the selected column
+    pub fn selected_header(&self) -> Option<&String> {
+        self.headers.get(self.selected_column)
+    }
+
+    /// returns the header and the value of the selected cell, which is `None` for NULL
+    pub fn selected_column_value(&self) -> Option<(&String, &Option<String>)> {
+        Some((
+            self.headers.get(self.selected_column)?,
+            self.rows
+                .get(self.selected_row.selected()?)?
+                .get(self.selected_column)?,
+        ))
+    }
+
+    /// returns the table, the column and the row position of the selected cell, such as `orders.customer_id — row 1,204 / 53,001`
+    fn selected_location(&self) -> String {
+        let column = match self.headers.get(self.selected_column) {
+            Some(column) => column,
+            None => return String::new(),
+        };
+        let column = match self.table.as_ref() {
+            Some((_, table)) => format!("{}.{}", table.name, column),
+            None => column.to_string(),
+        };
+        match self.selected_row.selected() {
+            Some(index) => format!(
+                "{} — {} {} / {}",
+                column,
+                Msg::Row.text(),
+                separate_thousands(self.row_offset + index + 1),
+                separate_thousands(self.total_row_count())
+            ),
+            None => column,
+        }
+    }
+
+    /// returns the number of the rows fetched so far, including the ones moved to the temporary file
+    fn total_row_count(&self) -> usize {
+        self.row_offset
+            + self.rows.len()
+            + self
+                .spill
+                .as_ref()
+                .map_or(0, |spill| spill.below_row_count())
+    }
+
+    /// returns the rows spanned by the selection area, or the selected row
+    pub fn selected_rows(&self) -> &[Vec<Option<String>>] {
+        let selected = match self.selected_row.selected() {
+            Some(selected) if selected < self.rows.len() => selected,
+            _ => return &[],
+        };
+        match self.selection_area_corner {
+            Some((_, y)) => &self.rows[y.min(selected)..y.max(selected) + 1],
+            None => &self.rows[selected..selected + 1],
+        }
+    }
+
+    /// returns the shown columns spanned by the selection area, or the selected column
+    pub fn selected_columns(&self) -> Vec<usize> {
+        let x = self
+            .selection_area_corner
+            .map_or(self.selected_column, |(x, _)| x);
+        (x.min(self.selected_column)..x.max(self.selected_column) + 1)
+            .filter(|column| !self.hidden_columns.contains(column))
+            .collect()
+    }
+
+    /// returns the shown headers, the kind of values each of them holds as the types of the columns tell, and the
+    /// values of the shown columns of the rows in `range`, with `None` for NULL
+    pub fn shown_values(
+        &self,
+        range: RowRange,
+    ) -> (Vec<String>, Vec<ColumnKind>, Vec<Vec<Option<String>>>) {
+        let columns = (0..self.headers.len())
+            .filter(|column| !self.hidden_columns.contains(column))
+            .collect::<Vec<usize>>();
+        let rows: Vec<&Vec<Option<String>>> = match range {
+            RowRange::Selected => self.selected_rows().iter().collect(),
+            RowRange::Visible => {
+                let mut indexes = self
+                    .row_hits
+                    .iter()
+                    .map(|(_, _, index)| *index)
+                    .collect::<Vec<usize>>();
+                indexes.sort_unstable();
+                indexes.dedup();
+                indexes
+                    .into_iter()
+                    .filter_map(|index| self.rows.get(index))
+                    .collect()
+            }
+            RowRange::All => self.rows.iter().collect(),
+        };
+        (
+            columns
+                .iter()
+                .map(|column| self.headers[*column].clone())
+                .collect(),
+            columns
+                .iter()
+                .map(|column| self.column_kind(self.headers[*column].as_str()))
+                .collect(),
+            rows.into_iter()
+                .map(|row| {
+                    columns
+                        .iter()
+                        .filter_map(|column| row.get(*column).cloned())
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    pub fn selected_record(&self) -> Option<Vec<Option<String>>> {
+        self.rows.get(self.selected_row.selected()?).cloned()
+    }
+
+    fn number_column_count(&self) -> usize {
+        if self.table_config.show_row_number {
+            1
+        } else {
+            0
+        }
     }
 
     fn selected_column_index(&self) -> usize {
@@ -232,14 +1184,15 @@ impl TableComponent {
         selected_column_index: usize,
     ) -> bool {
         if let Some((x, y)) = self.selection_area_corner {
-            let x_in_page = x
-                .saturating_add(1)
-                .saturating_sub(self.column_page_start.get());
+            let column = match column_index.checked_sub(self.number_column_count()) {
+                Some(index) => self.page_column(index),
+                None => return false,
+            };
             return matches!(
                 self.selected_row.selected(),
                 Some(selected_row_index)
-                if (x_in_page.min(selected_column_index).max(1)..x_in_page.max(selected_column_index) + 1)
-                    .contains(&column_index)
+                if (x.min(self.selected_column)..x.max(self.selected_column) + 1)
+                    .contains(&column)
                     && (y.min(selected_row_index)..y.max(selected_row_index) + 1)
                         .contains(&row_index)
             );
@@ -251,32 +1204,202 @@ impl TableComponent {
     }
 
     fn is_number_column(&self, row_index: usize, column_index: usize) -> bool {
-        matches!(
-            self.selected_row.selected(),
-            Some(selected_row_index) if row_index == selected_row_index && 0 == column_index
-        )
+        self.table_config.show_row_number
+            && matches!(
+                self.selected_row.selected(),
+                Some(selected_row_index) if row_index == selected_row_index && 0 == column_index
+            )
     }
 
-    fn headers(&self, left: usize, right: usize) -> Vec<String> {
-        let mut headers = self.headers.clone()[left..right].to_vec();
-        headers.insert(0, "".to_string());
+    fn headers(&self, columns: &[usize]) -> Vec<String> {
+        let mut headers = columns
+            .iter()
+            .map(|column| self.headers[*column].clone())
+            .collect::<Vec<String>>();
+        if self.table_config.show_row_number {
+            headers.insert(0, "".to_string());
+        }
         headers
     }
 
-    fn rows(&self, left: usize, right: usize) -> Vec<Vec<String>> {
-        let rows = self
+    fn rows(&self, columns: &[usize]) -> Vec<Vec<String>> {
+        let number_formats = self.number_formats();
+        let mut new_rows: Vec<Vec<String>> = self
             .rows
             .iter()
-            .map(|row| row.to_vec())
-            .collect::<Vec<Vec<String>>>();
-        let mut new_rows: Vec<Vec<String>> =
-            rows.iter().map(|row| row[left..right].to_vec()).collect();
-        for (index, row) in new_rows.iter_mut().enumerate() {
-            row.insert(0, (index + 1).to_string())
+            .map(|row| format_cells(row, columns, &number_formats))
+            .collect();
+        if self.table_config.show_row_number {
+            for (index, row) in new_rows.iter_mut().enumerate() {
+                row.insert(0, (self.row_offset + index + 1).to_string())
+            }
         }
         new_rows
     }
 
+    /// returns the cells of the pinned row in the `count` columns drawn after the row number
+    fn pinned_row_cells(&self, count: usize) -> Option<Vec<String>> {
+        let (number, row) = self.pinned_row.as_ref()?;
+        let columns = (0..count)
+            .map(|index| self.page_column(index))
+            .collect::<Vec<usize>>();
+        let mut cells = format_cells(row, &columns, &self.number_formats());
+        if self.table_config.show_row_number {
+            cells.insert(0, (number + 1).to_string());
+        }
+        Some(cells)
+    }
+
+    /// returns the height which fits every loaded row when the table is drawn `width` cells wide
+    fn full_height(&self, width: u16) -> u16 {
+        // the table is drawn inside the borders and has no margin of its own
+        let (_, headers, rows, constraints) = self.calculate_cell_widths(width.saturating_sub(2));
+        let pinned_row =
+            self.pinned_row_cells(headers.len().saturating_sub(self.number_column_count()));
+        let rows_height = rows
+            .iter()
+            .chain(pinned_row.iter())
+            .map(|row| {
+                self.wrap_row(row, &constraints)
+                    .iter()
+                    .map(|cell| cell.matches('\n').count())
+                    .max()
+                    .unwrap_or(0)
+                    + 2
+            })
+            .sum::<usize>();
+        // the borders, the value of the selected cell, the header and the status
+        (rows_height + 8).min(u16::MAX as usize) as u16
+    }
+
+    /// draws the table off screen as it was last drawn, or tall enough for every loaded row, and returns it as text
+    pub fn export(&mut self, all_rows: bool, ansi: bool) -> Result<String> {
+        let area = self.area;
+        let height = if all_rows {
+            self.full_height(area.width)
+        } else {
+            area.height
+        };
+        let state = self.selected_row.clone();
+        let visible_row_start = self.visible_row_start;
+        if all_rows {
+            // starts from the first row rather than from the one scrolled to
+            self.selected_row = TableState::default();
+            self.selected_row.select(state.selected());
+            self.visible_row_start = 0;
+        }
+        let text = render_to_text(area.width, height, ansi, |f| {
+            let size = f.size();
+            self.draw(f, size, true)
+        });
+        self.selected_row = state;
+        self.visible_row_start = visible_row_start;
+        self.area = area;
+        text
+    }
+
+    /// remembers where the rows are drawn, following how tui scrolls the table to the selected row
+    fn update_row_hits(
+        &mut self,
+        frozen_area: Rect,
+        area: Rect,
+        pinned_height: u16,
+        frozen_heights: &[u16],
+        heights: &[u16],
+        selection: Option<usize>,
+    ) {
+        self.row_hits.clear();
+        // the header and the line under it come first, then the pinned row
+        let mut y = frozen_area.y + 2 + pinned_height;
+        for (index, height) in frozen_heights.iter().enumerate() {
+            self.row_hits.push((y, height + 1, index));
+            y += height + 1;
+        }
+        if heights.is_empty() {
+            return;
+        }
+        let header_height = if frozen_heights.is_empty() && pinned_height == 0 {
+            area.height.min(2)
+        } else {
+            0
+        };
+        let (start, end) = row_bounds(
+            heights,
+            selection.and_then(|selection| selection.checked_sub(frozen_heights.len())),
+            self.visible_row_start,
+            area.height - header_height,
+        );
+        // the other tables are drawn with a copy of the state, so tui does not keep their scroll
+        if frozen_heights.is_empty() && self.selection_area_corner.is_none() {
+            self.visible_row_start = start;
+        }
+        let mut y = area.y + header_height;
+        for (index, height) in heights.iter().enumerate().take(end).skip(start) {
+            self.row_hits
+                .push((y, height + 1, frozen_heights.len() + index));
+            y += height + 1;
+        }
+    }
+
+    /// selects the cell drawn at `column` and `row` of the screen, returning whether there is one
+    fn select_at(&mut self, column: u16, row: u16) -> bool {
+        let row_index = match self
+            .row_hits
+            .iter()
+            .find(|(y, height, _)| (*y..y + height).contains(&row))
+        {
+            Some((_, _, index)) => *index,
+            None => return false,
+        };
+        self.reset_selection();
+        self.selected_row.select(Some(row_index));
+        if let Some((_, _, index)) = self
+            .column_hits
+            .iter()
+            .find(|(x, width, _)| (*x..x + width).contains(&column))
+        {
+            self.selected_column = *index;
+        }
+        true
+    }
+
+    /// returns the width of a column drawn without wrapping, which fits its header and its widest measured value up to 20 cells
+    fn column_width(&self, column_index: usize) -> usize {
+        let mut widths = self.data_widths.borrow_mut();
+        let widths = widths.get_or_insert_with(|| {
+            let mut widths = vec![];
+            measure_widths(
+                &self.rows,
+                sample_indices(self.rows.len()),
+                &self.number_formats(),
+                &mut widths,
+            );
+            widths
+        });
+        widths
+            .get(column_index)
+            .copied()
+            .unwrap_or(0)
+            .max(
+                self.headers
+                    .get(column_index)
+                    .map_or(3, |header| self.header_width(header)),
+            )
+            .clamp(3, 20)
+    }
+
+    /// measures the widths of the columns again from every row in memory instead of a sample of them
+    pub fn recalculate_widths(&mut self) {
+        let mut widths = vec![];
+        measure_widths(
+            &self.rows,
+            0..self.rows.len(),
+            &self.number_formats(),
+            &mut widths,
+        );
+        self.data_widths = RefCell::new(Some(widths));
+    }
+
     fn calculate_cell_widths(
         &self,
         area_width: u16,
@@ -284,42 +1407,47 @@ impl TableComponent {
         if self.rows.is_empty() {
             return (0, Vec::new(), Vec::new(), Vec::new());
         }
-        if self.selected_column_index() < self.column_page_start.get() {
-            self.column_page_start.set(self.selected_column_index());
+        // the page is counted in shown columns, skipping the hidden ones
+        let shown = self.shown_columns();
+        let last_column_index = shown.len().saturating_sub(1);
+        let selected_column_index = self.shown_position(self.selected_column_index());
+        let pinned = self.pinned_column_count();
+        if self.column_page_start.get() < pinned {
+            self.column_page_start.set(pinned);
         }
-
-        let far_right_column_index = self.selected_column_index();
-        let mut column_index = self.selected_column_index();
-        let number_column_width = (self.rows.len() + 1).to_string().width() as u16;
+        // the columns after the pinned ones stay where they are while a pinned one is selected
+        let far_right_column_index = if selected_column_index < pinned {
+            self.column_page_start.get()
+        } else {
+            selected_column_index
+        };
+        if far_right_column_index < self.column_page_start.get() {
+            self.column_page_start.set(far_right_column_index);
+        }
+        let mut column_index = far_right_column_index;
+        let number_column_width = if self.table_config.show_row_number {
+            (self.row_offset + self.rows.len() + 1).to_string().width() as u16
+        } else {
+            0
+        };
+        let area_width = area_width.saturating_sub(number_column_width) as usize;
+        let used = |widths: &[(String, usize)]| {
+            widths.iter().map(|(_, width)| width).sum::<usize>() + widths.len()
+        };
+        let column_width = |index: usize| shown.get(index).map_or(3, |c| self.column_width(*c));
+        // the pinned columns take their width out of every page
+        let pinned_widths = (0..pinned)
+            .map(|index| (self.headers[shown[index]].clone(), column_width(index)))
+            .collect::<Vec<(String, usize)>>();
+        let pinned_width = used(&pinned_widths);
         let mut widths = Vec::new();
         loop {
-            let length = self
-                .rows
-                .iter()
-                .map(|row| {
-                    row.get(column_index)
-                        .map_or(String::new(), |cell| cell.to_string())
-                        .width()
-                })
-                .collect::<Vec<usize>>()
-                .iter()
-                .max()
-                .map_or(3, |v| {
-                    *v.max(
-                        &self
-                            .headers
-                            .get(column_index)
-                            .map_or(3, |header| header.to_string().width()),
-                    )
-                    .clamp(&3, &20)
-                });
-            if widths.iter().map(|(_, width)| width).sum::<usize>() + length + widths.len()
-                >= area_width.saturating_sub(number_column_width) as usize
-            {
+            let length = column_width(column_index);
+            if pinned_width + used(&widths) + length >= area_width {
                 column_index += 1;
                 break;
             }
-            widths.push((self.headers[column_index].clone(), length));
+            widths.push((self.headers[shown[column_index]].clone(), length));
             if column_index == self.column_page_start.get() {
                 break;
             }
@@ -328,72 +1456,49 @@ impl TableComponent {
         widths.reverse();
 
         let far_left_column_index = column_index;
-        let selected_column_index = widths.len().saturating_sub(1);
         let mut column_index = far_right_column_index + 1;
-        while widths.iter().map(|(_, width)| width).sum::<usize>() + widths.len()
-            <= area_width.saturating_sub(number_column_width) as usize
-        {
-            let length = self
-                .rows
-                .iter()
-                .map(|row| {
-                    row.get(column_index)
-                        .map_or(String::new(), |cell| cell.to_string())
-                        .width()
-                })
-                .collect::<Vec<usize>>()
-                .iter()
-                .max()
-                .map_or(3, |v| {
-                    *v.max(
-                        self.headers
-                            .iter()
-                            .map(|header| header.to_string().width())
-                            .collect::<Vec<usize>>()
-                            .get(column_index)
-                            .unwrap_or(&3),
-                    )
-                    .clamp(&3, &20)
-                });
-            match self.headers.get(column_index) {
-                Some(header) => {
-                    widths.push((header.to_string(), length));
+        while pinned_width + used(&widths) <= area_width {
+            let length = column_width(column_index);
+            match shown.get(column_index) {
+                Some(column) => {
+                    widths.push((self.headers[*column].to_string(), length));
                 }
                 None => break,
             }
             column_index += 1
         }
-        if self.selected_column_index() != self.headers.len().saturating_sub(1)
-            && column_index.saturating_sub(1) != self.headers.len().saturating_sub(1)
+        if selected_column_index != last_column_index
+            && column_index.saturating_sub(1) != last_column_index
         {
             widths.pop();
         }
         let far_right_column_index = column_index;
-        let mut constraints = widths
+        let mut constraints = pinned_widths
             .iter()
+            .chain(widths.iter())
             .map(|(_, width)| Constraint::Length(*width as u16))
             .collect::<Vec<Constraint>>();
-        if self.selected_column_index() != self.headers.len().saturating_sub(1)
-            && column_index.saturating_sub(1) != self.headers.len().saturating_sub(1)
+        if selected_column_index != last_column_index
+            && column_index.saturating_sub(1) != last_column_index
         {
             constraints.push(Constraint::Min(10));
         }
-        constraints.insert(0, Constraint::Length(number_column_width));
+        if self.table_config.show_row_number {
+            constraints.insert(0, Constraint::Length(number_column_width));
+        }
         self.column_page_start.set(far_left_column_index);
 
+        let columns = (0..pinned)
+            .chain(far_left_column_index..far_right_column_index)
+            .map(|index| shown[index])
+            .collect::<Vec<usize>>();
         (
-            self.selection_area_corner
-                .map_or(selected_column_index + 1, |(x, _)| {
-                    if x > self.selected_column {
-                        (selected_column_index + 1)
-                            .saturating_sub(x.saturating_sub(self.selected_column))
-                    } else {
-                        (selected_column_index + 1)
-                            .saturating_add(self.selected_column.saturating_sub(x))
-                    }
-                }),
-            self.headers(far_left_column_index, far_right_column_index),
-            self.rows(far_left_column_index, far_right_column_index),
+            columns
+                .iter()
+                .position(|column| *column == self.selected_column)
+                .map_or(usize::MAX, |index| index + self.number_column_count()),
+            self.headers(&columns),
+            self.rows(&columns),
             constraints,
         )
     }
@@ -401,6 +1506,7 @@ impl TableComponent {
 
 impl DrawableComponent for TableComponent {
     fn draw<B: Backend>(&mut self, f: &mut Frame<B>, area: Rect, focused: bool) -> Result<()> {
+        self.area = area;
         let chunks = Layout::default()
             .vertical_margin(1)
             .horizontal_margin(1)
@@ -419,82 +1525,205 @@ impl DrawableComponent for TableComponent {
             Block::default()
                 .title(self.title())
                 .borders(Borders::ALL)
-                .style(if focused {
-                    Style::default()
-                } else {
-                    Style::default().fg(Color::DarkGray)
-                }),
+                .style(Theme::current().text(focused)),
             area,
         );
 
-        self.selected_row.selected().map_or_else(
-            || {
-                self.scroll.reset();
-            },
-            |selection| {
-                self.scroll.update(
-                    selection,
-                    self.rows.len(),
-                    chunks[1].height.saturating_sub(2) as usize,
-                );
-            },
-        );
-
         let block = Block::default().borders(Borders::NONE);
         let (selected_column_index, headers, rows, constraints) =
             self.calculate_cell_widths(block.inner(chunks[0]).width);
+        let visible_columns = headers
+            .len()
+            .saturating_sub(self.number_column_count() + self.pinned_column_count());
+        self.column_hits.clear();
+        let mut x = chunks[1].x;
+        for (index, constraint) in constraints.iter().enumerate() {
+            let width = match constraint {
+                Constraint::Length(width) => *width,
+                _ => chunks[1].right().saturating_sub(x),
+            };
+            if let Some(index) = index.checked_sub(self.number_column_count()) {
+                self.column_hits.push((x, width, self.page_column(index)));
+            }
+            // tui leaves a space between the columns
+            x = x.saturating_add(width + 1);
+        }
         let header_cells = headers.iter().enumerate().map(|(column_index, h)| {
-            Cell::from(h.to_string()).style(if selected_column_index == column_index {
-                Style::default().add_modifier(Modifier::BOLD)
+            let style = if selected_column_index == column_index {
+                Theme::current().header()
             } else {
                 Style::default()
-            })
+            };
+            let mut spans = vec![Span::styled(h.to_string(), style)];
+            if let Some(badge) = self.badge(h) {
+                spans.push(Span::styled(format!(" {}", badge), Theme::current().hint()));
+            }
+            Cell::from(Spans::from(spans))
         });
         let header = Row::new(header_cells).height(1).bottom_margin(1);
-        let rows = rows.iter().enumerate().map(|(row_index, item)| {
-            let height = item
-                .iter()
-                .map(|content| content.chars().filter(|c| *c == '\n').count())
-                .max()
-                .unwrap_or(0)
-                + 1;
-            let cells = item.iter().enumerate().map(|(column_index, c)| {
-                Cell::from(c.to_string()).style(
-                    if self.is_selected_cell(row_index, column_index, selected_column_index) {
-                        Style::default().bg(Color::Blue)
-                    } else if self.is_number_column(row_index, column_index) {
-                        Style::default().add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default()
-                    },
+        let rows = rows
+            .iter()
+            .map(|row| self.wrap_row(row, &constraints))
+            .collect::<Vec<Vec<String>>>();
+        let mut rows = rows
+            .iter()
+            .enumerate()
+            .map(|(row_index, item)| {
+                let height = item
+                    .iter()
+                    .map(|content| content.chars().filter(|c| *c == '\n').count())
+                    .max()
+                    .unwrap_or(0)
+                    + 1;
+                let cells = item.iter().enumerate().map(|(column_index, c)| {
+                    Cell::from(c.to_string()).style(
+                        if self.is_selected_cell(row_index, column_index, selected_column_index) {
+                            Theme::current().selected()
+                        } else if self.is_matched_cell(row_index, column_index) {
+                            Theme::current().matched()
+                        } else if self.is_number_column(row_index, column_index) {
+                            Theme::current().header()
+                        } else {
+                            Style::default()
+                        },
+                    )
+                });
+                (
+                    height as u16,
+                    Row::new(cells)
+                        .height(height as u16)
+                        .bottom_margin(1)
+                        .style(Theme::current().row(self.row_offset + row_index)),
+                )
+            })
+            .collect::<Vec<(u16, Row)>>();
+
+        // the pinned row stands out above the frozen ones, which can be clicked unlike it
+        let pinned_row = self
+            .pinned_row_cells(headers.len() - self.number_column_count())
+            .map(|cells| {
+                let cells = self.wrap_row(&cells, &constraints);
+                let height = cells
+                    .iter()
+                    .map(|content| content.chars().filter(|c| *c == '\n').count())
+                    .max()
+                    .unwrap_or(0) as u16
+                    + 1;
+                (
+                    height,
+                    Row::new(cells.into_iter().map(Cell::from))
+                        .height(height)
+                        .bottom_margin(1)
+                        .style(Theme::current().accent()),
                 )
             });
-            Row::new(cells).height(height as u16).bottom_margin(1)
-        });
+        let pinned_height = pinned_row.as_ref().map_or(0, |(height, _)| height + 1);
+
+        // frozen rows are drawn under the header in their own table and the rest scroll below them
+        let frozen_row_count = self.frozen_row_count();
+        let frozen_rows = pinned_row
+            .into_iter()
+            .chain(rows.drain(..frozen_row_count))
+            .collect::<Vec<(u16, Row)>>();
+        let table_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length(if frozen_rows.is_empty() {
+                        0
+                    } else {
+                        frozen_rows
+                            .iter()
+                            .map(|(height, _)| height + 1)
+                            .sum::<u16>()
+                            + 2
+                    }),
+                    Constraint::Min(1),
+                ]
+                .as_ref(),
+            )
+            .split(chunks[1]);
 
-        let table = Table::new(rows)
-            .header(header)
-            .block(block)
-            .style(if focused {
-                Style::default()
-            } else {
-                Style::default().fg(Color::DarkGray)
-            })
+        let frozen_heights = frozen_rows
+            .iter()
+            .skip(if pinned_height > 0 { 1 } else { 0 })
+            .map(|(height, _)| *height)
+            .collect::<Vec<u16>>();
+        let heights = rows.iter().map(|(height, _)| *height).collect::<Vec<u16>>();
+        let style = Theme::current().text(focused);
+        let rows = rows.into_iter().map(|(_, row)| row);
+        let mut table = Table::new(rows)
+            .block(block.clone())
+            .style(style)
             .widths(&constraints);
-        let mut state = self.selected_row.clone();
-        f.render_stateful_widget(
-            table,
-            chunks[1],
-            if let Some((_, y)) = self.selection_area_corner {
-                state.select(Some(y));
-                &mut state
-            } else {
-                &mut self.selected_row
-            },
+        if frozen_rows.is_empty() {
+            table = table.header(header);
+        } else {
+            f.render_widget(
+                Table::new(frozen_rows.into_iter().map(|(_, row)| row))
+                    .header(header)
+                    .block(block)
+                    .style(style)
+                    .widths(&constraints),
+                table_chunks[0],
+            );
+        }
+
+        self.selected_row
+            .selected()
+            .and_then(|selection| selection.checked_sub(frozen_row_count))
+            .map_or_else(
+                || {
+                    self.scroll.reset();
+                },
+                |selection| {
+                    self.scroll.update(
+                        selection,
+                        self.rows.len() - frozen_row_count,
+                        table_chunks[1].height.saturating_sub(2) as usize,
+                    );
+                },
+            );
+
+        let selection = self
+            .selection_area_corner
+            .map_or(self.selected_row.selected(), |(_, y)| Some(y));
+        self.update_row_hits(
+            table_chunks[0],
+            table_chunks[1],
+            pinned_height,
+            &frozen_heights,
+            &heights,
+            selection,
         );
+        if frozen_row_count == 0 && self.selection_area_corner.is_none() {
+            f.render_stateful_widget(table, table_chunks[1], &mut self.selected_row);
+        } else {
+            let mut state = self.selected_row.clone();
+            state.select(selection.and_then(|selection| selection.checked_sub(frozen_row_count)));
+            f.render_stateful_widget(table, table_chunks[1], &mut state);
+        }
+
+        if Theme::current().column_separators {
+            // no gap follows the last column, which also takes the rest of the width when it is not a fixed one
+            let widths = constraints[..constraints.len().saturating_sub(1)]
+                .iter()
+                .filter_map(|constraint| match constraint {
+                    Constraint::Length(width) => Some(*width),
+                    _ => None,
+                })
+                .collect::<Vec<u16>>();
+            draw_column_separators(f, chunks[1], &widths, Theme::current().hint());
+        }
 
-        TableValueComponent::new(self.selected_cells().unwrap_or_default())
-            .draw(f, chunks[0], focused)?;
+        self.value.update(
+            self.selected_location(),
+            self.selected_cells()
+                .map(|cell| binary_placeholder(cell.as_str()).unwrap_or(cell))
+                .unwrap_or_default(),
+            self.is_selected_cell_truncated(),
+        );
+        self.value.draw(f, chunks[0], focused)?;
 
         TableStatusComponent::new(
             if self.rows.is_empty() {
@@ -508,10 +1737,21 @@ impl DrawableComponent for TableComponent {
                 Some(self.headers.len())
             },
             self.table.as_ref().map(|t| t.1.clone()),
+            self.table_status.clone(),
         )
         .draw(f, chunks[2], focused)?;
 
-        self.scroll.draw(f, chunks[1]);
+        self.scroll.draw(f, table_chunks[1]);
+        draw_horizontal_scrollbar(
+            f,
+            area,
+            self.shown_column_count()
+                .saturating_sub(self.pinned_column_count() + visible_columns),
+            self.column_page_start
+                .get()
+                .saturating_sub(self.pinned_column_count()),
+            visible_columns,
+        );
         Ok(())
     }
 }
@@ -521,70 +1761,498 @@ impl Component for TableComponent {
         out.push(CommandInfo::new(command::extend_selection_by_one_cell(
             &self.key_config,
         )));
+        out.push(CommandInfo::new(command::toggle_wrap(&self.key_config)));
+        out.push(CommandInfo::new(command::recalculate_widths(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::open_cell_value(&self.key_config)));
+        out.push(CommandInfo::new(command::scroll_value(&self.key_config)));
+        out.push(CommandInfo::new(command::freeze_rows(&self.key_config)));
+        out.push(CommandInfo::new(command::pin_row(&self.key_config)));
+        out.push(CommandInfo::new(command::pin_columns(&self.key_config)));
+        out.push(CommandInfo::new(command::show_hide_columns(
+            &self.key_config,
+        )));
+        out.push(CommandInfo::new(command::pipe_to_command(&self.key_config)));
+        out.push(CommandInfo::new(command::search_in_table(&self.key_config)));
+        out.push(CommandInfo::new(command::jump_to_match(&self.key_config)));
+        out.push(CommandInfo::new(command::search_matches(&self.key_config)));
+        out.push(CommandInfo::new(command::go_to(&self.key_config)));
+        out.push(CommandInfo::new(command::export_view(&self.key_config)));
     }
 
     fn event(&mut self, key: Key) -> Result<EventState> {
-        if key == self.key_config.scroll_left {
-            self.previous_column();
+        if self.search_event(key).is_consumed() {
+            return Ok(EventState::Consumed);
+        }
+        if self.go_to_event(key)?.is_consumed() {
+            return Ok(EventState::Consumed);
+        }
+        if self.count_event(key).is_consumed() {
+            return Ok(EventState::Consumed);
+        }
+        let count = self.count.take().unwrap_or(1).max(1);
+        if key == self.key_config.search_in_table {
+            self.search = Some(TableSearch {
+                editing: true,
+                ..TableSearch::default()
+            });
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.go_to {
+            self.go_to = Some(String::new());
+            return Ok(EventState::Consumed);
+        } else if self.search.is_some() && key == self.key_config.next_match {
+            self.jump_to_match(true);
+            return Ok(EventState::Consumed);
+        } else if self.search.is_some() && key == self.key_config.previous_match {
+            self.jump_to_match(false);
+            return Ok(EventState::Consumed);
+        } else if self.search.is_some() && key == self.key_config.exit_popup {
+            self.search = None;
+            return Ok(EventState::Consumed);
+        } else if self.selecting() && key == self.key_config.exit_popup {
+            self.reset_selection();
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.scroll_left {
+            for _ in 0..count.min(self.headers.len()) {
+                self.previous_column();
+            }
             return Ok(EventState::Consumed);
         } else if key == self.key_config.scroll_down {
-            self.next_row(1);
+            self.load_rows_below(count)?;
+            self.next_row(count);
             return Ok(EventState::NotConsumed);
         } else if key == self.key_config.scroll_down_multiple_lines {
-            self.next_row(10);
+            self.load_rows_below(count.saturating_mul(10))?;
+            self.next_row(count.saturating_mul(10));
             return Ok(EventState::NotConsumed);
         } else if key == self.key_config.scroll_up {
-            self.previous_row(1);
+            self.load_rows_above(count)?;
+            self.previous_row(count);
             return Ok(EventState::Consumed);
         } else if key == self.key_config.scroll_up_multiple_lines {
-            self.previous_row(10);
+            self.load_rows_above(count.saturating_mul(10))?;
+            self.previous_row(count.saturating_mul(10));
             return Ok(EventState::Consumed);
         } else if key == self.key_config.scroll_to_top {
             self.scroll_to_top();
+            while self.load_above()? {
+                self.scroll_to_top();
+            }
             return Ok(EventState::Consumed);
         } else if key == self.key_config.scroll_to_bottom {
             self.scroll_to_bottom();
+            while self.load_below()? {
+                self.scroll_to_bottom();
+            }
             return Ok(EventState::Consumed);
         } else if key == self.key_config.scroll_right {
-            self.next_column();
+            for _ in 0..count.min(self.headers.len()) {
+                self.next_column();
+            }
             return Ok(EventState::Consumed);
         } else if key == self.key_config.extend_selection_by_one_cell_left {
-            self.expand_selected_area_x(false);
+            for _ in 0..count.min(self.headers.len()) {
+                self.expand_selected_area_x(false);
+            }
             return Ok(EventState::Consumed);
         } else if key == self.key_config.extend_selection_by_one_cell_up {
-            self.expand_selected_area_y(false);
+            for _ in 0..count.min(self.rows.len()) {
+                self.expand_selected_area_y(false);
+            }
             return Ok(EventState::Consumed);
         } else if key == self.key_config.extend_selection_by_one_cell_down {
-            self.expand_selected_area_y(true);
+            for _ in 0..count.min(self.rows.len()) {
+                self.expand_selected_area_y(true);
+            }
             return Ok(EventState::Consumed);
         } else if key == self.key_config.extend_selection_by_one_cell_right {
-            self.expand_selected_area_x(true);
+            for _ in 0..count.min(self.headers.len()) {
+                self.expand_selected_area_x(true);
+            }
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.toggle_wrap {
+            self.toggle_wrap();
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.recalculate_widths {
+            self.recalculate_widths();
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.freeze_rows {
+            self.toggle_frozen_rows();
             return Ok(EventState::Consumed);
+        } else if key == self.key_config.pin_row {
+            self.toggle_pinned_row();
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.pin_columns {
+            self.toggle_pinned_columns();
+            return Ok(EventState::Consumed);
+        }
+        self.value.event(key)
+    }
+
+    fn mouse_event(&mut self, mouse: Mouse) -> Result<EventState> {
+        if !mouse.is_in(self.area) {
+            return Ok(EventState::NotConsumed);
+        }
+        match mouse.kind {
+            MouseKind::Click => {
+                self.select_at(mouse.column, mouse.row);
+            }
+            MouseKind::ScrollUp => {
+                self.load_rows_above(WHEEL_LINES)?;
+                self.previous_row(WHEEL_LINES);
+            }
+            MouseKind::ScrollDown => {
+                self.load_rows_below(WHEEL_LINES)?;
+                self.next_row(WHEEL_LINES);
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+}
+
+/// returns the rows which fit in `max_height` lines from `offset`, moved so that the selected one is among them, as tui does
+fn row_bounds(
+    heights: &[u16],
+    selected: Option<usize>,
+    offset: usize,
+    max_height: u16,
+) -> (usize, usize) {
+    // every row has a line under it
+    let total_height = |index: usize| heights[index] + 1;
+    let mut start = offset;
+    let mut end = offset;
+    let mut height = 0;
+    for (index, row_height) in heights.iter().enumerate().skip(offset) {
+        if height + row_height > max_height {
+            break;
+        }
+        height += total_height(index);
+        end += 1;
+    }
+
+    let selected = selected.unwrap_or(0).min(heights.len() - 1);
+    while selected >= end {
+        height = height.saturating_add(total_height(end));
+        end += 1;
+        while height > max_height {
+            height = height.saturating_sub(total_height(start));
+            start += 1;
+        }
+    }
+    while selected < start {
+        start -= 1;
+        height = height.saturating_add(total_height(start));
+        while height > max_height {
+            end -= 1;
+            height = height.saturating_sub(total_height(end));
+        }
+    }
+    (start, end)
+}
+
+/// returns the rows to measure the widths of the columns from, which are the first ones followed by others chosen at random when there are too many to scan
+fn sample_indices(len: usize) -> Vec<usize> {
+    if len <= WIDTH_HEAD_ROWS + WIDTH_SAMPLE_ROWS {
+        return (0..len).collect();
+    }
+    let state = RandomState::new();
+    let mut indices = (0..WIDTH_HEAD_ROWS).collect::<Vec<usize>>();
+    indices.extend((0..WIDTH_SAMPLE_ROWS).map(|i| {
+        let mut hasher = state.build_hasher();
+        hasher.write_usize(i);
+        WIDTH_HEAD_ROWS + hasher.finish() as usize % (len - WIDTH_HEAD_ROWS)
+    }));
+    indices
+}
+
+/// widens `widths` to the values of the rows at `indices`
+fn measure_widths(
+    rows: &[Vec<Option<String>>],
+    indices: impl IntoIterator<Item = usize>,
+    number_formats: &[Option<NumberFormat>],
+    widths: &mut Vec<usize>,
+) {
+    for row in indices.into_iter().filter_map(|index| rows.get(index)) {
+        if widths.len() < row.len() {
+            widths.resize(row.len(), 0);
+        }
+        for (index, (width, cell)) in widths.iter_mut().zip(row).enumerate() {
+            let cell = display_value(cell);
+            let cell_width = number_formats
+                .get(index)
+                .and_then(|format| format_number(cell, format.as_ref()?))
+                .or_else(|| binary_placeholder(cell))
+                .map_or_else(|| cell.width(), |cell| cell.width());
+            *width = (*width).max(cell_width);
+        }
+    }
+}
+
+/// returns the cells of `row` in `columns` as they are drawn, with their number formats and binary values replaced
+fn format_cells(
+    row: &[Option<String>],
+    columns: &[usize],
+    number_formats: &[Option<NumberFormat>],
+) -> Vec<String> {
+    columns
+        .iter()
+        .map(|column| {
+            let cell = display_value(&row[*column]);
+            number_formats
+                .get(*column)
+                .and_then(|format| format_number(cell, format.as_ref()?))
+                .or_else(|| binary_placeholder(cell))
+                .unwrap_or_else(|| cell.to_string())
+        })
+        .collect()
+}
+
+fn wrap_text(text: &str, width: usize) -> String {
+    if width == 0 {
+        return text.to_string();
+    }
+    let mut lines = vec![];
+    for line in text.lines() {
+        let mut current = String::new();
+        let mut current_width = 0;
+        for c in line.chars() {
+            let char_width = c.width().unwrap_or(0);
+            if current_width + char_width > width && !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+                current_width = 0;
+            }
+            current.push(c);
+            current_width += char_width;
         }
-        Ok(EventState::NotConsumed)
+        lines.push(current);
     }
+    lines.join("\n")
+}
+
+/// cuts every line of `text` to `width` cells, ending the lines it cuts with `…`
+fn truncate_text(text: &str, width: usize) -> String {
+    if width == 0 {
+        return text.to_string();
+    }
+    text.lines()
+        .map(|line| {
+            if line.width() <= width {
+                return line.to_string();
+            }
+            let mut truncated = String::new();
+            let mut truncated_width = 0;
+            for c in line.chars() {
+                let char_width = c.width().unwrap_or(0);
+                if truncated_width + char_width >= width {
+                    break;
+                }
+                truncated.push(c);
+                truncated_width += char_width;
+            }
+            truncated.push('…');
+            truncated
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
 #[cfg(test)]
 mod test {
-    use super::{KeyConfig, TableComponent};
-    use tui::layout::Constraint;
+    use super::{
+        row_bounds, sample_indices, truncate_text, wrap_text, ColumnKind, Component as _, DTable,
+        Database, KeyConfig, Mouse, MouseKind, NumberFormat, RowRange, TableComponent, TableConfig,
+        WIDTH_HEAD_ROWS, WIDTH_SAMPLE_ROWS,
+    };
+    use crate::event::Key;
+    use tui::layout::{Constraint, Rect};
+
+    #[test]
+    fn test_wrap_text() {
+        assert_eq!(wrap_text("abcdefg", 3), "abc\ndef\ng");
+        assert_eq!(wrap_text("ab\ncdef", 3), "ab\ncde\nf");
+        assert_eq!(wrap_text("あいう", 4), "あい\nう");
+        assert_eq!(wrap_text("abc", 0), "abc");
+    }
+
+    #[test]
+    fn test_truncate_text() {
+        assert_eq!(truncate_text("abc", 3), "abc");
+        assert_eq!(truncate_text("abcdefg", 3), "ab…");
+        assert_eq!(truncate_text("abcd\nab", 3), "ab…\nab");
+        assert_eq!(truncate_text("あいう", 4), "あ…");
+        assert_eq!(truncate_text("abc", 0), "abc");
+    }
+
+    #[test]
+    fn test_toggle_wrap() {
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.headers = vec!["1", "2"].iter().map(|h| h.to_string()).collect();
+        component.rows = vec![vec!["aaaaa", "bbbbb"]
+            .iter()
+            .map(|h| Some(h.to_string()))
+            .collect()];
+        component.selected_column = 1;
+        component.toggle_wrap();
+        assert!(!component.is_wrapped_column(0));
+        assert!(component.is_wrapped_column(1));
+        assert_eq!(
+            component.wrap_row(
+                &["1", "aaaaa", "bbbbb"]
+                    .iter()
+                    .map(|h| h.to_string())
+                    .collect::<Vec<String>>(),
+                &[
+                    Constraint::Length(1),
+                    Constraint::Length(3),
+                    Constraint::Length(3)
+                ]
+            ),
+            vec!["1", "aa…", "bbb\nbb"]
+        );
+        component.toggle_wrap();
+        assert!(!component.is_wrapped_column(1));
+    }
 
     #[test]
     fn test_headers() {
-        let mut component = TableComponent::new(KeyConfig::default());
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
         component.headers = vec!["a", "b", "c"].iter().map(|h| h.to_string()).collect();
-        assert_eq!(component.headers(1, 2), vec!["", "b"])
+        assert_eq!(component.headers(&[1]), vec!["", "b"])
     }
 
     #[test]
     fn test_rows() {
-        let mut component = TableComponent::new(KeyConfig::default());
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.rows = vec![
+            vec!["a", "b", "c"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["d", "e", "f"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+        ];
+        assert_eq!(component.rows(&[1]), vec![vec!["1", "b"], vec!["2", "e"]],)
+    }
+
+    #[test]
+    fn test_rows_with_number_format() {
+        let mut table_config = TableConfig::default();
+        table_config.number_format.default.thousands_separator = Some(true);
+        table_config.number_format.columns.insert(
+            "price".to_string(),
+            NumberFormat {
+                precision: Some(2),
+                ..NumberFormat::default()
+            },
+        );
+        let mut component = TableComponent::new(KeyConfig::default(), table_config);
+        component.headers = vec!["id", "price", "zip"]
+            .iter()
+            .map(|h| h.to_string())
+            .collect();
+        component.rows = vec![
+            vec!["1024", "1234.5", "10001"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["2048", "free", "02134"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+        ];
+        component.set_badges(vec![("zip".to_string(), "txt")].into_iter().collect());
+        assert_eq!(
+            component.rows(&[0, 1, 2]),
+            vec![
+                vec!["1", "1,024", "1,234.50", "10001"],
+                vec!["2", "2,048", "free", "02134"]
+            ],
+        );
+        assert_eq!(component.column_width(1), 8);
+        component.selected_row.select(Some(0));
+        component.selected_column = 1;
+        assert_eq!(component.selected_cells(), Some("1234.5".to_string()));
+    }
+
+    #[test]
+    fn test_refresh_rows() {
+        let rows = |rows: &[&[&str]]| {
+            rows.iter()
+                .map(|row| row.iter().map(|cell| Some(cell.to_string())).collect())
+                .collect::<Vec<Vec<Option<String>>>>()
+        };
+        let headers = |headers: &[&str]| {
+            headers
+                .iter()
+                .map(|header| header.to_string())
+                .collect::<Vec<String>>()
+        };
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.update_rows(
+            rows(&[&["1", "a"], &["2", "b"], &["3", "c"]]),
+            headers(&["id", "name"]),
+        );
+        component.selected_row.select(Some(2));
+        component.selected_column = 1;
+        component.refresh_rows(
+            rows(&[&["1", "a"], &["2", "b"], &["3", "d"], &["4", "e"]]),
+            headers(&["id", "name"]),
+        );
+        assert_eq!(component.selected_cells(), Some("d".to_string()));
+
+        component.refresh_rows(rows(&[&["x", "1"]]), headers(&["name", "id"]));
+        assert_eq!(component.selected_row.selected(), Some(0));
+        assert_eq!(component.selected_cells(), Some("x".to_string()));
+
+        component.refresh_rows(vec![], headers(&["name", "id"]));
+        assert_eq!(component.selected_row.selected(), None);
+    }
+
+    #[test]
+    fn test_rows_with_row_offset() {
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.rows = vec![
+            vec!["a", "b", "c"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["d", "e", "f"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+        ];
+        component.row_offset = 200;
+        assert_eq!(
+            component.rows(&[1]),
+            vec![vec!["201", "b"], vec!["202", "e"]],
+        )
+    }
+
+    #[test]
+    fn test_rows_without_row_number() {
+        let mut component = TableComponent::new(
+            KeyConfig::default(),
+            TableConfig {
+                show_row_number: false,
+                ..TableConfig::default()
+            },
+        );
+        component.headers = vec!["a", "b", "c"].iter().map(|h| h.to_string()).collect();
         component.rows = vec![
-            vec!["a", "b", "c"].iter().map(|h| h.to_string()).collect(),
-            vec!["d", "e", "f"].iter().map(|h| h.to_string()).collect(),
+            vec!["a", "b", "c"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["d", "e", "f"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
         ];
-        assert_eq!(component.rows(1, 2), vec![vec!["1", "b"], vec!["2", "e"]],)
+        assert_eq!(component.headers(&[1]), vec!["b"]);
+        assert_eq!(component.rows(&[1]), vec![vec!["b"], vec!["e"]]);
     }
 
     #[test]
@@ -599,11 +2267,17 @@ mod test {
         // 1  a  b  c
         // 2 |d  e| f
 
-        let mut component = TableComponent::new(KeyConfig::default());
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
         component.headers = vec!["1", "2", "3"].iter().map(|h| h.to_string()).collect();
         component.rows = vec![
-            vec!["a", "b", "c"].iter().map(|h| h.to_string()).collect(),
-            vec!["d", "e", "f"].iter().map(|h| h.to_string()).collect(),
+            vec!["a", "b", "c"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["d", "e", "f"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
         ];
         component.selected_row.select(Some(1));
         component.selected_column = 1;
@@ -612,6 +2286,90 @@ mod test {
         assert_eq!(component.selected_cells(), Some("d,e".to_string()));
     }
 
+    #[test]
+    fn test_count() {
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.headers = vec!["1", "2", "3"].iter().map(|h| h.to_string()).collect();
+        component.rows = (0..30).map(|i| vec![Some(i.to_string()); 3]).collect();
+        component.selected_row.select(Some(0));
+
+        // the digits are left to the tab keys until a count is started
+        assert!(!component.event(Key::Char('1')).unwrap().is_consumed());
+        assert_eq!(component.count, None);
+
+        component.event(Key::Char('#')).unwrap();
+        component.event(Key::Char('1')).unwrap();
+        component.event(Key::Char('2')).unwrap();
+        assert_eq!(component.count, Some(12));
+        component.event(KeyConfig::default().scroll_down).unwrap();
+        assert_eq!(component.selected_row.selected(), Some(12));
+        assert_eq!(component.count, None);
+
+        component.event(Key::Char('#')).unwrap();
+        component.event(Key::Char('5')).unwrap();
+        component.event(KeyConfig::default().scroll_up).unwrap();
+        assert_eq!(component.selected_row.selected(), Some(7));
+
+        component.event(Key::Char('#')).unwrap();
+        component.event(Key::Char('0')).unwrap();
+        component.event(Key::Char('9')).unwrap();
+        component.event(KeyConfig::default().scroll_right).unwrap();
+        assert_eq!(component.selected_column, 2);
+
+        component.event(Key::Char('#')).unwrap();
+        component.event(Key::Char('3')).unwrap();
+        component
+            .event(KeyConfig::default().extend_selection_by_one_cell_up)
+            .unwrap();
+        assert_eq!(component.selection_area_corner, Some((2, 4)));
+    }
+
+    #[test]
+    fn test_go_to() {
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.update_rows(
+            (0..30).map(|i| vec![Some(i.to_string()); 3]).collect(),
+            vec![
+                "id".to_string(),
+                "user_id".to_string(),
+                "username".to_string(),
+            ],
+        );
+
+        component.event(KeyConfig::default().go_to).unwrap();
+        assert!(component.searching());
+        for c in "25".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        assert_eq!(component.title(), " -   :25");
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(!component.searching());
+        assert_eq!(component.selected_row.selected(), Some(24));
+        assert_eq!(component.take_wanted_row(), None);
+
+        component.event(KeyConfig::default().go_to).unwrap();
+        component.event(Key::Char('4')).unwrap();
+        component.event(Key::Char('0')).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(component.selected_row.selected(), Some(29));
+        assert_eq!(component.take_wanted_row(), Some(39));
+
+        component.event(KeyConfig::default().go_to).unwrap();
+        component.event(Key::Char('U')).unwrap();
+        component.event(Key::Tab).unwrap();
+        assert_eq!(component.go_to, Some("user".to_string()));
+        component.event(Key::Char('n')).unwrap();
+        component.event(Key::Tab).unwrap();
+        assert_eq!(component.go_to, Some("username".to_string()));
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(component.selected_column, 2);
+
+        component.event(KeyConfig::default().go_to).unwrap();
+        component.event(Key::Char('u')).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert_eq!(component.selected_column, 1);
+    }
+
     #[test]
     fn test_expand_selected_area_x_right() {
         // before
@@ -624,11 +2382,17 @@ mod test {
         // 1  a  b  c
         // 2  d |e  f|
 
-        let mut component = TableComponent::new(KeyConfig::default());
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
         component.headers = vec!["1", "2", "3"].iter().map(|h| h.to_string()).collect();
         component.rows = vec![
-            vec!["a", "b", "c"].iter().map(|h| h.to_string()).collect(),
-            vec!["d", "e", "f"].iter().map(|h| h.to_string()).collect(),
+            vec!["a", "b", "c"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["d", "e", "f"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
         ];
         component.selected_row.select(Some(1));
         component.selected_column = 1;
@@ -649,10 +2413,16 @@ mod test {
         // 1  a |b| c
         // 2  d |e| f
 
-        let mut component = TableComponent::new(KeyConfig::default());
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
         component.rows = vec![
-            vec!["a", "b", "c"].iter().map(|h| h.to_string()).collect(),
-            vec!["d", "e", "f"].iter().map(|h| h.to_string()).collect(),
+            vec!["a", "b", "c"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["d", "e", "f"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
         ];
         component.selected_row.select(Some(1));
         component.selected_column = 1;
@@ -673,10 +2443,16 @@ mod test {
         // 1  a |b| c
         // 2  d |e| f
 
-        let mut component = TableComponent::new(KeyConfig::default());
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
         component.rows = vec![
-            vec!["a", "b", "c"].iter().map(|h| h.to_string()).collect(),
-            vec!["d", "e", "f"].iter().map(|h| h.to_string()).collect(),
+            vec!["a", "b", "c"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["d", "e", "f"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
         ];
         component.selected_row.select(Some(0));
         component.selected_column = 1;
@@ -687,11 +2463,17 @@ mod test {
 
     #[test]
     fn test_is_number_column() {
-        let mut component = TableComponent::new(KeyConfig::default());
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
         component.headers = vec!["1", "2", "3"].iter().map(|h| h.to_string()).collect();
         component.rows = vec![
-            vec!["a", "b", "c"].iter().map(|h| h.to_string()).collect(),
-            vec!["d", "e", "f"].iter().map(|h| h.to_string()).collect(),
+            vec!["a", "b", "c"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["d", "e", "f"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
         ];
         component.selected_row.select(Some(0));
         assert!(component.is_number_column(0, 0));
@@ -704,31 +2486,407 @@ mod test {
         // 1 |a| b c
         // 2  d  e f
 
-        let mut component = TableComponent::new(KeyConfig::default());
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
         component.headers = vec!["1", "2", "3"].iter().map(|h| h.to_string()).collect();
         component.rows = vec![
-            vec!["a", "b", "c"].iter().map(|h| h.to_string()).collect(),
-            vec!["d", "e", "f"].iter().map(|h| h.to_string()).collect(),
+            vec!["a", "b", "c"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["d", "e", "f"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
         ];
         component.selected_row.select(Some(0));
         assert_eq!(component.selected_cells(), Some("a".to_string()));
     }
 
+    #[test]
+    fn test_spill_rows() {
+        let mut component = TableComponent::new(
+            KeyConfig::default(),
+            TableConfig {
+                max_rows_in_memory: 3,
+                ..TableConfig::default()
+            },
+        );
+        let rows = |range: std::ops::Range<usize>| {
+            range
+                .map(|i| vec![Some(i.to_string())])
+                .collect::<Vec<Vec<Option<String>>>>()
+        };
+        component.update_rows(rows(0..2), vec!["1".to_string()]);
+        component.selected_row.select(Some(1));
+        component.append_rows(rows(2..4)).unwrap();
+        assert_eq!(component.rows, rows(1..4));
+        assert_eq!(component.row_offset, 1);
+        assert_eq!(component.selected_row.selected(), Some(0));
+        assert_eq!(component.selected_location(), "1 — row 2 / 4");
+
+        component.event(KeyConfig::default().scroll_up).unwrap();
+        assert_eq!(component.rows, rows(0..3));
+        assert_eq!(component.row_offset, 0);
+        assert_eq!(component.selected_row.selected(), Some(0));
+        assert!(component.has_rows_below());
+        assert_eq!(component.selected_location(), "1 — row 1 / 4");
+
+        component
+            .event(KeyConfig::default().scroll_to_bottom)
+            .unwrap();
+        assert_eq!(component.rows, rows(1..4));
+        assert_eq!(component.selected_row.selected(), Some(2));
+        assert!(!component.has_rows_below());
+
+        component.event(KeyConfig::default().scroll_to_top).unwrap();
+        assert_eq!(component.rows, rows(0..3));
+        assert_eq!(component.selected_row.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_selected_location() {
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        assert_eq!(component.selected_location(), "");
+        component.update(
+            (0..1500)
+                .map(|i| vec![Some(i.to_string()), Some(i.to_string())])
+                .collect(),
+            vec!["id".to_string(), "customer_id".to_string()],
+            Database::new("shop".to_string(), vec![]),
+            DTable {
+                name: "orders".to_string(),
+                create_time: None,
+                update_time: None,
+                engine: None,
+                schema: None,
+                view: false,
+            },
+        );
+        component.selected_column = 1;
+        component.selected_row.select(Some(1203));
+        assert_eq!(
+            component.selected_location(),
+            "orders.customer_id — row 1,204 / 1,500"
+        );
+    }
+
+    #[test]
+    fn test_search() {
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.update_rows(
+            vec![
+                vec![Some("apple".to_string()), Some("banana".to_string())],
+                vec![Some("cherry".to_string()), Some("Grape".to_string())],
+                vec![Some("pineapple".to_string()), Some("kiwi".to_string())],
+            ],
+            vec!["a".to_string(), "b".to_string()],
+        );
+        component.selected_column = 1;
+        component
+            .event(KeyConfig::default().search_in_table)
+            .unwrap();
+        assert!(component.searching());
+        for c in "APn".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        assert_eq!(component.search.as_ref().unwrap().matches, vec![]);
+        component.event(Key::Backspace).unwrap();
+        assert_eq!(
+            component.search.as_ref().unwrap().matches,
+            vec![(0, 0), (1, 1), (2, 0)]
+        );
+        assert_eq!(component.title(), " -   /AP (3 matches)");
+
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(!component.searching());
+        assert_eq!(component.selected_row.selected(), Some(1));
+        assert_eq!(component.selected_column, 1);
+        assert!(component.is_matched_cell(2, 1));
+        assert!(!component.is_matched_cell(2, 0));
+
+        component.event(KeyConfig::default().next_match).unwrap();
+        assert_eq!(component.selected_row.selected(), Some(2));
+        assert_eq!(component.selected_column, 0);
+        component.event(KeyConfig::default().next_match).unwrap();
+        assert_eq!(component.selected_row.selected(), Some(0));
+        component
+            .event(KeyConfig::default().previous_match)
+            .unwrap();
+        assert_eq!(component.selected_row.selected(), Some(2));
+
+        component.event(KeyConfig::default().exit_popup).unwrap();
+        assert!(component.search.is_none());
+        assert_eq!(component.title(), " - ");
+    }
+
+    #[test]
+    fn test_export() {
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.update_rows(
+            (1..=20)
+                .map(|i| vec![Some(i.to_string()), Some(format!("row{}", i))])
+                .collect(),
+            vec!["id".to_string(), "name".to_string()],
+        );
+        component.area = Rect::new(0, 0, 40, 12);
+
+        let visible = component.export(false, false).unwrap();
+        assert_eq!(visible.lines().count(), 12);
+        assert!(visible.contains("row2"));
+        assert!(!visible.contains("row20"));
+
+        let all = component.export(true, false).unwrap();
+        assert_eq!(all.lines().count(), 20 * 2 + 8);
+        assert!(all.contains("row20"));
+        assert_eq!(component.area, Rect::new(0, 0, 40, 12));
+    }
+
+    #[test]
+    fn test_row_bounds() {
+        assert_eq!(row_bounds(&[1, 1, 1, 1], Some(0), 0, 5), (0, 3));
+        assert_eq!(row_bounds(&[1, 1, 1, 1], Some(3), 0, 5), (2, 4));
+        assert_eq!(row_bounds(&[1, 2, 1, 1], Some(3), 2, 5), (2, 4));
+        assert_eq!(row_bounds(&[1, 1, 1, 1], Some(0), 2, 5), (0, 2));
+    }
+
+    #[test]
+    fn test_mouse_event() {
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.update_rows(
+            (1..=20)
+                .map(|i| vec![Some(i.to_string()), Some(format!("row{}", i))])
+                .collect(),
+            vec!["id".to_string(), "name".to_string()],
+        );
+        component.area = Rect::new(0, 0, 40, 12);
+        component.export(false, false).unwrap();
+
+        // the rows start under the value of the cell and the header, two lines each
+        let click = |column, row| Mouse {
+            kind: MouseKind::Click,
+            column,
+            row,
+        };
+        component.mouse_event(click(9, 7)).unwrap();
+        assert_eq!(component.selected_row.selected(), Some(1));
+        assert_eq!(component.selected_column, 1);
+        component.mouse_event(click(5, 5)).unwrap();
+        assert_eq!(component.selected_row.selected(), Some(0));
+        assert_eq!(component.selected_column, 0);
+
+        component
+            .mouse_event(Mouse {
+                kind: MouseKind::ScrollDown,
+                column: 5,
+                row: 5,
+            })
+            .unwrap();
+        assert_eq!(component.selected_row.selected(), Some(3));
+        assert!(!component.mouse_event(click(50, 5)).unwrap().is_consumed());
+    }
+
+    #[test]
+    fn test_toggle_pinned_row() {
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.update_rows(
+            (1..=20)
+                .map(|i| vec![Some(i.to_string()), Some(format!("row{}", i))])
+                .collect(),
+            vec!["id".to_string(), "name".to_string()],
+        );
+        component.area = Rect::new(0, 0, 40, 12);
+        component.selected_row.select(Some(2));
+        component.event(KeyConfig::default().pin_row).unwrap();
+        assert_eq!(
+            component.pinned_row,
+            Some((2, vec![Some("3".to_string()), Some("row3".to_string())]))
+        );
+
+        // the pinned row stays under the header while the others scroll, and is not selected by a click
+        component.selected_row.select(Some(15));
+        let text = component.export(false, false).unwrap();
+        assert!(text.lines().nth(5).unwrap().contains("row3"));
+        assert!(text.contains("row16"));
+        component
+            .mouse_event(Mouse {
+                kind: MouseKind::Click,
+                column: 9,
+                row: 5,
+            })
+            .unwrap();
+        assert_eq!(component.selected_row.selected(), Some(15));
+
+        component.event(KeyConfig::default().pin_row).unwrap();
+        assert_eq!(component.pinned_row.as_ref().unwrap().0, 15);
+        component.event(KeyConfig::default().pin_row).unwrap();
+        assert_eq!(component.pinned_row, None);
+    }
+
+    #[test]
+    fn test_toggle_frozen_rows() {
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.headers = vec!["1"].iter().map(|h| h.to_string()).collect();
+        component.rows = vec![vec![Some("a".to_string())], vec![Some("b".to_string())]];
+        component.selected_row.select(Some(0));
+        assert_eq!(component.frozen_row_count(), 0);
+        component.event(KeyConfig::default().freeze_rows).unwrap();
+        assert_eq!(component.frozen_row_count(), 1);
+        component.event(KeyConfig::default().freeze_rows).unwrap();
+        assert_eq!(component.frozen_row_count(), 0);
+
+        component.selected_row.select(Some(1));
+        component.event(KeyConfig::default().freeze_rows).unwrap();
+        assert_eq!(component.frozen_rows, 2);
+        assert_eq!(component.frozen_row_count(), 1);
+    }
+
+    #[test]
+    fn test_calculate_cell_widths_with_pinned_columns() {
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.headers = vec!["1", "2", "3", "4"]
+            .iter()
+            .map(|h| h.to_string())
+            .collect();
+        component.rows = vec![vec!["aaaaa", "bbbbb", "ccccc", "ddddd"]
+            .iter()
+            .map(|h| Some(h.to_string()))
+            .collect()];
+        component.selected_column = 3;
+        component.event(KeyConfig::default().pin_columns).unwrap();
+        assert_eq!(component.pinned_column_count(), 3);
+        component.event(KeyConfig::default().pin_columns).unwrap();
+        assert_eq!(component.pinned_column_count(), 0);
+
+        component.pinned_columns = 1;
+        let (selected_column_index, headers, rows, constraints) =
+            component.calculate_cell_widths(14);
+        assert_eq!(selected_column_index, 2);
+        assert_eq!(headers, vec!["", "1", "4"]);
+        assert_eq!(rows, vec![vec!["1", "aaaaa", "ddddd"]]);
+        assert_eq!(
+            constraints,
+            vec![
+                Constraint::Length(1),
+                Constraint::Length(5),
+                Constraint::Length(5),
+            ]
+        );
+        assert_eq!(component.page_column(0), 0);
+        assert_eq!(component.page_column(1), 3);
+
+        // the page stays where it is while a pinned column is selected
+        component.selected_column = 0;
+        let (selected_column_index, headers, _, _) = component.calculate_cell_widths(14);
+        assert_eq!(selected_column_index, 1);
+        assert_eq!(headers, vec!["", "1", "4"]);
+    }
+
+    #[test]
+    fn test_hidden_columns() {
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.headers = vec!["1", "2", "3", "4"]
+            .iter()
+            .map(|h| h.to_string())
+            .collect();
+        component.rows = vec![vec!["aaaaa", "bbbbb", "ccccc", "ddddd"]
+            .iter()
+            .map(|h| Some(h.to_string()))
+            .collect()];
+        component.selected_row.select(Some(0));
+        component.selected_column = 1;
+        component.set_hidden_columns(vec![1, 2].into_iter().collect());
+        assert_eq!(component.selected_column, 3);
+        assert_eq!(component.hidden_headers(), vec!["2", "3"]);
+
+        let (selected_column_index, headers, rows, constraints) =
+            component.calculate_cell_widths(40);
+        assert_eq!(selected_column_index, 2);
+        assert_eq!(headers, vec!["", "1", "4"]);
+        assert_eq!(rows, vec![vec!["1", "aaaaa", "ddddd"]]);
+        assert_eq!(
+            constraints,
+            vec![
+                Constraint::Length(1),
+                Constraint::Length(5),
+                Constraint::Length(5),
+            ]
+        );
+        assert_eq!(component.page_column(1), 3);
+
+        component.previous_column();
+        assert_eq!(component.selected_column, 0);
+        component.expand_selected_area_x(true);
+        assert_eq!(component.selected_cells(), Some("aaaaa,ddddd".to_string()));
+
+        // at least one column stays shown
+        component.set_hidden_columns(vec![0, 1, 2, 3].into_iter().collect());
+        assert_eq!(component.hidden_headers(), vec!["2", "3"]);
+    }
+
     #[test]
     fn test_selected_cell_when_multiple_cells_selected() {
         //    1  2  3
         // 1 |a  b| c
         // 2 |d  e| f
 
-        let mut component = TableComponent::new(KeyConfig::default());
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
         component.headers = vec!["1", "2", "3"].iter().map(|h| h.to_string()).collect();
         component.rows = vec![
-            vec!["a", "b", "c"].iter().map(|h| h.to_string()).collect(),
-            vec!["d", "e", "f"].iter().map(|h| h.to_string()).collect(),
+            vec!["a", "b", "c"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["d", "e", "f"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
         ];
         component.selected_row.select(Some(0));
         component.selection_area_corner = Some((1, 1));
         assert_eq!(component.selected_cells(), Some("a,b\nd,e".to_string()));
+        assert_eq!(component.selected_tsv(), Some("a\tb\nd\te".to_string()));
+    }
+
+    #[test]
+    fn test_shown_values() {
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.headers = vec!["id", "name", "note"]
+            .iter()
+            .map(|h| h.to_string())
+            .collect();
+        component.rows = vec![
+            vec!["1", "a", "x"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["2", "NULL", "y"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec![Some("3".to_string()), None, Some("z".to_string())],
+        ];
+        component.set_badges(vec![("id".to_string(), "int")].into_iter().collect());
+        component.set_hidden_columns(vec![2].into_iter().collect());
+        component.selected_row.select(Some(1));
+        component.selection_area_corner = Some((0, 2));
+        component.row_hits = vec![(2, 1, 0), (3, 1, 1)];
+
+        let (headers, kinds, rows) = component.shown_values(RowRange::Selected);
+        assert_eq!(headers, vec!["id", "name"]);
+        assert_eq!(kinds, vec![ColumnKind::Number, ColumnKind::Text]);
+        let value = |value: &str| Some(value.to_string());
+        assert_eq!(
+            rows,
+            vec![vec![value("2"), value("NULL")], vec![value("3"), None]]
+        );
+        assert_eq!(
+            component.shown_values(RowRange::Visible).2,
+            vec![
+                vec![value("1"), value("a")],
+                vec![value("2"), value("NULL")]
+            ]
+        );
+        assert_eq!(component.shown_values(RowRange::All).2.len(), 3);
     }
 
     #[test]
@@ -737,11 +2895,17 @@ mod test {
         // 1 |a| b c
         // 2  d  e f
 
-        let mut component = TableComponent::new(KeyConfig::default());
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
         component.headers = vec!["1", "2", "3"].iter().map(|h| h.to_string()).collect();
         component.rows = vec![
-            vec!["a", "b", "c"].iter().map(|h| h.to_string()).collect(),
-            vec!["d", "e", "f"].iter().map(|h| h.to_string()).collect(),
+            vec!["a", "b", "c"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["d", "e", "f"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
         ];
         component.selected_row.select(Some(0));
         // a
@@ -758,11 +2922,17 @@ mod test {
         // 1 |a  b| c
         // 2 |d  e| f
 
-        let mut component = TableComponent::new(KeyConfig::default());
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
         component.headers = vec!["1", "2", "3"].iter().map(|h| h.to_string()).collect();
         component.rows = vec![
-            vec!["a", "b", "c"].iter().map(|h| h.to_string()).collect(),
-            vec!["d", "e", "f"].iter().map(|h| h.to_string()).collect(),
+            vec!["a", "b", "c"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["d", "e", "f"]
+                .iter()
+                .map(|h| Some(h.to_string()))
+                .collect(),
         ];
         component.selected_row.select(Some(0));
         component.selection_area_corner = Some((1, 1));
@@ -780,14 +2950,17 @@ mod test {
 
     #[test]
     fn test_calculate_cell_widths_when_sum_of_cell_widths_is_greater_than_table_width() {
-        let mut component = TableComponent::new(KeyConfig::default());
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
         component.headers = vec!["1", "2", "3"].iter().map(|h| h.to_string()).collect();
         component.rows = vec![
             vec!["aaaaa", "bbbbb", "ccccc"]
                 .iter()
-                .map(|h| h.to_string())
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["d", "e", "f"]
+                .iter()
+                .map(|h| Some(h.to_string()))
                 .collect(),
-            vec!["d", "e", "f"].iter().map(|h| h.to_string()).collect(),
         ];
         let (selected_column_index, headers, rows, constraints) =
             component.calculate_cell_widths(10);
@@ -806,14 +2979,17 @@ mod test {
 
     #[test]
     fn test_calculate_cell_widths_when_sum_of_cell_widths_is_less_than_table_width() {
-        let mut component = TableComponent::new(KeyConfig::default());
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
         component.headers = vec!["1", "2", "3"].iter().map(|h| h.to_string()).collect();
         component.rows = vec![
             vec!["aaaaa", "bbbbb", "ccccc"]
                 .iter()
-                .map(|h| h.to_string())
+                .map(|h| Some(h.to_string()))
+                .collect(),
+            vec!["d", "e", "f"]
+                .iter()
+                .map(|h| Some(h.to_string()))
                 .collect(),
-            vec!["d", "e", "f"].iter().map(|h| h.to_string()).collect(),
         ];
 
         let (selected_column_index, headers, rows, constraints) =
@@ -838,18 +3014,50 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_sample_indices() {
+        assert_eq!(sample_indices(3), vec![0, 1, 2]);
+        let indices = sample_indices(WIDTH_HEAD_ROWS + WIDTH_SAMPLE_ROWS + 10_000);
+        assert_eq!(indices.len(), WIDTH_HEAD_ROWS + WIDTH_SAMPLE_ROWS);
+        assert_eq!(indices[WIDTH_HEAD_ROWS - 1], WIDTH_HEAD_ROWS - 1);
+        assert!(indices[WIDTH_HEAD_ROWS..]
+            .iter()
+            .all(
+                |index| (WIDTH_HEAD_ROWS..WIDTH_HEAD_ROWS + WIDTH_SAMPLE_ROWS + 10_000)
+                    .contains(index)
+            ));
+    }
+
+    #[test]
+    fn test_recalculate_widths() {
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
+        component.update_rows(
+            vec![vec![Some("a".to_string())], vec![Some("b".to_string())]],
+            vec!["1".to_string()],
+        );
+        assert_eq!(component.column_width(0), 3);
+        component
+            .append_rows(vec![vec![Some("ccccc".to_string())]])
+            .unwrap();
+        assert_eq!(component.column_width(0), 5);
+        component.rows[0][0] = Some("dddddddddddddddddddddddd".to_string());
+        assert_eq!(component.column_width(0), 5);
+        component.recalculate_widths();
+        assert_eq!(component.column_width(0), 20);
+    }
+
     #[test]
     fn test_calculate_cell_widths_when_component_has_multiple_rows() {
-        let mut component = TableComponent::new(KeyConfig::default());
+        let mut component = TableComponent::new(KeyConfig::default(), TableConfig::default());
         component.headers = vec!["1", "2", "3"].iter().map(|h| h.to_string()).collect();
         component.rows = vec![
             vec!["aaaaa", "bbbbb", "ccccc"]
                 .iter()
-                .map(|h| h.to_string())
+                .map(|h| Some(h.to_string()))
                 .collect(),
             vec!["dddddddddd", "e", "f"]
                 .iter()
-                .map(|h| h.to_string())
+                .map(|h| Some(h.to_string()))
                 .collect(),
         ];
 
@@ -0,0 +1,134 @@
+use super::{Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::{Key, Mouse, MouseKind, WHEEL_LINES};
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+/// shows the statement creating the selected table
+pub struct TableDefinitionComponent {
+    definition: String,
+    scroll: u16,
+    area: Rect,
+    key_config: KeyConfig,
+}
+
+impl TableDefinitionComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            definition: String::new(),
+            scroll: 0,
+            area: Rect::default(),
+            key_config,
+        }
+    }
+
+    pub fn update(&mut self, definition: String) {
+        self.definition = definition;
+        self.scroll = 0;
+    }
+
+    pub fn definition(&self) -> Option<&str> {
+        if self.definition.is_empty() {
+            None
+        } else {
+            Some(self.definition.as_str())
+        }
+    }
+
+    fn line_count(&self) -> u16 {
+        self.definition.lines().count() as u16
+    }
+
+    fn scroll_down(&mut self, lines: u16) {
+        self.scroll = self
+            .scroll
+            .saturating_add(lines)
+            .min(self.line_count().saturating_sub(1));
+    }
+
+    fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+}
+
+impl DrawableComponent for TableDefinitionComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, area: Rect, focused: bool) -> Result<()> {
+        self.area = area;
+        f.render_widget(
+            Paragraph::new(self.definition.as_str())
+                .block(
+                    Block::default()
+                        .title(Msg::Definition.text())
+                        .borders(Borders::ALL),
+                )
+                .style(Theme::current().text(focused))
+                .scroll((self.scroll, 0)),
+            area,
+        );
+        Ok(())
+    }
+}
+
+impl Component for TableDefinitionComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if key == self.key_config.scroll_down {
+            self.scroll_down(1);
+        } else if key == self.key_config.scroll_up {
+            self.scroll_up(1);
+        } else if key == self.key_config.scroll_down_multiple_lines {
+            self.scroll_down(10);
+        } else if key == self.key_config.scroll_up_multiple_lines {
+            self.scroll_up(10);
+        } else if key == self.key_config.scroll_to_top {
+            self.scroll = 0;
+        } else if key == self.key_config.scroll_to_bottom {
+            self.scroll = self.line_count().saturating_sub(1);
+        } else {
+            return Ok(EventState::NotConsumed);
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn mouse_event(&mut self, mouse: Mouse) -> Result<EventState> {
+        if !mouse.is_in(self.area) {
+            return Ok(EventState::NotConsumed);
+        }
+        match mouse.kind {
+            MouseKind::ScrollDown => self.scroll_down(WHEEL_LINES as u16),
+            MouseKind::ScrollUp => self.scroll_up(WHEEL_LINES as u16),
+            MouseKind::Click => (),
+        }
+        Ok(EventState::Consumed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, KeyConfig, TableDefinitionComponent};
+
+    #[test]
+    fn test_scroll() {
+        let mut component = TableDefinitionComponent::new(KeyConfig::default());
+        assert_eq!(component.definition(), None);
+        component.update("CREATE TABLE a (\n  id int\n);".to_string());
+        component
+            .event(KeyConfig::default().scroll_down_multiple_lines)
+            .unwrap();
+        assert_eq!(component.scroll, 2);
+        component.event(KeyConfig::default().scroll_up).unwrap();
+        assert_eq!(component.scroll, 1);
+        component.update("CREATE TABLE b ();".to_string());
+        assert_eq!(component.scroll, 0);
+        assert_eq!(component.definition(), Some("CREATE TABLE b ();"));
+    }
+}
@@ -1,23 +1,53 @@
-use super::{compute_character_width, Component, DrawableComponent, EventState};
+use super::{
+    compute_character_width,
+    utils::completion::{common_prefix, CandidateKind},
+    utils::fuzzy::fuzzy_match,
+    utils::scroll_vertical::VerticalScroll,
+    Component, DrawableComponent, EventState,
+};
 use crate::components::command::CommandInfo;
+use crate::database::{functions, Engine};
 use crate::event::Key;
+use crate::i18n::Msg;
+use crate::metadata::MetadataCache;
+use crate::theme::Theme;
 use anyhow::Result;
-use database_tree::Table;
+use database_tree::{Database, Table};
 use tui::{
     backend::Backend,
     layout::Rect,
-    style::{Color, Style},
+    style::Style,
     text::{Span, Spans},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
     Frame,
 };
 use unicode_width::UnicodeWidthStr;
 
+/// the keywords of a condition, which are offered along with the columns
+const KEYWORDS: &[&str] = &[
+    "AND", "OR", "NOT", "IN", "IS", "NULL", "LIKE", "BETWEEN", "EXISTS", "TRUE", "FALSE",
+];
+/// the candidates listed at once, beyond which the list scrolls
+const VISIBLE_CANDIDATES: usize = 8;
+
+/// the candidates which Tab and Shift-Tab go through, replacing the word starting at `start` with the selected one
+struct Cycle {
+    start: usize,
+    candidates: Vec<(String, CandidateKind)>,
+    selected: usize,
+}
+
 pub struct TableFilterComponent {
     pub table: Option<Table>,
     pub input: Vec<char>,
     input_idx: usize,
     input_cursor_position: u16,
+    columns: Vec<String>,
+    /// the functions of the engine of the connection, which are offered along with the columns
+    functions: Vec<String>,
+    cycle: Option<Cycle>,
+    candidates_scroll: VerticalScroll,
+    pub metadata: MetadataCache,
 }
 
 impl Default for TableFilterComponent {
@@ -27,6 +57,11 @@ impl Default for TableFilterComponent {
             input: Vec::new(),
             input_idx: 0,
             input_cursor_position: 0,
+            columns: Vec::new(),
+            functions: Vec::new(),
+            cycle: None,
+            candidates_scroll: VerticalScroll::new(true, true),
+            metadata: MetadataCache::default(),
         }
     }
 }
@@ -36,11 +71,285 @@ impl TableFilterComponent {
         self.input.iter().collect()
     }
 
+    pub fn set_input(&mut self, input: &str) {
+        self.input = input.chars().collect();
+        self.input_idx = self.input.len();
+        self.input_cursor_position = input.width() as u16;
+        self.cycle = None;
+    }
+
+    pub fn set_columns(&mut self, columns: Vec<String>) {
+        self.columns = columns;
+    }
+
+    pub fn set_engine(&mut self, engine: Engine) {
+        self.functions = functions(engine);
+    }
+
     pub fn reset(&mut self) {
         self.table = None;
         self.input = Vec::new();
         self.input_idx = 0;
         self.input_cursor_position = 0;
+        self.columns = Vec::new();
+        self.cycle = None;
+    }
+
+    fn word_start(&self) -> usize {
+        self.input[..self.input_idx]
+            .iter()
+            .rposition(|c| !c.is_alphanumeric() && *c != '_')
+            .map_or(0, |i| i + 1)
+    }
+
+    /// returns the name before the dot which precedes the word before the cursor, such as `orders` in `orders.st`
+    fn qualifier(&self) -> Option<String> {
+        let start = self.word_start();
+        if start == 0 || self.input[start - 1] != '.' {
+            return None;
+        }
+        let qualifier = self.input[..start - 1]
+            .iter()
+            .rev()
+            .take_while(|c| c.is_alphanumeric() || **c == '_')
+            .collect::<Vec<&char>>();
+        if qualifier.is_empty() {
+            return None;
+        }
+        Some(qualifier.into_iter().rev().collect())
+    }
+
+    /// returns the table before the dot with its database when the cache does not know its columns yet
+    pub fn qualified_table(&self) -> Option<(Database, Table)> {
+        self.metadata.uncached_table(self.qualifier()?.as_str())
+    }
+
+    /// returns the names which the word before the cursor matches fuzzily, the best match first, which are the tables of a schema or the columns of a table after a dot and the column names, functions and keywords otherwise
+    fn candidates(&self) -> Vec<(String, CandidateKind)> {
+        let word = self.input[self.word_start()..self.input_idx]
+            .iter()
+            .collect::<String>()
+            .to_lowercase();
+        let names = match self.qualifier() {
+            Some(qualifier) => {
+                // the names after a schema are its tables, and those after a table are its columns
+                let kind = if self
+                    .metadata
+                    .has_table(Some(qualifier.as_str()), "")
+                    .is_some()
+                {
+                    CandidateKind::Table
+                } else {
+                    CandidateKind::Column
+                };
+                self.metadata
+                    .names(qualifier.as_str())
+                    .into_iter()
+                    .map(|name| (name.clone(), kind))
+                    .collect::<Vec<(String, CandidateKind)>>()
+            }
+            None if word.is_empty() => return vec![],
+            None => self
+                .columns
+                .iter()
+                .map(|name| (name.clone(), CandidateKind::Column))
+                .chain(
+                    self.functions
+                        .iter()
+                        .map(|name| (name.clone(), CandidateKind::Function)),
+                )
+                .chain(
+                    KEYWORDS
+                        .iter()
+                        .map(|name| (name.to_string(), CandidateKind::Keyword)),
+                )
+                .collect(),
+        };
+        // the names after a dot keep their order until a word is typed
+        if word.is_empty() {
+            return names;
+        }
+        let mut matches = names
+            .into_iter()
+            .filter_map(|(name, kind)| {
+                fuzzy_match(word.as_str(), name.as_str()).map(|(score, _)| (score, name, kind))
+            })
+            .collect::<Vec<(i64, String, CandidateKind)>>();
+        // the shorter of two names matched as well is closer to the word
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.len().cmp(&b.1.len())));
+        matches
+            .into_iter()
+            .map(|(_, name, kind)| (name, kind))
+            .collect()
+    }
+
+    fn replace_word(&mut self, start: usize, text: &[char]) {
+        self.input
+            .splice(start..self.input_idx, text.iter().cloned());
+        self.input_idx = start + text.len();
+        self.input_cursor_position = self.input[..self.input_idx]
+            .iter()
+            .collect::<String>()
+            .width() as u16;
+    }
+
+    /// replaces the word before the cursor with the only candidate or the longest prefix shared by the candidates, or
+    /// with the best of them when that prefix does not lengthen the word
+    fn complete(&mut self) {
+        let candidates = self.candidates();
+        let completion = match common_prefix(
+            &candidates
+                .iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<&String>>(),
+        ) {
+            Some(completion) => completion,
+            None => return,
+        };
+        let start = self.word_start();
+        let word = self.input[start..self.input_idx]
+            .iter()
+            .collect::<String>()
+            .to_lowercase();
+        let lengthens = completion.len() > word.chars().count()
+            && completion
+                .iter()
+                .collect::<String>()
+                .to_lowercase()
+                .starts_with(word.as_str());
+        if candidates.len() > 1 && !lengthens {
+            self.start_cycle(candidates, 0);
+            return;
+        }
+        self.replace_word(start, &completion);
+    }
+
+    fn start_cycle(&mut self, candidates: Vec<(String, CandidateKind)>, selected: usize) {
+        let start = self.word_start();
+        let name = candidates[selected].0.chars().collect::<Vec<char>>();
+        self.replace_word(start, &name);
+        self.cycle = Some(Cycle {
+            start,
+            candidates,
+            selected,
+        });
+    }
+
+    /// replaces the candidate in the input with the next one, or the previous one when not `forward`, starting with
+    /// the last one when Shift-Tab is pressed first
+    fn cycle(&mut self, forward: bool) {
+        let (start, name) = match self.cycle.as_mut() {
+            Some(cycle) => {
+                let len = cycle.candidates.len();
+                cycle.selected = if forward {
+                    (cycle.selected + 1) % len
+                } else {
+                    (cycle.selected + len - 1) % len
+                };
+                (
+                    cycle.start,
+                    cycle.candidates[cycle.selected]
+                        .0
+                        .chars()
+                        .collect::<Vec<char>>(),
+                )
+            }
+            None => {
+                let candidates = self.candidates();
+                match (forward, candidates.len()) {
+                    (_, 0) => (),
+                    (true, _) => self.complete(),
+                    (false, len) => self.start_cycle(candidates, len - 1),
+                }
+                return;
+            }
+        };
+        self.replace_word(start, &name);
+    }
+
+    /// draws the candidates with their kinds under the word they complete, or above it near the bottom of the frame,
+    /// over the records
+    pub fn draw_candidates<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let (candidates, selected) = match &self.cycle {
+            Some(cycle) => (cycle.candidates.clone(), Some(cycle.selected)),
+            None => (self.candidates(), None),
+        };
+        if candidates.len() < 2 {
+            return;
+        }
+        let start = self
+            .cycle
+            .as_ref()
+            .map_or(self.word_start(), |cycle| cycle.start);
+        let x = area.x
+            + (1 + self.table.as_ref().map_or(0, |table| table.name.width())
+                + 1
+                + self.input[..start].iter().collect::<String>().width()) as u16;
+        let name_width = candidates
+            .iter()
+            .map(|(name, _)| name.width())
+            .max()
+            .unwrap_or_default();
+        let kind_width = candidates
+            .iter()
+            .map(|(_, kind)| kind.label().width())
+            .max()
+            .unwrap_or_default();
+        let popup = candidates_area(
+            x,
+            area.y + 1,
+            (name_width + 1 + kind_width + 2) as u16,
+            (candidates.len().min(VISIBLE_CANDIDATES) + 2) as u16,
+            f.size(),
+        );
+        let visible = popup.height.saturating_sub(2) as usize;
+        if selected.is_none() {
+            self.candidates_scroll.reset();
+        }
+        let top =
+            self.candidates_scroll
+                .update(selected.unwrap_or_default(), candidates.len(), visible);
+        let items = candidates
+            .iter()
+            .enumerate()
+            .skip(top)
+            .take(visible)
+            .map(|(i, (name, kind))| {
+                let padding = " ".repeat(name_width - name.width() + 1);
+                ListItem::new(Spans::from(vec![
+                    Span::raw(format!("{}{}", name, padding)),
+                    Span::styled(kind.label(), Theme::current().hint()),
+                ]))
+                .style(if selected == Some(i) {
+                    Theme::current().selected()
+                } else {
+                    Style::default()
+                })
+            })
+            .collect::<Vec<ListItem>>();
+        f.render_widget(Clear, popup);
+        f.render_widget(
+            List::new(items).block(Block::default().borders(Borders::ALL)),
+            popup,
+        );
+        if candidates.len() > visible {
+            self.candidates_scroll.draw(f, popup);
+        }
+    }
+}
+
+/// places a list of `width` and `height` under the cursor at `x` and `y`, or above it when there is more room there,
+/// moving it left and shrinking it to keep it within `frame`
+fn candidates_area(x: u16, y: u16, width: u16, height: u16, frame: Rect) -> Rect {
+    let width = width.min(frame.width);
+    let x = x.min(frame.right().saturating_sub(width)).max(frame.x);
+    let below = frame.bottom().saturating_sub(y + 1);
+    let above = y.saturating_sub(frame.y);
+    if height <= below || below >= above {
+        Rect::new(x, y + 1, width, height.min(below))
+    } else {
+        let height = height.min(above);
+        Rect::new(x, y - height, width, height)
     }
 }
 
@@ -51,22 +360,18 @@ impl DrawableComponent for TableFilterComponent {
                 self.table
                     .as_ref()
                     .map_or("-".to_string(), |table| table.name.to_string()),
-                Style::default().fg(Color::Blue),
+                Theme::current().accent(),
             ),
             Span::from(format!(
                 " {}",
                 if focused || !self.input.is_empty() {
                     self.input.iter().collect::<String>()
                 } else {
-                    "Enter a SQL expression in WHERE clause to filter records".to_string()
+                    Msg::FilterRecordsPlaceholder.text().to_string()
                 }
             )),
         ]))
-        .style(if focused {
-            Style::default()
-        } else {
-            Style::default().fg(Color::DarkGray)
-        })
+        .style(Theme::current().text(focused))
         .block(Block::default().borders(Borders::ALL));
         f.render_widget(query, area);
         if focused {
@@ -91,7 +396,18 @@ impl Component for TableFilterComponent {
 
     fn event(&mut self, key: Key) -> Result<EventState> {
         let input_str: String = self.input.iter().collect();
+        if !matches!(key, Key::Tab | Key::BackTab) {
+            self.cycle = None;
+        }
         match key {
+            Key::Tab => {
+                self.cycle(true);
+                return Ok(EventState::Consumed);
+            }
+            Key::BackTab => {
+                self.cycle(false);
+                return Ok(EventState::Consumed);
+            }
             Key::Char(c) => {
                 self.input.insert(self.input_idx, c);
                 self.input_idx += 1;
@@ -143,3 +459,185 @@ impl Component for TableFilterComponent {
         Ok(EventState::NotConsumed)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{candidates_area, CandidateKind, Component as _, TableFilterComponent};
+    use crate::database::Engine;
+    use crate::event::Key;
+    use database_tree::{Child, Database, Schema, Table};
+    use tui::layout::Rect;
+
+    #[test]
+    fn test_complete() {
+        let mut filter = TableFilterComponent::default();
+        filter.set_columns(vec![
+            "user_id".to_string(),
+            "user_name".to_string(),
+            "email".to_string(),
+        ]);
+        filter.set_input("EM");
+        filter.complete();
+        assert_eq!(filter.input_str(), "email");
+        assert_eq!(filter.input_cursor_position, 5);
+
+        filter.set_input("email = 'a' and us");
+        assert_eq!(filter.candidates().len(), 2);
+        filter.complete();
+        assert_eq!(filter.input_str(), "email = 'a' and user_");
+
+        filter.set_input("z");
+        filter.complete();
+        assert_eq!(filter.input_str(), "z");
+    }
+
+    #[test]
+    fn test_candidates_area() {
+        let frame = Rect::new(0, 0, 80, 24);
+        assert_eq!(
+            candidates_area(10, 5, 20, 7, frame),
+            Rect::new(10, 6, 20, 7)
+        );
+        // moved left at the right edge
+        assert_eq!(
+            candidates_area(70, 5, 20, 7, frame),
+            Rect::new(60, 6, 20, 7)
+        );
+        // flipped above the cursor near the bottom
+        assert_eq!(
+            candidates_area(10, 20, 20, 7, frame),
+            Rect::new(10, 13, 20, 7)
+        );
+        // shrunk to the larger side when neither has room
+        assert_eq!(
+            candidates_area(10, 2, 100, 7, Rect::new(0, 0, 80, 6)),
+            Rect::new(0, 3, 80, 3)
+        );
+    }
+
+    #[test]
+    fn test_complete_fuzzy() {
+        let mut filter = TableFilterComponent::default();
+        filter.set_columns(vec![
+            "user_id".to_string(),
+            "id".to_string(),
+            "created_at".to_string(),
+        ]);
+        filter.set_input("usrid");
+        filter.complete();
+        assert_eq!(filter.input_str(), "user_id");
+
+        // the column named by the word comes before the one ending with it
+        filter.set_input("ID");
+        assert_eq!(
+            filter
+                .candidates()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<String>>(),
+            vec!["id", "user_id"]
+        );
+        filter.event(Key::Tab).unwrap();
+        assert_eq!(filter.input_str(), "id");
+        filter.event(Key::Tab).unwrap();
+        assert_eq!(filter.input_str(), "user_id");
+
+        filter.set_input("id > 1 AND cat");
+        filter.complete();
+        assert_eq!(filter.input_str(), "id > 1 AND created_at");
+    }
+
+    #[test]
+    fn test_complete_qualified() {
+        let table = |name: &str| Table {
+            name: name.to_string(),
+            create_time: None,
+            update_time: None,
+            engine: None,
+            schema: Some("public".to_string()),
+            view: false,
+        };
+        let mut filter = TableFilterComponent::default();
+        filter.metadata.add_database(&Database::new(
+            "shop".to_string(),
+            vec![Child::Schema(Schema {
+                name: "public".to_string(),
+                tables: vec![table("orders"), table("order_items")],
+            })],
+        ));
+        filter.set_input("id IN (SELECT id FROM public.");
+        assert_eq!(filter.candidates().len(), 2);
+        filter.complete();
+        assert_eq!(filter.input_str(), "id IN (SELECT id FROM public.order");
+
+        filter.set_input("orders.st");
+        assert_eq!(filter.qualified_table().unwrap().1, table("orders"));
+        filter.complete();
+        assert_eq!(filter.input_str(), "orders.st");
+
+        filter.metadata.set_columns(
+            &table("orders"),
+            vec!["id".to_string(), "status".to_string()],
+        );
+        assert_eq!(filter.qualified_table(), None);
+        filter.complete();
+        assert_eq!(filter.input_str(), "orders.status");
+
+        filter.set_input("orders.");
+        assert_eq!(
+            filter.candidates(),
+            vec![
+                ("id".to_string(), CandidateKind::Column),
+                ("status".to_string(), CandidateKind::Column)
+            ]
+        );
+        filter.set_input("public.");
+        assert_eq!(filter.candidates()[0].1, CandidateKind::Table);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let mut filter = TableFilterComponent::default();
+        filter.set_columns(vec!["user_id".to_string(), "user_name".to_string()]);
+        filter.set_input("us");
+        filter.event(Key::Tab).unwrap();
+        assert_eq!(filter.input_str(), "user_");
+        filter.event(Key::Tab).unwrap();
+        assert_eq!(filter.input_str(), "user_id");
+        filter.event(Key::Tab).unwrap();
+        assert_eq!(filter.input_str(), "user_name");
+        filter.event(Key::Tab).unwrap();
+        assert_eq!(filter.input_str(), "user_id");
+        filter.event(Key::BackTab).unwrap();
+        assert_eq!(filter.input_str(), "user_name");
+        assert_eq!(filter.input_cursor_position, 9);
+
+        // typing accepts the candidate
+        filter.event(Key::Char(' ')).unwrap();
+        filter.event(Key::Char('i')).unwrap();
+        filter.event(Key::BackTab).unwrap();
+        assert_eq!(filter.input_str(), "user_name EXISTS");
+        filter.event(Key::BackTab).unwrap();
+        assert_eq!(filter.input_str(), "user_name LIKE");
+        assert_eq!(filter.candidates().len(), 1);
+    }
+
+    #[test]
+    fn test_complete_functions() {
+        let mut filter = TableFilterComponent::default();
+        filter.set_columns(vec!["string_id".to_string()]);
+        filter.set_engine(Engine::Postgres);
+        filter.set_input("length(name) > 3 AND group_c");
+        filter.complete();
+        assert_eq!(filter.input_str(), "length(name) > 3 AND group_c");
+
+        filter.set_input("string_a");
+        filter.complete();
+        assert_eq!(filter.input_str(), "STRING_AGG");
+
+        filter.set_engine(Engine::Sqlite);
+        filter.set_input("group_c");
+        filter.complete();
+        assert_eq!(filter.input_str(), "GROUP_CONCAT");
+    }
+}
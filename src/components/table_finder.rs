@@ -0,0 +1,249 @@
+use super::{popup_area, utils::fuzzy::fuzzy_match, Component, DrawableComponent, EventState};
+use crate::components::command::CommandInfo;
+use crate::config::KeyConfig;
+use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
+use anyhow::Result;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// a table with the database and the schema which contain it
+pub type TablePath = (String, Option<String>, String);
+
+fn path_text((database, schema, table): &TablePath) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}.{}", database, schema, table),
+        None => format!("{}.{}", database, table),
+    }
+}
+
+/// searches the tables of every database of the connection by a fuzzy pattern to open one of them
+pub struct TableFinderComponent {
+    tables: Vec<TablePath>,
+    input: String,
+    /// the indices of the matching tables, best first, with the indices of the matched characters
+    matches: Vec<(usize, Vec<usize>)>,
+    state: ListState,
+    chosen: Option<TablePath>,
+    visible: bool,
+    key_config: KeyConfig,
+}
+
+impl TableFinderComponent {
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            tables: vec![],
+            input: String::new(),
+            matches: vec![],
+            state: ListState::default(),
+            chosen: None,
+            visible: false,
+            key_config,
+        }
+    }
+
+    pub fn open(&mut self, tables: Vec<TablePath>) -> Result<()> {
+        self.tables = tables;
+        self.input.clear();
+        self.chosen = None;
+        self.update_matches();
+        self.show()
+    }
+
+    /// returns the table the user chose
+    pub fn take_chosen(&mut self) -> Option<TablePath> {
+        self.chosen.take()
+    }
+
+    fn update_matches(&mut self) {
+        let mut matches = self
+            .tables
+            .iter()
+            .enumerate()
+            .filter_map(|(index, path)| {
+                fuzzy_match(self.input.as_str(), path_text(path).as_str())
+                    .map(|(score, indices)| (score, index, indices))
+            })
+            .collect::<Vec<_>>();
+        // keeps the order of the tree among the tables which match as well
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        self.matches = matches
+            .into_iter()
+            .map(|(_, index, indices)| (index, indices))
+            .collect();
+        self.state.select(if self.matches.is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+    }
+
+    fn select_next(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some((i + 1) % self.matches.len()));
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.state
+                .select(Some((i + self.matches.len() - 1) % self.matches.len()));
+        }
+    }
+}
+
+impl DrawableComponent for TableFinderComponent {
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>, _area: Rect, _focused: bool) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let area = popup_area(f, 70, 20);
+        f.render_widget(
+            Block::default()
+                .title(format!(
+                    "{} ({}/{})",
+                    Msg::FindTable.text(),
+                    self.matches.len(),
+                    self.tables.len()
+                ))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick),
+            area,
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Length(1), Constraint::Min(1)])
+            .split(area);
+        let input = if self.input.is_empty() {
+            Span::styled(Msg::FindTablePlaceholder.text(), Theme::current().hint())
+        } else {
+            Span::raw(self.input.clone())
+        };
+        f.render_widget(Paragraph::new(Spans::from(input)), chunks[0]);
+        let items = self
+            .matches
+            .iter()
+            .map(|(index, indices)| {
+                let spans = path_text(&self.tables[*index])
+                    .chars()
+                    .enumerate()
+                    .map(|(i, c)| {
+                        if indices.contains(&i) {
+                            Span::styled(c.to_string(), Theme::current().matched())
+                        } else {
+                            Span::raw(c.to_string())
+                        }
+                    })
+                    .collect::<Vec<Span>>();
+                ListItem::new(Spans::from(spans))
+            })
+            .collect::<Vec<ListItem>>();
+        f.render_stateful_widget(
+            List::new(items).highlight_style(Theme::current().selected()),
+            chunks[1],
+            &mut self.state,
+        );
+        f.set_cursor(chunks[0].x + self.input.width() as u16, chunks[0].y);
+        Ok(())
+    }
+}
+
+impl Component for TableFinderComponent {
+    fn commands(&self, _out: &mut Vec<CommandInfo>) {}
+
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if !self.visible {
+            return Ok(EventState::NotConsumed);
+        }
+        if key == self.key_config.exit_popup {
+            self.hide();
+        } else if key == self.key_config.enter {
+            self.chosen = self
+                .state
+                .selected()
+                .and_then(|i| self.matches.get(i))
+                .map(|(index, _)| self.tables[*index].clone());
+            self.hide();
+        } else {
+            match key {
+                Key::Down => self.select_next(),
+                Key::Up => self.select_previous(),
+                Key::Char(c) => {
+                    self.input.push(c);
+                    self.update_matches();
+                }
+                Key::Backspace | Key::Delete => {
+                    self.input.pop();
+                    self.update_matches();
+                }
+                _ => (),
+            }
+        }
+        Ok(EventState::Consumed)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Component as _, Key, KeyConfig, TableFinderComponent};
+
+    #[test]
+    fn test_find_table() {
+        let mut component = TableFinderComponent::new(KeyConfig::default());
+        component
+            .open(vec![
+                ("shop".to_string(), None, "audit_log".to_string()),
+                ("shop".to_string(), None, "user_items".to_string()),
+                (
+                    "app".to_string(),
+                    Some("public".to_string()),
+                    "users".to_string(),
+                ),
+            ])
+            .unwrap();
+        assert_eq!(component.matches.len(), 3);
+
+        for c in "usit".chars() {
+            component.event(Key::Char(c)).unwrap();
+        }
+        assert_eq!(component.matches.len(), 1);
+        assert_eq!(component.matches[0], (1, vec![5, 6, 10, 11]));
+
+        component.event(Key::Backspace).unwrap();
+        component.event(Key::Backspace).unwrap();
+        assert_eq!(component.matches.len(), 2);
+        component.event(Key::Down).unwrap();
+        component.event(KeyConfig::default().enter).unwrap();
+        assert!(!component.visible);
+        assert_eq!(
+            component.take_chosen(),
+            Some((
+                "app".to_string(),
+                Some("public".to_string()),
+                "users".to_string()
+            ))
+        );
+    }
+}
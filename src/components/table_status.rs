@@ -1,12 +1,14 @@
-use super::{Component, DrawableComponent, EventState};
+use super::{table_value::separate_thousands, Component, DrawableComponent, EventState};
 use crate::components::command::CommandInfo;
+use crate::database::TableStatus;
 use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
 use anyhow::Result;
 use database_tree::Table;
 use tui::{
     backend::Backend,
     layout::Rect,
-    style::{Color, Style},
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
     Frame,
@@ -16,6 +18,8 @@ pub struct TableStatusComponent {
     column_count: Option<usize>,
     row_count: Option<usize>,
     table: Option<Table>,
+    /// the size of the table, which is fetched after its records
+    status: Option<TableStatus>,
 }
 
 impl Default for TableStatusComponent {
@@ -24,6 +28,7 @@ impl Default for TableStatusComponent {
             row_count: None,
             column_count: None,
             table: None,
+            status: None,
         }
     }
 }
@@ -33,38 +38,99 @@ impl TableStatusComponent {
         row_count: Option<usize>,
         column_count: Option<usize>,
         table: Option<Table>,
+        status: Option<TableStatus>,
     ) -> Self {
         Self {
             row_count,
             column_count,
             table,
+            status,
         }
     }
+
+    /// returns the fetched rows, followed by how many rows the table has when it is known, such as `100 / ~12,345`
+    fn rows(&self) -> String {
+        let fetched = self.row_count.map_or("-".to_string(), |c| c.to_string());
+        match self.status.as_ref().and_then(|status| {
+            status
+                .rows
+                .map(|rows| (rows, if status.estimated { "~" } else { "" }))
+        }) {
+            Some((rows, estimated)) => format!(
+                "{} / {}{}",
+                fetched,
+                estimated,
+                separate_thousands(rows as usize)
+            ),
+            None => fetched,
+        }
+    }
+}
+
+/// returns `bytes` in the largest binary unit which leaves at least one of it, such as `1.5 MiB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
 }
 
 impl DrawableComponent for TableStatusComponent {
     fn draw<B: Backend>(&mut self, f: &mut Frame<B>, area: Rect, focused: bool) -> Result<()> {
-        let status = Paragraph::new(Spans::from(vec![
+        let mut spans = vec![
+            Span::from(format!("{}: {}, ", Msg::Rows.text(), self.rows())),
             Span::from(format!(
-                "rows: {}, ",
-                self.row_count.map_or("-".to_string(), |c| c.to_string())
-            )),
-            Span::from(format!(
-                "columns: {}, ",
+                "{}: {}, ",
+                Msg::ColumnCount.text(),
                 self.column_count.map_or("-".to_string(), |c| c.to_string())
             )),
-            Span::from(format!(
-                "engine: {}",
-                self.table.as_ref().map_or("-".to_string(), |c| {
-                    c.engine.as_ref().map_or("-".to_string(), |e| e.to_string())
-                })
-            )),
-        ]))
-        .block(Block::default().borders(Borders::TOP).style(if focused {
-            Style::default()
-        } else {
-            Style::default().fg(Color::DarkGray)
-        }));
+        ];
+        if let Some(status) = self.status.as_ref() {
+            for (label, size) in [
+                (Msg::DataSize, status.data_size),
+                (Msg::IndexSize, status.index_size),
+            ] {
+                if let Some(size) = size {
+                    spans.push(Span::from(format!(
+                        "{}: {}, ",
+                        label.text(),
+                        format_size(size)
+                    )));
+                }
+            }
+        }
+        spans.push(Span::from(format!(
+            "{}: {}",
+            Msg::Engine.text(),
+            self.status
+                .as_ref()
+                .and_then(|status| status.engine.clone())
+                .or_else(|| self.table.as_ref().and_then(|c| c.engine.clone()))
+                .unwrap_or_else(|| "-".to_string())
+        )));
+        if let Some(collation) = self
+            .status
+            .as_ref()
+            .and_then(|status| status.collation.as_ref())
+        {
+            spans.push(Span::from(format!(
+                ", {}: {}",
+                Msg::Collation.text(),
+                collation
+            )));
+        }
+        let status = Paragraph::new(Spans::from(spans)).block(
+            Block::default()
+                .borders(Borders::TOP)
+                .style(Theme::current().text(focused)),
+        );
         f.render_widget(status, area);
         Ok(())
     }
@@ -77,3 +143,33 @@ impl Component for TableStatusComponent {
         Ok(EventState::NotConsumed)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{format_size, TableStatus, TableStatusComponent};
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024 * 1024), "5.0 GiB");
+        assert_eq!(format_size(u64::MAX), "16777216.0 TiB");
+    }
+
+    #[test]
+    fn test_rows() {
+        let mut component = TableStatusComponent::new(Some(100), Some(3), None, None);
+        assert_eq!(component.rows(), "100");
+        component.status = Some(TableStatus {
+            rows: Some(12345),
+            estimated: true,
+            ..TableStatus::default()
+        });
+        assert_eq!(component.rows(), "100 / ~12,345");
+        component.status = Some(TableStatus {
+            rows: Some(100),
+            ..TableStatus::default()
+        });
+        assert_eq!(component.rows(), "100 / 100");
+    }
+}
@@ -1,35 +1,157 @@
 use super::{Component, DrawableComponent, EventState};
 use crate::components::command::CommandInfo;
+use crate::components::utils::invisible::reveal_invisible;
+use crate::config::KeyConfig;
 use crate::event::Key;
+use crate::i18n::Msg;
+use crate::theme::Theme;
 use anyhow::Result;
 use tui::{
     backend::Backend,
-    layout::Rect,
-    style::{Color, Style},
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
+
+const SCROLL_COLUMNS: u16 = 10;
 
 pub struct TableValueComponent {
+    location: String,
     value: String,
+    /// whether the table shows only the start of the value
+    truncated: bool,
+    reveal_invisible: bool,
+    scroll: u16,
+    key_config: KeyConfig,
 }
 
 impl TableValueComponent {
-    pub fn new(value: String) -> Self {
-        Self { value }
+    pub fn new(key_config: KeyConfig) -> Self {
+        Self {
+            location: String::new(),
+            value: String::new(),
+            truncated: false,
+            reveal_invisible: false,
+            scroll: 0,
+            key_config,
+        }
+    }
+
+    /// shows the value of the selected cell with where it is, scrolling back to its start when another cell is selected
+    pub fn update(&mut self, location: String, value: String, truncated: bool) {
+        if location != self.location || value != self.value {
+            self.scroll = 0;
+        }
+        self.location = location;
+        self.value = value;
+        self.truncated = truncated;
+    }
+
+    /// returns how many characters and bytes the value has when the table cuts it short, such as `4,096 chars · 4,102 bytes`
+    fn length(&self) -> Option<String> {
+        if !self.truncated {
+            return None;
+        }
+        Some(format!(
+            "{} {} · {} {}",
+            separate_thousands(self.value.chars().count()),
+            Msg::Characters.text(),
+            separate_thousands(self.value.len()),
+            Msg::Bytes.text()
+        ))
+    }
+
+    pub fn set_reveal_invisible(&mut self, reveal_invisible: bool) {
+        self.reveal_invisible = reveal_invisible;
+    }
+
+    /// returns the value on a single line, marking where its line breaks were
+    fn flat_value(&self) -> String {
+        if self.reveal_invisible {
+            reveal_invisible(self.value.as_str()).replace('\n', "↵")
+        } else {
+            self.value.replace('\n', "↵")
+        }
+    }
+
+    fn scroll_right(&mut self) {
+        self.scroll = self
+            .scroll
+            .saturating_add(SCROLL_COLUMNS)
+            .min(self.flat_value().width().saturating_sub(1) as u16);
+    }
+
+    fn scroll_left(&mut self) {
+        self.scroll = self.scroll.saturating_sub(SCROLL_COLUMNS);
     }
 }
 
+/// formats a number with commas between groups of three digits
+pub fn separate_thousands(number: usize) -> String {
+    let digits = number.to_string();
+    let mut separated = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            separated.push(',');
+        }
+        separated.push(c);
+    }
+    separated
+}
+
 impl DrawableComponent for TableValueComponent {
     fn draw<B: Backend>(&mut self, f: &mut Frame<B>, area: Rect, focused: bool) -> Result<()> {
-        let paragraph = Paragraph::new(self.value.clone())
-            .block(Block::default().borders(Borders::BOTTOM))
-            .style(if focused {
-                Style::default()
-            } else {
-                Style::default().fg(Color::DarkGray)
-            });
-        f.render_widget(paragraph, area);
+        let style = Theme::current().text(focused);
+        let block = Block::default().borders(Borders::BOTTOM).style(style);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let length = self.length().unwrap_or_default();
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                [
+                    Constraint::Length(if self.location.is_empty() {
+                        0
+                    } else {
+                        self.location.width() as u16 + 2
+                    }),
+                    Constraint::Min(1),
+                    Constraint::Length(if length.is_empty() {
+                        0
+                    } else {
+                        length.width() as u16 + 1
+                    }),
+                ]
+                .as_ref(),
+            )
+            .split(inner);
+        f.render_widget(
+            Paragraph::new(Spans::from(Span::styled(
+                self.location.as_str(),
+                if focused {
+                    Theme::current().accent()
+                } else {
+                    style
+                },
+            ))),
+            chunks[0],
+        );
+        f.render_widget(
+            Paragraph::new(self.flat_value())
+                .style(style)
+                .scroll((0, self.scroll)),
+            chunks[1],
+        );
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                format!(" {}", length),
+                Theme::current().hint(),
+            )),
+            chunks[2],
+        );
         Ok(())
     }
 }
@@ -37,7 +159,62 @@ impl DrawableComponent for TableValueComponent {
 impl Component for TableValueComponent {
     fn commands(&self, _out: &mut Vec<CommandInfo>) {}
 
-    fn event(&mut self, _key: Key) -> Result<EventState> {
-        todo!("scroll");
+    fn event(&mut self, key: Key) -> Result<EventState> {
+        if key == self.key_config.scroll_value_right {
+            self.scroll_right();
+            return Ok(EventState::Consumed);
+        } else if key == self.key_config.scroll_value_left {
+            self.scroll_left();
+            return Ok(EventState::Consumed);
+        }
+        Ok(EventState::NotConsumed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{separate_thousands, Component as _, KeyConfig, TableValueComponent};
+
+    #[test]
+    fn test_separate_thousands() {
+        assert_eq!(separate_thousands(0), "0");
+        assert_eq!(separate_thousands(999), "999");
+        assert_eq!(separate_thousands(1204), "1,204");
+        assert_eq!(separate_thousands(53001), "53,001");
+        assert_eq!(separate_thousands(1234567), "1,234,567");
+    }
+
+    #[test]
+    fn test_scroll() {
+        let mut component = TableValueComponent::new(KeyConfig::default());
+        component.update("t.c".to_string(), "a".repeat(15), false);
+        component
+            .event(KeyConfig::default().scroll_value_right)
+            .unwrap();
+        assert_eq!(component.scroll, 10);
+        component
+            .event(KeyConfig::default().scroll_value_right)
+            .unwrap();
+        assert_eq!(component.scroll, 14);
+        component.update("t.c".to_string(), "a".repeat(15), false);
+        assert_eq!(component.scroll, 14);
+        component
+            .event(KeyConfig::default().scroll_value_left)
+            .unwrap();
+        assert_eq!(component.scroll, 4);
+        component.update("t.d".to_string(), "a".repeat(15), false);
+        assert_eq!(component.scroll, 0);
+    }
+
+    #[test]
+    fn test_length() {
+        let mut component = TableValueComponent::new(KeyConfig::default());
+        component.update("t.c".to_string(), "あ".repeat(1500), false);
+        assert_eq!(component.length(), None);
+        component.update("t.c".to_string(), "あ".repeat(1500), true);
+        assert_eq!(
+            component.length(),
+            Some("1,500 chars · 4,500 bytes".to_string())
+        );
     }
 }
@@ -0,0 +1,57 @@
+use crate::database::binary_length;
+
+/// returns `<BLOB n bytes>` for a binary value, which is drawn in its place
+pub fn binary_placeholder(value: &str) -> Option<String> {
+    binary_length(value).map(|length| format!("<BLOB {} bytes>", length))
+}
+
+/// lays out `bytes` 16 to a line with the offset, the bytes in hex and the printable ones as ASCII
+pub fn hex_dump(bytes: &[u8]) -> String {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(line, chunk)| {
+            let hex = chunk
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<Vec<String>>()
+                .join(" ");
+            let ascii = chunk
+                .iter()
+                .map(|byte| {
+                    if byte.is_ascii_graphic() || *byte == b' ' {
+                        *byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>();
+            format!("{:08x}  {:<47}  {}", line * 16, hex, ascii)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{binary_placeholder, hex_dump};
+
+    #[test]
+    fn test_binary_placeholder() {
+        assert_eq!(
+            binary_placeholder("\\x00ff41"),
+            Some("<BLOB 3 bytes>".to_string())
+        );
+        assert_eq!(binary_placeholder("text"), None);
+    }
+
+    #[test]
+    fn test_hex_dump() {
+        assert_eq!(
+            hex_dump(b"Hello, world!\n\x00\xffAB"),
+            "00000000  48 65 6c 6c 6f 2c 20 77 6f 72 6c 64 21 0a 00 ff  Hello, world!...\n\
+             00000010  41 42                                            AB"
+        );
+        assert_eq!(hex_dump(b""), "");
+    }
+}
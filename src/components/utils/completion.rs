@@ -0,0 +1,61 @@
+/// what a candidate of a completion names, which is shown next to it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CandidateKind {
+    Keyword,
+    Table,
+    Column,
+    Function,
+}
+
+impl CandidateKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Keyword => "keyword",
+            Self::Table => "table",
+            Self::Column => "column",
+            Self::Function => "function",
+        }
+    }
+}
+
+/// returns the longest prefix which the candidates share, ignoring the case of ASCII letters and keeping that of the first candidate
+pub fn common_prefix(candidates: &[&String]) -> Option<Vec<char>> {
+    let (first, rest) = candidates.split_first()?;
+    Some(
+        first
+            .chars()
+            .enumerate()
+            .take_while(|(i, c)| {
+                rest.iter().all(|candidate| {
+                    candidate
+                        .chars()
+                        .nth(*i)
+                        .map(|other| other.to_ascii_lowercase())
+                        == Some(c.to_ascii_lowercase())
+                })
+            })
+            .map(|(_, c)| c)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::common_prefix;
+
+    #[test]
+    fn test_common_prefix() {
+        let (orders, order_items, users) = (
+            "orders".to_string(),
+            "Order_items".to_string(),
+            "users".to_string(),
+        );
+        assert_eq!(
+            common_prefix(&[&orders, &order_items]),
+            Some("order".chars().collect())
+        );
+        assert_eq!(common_prefix(&[&users]), Some("users".chars().collect()));
+        assert_eq!(common_prefix(&[&orders, &users]), Some(vec![]));
+        assert_eq!(common_prefix(&[]), None);
+    }
+}
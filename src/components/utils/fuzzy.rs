@@ -0,0 +1,59 @@
+/// matches the characters of `pattern` in order anywhere in `text`, ignoring case, and returns a score which is higher
+/// for consecutive characters and those starting a word, with the indices of the matched characters
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let lower = |c: char| c.to_lowercase().collect::<String>();
+    let text = text.chars().map(lower).collect::<Vec<String>>();
+    let pattern = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(lower)
+        .collect::<Vec<String>>();
+    // finds where the first match ends, then the latest start before it, which makes the match the shortest
+    let mut end = 0;
+    for p in &pattern {
+        end = (end..text.len()).find(|i| text[*i] == *p)? + 1;
+    }
+    let mut indices = vec![];
+    let mut start = end;
+    for p in pattern.iter().rev() {
+        start = (0..start).rev().find(|i| text[*i] == *p)?;
+        indices.push(start);
+    }
+    indices.reverse();
+
+    let mut score = -(text.len() as i64 / 8);
+    for (n, index) in indices.iter().enumerate() {
+        score += 1;
+        if *index == 0 || matches!(text[index - 1].as_str(), "." | "_" | "-" | " ") {
+            score += 6;
+        }
+        if n > 0 {
+            let gap = index - indices[n - 1] - 1;
+            score += if gap == 0 { 8 } else { -(gap as i64) };
+        }
+    }
+    Some((score, indices))
+}
+
+#[cfg(test)]
+mod test {
+    use super::fuzzy_match;
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(
+            fuzzy_match("ordit", "shop.order_items").map(|(_, indices)| indices),
+            Some(vec![5, 6, 7, 11, 12])
+        );
+        assert_eq!(
+            fuzzy_match("", "users").map(|(_, indices)| indices),
+            Some(vec![])
+        );
+        assert_eq!(fuzzy_match("xyz", "users"), None);
+        assert_eq!(fuzzy_match("sru", "users"), None);
+
+        let score = |text| fuzzy_match("ui", text).unwrap().0;
+        assert!(score("shop.user_items") > score("shop.audit_log"));
+        assert!(score("user_items") > score("shop.user_items_archive"));
+    }
+}
@@ -0,0 +1,63 @@
+/// replaces the characters which cannot be seen, such as zero-width spaces, control characters and trailing whitespace, with visible placeholders
+pub fn reveal_invisible(text: &str) -> String {
+    text.split('\n')
+        .map(reveal_line)
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn reveal_line(line: &str) -> String {
+    let content = line.trim_end();
+    let mut revealed = content
+        .chars()
+        .map(|c| placeholder(c).unwrap_or_else(|| c.to_string()))
+        .collect::<String>();
+    for c in line[content.len()..].chars() {
+        revealed.push_str(
+            match c {
+                ' ' => Some("·".to_string()),
+                _ => placeholder(c),
+            }
+            .unwrap_or_else(|| code_point(c))
+            .as_str(),
+        );
+    }
+    revealed
+}
+
+fn placeholder(c: char) -> Option<String> {
+    match c {
+        // the control pictures block has a symbol for each of the C0 control characters
+        '\u{0}'..='\u{1f}' => std::char::from_u32(0x2400 + c as u32).map(|c| c.to_string()),
+        '\u{7f}' => Some("␡".to_string()),
+        '\u{a0}' => Some("⍽".to_string()),
+        '\u{80}'..='\u{9f}'
+        | '\u{ad}'
+        | '\u{180e}'
+        | '\u{200b}'..='\u{200f}'
+        | '\u{202a}'..='\u{202e}'
+        | '\u{2060}'..='\u{2064}'
+        | '\u{2066}'..='\u{2069}'
+        | '\u{feff}' => Some(code_point(c)),
+        _ => None,
+    }
+}
+
+fn code_point(c: char) -> String {
+    format!("<U+{:04X}>", c as u32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::reveal_invisible;
+
+    #[test]
+    fn test_reveal_invisible() {
+        assert_eq!(reveal_invisible("plain text"), "plain text");
+        assert_eq!(reveal_invisible("a\u{200b}b"), "a<U+200B>b");
+        assert_eq!(reveal_invisible("\u{feff}id"), "<U+FEFF>id");
+        assert_eq!(reveal_invisible("a\tb\u{0}"), "a␉b␀");
+        assert_eq!(reveal_invisible("a \nb\t \r"), "a·\nb␉·␍");
+        assert_eq!(reveal_invisible("x\u{a0}y\u{3000}"), "x⍽y<U+3000>");
+    }
+}
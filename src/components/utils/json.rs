@@ -0,0 +1,247 @@
+use crate::theme::Theme;
+use serde_json::Value;
+use std::collections::HashSet;
+use tui::text::{Span, Spans};
+
+/// the kind of a piece of a pretty-printed JSON value, which decides its color
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum JsonToken {
+    Key,
+    String,
+    /// numbers, booleans and null
+    Literal,
+    Punct,
+}
+
+/// a line of a pretty-printed JSON value
+#[derive(Debug, Clone, PartialEq)]
+struct JsonLine {
+    indent: usize,
+    tokens: Vec<(JsonToken, String)>,
+    /// the index of the line closing the object or array this line opens
+    close: Option<usize>,
+}
+
+/// a pretty-printed JSON value whose objects and arrays can be collapsed into their first line
+pub struct JsonView {
+    value: Value,
+    lines: Vec<JsonLine>,
+    /// the lines opening the collapsed objects and arrays
+    collapsed: HashSet<usize>,
+}
+
+impl JsonView {
+    pub fn new(value: Value) -> Self {
+        let mut lines = vec![];
+        push_lines(&value, 0, None, false, &mut lines);
+        Self {
+            value,
+            lines,
+            collapsed: HashSet::new(),
+        }
+    }
+
+    /// parses `text` as any JSON value for a column typed as JSON, and only as an object or array otherwise, so that
+    /// plain numbers and strings are left alone
+    pub fn parse(text: &str, typed: bool) -> Option<Self> {
+        let trimmed = text.trim_start();
+        if !typed && !trimmed.starts_with('{') && !trimmed.starts_with('[') {
+            return None;
+        }
+        serde_json::from_str::<Value>(text).ok().map(Self::new)
+    }
+
+    pub fn pretty(&self) -> String {
+        serde_json::to_string_pretty(&self.value).unwrap_or_default()
+    }
+
+    pub fn minified(&self) -> String {
+        serde_json::to_string(&self.value).unwrap_or_default()
+    }
+
+    /// returns the indexes of the lines which are not inside a collapsed object or array
+    pub fn visible_lines(&self) -> Vec<usize> {
+        let mut visible = vec![];
+        let mut index = 0;
+        while index < self.lines.len() {
+            visible.push(index);
+            index = match self.lines[index].close {
+                Some(close) if self.collapsed.contains(&index) => close + 1,
+                _ => index + 1,
+            };
+        }
+        visible
+    }
+
+    /// collapses or expands the object or array opened at the line `index`, returning whether it opens one
+    pub fn toggle(&mut self, index: usize) -> bool {
+        if self.lines.get(index).and_then(|line| line.close).is_none() {
+            return false;
+        }
+        if !self.collapsed.remove(&index) {
+            self.collapsed.insert(index);
+        }
+        true
+    }
+
+    /// returns the pieces of the line `index`, followed by its closing line when it is collapsed
+    fn tokens(&self, index: usize) -> Vec<(JsonToken, String)> {
+        let line = &self.lines[index];
+        let mut tokens = vec![(JsonToken::Punct, "  ".repeat(line.indent))];
+        tokens.extend(line.tokens.iter().cloned());
+        if let Some(close) = line.close.filter(|_| self.collapsed.contains(&index)) {
+            tokens.push((JsonToken::Punct, " … ".to_string()));
+            tokens.extend(self.lines[close].tokens.iter().cloned());
+        }
+        tokens
+    }
+
+    pub fn text(&self, index: usize) -> String {
+        self.tokens(index)
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect()
+    }
+
+    pub fn spans(&self, index: usize) -> Spans<'static> {
+        let theme = Theme::current();
+        Spans::from(
+            self.tokens(index)
+                .into_iter()
+                .map(|(token, text)| match token {
+                    JsonToken::Key => Span::styled(text, theme.accent()),
+                    JsonToken::String => Span::styled(text, theme.success()),
+                    JsonToken::Literal => Span::styled(text, theme.literal()),
+                    JsonToken::Punct => Span::raw(text),
+                })
+                .collect::<Vec<Span>>(),
+        )
+    }
+}
+
+/// lays out `value` as `serde_json::to_string_pretty` does, a line per scalar and per bracket
+fn push_lines(
+    value: &Value,
+    indent: usize,
+    key: Option<&str>,
+    comma: bool,
+    lines: &mut Vec<JsonLine>,
+) {
+    let mut tokens = vec![];
+    if let Some(key) = key {
+        tokens.push((
+            JsonToken::Key,
+            serde_json::to_string(key).unwrap_or_default(),
+        ));
+        tokens.push((JsonToken::Punct, ": ".to_string()));
+    }
+    let comma = if comma { "," } else { "" };
+    let (open, close) = match value {
+        Value::Object(object) if !object.is_empty() => ("{", "}"),
+        Value::Array(array) if !array.is_empty() => ("[", "]"),
+        Value::Object(_) => {
+            tokens.push((JsonToken::Punct, format!("{{}}{}", comma)));
+            lines.push(JsonLine {
+                indent,
+                tokens,
+                close: None,
+            });
+            return;
+        }
+        Value::Array(_) => {
+            tokens.push((JsonToken::Punct, format!("[]{}", comma)));
+            lines.push(JsonLine {
+                indent,
+                tokens,
+                close: None,
+            });
+            return;
+        }
+        scalar => {
+            let token = if scalar.is_string() {
+                JsonToken::String
+            } else {
+                JsonToken::Literal
+            };
+            tokens.push((token, scalar.to_string()));
+            if !comma.is_empty() {
+                tokens.push((JsonToken::Punct, comma.to_string()));
+            }
+            lines.push(JsonLine {
+                indent,
+                tokens,
+                close: None,
+            });
+            return;
+        }
+    };
+    tokens.push((JsonToken::Punct, open.to_string()));
+    let start = lines.len();
+    lines.push(JsonLine {
+        indent,
+        tokens,
+        close: None,
+    });
+    match value {
+        Value::Object(object) => {
+            for (i, (key, child)) in object.iter().enumerate() {
+                push_lines(child, indent + 1, Some(key), i + 1 < object.len(), lines);
+            }
+        }
+        Value::Array(array) => {
+            for (i, child) in array.iter().enumerate() {
+                push_lines(child, indent + 1, None, i + 1 < array.len(), lines);
+            }
+        }
+        _ => (),
+    }
+    lines[start].close = Some(lines.len());
+    lines.push(JsonLine {
+        indent,
+        tokens: vec![(JsonToken::Punct, format!("{}{}", close, comma))],
+        close: None,
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::JsonView;
+
+    #[test]
+    fn test_json_lines() {
+        let json = r#"{"b":1,"a":[true,null],"c":{},"d":"x"}"#;
+        let view = JsonView::parse(json, false).unwrap();
+        let text = view
+            .visible_lines()
+            .into_iter()
+            .map(|index| view.text(index))
+            .collect::<Vec<String>>()
+            .join("\n");
+        assert_eq!(text, view.pretty());
+        assert_eq!(view.minified(), json);
+
+        assert!(JsonView::parse("42", false).is_none());
+        assert!(JsonView::parse("\"text\"", false).is_none());
+        assert!(JsonView::parse("{a,b}", true).is_none());
+        assert_eq!(
+            JsonView::parse("\"text\"", true).unwrap().text(0),
+            "\"text\""
+        );
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut view = JsonView::parse(r#"{"a":[1,2],"b":{"c":null}}"#, false).unwrap();
+        assert_eq!(view.visible_lines(), (0..9).collect::<Vec<usize>>());
+        assert!(!view.toggle(2));
+        assert!(view.toggle(1));
+        assert_eq!(view.visible_lines(), vec![0, 1, 5, 6, 7, 8]);
+        assert_eq!(view.text(1), "  \"a\": [ … ],");
+        assert!(view.toggle(0));
+        assert_eq!(view.visible_lines(), vec![0]);
+        assert_eq!(view.text(0), "{ … }");
+        assert!(view.toggle(0));
+        assert!(view.toggle(1));
+        assert_eq!(view.visible_lines().len(), 9);
+    }
+}
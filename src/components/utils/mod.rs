@@ -1 +1,7 @@
+pub mod binary;
+pub mod completion;
+pub mod fuzzy;
+pub mod invisible;
+pub mod json;
+pub mod number_format;
 pub mod scroll_vertical;
@@ -0,0 +1,121 @@
+use crate::config::NumberFormat;
+
+/// formats `value` following `format` when it is a number, returning `None` for other values and for codes with leading zeros
+pub fn format_number(value: &str, format: &NumberFormat) -> Option<String> {
+    let (integer, fraction, exponent) = number_parts(value)?;
+    let number = value.parse::<f64>().ok()?;
+    if let Some(threshold) = format.scientific_threshold {
+        if number != 0.0 && number.abs() >= threshold {
+            return Some(match format.precision {
+                Some(precision) => format!("{:.*e}", precision, number),
+                None => format!("{:e}", number),
+            });
+        }
+    }
+    let formatted = match format.precision {
+        Some(precision) if fraction.is_some() || exponent.is_some() => {
+            format!("{:.*}", precision, number)
+        }
+        // numbers written with an exponent keep it unless they are given a precision
+        _ if exponent.is_some() => return Some(value.to_string()),
+        _ => match fraction {
+            Some(fraction) => format!("{}.{}", integer, fraction),
+            None => integer.to_string(),
+        },
+    };
+    if format.thousands_separator != Some(true) {
+        return Some(formatted);
+    }
+    let (sign, digits) = match formatted.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", formatted.as_str()),
+    };
+    let (digits, rest) = digits.split_at(digits.find('.').unwrap_or(digits.len()));
+    let mut separated = sign.to_string();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            separated.push(',');
+        }
+        separated.push(c);
+    }
+    separated.push_str(rest);
+    Some(separated)
+}
+
+/// splits a decimal number into its signed integer part, its fractional part and its exponent
+fn number_parts(value: &str) -> Option<(&str, Option<&str>, Option<&str>)> {
+    let (mantissa, exponent) = match value.find(&['e', 'E'][..]) {
+        Some(index) => (&value[..index], Some(&value[index + 1..])),
+        None => (value, None),
+    };
+    let (integer, fraction) = match mantissa.find('.') {
+        Some(index) => (&mantissa[..index], Some(&mantissa[index + 1..])),
+        None => (mantissa, None),
+    };
+    let digits = integer.strip_prefix('-').unwrap_or(integer);
+    let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    if !is_digits(digits)
+        || (digits.len() > 1 && digits.starts_with('0'))
+        || !fraction.map_or(true, is_digits)
+        || !exponent.map_or(true, |exponent| {
+            is_digits(
+                exponent
+                    .strip_prefix(|c| c == '+' || c == '-')
+                    .unwrap_or(exponent),
+            )
+        })
+    {
+        return None;
+    }
+    Some((integer, fraction, exponent))
+}
+
+#[cfg(test)]
+mod test {
+    use super::format_number;
+    use crate::config::NumberFormat;
+
+    #[test]
+    fn test_format_number() {
+        let format = NumberFormat {
+            thousands_separator: Some(true),
+            precision: Some(2),
+            scientific_threshold: None,
+        };
+        assert_eq!(
+            format_number("1234567", &format),
+            Some("1,234,567".to_string())
+        );
+        assert_eq!(
+            format_number("-1234.5678", &format),
+            Some("-1,234.57".to_string())
+        );
+        assert_eq!(format_number("123", &format), Some("123".to_string()));
+        assert_eq!(
+            format_number("1.5e3", &format),
+            Some("1,500.00".to_string())
+        );
+        assert_eq!(format_number("00123", &format), None);
+        assert_eq!(format_number("12-34", &format), None);
+        assert_eq!(format_number("1.", &format), None);
+        assert_eq!(format_number("", &format), None);
+
+        let format = NumberFormat {
+            thousands_separator: None,
+            precision: None,
+            scientific_threshold: Some(1000000.0),
+        };
+        assert_eq!(
+            format_number("12345678", &format),
+            Some("1.2345678e7".to_string())
+        );
+        assert_eq!(format_number("-0.5", &format), Some("-0.5".to_string()));
+        assert_eq!(format_number("1e2", &format), Some("1e2".to_string()));
+
+        let format = NumberFormat {
+            precision: Some(1),
+            ..format
+        };
+        assert_eq!(format_number("2500000", &format), Some("2.5e6".to_string()));
+    }
+}
@@ -5,6 +5,8 @@ use tui::{backend::Backend, layout::Rect, Frame};
 pub struct VerticalScroll {
     top: Cell<usize>,
     max_top: Cell<usize>,
+    /// how many lines were in view when last updated, which sizes the thumb
+    visible: Cell<usize>,
     inside: bool,
     border: bool,
 }
@@ -14,6 +16,7 @@ impl VerticalScroll {
         Self {
             top: Cell::new(0),
             max_top: Cell::new(0),
+            visible: Cell::new(0),
             border,
             inside,
         }
@@ -30,6 +33,7 @@ impl VerticalScroll {
     pub fn update(&self, selection: usize, selection_max: usize, visual_height: usize) -> usize {
         let new_top = calc_scroll_top(self.get_top(), visual_height, selection, selection_max);
         self.top.set(new_top);
+        self.visible.set(visual_height);
 
         if visual_height == 0 {
             self.max_top.set(0);
@@ -47,6 +51,7 @@ impl VerticalScroll {
             r,
             self.max_top.get(),
             self.top.get(),
+            self.visible.get(),
             self.border,
             self.inside,
         );
@@ -0,0 +1,157 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+
+/// returns the delimiter the first line of a CSV file uses the most among a comma, a semicolon and a tab
+pub fn detect_delimiter(text: &str) -> char {
+    let line = text.lines().next().unwrap_or_default();
+    [',', ';', '\t']
+        .iter()
+        .copied()
+        .max_by_key(|delimiter| {
+            (
+                line.matches(*delimiter).count(),
+                // a comma wins when none of them is found
+                *delimiter == ',',
+            )
+        })
+        .unwrap_or(',')
+}
+
+/// splits CSV text into records of fields, where a field in double quotes may hold the delimiter, line breaks and
+/// doubled double quotes, skipping blank lines and a leading byte order mark
+pub fn parse_csv(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    // whether the line has a field, even an empty one in quotes
+    let mut started = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = false,
+                c => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => {
+                quoted = true;
+                started = true;
+            }
+            c if c == delimiter => {
+                record.push(std::mem::take(&mut field));
+                started = true;
+            }
+            '\r' if chars.peek() == Some(&'\n') => (),
+            '\n' | '\r' => {
+                if started || !field.is_empty() {
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+                started = false;
+            }
+            c => field.push(c),
+        }
+    }
+    if started || !field.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+/// returns whether a value read from a CSV file can be stored in a column holding the kind of values `badge` names,
+/// as `type_badge` names them, where an empty value stands for NULL
+pub fn valid_value(badge: Option<&str>, value: &str) -> bool {
+    let value = value.trim();
+    if value.is_empty() {
+        return true;
+    }
+    let date_time = |formats: &[&str]| {
+        formats
+            .iter()
+            .any(|format| NaiveDateTime::parse_from_str(value, format).is_ok())
+    };
+    match badge {
+        Some("int") => value.parse::<i128>().is_ok(),
+        Some("num") => value.parse::<f64>().map_or(false, f64::is_finite),
+        Some("bool") => matches!(
+            value.to_lowercase().as_str(),
+            "true" | "false" | "t" | "f" | "yes" | "no" | "y" | "n" | "1" | "0"
+        ),
+        Some("date") => NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+        Some("time") => ["%H:%M:%S%.f", "%H:%M"]
+            .iter()
+            .any(|format| NaiveTime::parse_from_str(value, format).is_ok()),
+        Some("ts") => {
+            date_time(&[
+                "%Y-%m-%d %H:%M:%S%.f",
+                "%Y-%m-%dT%H:%M:%S%.f",
+                "%Y-%m-%d %H:%M",
+            ]) || DateTime::parse_from_rfc3339(value).is_ok()
+                || DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f%#z").is_ok()
+                || NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+        }
+        Some("json") => serde_json::from_str::<serde_json::Value>(value).is_ok(),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{detect_delimiter, parse_csv, valid_value};
+
+    #[test]
+    fn test_parse_csv() {
+        assert_eq!(
+            parse_csv(
+                "\u{feff}id,name,note\r\n1,\"Smith, J\",\"said \"\"hi\"\"\ntwice\"\r\n\n2,,\"\"\n3",
+                ','
+            ),
+            vec![
+                vec!["id", "name", "note"],
+                vec!["1", "Smith, J", "said \"hi\"\ntwice"],
+                vec!["2", "", ""],
+                vec!["3"],
+            ]
+        );
+        assert_eq!(parse_csv("a;b\"c\n", ';'), vec![vec!["a", "b\"c"]]);
+        assert!(parse_csv("", ',').is_empty());
+    }
+
+    #[test]
+    fn test_detect_delimiter() {
+        assert_eq!(detect_delimiter("id;name;note\n1;a,b;c"), ';');
+        assert_eq!(detect_delimiter("id\tname\n1\t2"), '\t');
+        assert_eq!(detect_delimiter("id,name"), ',');
+        assert_eq!(detect_delimiter("id"), ',');
+    }
+
+    #[test]
+    fn test_valid_value() {
+        assert!(valid_value(Some("int"), " -42 "));
+        assert!(!valid_value(Some("int"), "4.2"));
+        assert!(valid_value(Some("int"), ""));
+        assert!(valid_value(Some("num"), "4.2e3"));
+        assert!(!valid_value(Some("num"), "NaN"));
+        assert!(valid_value(Some("bool"), "TRUE"));
+        assert!(!valid_value(Some("bool"), "maybe"));
+        assert!(valid_value(Some("date"), "2024-02-29"));
+        assert!(!valid_value(Some("date"), "2023-02-29"));
+        assert!(valid_value(Some("time"), "12:30"));
+        assert!(valid_value(Some("ts"), "2024-01-02 03:04:05.678"));
+        assert!(valid_value(Some("ts"), "2024-01-02T03:04:05Z"));
+        assert!(valid_value(Some("ts"), "2024-01-02 03:04:05+09"));
+        assert!(!valid_value(Some("ts"), "yesterday"));
+        assert!(valid_value(Some("json"), "{\"a\": [1]}"));
+        assert!(!valid_value(Some("json"), "{a}"));
+        assert!(valid_value(Some("txt"), "anything"));
+        assert!(valid_value(None, "anything"));
+    }
+}
@@ -0,0 +1,178 @@
+use super::{is_clause_keyword, located_sql_tokens, table_reference, Engine, SqlToken};
+use crate::metadata::MetadataCache;
+
+/// returns the tables which the statement reads or writes, each with the name it is written with in the statement,
+/// which is its alias when it has one, and with its own name as well
+fn statement_tables(tokens: &[SqlToken]) -> Vec<(String, String)> {
+    let word = |index: usize| match tokens.get(index) {
+        Some(SqlToken::Word(word)) => Some(word.to_uppercase()),
+        _ => None,
+    };
+    let mut tables = vec![];
+    for index in 0..tokens.len() {
+        let keyword = match word(index) {
+            Some(keyword) if matches!(keyword.as_str(), "FROM" | "JOIN" | "UPDATE" | "INTO") => {
+                keyword
+            }
+            _ => continue,
+        };
+        let mut next = index + 1;
+        while word(next).as_deref() == Some("ONLY") {
+            next += 1;
+        }
+        while let Some(((_, table), after)) = table_reference(tokens, next) {
+            next = after;
+            if word(next).as_deref() == Some("AS") {
+                next += 1;
+            }
+            if let Some(SqlToken::Word(alias)) = tokens.get(next) {
+                if !is_clause_keyword(alias) {
+                    tables.push((alias.clone(), table.clone()));
+                    next += 1;
+                }
+            }
+            tables.push((table.clone(), table));
+            if keyword != "FROM" || tokens.get(next) != Some(&SqlToken::Punct(',')) {
+                break;
+            }
+            next += 1;
+        }
+    }
+    tables
+}
+
+/// returns the columns which can follow the table or the alias before the dot which ends the text before the character
+/// at `cursor` of `sql`, such as the columns of `users` after `u.` in `SELECT u. FROM users u`, where the tables are
+/// those of the statement at the cursor, wherever they are named in it
+pub fn qualified_columns(
+    sql: &str,
+    engine: Engine,
+    cursor: usize,
+    metadata: &MetadataCache,
+) -> Vec<String> {
+    let located = located_sql_tokens(sql, engine);
+    let statement = located
+        .split(|(_, token)| *token == SqlToken::Punct(';'))
+        .find(|statement| {
+            statement
+                .last()
+                .map_or(false, |(range, _)| range.end >= cursor)
+        })
+        .unwrap_or_default();
+    let before = statement
+        .iter()
+        .take_while(|(range, _)| range.end <= cursor)
+        .collect::<Vec<_>>();
+    // the dot may be followed by the beginning of the column
+    let dot = match before.as_slice() {
+        [.., (dot, SqlToken::Punct('.')), (word, SqlToken::Word(_))]
+            if dot.end == word.start && word.end == cursor =>
+        {
+            before.len() - 2
+        }
+        [.., (dot, SqlToken::Punct('.'))] if dot.end == cursor => before.len() - 1,
+        _ => return vec![],
+    };
+    let qualifier = match dot.checked_sub(1).map(|index| &before[index]) {
+        Some((range, SqlToken::Word(name))) | Some((range, SqlToken::Quoted(name)))
+            if range.end == before[dot].0.start =>
+        {
+            name
+        }
+        _ => return vec![],
+    };
+    let tokens = statement
+        .iter()
+        .map(|(_, token)| token.clone())
+        .collect::<Vec<SqlToken>>();
+    statement_tables(&tokens)
+        .into_iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(qualifier))
+        .and_then(|(_, table)| metadata.columns(&table))
+        .map_or_else(Vec::new, <[String]>::to_vec)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{qualified_columns, statement_tables};
+    use crate::database::{sql_tokens, Engine};
+    use crate::metadata::MetadataCache;
+    use database_tree::Table;
+
+    fn table(name: &str) -> Table {
+        Table {
+            name: name.to_string(),
+            create_time: None,
+            update_time: None,
+            engine: None,
+            schema: None,
+            view: false,
+        }
+    }
+
+    #[test]
+    fn test_statement_tables() {
+        let pair = |alias: &str, table: &str| (alias.to_string(), table.to_string());
+        assert_eq!(
+            statement_tables(&sql_tokens(
+                "SELECT * FROM public.users AS u, teams JOIN orders o ON o.user_id = u.id WHERE",
+                Engine::MySql
+            )),
+            vec![
+                pair("u", "users"),
+                pair("users", "users"),
+                pair("teams", "teams"),
+                pair("o", "orders"),
+                pair("orders", "orders"),
+            ]
+        );
+        assert_eq!(
+            statement_tables(&sql_tokens("UPDATE users SET name = 'a'", Engine::MySql)),
+            vec![pair("users", "users")]
+        );
+    }
+
+    #[test]
+    fn test_qualified_columns() {
+        let mut metadata = MetadataCache::default();
+        metadata.set_columns(&table("users"), vec!["id".to_string(), "name".to_string()]);
+        metadata.set_columns(&table("orders"), vec!["user_id".to_string()]);
+
+        // the table may be named after the cursor
+        let sql = "SELECT u. FROM users u";
+        assert_eq!(
+            qualified_columns(sql, Engine::MySql, 9, &metadata),
+            vec!["id", "name"]
+        );
+        let sql = "SELECT u.na FROM users u";
+        assert_eq!(
+            qualified_columns(sql, Engine::MySql, 11, &metadata),
+            vec!["id", "name"]
+        );
+        assert!(qualified_columns(sql, Engine::MySql, 10, &metadata).is_empty());
+
+        let sql = "SELECT 1; SELECT * FROM users u JOIN orders o ON o.";
+        assert_eq!(
+            qualified_columns(sql, Engine::MySql, sql.len(), &metadata),
+            vec!["user_id"]
+        );
+        let sql = "SELECT * FROM users u JOIN orders o ON o.user_id = U.";
+        assert_eq!(
+            qualified_columns(sql, Engine::MySql, sql.len(), &metadata),
+            vec!["id", "name"]
+        );
+        let sql = "SELECT users. FROM users";
+        assert_eq!(
+            qualified_columns(sql, Engine::MySql, 13, &metadata),
+            vec!["id", "name"]
+        );
+
+        // an alias of another statement, or a table whose columns are not cached
+        let sql = "SELECT * FROM orders o; SELECT o.";
+        assert!(qualified_columns(sql, Engine::MySql, sql.len(), &metadata).is_empty());
+        let sql = "SELECT t. FROM teams t";
+        assert!(qualified_columns(sql, Engine::MySql, 9, &metadata).is_empty());
+        let sql = "SELECT u . FROM users u";
+        assert!(qualified_columns(sql, Engine::MySql, 10, &metadata).is_empty());
+    }
+}
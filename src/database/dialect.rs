@@ -0,0 +1,275 @@
+use super::{located_sql_tokens, SqlToken};
+use serde::Deserialize;
+use std::ops::Range;
+
+/// the SQL engines a connection can be made to, whose functions and syntax differ
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Engine {
+    MySql,
+    Postgres,
+    Sqlite,
+    DuckDb,
+}
+
+impl std::fmt::Display for Engine {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::MySql => write!(f, "MySQL"),
+            Self::Postgres => write!(f, "PostgreSQL"),
+            Self::Sqlite => write!(f, "SQLite"),
+            Self::DuckDb => write!(f, "DuckDB"),
+        }
+    }
+}
+
+const ALL: &[Engine] = &[
+    Engine::MySql,
+    Engine::Postgres,
+    Engine::Sqlite,
+    Engine::DuckDb,
+];
+
+/// the functions offered in completion, with the engines which have them, which are flagged on the others
+const FUNCTIONS: &[(&str, &[Engine])] = &[
+    ("ABS", ALL),
+    ("ARRAY_AGG", &[Engine::Postgres, Engine::DuckDb]),
+    ("AVG", ALL),
+    ("COALESCE", ALL),
+    (
+        "CONCAT_WS",
+        &[Engine::MySql, Engine::Postgres, Engine::DuckDb],
+    ),
+    ("COUNT", ALL),
+    ("CURDATE", &[Engine::MySql]),
+    ("DATE_ADD", &[Engine::MySql, Engine::DuckDb]),
+    ("DATE_FORMAT", &[Engine::MySql]),
+    ("DATE_SUB", &[Engine::MySql]),
+    ("DATE_TRUNC", &[Engine::Postgres, Engine::DuckDb]),
+    ("FROM_UNIXTIME", &[Engine::MySql]),
+    ("GENERATE_SERIES", &[Engine::Postgres, Engine::DuckDb]),
+    (
+        "GREATEST",
+        &[Engine::MySql, Engine::Postgres, Engine::DuckDb],
+    ),
+    (
+        "GROUP_CONCAT",
+        &[Engine::MySql, Engine::Sqlite, Engine::DuckDb],
+    ),
+    ("IFNULL", &[Engine::MySql, Engine::Sqlite, Engine::DuckDb]),
+    ("INSTR", &[Engine::MySql, Engine::Sqlite, Engine::DuckDb]),
+    ("JSON_BUILD_OBJECT", &[Engine::Postgres]),
+    (
+        "JSON_EXTRACT",
+        &[Engine::MySql, Engine::Sqlite, Engine::DuckDb],
+    ),
+    ("JULIANDAY", &[Engine::Sqlite]),
+    ("LAST_INSERT_ID", &[Engine::MySql]),
+    ("LAST_INSERT_ROWID", &[Engine::Sqlite]),
+    ("LEAST", &[Engine::MySql, Engine::Postgres, Engine::DuckDb]),
+    ("LENGTH", ALL),
+    ("LOCATE", &[Engine::MySql]),
+    ("LOWER", ALL),
+    ("MAX", ALL),
+    ("MIN", ALL),
+    ("NOW", &[Engine::MySql, Engine::Postgres, Engine::DuckDb]),
+    ("NULLIF", ALL),
+    ("RAND", &[Engine::MySql]),
+    (
+        "RANDOM",
+        &[Engine::Postgres, Engine::Sqlite, Engine::DuckDb],
+    ),
+    ("REPLACE", ALL),
+    ("ROUND", ALL),
+    ("STRFTIME", &[Engine::Sqlite, Engine::DuckDb]),
+    ("STRING_AGG", &[Engine::Postgres, Engine::DuckDb]),
+    ("STR_TO_DATE", &[Engine::MySql]),
+    ("SUBSTR", ALL),
+    ("SUBSTRING_INDEX", &[Engine::MySql]),
+    ("SUM", ALL),
+    ("TO_CHAR", &[Engine::Postgres]),
+    ("TO_DATE", &[Engine::Postgres]),
+    ("TO_TIMESTAMP", &[Engine::Postgres, Engine::DuckDb]),
+    ("TRIM", ALL),
+    ("UNIX_TIMESTAMP", &[Engine::MySql]),
+    ("UPPER", ALL),
+];
+
+/// returns the names of the functions `engine` has, to be offered in completion
+pub fn functions(engine: Engine) -> Vec<String> {
+    FUNCTIONS
+        .iter()
+        .filter(|(_, engines)| engines.contains(&engine))
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+/// the range of characters of a function or syntax which an engine does not have, with what it is
+pub type DialectHint = (Range<usize>, String);
+
+/// returns the ranges of characters of the functions and syntax of `sql` which `engine` does not have, with what each
+/// one is, such as `GROUP_CONCAT()` or `::`
+pub fn dialect_hints(sql: &str, engine: Engine) -> Vec<DialectHint> {
+    let chars = sql.chars().collect::<Vec<char>>();
+    let tokens = located_sql_tokens(sql, engine);
+    let word = |index: usize| match tokens.get(index) {
+        Some((_, SqlToken::Word(word))) => Some(word.to_uppercase()),
+        _ => None,
+    };
+    let punct = |index: usize| match tokens.get(index) {
+        Some((_, SqlToken::Punct(c))) => Some(*c),
+        _ => None,
+    };
+    // whether two punctuation tokens are written next to each other, such as `::`
+    let adjacent = |index: usize| matches!((tokens.get(index), tokens.get(index + 1)), (Some((a, _)), Some((b, _))) if a.end == b.start);
+    let previous = |index: usize| index.checked_sub(1).and_then(word);
+    let mut hints = vec![];
+    let mut hint = |range: Range<usize>, construct: &str, engines: &[Engine]| {
+        if !engines.contains(&engine) {
+            hints.push((range, construct.to_string()));
+        }
+    };
+    // the types of columns such as DATETIME(6) look like calls where tables are defined
+    let defines = matches!(word(0).as_deref(), Some("CREATE") | Some("ALTER"));
+    for (index, (range, token)) in tokens.iter().enumerate() {
+        match token {
+            SqlToken::Word(_) => {
+                let name = word(index).unwrap_or_default();
+                let called =
+                    punct(index + 1) == Some('(') && punct(index.wrapping_sub(1)) != Some('.');
+                if called && !defines {
+                    if let Some((name, engines)) =
+                        FUNCTIONS.iter().find(|(function, _)| *function == name)
+                    {
+                        hint(range.clone(), format!("{}()", name).as_str(), engines);
+                    }
+                }
+                match name.as_str() {
+                    "ILIKE" => hint(range.clone(), "ILIKE", &[Engine::Postgres, Engine::DuckDb]),
+                    "REGEXP" => hint(range.clone(), "REGEXP", &[Engine::MySql]),
+                    "AUTO_INCREMENT" => hint(range.clone(), "AUTO_INCREMENT", &[Engine::MySql]),
+                    "AUTOINCREMENT" => hint(range.clone(), "AUTOINCREMENT", &[Engine::Sqlite]),
+                    "DUPLICATE" if previous(index).as_deref() == Some("ON") => hint(
+                        tokens[index - 1].0.start..range.end,
+                        "ON DUPLICATE KEY",
+                        &[Engine::MySql],
+                    ),
+                    "CONFLICT" if previous(index).as_deref() == Some("ON") => hint(
+                        tokens[index - 1].0.start..range.end,
+                        "ON CONFLICT",
+                        &[Engine::Postgres, Engine::Sqlite, Engine::DuckDb],
+                    ),
+                    "ON" if previous(index).as_deref() == Some("DISTINCT")
+                        && punct(index + 1) == Some('(') =>
+                    {
+                        hint(
+                            tokens[index - 1].0.start..range.end,
+                            "DISTINCT ON",
+                            &[Engine::Postgres, Engine::DuckDb],
+                        )
+                    }
+                    // the offset before the count, such as LIMIT 10, 20
+                    "LIMIT" if punct(index + 2) == Some(',') => hint(
+                        range.start..tokens[index + 2].0.end,
+                        "LIMIT offset, count",
+                        &[Engine::MySql, Engine::Sqlite],
+                    ),
+                    _ => (),
+                }
+            }
+            SqlToken::Quoted(_) if chars.get(range.start) == Some(&'`') => {
+                hint(range.clone(), "`name`", &[Engine::MySql, Engine::Sqlite])
+            }
+            SqlToken::Punct(':') if punct(index + 1) == Some(':') && adjacent(index) => hint(
+                range.start..range.end + 1,
+                "::",
+                &[Engine::Postgres, Engine::DuckDb],
+            ),
+            // MySQL reads || as OR unless PIPES_AS_CONCAT is set
+            SqlToken::Punct('|') if punct(index + 1) == Some('|') && adjacent(index) => hint(
+                range.start..range.end + 1,
+                "||",
+                &[Engine::Postgres, Engine::Sqlite, Engine::DuckDb],
+            ),
+            _ => (),
+        }
+    }
+    hints
+}
+
+/// returns what the hints flag, each once in the order they appear
+pub fn unsupported_constructs(hints: &[DialectHint]) -> Vec<String> {
+    let mut constructs: Vec<String> = vec![];
+    for (_, construct) in hints {
+        if !constructs.contains(construct) {
+            constructs.push(construct.clone());
+        }
+    }
+    constructs
+}
+
+#[cfg(test)]
+mod test {
+    use super::{dialect_hints, functions, unsupported_constructs, Engine};
+
+    fn constructs(sql: &str, engine: Engine) -> Vec<String> {
+        dialect_hints(sql, engine)
+            .into_iter()
+            .map(|(_, construct)| construct)
+            .collect()
+    }
+
+    #[test]
+    fn test_functions() {
+        assert!(functions(Engine::Postgres).contains(&"STRING_AGG".to_string()));
+        assert!(!functions(Engine::Postgres).contains(&"GROUP_CONCAT".to_string()));
+        assert!(functions(Engine::Sqlite).contains(&"GROUP_CONCAT".to_string()));
+        assert!(functions(Engine::MySql).contains(&"COUNT".to_string()));
+        assert!(functions(Engine::DuckDb).contains(&"STRFTIME".to_string()));
+        assert!(!functions(Engine::DuckDb).contains(&"DATE_FORMAT".to_string()));
+    }
+
+    #[test]
+    fn test_dialect_hints() {
+        let sql =
+            "SELECT group_concat(name), id::text FROM `users` WHERE name ILIKE 'a%' LIMIT 5, 10";
+        assert_eq!(
+            dialect_hints(sql, Engine::Postgres),
+            vec![
+                (7..19, "GROUP_CONCAT()".to_string()),
+                (41..48, "`name`".to_string()),
+                (71..79, "LIMIT offset, count".to_string()),
+            ]
+        );
+        assert_eq!(
+            constructs(sql, Engine::MySql),
+            vec!["::".to_string(), "ILIKE".to_string()]
+        );
+        assert_eq!(
+            constructs(
+                "INSERT INTO t VALUES (1) ON CONFLICT (id) DO NOTHING",
+                Engine::MySql
+            ),
+            vec!["ON CONFLICT".to_string()]
+        );
+        assert_eq!(
+            constructs("SELECT DISTINCT ON (a) a, b || c FROM t", Engine::Sqlite),
+            vec!["DISTINCT ON".to_string()]
+        );
+        assert_eq!(
+            constructs(sql, Engine::DuckDb),
+            vec!["`name`".to_string(), "LIMIT offset, count".to_string()]
+        );
+        // the types of columns are not calls, and neither are the columns of a qualified name
+        assert!(constructs("CREATE TABLE t (a DATE_FORMAT(6))", Engine::Postgres).is_empty());
+        assert!(constructs("SELECT t.now(1) FROM t", Engine::Sqlite).is_empty());
+        assert!(constructs("SELECT 'a::b', '`c`' -- now()", Engine::Sqlite).is_empty());
+    }
+
+    #[test]
+    fn test_unsupported_constructs() {
+        let hints = dialect_hints("SELECT a::int, b::text, c FROM `t`", Engine::MySql);
+        assert_eq!(hints.len(), 2);
+        assert_eq!(unsupported_constructs(&hints), vec!["::".to_string()]);
+    }
+}
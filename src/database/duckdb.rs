@@ -0,0 +1,983 @@
+use super::{
+    encode_binary, insert_statement, is_read_query, key_columns, keyset_clause, setting_statement,
+    split_key, CancelToken, ChildReference, DumpDefinition, Engine, ExecuteResult, Feature,
+    ForeignKeyReference, KeysetPage, PlanNode, Pool, ScheduledJob, ServerVersion, Session,
+    StatementMode, TableRow, TableStatus, TextSyntax, RECORDS_LIMIT_PER_PAGE,
+};
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDate, NaiveTime};
+use database_tree::{Child, Database, Routine, Schema, Table};
+use duckdb::types::{TimeUnit, Value};
+use duckdb::{Connection, InterruptHandle};
+use itertools::Itertools;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// the interrupt handles of the open sessions by the ids their cancel tokens carry
+type Interrupts = Arc<Mutex<HashMap<usize, Arc<InterruptHandle>>>>;
+
+/// a DuckDB database file, whose connections run statements on the calling thread and so run them in blocking tasks
+pub struct DuckDbPool {
+    conn: Arc<Mutex<Connection>>,
+    /// the settings applied to each connection opened for a session
+    statements: Vec<String>,
+    interrupts: Interrupts,
+    next_session: AtomicUsize,
+    version: ServerVersion,
+}
+
+impl DuckDbPool {
+    pub async fn new(
+        database_url: &str,
+        settings: &BTreeMap<String, String>,
+    ) -> anyhow::Result<Self> {
+        let statements = settings
+            .iter()
+            .map(|(name, value)| setting_statement("SET", name, value))
+            .collect::<anyhow::Result<Vec<String>>>()?;
+        let path = database_url.trim_start_matches("duckdb://").to_string();
+        let setup = statements.clone();
+        let (conn, version) = tokio::task::spawn_blocking(move || -> anyhow::Result<_> {
+            let conn = Connection::open(path)?;
+            for statement in &setup {
+                conn.execute_batch(statement)?;
+            }
+            let (_, rows) = query_values(&conn, "SELECT version()", &[])?;
+            let version = rows
+                .into_iter()
+                .flatten()
+                .flatten()
+                .next()
+                .unwrap_or_default();
+            Ok((conn, version))
+        })
+        .await??;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            statements,
+            interrupts: Arc::default(),
+            next_session: AtomicUsize::new(0),
+            version: ServerVersion::parse(version.as_str()),
+        })
+    }
+
+    /// runs `query` with `params` bound to its `?` in a blocking task, with `None` for NULL
+    async fn query(
+        &self,
+        query: &str,
+        params: &[&str],
+    ) -> anyhow::Result<(Vec<String>, Vec<Vec<Option<String>>>)> {
+        let conn = Arc::clone(&self.conn);
+        let query = query.to_string();
+        let params = params
+            .iter()
+            .map(|param| param.to_string())
+            .collect::<Vec<String>>();
+        tokio::task::spawn_blocking(move || {
+            let conn = lock(&conn)?;
+            query_values(&conn, query.as_str(), &params)
+        })
+        .await?
+    }
+
+    /// builds the SELECT of the page of records of `table` at the offset `page`
+    fn records_query(
+        &self,
+        database: &Database,
+        table: &Table,
+        page: usize,
+        filter: Option<String>,
+    ) -> String {
+        format!(
+            "SELECT * FROM {table} {filter}LIMIT {limit} OFFSET {page}",
+            table = self.table_name(database, table),
+            filter = filter.map_or(String::new(), |filter| format!("WHERE {} ", filter)),
+            page = page,
+            limit = RECORDS_LIMIT_PER_PAGE
+        )
+    }
+
+    /// builds the SELECT of the page of records of `table` following `key` in primary key order
+    fn records_after_query(
+        &self,
+        database: &Database,
+        table: &Table,
+        primary_key: &[String],
+        key: Option<&[String]>,
+        filter: Option<String>,
+    ) -> String {
+        format!(
+            "SELECT *{} FROM {} {}",
+            key_columns(primary_key, '"', self.text_syntax().cast),
+            self.table_name(database, table),
+            keyset_clause(Engine::DuckDb, primary_key, key, filter, '"')
+        )
+    }
+
+    async fn foreign_keys(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<ForeignKey>> {
+        let (_, rows) = self
+            .query(
+                "
+                SELECT
+                    rc.constraint_name,
+                    kcu.column_name,
+                    ukcu.table_schema,
+                    ukcu.table_name,
+                    ukcu.column_name
+                FROM
+                    information_schema.referential_constraints rc
+                    JOIN information_schema.key_column_usage kcu ON kcu.constraint_name = rc.constraint_name
+                    AND kcu.constraint_schema = rc.constraint_schema
+                    JOIN information_schema.key_column_usage ukcu ON ukcu.constraint_name = rc.unique_constraint_name
+                    AND ukcu.constraint_schema = rc.unique_constraint_schema
+                    AND ukcu.ordinal_position = kcu.position_in_unique_constraint
+                WHERE
+                    kcu.table_catalog = ?
+                    AND kcu.table_schema = ?
+                    AND kcu.table_name = ?
+                ORDER BY
+                    rc.constraint_name,
+                    kcu.ordinal_position
+                ",
+                &[database.name.as_str(), schema(table), table.name.as_str()],
+            )
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| ForeignKey {
+                name: row[0].clone(),
+                column_name: row[1].clone(),
+                ref_schema: row[2].clone(),
+                ref_table: row[3].clone(),
+                ref_column: row[4].clone(),
+            })
+            .collect())
+    }
+}
+
+fn lock(conn: &Mutex<Connection>) -> anyhow::Result<std::sync::MutexGuard<Connection>> {
+    conn.lock()
+        .map_err(|_| anyhow::anyhow!("a statement panicked on the DuckDB connection"))
+}
+
+/// the schema of `table`, which is `main` unless another one was created or attached
+fn schema(table: &Table) -> &str {
+    table.schema.as_deref().unwrap_or("main")
+}
+
+/// runs `query` and returns the names of its columns with its rows written as text, with `None` for NULL
+fn query_values(
+    conn: &Connection,
+    query: &str,
+    params: &[String],
+) -> anyhow::Result<(Vec<String>, Vec<Vec<Option<String>>>)> {
+    let mut statement = conn.prepare(query)?;
+    let mut rows = statement.query(duckdb::params_from_iter(params.iter()))?;
+    let headers = rows
+        .as_ref()
+        .map_or_else(Vec::new, |statement| statement.column_names());
+    let mut records = vec![];
+    while let Some(row) = rows.next()? {
+        let mut record = vec![];
+        for index in 0..headers.len() {
+            record.push(match row.get::<_, Value>(index)? {
+                Value::Null => None,
+                value => Some(value_to_string(value)),
+            });
+        }
+        records.push(record);
+    }
+    Ok((headers, records))
+}
+
+fn micros(unit: TimeUnit, value: i64) -> i64 {
+    match unit {
+        TimeUnit::Second => value * 1_000_000,
+        TimeUnit::Millisecond => value * 1_000,
+        TimeUnit::Microsecond => value,
+        TimeUnit::Nanosecond => value / 1_000,
+    }
+}
+
+fn value_to_string(value: Value) -> String {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap_or_default();
+    match value {
+        Value::Null => "NULL".to_string(),
+        Value::Boolean(value) => value.to_string(),
+        Value::TinyInt(value) => value.to_string(),
+        Value::SmallInt(value) => value.to_string(),
+        Value::Int(value) => value.to_string(),
+        Value::BigInt(value) => value.to_string(),
+        Value::HugeInt(value) => value.to_string(),
+        Value::UTinyInt(value) => value.to_string(),
+        Value::USmallInt(value) => value.to_string(),
+        Value::UInt(value) => value.to_string(),
+        Value::UBigInt(value) => value.to_string(),
+        Value::Float(value) => value.to_string(),
+        Value::Double(value) => value.to_string(),
+        Value::Decimal(value) => value.to_string(),
+        Value::Text(value) | Value::Enum(value) => value,
+        Value::Blob(value) => encode_binary(&value),
+        Value::Date32(days) => (epoch + Duration::days(days.into())).to_string(),
+        Value::Timestamp(unit, value) => (epoch.and_hms_opt(0, 0, 0).unwrap_or_default()
+            + Duration::microseconds(micros(unit, value)))
+        .to_string(),
+        Value::Time64(unit, value) => (NaiveTime::from_hms_opt(0, 0, 0).unwrap_or_default()
+            + Duration::microseconds(micros(unit, value)))
+        .to_string(),
+        Value::List(values) => format!("[{}]", values.into_iter().map(value_to_string).join(", ")),
+        value => format!("{:?}", value),
+    }
+}
+
+pub struct Column {
+    name: String,
+    r#type: String,
+    null: String,
+    default: String,
+    comment: String,
+}
+
+impl TableRow for Column {
+    fn fields(&self) -> Vec<String> {
+        vec![
+            "name".to_string(),
+            "type".to_string(),
+            "null".to_string(),
+            "default".to_string(),
+            "comment".to_string(),
+        ]
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.name.to_string(),
+            self.r#type.to_string(),
+            self.null.to_string(),
+            self.default.to_string(),
+            self.comment.to_string(),
+        ]
+    }
+}
+
+pub struct Constraint {
+    r#type: String,
+    columns: String,
+    definition: String,
+}
+
+impl TableRow for Constraint {
+    fn fields(&self) -> Vec<String> {
+        vec![
+            "type".to_string(),
+            "columns".to_string(),
+            "definition".to_string(),
+        ]
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.r#type.to_string(),
+            self.columns.to_string(),
+            self.definition.to_string(),
+        ]
+    }
+}
+
+pub struct ForeignKey {
+    name: String,
+    column_name: String,
+    ref_schema: String,
+    ref_table: String,
+    ref_column: String,
+}
+
+impl ForeignKey {
+    fn reference(&self, database: &Database) -> ForeignKeyReference {
+        ForeignKeyReference {
+            column: self.column_name.clone(),
+            ref_database: Some(database.name.clone()),
+            ref_schema: Some(self.ref_schema.clone()),
+            ref_table: self.ref_table.clone(),
+            ref_column: self.ref_column.clone(),
+        }
+    }
+}
+
+impl TableRow for ForeignKey {
+    fn fields(&self) -> Vec<String> {
+        vec![
+            "name".to_string(),
+            "column_name".to_string(),
+            "ref_schema".to_string(),
+            "ref_table".to_string(),
+            "ref_column".to_string(),
+        ]
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.name.to_string(),
+            self.column_name.to_string(),
+            self.ref_schema.to_string(),
+            self.ref_table.to_string(),
+            self.ref_column.to_string(),
+        ]
+    }
+}
+
+pub struct Index {
+    name: String,
+    columns: String,
+    unique: bool,
+}
+
+impl TableRow for Index {
+    fn fields(&self) -> Vec<String> {
+        vec![
+            "name".to_string(),
+            "columns".to_string(),
+            "unique".to_string(),
+            "type".to_string(),
+        ]
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.name.to_string(),
+            self.columns.to_string(),
+            if self.unique { "YES" } else { "NO" }.to_string(),
+            "art".to_string(),
+        ]
+    }
+}
+
+#[async_trait]
+impl Pool for DuckDbPool {
+    async fn get_databases(&self) -> anyhow::Result<Vec<Database>> {
+        let (_, rows) = self
+            .query(
+                "SELECT database_name FROM duckdb_databases() WHERE NOT internal ORDER BY database_name",
+                &[],
+            )
+            .await?;
+        let mut list = vec![];
+        for name in rows.into_iter().flatten() {
+            list.push(Database::new(
+                name.clone(),
+                self.get_tables(name.clone()).await?,
+            ))
+        }
+        Ok(list)
+    }
+
+    async fn get_tables(&self, database: String) -> anyhow::Result<Vec<Child>> {
+        let (_, rows) = self
+            .query(
+                "
+                SELECT schema_name, table_name, false FROM duckdb_tables()
+                WHERE database_name = ? AND NOT internal
+                UNION ALL
+                SELECT schema_name, view_name, true FROM duckdb_views()
+                WHERE database_name = ? AND NOT internal AND NOT temporary
+                ORDER BY 1, 2
+                ",
+                &[database.as_str(), database.as_str()],
+            )
+            .await?;
+        let tables = rows.into_iter().map(|row| Table {
+            name: row[1].clone(),
+            create_time: None,
+            update_time: None,
+            engine: None,
+            schema: Some(row[0].clone()),
+            view: row[2] == "true",
+        });
+        let mut schemas = vec![];
+        for (key, group) in &tables.group_by(|table| table.schema.clone()) {
+            if let Some(key) = key {
+                schemas.push(
+                    Schema {
+                        name: key,
+                        tables: group.collect(),
+                    }
+                    .into(),
+                )
+            }
+        }
+        Ok(schemas)
+    }
+
+    async fn get_records(
+        &self,
+        database: &Database,
+        table: &Table,
+        page: usize,
+        filter: Option<String>,
+    ) -> anyhow::Result<(Vec<String>, Vec<Vec<Option<String>>>)> {
+        self.query(
+            self.records_query(database, table, page, filter).as_str(),
+            &[],
+        )
+        .await
+    }
+
+    async fn get_records_after(
+        &self,
+        database: &Database,
+        table: &Table,
+        primary_key: &[String],
+        key: Option<&[String]>,
+        filter: Option<String>,
+    ) -> anyhow::Result<KeysetPage<Option<String>>> {
+        let (headers, records) = self
+            .query(
+                self.records_after_query(database, table, primary_key, key, filter)
+                    .as_str(),
+                &[],
+            )
+            .await?;
+        Ok(split_key(headers, records, primary_key.len()))
+    }
+
+    async fn get_values(
+        &self,
+        database: &Database,
+        table: &Table,
+        page: usize,
+        primary_key: &[String],
+        key: Option<&[String]>,
+    ) -> anyhow::Result<KeysetPage<Option<String>>> {
+        // offsets only page a table in an order which stays the same, which the row ids give
+        let query = if primary_key.is_empty() {
+            format!(
+                "SELECT * FROM {} ORDER BY rowid LIMIT {} OFFSET {}",
+                self.table_name(database, table),
+                RECORDS_LIMIT_PER_PAGE,
+                page
+            )
+        } else {
+            self.records_after_query(database, table, primary_key, key, None)
+        };
+        let (headers, records) = self.query(query.as_str(), &[]).await?;
+        Ok(split_key(headers, records, primary_key.len()))
+    }
+
+    async fn get_primary_key(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<String>> {
+        let (_, rows) = self
+            .query(
+                "
+                SELECT unnest(constraint_column_names) FROM duckdb_constraints()
+                WHERE database_name = ? AND schema_name = ? AND table_name = ? AND constraint_type = 'PRIMARY KEY'
+                ",
+                &[database.name.as_str(), schema(table), table.name.as_str()],
+            )
+            .await?;
+        Ok(rows.into_iter().flatten().collect())
+    }
+
+    async fn get_generated_columns(
+        &self,
+        _database: &Database,
+        _table: &Table,
+    ) -> anyhow::Result<Vec<String>> {
+        // duckdb_columns() does not tell the generated columns from the others
+        Ok(vec![])
+    }
+
+    async fn get_columns(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        let (_, rows) = self
+            .query(
+                "
+                SELECT
+                    column_name,
+                    data_type,
+                    CASE WHEN is_nullable THEN 'YES' ELSE 'NO' END,
+                    COALESCE(column_default, ''),
+                    COALESCE(comment, '')
+                FROM
+                    duckdb_columns()
+                WHERE
+                    database_name = ?
+                    AND schema_name = ?
+                    AND table_name = ?
+                ORDER BY
+                    column_index
+                ",
+                &[database.name.as_str(), schema(table), table.name.as_str()],
+            )
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                Box::new(Column {
+                    name: row[0].clone(),
+                    r#type: row[1].clone(),
+                    null: row[2].clone(),
+                    default: row[3].clone(),
+                    comment: row[4].clone(),
+                }) as Box<dyn TableRow>
+            })
+            .collect())
+    }
+
+    async fn get_constraints(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        // DuckDB names no constraints, so they are told apart by their columns and definitions
+        let (_, rows) = self
+            .query(
+                "
+                SELECT
+                    constraint_type,
+                    array_to_string(constraint_column_names, ', '),
+                    constraint_text
+                FROM
+                    duckdb_constraints()
+                WHERE
+                    database_name = ?
+                    AND schema_name = ?
+                    AND table_name = ?
+                    AND constraint_type <> 'NOT NULL'
+                ORDER BY
+                    constraint_index
+                ",
+                &[database.name.as_str(), schema(table), table.name.as_str()],
+            )
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                Box::new(Constraint {
+                    r#type: row[0].clone(),
+                    columns: row[1].clone(),
+                    definition: row[2].clone(),
+                }) as Box<dyn TableRow>
+            })
+            .collect())
+    }
+
+    async fn get_foreign_keys(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        Ok(self
+            .foreign_keys(database, table)
+            .await?
+            .into_iter()
+            .map(|foreign_key| Box::new(foreign_key) as Box<dyn TableRow>)
+            .collect())
+    }
+
+    async fn get_foreign_key_references(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<ForeignKeyReference>> {
+        Ok(self
+            .foreign_keys(database, table)
+            .await?
+            .iter()
+            .map(|foreign_key| foreign_key.reference(database))
+            .collect())
+    }
+
+    async fn get_child_references(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<ChildReference>> {
+        let (_, rows) = self
+            .query(
+                "
+                SELECT
+                    kcu.table_schema,
+                    kcu.table_name,
+                    kcu.column_name,
+                    ukcu.column_name
+                FROM
+                    information_schema.referential_constraints rc
+                    JOIN information_schema.key_column_usage kcu ON kcu.constraint_name = rc.constraint_name
+                    AND kcu.constraint_schema = rc.constraint_schema
+                    JOIN information_schema.key_column_usage ukcu ON ukcu.constraint_name = rc.unique_constraint_name
+                    AND ukcu.constraint_schema = rc.unique_constraint_schema
+                    AND ukcu.ordinal_position = kcu.position_in_unique_constraint
+                WHERE
+                    ukcu.table_catalog = ?
+                    AND ukcu.table_schema = ?
+                    AND ukcu.table_name = ?
+                ORDER BY
+                    kcu.table_schema,
+                    kcu.table_name
+                ",
+                &[database.name.as_str(), schema(table), table.name.as_str()],
+            )
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| ChildReference {
+                table: Table {
+                    name: row[1].clone(),
+                    create_time: None,
+                    update_time: None,
+                    engine: None,
+                    schema: Some(row[0].clone()),
+                    view: false,
+                },
+                column: row[2].clone(),
+                ref_column: row[3].clone(),
+            })
+            .collect())
+    }
+
+    async fn get_indexes(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        let (_, rows) = self
+            .query(
+                "
+                SELECT index_name, COALESCE(CAST(expressions AS VARCHAR), ''), is_unique
+                FROM duckdb_indexes()
+                WHERE database_name = ? AND schema_name = ? AND table_name = ?
+                ORDER BY index_name
+                ",
+                &[database.name.as_str(), schema(table), table.name.as_str()],
+            )
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                Box::new(Index {
+                    name: row[0].clone(),
+                    columns: row[1].clone(),
+                    unique: row[2] == "true",
+                }) as Box<dyn TableRow>
+            })
+            .collect())
+    }
+
+    async fn get_definition(&self, database: &Database, table: &Table) -> anyhow::Result<String> {
+        let (_, rows) = self
+            .query(
+                "
+                SELECT sql FROM duckdb_tables() WHERE database_name = ? AND schema_name = ? AND table_name = ?
+                UNION ALL
+                SELECT sql FROM duckdb_views() WHERE database_name = ? AND schema_name = ? AND view_name = ?
+                UNION ALL
+                SELECT sql FROM duckdb_indexes()
+                WHERE database_name = ? AND schema_name = ? AND table_name = ? AND sql IS NOT NULL
+                ",
+                &[database.name.as_str(), schema(table), table.name.as_str()].repeat(3),
+            )
+            .await?;
+        Ok(rows.into_iter().flatten().join("\n\n"))
+    }
+
+    async fn get_dump_definition(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<DumpDefinition> {
+        // DuckDB adds no foreign key to a table once it is created, so the definition keeps them
+        Ok(DumpDefinition {
+            definition: self.get_definition(database, table).await?,
+            ..DumpDefinition::default()
+        })
+    }
+
+    async fn get_triggers(
+        &self,
+        _database: &Database,
+        _table: &Table,
+    ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        // DuckDB has no triggers
+        Ok(vec![])
+    }
+
+    async fn get_table_status(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<TableStatus> {
+        let (_, rows) = self
+            .query(
+                "
+                SELECT estimated_size FROM duckdb_tables()
+                WHERE database_name = ? AND schema_name = ? AND table_name = ?
+                ",
+                &[database.name.as_str(), schema(table), table.name.as_str()],
+            )
+            .await?;
+        Ok(TableStatus {
+            rows: rows
+                .into_iter()
+                .flatten()
+                .next()
+                .and_then(|rows| rows.parse().ok()),
+            estimated: true,
+            ..TableStatus::default()
+        })
+    }
+
+    async fn get_routine_definition(
+        &self,
+        _database: &Database,
+        _routine: &Routine,
+    ) -> anyhow::Result<String> {
+        // the macros of DuckDB are not listed as routines
+        Ok(String::new())
+    }
+
+    async fn get_scheduled_jobs(&self) -> anyhow::Result<Vec<ScheduledJob>> {
+        Ok(vec![])
+    }
+
+    async fn insert_records(
+        &self,
+        database: &Database,
+        table: &Table,
+        headers: &[String],
+        rows: &[Vec<String>],
+    ) -> anyhow::Result<u64> {
+        let query = insert_statement(
+            Engine::DuckDb,
+            self.table_name(database, table).as_str(),
+            '"',
+            headers,
+            rows,
+        );
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || Ok(lock(&conn)?.execute(query.as_str(), [])? as u64))
+            .await?
+    }
+
+    async fn session(&self) -> anyhow::Result<Box<dyn Session>> {
+        let conn = lock(&self.conn)?.try_clone()?;
+        for statement in &self.statements {
+            conn.execute_batch(statement)?;
+        }
+        let id = self.next_session.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut interrupts) = self.interrupts.lock() {
+            interrupts.insert(id, conn.interrupt_handle());
+        }
+        Ok(Box::new(DuckDbSession {
+            conn: Arc::new(Mutex::new(conn)),
+            id,
+            interrupts: Arc::clone(&self.interrupts),
+        }))
+    }
+
+    async fn cancel(&self, token: CancelToken) -> anyhow::Result<()> {
+        if let CancelToken::DuckDb(id) = token {
+            if let Some(handle) = self
+                .interrupts
+                .lock()
+                .ok()
+                .and_then(|interrupts| interrupts.get(&id).cloned())
+            {
+                handle.interrupt();
+            }
+        }
+        Ok(())
+    }
+
+    async fn close(&self) {}
+
+    fn identifier_quote(&self) -> char {
+        '"'
+    }
+
+    fn table_name(&self, database: &Database, table: &Table) -> String {
+        format!(
+            r#""{}"."{}"."{}""#,
+            database.name,
+            schema(table),
+            table.name
+        )
+    }
+
+    fn call_statement(
+        &self,
+        _database: &Database,
+        _routine: &Routine,
+        _arguments: &[Option<String>],
+    ) -> String {
+        String::new()
+    }
+
+    fn text_syntax(&self) -> TextSyntax {
+        TextSyntax {
+            cast: "CAST({text} AS VARCHAR)",
+            length: "LENGTH({text})",
+            regex: Some("regexp_matches({text}, {pattern})"),
+        }
+    }
+
+    fn analyze_statement(&self, _database: &Database, _table: &Table) -> String {
+        // DuckDB keeps the statistics of a whole database up to date on its own, and ANALYZE refreshes them all
+        "ANALYZE".to_string()
+    }
+
+    fn enable_job_statement(&self, _job: &ScheduledJob, _enable: bool) -> Option<String> {
+        None
+    }
+
+    fn server_version(&self) -> &ServerVersion {
+        &self.version
+    }
+
+    fn engine(&self) -> Engine {
+        Engine::DuckDb
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        match feature {
+            Feature::ExplainAnalyze | Feature::CommonTableExpressions | Feature::Returning => true,
+            Feature::MaterializedViews | Feature::LockNowait => false,
+        }
+    }
+}
+
+/// a connection to the database of the pool running statements one after another
+pub struct DuckDbSession {
+    conn: Arc<Mutex<Connection>>,
+    id: usize,
+    interrupts: Interrupts,
+}
+
+impl Drop for DuckDbSession {
+    fn drop(&mut self) {
+        if let Ok(mut interrupts) = self.interrupts.lock() {
+            interrupts.remove(&self.id);
+        }
+    }
+}
+
+#[async_trait]
+impl Session for DuckDbSession {
+    fn cancel_token(&mut self) -> CancelToken {
+        CancelToken::DuckDb(self.id)
+    }
+
+    async fn run(&mut self, statement: &str, mode: StatementMode) -> anyhow::Result<ExecuteResult> {
+        let conn = Arc::clone(&self.conn);
+        let statement = statement.to_string();
+        tokio::task::spawn_blocking(move || {
+            let conn = lock(&conn)?;
+            match mode {
+                StatementMode::Execute if is_read_query(statement.as_str()) => {
+                    let (headers, rows) = query_values(&conn, statement.as_str(), &[])?;
+                    Ok(ExecuteResult::Read { headers, rows })
+                }
+                StatementMode::Execute => Ok(ExecuteResult::Write {
+                    updated_rows: conn.execute(statement.as_str(), [])? as u64,
+                }),
+                // the plan is drawn as a tree of boxes, whose lines are shown as they are
+                StatementMode::Explain { analyze } => {
+                    let query = format!(
+                        "EXPLAIN {}{}",
+                        if analyze { "ANALYZE " } else { "" },
+                        statement
+                    );
+                    let (_, rows) = query_values(&conn, query.as_str(), &[])?;
+                    Ok(ExecuteResult::Plan(
+                        rows.iter()
+                            .filter_map(|row| row.last()?.as_deref())
+                            .flat_map(|plan| plan.lines())
+                            .filter(|line| !line.trim().is_empty())
+                            .map(|line| PlanNode::new(line.to_string()))
+                            .collect(),
+                    ))
+                }
+            }
+        })
+        .await?
+    }
+
+    async fn apply_settings(
+        &mut self,
+        settings: &[(String, String)],
+    ) -> anyhow::Result<Vec<(String, String)>> {
+        let conn = Arc::clone(&self.conn);
+        let settings = settings.to_vec();
+        tokio::task::spawn_blocking(move || {
+            let conn = lock(&conn)?;
+            let mut previous = vec![];
+            for (name, value) in settings {
+                let statement = setting_statement("SET", name.as_str(), value.as_str())?;
+                let (_, rows) = query_values(
+                    &conn,
+                    format!("SELECT current_setting('{}')", name).as_str(),
+                    &[],
+                )?;
+                previous.push((
+                    name,
+                    rows.into_iter()
+                        .flatten()
+                        .flatten()
+                        .next()
+                        .unwrap_or_default(),
+                ));
+                conn.execute_batch(statement.as_str())?;
+            }
+            Ok(previous)
+        })
+        .await?
+    }
+
+    async fn lock_rows(&mut self, _statement: &str) -> anyhow::Result<bool> {
+        Err(anyhow::anyhow!(
+            "{} is not supported by DuckDB",
+            Feature::LockNowait.name()
+        ))
+    }
+
+    async fn warnings(&mut self) -> anyhow::Result<Vec<String>> {
+        Ok(vec![])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{query_values, Connection};
+
+    #[test]
+    fn test_query_values() {
+        let conn = Connection::open_in_memory().unwrap();
+        let (headers, rows) = query_values(
+            &conn,
+            "SELECT 1 AS id, ? AS name, NULL AS note, DATE '2024-02-29' AS day, [1, 2] AS list",
+            &["gobang".to_string()],
+        )
+        .unwrap();
+        assert_eq!(headers, vec!["id", "name", "note", "day", "list"]);
+        assert_eq!(
+            rows,
+            vec![vec![
+                Some("1".to_string()),
+                Some("gobang".to_string()),
+                None,
+                Some("2024-02-29".to_string()),
+                Some("[1, 2]".to_string())
+            ]]
+        );
+    }
+}
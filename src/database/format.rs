@@ -0,0 +1,762 @@
+use super::Engine;
+use serde::Deserialize;
+use std::ops::Range;
+use unicode_width::UnicodeWidthStr;
+
+/// how the case of keywords is changed by `format_sql`
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeywordCase {
+    Upper,
+    Lower,
+    Preserve,
+}
+
+/// how `format_sql` lays out a query
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// the engine whose syntax the query is read with
+    pub engine: Engine,
+    pub keyword_case: KeywordCase,
+    /// the spaces each level of clauses, listed items and subqueries is indented by
+    pub indent_width: usize,
+    /// the width beyond which a clause puts each of its items on its own line
+    pub max_line_width: usize,
+}
+
+/// the keywords MySQL reserves, which cannot name its tables unquoted, so that changing their case never changes the
+/// table a query reads on a server whose table names are case sensitive
+const RESERVED: &[&str] = &[
+    "ADD",
+    "ALL",
+    "ALTER",
+    "AND",
+    "AS",
+    "ASC",
+    "BETWEEN",
+    "BY",
+    "CASCADE",
+    "CASE",
+    "CHECK",
+    "COLUMN",
+    "CONSTRAINT",
+    "CREATE",
+    "CROSS",
+    "DEFAULT",
+    "DELETE",
+    "DESC",
+    "DISTINCT",
+    "DROP",
+    "ELSE",
+    "EXISTS",
+    "FALSE",
+    "FOR",
+    "FOREIGN",
+    "FROM",
+    "GROUP",
+    "HAVING",
+    "IF",
+    "IGNORE",
+    "IN",
+    "INDEX",
+    "INNER",
+    "INSERT",
+    "INTERVAL",
+    "INTO",
+    "IS",
+    "JOIN",
+    "KEY",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "NATURAL",
+    "NOT",
+    "NULL",
+    "ON",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "OVER",
+    "PARTITION",
+    "PRIMARY",
+    "REFERENCES",
+    "REGEXP",
+    "REPLACE",
+    "RIGHT",
+    "SELECT",
+    "SET",
+    "TABLE",
+    "THEN",
+    "TRUE",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USING",
+    "VALUES",
+    "WHEN",
+    "WHERE",
+    "WINDOW",
+    "WITH",
+];
+
+/// the keywords which are only cased on the engines whose unquoted names do not depend on their case
+const KEYWORDS: &[&str] = &[
+    "ANTI",
+    "BEGIN",
+    "COMMIT",
+    "CONFLICT",
+    "DO",
+    "END",
+    "EXCEPT",
+    "FETCH",
+    "FIRST",
+    "FULL",
+    "ILIKE",
+    "INTERSECT",
+    "LAST",
+    "LATERAL",
+    "NEXT",
+    "NOTHING",
+    "NULLS",
+    "OFFSET",
+    "ONLY",
+    "QUALIFY",
+    "RECURSIVE",
+    "RETURNING",
+    "ROLLBACK",
+    "ROWS",
+    "SEMI",
+    "TEMPORARY",
+    "TRUNCATE",
+    "VIEW",
+];
+
+/// the keywords which MySQL also has as functions, such as `LEFT(name, 3)`, whose parenthesis keeps the spacing it
+/// was written with since MySQL reads a built-in function followed by a space differently
+const FUNCTION_KEYWORDS: &[&str] = &[
+    "DEFAULT", "IF", "INSERT", "INTERVAL", "LEFT", "REPLACE", "RIGHT", "VALUES",
+];
+
+const JOIN_MODIFIERS: &[&str] = &[
+    "INNER", "LEFT", "RIGHT", "FULL", "OUTER", "CROSS", "NATURAL",
+];
+
+/// the operators of more than one character, the longest first
+const OPERATORS: &[&str] = &[
+    "->>", "#>>", "::", "<=", ">=", "<>", "!=", "||", "->", "#>", ":=", "=>", "<<", ">>", "@>",
+    "<@", "&&",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Token {
+    Word(String),
+    /// a quoted name, a string literal or a number, written as it is
+    Verbatim(String),
+    /// a comment running to the end of its line
+    LineComment(String),
+    BlockComment(String),
+    Punct(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Token(Token),
+    /// the nodes between parentheses, whether the closing one was written, and whether a space preceded the opening
+    /// one
+    Group {
+        nodes: Vec<Node>,
+        closed: bool,
+        spaced: bool,
+    },
+}
+
+/// pretty-prints `sql`, starting each clause on its own line, indenting subqueries and the columns of a created
+/// table, and putting the conditions of a WHERE, a HAVING or a join on lines of their own, while the names, literals
+/// and comments stay as they were written
+pub fn format_sql(sql: &str, options: &FormatOptions) -> String {
+    let tokens = tokenize(sql, options.engine);
+    let (nodes, _) = group(&mut tokens.into_iter(), false);
+    let formatter = Formatter { options };
+    let statements = nodes
+        .split(|node| *node == Node::Token(Token::Punct(";".to_string())))
+        .collect::<Vec<&[Node]>>();
+    let mut formatted = vec![];
+    for (index, statement) in statements.iter().enumerate() {
+        if statement.is_empty() {
+            continue;
+        }
+        let mut lines = formatter.statement(statement, 0);
+        if index + 1 < statements.len() {
+            // a semicolon after a line comment would be part of it
+            match (statement.last(), lines.last_mut()) {
+                (Some(Node::Token(Token::LineComment(_))), _) | (_, None) => {
+                    lines.push(";".to_string())
+                }
+                (_, Some(last)) => last.push(';'),
+            }
+        }
+        formatted.push(lines.join("\n"));
+    }
+    formatted.join("\n\n")
+}
+
+/// splits `sql` into tokens, keeping comments and literals, with whether whitespace precedes each one
+fn tokenize(sql: &str, engine: Engine) -> Vec<(Token, bool)> {
+    let mut end = 0;
+    located_tokens(sql, engine)
+        .into_iter()
+        .map(|(range, token)| {
+            let spaced = range.start > end;
+            end = range.end;
+            (token, spaced)
+        })
+        .collect()
+}
+
+/// splits `sql` into tokens with the range of characters each one spans, keeping comments and literals
+pub(super) fn located_tokens(sql: &str, engine: Engine) -> Vec<(Range<usize>, Token)> {
+    let chars = sql.chars().collect::<Vec<char>>();
+    let text = |range: Range<usize>| chars[range].iter().collect::<String>();
+    let mut tokens = vec![];
+    let mut index = 0;
+    while index < chars.len() {
+        let start = index;
+        let c = chars[index];
+        index += 1;
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            // MySQL only reads `--` followed by a space as a comment, so that `5--1` subtracts
+            '-' if chars.get(index) == Some(&'-')
+                && (engine != Engine::MySql
+                    || index + 1 == chars.len()
+                    || chars[index + 1].is_whitespace()) =>
+            {
+                while index < chars.len() && chars[index] != '\n' {
+                    index += 1;
+                }
+                Token::LineComment(text(start..index).trim_end().to_string())
+            }
+            '#' if engine == Engine::MySql => {
+                while index < chars.len() && chars[index] != '\n' {
+                    index += 1;
+                }
+                Token::LineComment(text(start..index).trim_end().to_string())
+            }
+            '/' if chars.get(index) == Some(&'*') => {
+                index += 1;
+                while index < chars.len() && !(chars[index - 1] == '*' && chars[index] == '/') {
+                    index += 1;
+                }
+                index = (index + 1).min(chars.len());
+                Token::BlockComment(text(start..index))
+            }
+            '\'' | '"' | '`' => {
+                index = quoted_end(&chars, index, c, engine == Engine::MySql && c != '`');
+                Token::Verbatim(text(start..index))
+            }
+            // SQLite quotes names with brackets, which the others index arrays with
+            '[' if engine == Engine::Sqlite => {
+                index = quoted_end(&chars, index, ']', false);
+                Token::Verbatim(text(start..index))
+            }
+            // a string between tags such as $body$, which PostgreSQL writes functions with
+            '$' if matches!(engine, Engine::Postgres | Engine::DuckDb)
+                && dollar_tag(&chars, start).is_some() =>
+            {
+                let tag = dollar_tag(&chars, start).unwrap_or_default();
+                index = start + tag.len();
+                while index < chars.len() && !chars[index..].starts_with(&tag) {
+                    index += 1;
+                }
+                index = (index + tag.len()).min(chars.len());
+                Token::Verbatim(text(start..index))
+            }
+            c if c.is_ascii_digit()
+                || (c == '.' && chars.get(index).map_or(false, char::is_ascii_digit)) =>
+            {
+                while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '.')
+                {
+                    index += 1;
+                    // the sign of an exponent such as 1e-5
+                    if matches!(chars[index - 1], 'e' | 'E')
+                        && matches!(chars.get(index), Some('+') | Some('-'))
+                    {
+                        index += 1;
+                    }
+                }
+                Token::Verbatim(text(start..index))
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                while index < chars.len()
+                    && (chars[index].is_alphanumeric() || matches!(chars[index], '_' | '$'))
+                {
+                    index += 1;
+                }
+                // the prefix of a literal such as E'\n' or X'ff'
+                if index - start == 1
+                    && matches!(c.to_ascii_uppercase(), 'E' | 'X' | 'B' | 'N')
+                    && chars.get(index) == Some(&'\'')
+                {
+                    let escaped = engine == Engine::MySql || c.eq_ignore_ascii_case(&'E');
+                    index = quoted_end(&chars, index + 1, '\'', escaped);
+                    Token::Verbatim(text(start..index))
+                } else {
+                    Token::Word(text(start..index))
+                }
+            }
+            _ => {
+                let operator = OPERATORS
+                    .iter()
+                    .find(|operator| {
+                        chars[start..]
+                            .iter()
+                            .take(operator.len())
+                            .copied()
+                            .eq(operator.chars())
+                    })
+                    .map_or_else(|| c.to_string(), |operator| operator.to_string());
+                index = start + operator.chars().count();
+                Token::Punct(operator)
+            }
+        };
+        tokens.push((start..index, token));
+    }
+    tokens
+}
+
+/// returns the index after the quote closing the one before `index`, where a doubled quote and, when `escaped`, a
+/// backslash escape the next character
+fn quoted_end(chars: &[char], mut index: usize, quote: char, escaped: bool) -> usize {
+    while index < chars.len() {
+        match chars[index] {
+            '\\' if escaped => index += 2,
+            c if c == quote && chars.get(index + 1) == Some(&quote) => index += 2,
+            c if c == quote => return index + 1,
+            _ => index += 1,
+        }
+    }
+    chars.len()
+}
+
+/// returns the tag such as `$body$` starting at `start`, rather than a parameter such as `$1`
+fn dollar_tag(chars: &[char], start: usize) -> Option<Vec<char>> {
+    let end = chars[start + 1..].iter().position(|c| *c == '$')? + start + 1;
+    let name = &chars[start + 1..end];
+    if name.first().map_or(false, char::is_ascii_digit)
+        || !name.iter().all(|c| c.is_alphanumeric() || *c == '_')
+    {
+        return None;
+    }
+    Some(chars[start..=end].to_vec())
+}
+
+/// nests the tokens between parentheses, up to the one closing the group when `nested`, returning whether it was
+/// closed
+fn group(tokens: &mut std::vec::IntoIter<(Token, bool)>, nested: bool) -> (Vec<Node>, bool) {
+    let mut nodes = vec![];
+    while let Some((token, spaced)) = tokens.next() {
+        match token {
+            Token::Punct(p) if p == "(" => {
+                let (inner, closed) = group(tokens, true);
+                nodes.push(Node::Group {
+                    nodes: inner,
+                    closed,
+                    spaced,
+                });
+            }
+            Token::Punct(p) if p == ")" && nested => return (nodes, true),
+            token => nodes.push(Node::Token(token)),
+        }
+    }
+    (nodes, false)
+}
+
+fn word(node: Option<&Node>) -> Option<String> {
+    match node {
+        Some(Node::Token(Token::Word(word))) => Some(word.to_uppercase()),
+        _ => None,
+    }
+}
+
+fn is_subquery(nodes: &[Node]) -> bool {
+    matches!(
+        word(nodes.first()).as_deref(),
+        Some("SELECT") | Some("WITH")
+    )
+}
+
+/// tells whether a clause such as a FROM or a LEFT JOIN starts at `index` of a statement
+fn is_clause_start(nodes: &[Node], index: usize) -> bool {
+    let current = match word(nodes.get(index)) {
+        Some(word) => word,
+        None => return false,
+    };
+    let previous = index
+        .checked_sub(1)
+        .and_then(|index| word(nodes.get(index)))
+        .unwrap_or_default();
+    let next = word(nodes.get(index + 1)).unwrap_or_default();
+    match current.as_str() {
+        "SELECT" | "WHERE" | "HAVING" | "LIMIT" | "OFFSET" | "RETURNING" | "WINDOW" | "QUALIFY"
+        | "UNION" | "EXCEPT" | "INTERSECT" | "INSERT" => true,
+        // such as DELETE FROM and IS DISTINCT FROM
+        "FROM" => !matches!(previous.as_str(), "DELETE" | "DISTINCT"),
+        "VALUES" => previous != "DEFAULT",
+        "GROUP" | "ORDER" => next == "BY",
+        // such as ON DELETE CASCADE
+        "DELETE" => previous != "ON",
+        // such as FOR UPDATE and ON DUPLICATE KEY UPDATE
+        "UPDATE" => !matches!(previous.as_str(), "FOR" | "KEY" | "DO" | "ON" | "NO"),
+        "SET" => !matches!(previous.as_str(), "CHARACTER" | "DELETE" | "UPDATE"),
+        "JOIN" => !JOIN_MODIFIERS.contains(&previous.as_str()),
+        "INNER" | "CROSS" => next == "JOIN",
+        "NATURAL" => true,
+        // LEFT and RIGHT are functions as well
+        "LEFT" | "RIGHT" | "FULL" => {
+            previous != "NATURAL" && matches!(next.as_str(), "JOIN" | "OUTER" | "SEMI" | "ANTI")
+        }
+        "ON" => matches!(next.as_str(), "CONFLICT" | "DUPLICATE"),
+        "FOR" => matches!(next.as_str(), "UPDATE" | "SHARE" | "NO"),
+        "FETCH" => matches!(next.as_str(), "FIRST" | "NEXT"),
+        _ => false,
+    }
+}
+
+/// returns how many words name the clause starting `nodes`, such as the three of LEFT OUTER JOIN
+fn head_len(nodes: &[Node]) -> usize {
+    let words = nodes
+        .iter()
+        .map(|node| word(Some(node)).unwrap_or_default())
+        .collect::<Vec<String>>();
+    let count = |from: usize, allowed: &[&str]| {
+        from + words[from..]
+            .iter()
+            .take_while(|word| allowed.contains(&word.as_str()))
+            .count()
+    };
+    match words.first().map(String::as_str) {
+        Some("SELECT") => count(1, &["DISTINCT", "ALL"]).min(2),
+        Some("GROUP") | Some("ORDER") => 2,
+        Some("INSERT") => count(1, &["IGNORE", "OR", "REPLACE", "INTO"]),
+        Some("DELETE") => count(1, &["FROM"]).min(2),
+        Some("UNION") | Some("EXCEPT") | Some("INTERSECT") => count(1, &["ALL", "DISTINCT"]).min(2),
+        Some("ON") => count(1, &["CONFLICT", "DUPLICATE", "KEY", "UPDATE"]),
+        Some("FOR") => count(1, &["NO", "KEY", "UPDATE", "SHARE"]),
+        Some(word) if JOIN_MODIFIERS.contains(&word) || word == "JOIN" => {
+            let modifiers = count(0, JOIN_MODIFIERS);
+            count(modifiers, &["JOIN", "SEMI", "ANTI"])
+        }
+        Some(_) if is_clause_start(nodes, 0) => 1,
+        _ => 0,
+    }
+}
+
+struct Formatter<'a> {
+    options: &'a FormatOptions,
+}
+
+impl Formatter<'_> {
+    fn pad(&self, indent: usize) -> String {
+        " ".repeat(indent * self.options.indent_width)
+    }
+
+    fn is_keyword(&self, word: &str) -> bool {
+        let upper = word.to_uppercase();
+        RESERVED.contains(&upper.as_str())
+            || (self.options.engine != Engine::MySql && KEYWORDS.contains(&upper.as_str()))
+    }
+
+    /// changes the case of a keyword, leaving the names qualified with a dot as they are
+    fn word(&self, word: &str, qualified: bool) -> String {
+        if !self.is_keyword(word) || qualified {
+            return word.to_string();
+        }
+        match self.options.keyword_case {
+            KeywordCase::Upper => word.to_uppercase(),
+            KeywordCase::Lower => word.to_lowercase(),
+            KeywordCase::Preserve => word.to_string(),
+        }
+    }
+
+    /// lays out the clauses of a statement, each starting on a line at `indent`
+    fn statement(&self, nodes: &[Node], indent: usize) -> Vec<String> {
+        let mut lines = vec![];
+        let mut start = 0;
+        // the comments before the statement, such as a `-- gobang: fetch=all` one, keep their own lines
+        while let Some(Node::Token(Token::LineComment(comment))) = nodes.get(start) {
+            lines.push(format!("{}{}", self.pad(indent), comment));
+            start += 1;
+        }
+        for index in start + 1..=nodes.len() {
+            if index == nodes.len() || is_clause_start(nodes, index) {
+                lines.extend(self.clause(&nodes[start..index], indent));
+                start = index;
+            }
+        }
+        lines
+    }
+
+    /// lays out a clause on one line, or with each of its items on its own line when it is too wide
+    fn clause(&self, nodes: &[Node], indent: usize) -> Vec<String> {
+        let head_len = head_len(nodes);
+        let head = nodes[..head_len]
+            .iter()
+            .filter_map(|node| match node {
+                Node::Token(Token::Word(word)) => Some(self.word(word, false)),
+                _ => None,
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+        let body = &nodes[head_len..];
+        let conditions = matches!(head.to_uppercase().as_str(), "WHERE" | "HAVING")
+            || head.to_uppercase().ends_with("JOIN");
+        let inline = self.inline(body, indent, conditions);
+        let line = match (head.is_empty(), inline.is_empty()) {
+            (true, _) => format!("{}{}", self.pad(indent), inline),
+            (false, true) => format!("{}{}", self.pad(indent), head),
+            (false, false) => format!("{}{} {}", self.pad(indent), head, inline),
+        };
+        let items = body
+            .split(|node| *node == Node::Token(Token::Punct(",".to_string())))
+            .collect::<Vec<&[Node]>>();
+        let commented = body
+            .iter()
+            .any(|node| matches!(node, Node::Token(Token::LineComment(_))));
+        let wide =
+            line.lines().next().map_or(0, UnicodeWidthStr::width) > self.options.max_line_width;
+        if head.is_empty() || items.len() < 2 || !(wide || commented) {
+            return line.lines().map(str::to_string).collect();
+        }
+        let mut lines = vec![format!("{}{}", self.pad(indent), head)];
+        for (index, item) in items.iter().enumerate() {
+            // the comma goes before a comment ending the item
+            let (item, comment) = match item.split_last() {
+                Some((Node::Token(Token::LineComment(comment)), item)) => {
+                    (item, format!(" {}", comment))
+                }
+                _ => (*item, String::new()),
+            };
+            let text = format!(
+                "{}{}{}{}",
+                self.pad(indent + 1),
+                self.inline(item, indent + 1, false),
+                if index + 1 < items.len() { "," } else { "" },
+                comment
+            );
+            lines.extend(text.lines().map(str::to_string));
+        }
+        lines
+    }
+
+    /// writes the nodes on a line, breaking it only before the conditions joined by AND or OR when `conditions`, after
+    /// line comments, and around subqueries and the columns of a created table
+    fn inline(&self, nodes: &[Node], indent: usize, conditions: bool) -> String {
+        let mut text = String::new();
+        // whether the line was just broken, so that no space is written before the next node
+        let mut fresh = true;
+        // whether the line is broken before the next node, as it is after a line comment
+        let mut broken = false;
+        // the indent of the line being written, deeper after it was broken
+        let mut line_indent = indent;
+        let mut between = false;
+        let mut unary = false;
+        let defines = word(nodes.first()).as_deref() == Some("CREATE");
+        let mut table_named = false;
+        for (index, node) in nodes.iter().enumerate() {
+            let previous = index.checked_sub(1).map(|index| &nodes[index]);
+            let current = word(Some(node)).unwrap_or_default();
+            match current.as_str() {
+                "BETWEEN" => between = true,
+                "AND" if between => between = false,
+                "AND" | "OR" if conditions && !text.is_empty() => broken = true,
+                "TABLE" if defines => table_named = true,
+                _ => (),
+            }
+            if broken {
+                text.push('\n');
+                text.push_str(self.pad(indent + 1).as_str());
+                fresh = true;
+                broken = false;
+                line_indent = indent + 1;
+            }
+            if !fresh && !unary && self.spaced(previous, node) {
+                text.push(' ');
+            }
+            unary = matches!(node, Node::Token(Token::Punct(p)) if p == "-" || p == "+")
+                && match previous {
+                    None => true,
+                    Some(Node::Token(Token::Punct(_))) => true,
+                    Some(Node::Token(Token::Word(word))) => self.is_keyword(word),
+                    _ => false,
+                };
+            fresh = false;
+            match node {
+                Node::Token(Token::Word(word)) => {
+                    let dot = |node: Option<&Node>| matches!(node, Some(Node::Token(Token::Punct(p))) if p == ".");
+                    text.push_str(
+                        self.word(word, dot(previous) || dot(nodes.get(index + 1)))
+                            .as_str(),
+                    )
+                }
+                Node::Token(Token::LineComment(comment)) => {
+                    text.push_str(comment);
+                    broken = true;
+                }
+                Node::Token(Token::Verbatim(token))
+                | Node::Token(Token::BlockComment(token))
+                | Node::Token(Token::Punct(token)) => text.push_str(token),
+                Node::Group {
+                    nodes: inner,
+                    closed,
+                    ..
+                } => {
+                    let close = if *closed { ")" } else { "" };
+                    if is_subquery(inner) {
+                        text.push_str(
+                            format!(
+                                "(\n{}\n{}{}",
+                                self.statement(inner, line_indent + 1).join("\n"),
+                                self.pad(line_indent),
+                                close
+                            )
+                            .as_str(),
+                        );
+                    } else if table_named {
+                        // the columns and constraints of the created table, one a line
+                        table_named = false;
+                        let items = inner
+                            .split(|node| *node == Node::Token(Token::Punct(",".to_string())))
+                            .map(|item| {
+                                format!(
+                                    "{}{}",
+                                    self.pad(indent + 1),
+                                    self.inline(item, indent + 1, false)
+                                )
+                            })
+                            .collect::<Vec<String>>();
+                        text.push_str(
+                            format!("(\n{}\n{}{}", items.join(",\n"), self.pad(indent), close)
+                                .as_str(),
+                        );
+                    } else {
+                        text.push_str(
+                            format!("({}{}", self.inline(inner, indent, false), close).as_str(),
+                        );
+                    }
+                }
+            }
+        }
+        text
+    }
+
+    /// tells whether a space is written between `previous` and `node`
+    fn spaced(&self, previous: Option<&Node>, node: &Node) -> bool {
+        let punct = |node: Option<&Node>, puncts: &[&str]| matches!(node, Some(Node::Token(Token::Punct(p))) if puncts.contains(&p.as_str()));
+        if previous.is_none()
+            || punct(Some(node), &[",", ";", ")", ".", "::", "]"])
+            || punct(previous, &["(", ".", "::", "[", "@", "$", ":"])
+        {
+            return false;
+        }
+        match (previous, node) {
+            // an element of an array such as tags[1]
+            (Some(Node::Token(Token::Word(_))), Node::Token(Token::Punct(p))) if p == "[" => false,
+            (Some(Node::Group { .. }), Node::Token(Token::Punct(p))) if p == "[" => false,
+            // a keyword such as IN or AS is followed by a space, while a function such as count(*) and a list of
+            // columns keep the spacing they were written with
+            (Some(Node::Token(Token::Word(word))), Node::Group { .. })
+                if self.is_keyword(word)
+                    && !FUNCTION_KEYWORDS.contains(&word.to_uppercase().as_str()) =>
+            {
+                true
+            }
+            (Some(_), Node::Group { spaced, .. }) => *spaced,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_sql, Engine, FormatOptions, KeywordCase};
+
+    fn options(engine: Engine) -> FormatOptions {
+        FormatOptions {
+            engine,
+            keyword_case: KeywordCase::Upper,
+            indent_width: 2,
+            max_line_width: 40,
+        }
+    }
+
+    #[test]
+    fn test_format_sql() {
+        assert_eq!(
+            format_sql(
+                "select id, name from users u left join orders o on o.user_id = u.id and o.total > 0 where u.id between 1 and 5 or u.name like 'a%' order by id desc limit 10",
+                &options(Engine::Postgres)
+            ),
+            "SELECT id, name\n\
+             FROM users u\n\
+             LEFT JOIN orders o ON o.user_id = u.id\n  AND o.total > 0\n\
+             WHERE u.id BETWEEN 1 AND 5\n  OR u.name LIKE 'a%'\n\
+             ORDER BY id DESC\n\
+             LIMIT 10"
+        );
+        // the items of a wide clause go one a line, and subqueries are indented
+        assert_eq!(
+            format_sql(
+                "SELECT id, count(*) AS order_count, max(created_at) AS last_order FROM orders WHERE user_id IN (SELECT id FROM users WHERE active) GROUP BY id;",
+                &options(Engine::Postgres)
+            ),
+            "SELECT\n  id,\n  count(*) AS order_count,\n  max(created_at) AS last_order\n\
+             FROM orders\n\
+             WHERE user_id IN (\n  SELECT id\n  FROM users\n  WHERE active\n)\n\
+             GROUP BY id;"
+        );
+        assert_eq!(
+            format_sql(
+                "create table t (id int primary key, name text not null);insert into t values (1, 'it''s'), (-2, E'\\n')",
+                &options(Engine::Postgres)
+            ),
+            "CREATE TABLE t (\n  id int PRIMARY KEY,\n  name text NOT NULL\n);\n\n\
+             INSERT INTO t\n\
+             VALUES (1, 'it''s'), (-2, E'\\n')"
+        );
+    }
+
+    #[test]
+    fn test_format_sql_dialects() {
+        // MySQL reads its reserved words only, keeps LEFT() as it was written and has # comments
+        assert_eq!(
+            format_sql(
+                "-- gobang: fetch=all\nselect left(name, 3), `end`, status from t # the table\nwhere a = 'it\\'s' and b -- why\n;",
+                &options(Engine::MySql)
+            ),
+            "-- gobang: fetch=all\n\
+             SELECT LEFT(name, 3), `end`, status\n\
+             FROM t # the table\n\
+             WHERE a = 'it\\'s'\n  AND b -- why\n\
+             ;"
+        );
+        assert_eq!(
+            format_sql(
+                "select $$ x $$, tags[1], $1 from t offset 5",
+                &options(Engine::Postgres)
+            ),
+            "SELECT $$ x $$, tags[1], $1\nFROM t\nOFFSET 5"
+        );
+        assert_eq!(
+            format_sql("select [end] from t", &options(Engine::Sqlite)),
+            "SELECT [end]\nFROM t"
+        );
+        let lower = FormatOptions {
+            keyword_case: KeywordCase::Lower,
+            ..options(Engine::Sqlite)
+        };
+        assert_eq!(
+            format_sql("SELECT A FROM T WHERE B IS NOT NULL", &lower),
+            "select A\nfrom T\nwhere B is not null"
+        );
+    }
+}
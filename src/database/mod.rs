@@ -1,27 +1,269 @@
+pub mod analyzer;
+pub mod dialect;
+#[cfg(feature = "duckdb")]
+pub mod duckdb;
+pub mod format;
+/// runs the drivers against servers started in Docker containers, with `cargo test --features integration`
+#[cfg(all(test, feature = "integration"))]
+mod integration;
 pub mod mysql;
+pub mod plugin;
 pub mod postgres;
 pub mod sqlite;
 
+#[cfg(feature = "duckdb")]
+pub use self::duckdb::DuckDbPool;
+pub use analyzer::qualified_columns;
+pub use dialect::{dialect_hints, functions, unsupported_constructs, DialectHint, Engine};
+pub use format::{format_sql, FormatOptions, KeywordCase};
 pub use mysql::MySqlPool;
+pub use plugin::PluginPool;
 pub use postgres::PostgresPool;
 pub use sqlite::SqlitePool;
 
+use crate::metadata::MetadataCache;
 use async_trait::async_trait;
-use database_tree::{Child, Database, Table};
+use database_tree::{Child, Database, Routine, Table};
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hasher};
+use std::ops::Range;
+use std::time::Duration;
 
 pub const RECORDS_LIMIT_PER_PAGE: u8 = 200;
 
+pub enum ExecuteResult {
+    Read {
+        headers: Vec<String>,
+        /// the values of the rows with `None` for NULL
+        rows: Vec<Vec<Option<String>>>,
+    },
+    Write {
+        updated_rows: u64,
+    },
+    Plan(Vec<PlanNode>),
+}
+
+/// a step of a query plan
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlanNode {
+    pub label: String,
+    pub children: Vec<PlanNode>,
+}
+
+impl PlanNode {
+    pub fn new(label: String) -> Self {
+        Self {
+            label,
+            children: vec![],
+        }
+    }
+
+    /// builds nodes from lines indented by four spaces per level, as MySQL prints `EXPLAIN FORMAT=TREE`
+    pub fn from_indented_lines(text: &str) -> Vec<Self> {
+        fn attach(stack: &mut [(usize, PlanNode)], roots: &mut Vec<PlanNode>, node: PlanNode) {
+            match stack.last_mut() {
+                Some((_, parent)) => parent.children.push(node),
+                None => roots.push(node),
+            }
+        }
+
+        let mut roots = vec![];
+        let mut stack: Vec<(usize, PlanNode)> = vec![];
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let depth = (line.len() - line.trim_start().len()) / 4;
+            while matches!(stack.last(), Some((d, _)) if *d >= depth) {
+                let (_, node) = stack.pop().unwrap();
+                attach(&mut stack, &mut roots, node);
+            }
+            let label = line.trim().trim_start_matches("-> ").to_string();
+            stack.push((depth, PlanNode::new(label)));
+        }
+        while let Some((_, node)) = stack.pop() {
+            attach(&mut stack, &mut roots, node);
+        }
+        roots
+    }
+
+    /// builds nodes from rows of an id, the id of the parent and a label, as SQLite returns `EXPLAIN QUERY PLAN`
+    pub fn from_parent_ids(rows: &[(i64, i64, String)]) -> Vec<Self> {
+        fn children(rows: &[(i64, i64, String)], parent: i64) -> Vec<PlanNode> {
+            rows.iter()
+                .filter(|(id, p, _)| *p == parent && *id != parent)
+                .map(|(id, _, label)| PlanNode {
+                    label: label.clone(),
+                    children: children(rows, *id),
+                })
+                .collect()
+        }
+        children(rows, 0)
+    }
+}
+
+/// a job the server runs on a schedule, such as a MySQL event or a pg_cron job
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScheduledJob {
+    /// what the statements changing the job refer to it by, such as the quoted name of an event or the id of a job
+    pub id: String,
+    pub name: String,
+    pub schedule: String,
+    pub enabled: bool,
+    /// when the job last started, with how that run ended when the server keeps it
+    pub last_run: Option<String>,
+    pub definition: String,
+}
+
+/// how many rows a table has and how much space it takes, shown in the status of its records
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableStatus {
+    pub rows: Option<u64>,
+    /// whether `rows` is the estimate the server keeps rather than a count
+    pub estimated: bool,
+    /// the bytes the rows take, and those the indexes take
+    pub data_size: Option<u64>,
+    pub index_size: Option<u64>,
+    pub engine: Option<String>,
+    pub collation: Option<String>,
+}
+
+/// the statements of a dump creating a table, split so that the tables can be created and filled in any order
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DumpDefinition {
+    /// runs before the table is created, such as creating the sequences the defaults of its columns take values from
+    pub before: Vec<String>,
+    /// creates the table, without the foreign keys when `after` adds them
+    pub definition: String,
+    /// runs once the rows of every table are inserted, such as adding the foreign keys and moving the sequences past
+    /// the inserted values
+    pub after: Vec<String>,
+}
+
+/// a column which references a column of another table
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForeignKeyReference {
+    pub column: String,
+    pub ref_database: Option<String>,
+    pub ref_schema: Option<String>,
+    pub ref_table: String,
+    pub ref_column: String,
+}
+
+impl ForeignKeyReference {
+    /// builds a filter which selects the rows referenced by `value`
+    pub fn filter(&self, engine: Engine, value: &str, quote: char) -> String {
+        format!(
+            "{quote}{}{quote} = {}",
+            self.ref_column,
+            literal(engine, Some(value), ColumnKind::Text),
+            quote = quote
+        )
+    }
+}
+
+/// a foreign key of another table referencing a column of a table, to list the rows referencing one of its rows
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChildReference {
+    pub table: Table,
+    pub column: String,
+    /// the column of the referenced table
+    pub ref_column: String,
+}
+
+impl ChildReference {
+    /// builds a filter which selects the rows referencing the row whose referenced column is `value`
+    pub fn filter(&self, engine: Engine, value: &str, quote: char) -> String {
+        format!(
+            "{quote}{}{quote} = {}",
+            self.column,
+            literal(engine, Some(value), ColumnKind::Text),
+            quote = quote
+        )
+    }
+}
+
+/// identifies the connection running statements so that another connection can cancel them
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CancelToken {
+    MySql(u64),
+    Postgres(i32),
+    /// the id the pool keeps the `sqlite3` handle of the connection under while it runs a statement
+    Sqlite(usize),
+    /// the id the pool keeps the interrupt handle of the connection under
+    DuckDb(usize),
+    /// the id the plugin keeps the connection of the session under
+    Plugin(u64),
+}
+
+/// what to do with each statement run by `Session::run`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatementMode {
+    Execute,
+    /// `analyze` is only set when the server supports `Feature::ExplainAnalyze`
+    Explain {
+        analyze: bool,
+    },
+}
+
+/// a connection which keeps its state, such as transactions, between statements
+#[async_trait]
+pub trait Session: Send {
+    /// returns the token with which `Pool::cancel` interrupts the statement running on this connection
+    fn cancel_token(&mut self) -> CancelToken;
+    async fn run(&mut self, statement: &str, mode: StatementMode) -> anyhow::Result<ExecuteResult>;
+    /// changes settings of this connection, returning their values before the change so that they can be restored
+    async fn apply_settings(
+        &mut self,
+        settings: &[(String, String)],
+    ) -> anyhow::Result<Vec<(String, String)>>;
+    /// runs a `lock_statement` in the transaction of this connection, returning false when another transaction holds the rows
+    async fn lock_rows(&mut self, statement: &str) -> anyhow::Result<bool>;
+    /// returns the warnings the server raised while running the last statement on this connection
+    async fn warnings(&mut self) -> anyhow::Result<Vec<String>>;
+}
+
 #[async_trait]
-pub trait Pool {
+pub trait Pool: Send + Sync {
     async fn get_databases(&self) -> anyhow::Result<Vec<Database>>;
     async fn get_tables(&self, database: String) -> anyhow::Result<Vec<Child>>;
     async fn get_records(
         &self,
         database: &Database,
         table: &Table,
-        page: u16,
+        page: usize,
         filter: Option<String>,
-    ) -> anyhow::Result<(Vec<String>, Vec<Vec<String>>)>;
+    ) -> anyhow::Result<(Vec<String>, Vec<Vec<Option<String>>>)>;
+    /// fetches the page of the records of `table` in `primary_key` order after `key`, which is the key a previous page
+    /// returned
+    async fn get_records_after(
+        &self,
+        database: &Database,
+        table: &Table,
+        primary_key: &[String],
+        key: Option<&[String]>,
+        filter: Option<String>,
+    ) -> anyhow::Result<KeysetPage<Option<String>>>;
+    /// fetches a page of the records of `table` as a dump reads them, in `primary_key` order after `key` or, when
+    /// `primary_key` is empty, at the offset `page` in an order which stays the same from one page to the next, with
+    /// `None` for NULL so that it is told apart from text reading `NULL`
+    async fn get_values(
+        &self,
+        database: &Database,
+        table: &Table,
+        page: usize,
+        primary_key: &[String],
+        key: Option<&[String]>,
+    ) -> anyhow::Result<KeysetPage<Option<String>>>;
+    async fn get_primary_key(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<String>>;
+    /// returns the columns whose values the database computes and refuses to be given
+    async fn get_generated_columns(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<String>>;
     async fn get_columns(
         &self,
         database: &Database,
@@ -37,15 +279,2625 @@ pub trait Pool {
         database: &Database,
         table: &Table,
     ) -> anyhow::Result<Vec<Box<dyn TableRow>>>;
+    async fn get_foreign_key_references(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<ForeignKeyReference>>;
+    /// returns the foreign keys of the tables referencing `table`
+    async fn get_child_references(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<ChildReference>>;
     async fn get_indexes(
         &self,
         database: &Database,
         table: &Table,
     ) -> anyhow::Result<Vec<Box<dyn TableRow>>>;
+    async fn get_definition(&self, database: &Database, table: &Table) -> anyhow::Result<String>;
+    /// returns the statements a dump creates `table` with, which restore it whichever tables are restored before it
+    async fn get_dump_definition(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<DumpDefinition>;
+    /// returns the triggers of `table` with when and on which changes they fire and what they run
+    async fn get_triggers(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<Box<dyn TableRow>>>;
+    /// returns how many rows `table` has and how much space it takes, estimated where counting would read every row
+    async fn get_table_status(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<TableStatus>;
+    /// returns the statement creating `routine`, with its body
+    async fn get_routine_definition(
+        &self,
+        database: &Database,
+        routine: &Routine,
+    ) -> anyhow::Result<String>;
+    /// returns the jobs the server runs on a schedule, which are none when it has no scheduler
+    async fn get_scheduled_jobs(&self) -> anyhow::Result<Vec<ScheduledJob>>;
+    async fn insert_records(
+        &self,
+        database: &Database,
+        table: &Table,
+        headers: &[String],
+        rows: &[Vec<String>],
+    ) -> anyhow::Result<u64>;
+    /// takes a connection out of the pool to run statements one after another on it
+    async fn session(&self) -> anyhow::Result<Box<dyn Session>>;
+    async fn cancel(&self, token: CancelToken) -> anyhow::Result<()>;
     async fn close(&self);
+    fn identifier_quote(&self) -> char;
+    /// returns the name of `table`, qualified and quoted to be written in statements
+    fn table_name(&self, database: &Database, table: &Table) -> String;
+    /// returns the statements running `routine` with `arguments`, one per parameter of `routine_parameters` with
+    /// `None` for the output ones, followed by a SELECT of the output parameters which are read into variables
+    fn call_statement(
+        &self,
+        database: &Database,
+        routine: &Routine,
+        arguments: &[Option<String>],
+    ) -> String;
+    /// returns how the server casts values to text, counts their characters and matches them against patterns
+    fn text_syntax(&self) -> TextSyntax;
+    /// returns the statement which refreshes the statistics the planner keeps about `table`
+    fn analyze_statement(&self, database: &Database, table: &Table) -> String;
+    /// returns the statement which enables or disables `job`, or `None` when the server has no scheduler
+    fn enable_job_statement(&self, job: &ScheduledJob, enable: bool) -> Option<String>;
+    fn server_version(&self) -> &ServerVersion;
+    fn supports(&self, feature: Feature) -> bool;
+    /// returns the engine of the server, whose functions and syntax are offered and checked in the editor
+    fn engine(&self) -> Engine;
+}
+
+/// the version a server reported on connect
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ServerVersion {
+    text: String,
+    numbers: Vec<u32>,
+}
+
+impl ServerVersion {
+    /// reads the first dotted number, e.g. `13.4` from `13.4 (Debian 13.4-1.pgdg100+1)`
+    pub fn parse(text: &str) -> Self {
+        let numbers = text
+            .trim_start_matches(|c: char| !c.is_ascii_digit())
+            .split(|c: char| !c.is_ascii_digit() && c != '.')
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map_while(|number| number.parse().ok())
+            .collect();
+        Self {
+            text: text.to_string(),
+            numbers,
+        }
+    }
+
+    pub fn at_least(&self, version: &[u32]) -> bool {
+        for (i, required) in version.iter().enumerate() {
+            let number = self.numbers.get(i).copied().unwrap_or(0);
+            if number != *required {
+                return number > *required;
+            }
+        }
+        true
+    }
+
+    pub fn is_mariadb(&self) -> bool {
+        self.text.to_lowercase().contains("mariadb")
+    }
+}
+
+impl std::fmt::Display for ServerVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// a feature of SQL which old versions or some kinds of servers lack
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Feature {
+    ExplainAnalyze,
+    MaterializedViews,
+    CommonTableExpressions,
+    Returning,
+    LockNowait,
+}
+
+impl Feature {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ExplainAnalyze => "EXPLAIN ANALYZE",
+            Self::MaterializedViews => "MATERIALIZED VIEW",
+            Self::CommonTableExpressions => "WITH",
+            Self::Returning => "RETURNING",
+            Self::LockNowait => "FOR UPDATE NOWAIT",
+        }
+    }
+
+    /// returns the feature a statement needs, judging by its keywords outside of quotes
+    pub fn required_by(query: &str, engine: Engine) -> Option<Self> {
+        let words = keywords(query, engine);
+        if words.starts_with(&["EXPLAIN".to_string(), "ANALYZE".to_string()]) {
+            Some(Self::ExplainAnalyze)
+        } else if words
+            .windows(2)
+            .any(|pair| pair[0] == "MATERIALIZED" && pair[1] == "VIEW")
+        {
+            Some(Self::MaterializedViews)
+        } else if words.first().map(|word| word.as_str()) == Some("WITH") {
+            Some(Self::CommonTableExpressions)
+        } else if words.iter().any(|word| word == "RETURNING") {
+            Some(Self::Returning)
+        } else if words.iter().any(|word| word == "NOWAIT") {
+            Some(Self::LockNowait)
+        } else {
+            None
+        }
+    }
+}
+
+/// returns the upper-cased words of a statement outside of quotes and comments
+fn keywords(query: &str, engine: Engine) -> Vec<String> {
+    sql_tokens(query, engine)
+        .into_iter()
+        .filter_map(|token| match token {
+            SqlToken::Word(word) => Some(word.to_uppercase()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// builds a statement such as `SET SESSION max_execution_time = 5000` which changes a setting, quoting the value unless it is a number
+pub fn setting_statement(command: &str, name: &str, value: &str) -> anyhow::Result<String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+    {
+        return Err(anyhow::anyhow!("invalid setting name: {}", name));
+    }
+    let is_number = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == '-')
+        && value.parse::<f64>().is_ok();
+    Ok(if is_number {
+        format!("{} {} = {}", command, name, value)
+    } else {
+        format!("{} {} = '{}'", command, name, value.replace('\'', "''"))
+    })
+}
+
+/// splits a SQL text into statements by semicolons outside of quotes and comments, leaving out the comments before
+/// and after each one but the versioned ones of MySQL such as `/*!40101 SET NAMES utf8 */`, which it runs
+pub fn split_statements(sql: &str, engine: Engine) -> Vec<String> {
+    let chars = sql.chars().collect::<Vec<char>>();
+    let tokens = format::located_tokens(sql, engine);
+    tokens
+        .split(|(_, token)| *token == format::Token::Punct(";".to_string()))
+        .filter_map(|statement| {
+            let runs = |(_, token): &&(Range<usize>, format::Token)| match token {
+                format::Token::LineComment(_) => false,
+                format::Token::BlockComment(comment) => {
+                    engine == Engine::MySql && comment.starts_with("/*!")
+                }
+                _ => true,
+            };
+            let start = statement.iter().find(runs)?.0.start;
+            let end = statement.iter().rev().find(runs)?.0.end;
+            Some(chars[start..end].iter().collect())
+        })
+        .collect()
+}
+
+/// a word, a quoted identifier or a punctuation character of a statement, leaving string literals out
+#[derive(Debug, Clone, PartialEq)]
+enum SqlToken {
+    Word(String),
+    Quoted(String),
+    Punct(char),
+}
+
+fn sql_tokens(statement: &str, engine: Engine) -> Vec<SqlToken> {
+    located_sql_tokens(statement, engine)
+        .into_iter()
+        .map(|(_, token)| token)
+        .collect()
+}
+
+/// splits `sql` into tokens with the range of characters each one spans, leaving comments out as well, and
+/// operators such as `::` into one token for each of their characters
+fn located_sql_tokens(sql: &str, engine: Engine) -> Vec<(Range<usize>, SqlToken)> {
+    let mut tokens = vec![];
+    for (range, token) in format::located_tokens(sql, engine) {
+        match token {
+            format::Token::Word(word) => tokens.push((range, SqlToken::Word(word))),
+            format::Token::Verbatim(text) => match text.chars().next() {
+                Some(c) if c.is_ascii_digit() || c == '.' => {
+                    tokens.push((range, SqlToken::Word(text)))
+                }
+                Some(quote) if matches!(quote, '"' | '`' | '[') => {
+                    let close = if quote == '[' { ']' } else { quote };
+                    let name = text[1..].strip_suffix(close).unwrap_or(&text[1..]);
+                    tokens.push((range, SqlToken::Quoted(name.to_string())));
+                }
+                _ => (),
+            },
+            format::Token::Punct(operator) => {
+                for (index, c) in operator.chars().enumerate() {
+                    let start = range.start + index;
+                    tokens.push((start..start + 1, SqlToken::Punct(c)));
+                }
+            }
+            format::Token::LineComment(_) | format::Token::BlockComment(_) => (),
+        }
+    }
+    tokens
+}
+
+/// returns the name at `index`, which may be qualified with its schema or its database, and the index after it
+fn table_reference(tokens: &[SqlToken], index: usize) -> Option<((Option<String>, String), usize)> {
+    let part = |index: usize| match tokens.get(index) {
+        Some(SqlToken::Word(word)) if !is_clause_keyword(word) => Some(word.clone()),
+        Some(SqlToken::Quoted(name)) => Some(name.clone()),
+        _ => None,
+    };
+    let mut parts = vec![part(index)?];
+    let mut index = index + 1;
+    while tokens.get(index) == Some(&SqlToken::Punct('.')) {
+        parts.push(part(index + 1)?);
+        index += 2;
+    }
+    let table = parts.pop()?;
+    Some(((parts.pop(), table), index))
+}
+
+/// tells whether a word starts a clause or a join rather than naming a table or an alias
+fn is_clause_keyword(word: &str) -> bool {
+    matches!(
+        word.to_uppercase().as_str(),
+        "SELECT"
+            | "WHERE"
+            | "JOIN"
+            | "INNER"
+            | "LEFT"
+            | "RIGHT"
+            | "FULL"
+            | "CROSS"
+            | "NATURAL"
+            | "ON"
+            | "USING"
+            | "GROUP"
+            | "ORDER"
+            | "HAVING"
+            | "LIMIT"
+            | "OFFSET"
+            | "FETCH"
+            | "FOR"
+            | "UNION"
+            | "EXCEPT"
+            | "INTERSECT"
+            | "WINDOW"
+            | "SET"
+            | "VALUES"
+            | "RETURNING"
+            | "LATERAL"
+            | "IF"
+            | "NOT"
+            | "EXISTS"
+    )
+}
+
+/// returns the ranges of characters of the tables which `metadata` does not know, and of the columns qualified with a
+/// table or its alias which the table does not have, leaving out the tables whose columns have not been fetched yet
+pub fn unknown_names(sql: &str, engine: Engine, metadata: &MetadataCache) -> Vec<Range<usize>> {
+    let mut unknown = vec![];
+    let located = located_sql_tokens(sql, engine);
+    for statement in located.split(|(_, token)| *token == SqlToken::Punct(';')) {
+        let ranges = statement
+            .iter()
+            .map(|(range, _)| range.clone())
+            .collect::<Vec<Range<usize>>>();
+        let tokens = statement
+            .iter()
+            .map(|(_, token)| token.clone())
+            .collect::<Vec<SqlToken>>();
+        let word = |index: usize| match tokens.get(index) {
+            Some(SqlToken::Word(word)) => Some(word.to_uppercase()),
+            _ => None,
+        };
+        // statements defining objects name the ones which do not exist yet
+        if matches!(
+            word(0).as_deref(),
+            Some("CREATE") | Some("ALTER") | Some("DROP")
+        ) {
+            continue;
+        }
+        let ctes = (2..tokens.len())
+            .filter(|index| {
+                word(*index).as_deref() == Some("AS")
+                    && tokens.get(index + 1) == Some(&SqlToken::Punct('('))
+                    && (tokens[index - 2] == SqlToken::Punct(',')
+                        || matches!(word(index - 2).as_deref(), Some("WITH") | Some("RECURSIVE")))
+            })
+            .filter_map(|index| match &tokens[index - 1] {
+                SqlToken::Word(name) => Some(name.to_lowercase()),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+
+        // the table of each alias, and of each table name itself
+        let mut tables = HashMap::new();
+        let mut table_tokens = HashSet::new();
+        let mut parentheses = vec![];
+        for (index, token) in tokens.iter().enumerate() {
+            match token {
+                SqlToken::Punct('(') => parentheses.push(index),
+                SqlToken::Punct(')') => {
+                    parentheses.pop();
+                }
+                _ => (),
+            }
+            let keyword = match word(index) {
+                Some(keyword)
+                    if matches!(keyword.as_str(), "FROM" | "JOIN" | "UPDATE" | "INTO") =>
+                {
+                    keyword
+                }
+                _ => continue,
+            };
+            // FROM also separates the arguments of functions such as EXTRACT and TRIM, unlike in a subquery
+            let in_subquery = parentheses.last().map_or(true, |open| {
+                matches!(word(open + 1).as_deref(), Some("SELECT") | Some("WITH"))
+            });
+            if !in_subquery {
+                continue;
+            }
+            let mut next = index + 1;
+            while word(next).as_deref() == Some("ONLY") {
+                next += 1;
+            }
+            while let Some(((schema, table), after)) = table_reference(&tokens, next) {
+                table_tokens.extend(next..after);
+                next = after;
+                // a function returning rows
+                if keyword != "INTO" && tokens.get(next) == Some(&SqlToken::Punct('(')) {
+                    break;
+                }
+                let known = (schema.is_none()
+                    && (ctes.contains(&table.to_lowercase())
+                        || table.eq_ignore_ascii_case("dual")))
+                    || metadata.has_table(schema.as_deref(), &table) != Some(false);
+                if !known {
+                    unknown.push(ranges[after - 1].clone());
+                }
+                if word(next).as_deref() == Some("AS") {
+                    next += 1;
+                }
+                if let Some(SqlToken::Word(alias)) = tokens.get(next) {
+                    if !is_clause_keyword(alias) {
+                        tables.insert(alias.to_lowercase(), table.clone());
+                        table_tokens.insert(next);
+                        next += 1;
+                    }
+                }
+                tables.insert(table.to_lowercase(), table);
+                if keyword != "FROM" || tokens.get(next) != Some(&SqlToken::Punct(',')) {
+                    break;
+                }
+                next += 1;
+            }
+        }
+
+        let name = |index: usize| match tokens.get(index) {
+            Some(SqlToken::Word(name)) | Some(SqlToken::Quoted(name)) => Some(name),
+            _ => None,
+        };
+        for index in 0..tokens.len() {
+            let dot = |index: usize| tokens.get(index) == Some(&SqlToken::Punct('.'));
+            // only names of two parts, which are not those of tables or functions
+            if table_tokens.contains(&index)
+                || (index > 0 && dot(index - 1))
+                || !dot(index + 1)
+                || dot(index + 3)
+                || tokens.get(index + 3) == Some(&SqlToken::Punct('('))
+            {
+                continue;
+            }
+            if let (Some(qualifier), Some(column)) = (name(index), name(index + 2)) {
+                let columns = tables
+                    .get(&qualifier.to_lowercase())
+                    .and_then(|table| metadata.columns(table));
+                if let Some(columns) = columns {
+                    if !columns.iter().any(|name| name.eq_ignore_ascii_case(column)) {
+                        unknown.push(ranges[index + 2].clone());
+                    }
+                }
+            }
+        }
+    }
+    unknown.sort_by_key(|range| range.start);
+    unknown
+}
+
+/// returns the conditions joining the table of a `JOIN ... ON` which ends `sql` to the tables named before it, such as
+/// `o.customer_id = c.id`, by the foreign keys between them first and then by the names of their columns
+pub fn join_conditions(sql: &str, engine: Engine, metadata: &MetadataCache) -> Vec<String> {
+    let tokens = sql_tokens(sql, engine);
+    let statement = tokens
+        .rsplit(|token| *token == SqlToken::Punct(';'))
+        .next()
+        .unwrap_or_default();
+    let word = |index: usize| match statement.get(index) {
+        Some(SqlToken::Word(word)) => Some(word.to_uppercase()),
+        _ => None,
+    };
+    let on = match statement.len().checked_sub(1) {
+        Some(on) if word(on).as_deref() == Some("ON") => on,
+        _ => return vec![],
+    };
+    // the name each table is written with in the conditions, which is its alias when it has one, and where the last
+    // table of a JOIN ends
+    let mut tables = vec![];
+    let mut joined_end = None;
+    for index in 0..on {
+        let keyword = match word(index) {
+            Some(keyword) if keyword == "FROM" || keyword == "JOIN" => keyword,
+            _ => continue,
+        };
+        let mut next = index + 1;
+        while let Some(((_, table), after)) = table_reference(statement, next) {
+            next = after;
+            if word(next).as_deref() == Some("AS") {
+                next += 1;
+            }
+            let alias = match statement.get(next) {
+                Some(SqlToken::Word(alias)) if !is_clause_keyword(alias) => {
+                    next += 1;
+                    alias.clone()
+                }
+                _ => table.clone(),
+            };
+            tables.push((alias, table));
+            if keyword == "JOIN" || statement.get(next) != Some(&SqlToken::Punct(',')) {
+                break;
+            }
+            next += 1;
+        }
+        joined_end = Some(if keyword == "JOIN" { next } else { 0 });
+    }
+    let (alias, joined) = match tables.pop() {
+        Some(joined) if joined_end == Some(on) => joined,
+        _ => return vec![],
+    };
+
+    let mut conditions: Vec<String> = vec![];
+    let mut push = |column: &str, other: &str, other_column: &str| {
+        let condition = format!("{}.{} = {}.{}", alias, column, other, other_column);
+        if !conditions.contains(&condition) {
+            conditions.push(condition);
+        }
+    };
+    // the tables written closest to the join come first
+    for (other, table) in tables.iter().rev() {
+        for reference in metadata.references(&joined).unwrap_or_default() {
+            if reference.ref_table.eq_ignore_ascii_case(table) {
+                push(&reference.column, other, &reference.ref_column);
+            }
+        }
+        for reference in metadata.references(table).unwrap_or_default() {
+            if reference.ref_table.eq_ignore_ascii_case(&joined) {
+                push(&reference.ref_column, other, &reference.column);
+            }
+        }
+    }
+    // `customer_id` refers to the `id` of `customers` by convention, and other columns of the same name are often keys too
+    let refers = |column: &str, table: &str| {
+        let table = table.to_lowercase();
+        let column = column.to_lowercase();
+        column == format!("{}_id", table) || column == format!("{}_id", table.trim_end_matches('s'))
+    };
+    let has = |columns: &[String], name: &str| columns.iter().any(|c| c.eq_ignore_ascii_case(name));
+    for (other, table) in tables.iter().rev() {
+        if let (Some(joined_columns), Some(columns)) =
+            (metadata.columns(&joined), metadata.columns(table))
+        {
+            for column in joined_columns {
+                if refers(column, table) && has(columns, "id") {
+                    push(column, other, "id");
+                }
+            }
+            for column in columns {
+                if refers(column, &joined) && has(joined_columns, "id") {
+                    push("id", other, column);
+                }
+            }
+            for column in joined_columns {
+                if !column.eq_ignore_ascii_case("id") && has(columns, column) {
+                    push(column, other, column);
+                }
+            }
+        }
+    }
+    conditions
+}
+
+/// returns the tables which the statements read or write, with the schema or the database they are qualified with,
+/// leaving out the names of common table expressions
+pub fn referenced_tables(sql: &str, engine: Engine) -> Vec<(Option<String>, String)> {
+    let mut tables: Vec<(Option<String>, String)> = vec![];
+    let mut ctes = vec![];
+    for statement in split_statements(sql, engine) {
+        let tokens = sql_tokens(statement.as_str(), engine);
+        for (index, token) in tokens.iter().enumerate() {
+            let keyword = match token {
+                SqlToken::Word(word) => word.to_uppercase(),
+                _ => continue,
+            };
+            // `name AS (` after WITH or a comma defines a common table expression
+            if keyword == "AS" && tokens.get(index + 1) == Some(&SqlToken::Punct('(')) && index >= 2
+            {
+                if let (Some(SqlToken::Word(name)), Some(previous)) =
+                    (tokens.get(index - 1), tokens.get(index - 2))
+                {
+                    if matches!(previous, SqlToken::Punct(','))
+                        || matches!(previous, SqlToken::Word(word) if matches!(word.to_uppercase().as_str(), "WITH" | "RECURSIVE"))
+                    {
+                        ctes.push(name.to_lowercase());
+                    }
+                }
+                continue;
+            }
+            if !matches!(
+                keyword.as_str(),
+                "FROM" | "JOIN" | "UPDATE" | "INTO" | "TABLE"
+            ) {
+                continue;
+            }
+            let mut next = index + 1;
+            while matches!(tokens.get(next), Some(SqlToken::Word(word)) if matches!(word.to_uppercase().as_str(), "IF" | "NOT" | "EXISTS" | "ONLY"))
+            {
+                next += 1;
+            }
+            // FROM lists tables separated by commas, each of which may be followed by an alias
+            while let Some((table, after)) = table_reference(&tokens, next) {
+                if !tables.iter().any(|found| {
+                    found.0.as_ref().map(|schema| schema.to_lowercase())
+                        == table.0.as_ref().map(|schema| schema.to_lowercase())
+                        && found.1.to_lowercase() == table.1.to_lowercase()
+                }) {
+                    tables.push(table);
+                }
+                next = after;
+                if keyword != "FROM" {
+                    break;
+                }
+                if matches!(tokens.get(next), Some(SqlToken::Word(word)) if word.eq_ignore_ascii_case("AS"))
+                {
+                    next += 1;
+                }
+                if table_reference(&tokens, next).is_some() {
+                    next += 1;
+                }
+                if tokens.get(next) != Some(&SqlToken::Punct(',')) {
+                    break;
+                }
+                next += 1;
+            }
+        }
+    }
+    tables.retain(|(schema, table)| schema.is_some() || !ctes.contains(&table.to_lowercase()));
+    tables
+}
+
+pub fn is_read_query(query: &str) -> bool {
+    let keyword = query
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_uppercase();
+    matches!(
+        keyword.as_str(),
+        "SELECT" | "SHOW" | "WITH" | "EXPLAIN" | "DESCRIBE" | "DESC" | "PRAGMA" | "VALUES"
+    )
+}
+
+/// tells whether a statement only reads, judging by its keywords outside of quotes, which is all a read-only connection runs
+pub fn is_read_only_statement(statement: &str, engine: Engine) -> bool {
+    let words = keywords(statement, engine);
+    let reads = words.first().map_or(false, |word| {
+        matches!(
+            word.as_str(),
+            "SELECT" | "SHOW" | "EXPLAIN" | "DESCRIBE" | "DESC" | "WITH" | "VALUES"
+        )
+    });
+    // a CTE may change rows, SELECT ... INTO writes a table or a file and EXPLAIN ANALYZE runs what it explains
+    reads
+        && !words.iter().any(|word| {
+            matches!(
+                word.as_str(),
+                "INSERT"
+                    | "UPDATE"
+                    | "DELETE"
+                    | "MERGE"
+                    | "INTO"
+                    | "CREATE"
+                    | "DROP"
+                    | "ALTER"
+                    | "TRUNCATE"
+                    | "CALL"
+            )
+        })
+}
+
+/// wraps a query so that it returns the `limit` rows after the first `offset` ones, or returns `None` for a statement
+/// which cannot be wrapped, such as SHOW or a SELECT locking rows
+pub fn page_statement(
+    statement: &str,
+    engine: Engine,
+    offset: usize,
+    limit: usize,
+) -> Option<String> {
+    let words = keywords(statement, engine);
+    if !matches!(
+        words.first().map(String::as_str),
+        Some("SELECT") | Some("WITH")
+    ) || !is_read_only_statement(statement, engine)
+        || words.iter().any(|word| word == "FOR" || word == "LOCK")
+    {
+        return None;
+    }
+    // the query may end with a comment, which would comment the parenthesis out on the same line
+    Some(format!(
+        "SELECT * FROM ({}\n) AS gobang_page LIMIT {} OFFSET {}",
+        statement, limit, offset
+    ))
+}
+
+/// tells how a statement changes the transaction of the connection running it, `Some(true)` when it starts one and
+/// `Some(false)` when it commits or rolls it back, leaving savepoints out
+pub fn transaction_change(statement: &str, engine: Engine) -> Option<bool> {
+    let words = keywords(statement, engine);
+    match words.first()?.as_str() {
+        "BEGIN" => Some(true),
+        "START" if words.get(1).map(String::as_str) == Some("TRANSACTION") => Some(true),
+        "COMMIT" | "END" | "ABORT" => Some(false),
+        "ROLLBACK" if !words.iter().any(|word| word == "TO") => Some(false),
+        _ => None,
+    }
+}
+
+/// tells whether an error may not happen again when the statement runs again, such as a deadlock, a serialization failure or a lost connection
+pub fn is_transient_error(err: &anyhow::Error) -> bool {
+    match err.downcast_ref::<sqlx::Error>() {
+        Some(sqlx::Error::Io(_)) | Some(sqlx::Error::PoolTimedOut) => true,
+        // serialization_failure, which MySQL also reports for deadlocks, deadlock_detected, SQLITE_BUSY and SQLITE_LOCKED
+        Some(sqlx::Error::Database(err)) => matches!(
+            err.code().as_deref(),
+            Some("40001") | Some("40P01") | Some("5") | Some("6")
+        ),
+        _ => false,
+    }
+}
+
+/// tells whether an error is the server refusing the wrapper of `page_statement` rather than the query it wraps, such
+/// as MySQL refusing two columns of the same name in a derived table or an error naming the alias of the wrapper
+pub fn is_page_error(err: &anyhow::Error) -> bool {
+    match err.downcast_ref::<sqlx::Error>() {
+        // duplicate column names on MySQL and PostgreSQL
+        Some(sqlx::Error::Database(db))
+            if matches!(db.code().as_deref(), Some("42S21") | Some("42701")) =>
+        {
+            true
+        }
+        _ => err.to_string().contains("gobang_page"),
+    }
+}
+
+/// tells whether an error left the connection unusable, so that a statement must run again on another one
+pub fn is_connection_error(err: &anyhow::Error) -> bool {
+    matches!(err.downcast_ref::<sqlx::Error>(), Some(sqlx::Error::Io(_)))
+}
+
+/// returns the wait before retry number `attempt` counted from 0, doubling `base` each time and adding up to `base` of jitter in proportion to `jitter` between 0 and 1
+pub fn retry_delay(base: Duration, attempt: u32, jitter: f64) -> Duration {
+    base * 2u32.saturating_pow(attempt) + base.mul_f64(jitter.clamp(0.0, 1.0))
+}
+
+/// returns a random number between 0 and 1 to spread the retries of clients which failed at the same time
+pub fn jitter() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    (hasher.finish() % 1000) as f64 / 1000.0
+}
+
+pub fn insert_statement(
+    engine: Engine,
+    table: &str,
+    quote: char,
+    headers: &[String],
+    rows: &[Vec<String>],
+) -> String {
+    format!(
+        "INSERT INTO {table} ({columns}) VALUES {values}",
+        table = table,
+        columns = headers
+            .iter()
+            .map(|header| format!("{quote}{}{quote}", header, quote = quote))
+            .collect::<Vec<String>>()
+            .join(", "),
+        values = rows
+            .iter()
+            .map(|row| format!(
+                "({})",
+                row.iter()
+                    .map(|value| literal(engine, Some(value), ColumnKind::Text))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ))
+            .collect::<Vec<String>>()
+            .join(", ")
+    )
+}
+
+/// builds an INSERT of a single row, writing `None` as NULL and leaving out the columns which should take their defaults
+pub fn insert_row_statement(
+    engine: Engine,
+    table: &str,
+    quote: char,
+    values: &[(String, Option<String>)],
+) -> anyhow::Result<String> {
+    if values.is_empty() {
+        return Err(anyhow::anyhow!("enter a value for at least one column"));
+    }
+    Ok(format!(
+        "INSERT INTO {table} ({columns}) VALUES ({values})",
+        table = table,
+        columns = values
+            .iter()
+            .map(|(column, _)| format!("{quote}{}{quote}", column, quote = quote))
+            .collect::<Vec<String>>()
+            .join(", "),
+        values = values
+            .iter()
+            .map(|(_, value)| literal(engine, value.as_deref(), ColumnKind::Text))
+            .collect::<Vec<String>>()
+            .join(", ")
+    ))
+}
+
+/// a parameter of a stored procedure or function
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutineParameter {
+    /// IN, OUT, INOUT or VARIADIC
+    pub mode: String,
+    /// the name, which PostgreSQL lets be left out
+    pub name: String,
+    pub r#type: String,
+}
+
+impl RoutineParameter {
+    /// returns whether the routine only writes the parameter, so that no value is passed to it
+    pub fn is_output(&self) -> bool {
+        self.mode == "OUT"
+    }
+}
+
+/// splits the parameters of a routine such as `IN id int, OUT total decimal(10,2)`, whose modes default to IN
+pub fn routine_parameters(arguments: &str) -> Vec<RoutineParameter> {
+    let mut parameters = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, c) in arguments
+        .char_indices()
+        .chain(std::iter::once((arguments.len(), ',')))
+    {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                let mut words = arguments[start..index].split_whitespace().peekable();
+                start = index + 1;
+                let mode = match words.peek().map(|word| word.to_uppercase()) {
+                    Some(mode) if ["IN", "OUT", "INOUT", "VARIADIC"].contains(&mode.as_str()) => {
+                        words.next();
+                        mode
+                    }
+                    Some(_) => "IN".to_string(),
+                    None => continue,
+                };
+                let (name, r#type) = match words.collect::<Vec<&str>>().as_slice() {
+                    [r#type] => (String::new(), r#type.to_string()),
+                    [name, r#type @ ..] => (name.to_string(), r#type.join(" ")),
+                    [] => (String::new(), String::new()),
+                };
+                parameters.push(RoutineParameter { mode, name, r#type });
+            }
+            _ => (),
+        }
+    }
+    parameters
+}
+
+/// writes a value typed for a parameter as a string, which the server converts to the type of the parameter, or as
+/// NULL when nothing is typed
+pub fn routine_argument(engine: Engine, value: &str) -> String {
+    literal(
+        engine,
+        Some(value).filter(|value| !value.is_empty()),
+        ColumnKind::Text,
+    )
+}
+
+/// builds a DELETE of the rows whose primary key values are `keys`
+pub fn delete_statement(
+    engine: Engine,
+    table: &str,
+    quote: char,
+    primary_key: &[String],
+    keys: &[Vec<String>],
+) -> anyhow::Result<String> {
+    Ok(format!(
+        "DELETE FROM {} WHERE {}",
+        table,
+        key_condition(engine, table, quote, primary_key, keys, "delete")?
+    ))
+}
+
+/// builds an UPDATE of `column` of the rows whose primary key values are `keys`, leaving `<value>` in place of the new
+/// value so that it cannot run before it is written
+pub fn update_statement(
+    engine: Engine,
+    table: &str,
+    quote: char,
+    primary_key: &[String],
+    keys: &[Vec<String>],
+    column: &str,
+) -> anyhow::Result<String> {
+    Ok(format!(
+        "UPDATE {} SET {quote}{}{quote} = <value> WHERE {}",
+        table,
+        column,
+        key_condition(engine, table, quote, primary_key, keys, "update")?,
+        quote = quote
+    ))
+}
+
+/// an edit of every selected cell at once, as in a spreadsheet
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BulkEdit {
+    /// copies the values of the first row to the others
+    FillDown,
+    /// sets the cells to NULL
+    Clear,
+    /// counts up from the values of the first row, one more on each following row
+    Increment,
+}
+
+/// builds the UPDATEs making `edit` to the cells of `columns` in the rows whose primary key values are `keys`, where
+/// `rows` holds the values of `columns` in the same rows and `kinds` the kinds of values the columns hold
+#[allow(clippy::too_many_arguments)]
+pub fn bulk_edit_statements(
+    engine: Engine,
+    table: &str,
+    quote: char,
+    primary_key: &[String],
+    keys: &[Vec<String>],
+    columns: &[String],
+    kinds: &[ColumnKind],
+    rows: &[Vec<Option<String>>],
+    edit: BulkEdit,
+) -> anyhow::Result<Vec<String>> {
+    let kind = |index: usize| kinds.get(index).copied().unwrap_or(ColumnKind::Text);
+    let update = |values: Vec<String>, keys: &[Vec<String>]| -> anyhow::Result<String> {
+        Ok(format!(
+            "UPDATE {} SET {} WHERE {}",
+            table,
+            columns
+                .iter()
+                .zip(values)
+                .map(|(column, value)| format!(
+                    "{quote}{}{quote} = {}",
+                    column,
+                    value,
+                    quote = quote
+                ))
+                .collect::<Vec<String>>()
+                .join(", "),
+            key_condition(engine, table, quote, primary_key, keys, "update")?
+        ))
+    };
+    let first = match rows.first() {
+        Some(first) => first,
+        None => return Ok(vec![]),
+    };
+    // the keys are missing when the table has no primary key, which building the statements tells
+    match edit {
+        // every row takes the same values, so that one statement changes them all
+        BulkEdit::FillDown if rows.len() > 1 => Ok(vec![update(
+            first
+                .iter()
+                .take(columns.len())
+                .enumerate()
+                .map(|(column, value)| literal(engine, value.as_deref(), kind(column)))
+                .collect(),
+            keys.get(1..).unwrap_or(&[]),
+        )?]),
+        BulkEdit::FillDown => Ok(vec![]),
+        BulkEdit::Clear => Ok(vec![update(
+            columns.iter().map(|_| "NULL".to_string()).collect(),
+            keys,
+        )?]),
+        BulkEdit::Increment => (1..rows.len())
+            .map(|index| {
+                let values = first
+                    .iter()
+                    .take(columns.len())
+                    .enumerate()
+                    .map(|(column, value)| {
+                        value
+                            .as_deref()
+                            .and_then(|value| increment(value, index))
+                            .map(|value| literal(engine, Some(value.as_str()), kind(column)))
+                            .ok_or_else(|| {
+                                anyhow::anyhow!(
+                                    "{} has no number to count up from",
+                                    display_value(value)
+                                )
+                            })
+                    })
+                    .collect::<anyhow::Result<Vec<String>>>()?;
+                update(values, keys.get(index..=index).unwrap_or(&[]))
+            })
+            .collect(),
+    }
+}
+
+/// builds the UPDATEs putting back the values `rows` held in `columns` before they were changed in the rows whose
+/// primary key values are `keys`, one for each row, or none when a column of the primary key was changed too, since
+/// the rows cannot be found by their former keys then
+#[allow(clippy::too_many_arguments)]
+pub fn restore_statements(
+    engine: Engine,
+    table: &str,
+    quote: char,
+    primary_key: &[String],
+    keys: &[Vec<String>],
+    columns: &[String],
+    kinds: &[ColumnKind],
+    rows: &[Vec<Option<String>>],
+) -> anyhow::Result<Vec<String>> {
+    if columns.iter().any(|column| primary_key.contains(column)) {
+        return Ok(vec![]);
+    }
+    keys.iter()
+        .zip(rows)
+        .map(|(key, row)| {
+            Ok(format!(
+                "UPDATE {} SET {} WHERE {}",
+                table,
+                columns
+                    .iter()
+                    .zip(row)
+                    .enumerate()
+                    .map(|(index, (column, value))| format!(
+                        "{quote}{}{quote} = {}",
+                        column,
+                        literal(
+                            engine,
+                            value.as_deref(),
+                            kinds.get(index).copied().unwrap_or(ColumnKind::Text)
+                        ),
+                        quote = quote
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                key_condition(
+                    engine,
+                    table,
+                    quote,
+                    primary_key,
+                    std::slice::from_ref(key),
+                    "restore"
+                )?
+            ))
+        })
+        .collect()
+}
+
+/// builds an INSERT putting back `rows`, the whole records of the columns `headers` holding values of `kinds` before
+/// they were deleted, leaving out the `generated` columns, whose values the database computes again
+pub fn reinsert_statement(
+    engine: Engine,
+    table: &str,
+    quote: char,
+    headers: &[String],
+    kinds: &[ColumnKind],
+    generated: &[String],
+    rows: &[Vec<Option<String>>],
+) -> String {
+    let columns = (0..headers.len())
+        .filter(|index| !generated.contains(&headers[*index]))
+        .collect::<Vec<usize>>();
+    format!(
+        "INSERT INTO {table} ({columns}) VALUES {values}",
+        table = table,
+        columns = columns
+            .iter()
+            .map(|index| format!("{quote}{}{quote}", headers[*index], quote = quote))
+            .collect::<Vec<String>>()
+            .join(", "),
+        values = rows
+            .iter()
+            .map(|row| format!(
+                "({})",
+                columns
+                    .iter()
+                    .map(|index| literal(
+                        engine,
+                        row.get(*index).and_then(Option::as_deref),
+                        kinds.get(*index).copied().unwrap_or(ColumnKind::Text)
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ))
+            .collect::<Vec<String>>()
+            .join(", ")
+    )
+}
+
+/// builds an INSERT statement ending with a semicolon on a line of its own for each row, with the values written as
+/// literals of `engine` of the kinds `kinds` tells the columns hold, where `None` is NULL
+pub fn insert_statements(
+    engine: Engine,
+    table: &str,
+    quote: char,
+    headers: &[String],
+    kinds: &[ColumnKind],
+    rows: &[Vec<Option<String>>],
+) -> String {
+    insert_batches(engine, table, quote, headers, kinds, rows, 1)
+        .into_iter()
+        .map(|statement| format!("{};", statement))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// builds INSERT statements of up to `batch_size` rows each, with the values written like `insert_statements`
+pub fn insert_batches(
+    engine: Engine,
+    table: &str,
+    quote: char,
+    headers: &[String],
+    kinds: &[ColumnKind],
+    rows: &[Vec<Option<String>>],
+    batch_size: usize,
+) -> Vec<String> {
+    let columns = headers
+        .iter()
+        .map(|header| format!("{quote}{}{quote}", header, quote = quote))
+        .collect::<Vec<String>>()
+        .join(", ");
+    rows.chunks(batch_size.max(1))
+        .map(|batch| {
+            format!(
+                "INSERT INTO {} ({}) VALUES {}",
+                table,
+                columns,
+                batch
+                    .iter()
+                    .map(|row| format!(
+                        "({})",
+                        row.iter()
+                            .enumerate()
+                            .map(|(index, value)| literal(
+                                engine,
+                                value.as_deref(),
+                                kinds.get(index).copied().unwrap_or(ColumnKind::Text)
+                            ))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        })
+        .collect()
+}
+
+/// how the values of a column are written as literals, which the type of the column tells rather than the values
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnKind {
+    Text,
+    /// numbers, which are left unquoted
+    Number,
+    /// bytes, which the drivers write as `encode_binary` does
+    Binary,
+}
+
+impl ColumnKind {
+    /// returns the kind of the values of a column whose type `type_badge` names as `badge`
+    pub fn from_badge(badge: Option<&str>) -> Self {
+        match badge {
+            Some("int") | Some("num") => Self::Number,
+            Some("bin") => Self::Binary,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// returns a value as the record tables draw it, with NULL as `NULL`
+pub fn display_value(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("NULL")
+}
+
+/// quotes `value` of a column of `kind` as a literal of `engine`, where `None` is NULL, a number is left unquoted, the
+/// bytes of a binary value are written in hex and the backslashes MySQL reads as escapes are doubled
+pub fn literal(engine: Engine, value: Option<&str>, kind: ColumnKind) -> String {
+    let value = match value {
+        Some(value) => value,
+        None => return "NULL".to_string(),
+    };
+    match kind {
+        ColumnKind::Number if value.parse::<f64>().map_or(false, f64::is_finite) => {
+            return value.to_string()
+        }
+        ColumnKind::Binary => {
+            if let Some(bytes) = decode_binary(value) {
+                let hex = bytes
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect::<String>();
+                return match engine {
+                    Engine::MySql | Engine::Sqlite => format!("X'{}'", hex),
+                    Engine::Postgres => format!("'\\x{}'::bytea", hex),
+                    Engine::DuckDb => format!("from_hex('{}')", hex),
+                };
+            }
+        }
+        _ => (),
+    }
+    match engine {
+        Engine::MySql => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''")),
+        _ => format!("'{}'", value.replace('\'', "''")),
+    }
+}
+
+/// adds `by` to the last number in `value`, keeping the text around it and its leading zeros, such as `item-09` to
+/// `item-10`, and the sign of a value which is only a number
+fn increment(value: &str, by: usize) -> Option<String> {
+    if let Ok(number) = value.parse::<i64>() {
+        return number
+            .checked_add(by as i64)
+            .map(|number| number.to_string());
+    }
+    let end = value.rfind(|c: char| c.is_ascii_digit())? + 1;
+    let start = value[..end]
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |index| index + 1);
+    let number = value[start..end]
+        .parse::<u128>()
+        .ok()?
+        .checked_add(by as u128)?;
+    Some(format!(
+        "{}{:0width$}{}",
+        &value[..start],
+        number,
+        &value[end..],
+        width = end - start
+    ))
+}
+
+/// builds a SELECT of the number of rows, non-null values and distinct values, the minimum, the maximum and, for a numeric column, the average of `column`
+pub fn column_stats_statement(
+    table: &str,
+    quote: char,
+    column: &str,
+    numeric: bool,
+    filter: Option<&str>,
+) -> String {
+    let column = format!("{quote}{}{quote}", column, quote = quote);
+    format!(
+        "SELECT COUNT(*), COUNT({column}), COUNT(DISTINCT {column}), MIN({column}), MAX({column}){avg} FROM {table}{filter}",
+        column = column,
+        avg = if numeric {
+            format!(", AVG({})", column)
+        } else {
+            String::new()
+        },
+        table = table,
+        filter = filter.map_or(String::new(), |filter| format!(" WHERE {}", filter))
+    )
+}
+
+/// the formats the values of a column are checked against by `quality_statement`, with the regular expression each
+/// value must match, written without backslashes which MySQL would read as escapes
+pub const VALUE_FORMATS: [(&str, &str); 2] = [
+    ("email", "^[^@ ]+@[^@ ]+[.][^@ ]+$"),
+    (
+        "uuid",
+        "^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
+    ),
+];
+
+/// how a driver writes the text expressions of `quality_statement`, where `{text}` stands for an expression and
+/// `{pattern}` for a quoted regular expression
+pub struct TextSyntax {
+    /// casts `{text}` to text
+    pub cast: &'static str,
+    /// counts the characters of `{text}`
+    pub length: &'static str,
+    /// tells whether `{text}` matches `{pattern}`, or `None` when the server has no regular expressions
+    pub regex: Option<&'static str>,
+}
+
+/// builds a SELECT of the number of rows, non-null values, empty strings and distinct values, the shortest and the
+/// longest length and the number of values matching each of `VALUE_FORMATS` of `column`, whose numbers of matches are
+/// NULL when the server has no regular expressions
+pub fn quality_statement(
+    table: &str,
+    quote: char,
+    column: &str,
+    syntax: &TextSyntax,
+    filter: Option<&str>,
+) -> String {
+    let column = format!("{quote}{}{quote}", column, quote = quote);
+    let text = syntax.cast.replace("{text}", column.as_str());
+    let length = syntax.length.replace("{text}", text.as_str());
+    let formats = VALUE_FORMATS
+        .iter()
+        .map(|(_, pattern)| match syntax.regex {
+            Some(regex) => format!(
+                "SUM(CASE WHEN {} THEN 1 ELSE 0 END)",
+                regex
+                    .replace("{text}", text.as_str())
+                    .replace("{pattern}", format!("'{}'", pattern).as_str())
+            ),
+            None => "NULL".to_string(),
+        })
+        .collect::<Vec<String>>();
+    format!(
+        "SELECT COUNT(*), COUNT({column}), SUM(CASE WHEN {text} = '' THEN 1 ELSE 0 END), COUNT(DISTINCT {column}), MIN({length}), MAX({length}), {formats} FROM {table}{filter}",
+        column = column,
+        text = text,
+        length = length,
+        formats = formats.join(", "),
+        table = table,
+        filter = filter.map_or(String::new(), |filter| format!(" WHERE {}", filter))
+    )
+}
+
+/// tells whether a column of type `type`, as the columns tab shows it, holds numbers which can be averaged
+pub fn is_numeric_type(r#type: &str) -> bool {
+    matches!(type_badge(r#type), Some("int") | Some("num"))
+}
+
+/// returns a short name of the kind of values a column of type `type` holds, as the columns tab shows it
+pub fn type_badge(r#type: &str) -> Option<&'static str> {
+    let r#type = r#type.trim().to_lowercase();
+    // MySQL stores BOOLEAN as TINYINT(1)
+    if r#type.starts_with("tinyint(1)") || r#type.starts_with("bit(1)") {
+        return Some("bool");
+    }
+    let name = r#type
+        .split(|c: char| c.is_whitespace() || c == '(')
+        .next()
+        .unwrap_or_default();
+    let badge = match name {
+        "tinyint" | "smallint" | "mediumint" | "int" | "integer" | "bigint" | "int2" | "int4"
+        | "int8" | "smallserial" | "serial" | "bigserial" => "int",
+        "decimal" | "dec" | "numeric" | "float" | "float4" | "float8" | "double" | "real"
+        | "money" => "num",
+        "char" | "varchar" | "character" | "nchar" | "nvarchar" | "text" | "tinytext"
+        | "mediumtext" | "longtext" | "citext" | "clob" | "enum" | "set" | "name" => "txt",
+        "timestamp" | "timestamptz" | "datetime" => "ts",
+        "date" => "date",
+        "time" | "timetz" => "time",
+        "json" | "jsonb" => "json",
+        "bool" | "boolean" => "bool",
+        "binary" | "varbinary" | "blob" | "tinyblob" | "mediumblob" | "longblob" | "bytea" => "bin",
+        _ => return None,
+    };
+    Some(badge)
+}
+
+/// returns how many characters a column of a text type such as `varchar(20)` or `character varying(20)` holds at
+/// most, or `None` when its type does not limit them
+pub fn type_length(r#type: &str) -> Option<usize> {
+    if type_badge(r#type) != Some("txt") {
+        return None;
+    }
+    let start = r#type.find('(')? + 1;
+    let end = start + r#type[start..].find(')')?;
+    r#type[start..end].trim().parse().ok()
+}
+
+/// tells whether the null column of the columns tab allows NULL, which is YES or NO on MySQL and PostgreSQL and marks NOT NULL columns on SQLite
+pub fn is_nullable(null: &str) -> bool {
+    !(null.eq_ignore_ascii_case("NO") || null.starts_with('✔'))
+}
+
+/// writes a binary value as `\x` followed by its bytes in hex, as PostgreSQL prints `bytea`, so that a cell keeps it
+/// whole rather than decoding it as text
+pub fn encode_binary(bytes: &[u8]) -> String {
+    let mut value = String::with_capacity(2 + bytes.len() * 2);
+    value.push_str("\\x");
+    for byte in bytes {
+        value.push_str(format!("{:02x}", byte).as_str());
+    }
+    value
+}
+
+/// returns how many bytes a value written by `encode_binary` holds, looking only at its start so that large values
+/// are not read through on every draw
+pub fn binary_length(value: &str) -> Option<usize> {
+    let hex = value.strip_prefix("\\x")?;
+    if hex.len() % 2 != 0 || !hex.bytes().take(64).all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(hex.len() / 2)
+}
+
+/// reads back the bytes of a value written by `encode_binary`
+pub fn decode_binary(value: &str) -> Option<Vec<u8>> {
+    let hex = value.strip_prefix("\\x")?;
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// builds a SELECT which locks the rows whose primary key values are `keys`, failing at once instead of waiting when another transaction holds them
+pub fn lock_statement(
+    engine: Engine,
+    table: &str,
+    quote: char,
+    primary_key: &[String],
+    keys: &[Vec<String>],
+) -> anyhow::Result<String> {
+    Ok(format!(
+        "SELECT 1 FROM {} WHERE {} FOR UPDATE NOWAIT",
+        table,
+        key_condition(engine, table, quote, primary_key, keys, "lock")?
+    ))
+}
+
+/// builds the condition matching the rows whose primary key values are `keys`
+fn key_condition(
+    engine: Engine,
+    table: &str,
+    quote: char,
+    primary_key: &[String],
+    keys: &[Vec<String>],
+    action: &str,
+) -> anyhow::Result<String> {
+    if primary_key.is_empty() {
+        return Err(anyhow::anyhow!(
+            "{} has no primary key to identify the rows to {}",
+            table,
+            action
+        ));
+    }
+    let tuple = |values: Vec<String>| {
+        if values.len() == 1 {
+            values.join("")
+        } else {
+            format!("({})", values.join(", "))
+        }
+    };
+    Ok(format!(
+        "{columns} IN ({keys})",
+        columns = tuple(
+            primary_key
+                .iter()
+                .map(|column| format!("{quote}{}{quote}", column, quote = quote))
+                .collect()
+        ),
+        keys = keys
+            .iter()
+            .map(|key| tuple(
+                key.iter()
+                    .map(|value| literal(engine, Some(value), ColumnKind::Text))
+                    .collect()
+            ))
+            .collect::<Vec<String>>()
+            .join(", ")
+    ))
+}
+
+/// a page of records in primary key order with their headers, and the key of its last record which the next page is
+/// fetched after
+pub type KeysetPage<T> = (Vec<String>, Vec<Vec<T>>, Option<Vec<String>>);
+
+/// the name of the columns a keyset page selects the key of its records into, followed by their position in the key
+const KEY_COLUMN: &str = "gobang_key_";
+
+/// returns the columns a keyset page selects after those of the table, which are the columns of `primary_key` cast
+/// to text with `cast`, such as `CAST({text} AS TEXT)`. unlike the values shown in the table, the server reads its
+/// own text back as the types of the columns, so the next page starts right after the last record
+pub fn key_columns(primary_key: &[String], quote: char, cast: &str) -> String {
+    primary_key
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            format!(
+                ", {} AS {quote}{}{}{quote}",
+                cast.replace(
+                    "{text}",
+                    format!("{quote}{}{quote}", column, quote = quote).as_str()
+                ),
+                KEY_COLUMN,
+                index,
+                quote = quote
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+/// takes the `keys` columns selected by `key_columns` off the end of the headers and the records, returning the key
+/// of the last record
+pub fn split_key(
+    mut headers: Vec<String>,
+    mut records: Vec<Vec<Option<String>>>,
+    keys: usize,
+) -> KeysetPage<Option<String>> {
+    headers.truncate(headers.len().saturating_sub(keys));
+    let mut key = None;
+    for record in records.iter_mut() {
+        key = record
+            .split_off(record.len().saturating_sub(keys))
+            .into_iter()
+            .collect::<Option<Vec<String>>>()
+            .filter(|key| !key.is_empty());
+    }
+    (headers, records, key)
+}
+
+/// builds a clause to fetch the page of records following `key` in primary key order, where `key` holds the values
+/// of the columns as the server writes them as text, written as text literals of `engine`
+pub fn keyset_clause(
+    engine: Engine,
+    primary_key: &[String],
+    key: Option<&[String]>,
+    filter: Option<String>,
+    quote: char,
+) -> String {
+    let columns = primary_key
+        .iter()
+        .map(|column| format!("{quote}{}{quote}", column, quote = quote))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let mut conditions = vec![];
+    if let Some(filter) = filter {
+        conditions.push(format!("({})", filter));
+    }
+    if let Some(key) = key {
+        conditions.push(format!(
+            "({}) > ({})",
+            columns,
+            key.iter()
+                .map(|value| literal(engine, Some(value), ColumnKind::Text))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+    }
+    format!(
+        "{}ORDER BY {} LIMIT {}",
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {} ", conditions.join(" AND "))
+        },
+        columns,
+        RECORDS_LIMIT_PER_PAGE
+    )
 }
 
 pub trait TableRow: std::marker::Send {
     fn fields(&self) -> Vec<String>;
     fn columns(&self) -> Vec<String>;
 }
+
+#[cfg(test)]
+mod test {
+    use super::{
+        binary_length, bulk_edit_statements, column_stats_statement, decode_binary,
+        delete_statement, display_value, encode_binary, increment, insert_batches,
+        insert_row_statement, insert_statement, insert_statements, is_connection_error,
+        is_nullable, is_numeric_type, is_page_error, is_read_only_statement, is_read_query,
+        is_transient_error, jitter, join_conditions, key_columns, keyset_clause, literal,
+        lock_statement, page_statement, quality_statement, referenced_tables, reinsert_statement,
+        restore_statements, retry_delay, routine_argument, routine_parameters, setting_statement,
+        split_key, split_statements, transaction_change, type_badge, type_length, unknown_names,
+        update_statement, BulkEdit, ChildReference, ColumnKind, Engine, Feature,
+        ForeignKeyReference, PlanNode, RoutineParameter, ServerVersion, TextSyntax,
+    };
+    use crate::metadata::MetadataCache;
+    use database_tree::{Child, Database, Table};
+    use std::time::Duration;
+
+    #[test]
+    fn test_plan_from_indented_lines() {
+        let plan = PlanNode::from_indented_lines(
+            "-> Nested loop inner join  (cost=1.10 rows=2)
+    -> Table scan on a  (cost=0.45 rows=2)
+    -> Single-row index lookup on b using PRIMARY (id=a.b_id)
+        -> Filter: (b.x > 1)
+-> Limit: 10 row(s)
+",
+        );
+        assert_eq!(
+            plan,
+            vec![
+                PlanNode {
+                    label: "Nested loop inner join  (cost=1.10 rows=2)".to_string(),
+                    children: vec![
+                        PlanNode::new("Table scan on a  (cost=0.45 rows=2)".to_string()),
+                        PlanNode {
+                            label: "Single-row index lookup on b using PRIMARY (id=a.b_id)"
+                                .to_string(),
+                            children: vec![PlanNode::new("Filter: (b.x > 1)".to_string())],
+                        },
+                    ],
+                },
+                PlanNode::new("Limit: 10 row(s)".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_from_parent_ids() {
+        let plan = PlanNode::from_parent_ids(&[
+            (2, 0, "SCAN a".to_string()),
+            (
+                5,
+                0,
+                "SEARCH b USING INTEGER PRIMARY KEY (rowid=?)".to_string(),
+            ),
+            (7, 5, "SCALAR SUBQUERY 1".to_string()),
+        ]);
+        assert_eq!(
+            plan,
+            vec![
+                PlanNode::new("SCAN a".to_string()),
+                PlanNode {
+                    label: "SEARCH b USING INTEGER PRIMARY KEY (rowid=?)".to_string(),
+                    children: vec![PlanNode::new("SCALAR SUBQUERY 1".to_string())],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_server_version() {
+        let version = ServerVersion::parse("13.4 (Debian 13.4-1.pgdg100+1)");
+        assert!(version.at_least(&[9, 3]));
+        assert!(version.at_least(&[13, 4, 0]));
+        assert!(!version.at_least(&[13, 5]));
+        assert!(!version.is_mariadb());
+
+        let version = ServerVersion::parse("10.5.12-MariaDB-1:10.5.12+maria~focal");
+        assert!(version.is_mariadb());
+        assert!(version.at_least(&[10, 5]));
+        assert!(!version.at_least(&[10, 5, 13]));
+        assert_eq!(version.to_string(), "10.5.12-MariaDB-1:10.5.12+maria~focal");
+
+        assert!(!ServerVersion::parse("5.7.35-log").at_least(&[8, 0, 18]));
+    }
+
+    #[test]
+    fn test_feature_required_by() {
+        assert_eq!(
+            Feature::required_by("explain analyze select * from a", Engine::MySql),
+            Some(Feature::ExplainAnalyze)
+        );
+        assert_eq!(
+            Feature::required_by("EXPLAIN (ANALYZE, BUFFERS) SELECT 1", Engine::MySql),
+            Some(Feature::ExplainAnalyze)
+        );
+        assert_eq!(
+            Feature::required_by("EXPLAIN SELECT 1", Engine::MySql),
+            None
+        );
+        assert_eq!(
+            Feature::required_by("REFRESH MATERIALIZED VIEW a", Engine::MySql),
+            Some(Feature::MaterializedViews)
+        );
+        assert_eq!(
+            Feature::required_by("WITH a AS (SELECT 1) SELECT * FROM a", Engine::MySql),
+            Some(Feature::CommonTableExpressions)
+        );
+        assert_eq!(
+            Feature::required_by("DELETE FROM a WHERE id = 1 RETURNING id", Engine::MySql),
+            Some(Feature::Returning)
+        );
+        assert_eq!(
+            Feature::required_by("SELECT 1 FROM a FOR UPDATE NOWAIT", Engine::MySql),
+            Some(Feature::LockNowait)
+        );
+        assert_eq!(
+            Feature::required_by("SELECT 'returning', `with` FROM a", Engine::MySql),
+            None
+        );
+    }
+
+    #[test]
+    fn test_foreign_key_reference_filter() {
+        let reference = ForeignKeyReference {
+            column: "user_id".to_string(),
+            ref_database: None,
+            ref_schema: None,
+            ref_table: "users".to_string(),
+            ref_column: "id".to_string(),
+        };
+        assert_eq!(reference.filter(Engine::MySql, "1", '`'), "`id` = '1'");
+        assert_eq!(
+            reference.filter(Engine::Postgres, "it's", '"'),
+            r#""id" = 'it''s'"#
+        );
+
+        let child = ChildReference {
+            table: Table {
+                name: "order_items".to_string(),
+                create_time: None,
+                update_time: None,
+                engine: None,
+                schema: None,
+                view: false,
+            },
+            column: "order_id".to_string(),
+            ref_column: "id".to_string(),
+        };
+        assert_eq!(child.filter(Engine::MySql, "5", '`'), "`order_id` = '5'");
+    }
+
+    #[test]
+    fn test_key_columns() {
+        let primary_key = vec!["id".to_string(), "name".to_string()];
+        assert_eq!(
+            key_columns(&primary_key, '`', "CAST({text} AS CHAR)"),
+            ", CAST(`id` AS CHAR) AS `gobang_key_0`, CAST(`name` AS CHAR) AS `gobang_key_1`"
+        );
+        assert_eq!(key_columns(&[], '"', "CAST({text} AS TEXT)"), "");
+        let some = |value: &str| Some(value.to_string());
+        assert_eq!(
+            split_key(
+                vec!["id", "data", "gobang_key_0"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                vec![
+                    vec![some("1"), None, some("1")],
+                    vec![some("2"), some("ab"), some("2.50")],
+                ],
+                1
+            ),
+            (
+                vec!["id".to_string(), "data".to_string()],
+                vec![vec![some("1"), None], vec![some("2"), some("ab")]],
+                Some(vec!["2.50".to_string()])
+            )
+        );
+        assert_eq!(
+            split_key(vec!["id".to_string()], vec![vec![some("1")]], 0),
+            (vec!["id".to_string()], vec![vec![some("1")]], None)
+        );
+    }
+
+    #[test]
+    fn test_keyset_clause() {
+        let primary_key = vec!["id".to_string(), "name".to_string()];
+        assert_eq!(
+            keyset_clause(Engine::MySql, &primary_key, None, None, '`'),
+            "ORDER BY `id`, `name` LIMIT 200"
+        );
+        assert_eq!(
+            keyset_clause(
+                Engine::Postgres,
+                &primary_key,
+                Some(&["1".to_string(), "it's".to_string()]),
+                Some("age > 20".to_string()),
+                '"'
+            ),
+            r#"WHERE (age > 20) AND ("id", "name") > ('1', 'it''s') ORDER BY "id", "name" LIMIT 200"#
+        );
+        // MySQL reads a backslash in a key as an escape unless it is doubled
+        assert_eq!(
+            keyset_clause(
+                Engine::MySql,
+                &primary_key,
+                Some(&["1".to_string(), "C:\\".to_string()]),
+                None,
+                '`'
+            ),
+            r"WHERE (`id`, `name`) > ('1', 'C:\\') ORDER BY `id`, `name` LIMIT 200"
+        );
+    }
+
+    #[test]
+    fn test_split_statements() {
+        assert_eq!(
+            split_statements(
+                "SELECT 1; SELECT ';' ;\n\nUPDATE a SET b = \"c;d\";",
+                Engine::MySql
+            ),
+            vec![
+                "SELECT 1".to_string(),
+                "SELECT ';'".to_string(),
+                "UPDATE a SET b = \"c;d\"".to_string()
+            ]
+        );
+        assert!(split_statements(" ; ", Engine::MySql).is_empty());
+        assert_eq!(
+            split_statements(
+                "-- users; orders\nSELECT 1 /* ; */ FROM a;\nSELECT '--;' -- last\n; -- done",
+                Engine::MySql
+            ),
+            vec![
+                "SELECT 1 /* ; */ FROM a".to_string(),
+                "SELECT '--;'".to_string()
+            ]
+        );
+        // MySQL runs its versioned comments, escapes quotes with backslashes, comments with `#` and subtracts `--1`
+        assert_eq!(
+            split_statements(
+                "/*!40101 SET NAMES utf8 */;\n# a; b\nSELECT 'it\\'s; fine', 5--1;\nSELECT /*+ MAX_EXECUTION_TIME(1) */ 1",
+                Engine::MySql
+            ),
+            vec![
+                "/*!40101 SET NAMES utf8 */".to_string(),
+                "SELECT 'it\\'s; fine', 5--1".to_string(),
+                "SELECT /*+ MAX_EXECUTION_TIME(1) */ 1".to_string()
+            ]
+        );
+        assert_eq!(
+            split_statements(
+                "CREATE FUNCTION f() RETURNS int AS $body$ SELECT 1; $body$ LANGUAGE sql; SELECT $$a;b$$, $1",
+                Engine::Postgres
+            ),
+            vec![
+                "CREATE FUNCTION f() RETURNS int AS $body$ SELECT 1; $body$ LANGUAGE sql".to_string(),
+                "SELECT $$a;b$$, $1".to_string()
+            ]
+        );
+        assert_eq!(
+            split_statements("SELECT 1 # 2; SELECT '\\'; SELECT 3", Engine::Postgres),
+            vec![
+                "SELECT 1 # 2".to_string(),
+                "SELECT '\\'".to_string(),
+                "SELECT 3".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_names() {
+        let mut metadata = MetadataCache::default();
+        let table = |name: &str| Table {
+            name: name.to_string(),
+            create_time: None,
+            update_time: None,
+            engine: None,
+            schema: None,
+            view: false,
+        };
+        metadata.add_database(&Database::new(
+            "shop".to_string(),
+            vec![
+                Child::Table(table("orders")),
+                Child::Table(table("customers")),
+            ],
+        ));
+        metadata.set_columns(
+            &table("orders"),
+            vec!["id".to_string(), "customer_id".to_string()],
+        );
+        let unknown = |sql: &str| {
+            unknown_names(sql, Engine::MySql, &metadata)
+                .into_iter()
+                .map(|range| sql.chars().skip(range.start).take(range.len()).collect())
+                .collect::<Vec<String>>()
+        };
+
+        assert_eq!(
+            unknown(
+                "SELECT o.custmer_id, o.id FROM orders o JOIN custmers c ON c.id = o.customer_id"
+            ),
+            vec!["custmer_id", "custmers"]
+        );
+        assert_eq!(
+            unknown("select orders.customer_id from shop.orders; select * from shop.ordrs"),
+            vec!["ordrs"]
+        );
+        // the columns of customers are not fetched, and the names of CTEs, functions and comments are not tables
+        assert!(unknown(
+            "WITH recent AS (SELECT * FROM orders) SELECT c.nme, EXTRACT(YEAR FROM created_at) \
+             FROM recent, customers c, generate_series(1, 3) -- FROM nowhere"
+        )
+        .is_empty());
+        assert!(unknown("CREATE TABLE invoices (id INT)").is_empty());
+        assert!(unknown("SELECT * FROM missing").len() == 1);
+        assert!(unknown_names(
+            "SELECT * FROM missing",
+            Engine::MySql,
+            &MetadataCache::default()
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_join_conditions() {
+        let mut metadata = MetadataCache::default();
+        let table = |name: &str| Table {
+            name: name.to_string(),
+            create_time: None,
+            update_time: None,
+            engine: None,
+            schema: None,
+            view: false,
+        };
+        let columns = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        metadata.set_columns(&table("orders"), columns(&["id", "customer_id", "region"]));
+        metadata.set_columns(&table("customers"), columns(&["id", "name", "region"]));
+        metadata.set_columns(&table("items"), columns(&["id", "order_id"]));
+        metadata.set_references(
+            &table("orders"),
+            vec![ForeignKeyReference {
+                column: "customer_id".to_string(),
+                ref_database: None,
+                ref_schema: None,
+                ref_table: "customers".to_string(),
+                ref_column: "id".to_string(),
+            }],
+        );
+
+        assert_eq!(
+            join_conditions(
+                "SELECT * FROM orders o JOIN customers AS c ON ",
+                Engine::MySql,
+                &metadata
+            ),
+            vec!["c.id = o.customer_id", "c.region = o.region"]
+        );
+        // the foreign keys are not fetched for items, whose order_id is matched by its name
+        assert_eq!(
+            join_conditions(
+                "select 1; SELECT * FROM customers c JOIN orders o ON o.customer_id = c.id JOIN items ON",
+                Engine::MySql,
+                &metadata
+            ),
+            vec!["items.order_id = o.id"]
+        );
+        assert!(join_conditions(
+            "SELECT * FROM orders o JOIN customers c",
+            Engine::MySql,
+            &metadata
+        )
+        .is_empty());
+        assert!(
+            join_conditions("SELECT * FROM orders o WHERE ON", Engine::MySql, &metadata).is_empty()
+        );
+        assert!(join_conditions(
+            "SELECT * FROM orders JOIN missing ON",
+            Engine::MySql,
+            &metadata
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_referenced_tables() {
+        let table = |schema: Option<&str>, name: &str| (schema.map(String::from), name.to_string());
+        assert_eq!(
+            referenced_tables(
+                "WITH recent AS (SELECT * FROM orders WHERE created_at > '2021-01-01 FROM x')
+                 SELECT * FROM recent r, public.\"Users\" AS u
+                 LEFT JOIN `shop`.items i ON i.id = r.item_id
+                 WHERE u.id IN (SELECT user_id FROM orders)",
+                Engine::MySql
+            ),
+            vec![
+                table(None, "orders"),
+                table(Some("public"), "Users"),
+                table(Some("shop"), "items"),
+            ]
+        );
+        assert_eq!(
+            referenced_tables(
+                "INSERT INTO logs (id) VALUES (1); UPDATE users SET a = 1; DROP TABLE IF EXISTS tmp",
+                Engine::MySql
+            ),
+            vec![
+                table(None, "logs"),
+                table(None, "users"),
+                table(None, "tmp")
+            ]
+        );
+        assert_eq!(
+            referenced_tables("SELECT * FROM (SELECT 1) t", Engine::MySql),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_setting_statement() {
+        assert_eq!(
+            setting_statement("SET SESSION", "max_execution_time", "5000").unwrap(),
+            "SET SESSION max_execution_time = 5000"
+        );
+        assert_eq!(
+            setting_statement("SET", "work_mem", "64MB").unwrap(),
+            "SET work_mem = '64MB'"
+        );
+        assert_eq!(
+            setting_statement("SET", "search_path", "it's").unwrap(),
+            "SET search_path = 'it''s'"
+        );
+        assert!(setting_statement("SET", "a; DROP TABLE b", "1").is_err());
+    }
+
+    #[test]
+    fn test_is_read_query() {
+        assert!(is_read_query("select * from a"));
+        assert!(is_read_query("  SHOW TABLES"));
+        assert!(!is_read_query("DELETE FROM a"));
+        assert!(!is_read_query(""));
+    }
+
+    #[test]
+    fn test_is_read_only_statement() {
+        assert!(is_read_only_statement(
+            "select * from a where note = 'delete'",
+            Engine::MySql
+        ));
+        assert!(is_read_only_statement("SHOW TABLES", Engine::MySql));
+        assert!(!is_read_only_statement(
+            "SELECT $$delete$$, 1 # into",
+            Engine::Postgres
+        ));
+        assert!(is_read_only_statement(
+            "SELECT $$ delete $$ /* into */",
+            Engine::Postgres
+        ));
+        assert!(is_read_only_statement("SELECT 1 # into b", Engine::MySql));
+        assert!(is_read_only_statement(
+            "EXPLAIN ANALYZE SELECT 1",
+            Engine::MySql
+        ));
+        assert!(!is_read_only_statement(
+            "EXPLAIN ANALYZE DELETE FROM a",
+            Engine::MySql
+        ));
+        assert!(!is_read_only_statement(
+            "WITH d AS (DELETE FROM a RETURNING *) SELECT * FROM d",
+            Engine::MySql
+        ));
+        assert!(!is_read_only_statement(
+            "SELECT * INTO b FROM a",
+            Engine::MySql
+        ));
+        assert!(!is_read_only_statement(
+            "SELECT * FROM a FOR UPDATE",
+            Engine::MySql
+        ));
+        assert!(!is_read_only_statement(
+            "PRAGMA journal_mode = WAL",
+            Engine::MySql
+        ));
+        assert!(!is_read_only_statement("", Engine::MySql));
+    }
+
+    #[test]
+    fn test_transaction_change() {
+        assert_eq!(transaction_change("BEGIN", Engine::MySql), Some(true));
+        assert_eq!(
+            transaction_change("begin immediate", Engine::MySql),
+            Some(true)
+        );
+        assert_eq!(
+            transaction_change("START TRANSACTION READ ONLY", Engine::MySql),
+            Some(true)
+        );
+        assert_eq!(transaction_change("commit", Engine::MySql), Some(false));
+        assert_eq!(
+            transaction_change("ROLLBACK WORK", Engine::MySql),
+            Some(false)
+        );
+        assert_eq!(
+            transaction_change("ROLLBACK TO SAVEPOINT a", Engine::MySql),
+            None
+        );
+        assert_eq!(transaction_change("SAVEPOINT a", Engine::MySql), None);
+        assert_eq!(transaction_change("START SLAVE", Engine::MySql), None);
+        assert_eq!(transaction_change("SELECT 'BEGIN'", Engine::MySql), None);
+        assert_eq!(transaction_change("", Engine::MySql), None);
+    }
+
+    #[test]
+    fn test_is_transient_error() {
+        let reset = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+        let err = anyhow::Error::from(sqlx::Error::Io(reset));
+        assert!(is_transient_error(&err));
+        assert!(is_connection_error(&err));
+        let err = anyhow::Error::from(sqlx::Error::RowNotFound);
+        assert!(!is_transient_error(&err));
+        assert!(!is_transient_error(&anyhow::anyhow!("syntax error")));
+        assert!(!is_page_error(&err));
+        assert!(is_page_error(&anyhow::anyhow!(
+            "near \"gobang_page\": syntax error"
+        )));
+        assert!(!is_page_error(&anyhow::anyhow!("syntax error")));
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let base = Duration::from_millis(100);
+        assert_eq!(retry_delay(base, 0, 0.0), Duration::from_millis(100));
+        assert_eq!(retry_delay(base, 2, 0.5), Duration::from_millis(450));
+        assert_eq!(retry_delay(base, 1, 2.0), Duration::from_millis(300));
+        assert!((0.0..1.0).contains(&jitter()));
+    }
+
+    #[test]
+    fn test_insert_statement() {
+        assert_eq!(
+            insert_statement(
+                Engine::MySql,
+                "`foo`",
+                '`',
+                &["id".to_string(), "name".to_string()],
+                &[
+                    vec!["1".to_string(), "a'b".to_string()],
+                    vec!["2".to_string(), "c".to_string()]
+                ]
+            ),
+            "INSERT INTO `foo` (`id`, `name`) VALUES ('1', 'a''b'), ('2', 'c')"
+        );
+    }
+
+    #[test]
+    fn test_delete_statement() {
+        assert_eq!(
+            delete_statement(
+                Engine::MySql,
+                "`users`",
+                '`',
+                &["id".to_string()],
+                &[vec!["1".to_string()], vec!["2".to_string()]]
+            )
+            .unwrap(),
+            "DELETE FROM `users` WHERE `id` IN ('1', '2')"
+        );
+        assert_eq!(
+            delete_statement(
+                Engine::Postgres,
+                r#""public"."items""#,
+                '"',
+                &["order_id".to_string(), "sku".to_string()],
+                &[vec!["1".to_string(), "a'b".to_string()]]
+            )
+            .unwrap(),
+            r#"DELETE FROM "public"."items" WHERE ("order_id", "sku") IN (('1', 'a''b'))"#
+        );
+        assert!(
+            delete_statement(Engine::MySql, "`logs`", '`', &[], &[vec!["1".to_string()]]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_routine_parameters() {
+        assert_eq!(
+            routine_parameters("IN id int, OUT total decimal(10,2), note varchar(20)"),
+            vec![
+                RoutineParameter {
+                    mode: "IN".to_string(),
+                    name: "id".to_string(),
+                    r#type: "int".to_string(),
+                },
+                RoutineParameter {
+                    mode: "OUT".to_string(),
+                    name: "total".to_string(),
+                    r#type: "decimal(10,2)".to_string(),
+                },
+                RoutineParameter {
+                    mode: "IN".to_string(),
+                    name: "note".to_string(),
+                    r#type: "varchar(20)".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            routine_parameters("integer, VARIADIC nums numeric[] DEFAULT '{}'"),
+            vec![
+                RoutineParameter {
+                    mode: "IN".to_string(),
+                    name: String::new(),
+                    r#type: "integer".to_string(),
+                },
+                RoutineParameter {
+                    mode: "VARIADIC".to_string(),
+                    name: "nums".to_string(),
+                    r#type: "numeric[] DEFAULT '{}'".to_string(),
+                },
+            ]
+        );
+        assert!(routine_parameters("").is_empty());
+        assert_eq!(routine_argument(Engine::Postgres, ""), "NULL");
+        assert_eq!(routine_argument(Engine::Postgres, "it's"), "'it''s'");
+        assert_eq!(routine_argument(Engine::MySql, "C:\\"), "'C:\\\\'");
+    }
+
+    #[test]
+    fn test_update_statement() {
+        assert_eq!(
+            update_statement(
+                Engine::MySql,
+                "`users`",
+                '`',
+                &["id".to_string()],
+                &[vec!["1".to_string()], vec!["2".to_string()]],
+                "name"
+            )
+            .unwrap(),
+            "UPDATE `users` SET `name` = <value> WHERE `id` IN ('1', '2')"
+        );
+        assert!(update_statement(
+            Engine::MySql,
+            "`logs`",
+            '`',
+            &[],
+            &[vec!["1".to_string()]],
+            "at"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_bulk_edit_statements() {
+        let keys = vec![
+            vec!["1".to_string()],
+            vec!["2".to_string()],
+            vec!["3".to_string()],
+        ];
+        let columns = vec!["code".to_string(), "note".to_string()];
+        let kinds = vec![ColumnKind::Text, ColumnKind::Text];
+        let rows = vec![
+            vec![Some("A-09".to_string()), Some("it's".to_string())],
+            vec![Some("B".to_string()), None],
+            vec![Some("C".to_string()), Some("x".to_string())],
+        ];
+        let statements = |edit| {
+            bulk_edit_statements(
+                Engine::MySql,
+                "`items`",
+                '`',
+                &["id".to_string()],
+                &keys,
+                &columns,
+                &kinds,
+                &rows,
+                edit,
+            )
+        };
+        assert_eq!(
+            statements(BulkEdit::FillDown).unwrap(),
+            vec!["UPDATE `items` SET `code` = 'A-09', `note` = 'it''s' WHERE `id` IN ('2', '3')"]
+        );
+        assert_eq!(
+            statements(BulkEdit::Clear).unwrap(),
+            vec!["UPDATE `items` SET `code` = NULL, `note` = NULL WHERE `id` IN ('1', '2', '3')"]
+        );
+        // a note has no number to count up from
+        assert!(statements(BulkEdit::Increment).is_err());
+        assert_eq!(
+            bulk_edit_statements(
+                Engine::MySql,
+                "`items`",
+                '`',
+                &["id".to_string()],
+                &keys,
+                &columns[..1],
+                &kinds[..1],
+                &rows,
+                BulkEdit::Increment
+            )
+            .unwrap(),
+            vec![
+                "UPDATE `items` SET `code` = 'A-10' WHERE `id` IN ('2')",
+                "UPDATE `items` SET `code` = 'A-11' WHERE `id` IN ('3')",
+            ]
+        );
+        // numbers are left unquoted and bytes are written as binary literals
+        assert_eq!(
+            bulk_edit_statements(
+                Engine::MySql,
+                "`items`",
+                '`',
+                &["id".to_string()],
+                &keys[..2],
+                &["count".to_string(), "data".to_string()],
+                &[ColumnKind::Number, ColumnKind::Binary],
+                &[
+                    vec![Some("7".to_string()), Some("\\x00ff".to_string())],
+                    vec![Some("8".to_string()), None],
+                ],
+                BulkEdit::FillDown
+            )
+            .unwrap(),
+            vec!["UPDATE `items` SET `count` = 7, `data` = X'00ff' WHERE `id` IN ('2')"]
+        );
+        assert!(bulk_edit_statements(
+            Engine::MySql,
+            "`items`",
+            '`',
+            &[],
+            &[],
+            &columns,
+            &kinds,
+            &rows,
+            BulkEdit::Clear
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_restore_statements() {
+        let keys = vec![vec!["1".to_string()], vec!["2".to_string()]];
+        let kinds = vec![ColumnKind::Text, ColumnKind::Number, ColumnKind::Binary];
+        let rows = vec![
+            vec![
+                Some("it's".to_string()),
+                Some("5".to_string()),
+                Some("\\x01".to_string()),
+            ],
+            vec![None, Some("6".to_string()), None],
+        ];
+        assert_eq!(
+            restore_statements(
+                Engine::Postgres,
+                r#""items""#,
+                '"',
+                &["id".to_string()],
+                &keys,
+                &["note".to_string(), "count".to_string(), "data".to_string()],
+                &kinds,
+                &rows
+            )
+            .unwrap(),
+            vec![
+                r#"UPDATE "items" SET "note" = 'it''s', "count" = 5, "data" = '\x01'::bytea WHERE "id" IN ('1')"#,
+                r#"UPDATE "items" SET "note" = NULL, "count" = 6, "data" = NULL WHERE "id" IN ('2')"#,
+            ]
+        );
+        // the changed rows cannot be found by their former keys
+        assert!(restore_statements(
+            Engine::Postgres,
+            r#""items""#,
+            '"',
+            &["id".to_string()],
+            &keys,
+            &["id".to_string(), "count".to_string()],
+            &kinds,
+            &rows
+        )
+        .unwrap()
+        .is_empty());
+    }
+
+    #[test]
+    fn test_reinsert_statement() {
+        assert_eq!(
+            reinsert_statement(
+                Engine::MySql,
+                "`items`",
+                '`',
+                &[
+                    "id".to_string(),
+                    "note".to_string(),
+                    "total".to_string(),
+                    "data".to_string()
+                ],
+                &[
+                    ColumnKind::Number,
+                    ColumnKind::Text,
+                    ColumnKind::Number,
+                    ColumnKind::Binary
+                ],
+                &["total".to_string()],
+                &[
+                    vec![Some("1".to_string()), None, Some("2".to_string()), None],
+                    vec![
+                        Some("2".to_string()),
+                        Some("it's C:\\".to_string()),
+                        Some("4".to_string()),
+                        Some("\\x00ff".to_string())
+                    ],
+                    vec![
+                        Some("3".to_string()),
+                        Some("NULL".to_string()),
+                        Some("6".to_string()),
+                        Some("\\x".to_string())
+                    ]
+                ]
+            ),
+            "INSERT INTO `items` (`id`, `note`, `data`) VALUES (1, NULL, NULL), (2, 'it''s C:\\\\', X'00ff'), (3, 'NULL', X'')"
+        );
+    }
+
+    #[test]
+    fn test_insert_statements() {
+        let headers = ["id".to_string(), "note".to_string(), "data".to_string()];
+        let kinds = [ColumnKind::Number, ColumnKind::Text, ColumnKind::Binary];
+        let rows = [
+            vec![
+                Some("1".to_string()),
+                Some("it's C:\\tmp".to_string()),
+                Some("\\x00ff".to_string()),
+            ],
+            vec![Some("007".to_string()), None, Some("\\x".to_string())],
+        ];
+        assert_eq!(
+            insert_statements(Engine::MySql, "`items`", '`', &headers, &kinds, &rows),
+            "INSERT INTO `items` (`id`, `note`, `data`) VALUES (1, 'it''s C:\\\\tmp', X'00ff');\n\
+             INSERT INTO `items` (`id`, `note`, `data`) VALUES (007, NULL, X'');"
+        );
+        assert_eq!(
+            insert_statements(Engine::Postgres, "\"items\"", '"', &headers, &kinds, &rows[..1]),
+            "INSERT INTO \"items\" (\"id\", \"note\", \"data\") VALUES (1, 'it''s C:\\tmp', '\\x00ff'::bytea);"
+        );
+        assert_eq!(
+            insert_statements(
+                Engine::DuckDb,
+                "items",
+                '"',
+                &headers[2..],
+                &kinds[2..],
+                &[vec![Some("\\x0a".to_string())]]
+            ),
+            "INSERT INTO items (\"data\") VALUES (from_hex('0a'));"
+        );
+        // text is never read as bytes or NULL, however it looks
+        assert_eq!(
+            insert_statements(
+                Engine::Sqlite,
+                "items",
+                '"',
+                &headers[..2],
+                &[ColumnKind::Number, ColumnKind::Text],
+                &[vec![Some("inf".to_string()), Some("\\x00ff".to_string())]]
+            ),
+            "INSERT INTO items (\"id\", \"note\") VALUES ('inf', '\\x00ff');"
+        );
+        assert_eq!(
+            insert_statements(
+                Engine::Sqlite,
+                "items",
+                '"',
+                &headers[1..2],
+                &[],
+                &[vec![Some("NULL".to_string())], vec![Some("\\x".to_string())]]
+            ),
+            "INSERT INTO items (\"note\") VALUES ('NULL');\nINSERT INTO items (\"note\") VALUES ('\\x');"
+        );
+    }
+
+    #[test]
+    fn test_literal() {
+        assert_eq!(
+            literal(Engine::MySql, Some("a\\'b"), ColumnKind::Text),
+            "'a\\\\''b'"
+        );
+        assert_eq!(
+            literal(Engine::Postgres, Some("a\\'b"), ColumnKind::Text),
+            "'a\\''b'"
+        );
+        assert_eq!(literal(Engine::Sqlite, None, ColumnKind::Binary), "NULL");
+        // a value of a binary column which is not hex stays text
+        assert_eq!(
+            literal(Engine::Sqlite, Some("\\xzz"), ColumnKind::Binary),
+            "'\\xzz'"
+        );
+        assert_eq!(ColumnKind::from_badge(Some("bin")), ColumnKind::Binary);
+        assert_eq!(ColumnKind::from_badge(Some("num")), ColumnKind::Number);
+        assert_eq!(ColumnKind::from_badge(None), ColumnKind::Text);
+        assert_eq!(display_value(&None), "NULL");
+        assert_eq!(display_value(&Some("NULL".to_string())), "NULL");
+    }
+
+    #[test]
+    fn test_insert_batches() {
+        let rows = (1..=5)
+            .map(|id| vec![Some(id.to_string())])
+            .collect::<Vec<_>>();
+        assert_eq!(
+            insert_batches(
+                Engine::Sqlite,
+                "items",
+                '"',
+                &["id".to_string()],
+                &[ColumnKind::Number],
+                &rows,
+                2
+            ),
+            vec![
+                "INSERT INTO items (\"id\") VALUES (1), (2)",
+                "INSERT INTO items (\"id\") VALUES (3), (4)",
+                "INSERT INTO items (\"id\") VALUES (5)",
+            ]
+        );
+        assert!(insert_batches(Engine::Sqlite, "items", '"', &[], &[], &[], 2).is_empty());
+    }
+
+    #[test]
+    fn test_increment() {
+        assert_eq!(increment("41", 1).as_deref(), Some("42"));
+        assert_eq!(increment("-3", 5).as_deref(), Some("2"));
+        assert_eq!(increment("item-099", 2).as_deref(), Some("item-101"));
+        assert_eq!(increment("v1.9 beta", 1).as_deref(), Some("v1.10 beta"));
+        assert_eq!(increment("none", 1), None);
+    }
+
+    #[test]
+    fn test_column_stats_statement() {
+        assert_eq!(
+            column_stats_statement("`shop`.`orders`", '`', "total", true, Some("total > 0")),
+            "SELECT COUNT(*), COUNT(`total`), COUNT(DISTINCT `total`), MIN(`total`), MAX(`total`), AVG(`total`) FROM `shop`.`orders` WHERE total > 0"
+        );
+        assert_eq!(
+            column_stats_statement(r#""public"."users""#, '"', "name", false, None),
+            r#"SELECT COUNT(*), COUNT("name"), COUNT(DISTINCT "name"), MIN("name"), MAX("name") FROM "public"."users""#
+        );
+    }
+
+    #[test]
+    fn test_quality_statement() {
+        let syntax = TextSyntax {
+            cast: "CAST({text} AS TEXT)",
+            length: "LENGTH({text})",
+            regex: None,
+        };
+        assert_eq!(
+            quality_statement("`users`", '`', "email", &syntax, Some("id > 5")),
+            "SELECT COUNT(*), COUNT(`email`), SUM(CASE WHEN CAST(`email` AS TEXT) = '' THEN 1 ELSE 0 END), COUNT(DISTINCT `email`), MIN(LENGTH(CAST(`email` AS TEXT))), MAX(LENGTH(CAST(`email` AS TEXT))), NULL, NULL FROM `users` WHERE id > 5"
+        );
+        let syntax = TextSyntax {
+            cast: "CAST({text} AS TEXT)",
+            length: "CHAR_LENGTH({text})",
+            regex: Some("{text} ~ {pattern}"),
+        };
+        assert!(
+            quality_statement(r#""public"."users""#, '"', "email", &syntax, None).contains(
+                r#"SUM(CASE WHEN CAST("email" AS TEXT) ~ '^[^@ ]+@[^@ ]+[.][^@ ]+$' THEN 1 ELSE 0 END)"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_is_numeric_type() {
+        assert!(is_numeric_type("int(11) unsigned"));
+        assert!(is_numeric_type("DECIMAL(10,2)"));
+        assert!(is_numeric_type("double precision"));
+        assert!(!is_numeric_type("interval"));
+        assert!(!is_numeric_type("varchar(255)"));
+        assert!(!is_numeric_type(""));
+    }
+
+    #[test]
+    fn test_type_badge() {
+        assert_eq!(type_badge("bigint unsigned"), Some("int"));
+        assert_eq!(type_badge("tinyint(1)"), Some("bool"));
+        assert_eq!(type_badge("boolean"), Some("bool"));
+        assert_eq!(type_badge("character varying(255)"), Some("txt"));
+        assert_eq!(type_badge("timestamp with time zone"), Some("ts"));
+        assert_eq!(type_badge("DATETIME"), Some("ts"));
+        assert_eq!(type_badge("jsonb"), Some("json"));
+        assert_eq!(type_badge("geometry"), None);
+    }
+
+    #[test]
+    fn test_type_length() {
+        assert_eq!(type_length("varchar(20)"), Some(20));
+        assert_eq!(type_length("character varying(255)"), Some(255));
+        assert_eq!(type_length("CHAR(2)"), Some(2));
+        assert_eq!(type_length("text"), None);
+        assert_eq!(type_length("decimal(10,2)"), None);
+        assert_eq!(type_length("enum('a','b')"), None);
+    }
+
+    #[test]
+    fn test_is_nullable() {
+        assert!(is_nullable("YES"));
+        assert!(!is_nullable("NO"));
+        assert!(!is_nullable("✔︎"));
+        assert!(is_nullable(""));
+    }
+
+    #[test]
+    fn test_lock_statement() {
+        assert_eq!(
+            lock_statement(
+                Engine::MySql,
+                "`shop`.`users`",
+                '`',
+                &["id".to_string()],
+                &[vec!["1".to_string()]]
+            )
+            .unwrap(),
+            "SELECT 1 FROM `shop`.`users` WHERE `id` IN ('1') FOR UPDATE NOWAIT"
+        );
+        assert!(
+            lock_statement(Engine::MySql, "`logs`", '`', &[], &[vec!["1".to_string()]]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_insert_row_statement() {
+        assert_eq!(
+            insert_row_statement(
+                Engine::Postgres,
+                r#""public"."users""#,
+                '"',
+                &[
+                    ("name".to_string(), Some("O'Brien".to_string())),
+                    ("note".to_string(), None),
+                ]
+            )
+            .unwrap(),
+            r#"INSERT INTO "public"."users" ("name", "note") VALUES ('O''Brien', NULL)"#
+        );
+        assert!(insert_row_statement(Engine::MySql, "`users`", '`', &[]).is_err());
+    }
+
+    #[test]
+    fn test_binary() {
+        let value = encode_binary(&[0x00, 0xff, 0x41]);
+        assert_eq!(value, "\\x00ff41");
+        assert_eq!(binary_length(value.as_str()), Some(3));
+        assert_eq!(decode_binary(value.as_str()), Some(vec![0x00, 0xff, 0x41]));
+        assert_eq!(binary_length("\\x"), Some(0));
+        assert_eq!(binary_length("\\x0"), None);
+        assert_eq!(binary_length("\\xzz"), None);
+        assert_eq!(binary_length("plain text"), None);
+        assert_eq!(decode_binary("\\x0g"), None);
+    }
+
+    #[test]
+    fn test_page_statement() {
+        assert_eq!(
+            page_statement("SELECT * FROM logs -- recent", Engine::MySql, 200, 200),
+            Some("SELECT * FROM (SELECT * FROM logs -- recent\n) AS gobang_page LIMIT 200 OFFSET 200".to_string())
+        );
+        assert!(page_statement(
+            "WITH t AS (SELECT 1) SELECT * FROM t",
+            Engine::MySql,
+            0,
+            200
+        )
+        .is_some());
+        assert_eq!(page_statement("SHOW TABLES", Engine::MySql, 0, 200), None);
+        assert_eq!(
+            page_statement("SELECT * FROM jobs FOR UPDATE", Engine::MySql, 0, 200),
+            None
+        );
+        assert_eq!(
+            page_statement("SELECT * INTO backup FROM jobs", Engine::MySql, 0, 200),
+            None
+        );
+        assert_eq!(
+            page_statement("DELETE FROM jobs", Engine::MySql, 0, 200),
+            None
+        );
+    }
+}
@@ -1,39 +1,193 @@
-use super::{Pool, TableRow, RECORDS_LIMIT_PER_PAGE};
+use super::{
+    encode_binary, insert_statement, is_read_query, key_columns, keyset_clause, routine_argument,
+    routine_parameters, setting_statement, split_key, CancelToken, ChildReference, DumpDefinition,
+    Engine, ExecuteResult, Feature, ForeignKeyReference, KeysetPage, PlanNode, Pool, ScheduledJob,
+    ServerVersion, Session, StatementMode, TableRow, TableStatus, TextSyntax,
+    RECORDS_LIMIT_PER_PAGE,
+};
 use async_trait::async_trait;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
-use database_tree::{Child, Database, Table};
+use database_tree::{Child, Database, Routine, Table};
 use futures::TryStreamExt;
-use sqlx::mysql::{MySqlColumn, MySqlPoolOptions, MySqlRow};
-use sqlx::{Column as _, Row as _, TypeInfo as _};
+use sqlx::mysql::{MySqlColumn, MySqlDatabaseError, MySqlPoolOptions, MySqlRow};
+use sqlx::pool::PoolConnection;
+use sqlx::{Column as _, MySql, Row as _, TypeInfo as _, ValueRef as _};
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 pub struct MySqlPool {
     pool: sqlx::mysql::MySqlPool,
+    version: ServerVersion,
+}
+
+/// explains a failure to authenticate with a plugin the driver cannot speak, such as client_ed25519 of MariaDB, which
+/// is otherwise only reported by its name
+fn auth_error(err: sqlx::Error) -> anyhow::Error {
+    match &err {
+        sqlx::Error::Protocol(message) if message.contains("authentication plugin") => {
+            anyhow::anyhow!(
+                "{}: only mysql_native_password, caching_sha2_password and sha256_password are supported, \
+                 so the account needs to authenticate with one of them, such as with \
+                 ALTER USER ... IDENTIFIED WITH mysql_native_password BY '...' \
+                 (IDENTIFIED VIA on MariaDB)",
+                message
+            )
+        }
+        _ => err.into(),
+    }
 }
 
 impl MySqlPool {
-    pub async fn new(database_url: &str) -> anyhow::Result<Self> {
+    /// connects with `settings` applied to every connection, whose transactions cannot write when `read_only` is set
+    pub async fn new(
+        database_url: &str,
+        settings: &BTreeMap<String, String>,
+        read_only: bool,
+    ) -> anyhow::Result<Self> {
+        let mut statements = settings
+            .iter()
+            .map(|(name, value)| setting_statement("SET SESSION", name, value))
+            .collect::<anyhow::Result<Vec<String>>>()?;
+        if read_only {
+            statements.push("SET SESSION TRANSACTION READ ONLY".to_string());
+        }
+        let pool = MySqlPoolOptions::new()
+            .connect_timeout(Duration::from_millis(500))
+            .after_connect(move |conn| {
+                let statements = statements.clone();
+                Box::pin(async move {
+                    for statement in statements {
+                        sqlx::query(statement.as_str()).execute(&mut *conn).await?;
+                    }
+                    Ok(())
+                })
+            })
+            .connect(database_url)
+            .await
+            .map_err(auth_error)?;
+        let version = sqlx::query("SELECT VERSION()")
+            .fetch_one(&pool)
+            .await?
+            .try_get::<String, _>(0)?;
         Ok(Self {
-            pool: MySqlPoolOptions::new()
-                .connect_timeout(Duration::from_millis(500))
-                .connect(database_url)
-                .await?,
+            pool,
+            version: ServerVersion::parse(version.as_str()),
+        })
+    }
+
+    async fn get_values_by_query(
+        &self,
+        query: &str,
+    ) -> anyhow::Result<(Vec<String>, Vec<Vec<Option<String>>>)> {
+        let mut rows = sqlx::query(query).fetch(&self.pool);
+        let mut headers = vec![];
+        let mut records = vec![];
+        while let Some(row) = rows.try_next().await? {
+            headers = row
+                .columns()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect();
+            let mut new_row = vec![];
+            for column in row.columns() {
+                new_row.push(convert_column_value(&row, column)?)
+            }
+            records.push(new_row)
+        }
+        Ok((headers, records))
+    }
+
+    async fn foreign_keys(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<ForeignKey>> {
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            TABLE_NAME,
+            COLUMN_NAME,
+            CONSTRAINT_NAME,
+            REFERENCED_TABLE_SCHEMA,
+            REFERENCED_TABLE_NAME,
+            REFERENCED_COLUMN_NAME
+        FROM
+            INFORMATION_SCHEMA.KEY_COLUMN_USAGE
+        WHERE
+            REFERENCED_TABLE_SCHEMA IS NOT NULL
+            AND REFERENCED_TABLE_NAME IS NOT NULL
+            AND TABLE_SCHEMA = ?
+            AND TABLE_NAME = ?
+        ",
+        )
+        .bind(&database.name)
+        .bind(&table.name)
+        .fetch(&self.pool);
+        let mut foreign_keys = vec![];
+        while let Some(row) = rows.try_next().await? {
+            foreign_keys.push(ForeignKey {
+                name: row.try_get("CONSTRAINT_NAME")?,
+                column_name: row.try_get("COLUMN_NAME")?,
+                ref_database: row.try_get("REFERENCED_TABLE_SCHEMA")?,
+                ref_table: row.try_get("REFERENCED_TABLE_NAME")?,
+                ref_column: row.try_get("REFERENCED_COLUMN_NAME")?,
+            })
+        }
+        Ok(foreign_keys)
+    }
+
+    /// returns the expression of a CHECK constraint, which only MySQL 8.0.16 and MariaDB 10.2 list
+    async fn check_clause(
+        &self,
+        database: &Database,
+        table: &Table,
+        name: &str,
+    ) -> anyhow::Result<Option<String>> {
+        // MariaDB names the CHECK constraints per table, while MySQL names them per database
+        let query = if self.version.is_mariadb() {
+            "SELECT CHECK_CLAUSE FROM information_schema.CHECK_CONSTRAINTS WHERE CONSTRAINT_SCHEMA = ? AND CONSTRAINT_NAME = ? AND TABLE_NAME = ?"
+        } else {
+            "SELECT CHECK_CLAUSE FROM information_schema.CHECK_CONSTRAINTS WHERE CONSTRAINT_SCHEMA = ? AND CONSTRAINT_NAME = ?"
+        };
+        let mut query = sqlx::query(query).bind(&database.name).bind(name);
+        if self.version.is_mariadb() {
+            query = query.bind(&table.name);
+        }
+        Ok(match query.fetch_optional(&self.pool).await? {
+            Some(row) => row.try_get("CHECK_CLAUSE")?,
+            None => None,
         })
     }
 }
 
 pub struct Constraint {
     name: String,
-    column_name: String,
+    r#type: String,
+    columns: Option<String>,
+    definition: Option<String>,
 }
 
 impl TableRow for Constraint {
     fn fields(&self) -> Vec<String> {
-        vec!["name".to_string(), "column_name".to_string()]
+        vec![
+            "name".to_string(),
+            "type".to_string(),
+            "columns".to_string(),
+            "definition".to_string(),
+        ]
     }
 
     fn columns(&self) -> Vec<String> {
-        vec![self.name.to_string(), self.column_name.to_string()]
+        vec![
+            self.name.to_string(),
+            self.r#type.to_string(),
+            self.columns
+                .as_ref()
+                .map_or(String::new(), |columns| columns.to_string()),
+            self.definition
+                .as_ref()
+                .map_or(String::new(), |definition| definition.to_string()),
+        ]
     }
 }
 
@@ -80,10 +234,23 @@ impl TableRow for Column {
 pub struct ForeignKey {
     name: Option<String>,
     column_name: Option<String>,
+    ref_database: Option<String>,
     ref_table: Option<String>,
     ref_column: Option<String>,
 }
 
+impl ForeignKey {
+    fn reference(&self) -> Option<ForeignKeyReference> {
+        Some(ForeignKeyReference {
+            column: self.column_name.clone()?,
+            ref_database: self.ref_database.clone(),
+            ref_schema: None,
+            ref_table: self.ref_table.clone()?,
+            ref_column: self.ref_column.clone()?,
+        })
+    }
+}
+
 impl TableRow for ForeignKey {
     fn fields(&self) -> Vec<String> {
         vec![
@@ -113,8 +280,9 @@ impl TableRow for ForeignKey {
 }
 
 pub struct Index {
-    name: Option<String>,
-    column_name: Option<String>,
+    name: String,
+    columns: Option<String>,
+    unique: bool,
     r#type: Option<String>,
 }
 
@@ -122,19 +290,19 @@ impl TableRow for Index {
     fn fields(&self) -> Vec<String> {
         vec![
             "name".to_string(),
-            "column_name".to_string(),
+            "columns".to_string(),
+            "unique".to_string(),
             "type".to_string(),
         ]
     }
 
     fn columns(&self) -> Vec<String> {
         vec![
-            self.name
+            self.name.to_string(),
+            self.columns
                 .as_ref()
-                .map_or(String::new(), |name| name.to_string()),
-            self.column_name
-                .as_ref()
-                .map_or(String::new(), |column_name| column_name.to_string()),
+                .map_or(String::new(), |columns| columns.to_string()),
+            if self.unique { "YES" } else { "NO" }.to_string(),
             self.r#type
                 .as_ref()
                 .map_or(String::new(), |r#type| r#type.to_string()),
@@ -142,6 +310,33 @@ impl TableRow for Index {
     }
 }
 
+pub struct Trigger {
+    name: String,
+    timing: String,
+    event: String,
+    body: String,
+}
+
+impl TableRow for Trigger {
+    fn fields(&self) -> Vec<String> {
+        vec![
+            "name".to_string(),
+            "timing".to_string(),
+            "event".to_string(),
+            "body".to_string(),
+        ]
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.name.to_string(),
+            self.timing.to_string(),
+            self.event.to_string(),
+            self.body.to_string(),
+        ]
+    }
+}
+
 #[async_trait]
 impl Pool for MySqlPool {
     async fn get_databases(&self) -> anyhow::Result<Vec<Database>> {
@@ -172,52 +367,189 @@ impl Pool for MySqlPool {
                 update_time: row.try_get("Update_time")?,
                 engine: row.try_get("Engine")?,
                 schema: None,
+                // the status of a view has no engine and only this comment
+                view: row.try_get::<Option<String>, _>("Comment")?.as_deref() == Some("VIEW"),
             })
         }
-        Ok(tables.into_iter().map(|table| table.into()).collect())
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            r.ROUTINE_NAME,
+            CAST(r.ROUTINE_TYPE AS CHAR) AS ROUTINE_TYPE,
+            CAST(r.DTD_IDENTIFIER AS CHAR) AS DTD_IDENTIFIER,
+            CAST(GROUP_CONCAT(
+                CONCAT_WS(' ', p.PARAMETER_MODE, p.PARAMETER_NAME, p.DTD_IDENTIFIER)
+                ORDER BY p.ORDINAL_POSITION SEPARATOR ', '
+            ) AS CHAR) AS ARGUMENTS
+        FROM
+            information_schema.ROUTINES r
+            LEFT JOIN information_schema.PARAMETERS p
+                ON p.SPECIFIC_SCHEMA = r.ROUTINE_SCHEMA
+                AND p.SPECIFIC_NAME = r.SPECIFIC_NAME
+                AND p.ORDINAL_POSITION > 0
+        WHERE
+            r.ROUTINE_SCHEMA = ?
+        GROUP BY
+            r.ROUTINE_NAME,
+            r.ROUTINE_TYPE,
+            r.DTD_IDENTIFIER
+        ORDER BY
+            r.ROUTINE_NAME
+        ",
+        )
+        .bind(&database)
+        .fetch(&self.pool);
+        let mut routines = vec![];
+        while let Some(row) = rows.try_next().await? {
+            let name: String = row.try_get("ROUTINE_NAME")?;
+            let function = row.try_get::<String, _>("ROUTINE_TYPE")? == "FUNCTION";
+            routines.push(Routine {
+                specific_name: name.clone(),
+                name,
+                schema: None,
+                function,
+                arguments: row
+                    .try_get::<Option<String>, _>("ARGUMENTS")?
+                    .unwrap_or_default(),
+                returns: if function {
+                    row.try_get("DTD_IDENTIFIER")?
+                } else {
+                    None
+                },
+            })
+        }
+        Ok(tables
+            .into_iter()
+            .map(|table| table.into())
+            .chain(routines.into_iter().map(|routine| routine.into()))
+            .collect())
     }
 
     async fn get_records(
         &self,
         database: &Database,
         table: &Table,
-        page: u16,
+        page: usize,
         filter: Option<String>,
-    ) -> anyhow::Result<(Vec<String>, Vec<Vec<String>>)> {
-        let query = if let Some(filter) = filter {
-            format!(
-                "SELECT * FROM `{database}`.`{table}` WHERE {filter} LIMIT {page}, {limit}",
-                database = database.name,
-                table = table.name,
-                filter = filter,
-                page = page,
-                limit = RECORDS_LIMIT_PER_PAGE
-            )
-        } else {
+    ) -> anyhow::Result<(Vec<String>, Vec<Vec<Option<String>>>)> {
+        self.get_values_by_query(records_query(database, table, page, filter).as_str())
+            .await
+    }
+
+    async fn get_records_after(
+        &self,
+        database: &Database,
+        table: &Table,
+        primary_key: &[String],
+        key: Option<&[String]>,
+        filter: Option<String>,
+    ) -> anyhow::Result<KeysetPage<Option<String>>> {
+        let query = records_after_query(
+            database,
+            table,
+            primary_key,
+            key,
+            filter,
+            self.text_syntax().cast,
+        );
+        let (headers, records) = self.get_values_by_query(query.as_str()).await?;
+        Ok(split_key(headers, records, primary_key.len()))
+    }
+
+    async fn get_values(
+        &self,
+        database: &Database,
+        table: &Table,
+        page: usize,
+        primary_key: &[String],
+        key: Option<&[String]>,
+    ) -> anyhow::Result<KeysetPage<Option<String>>> {
+        // offsets only page a table in an order which stays the same, which MySQL has no row ids to give, so the rows
+        // are sorted by every column
+        let query = if primary_key.is_empty() {
+            let columns = self.get_columns(database, table).await?.len();
             format!(
-                "SELECT * FROM `{}`.`{}` LIMIT {page}, {limit}",
+                "SELECT * FROM `{}`.`{}` ORDER BY {} LIMIT {}, {}",
                 database.name,
                 table.name,
-                page = page,
-                limit = RECORDS_LIMIT_PER_PAGE
+                (1..=columns.max(1))
+                    .map(|index| index.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                page,
+                RECORDS_LIMIT_PER_PAGE
+            )
+        } else {
+            records_after_query(
+                database,
+                table,
+                primary_key,
+                key,
+                None,
+                self.text_syntax().cast,
             )
         };
-        let mut rows = sqlx::query(query.as_str()).fetch(&self.pool);
-        let mut headers = vec![];
-        let mut records = vec![];
+        let (headers, records) = self.get_values_by_query(query.as_str()).await?;
+        Ok(split_key(headers, records, primary_key.len()))
+    }
+
+    async fn get_primary_key(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<String>> {
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            COLUMN_NAME
+        FROM
+            information_schema.KEY_COLUMN_USAGE
+        WHERE
+            CONSTRAINT_NAME = 'PRIMARY'
+            AND TABLE_SCHEMA = ?
+            AND TABLE_NAME = ?
+        ORDER BY
+            ORDINAL_POSITION
+        ",
+        )
+        .bind(&database.name)
+        .bind(&table.name)
+        .fetch(&self.pool);
+        let mut primary_key = vec![];
         while let Some(row) = rows.try_next().await? {
-            headers = row
-                .columns()
-                .iter()
-                .map(|column| column.name().to_string())
-                .collect();
-            let mut new_row = vec![];
-            for column in row.columns() {
-                new_row.push(convert_column_value_to_string(&row, column)?)
-            }
-            records.push(new_row)
+            primary_key.push(row.try_get("COLUMN_NAME")?)
         }
-        Ok((headers, records))
+        Ok(primary_key)
+    }
+
+    async fn get_generated_columns(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<String>> {
+        // MariaDB before 10.2 names them VIRTUAL and PERSISTENT without GENERATED
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            COLUMN_NAME
+        FROM
+            information_schema.COLUMNS
+        WHERE
+            TABLE_SCHEMA = ?
+            AND TABLE_NAME = ?
+            AND (EXTRA LIKE '%VIRTUAL%' OR EXTRA LIKE '%STORED%' OR EXTRA LIKE '%PERSISTENT%')
+        ORDER BY
+            ORDINAL_POSITION
+        ",
+        )
+        .bind(&database.name)
+        .bind(&table.name)
+        .fetch(&self.pool);
+        let mut columns = vec![];
+        while let Some(row) = rows.try_next().await? {
+            columns.push(row.try_get("COLUMN_NAME")?)
+        }
+        Ok(columns)
     }
 
     async fn get_columns(
@@ -248,28 +580,58 @@ impl Pool for MySqlPool {
         database: &Database,
         table: &Table,
     ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
-        let mut rows = sqlx::query(
+        let rows = sqlx::query(
             "
         SELECT
-            COLUMN_NAME,
-            CONSTRAINT_NAME
+            tc.CONSTRAINT_NAME,
+            tc.CONSTRAINT_TYPE,
+            GROUP_CONCAT(kcu.COLUMN_NAME ORDER BY kcu.ORDINAL_POSITION SEPARATOR ', ') AS COLUMNS,
+            MAX(kcu.REFERENCED_TABLE_NAME) AS REFERENCED_TABLE_NAME,
+            GROUP_CONCAT(kcu.REFERENCED_COLUMN_NAME ORDER BY kcu.ORDINAL_POSITION SEPARATOR ', ') AS REFERENCED_COLUMNS
         FROM
-            information_schema.KEY_COLUMN_USAGE
+            information_schema.TABLE_CONSTRAINTS AS tc
+            LEFT JOIN information_schema.KEY_COLUMN_USAGE AS kcu ON kcu.CONSTRAINT_SCHEMA = tc.CONSTRAINT_SCHEMA
+            AND kcu.CONSTRAINT_NAME = tc.CONSTRAINT_NAME
+            AND kcu.TABLE_NAME = tc.TABLE_NAME
         WHERE
-            REFERENCED_TABLE_SCHEMA IS NULL
-            AND REFERENCED_TABLE_NAME IS NULL
-            AND TABLE_SCHEMA = ?
-            AND TABLE_NAME = ?
+            tc.TABLE_SCHEMA = ?
+            AND tc.TABLE_NAME = ?
+        GROUP BY
+            tc.CONSTRAINT_NAME,
+            tc.CONSTRAINT_TYPE
+        ORDER BY
+            FIELD(tc.CONSTRAINT_TYPE, 'PRIMARY KEY', 'UNIQUE', 'FOREIGN KEY', 'CHECK'),
+            tc.CONSTRAINT_NAME
         ",
         )
         .bind(&database.name)
         .bind(&table.name)
-        .fetch(&self.pool);
+        .fetch_all(&self.pool)
+        .await?;
         let mut constraints: Vec<Box<dyn TableRow>> = vec![];
-        while let Some(row) = rows.try_next().await? {
+        for row in rows {
+            let name: String = row.try_get("CONSTRAINT_NAME")?;
+            let r#type: String = row.try_get("CONSTRAINT_TYPE")?;
+            let definition = match r#type.as_str() {
+                "FOREIGN KEY" => {
+                    let ref_table: Option<String> = row.try_get("REFERENCED_TABLE_NAME")?;
+                    let ref_columns: Option<String> = row.try_get("REFERENCED_COLUMNS")?;
+                    ref_table.map(|ref_table| {
+                        format!(
+                            "REFERENCES {} ({})",
+                            ref_table,
+                            ref_columns.unwrap_or_default()
+                        )
+                    })
+                }
+                "CHECK" => self.check_clause(database, table, name.as_str()).await?,
+                _ => None,
+            };
             constraints.push(Box::new(Constraint {
-                name: row.try_get("CONSTRAINT_NAME")?,
-                column_name: row.try_get("COLUMN_NAME")?,
+                columns: row.try_get("COLUMNS")?,
+                name,
+                r#type,
+                definition,
             }))
         }
         Ok(constraints)
@@ -280,37 +642,70 @@ impl Pool for MySqlPool {
         database: &Database,
         table: &Table,
     ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        Ok(self
+            .foreign_keys(database, table)
+            .await?
+            .into_iter()
+            .map(|foreign_key| Box::new(foreign_key) as Box<dyn TableRow>)
+            .collect())
+    }
+
+    async fn get_foreign_key_references(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<ForeignKeyReference>> {
+        Ok(self
+            .foreign_keys(database, table)
+            .await?
+            .iter()
+            .filter_map(ForeignKey::reference)
+            .collect())
+    }
+
+    async fn get_child_references(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<ChildReference>> {
         let mut rows = sqlx::query(
             "
         SELECT
             TABLE_NAME,
             COLUMN_NAME,
-            CONSTRAINT_NAME,
-            REFERENCED_TABLE_SCHEMA,
-            REFERENCED_TABLE_NAME,
             REFERENCED_COLUMN_NAME
         FROM
             INFORMATION_SCHEMA.KEY_COLUMN_USAGE
         WHERE
-            REFERENCED_TABLE_SCHEMA IS NOT NULL
-            AND REFERENCED_TABLE_NAME IS NOT NULL
+            REFERENCED_TABLE_SCHEMA = ?
+            AND REFERENCED_TABLE_NAME = ?
             AND TABLE_SCHEMA = ?
-            AND TABLE_NAME = ?
+        ORDER BY
+            TABLE_NAME,
+            CONSTRAINT_NAME,
+            ORDINAL_POSITION
         ",
         )
         .bind(&database.name)
         .bind(&table.name)
+        .bind(&database.name)
         .fetch(&self.pool);
-        let mut foreign_keys: Vec<Box<dyn TableRow>> = vec![];
+        let mut references = vec![];
         while let Some(row) = rows.try_next().await? {
-            foreign_keys.push(Box::new(ForeignKey {
-                name: row.try_get("CONSTRAINT_NAME")?,
-                column_name: row.try_get("COLUMN_NAME")?,
-                ref_table: row.try_get("REFERENCED_TABLE_NAME")?,
+            references.push(ChildReference {
+                table: Table {
+                    name: row.try_get("TABLE_NAME")?,
+                    create_time: None,
+                    update_time: None,
+                    engine: None,
+                    schema: None,
+                    view: false,
+                },
+                column: row.try_get("COLUMN_NAME")?,
                 ref_column: row.try_get("REFERENCED_COLUMN_NAME")?,
-            }))
+            })
         }
-        Ok(foreign_keys)
+        Ok(references)
     }
 
     async fn get_indexes(
@@ -321,38 +716,547 @@ impl Pool for MySqlPool {
         let mut rows = sqlx::query(
             "
         SELECT
-            DISTINCT TABLE_NAME,
             INDEX_NAME,
-            INDEX_TYPE,
-            COLUMN_NAME
+            GROUP_CONCAT(COLUMN_NAME ORDER BY SEQ_IN_INDEX SEPARATOR ', ') AS COLUMNS,
+            CAST(MIN(NON_UNIQUE) = 0 AS SIGNED) AS IS_UNIQUE,
+            INDEX_TYPE
         FROM
             INFORMATION_SCHEMA.STATISTICS
         WHERE
             TABLE_SCHEMA = ?
             AND TABLE_NAME = ?
+        GROUP BY
+            INDEX_NAME,
+            INDEX_TYPE
+        ORDER BY
+            INDEX_NAME = 'PRIMARY' DESC,
+            INDEX_NAME
         ",
         )
         .bind(&database.name)
         .bind(&table.name)
         .fetch(&self.pool);
-        let mut foreign_keys: Vec<Box<dyn TableRow>> = vec![];
+        let mut indexes: Vec<Box<dyn TableRow>> = vec![];
         while let Some(row) = rows.try_next().await? {
-            foreign_keys.push(Box::new(Index {
+            indexes.push(Box::new(Index {
                 name: row.try_get("INDEX_NAME")?,
-                column_name: row.try_get("COLUMN_NAME")?,
+                columns: row.try_get("COLUMNS")?,
+                unique: row.try_get::<i64, _>("IS_UNIQUE")? != 0,
                 r#type: row.try_get("INDEX_TYPE")?,
             }))
         }
-        Ok(foreign_keys)
+        Ok(indexes)
+    }
+
+    async fn get_triggers(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            TRIGGER_NAME,
+            CAST(ACTION_TIMING AS CHAR) AS ACTION_TIMING,
+            CAST(EVENT_MANIPULATION AS CHAR) AS EVENT_MANIPULATION,
+            ACTION_STATEMENT
+        FROM
+            INFORMATION_SCHEMA.TRIGGERS
+        WHERE
+            EVENT_OBJECT_SCHEMA = ?
+            AND EVENT_OBJECT_TABLE = ?
+        ORDER BY
+            ACTION_TIMING,
+            EVENT_MANIPULATION,
+            ACTION_ORDER
+        ",
+        )
+        .bind(&database.name)
+        .bind(&table.name)
+        .fetch(&self.pool);
+        let mut triggers: Vec<Box<dyn TableRow>> = vec![];
+        while let Some(row) = rows.try_next().await? {
+            triggers.push(Box::new(Trigger {
+                name: row.try_get("TRIGGER_NAME")?,
+                timing: row.try_get("ACTION_TIMING")?,
+                event: row.try_get("EVENT_MANIPULATION")?,
+                body: row.try_get("ACTION_STATEMENT")?,
+            }))
+        }
+        Ok(triggers)
+    }
+
+    async fn get_definition(&self, database: &Database, table: &Table) -> anyhow::Result<String> {
+        let query = format!("SHOW CREATE TABLE `{}`.`{}`", database.name, table.name);
+        let row = sqlx::query(query.as_str()).fetch_one(&self.pool).await?;
+        Ok(format!("{};", row.try_get::<String, _>(1)?))
+    }
+
+    async fn get_dump_definition(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<DumpDefinition> {
+        // the checks of the foreign keys are off while a dump is restored, so the definition keeps them
+        Ok(DumpDefinition {
+            definition: self.get_definition(database, table).await?,
+            ..DumpDefinition::default()
+        })
+    }
+
+    async fn get_table_status(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<TableStatus> {
+        let row = sqlx::query(
+            "
+        SELECT
+            TABLE_ROWS,
+            DATA_LENGTH,
+            INDEX_LENGTH,
+            CAST(ENGINE AS CHAR) AS ENGINE,
+            CAST(TABLE_COLLATION AS CHAR) AS TABLE_COLLATION
+        FROM
+            information_schema.TABLES
+        WHERE
+            TABLE_SCHEMA = ?
+            AND TABLE_NAME = ?
+        ",
+        )
+        .bind(&database.name)
+        .bind(&table.name)
+        .fetch_optional(&self.pool)
+        .await?;
+        let row = match row {
+            Some(row) => row,
+            None => return Ok(TableStatus::default()),
+        };
+        let engine: Option<String> = row.try_get("ENGINE")?;
+        Ok(TableStatus {
+            rows: row.try_get("TABLE_ROWS")?,
+            // only MyISAM keeps the exact count, which InnoDB samples
+            estimated: engine.as_deref() != Some("MyISAM"),
+            data_size: row.try_get("DATA_LENGTH")?,
+            index_size: row.try_get("INDEX_LENGTH")?,
+            engine,
+            collation: row.try_get("TABLE_COLLATION")?,
+        })
+    }
+
+    async fn get_routine_definition(
+        &self,
+        database: &Database,
+        routine: &Routine,
+    ) -> anyhow::Result<String> {
+        let query = format!(
+            "SHOW CREATE {} `{}`.`{}`",
+            if routine.function {
+                "FUNCTION"
+            } else {
+                "PROCEDURE"
+            },
+            database.name,
+            routine.name
+        );
+        let row = sqlx::query(query.as_str()).fetch_one(&self.pool).await?;
+        // the body is NULL for the users who do not own the routine
+        Ok(match row.try_get::<Option<String>, _>(2)? {
+            Some(definition) => format!("{};", definition),
+            None => String::new(),
+        })
+    }
+
+    async fn get_scheduled_jobs(&self) -> anyhow::Result<Vec<ScheduledJob>> {
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            EVENT_SCHEMA,
+            EVENT_NAME,
+            CAST(EXECUTE_AT AS CHAR) AS EXECUTE_AT,
+            INTERVAL_VALUE,
+            INTERVAL_FIELD,
+            CAST(STATUS AS CHAR) AS STATUS,
+            CAST(LAST_EXECUTED AS CHAR) AS LAST_EXECUTED,
+            EVENT_DEFINITION
+        FROM
+            INFORMATION_SCHEMA.EVENTS
+        ORDER BY
+            EVENT_SCHEMA,
+            EVENT_NAME
+        ",
+        )
+        .fetch(&self.pool);
+        let mut jobs = vec![];
+        while let Some(row) = rows.try_next().await? {
+            let schema = row.try_get::<String, _>("EVENT_SCHEMA")?;
+            let name = row.try_get::<String, _>("EVENT_NAME")?;
+            // an event runs either once at a time or repeatedly at an interval
+            let schedule = match row.try_get::<Option<String>, _>("EXECUTE_AT")? {
+                Some(at) => format!("AT {}", at),
+                None => format!(
+                    "EVERY {} {}",
+                    row.try_get::<Option<String>, _>("INTERVAL_VALUE")?
+                        .unwrap_or_default(),
+                    row.try_get::<Option<String>, _>("INTERVAL_FIELD")?
+                        .unwrap_or_default()
+                ),
+            };
+            jobs.push(ScheduledJob {
+                id: format!("`{}`.`{}`", schema, name),
+                name: format!("{}.{}", schema, name),
+                schedule,
+                enabled: row.try_get::<String, _>("STATUS")? == "ENABLED",
+                last_run: row.try_get("LAST_EXECUTED")?,
+                definition: row.try_get("EVENT_DEFINITION")?,
+            })
+        }
+        Ok(jobs)
+    }
+
+    async fn insert_records(
+        &self,
+        database: &Database,
+        table: &Table,
+        headers: &[String],
+        rows: &[Vec<String>],
+    ) -> anyhow::Result<u64> {
+        let query = insert_statement(
+            Engine::MySql,
+            self.table_name(database, table).as_str(),
+            '`',
+            headers,
+            rows,
+        );
+        let result = sqlx::query(query.as_str()).execute(&self.pool).await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn session(&self) -> anyhow::Result<Box<dyn Session>> {
+        let mut conn = self.pool.acquire().await?;
+        let id = sqlx::query("SELECT CONNECTION_ID()")
+            .fetch_one(&mut conn)
+            .await?
+            .try_get::<u64, _>(0)?;
+        Ok(Box::new(MySqlSession {
+            conn,
+            id,
+            version: self.version.clone(),
+        }))
+    }
+
+    async fn cancel(&self, token: CancelToken) -> anyhow::Result<()> {
+        if let CancelToken::MySql(id) = token {
+            sqlx::query(format!("KILL QUERY {}", id).as_str())
+                .execute(&self.pool)
+                .await?;
+        }
+        Ok(())
     }
 
     async fn close(&self) {
         self.pool.close().await;
     }
+
+    fn identifier_quote(&self) -> char {
+        '`'
+    }
+
+    fn table_name(&self, database: &Database, table: &Table) -> String {
+        format!("`{}`.`{}`", database.name, table.name)
+    }
+
+    fn call_statement(
+        &self,
+        database: &Database,
+        routine: &Routine,
+        arguments: &[Option<String>],
+    ) -> String {
+        let name = format!("`{}`.`{}`", database.name, routine.name);
+        if routine.function {
+            let arguments = arguments
+                .iter()
+                .map(|argument| {
+                    routine_argument(Engine::MySql, argument.as_deref().unwrap_or_default())
+                })
+                .collect::<Vec<String>>();
+            return format!("SELECT {}({});", name, arguments.join(", "));
+        }
+        // the output parameters are read from session variables named after them
+        let mut statements = vec![];
+        let mut values = vec![];
+        let mut outputs = vec![];
+        for (parameter, argument) in routine_parameters(&routine.arguments)
+            .into_iter()
+            .zip(arguments)
+        {
+            if parameter.mode == "IN" {
+                values.push(routine_argument(
+                    Engine::MySql,
+                    argument.as_deref().unwrap_or_default(),
+                ));
+                continue;
+            }
+            let variable = format!("@{}", parameter.name);
+            if let Some(argument) = argument {
+                statements.push(format!(
+                    "SET {} = {};",
+                    variable,
+                    routine_argument(Engine::MySql, argument)
+                ));
+            }
+            values.push(variable.clone());
+            outputs.push(variable);
+        }
+        statements.push(format!("CALL {}({});", name, values.join(", ")));
+        if !outputs.is_empty() {
+            statements.push(format!("SELECT {};", outputs.join(", ")));
+        }
+        statements.join("\n")
+    }
+
+    fn text_syntax(&self) -> TextSyntax {
+        TextSyntax {
+            cast: "CAST({text} AS CHAR)",
+            length: "CHAR_LENGTH({text})",
+            regex: Some("{text} REGEXP {pattern}"),
+        }
+    }
+
+    fn analyze_statement(&self, database: &Database, table: &Table) -> String {
+        format!("ANALYZE TABLE {}", self.table_name(database, table))
+    }
+
+    fn enable_job_statement(&self, job: &ScheduledJob, enable: bool) -> Option<String> {
+        Some(format!(
+            "ALTER EVENT {} {}",
+            job.id,
+            if enable { "ENABLE" } else { "DISABLE" }
+        ))
+    }
+
+    fn server_version(&self) -> &ServerVersion {
+        &self.version
+    }
+
+    fn engine(&self) -> Engine {
+        Engine::MySql
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        let mariadb = self.version.is_mariadb();
+        match feature {
+            Feature::ExplainAnalyze => !mariadb && self.version.at_least(&[8, 0, 18]),
+            Feature::MaterializedViews => false,
+            Feature::CommonTableExpressions if mariadb => self.version.at_least(&[10, 2, 1]),
+            Feature::CommonTableExpressions => self.version.at_least(&[8]),
+            Feature::Returning => mariadb && self.version.at_least(&[10, 5]),
+            Feature::LockNowait if mariadb => self.version.at_least(&[10, 3]),
+            Feature::LockNowait => self.version.at_least(&[8, 0, 1]),
+        }
+    }
+}
+
+/// a connection of the pool running statements one after another
+pub struct MySqlSession {
+    conn: PoolConnection<MySql>,
+    id: u64,
+    version: ServerVersion,
+}
+
+#[async_trait]
+impl Session for MySqlSession {
+    fn cancel_token(&mut self) -> CancelToken {
+        CancelToken::MySql(self.id)
+    }
+
+    async fn run(&mut self, statement: &str, mode: StatementMode) -> anyhow::Result<ExecuteResult> {
+        match mode {
+            StatementMode::Execute => self.execute(statement).await,
+            StatementMode::Explain { analyze } => {
+                Ok(ExecuteResult::Plan(self.explain(statement, analyze).await?))
+            }
+        }
+    }
+
+    async fn apply_settings(
+        &mut self,
+        settings: &[(String, String)],
+    ) -> anyhow::Result<Vec<(String, String)>> {
+        let mut previous = vec![];
+        for (name, value) in settings {
+            let statement = setting_statement("SET SESSION", name, value)?;
+            let row = sqlx::query(format!("SELECT @@SESSION.{}", name).as_str())
+                .fetch_one(&mut *self.conn)
+                .await?;
+            previous.push((
+                name.clone(),
+                convert_column_value_to_string(&row, &row.columns()[0])?,
+            ));
+            sqlx::query(statement.as_str())
+                .execute(&mut *self.conn)
+                .await?;
+        }
+        Ok(previous)
+    }
+
+    async fn lock_rows(&mut self, statement: &str) -> anyhow::Result<bool> {
+        match sqlx::query(statement).execute(&mut *self.conn).await {
+            Ok(_) => Ok(true),
+            // MySQL fails with ER_LOCK_NOWAIT and MariaDB with ER_LOCK_WAIT_TIMEOUT
+            Err(sqlx::Error::Database(err))
+                if matches!(
+                    err.try_downcast_ref::<MySqlDatabaseError>()
+                        .map(|err| err.number()),
+                    Some(3572) | Some(1205)
+                ) =>
+            {
+                Ok(false)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn warnings(&mut self) -> anyhow::Result<Vec<String>> {
+        // such as Warning 1265: Data truncated for column 'name' at row 1
+        sqlx::query("SHOW WARNINGS")
+            .fetch_all(&mut *self.conn)
+            .await?
+            .iter()
+            .map(|row| {
+                let value =
+                    |index: usize| convert_column_value_to_string(row, &row.columns()[index]);
+                Ok(format!("{} {}: {}", value(0)?, value(1)?, value(2)?))
+            })
+            .collect()
+    }
+}
+
+impl MySqlSession {
+    async fn execute(&mut self, query: &str) -> anyhow::Result<ExecuteResult> {
+        if !is_read_query(query) {
+            let result = sqlx::query(query).execute(&mut *self.conn).await?;
+            return Ok(ExecuteResult::Write {
+                updated_rows: result.rows_affected(),
+            });
+        }
+        let mut rows = sqlx::query(query).fetch(&mut *self.conn);
+        let mut headers = vec![];
+        let mut records = vec![];
+        while let Some(row) = rows.try_next().await? {
+            headers = row
+                .columns()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect();
+            let mut new_row = vec![];
+            for column in row.columns() {
+                new_row.push(convert_column_value(&row, column)?)
+            }
+            records.push(new_row)
+        }
+        Ok(ExecuteResult::Read {
+            headers,
+            rows: records,
+        })
+    }
+
+    async fn explain(&mut self, query: &str, analyze: bool) -> anyhow::Result<Vec<PlanNode>> {
+        // the tree format is only printed by MySQL 8.0.16 or later
+        if analyze || (!self.version.is_mariadb() && self.version.at_least(&[8, 0, 16])) {
+            let query = format!(
+                "EXPLAIN {} {}",
+                if analyze { "ANALYZE" } else { "FORMAT=TREE" },
+                query
+            );
+            let row = sqlx::query(query.as_str())
+                .fetch_one(&mut *self.conn)
+                .await?;
+            return Ok(PlanNode::from_indented_lines(
+                row.try_get::<String, _>(0)?.as_str(),
+            ));
+        }
+        let query = format!("EXPLAIN {}", query);
+        let mut rows = sqlx::query(query.as_str()).fetch(&mut *self.conn);
+        let mut steps = vec![];
+        while let Some(row) = rows.try_next().await? {
+            let mut values = vec![];
+            for column in row.columns() {
+                if let Some(value) = convert_column_value(&row, column)? {
+                    values.push(format!("{}={}", column.name(), value));
+                }
+            }
+            steps.push(PlanNode::new(values.join(" ")));
+        }
+        Ok(steps)
+    }
+}
+
+/// builds the SELECT of the page of records of `table` at the offset `page`
+fn records_query(
+    database: &Database,
+    table: &Table,
+    page: usize,
+    filter: Option<String>,
+) -> String {
+    if let Some(filter) = filter {
+        format!(
+            "SELECT * FROM `{database}`.`{table}` WHERE {filter} LIMIT {page}, {limit}",
+            database = database.name,
+            table = table.name,
+            filter = filter,
+            page = page,
+            limit = RECORDS_LIMIT_PER_PAGE
+        )
+    } else {
+        format!(
+            "SELECT * FROM `{}`.`{}` LIMIT {page}, {limit}",
+            database.name,
+            table.name,
+            page = page,
+            limit = RECORDS_LIMIT_PER_PAGE
+        )
+    }
+}
+
+/// builds the SELECT of the page of records of `table` following `key` in primary key order
+fn records_after_query(
+    database: &Database,
+    table: &Table,
+    primary_key: &[String],
+    key: Option<&[String]>,
+    filter: Option<String>,
+    cast: &str,
+) -> String {
+    format!(
+        "SELECT *{} FROM `{}`.`{}` {}",
+        key_columns(primary_key, '`', cast),
+        database.name,
+        table.name,
+        keyset_clause(Engine::MySql, primary_key, key, filter, '`')
+    )
+}
+
+/// reads the value of `column` of `row` as text, with `None` for NULL
+fn convert_column_value(row: &MySqlRow, column: &MySqlColumn) -> anyhow::Result<Option<String>> {
+    if row.try_get_raw(column.ordinal())?.is_null() {
+        return Ok(None);
+    }
+    convert_column_value_to_string(row, column).map(Some)
 }
 
 fn convert_column_value_to_string(row: &MySqlRow, column: &MySqlColumn) -> anyhow::Result<String> {
     let column_name = column.name();
+    // binary strings would be decoded as text too, lossily or not at all
+    if matches!(
+        column.type_info().name(),
+        "BINARY" | "VARBINARY" | "BLOB" | "TINYBLOB" | "MEDIUMBLOB" | "LONGBLOB"
+    ) {
+        if let Ok(value) = row.try_get(column_name) {
+            let value: Option<Vec<u8>> = value;
+            return Ok(value.map_or("NULL".to_string(), |v| encode_binary(&v)));
+        }
+    }
     if let Ok(value) = row.try_get(column_name) {
         let value: Option<String> = value;
         return Ok(value.unwrap_or_else(|| "NULL".to_string()));
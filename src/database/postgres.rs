@@ -1,40 +1,93 @@
-use super::{Pool, TableRow, RECORDS_LIMIT_PER_PAGE};
+use super::{
+    encode_binary, insert_statement, is_read_query, key_columns, keyset_clause, routine_argument,
+    routine_parameters, setting_statement, split_key, CancelToken, ChildReference, DumpDefinition,
+    Engine, ExecuteResult, Feature, ForeignKeyReference, KeysetPage, PlanNode, Pool, ScheduledJob,
+    ServerVersion, Session, StatementMode, TableRow, TableStatus, TextSyntax,
+    RECORDS_LIMIT_PER_PAGE,
+};
 use async_trait::async_trait;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
-use database_tree::{Child, Database, Schema, Table};
+use database_tree::{Child, Database, Routine, Schema, Table};
 use futures::TryStreamExt;
 use itertools::Itertools;
+use sqlx::pool::PoolConnection;
 use sqlx::postgres::{PgColumn, PgPool, PgPoolOptions, PgRow};
-use sqlx::{Column as _, Row as _, TypeInfo as _};
+use sqlx::{Column as _, Postgres, Row as _, TypeInfo as _, ValueRef as _};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
 use std::time::Duration;
 
 pub struct PostgresPool {
     pool: PgPool,
+    version: ServerVersion,
 }
 
 impl PostgresPool {
-    pub async fn new(database_url: &str) -> anyhow::Result<Self> {
+    /// connects with `settings` applied to every connection, whose transactions cannot write when `read_only` is set
+    pub async fn new(
+        database_url: &str,
+        settings: &BTreeMap<String, String>,
+        read_only: bool,
+    ) -> anyhow::Result<Self> {
+        let mut statements = settings
+            .iter()
+            .map(|(name, value)| setting_statement("SET", name, value))
+            .collect::<anyhow::Result<Vec<String>>>()?;
+        if read_only {
+            statements.push("SET default_transaction_read_only = on".to_string());
+        }
+        let pool = PgPoolOptions::new()
+            .connect_timeout(Duration::from_millis(500))
+            .after_connect(move |conn| {
+                let statements = statements.clone();
+                Box::pin(async move {
+                    for statement in statements {
+                        sqlx::query(statement.as_str()).execute(&mut *conn).await?;
+                    }
+                    Ok(())
+                })
+            })
+            .connect(database_url)
+            .await?;
+        let version = sqlx::query("SHOW server_version")
+            .fetch_one(&pool)
+            .await?
+            .try_get::<String, _>(0)?;
         Ok(Self {
-            pool: PgPoolOptions::new()
-                .connect_timeout(Duration::from_millis(500))
-                .connect(database_url)
-                .await?,
+            pool,
+            version: ServerVersion::parse(version.as_str()),
         })
     }
 }
 
 pub struct Constraint {
     name: String,
-    column_name: String,
+    r#type: String,
+    columns: Option<String>,
+    definition: Option<String>,
 }
 
 impl TableRow for Constraint {
     fn fields(&self) -> Vec<String> {
-        vec!["name".to_string(), "column_name".to_string()]
+        vec![
+            "name".to_string(),
+            "type".to_string(),
+            "columns".to_string(),
+            "definition".to_string(),
+        ]
     }
 
     fn columns(&self) -> Vec<String> {
-        vec![self.name.to_string(), self.column_name.to_string()]
+        vec![
+            self.name.to_string(),
+            self.r#type.to_string(),
+            self.columns
+                .as_ref()
+                .map_or(String::new(), |columns| columns.to_string()),
+            self.definition
+                .as_ref()
+                .map_or(String::new(), |definition| definition.to_string()),
+        ]
     }
 }
 
@@ -81,10 +134,23 @@ impl TableRow for Column {
 pub struct ForeignKey {
     name: Option<String>,
     column_name: Option<String>,
+    ref_schema: Option<String>,
     ref_table: Option<String>,
     ref_column: Option<String>,
 }
 
+impl ForeignKey {
+    fn reference(&self) -> Option<ForeignKeyReference> {
+        Some(ForeignKeyReference {
+            column: self.column_name.clone()?,
+            ref_database: None,
+            ref_schema: self.ref_schema.clone(),
+            ref_table: self.ref_table.clone()?,
+            ref_column: self.ref_column.clone()?,
+        })
+    }
+}
+
 impl TableRow for ForeignKey {
     fn fields(&self) -> Vec<String> {
         vec![
@@ -114,8 +180,9 @@ impl TableRow for ForeignKey {
 }
 
 pub struct Index {
-    name: Option<String>,
-    column_name: Option<String>,
+    name: String,
+    columns: Option<String>,
+    unique: bool,
     r#type: Option<String>,
 }
 
@@ -123,19 +190,19 @@ impl TableRow for Index {
     fn fields(&self) -> Vec<String> {
         vec![
             "name".to_string(),
-            "column_name".to_string(),
+            "columns".to_string(),
+            "unique".to_string(),
             "type".to_string(),
         ]
     }
 
     fn columns(&self) -> Vec<String> {
         vec![
-            self.name
-                .as_ref()
-                .map_or(String::new(), |name| name.to_string()),
-            self.column_name
+            self.name.to_string(),
+            self.columns
                 .as_ref()
-                .map_or(String::new(), |column_name| column_name.to_string()),
+                .map_or(String::new(), |columns| columns.to_string()),
+            if self.unique { "YES" } else { "NO" }.to_string(),
             self.r#type
                 .as_ref()
                 .map_or(String::new(), |r#type| r#type.to_string()),
@@ -143,6 +210,33 @@ impl TableRow for Index {
     }
 }
 
+pub struct Trigger {
+    name: String,
+    timing: String,
+    event: String,
+    body: String,
+}
+
+impl TableRow for Trigger {
+    fn fields(&self) -> Vec<String> {
+        vec![
+            "name".to_string(),
+            "timing".to_string(),
+            "event".to_string(),
+            "body".to_string(),
+        ]
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.name.to_string(),
+            self.timing.to_string(),
+            self.event.to_string(),
+            self.body.to_string(),
+        ]
+    }
+}
+
 #[async_trait]
 impl Pool for PostgresPool {
     async fn get_databases(&self) -> anyhow::Result<Vec<Database>> {
@@ -175,8 +269,26 @@ impl Pool for PostgresPool {
                 update_time: None,
                 engine: None,
                 schema: row.try_get("table_schema")?,
+                view: row.try_get::<String, _>("table_type")? == "VIEW",
             })
         }
+        // materialized views are not listed in information_schema
+        if self.supports(Feature::MaterializedViews) {
+            let mut rows = sqlx::query(
+                "SELECT schemaname::text AS table_schema, matviewname::text AS table_name FROM pg_matviews",
+            )
+            .fetch(&self.pool);
+            while let Some(row) = rows.try_next().await? {
+                tables.push(Table {
+                    name: row.try_get("table_name")?,
+                    create_time: None,
+                    update_time: None,
+                    engine: None,
+                    schema: row.try_get("table_schema")?,
+                    view: true,
+                })
+            }
+        }
         let mut schemas = vec![];
         for (key, group) in &tables
             .iter()
@@ -193,6 +305,41 @@ impl Pool for PostgresPool {
                 )
             }
         }
+        // the aggregates are functions too, but cannot be run on their own, and procedures return no result type
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            n.nspname::text AS routine_schema,
+            p.proname::text AS routine_name,
+            p.oid::text AS specific_name,
+            pg_get_function_arguments(p.oid) AS arguments,
+            pg_get_function_result(p.oid) AS returns
+        FROM
+            pg_proc p
+            JOIN pg_namespace n ON n.oid = p.pronamespace
+        WHERE
+            n.nspname NOT IN ('pg_catalog', 'information_schema')
+            AND p.oid NOT IN (SELECT aggfnoid FROM pg_aggregate)
+        ORDER BY
+            n.nspname,
+            p.proname
+        ",
+        )
+        .fetch(&self.pool);
+        while let Some(row) = rows.try_next().await? {
+            let returns: Option<String> = row.try_get("returns")?;
+            schemas.push(
+                Routine {
+                    name: row.try_get("routine_name")?,
+                    schema: row.try_get("routine_schema")?,
+                    specific_name: row.try_get("specific_name")?,
+                    function: returns.is_some(),
+                    arguments: row.try_get("arguments")?,
+                    returns,
+                }
+                .into(),
+            )
+        }
         Ok(schemas)
     }
 
@@ -200,79 +347,121 @@ impl Pool for PostgresPool {
         &self,
         database: &Database,
         table: &Table,
-        page: u16,
+        page: usize,
         filter: Option<String>,
-    ) -> anyhow::Result<(Vec<String>, Vec<Vec<String>>)> {
-        let query = if let Some(filter) = filter.as_ref() {
-            format!(
-                r#"SELECT * FROM "{database}""{table_schema}"."{table}" WHERE {filter} LIMIT {page}, {limit}"#,
-                database = database.name,
-                table = table.name,
-                filter = filter,
-                table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
-                page = page,
-                limit = RECORDS_LIMIT_PER_PAGE
-            )
+    ) -> anyhow::Result<(Vec<String>, Vec<Vec<Option<String>>>)> {
+        let (headers, records, _) = self
+            .get_values_by_clause(database, table, &[], page_clause(page, filter).as_str())
+            .await?;
+        Ok((headers, records))
+    }
+
+    async fn get_records_after(
+        &self,
+        database: &Database,
+        table: &Table,
+        primary_key: &[String],
+        key: Option<&[String]>,
+        filter: Option<String>,
+    ) -> anyhow::Result<KeysetPage<Option<String>>> {
+        self.get_values_by_clause(
+            database,
+            table,
+            primary_key,
+            keyset_clause(Engine::Postgres, primary_key, key, filter, '"').as_str(),
+        )
+        .await
+    }
+
+    async fn get_values(
+        &self,
+        database: &Database,
+        table: &Table,
+        page: usize,
+        primary_key: &[String],
+        key: Option<&[String]>,
+    ) -> anyhow::Result<KeysetPage<Option<String>>> {
+        let clause = if primary_key.is_empty() {
+            // offsets only page a table in an order which stays the same, which the location of the rows gives,
+            // with the partition of each one
+            format!("ORDER BY tableoid, ctid {}", page_clause(page, None))
         } else {
-            format!(
-                r#"SELECT * FROM "{database}"."{table_schema}"."{table}" limit {limit} offset {page}"#,
-                database = database.name,
-                table = table.name,
-                table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
-                page = page,
-                limit = RECORDS_LIMIT_PER_PAGE
-            )
+            keyset_clause(Engine::Postgres, primary_key, key, None, '"')
         };
-        let mut rows = sqlx::query(query.as_str()).fetch(&self.pool);
-        let mut headers = vec![];
-        let mut records = vec![];
-        let mut json_records = None;
+        self.get_values_by_clause(database, table, primary_key, clause.as_str())
+            .await
+    }
+
+    async fn get_primary_key(
+        &self,
+        _database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<String>> {
+        let table_schema = table
+            .schema
+            .as_ref()
+            .map_or("public", |schema| schema.as_str());
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            kcu.column_name
+        FROM
+            information_schema.table_constraints AS tc
+            JOIN information_schema.key_column_usage AS kcu
+              ON tc.constraint_name = kcu.constraint_name
+              AND tc.table_schema = kcu.table_schema
+        WHERE
+            tc.constraint_type = 'PRIMARY KEY'
+            AND tc.table_schema = $1
+            AND tc.table_name = $2
+        ORDER BY
+            kcu.ordinal_position
+        ",
+        )
+        .bind(table_schema)
+        .bind(&table.name)
+        .fetch(&self.pool);
+        let mut primary_key = vec![];
         while let Some(row) = rows.try_next().await? {
-            headers = row
-                .columns()
-                .iter()
-                .map(|column| column.name().to_string())
-                .collect();
-            let mut new_row = vec![];
-            for column in row.columns() {
-                match convert_column_value_to_string(&row, column) {
-                    Ok(v) => new_row.push(v),
-                    Err(_) => {
-                        if json_records.is_none() {
-                            json_records = Some(
-                                self.get_json_records(database, table, page, filter.clone())
-                                    .await?,
-                            );
-                        }
-                        if let Some(json_records) = &json_records {
-                            match json_records
-                                .get(records.len())
-                                .unwrap()
-                                .get(column.name())
-                                .unwrap()
-                            {
-                                serde_json::Value::String(v) => new_row.push(v.to_string()),
-                                serde_json::Value::Null => new_row.push("NULL".to_string()),
-                                serde_json::Value::Array(v) => {
-                                    new_row.push(v.iter().map(|v| v.to_string()).join(","))
-                                }
-                                serde_json::Value::Number(v) => new_row.push(v.to_string()),
-                                serde_json::Value::Bool(v) => new_row.push(v.to_string()),
-                                others => {
-                                    panic!(
-                                        "column type not implemented: `{}` {}",
-                                        column.name(),
-                                        others
-                                    )
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            records.push(new_row)
+            primary_key.push(row.try_get("column_name")?)
         }
-        Ok((headers, records))
+        Ok(primary_key)
+    }
+
+    async fn get_generated_columns(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<String>> {
+        let table_schema = table
+            .schema
+            .as_ref()
+            .map_or("public", |schema| schema.as_str());
+        // GENERATED ALWAYS identities refuse values too, unlike the ones generated by default
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            column_name::text
+        FROM
+            information_schema.columns
+        WHERE
+            table_catalog = $1
+            AND table_schema = $2
+            AND table_name = $3
+            AND (is_generated = 'ALWAYS' OR identity_generation = 'ALWAYS')
+        ORDER BY
+            ordinal_position
+        ",
+        )
+        .bind(&database.name)
+        .bind(table_schema)
+        .bind(&table.name)
+        .fetch(&self.pool);
+        let mut columns = vec![];
+        while let Some(row) = rows.try_next().await? {
+            columns.push(row.try_get("column_name")?)
+        }
+        Ok(columns)
     }
 
     async fn get_columns(
@@ -307,34 +496,53 @@ impl Pool for PostgresPool {
         _database: &Database,
         table: &Table,
     ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        let table_schema = table
+            .schema
+            .as_ref()
+            .map_or("public", |schema| schema.as_str());
         let mut rows = sqlx::query(
             "
         SELECT
-            tc.table_schema,
-            tc.constraint_name,
-            tc.table_name,
-            kcu.column_name,
-            ccu.table_schema AS foreign_table_schema,
-            ccu.table_name AS foreign_table_name,
-            ccu.column_name AS foreign_column_name
+            c.conname::text AS name,
+            CASE c.contype
+                WHEN 'p' THEN 'PRIMARY KEY'
+                WHEN 'u' THEN 'UNIQUE'
+                WHEN 'f' THEN 'FOREIGN KEY'
+                ELSE 'CHECK'
+            END AS type,
+            array_to_string(
+                ARRAY(
+                    SELECT a.attname::text
+                    FROM unnest(c.conkey) WITH ORDINALITY AS k(attnum, position)
+                        JOIN pg_attribute a ON a.attrelid = c.conrelid AND a.attnum = k.attnum
+                    ORDER BY k.position
+                ),
+                ', '
+            ) AS columns,
+            pg_get_constraintdef(c.oid, true) AS definition
         FROM
-            information_schema.table_constraints AS tc
-            JOIN information_schema.key_column_usage AS kcu ON tc.constraint_name = kcu.constraint_name
-            AND tc.table_schema = kcu.table_schema
-            JOIN information_schema.constraint_column_usage AS ccu ON ccu.constraint_name = tc.constraint_name
-            AND ccu.table_schema = tc.table_schema
+            pg_constraint c
+            JOIN pg_class t ON t.oid = c.conrelid
+            JOIN pg_namespace n ON n.oid = t.relnamespace
         WHERE
-            NOT tc.constraint_type = 'FOREIGN KEY'
-            AND tc.table_name = $1
+            c.contype IN ('p', 'u', 'f', 'c')
+            AND n.nspname = $1
+            AND t.relname = $2
+        ORDER BY
+            position(c.contype in 'pufc'),
+            c.conname
         ",
         )
+        .bind(table_schema)
         .bind(&table.name)
         .fetch(&self.pool);
         let mut constraints: Vec<Box<dyn TableRow>> = vec![];
         while let Some(row) = rows.try_next().await? {
             constraints.push(Box::new(Constraint {
-                name: row.try_get("constraint_name")?,
-                column_name: row.try_get("column_name")?,
+                name: row.try_get("name")?,
+                r#type: row.try_get("type")?,
+                columns: row.try_get("columns")?,
+                definition: row.try_get("definition")?,
             }))
         }
         Ok(constraints)
@@ -342,19 +550,46 @@ impl Pool for PostgresPool {
 
     async fn get_foreign_keys(
         &self,
-        _database: &Database,
+        database: &Database,
         table: &Table,
     ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        Ok(self
+            .foreign_keys(database, table)
+            .await?
+            .into_iter()
+            .map(|foreign_key| Box::new(foreign_key) as Box<dyn TableRow>)
+            .collect())
+    }
+
+    async fn get_foreign_key_references(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<ForeignKeyReference>> {
+        Ok(self
+            .foreign_keys(database, table)
+            .await?
+            .iter()
+            .filter_map(ForeignKey::reference)
+            .collect())
+    }
+
+    async fn get_child_references(
+        &self,
+        _database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<ChildReference>> {
+        let table_schema = table
+            .schema
+            .as_ref()
+            .map_or("public", |schema| schema.as_str());
         let mut rows = sqlx::query(
             "
         SELECT
-            tc.table_schema,
-            tc.constraint_name,
-            tc.table_name,
-            kcu.column_name,
-            ccu.table_schema AS foreign_table_schema,
-            ccu.table_name AS foreign_table_name,
-            ccu.column_name AS foreign_column_name
+            kcu.table_schema::text AS table_schema,
+            kcu.table_name::text AS table_name,
+            kcu.column_name::text AS column_name,
+            ccu.column_name::text AS ref_column_name
         FROM
             information_schema.table_constraints AS tc
             JOIN information_schema.key_column_usage AS kcu ON tc.constraint_name = kcu.constraint_name
@@ -363,21 +598,33 @@ impl Pool for PostgresPool {
             AND ccu.table_schema = tc.table_schema
         WHERE
             tc.constraint_type = 'FOREIGN KEY'
-            AND tc.table_name = $1
+            AND ccu.table_schema = $1
+            AND ccu.table_name = $2
+        ORDER BY
+            kcu.table_schema,
+            kcu.table_name,
+            kcu.column_name
         ",
         )
+        .bind(table_schema)
         .bind(&table.name)
         .fetch(&self.pool);
-        let mut constraints: Vec<Box<dyn TableRow>> = vec![];
+        let mut references = vec![];
         while let Some(row) = rows.try_next().await? {
-            constraints.push(Box::new(ForeignKey {
-                name: row.try_get("constraint_name")?,
-                column_name: row.try_get("column_name")?,
-                ref_table: row.try_get("foreign_table_name")?,
-                ref_column: row.try_get("foreign_column_name")?,
-            }))
+            references.push(ChildReference {
+                table: Table {
+                    name: row.try_get("table_name")?,
+                    create_time: None,
+                    update_time: None,
+                    engine: None,
+                    schema: row.try_get("table_schema")?,
+                    view: false,
+                },
+                column: row.try_get("column_name")?,
+                ref_column: row.try_get("ref_column_name")?,
+            })
         }
-        Ok(constraints)
+        Ok(references)
     }
 
     async fn get_indexes(
@@ -385,113 +632,962 @@ impl Pool for PostgresPool {
         _database: &Database,
         table: &Table,
     ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        let table_schema = table
+            .schema
+            .as_ref()
+            .map_or("public", |schema| schema.as_str());
         let mut rows = sqlx::query(
             "
         SELECT
-            t.relname AS table_name,
-            i.relname AS index_name,
-            a.attname AS column_name,
-            am.amname AS type
+            i.relname::text AS index_name,
+            array_to_string(
+                ARRAY(
+                    SELECT pg_get_indexdef(ix.indexrelid, k + 1, true)
+                    FROM generate_subscripts(ix.indkey, 1) AS k
+                    ORDER BY k
+                ),
+                ', '
+            ) AS columns,
+            ix.indisunique AS is_unique,
+            am.amname::text AS type
         FROM
-            pg_class t,
-            pg_class i,
-            pg_index ix,
-            pg_attribute a,
-            pg_am am
+            pg_index ix
+            JOIN pg_class i ON i.oid = ix.indexrelid
+            JOIN pg_class t ON t.oid = ix.indrelid
+            JOIN pg_namespace n ON n.oid = t.relnamespace
+            JOIN pg_am am ON am.oid = i.relam
         WHERE
-            t.oid = ix.indrelid
-            and i.oid = ix.indexrelid
-            and a.attrelid = t.oid
-            and a.attnum = ANY(ix.indkey)
-            and t.relkind = 'r'
-            and am.oid = i.relam
-            and t.relname = $1
+            n.nspname = $1
+            AND t.relname = $2
         ORDER BY
-            t.relname,
+            ix.indisprimary DESC,
             i.relname
         ",
         )
+        .bind(table_schema)
         .bind(&table.name)
         .fetch(&self.pool);
-        let mut foreign_keys: Vec<Box<dyn TableRow>> = vec![];
+        let mut indexes: Vec<Box<dyn TableRow>> = vec![];
         while let Some(row) = rows.try_next().await? {
-            foreign_keys.push(Box::new(Index {
+            indexes.push(Box::new(Index {
                 name: row.try_get("index_name")?,
-                column_name: row.try_get("column_name")?,
+                columns: row.try_get("columns")?,
+                unique: row.try_get("is_unique")?,
                 r#type: row.try_get("type")?,
             }))
         }
-        Ok(foreign_keys)
+        Ok(indexes)
     }
 
-    async fn close(&self) {
-        self.pool.close().await;
+    async fn get_triggers(
+        &self,
+        _database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        let table_schema = table
+            .schema
+            .as_ref()
+            .map_or("public", |schema| schema.as_str());
+        // the bits of tgtype tell the timing and the events, and the body is that of the function the trigger runs
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            t.tgname::text AS trigger_name,
+            CASE
+                WHEN t.tgtype & 2 <> 0 THEN 'BEFORE'
+                WHEN t.tgtype & 64 <> 0 THEN 'INSTEAD OF'
+                ELSE 'AFTER'
+            END AS timing,
+            concat_ws(
+                ' OR ',
+                CASE WHEN t.tgtype & 4 <> 0 THEN 'INSERT' END,
+                CASE WHEN t.tgtype & 16 <> 0 THEN 'UPDATE' END,
+                CASE WHEN t.tgtype & 8 <> 0 THEN 'DELETE' END,
+                CASE WHEN t.tgtype & 32 <> 0 THEN 'TRUNCATE' END
+            ) AS event,
+            pg_get_triggerdef(t.oid, true) || E'\\n' || p.prosrc AS body
+        FROM
+            pg_trigger t
+            JOIN pg_class c ON c.oid = t.tgrelid
+            JOIN pg_namespace n ON n.oid = c.relnamespace
+            JOIN pg_proc p ON p.oid = t.tgfoid
+        WHERE
+            n.nspname = $1
+            AND c.relname = $2
+            AND NOT t.tgisinternal
+        ORDER BY
+            t.tgname
+        ",
+        )
+        .bind(table_schema)
+        .bind(&table.name)
+        .fetch(&self.pool);
+        let mut triggers: Vec<Box<dyn TableRow>> = vec![];
+        while let Some(row) = rows.try_next().await? {
+            triggers.push(Box::new(Trigger {
+                name: row.try_get("trigger_name")?,
+                timing: row.try_get("timing")?,
+                event: row.try_get("event")?,
+                body: row.try_get("body")?,
+            }))
+        }
+        Ok(triggers)
     }
-}
 
-impl PostgresPool {
-    async fn get_json_records(
+    async fn get_definition(&self, _database: &Database, table: &Table) -> anyhow::Result<String> {
+        let table_name = format!(
+            "\"{}\".\"{}\"",
+            table
+                .schema
+                .as_ref()
+                .map_or("public", |schema| schema.as_str()),
+            table.name
+        );
+        if table.view {
+            let row = sqlx::query(
+                "SELECT relkind::text AS kind, pg_get_viewdef(oid, true) AS definition FROM pg_class WHERE oid = $1::regclass",
+            )
+            .bind(&table_name)
+            .fetch_one(&self.pool)
+            .await?;
+            return Ok(format!(
+                "CREATE {}VIEW {} AS\n{}",
+                if row.try_get::<String, _>("kind")? == "m" {
+                    "MATERIALIZED "
+                } else {
+                    ""
+                },
+                table_name,
+                row.try_get::<String, _>("definition")?.trim_end()
+            ));
+        }
+        Ok(self.table_definition(&table_name, false).await?.definition)
+    }
+
+    async fn get_dump_definition(
         &self,
         database: &Database,
         table: &Table,
-        page: u16,
-        filter: Option<String>,
-    ) -> anyhow::Result<Vec<serde_json::Value>> {
-        let query = if let Some(filter) = filter {
-            format!(
-                r#"SELECT to_json({table}.*) FROM "{database}""{table_schema}"."{table}" WHERE {filter} LIMIT {page}, {limit}"#,
-                database = database.name,
-                table = table.name,
-                filter = filter,
-                table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
-                page = page,
-                limit = RECORDS_LIMIT_PER_PAGE
-            )
-        } else {
-            format!(
-                r#"SELECT to_json({table}.*) FROM "{database}"."{table_schema}"."{table}" limit {limit} offset {page}"#,
-                database = database.name,
-                table = table.name,
-                table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
-                page = page,
-                limit = RECORDS_LIMIT_PER_PAGE
-            )
-        };
-        let json: Vec<(serde_json::Value,)> =
-            sqlx::query_as(query.as_str()).fetch_all(&self.pool).await?;
-        Ok(json.iter().map(|v| v.clone().0).collect())
+    ) -> anyhow::Result<DumpDefinition> {
+        if table.view {
+            return Ok(DumpDefinition {
+                definition: self.get_definition(database, table).await?,
+                ..DumpDefinition::default()
+            });
+        }
+        let table_name = format!(
+            "\"{}\".\"{}\"",
+            table
+                .schema
+                .as_ref()
+                .map_or("public", |schema| schema.as_str()),
+            table.name
+        );
+        self.table_definition(&table_name, true).await
     }
-}
 
-fn convert_column_value_to_string(row: &PgRow, column: &PgColumn) -> anyhow::Result<String> {
-    let column_name = column.name();
-    if let Ok(value) = row.try_get(column_name) {
-        let value: Option<i16> = value;
-        return Ok(value.map_or("NULL".to_string(), |v| v.to_string()));
-    }
-    if let Ok(value) = row.try_get(column_name) {
-        let value: Option<i32> = value;
-        return Ok(value.map_or("NULL".to_string(), |v| v.to_string()));
-    }
-    if let Ok(value) = row.try_get(column_name) {
-        let value: Option<i64> = value;
-        return Ok(value.map_or("NULL".to_string(), |v| v.to_string()));
-    }
-    if let Ok(value) = row.try_get(column_name) {
-        let value: Option<rust_decimal::Decimal> = value;
+    async fn get_table_status(
+        &self,
+        _database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<TableStatus> {
+        let table_schema = table
+            .schema
+            .as_ref()
+            .map_or("public", |schema| schema.as_str());
+        let row = sqlx::query(
+            "
+        SELECT
+            c.reltuples::bigint AS rows,
+            pg_table_size(c.oid) AS data_size,
+            pg_indexes_size(c.oid) AS index_size,
+            d.datcollate::text AS collation
+        FROM
+            pg_class c
+            JOIN pg_namespace n ON n.oid = c.relnamespace
+            JOIN pg_database d ON d.datname = current_database()
+        WHERE
+            n.nspname = $1
+            AND c.relname = $2
+        ",
+        )
+        .bind(table_schema)
+        .bind(&table.name)
+        .fetch_optional(&self.pool)
+        .await?;
+        let row = match row {
+            Some(row) => row,
+            None => return Ok(TableStatus::default()),
+        };
+        let size = |column: &str| -> anyhow::Result<Option<u64>> {
+            // the estimate is -1 until the table is first vacuumed or analyzed
+            Ok(row
+                .try_get::<Option<i64>, _>(column)?
+                .and_then(|size| u64::try_from(size).ok()))
+        };
+        Ok(TableStatus {
+            rows: size("rows")?,
+            estimated: true,
+            data_size: size("data_size")?,
+            index_size: size("index_size")?,
+            engine: None,
+            collation: row.try_get("collation")?,
+        })
+    }
+
+    async fn get_routine_definition(
+        &self,
+        _database: &Database,
+        routine: &Routine,
+    ) -> anyhow::Result<String> {
+        let row = sqlx::query("SELECT pg_get_functiondef($1::text::oid) AS definition")
+            .bind(&routine.specific_name)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(format!(
+            "{};",
+            row.try_get::<String, _>("definition")?.trim_end()
+        ))
+    }
+
+    async fn get_scheduled_jobs(&self) -> anyhow::Result<Vec<ScheduledJob>> {
+        let installed = sqlx::query(
+            "SELECT EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'pg_cron') AS installed",
+        )
+        .fetch_one(&self.pool)
+        .await?
+        .try_get::<bool, _>("installed")?;
+        if !installed {
+            return Ok(vec![]);
+        }
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            job.jobid::text AS id,
+            job.jobname::text AS name,
+            job.schedule::text AS schedule,
+            job.command::text AS command,
+            job.active AS active,
+            run.status::text AS status,
+            run.start_time::text AS start_time
+        FROM
+            cron.job AS job
+            LEFT JOIN LATERAL (
+                SELECT
+                    details.status,
+                    details.start_time
+                FROM
+                    cron.job_run_details AS details
+                WHERE
+                    details.jobid = job.jobid
+                ORDER BY
+                    details.start_time DESC
+                LIMIT 1
+            ) AS run ON true
+        ORDER BY
+            job.jobid
+        ",
+        )
+        .fetch(&self.pool);
+        let mut jobs = vec![];
+        while let Some(row) = rows.try_next().await? {
+            let id = row.try_get::<String, _>("id")?;
+            let last_run = match (
+                row.try_get::<Option<String>, _>("start_time")?,
+                row.try_get::<Option<String>, _>("status")?,
+            ) {
+                (Some(start_time), Some(status)) => Some(format!("{} {}", start_time, status)),
+                (start_time, _) => start_time,
+            };
+            jobs.push(ScheduledJob {
+                name: row
+                    .try_get::<Option<String>, _>("name")?
+                    .unwrap_or_else(|| format!("#{}", id)),
+                id,
+                schedule: row.try_get("schedule")?,
+                enabled: row.try_get("active")?,
+                last_run,
+                definition: row.try_get("command")?,
+            })
+        }
+        Ok(jobs)
+    }
+
+    async fn insert_records(
+        &self,
+        database: &Database,
+        table: &Table,
+        headers: &[String],
+        rows: &[Vec<String>],
+    ) -> anyhow::Result<u64> {
+        let query = insert_statement(
+            Engine::Postgres,
+            self.table_name(database, table).as_str(),
+            '"',
+            headers,
+            rows,
+        );
+        let result = sqlx::query(query.as_str()).execute(&self.pool).await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn session(&self) -> anyhow::Result<Box<dyn Session>> {
+        let mut conn = self.pool.acquire().await?;
+        let pid = sqlx::query("SELECT pg_backend_pid()")
+            .fetch_one(&mut conn)
+            .await?
+            .try_get::<i32, _>(0)?;
+        Ok(Box::new(PostgresSession { conn, pid }))
+    }
+
+    async fn cancel(&self, token: CancelToken) -> anyhow::Result<()> {
+        if let CancelToken::Postgres(pid) = token {
+            sqlx::query("SELECT pg_cancel_backend($1)")
+                .bind(pid)
+                .execute(&self.pool)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn close(&self) {
+        self.pool.close().await;
+    }
+
+    fn identifier_quote(&self) -> char {
+        '"'
+    }
+
+    fn table_name(&self, _database: &Database, table: &Table) -> String {
+        format!(
+            r#""{}"."{}""#,
+            table.schema.clone().unwrap_or_else(|| "public".to_string()),
+            table.name
+        )
+    }
+
+    fn call_statement(
+        &self,
+        _database: &Database,
+        routine: &Routine,
+        arguments: &[Option<String>],
+    ) -> String {
+        let name = format!(
+            r#""{}"."{}""#,
+            routine
+                .schema
+                .clone()
+                .unwrap_or_else(|| "public".to_string()),
+            routine.name
+        );
+        // a function leaves its output parameters out and returns them as columns, while a procedure is passed NULL
+        // for them and returns them as a row
+        let values = routine_parameters(&routine.arguments)
+            .into_iter()
+            .zip(arguments)
+            .filter(|(parameter, _)| !routine.function || !parameter.is_output())
+            .map(|(_, argument)| {
+                routine_argument(Engine::Postgres, argument.as_deref().unwrap_or_default())
+            })
+            .collect::<Vec<String>>();
+        if routine.function {
+            format!("SELECT * FROM {}({});", name, values.join(", "))
+        } else {
+            format!("CALL {}({});", name, values.join(", "))
+        }
+    }
+
+    fn text_syntax(&self) -> TextSyntax {
+        TextSyntax {
+            cast: "CAST({text} AS TEXT)",
+            length: "CHAR_LENGTH({text})",
+            regex: Some("{text} ~ {pattern}"),
+        }
+    }
+
+    fn analyze_statement(&self, database: &Database, table: &Table) -> String {
+        format!("ANALYZE {}", self.table_name(database, table))
+    }
+
+    fn enable_job_statement(&self, job: &ScheduledJob, enable: bool) -> Option<String> {
+        Some(format!(
+            "SELECT cron.alter_job({}, active := {})",
+            job.id, enable
+        ))
+    }
+
+    fn server_version(&self) -> &ServerVersion {
+        &self.version
+    }
+
+    fn engine(&self) -> Engine {
+        Engine::Postgres
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        match feature {
+            Feature::ExplainAnalyze | Feature::Returning | Feature::LockNowait => true,
+            Feature::MaterializedViews => self.version.at_least(&[9, 3]),
+            Feature::CommonTableExpressions => self.version.at_least(&[8, 4]),
+        }
+    }
+}
+
+/// a connection of the pool running statements one after another
+pub struct PostgresSession {
+    conn: PoolConnection<Postgres>,
+    pid: i32,
+}
+
+#[async_trait]
+impl Session for PostgresSession {
+    fn cancel_token(&mut self) -> CancelToken {
+        CancelToken::Postgres(self.pid)
+    }
+
+    async fn run(&mut self, statement: &str, mode: StatementMode) -> anyhow::Result<ExecuteResult> {
+        match mode {
+            StatementMode::Execute => self.execute(statement).await,
+            StatementMode::Explain { analyze } => {
+                Ok(ExecuteResult::Plan(self.explain(statement, analyze).await?))
+            }
+        }
+    }
+
+    async fn apply_settings(
+        &mut self,
+        settings: &[(String, String)],
+    ) -> anyhow::Result<Vec<(String, String)>> {
+        let mut previous = vec![];
+        for (name, value) in settings {
+            let statement = setting_statement("SET", name, value)?;
+            let row = sqlx::query(format!("SELECT current_setting('{}')", name).as_str())
+                .fetch_one(&mut *self.conn)
+                .await?;
+            previous.push((
+                name.clone(),
+                convert_column_value_to_string(&row, &row.columns()[0])?,
+            ));
+            sqlx::query(statement.as_str())
+                .execute(&mut *self.conn)
+                .await?;
+        }
+        Ok(previous)
+    }
+
+    async fn lock_rows(&mut self, statement: &str) -> anyhow::Result<bool> {
+        match sqlx::query(statement).execute(&mut *self.conn).await {
+            Ok(_) => Ok(true),
+            // lock_not_available
+            Err(sqlx::Error::Database(err)) if err.code().as_deref() == Some("55P03") => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn warnings(&mut self) -> anyhow::Result<Vec<String>> {
+        // sqlx only logs the notices the server sends, so there are none to read back
+        Ok(vec![])
+    }
+}
+
+impl PostgresSession {
+    async fn execute(&mut self, query: &str) -> anyhow::Result<ExecuteResult> {
+        if !is_read_query(query) {
+            let result = sqlx::query(query).execute(&mut *self.conn).await?;
+            return Ok(ExecuteResult::Write {
+                updated_rows: result.rows_affected(),
+            });
+        }
+        let mut rows = sqlx::query(query).fetch(&mut *self.conn);
+        let mut headers = vec![];
+        let mut records = vec![];
+        while let Some(row) = rows.try_next().await? {
+            headers = row
+                .columns()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect();
+            let mut new_row = vec![];
+            for column in row.columns() {
+                new_row.push(convert_column_value(&row, column)?)
+            }
+            records.push(new_row)
+        }
+        Ok(ExecuteResult::Read {
+            headers,
+            rows: records,
+        })
+    }
+
+    async fn explain(&mut self, query: &str, analyze: bool) -> anyhow::Result<Vec<PlanNode>> {
+        let query = format!(
+            "EXPLAIN (FORMAT JSON{}) {}",
+            if analyze { ", ANALYZE" } else { "" },
+            query
+        );
+        let row = sqlx::query(query.as_str())
+            .fetch_one(&mut *self.conn)
+            .await?;
+        Ok(plan_nodes(&row.try_get::<serde_json::Value, _>(0)?))
+    }
+}
+
+impl PostgresPool {
+    /// returns the statements creating the table named `table_name`. for a dump, the foreign keys are added once
+    /// the rows of every table are inserted, and the identity columns take the inserted values until then
+    async fn table_definition(
+        &self,
+        table_name: &str,
+        dump: bool,
+    ) -> anyhow::Result<DumpDefinition> {
+        let mut before = vec![];
+        let mut after = vec![];
+        let quoted_table_name = table_name.replace('\'', "''");
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            a.attname::text AS name,
+            format_type(a.atttypid, a.atttypmod) AS type,
+            a.attnotnull AS not_null,
+            a.attidentity::text AS identity,
+            pg_get_expr(d.adbin, d.adrelid) AS default_value
+        FROM
+            pg_attribute AS a
+            LEFT JOIN pg_attrdef AS d ON d.adrelid = a.attrelid
+            AND d.adnum = a.attnum
+        WHERE
+            a.attrelid = $1::regclass
+            AND a.attnum > 0
+            AND NOT a.attisdropped
+        ORDER BY
+            a.attnum
+        ",
+        )
+        .bind(table_name)
+        .fetch(&self.pool);
+        let mut columns = vec![];
+        while let Some(row) = rows.try_next().await? {
+            let name: String = row.try_get("name")?;
+            let default_value: Option<String> = row.try_get("default_value")?;
+            let not_null: bool = row.try_get("not_null")?;
+            let identity: String = row.try_get("identity")?;
+            let generated = match identity.as_str() {
+                // the rows of a dump keep their values, which GENERATED ALWAYS refuses until it is set again
+                "a" if dump => {
+                    after.push(format!(
+                        "ALTER TABLE {} ALTER COLUMN \"{}\" SET GENERATED ALWAYS;",
+                        table_name, name
+                    ));
+                    " GENERATED BY DEFAULT AS IDENTITY"
+                }
+                "a" => " GENERATED ALWAYS AS IDENTITY",
+                "d" => " GENERATED BY DEFAULT AS IDENTITY",
+                _ => "",
+            };
+            if dump && !generated.is_empty() {
+                after.push(format!(
+                    "SELECT setval(pg_get_serial_sequence('{table_literal}', '{column_literal}'), max(\"{column}\")) FROM {table} HAVING max(\"{column}\") IS NOT NULL;",
+                    table_literal = quoted_table_name,
+                    column_literal = name.replace('\'', "''"),
+                    column = name,
+                    table = table_name,
+                ));
+            }
+            columns.push(format!(
+                "\"{}\" {}{}{}{}",
+                name,
+                row.try_get::<String, _>("type")?,
+                default_value.map_or(String::new(), |value| format!(" DEFAULT {}", value)),
+                generated,
+                if not_null { " NOT NULL" } else { "" }
+            ));
+        }
+        if dump {
+            // the sequences the defaults of the columns take their values from, which exist before the table does
+            let mut rows = sqlx::query(
+                "
+            SELECT DISTINCT
+                format('%I.%I', s.schemaname, s.sequencename) AS name,
+                s.data_type::text AS data_type,
+                s.start_value,
+                s.min_value,
+                s.max_value,
+                s.increment_by,
+                s.cycle,
+                s.last_value,
+                (
+                    SELECT
+                        a.attname::text
+                    FROM
+                        pg_depend AS o
+                        JOIN pg_attribute AS a ON a.attrelid = o.refobjid
+                        AND a.attnum = o.refobjsubid
+                    WHERE
+                        o.classid = 'pg_class'::regclass
+                        AND o.objid = c.oid
+                        AND o.deptype = 'a'
+                        AND o.refobjid = $1::regclass
+                ) AS owner
+            FROM
+                pg_attrdef AS d
+                JOIN pg_depend AS p ON p.classid = 'pg_attrdef'::regclass
+                AND p.objid = d.oid
+                AND p.refclassid = 'pg_class'::regclass
+                JOIN pg_class AS c ON c.oid = p.refobjid
+                AND c.relkind = 'S'
+                JOIN pg_namespace AS n ON n.oid = c.relnamespace
+                JOIN pg_sequences AS s ON s.schemaname = n.nspname
+                AND s.sequencename = c.relname
+            WHERE
+                d.adrelid = $1::regclass
+            ORDER BY
+                name
+            ",
+            )
+            .bind(table_name)
+            .fetch(&self.pool);
+            while let Some(row) = rows.try_next().await? {
+                let name: String = row.try_get("name")?;
+                before.push(format!(
+                    "CREATE SEQUENCE IF NOT EXISTS {} AS {} INCREMENT BY {} MINVALUE {} MAXVALUE {} START WITH {}{};",
+                    name,
+                    row.try_get::<String, _>("data_type")?,
+                    row.try_get::<i64, _>("increment_by")?,
+                    row.try_get::<i64, _>("min_value")?,
+                    row.try_get::<i64, _>("max_value")?,
+                    row.try_get::<i64, _>("start_value")?,
+                    if row.try_get::<bool, _>("cycle")? {
+                        " CYCLE"
+                    } else {
+                        ""
+                    }
+                ));
+                if let Some(owner) = row.try_get::<Option<String>, _>("owner")? {
+                    after.push(format!(
+                        "ALTER SEQUENCE {} OWNED BY {}.\"{}\";",
+                        name, table_name, owner
+                    ));
+                }
+                if let Some(last_value) = row.try_get::<Option<i64>, _>("last_value")? {
+                    after.push(format!(
+                        "SELECT setval('{}', {});",
+                        name.replace('\'', "''"),
+                        last_value
+                    ));
+                }
+            }
+        }
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            conname::text AS name,
+            contype = 'f' AS foreign_key,
+            pg_get_constraintdef(oid) AS definition
+        FROM
+            pg_constraint
+        WHERE
+            conrelid = $1::regclass
+        ORDER BY
+            contype = 'p' DESC,
+            conname
+        ",
+        )
+        .bind(table_name)
+        .fetch(&self.pool);
+        let mut constraints = vec![];
+        while let Some(row) = rows.try_next().await? {
+            let name: String = row.try_get("name")?;
+            let definition: String = row.try_get("definition")?;
+            if dump && row.try_get::<bool, _>("foreign_key")? {
+                after.push(format!(
+                    "ALTER TABLE {} ADD CONSTRAINT \"{}\" {};",
+                    table_name, name, definition
+                ));
+            } else {
+                constraints.push(format!("CONSTRAINT \"{}\" {}", name, definition));
+            }
+        }
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            pg_get_indexdef(i.indexrelid) AS definition
+        FROM
+            pg_index AS i
+        WHERE
+            i.indrelid = $1::regclass
+            AND NOT EXISTS (
+                SELECT 1 FROM pg_constraint AS c WHERE c.conindid = i.indexrelid
+            )
+        ORDER BY
+            i.indexrelid
+        ",
+        )
+        .bind(table_name)
+        .fetch(&self.pool);
+        let mut indexes = vec![];
+        while let Some(row) = rows.try_next().await? {
+            indexes.push(row.try_get::<String, _>("definition")?);
+        }
+        Ok(DumpDefinition {
+            before,
+            definition: create_table_statement(table_name, &columns, &constraints, &indexes),
+            after,
+        })
+    }
+
+    /// selects the records of `table` with `clause`, and the columns of `primary_key` as text to return the key of
+    /// the last one
+    async fn get_values_by_clause(
+        &self,
+        database: &Database,
+        table: &Table,
+        primary_key: &[String],
+        clause: &str,
+    ) -> anyhow::Result<KeysetPage<Option<String>>> {
+        let query = format!(
+            r#"SELECT *{keys} FROM "{database}"."{table_schema}"."{table}" {clause}"#,
+            keys = key_columns(primary_key, '"', self.text_syntax().cast),
+            database = database.name,
+            table = table.name,
+            table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
+            clause = clause
+        );
+        let mut rows = sqlx::query(query.as_str()).fetch(&self.pool);
+        let mut headers = vec![];
+        let mut records = vec![];
+        let mut json_records = None;
+        while let Some(row) = rows.try_next().await? {
+            headers = row
+                .columns()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect();
+            let mut new_row = vec![];
+            for column in row.columns() {
+                if row.try_get_raw(column.ordinal())?.is_null() {
+                    new_row.push(None);
+                    continue;
+                }
+                match convert_column_value_to_string(&row, column) {
+                    Ok(v) => new_row.push(Some(v)),
+                    Err(_) => {
+                        if json_records.is_none() {
+                            json_records = Some((
+                                self.get_json_records(database, table, clause).await?,
+                                self.row_shape(table).await?,
+                            ));
+                        }
+                        if let Some((json_records, shape)) = &json_records {
+                            new_row.push(
+                                json_records
+                                    .get(records.len())
+                                    .and_then(|record| record.get(column.name()))
+                                    .filter(|value| !value.is_null())
+                                    .map(|value| {
+                                        json_value_to_string(value, shape.field(column.name()))
+                                    }),
+                            )
+                        }
+                    }
+                }
+            }
+            records.push(new_row)
+        }
+        Ok(split_key(headers, records, primary_key.len()))
+    }
+
+    async fn get_json_records(
+        &self,
+        database: &Database,
+        table: &Table,
+        clause: &str,
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        let query = format!(
+            r#"SELECT to_json({table}.*) FROM "{database}"."{table_schema}"."{table}" {clause}"#,
+            database = database.name,
+            table = table.name,
+            table_schema = table.schema.clone().unwrap_or_else(|| "public".to_string()),
+            clause = clause
+        );
+        let json: Vec<(serde_json::Value,)> =
+            sqlx::query_as(query.as_str()).fetch_all(&self.pool).await?;
+        Ok(json.iter().map(|v| v.clone().0).collect())
+    }
+
+    /// returns the shape of the rows of `table`, read from the catalog down to the fields of the composite types and
+    /// the elements of the arrays in its columns
+    async fn row_shape(&self, table: &Table) -> anyhow::Result<ValueShape> {
+        let table_name = format!(
+            "\"{}\".\"{}\"",
+            table
+                .schema
+                .as_ref()
+                .map_or("public", |schema| schema.as_str()),
+            table.name
+        );
+        let mut rows = sqlx::query(
+            "
+        WITH RECURSIVE types(oid) AS (
+            SELECT reltype FROM pg_class WHERE oid = $1::regclass
+            UNION
+            SELECT
+                child.oid
+            FROM
+                types
+                JOIN pg_type AS t ON t.oid = types.oid
+                CROSS JOIN LATERAL (
+                    SELECT t.typelem AS oid WHERE t.typcategory = 'A' AND t.typelem <> 0
+                    UNION ALL
+                    SELECT t.typbasetype WHERE t.typtype = 'd'
+                    UNION ALL
+                    SELECT
+                        a.atttypid
+                    FROM
+                        pg_attribute AS a
+                    WHERE
+                        t.typtype = 'c'
+                        AND a.attrelid = t.typrelid
+                        AND a.attnum > 0
+                        AND NOT a.attisdropped
+                ) AS child
+        )
+        SELECT
+            t.oid::int8 AS oid,
+            t.typname::text AS name,
+            t.typtype::text AS kind,
+            t.typcategory::text AS category,
+            t.typelem::int8 AS element,
+            t.typbasetype::int8 AS base,
+            t.typrelid = $1::regclass AS is_row,
+            ARRAY(
+                SELECT a.attname::text FROM pg_attribute AS a
+                WHERE t.typtype = 'c' AND a.attrelid = t.typrelid AND a.attnum > 0 AND NOT a.attisdropped
+                ORDER BY a.attnum
+            ) AS attribute_names,
+            ARRAY(
+                SELECT a.atttypid::int8 FROM pg_attribute AS a
+                WHERE t.typtype = 'c' AND a.attrelid = t.typrelid AND a.attnum > 0 AND NOT a.attisdropped
+                ORDER BY a.attnum
+            ) AS attribute_types
+        FROM
+            types
+            JOIN pg_type AS t ON t.oid = types.oid
+        ",
+        )
+        .bind(&table_name)
+        .fetch(&self.pool);
+        let mut types = HashMap::new();
+        let mut root = None;
+        while let Some(row) = rows.try_next().await? {
+            let oid: i64 = row.try_get("oid")?;
+            let names: Vec<String> = row.try_get("attribute_names")?;
+            let attribute_types: Vec<i64> = row.try_get("attribute_types")?;
+            if row.try_get("is_row")? {
+                root = Some(oid);
+            }
+            types.insert(
+                oid,
+                PgType {
+                    name: row.try_get("name")?,
+                    kind: row.try_get("kind")?,
+                    category: row.try_get("category")?,
+                    element: row.try_get("element")?,
+                    base: row.try_get("base")?,
+                    attributes: names.into_iter().zip(attribute_types).collect(),
+                },
+            );
+        }
+        Ok(root.map_or(ValueShape::Scalar, |oid| value_shape(&types, oid)))
+    }
+
+    async fn foreign_keys(
+        &self,
+        _database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<ForeignKey>> {
+        let mut rows = sqlx::query(
+            "
+        SELECT
+            tc.table_schema,
+            tc.constraint_name,
+            tc.table_name,
+            kcu.column_name,
+            ccu.table_schema AS foreign_table_schema,
+            ccu.table_name AS foreign_table_name,
+            ccu.column_name AS foreign_column_name
+        FROM
+            information_schema.table_constraints AS tc
+            JOIN information_schema.key_column_usage AS kcu ON tc.constraint_name = kcu.constraint_name
+            AND tc.table_schema = kcu.table_schema
+            JOIN information_schema.constraint_column_usage AS ccu ON ccu.constraint_name = tc.constraint_name
+            AND ccu.table_schema = tc.table_schema
+        WHERE
+            tc.constraint_type = 'FOREIGN KEY'
+            AND tc.table_name = $1
+        ",
+        )
+        .bind(&table.name)
+        .fetch(&self.pool);
+        let mut foreign_keys = vec![];
+        while let Some(row) = rows.try_next().await? {
+            foreign_keys.push(ForeignKey {
+                name: row.try_get("constraint_name")?,
+                column_name: row.try_get("column_name")?,
+                ref_schema: row.try_get("foreign_table_schema")?,
+                ref_table: row.try_get("foreign_table_name")?,
+                ref_column: row.try_get("foreign_column_name")?,
+            })
+        }
+        Ok(foreign_keys)
+    }
+}
+
+/// builds the clause of the page of records at the offset `page`
+fn page_clause(page: usize, filter: Option<String>) -> String {
+    if let Some(filter) = filter {
+        format!(
+            "WHERE {filter} limit {limit} offset {page}",
+            filter = filter,
+            page = page,
+            limit = RECORDS_LIMIT_PER_PAGE
+        )
+    } else {
+        format!(
+            "limit {limit} offset {page}",
+            page = page,
+            limit = RECORDS_LIMIT_PER_PAGE
+        )
+    }
+}
+
+fn convert_column_value(row: &PgRow, column: &PgColumn) -> anyhow::Result<Option<String>> {
+    if row.try_get_raw(column.ordinal())?.is_null() {
+        return Ok(None);
+    }
+    convert_column_value_to_string(row, column).map(Some)
+}
+
+fn convert_column_value_to_string(row: &PgRow, column: &PgColumn) -> anyhow::Result<String> {
+    let column_name = column.name();
+    if let Ok(value) = row.try_get(column_name) {
+        let value: Option<i16> = value;
+        return Ok(value.map_or("NULL".to_string(), |v| v.to_string()));
+    }
+    if let Ok(value) = row.try_get(column_name) {
+        let value: Option<i32> = value;
+        return Ok(value.map_or("NULL".to_string(), |v| v.to_string()));
+    }
+    if let Ok(value) = row.try_get(column_name) {
+        let value: Option<i64> = value;
+        return Ok(value.map_or("NULL".to_string(), |v| v.to_string()));
+    }
+    if let Ok(value) = row.try_get(column_name) {
+        let value: Option<rust_decimal::Decimal> = value;
         return Ok(value.map_or("NULL".to_string(), |v| v.to_string()));
     }
     if let Ok(value) = row.try_get(column_name) {
         let value: Option<&[u8]> = value;
-        return Ok(value.map_or("NULL".to_string(), |values| {
-            format!(
-                "\\x{}",
-                values
-                    .iter()
-                    .map(|v| format!("{:02x}", v))
-                    .collect::<String>()
-            )
-        }));
+        return Ok(value.map_or("NULL".to_string(), encode_binary));
     }
     if let Ok(value) = row.try_get(column_name) {
         let value: Option<NaiveDate> = value;
@@ -529,9 +1625,43 @@ fn convert_column_value_to_string(row: &PgRow, column: &PgColumn) -> anyhow::Res
         let value: Option<bool> = value;
         return Ok(value.map_or("NULL".to_string(), |v| v.to_string()));
     }
+    if let Ok(value) = row.try_get(column_name) {
+        let value: Option<f32> = value;
+        return Ok(value.map_or("NULL".to_string(), |v| v.to_string()));
+    }
+    if let Ok(value) = row.try_get(column_name) {
+        let value: Option<f64> = value;
+        return Ok(value.map_or("NULL".to_string(), |v| v.to_string()));
+    }
     if let Ok(value) = row.try_get(column_name) {
         let value: Option<Vec<String>> = value;
-        return Ok(value.map_or("NULL".to_string(), |v| v.join(",")));
+        return Ok(value.map_or("NULL".to_string(), |v| {
+            format_array(v.iter().map(|v| quote_array_element(v)))
+        }));
+    }
+    if let Ok(value) = row.try_get(column_name) {
+        let value: Option<Vec<i16>> = value;
+        return Ok(value.map_or("NULL".to_string(), |v| format_array(v.iter())));
+    }
+    if let Ok(value) = row.try_get(column_name) {
+        let value: Option<Vec<i32>> = value;
+        return Ok(value.map_or("NULL".to_string(), |v| format_array(v.iter())));
+    }
+    if let Ok(value) = row.try_get(column_name) {
+        let value: Option<Vec<i64>> = value;
+        return Ok(value.map_or("NULL".to_string(), |v| format_array(v.iter())));
+    }
+    if let Ok(value) = row.try_get(column_name) {
+        let value: Option<Vec<f32>> = value;
+        return Ok(value.map_or("NULL".to_string(), |v| format_array(v.iter())));
+    }
+    if let Ok(value) = row.try_get(column_name) {
+        let value: Option<Vec<f64>> = value;
+        return Ok(value.map_or("NULL".to_string(), |v| format_array(v.iter())));
+    }
+    if let Ok(value) = row.try_get(column_name) {
+        let value: Option<Vec<bool>> = value;
+        return Ok(value.map_or("NULL".to_string(), |v| format_array(v.iter())));
     }
     Err(anyhow::anyhow!(
         "column type not implemented: `{}` {}",
@@ -539,3 +1669,381 @@ fn convert_column_value_to_string(row: &PgRow, column: &PgColumn) -> anyhow::Res
         column.type_info().clone().name()
     ))
 }
+
+/// formats values like the text representation of a postgres array, e.g. `{a,b,c}`
+fn format_array<T: std::fmt::Display>(values: impl Iterator<Item = T>) -> String {
+    format!("{{{}}}", values.map(|v| v.to_string()).join(","))
+}
+
+fn quote_array_element(value: &str) -> String {
+    if value.is_empty()
+        || value.eq_ignore_ascii_case("null")
+        || value
+            .chars()
+            .any(|c| matches!(c, ',' | '{' | '}' | '(' | ')' | '"' | '\\') || c.is_whitespace())
+    {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// quotes a field of a composite value as postgres writes it, e.g. `"a b"` in `("a b",1)`
+fn quote_composite_field(value: &str) -> String {
+    if value.is_empty()
+        || value
+            .chars()
+            .any(|c| matches!(c, ',' | '(' | ')' | '"' | '\\') || c.is_whitespace())
+    {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// a type in `pg_type`, with the names and the types of its attributes when it is a composite type
+struct PgType {
+    name: String,
+    kind: String,
+    category: String,
+    element: i64,
+    base: i64,
+    attributes: Vec<(String, i64)>,
+}
+
+/// how a value of `to_json` is written in the text representation of postgres, which depends on its type rather than
+/// on the json, where a composite and a json object look the same
+#[derive(Debug, PartialEq)]
+enum ValueShape {
+    Scalar,
+    /// a value of json or jsonb, which is written as json
+    Json,
+    Array(Box<ValueShape>),
+    /// the fields of a composite type in the order of its attributes
+    Composite(Vec<(String, ValueShape)>),
+}
+
+impl ValueShape {
+    /// returns the shape of the field `name` of a composite, or a scalar when there is no such field
+    fn field(&self, name: &str) -> &ValueShape {
+        match self {
+            ValueShape::Composite(fields) => fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map_or(&ValueShape::Scalar, |(_, shape)| shape),
+            _ => &ValueShape::Scalar,
+        }
+    }
+}
+
+/// returns the shape of the values of the type `oid` among `types`, following domains to their base types
+fn value_shape(types: &HashMap<i64, PgType>, oid: i64) -> ValueShape {
+    match types.get(&oid) {
+        Some(t) if t.kind == "d" => value_shape(types, t.base),
+        Some(t) if t.kind == "c" => ValueShape::Composite(
+            t.attributes
+                .iter()
+                .map(|(name, oid)| (name.clone(), value_shape(types, *oid)))
+                .collect(),
+        ),
+        Some(t) if t.category == "A" && t.element != 0 => {
+            ValueShape::Array(Box::new(value_shape(types, t.element)))
+        }
+        Some(t) if t.name == "json" || t.name == "jsonb" => ValueShape::Json,
+        _ => ValueShape::Scalar,
+    }
+}
+
+/// converts a value of `to_json` of the type `shape` describes into the text representation of postgres.
+/// arrays are rendered as `{a,b}` and composite types as `(a,b)`, while json is left as it is.
+fn json_value_to_string(value: &serde_json::Value, shape: &ValueShape) -> String {
+    match (value, shape) {
+        (serde_json::Value::Null, _) => "NULL".to_string(),
+        (value, ValueShape::Json) => value.to_string(),
+        (serde_json::Value::String(v), _) => v.to_string(),
+        (serde_json::Value::Array(v), ValueShape::Array(element)) => {
+            format_array(v.iter().map(|item| match item {
+                serde_json::Value::Null => "NULL".to_string(),
+                // the dimensions of a multidimensional array are nested arrays of the same type
+                serde_json::Value::Array(_) if **element != ValueShape::Json => {
+                    json_value_to_string(item, shape)
+                }
+                item => quote_array_element(&json_value_to_string(item, element)),
+            }))
+        }
+        (serde_json::Value::Object(v), ValueShape::Composite(fields)) => format!(
+            "({})",
+            fields
+                .iter()
+                .map(|(name, shape)| match v.get(name) {
+                    None | Some(serde_json::Value::Null) => String::new(),
+                    Some(value) => quote_composite_field(&json_value_to_string(value, shape)),
+                })
+                .join(",")
+        ),
+        (value, _) => value.to_string(),
+    }
+}
+
+/// converts the output of `EXPLAIN (FORMAT JSON)` into nodes labeled as the text format prints them
+fn plan_nodes(value: &serde_json::Value) -> Vec<PlanNode> {
+    fn node(plan: &serde_json::Value) -> PlanNode {
+        let field = |name: &str| {
+            plan.get(name)
+                .map(|value| json_value_to_string(value, &ValueShape::Scalar))
+        };
+        let mut label = field("Node Type").unwrap_or_default();
+        if let Some(relation) = field("Relation Name") {
+            label.push_str(format!(" on {}", relation).as_str());
+        }
+        if let Some(index) = field("Index Name") {
+            label.push_str(format!(" using {}", index).as_str());
+        }
+        if let (Some(startup), Some(total)) = (field("Startup Cost"), field("Total Cost")) {
+            label.push_str(
+                format!(
+                    "  (cost={}..{} rows={})",
+                    startup,
+                    total,
+                    field("Plan Rows").unwrap_or_default()
+                )
+                .as_str(),
+            );
+        }
+        if let (Some(startup), Some(total)) =
+            (field("Actual Startup Time"), field("Actual Total Time"))
+        {
+            label.push_str(
+                format!(
+                    " (actual time={}..{} rows={} loops={})",
+                    startup,
+                    total,
+                    field("Actual Rows").unwrap_or_default(),
+                    field("Actual Loops").unwrap_or_default()
+                )
+                .as_str(),
+            );
+        }
+        PlanNode {
+            label,
+            children: plan
+                .get("Plans")
+                .and_then(|plans| plans.as_array())
+                .map(|plans| plans.iter().map(node).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    let mut nodes = vec![];
+    for statement in value.as_array().into_iter().flatten() {
+        if let Some(plan) = statement.get("Plan") {
+            nodes.push(node(plan));
+        }
+        for name in &["Planning Time", "Execution Time"] {
+            if let Some(time) = statement.get(*name) {
+                nodes.push(PlanNode::new(format!(
+                    "{}: {} ms",
+                    name,
+                    json_value_to_string(time, &ValueShape::Scalar)
+                )));
+            }
+        }
+    }
+    nodes
+}
+
+/// reconstructs the statements creating a table as pg_dump prints them
+fn create_table_statement(
+    table_name: &str,
+    columns: &[String],
+    constraints: &[String],
+    indexes: &[String],
+) -> String {
+    format!(
+        "CREATE TABLE {} (\n{}\n);{}",
+        table_name,
+        columns
+            .iter()
+            .chain(constraints.iter())
+            .map(|line| format!("    {}", line))
+            .join(",\n"),
+        indexes
+            .iter()
+            .map(|index| format!("\n\n{};", index))
+            .join("")
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        create_table_statement, json_value_to_string, plan_nodes, value_shape, PgType, PlanNode,
+        ValueShape,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_plan_nodes() {
+        let value = serde_json::json!([{
+            "Plan": {
+                "Node Type": "Hash Join",
+                "Startup Cost": 1.5,
+                "Total Cost": 30.25,
+                "Plan Rows": 10,
+                "Plans": [
+                    {
+                        "Node Type": "Seq Scan",
+                        "Relation Name": "users",
+                        "Startup Cost": 0.42,
+                        "Total Cost": 22.7,
+                        "Plan Rows": 1270,
+                        "Actual Startup Time": 0.01,
+                        "Actual Total Time": 0.02,
+                        "Actual Rows": 3,
+                        "Actual Loops": 1
+                    }
+                ]
+            },
+            "Execution Time": 0.05
+        }]);
+        assert_eq!(
+            plan_nodes(&value),
+            vec![
+                PlanNode {
+                    label: "Hash Join  (cost=1.5..30.25 rows=10)".to_string(),
+                    children: vec![PlanNode::new(
+                        "Seq Scan on users  (cost=0.42..22.7 rows=1270) (actual time=0.01..0.02 rows=3 loops=1)"
+                            .to_string()
+                    )],
+                },
+                PlanNode::new("Execution Time: 0.05 ms".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create_table_statement() {
+        assert_eq!(
+            create_table_statement(
+                r#""public"."users""#,
+                &[
+                    r#""id" integer DEFAULT nextval('users_id_seq'::regclass) NOT NULL"#
+                        .to_string(),
+                    r#""name" text"#.to_string()
+                ],
+                &[r#"CONSTRAINT "users_pkey" PRIMARY KEY (id)"#.to_string()],
+                &["CREATE INDEX users_name ON public.users USING btree (name)".to_string()]
+            ),
+            r#"CREATE TABLE "public"."users" (
+    "id" integer DEFAULT nextval('users_id_seq'::regclass) NOT NULL,
+    "name" text,
+    CONSTRAINT "users_pkey" PRIMARY KEY (id)
+);
+
+CREATE INDEX users_name ON public.users USING btree (name);"#
+        );
+    }
+    use serde_json::json;
+
+    #[test]
+    fn test_json_value_to_string() {
+        let array = |element| ValueShape::Array(Box::new(element));
+        let address = || {
+            ValueShape::Composite(vec![
+                ("zip".to_string(), ValueShape::Scalar),
+                ("street".to_string(), ValueShape::Scalar),
+            ])
+        };
+        assert_eq!(
+            json_value_to_string(&json!("happy"), &ValueShape::Scalar),
+            "happy"
+        );
+        assert_eq!(
+            json_value_to_string(&json!(["a", "b c", null]), &array(ValueShape::Scalar)),
+            r#"{a,"b c",NULL}"#
+        );
+        assert_eq!(
+            json_value_to_string(&json!([[1, 2], [3, 4]]), &array(ValueShape::Scalar)),
+            "{{1,2},{3,4}}"
+        );
+        // the fields are written in the order of the attributes of the type rather than of the json
+        assert_eq!(
+            json_value_to_string(&json!({"street": "Main St", "zip": 123}), &address()),
+            r#"(123,"Main St")"#
+        );
+        assert_eq!(
+            json_value_to_string(&json!({"street": "say \"hi\"", "zip": null}), &address()),
+            r#"(,"say ""hi""")"#
+        );
+        assert_eq!(
+            json_value_to_string(
+                &json!([{"street": "Main St", "zip": 123}]),
+                &array(address())
+            ),
+            r#"{"(123,\"Main St\")"}"#
+        );
+        // the objects and arrays in json are written as json, not as composites or arrays
+        assert_eq!(
+            json_value_to_string(&json!({"a": 1}), &ValueShape::Json),
+            r#"{"a":1}"#
+        );
+        assert_eq!(
+            json_value_to_string(&json!([{"a": 1}, [1, 2]]), &array(ValueShape::Json)),
+            r#"{"{\"a\":1}","[1,2]"}"#
+        );
+    }
+
+    #[test]
+    fn test_value_shape() {
+        let pg_type = |name: &str, kind: &str, category: &str, element, base, attributes| PgType {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            category: category.to_string(),
+            element,
+            base,
+            attributes,
+        };
+        let mut types = HashMap::new();
+        types.insert(25, pg_type("text", "b", "S", 0, 0, vec![]));
+        types.insert(114, pg_type("json", "b", "U", 0, 0, vec![]));
+        types.insert(199, pg_type("_json", "b", "A", 114, 0, vec![]));
+        types.insert(
+            16400,
+            pg_type("address", "c", "C", 0, 0, vec![("street".to_string(), 25)]),
+        );
+        types.insert(16401, pg_type("_address", "b", "A", 16400, 0, vec![]));
+        types.insert(16402, pg_type("address_domain", "d", "C", 0, 16400, vec![]));
+        types.insert(
+            16410,
+            pg_type(
+                "users",
+                "c",
+                "C",
+                0,
+                0,
+                vec![
+                    ("tags".to_string(), 199),
+                    ("addresses".to_string(), 16401),
+                    ("home".to_string(), 16402),
+                    ("name".to_string(), 25),
+                ],
+            ),
+        );
+        let address = ValueShape::Composite(vec![("street".to_string(), ValueShape::Scalar)]);
+        let shape = value_shape(&types, 16410);
+        assert_eq!(
+            shape.field("tags"),
+            &ValueShape::Array(Box::new(ValueShape::Json))
+        );
+        assert_eq!(
+            shape.field("addresses"),
+            &ValueShape::Array(Box::new(address))
+        );
+        assert_eq!(
+            shape.field("home"),
+            &ValueShape::Composite(vec![("street".to_string(), ValueShape::Scalar)])
+        );
+        assert_eq!(shape.field("name"), &ValueShape::Scalar);
+        assert_eq!(shape.field("missing"), &ValueShape::Scalar);
+    }
+}
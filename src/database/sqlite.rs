@@ -1,47 +1,143 @@
-use super::{Pool, TableRow, RECORDS_LIMIT_PER_PAGE};
+use super::{
+    encode_binary, insert_statement, is_read_query, key_columns, keyset_clause, setting_statement,
+    split_key, CancelToken, ChildReference, DumpDefinition, Engine, ExecuteResult, Feature,
+    ForeignKeyReference, KeysetPage, PlanNode, Pool, ScheduledJob, ServerVersion, Session,
+    StatementMode, TableRow, TableStatus, TextSyntax, RECORDS_LIMIT_PER_PAGE,
+};
 use async_trait::async_trait;
 use chrono::NaiveDateTime;
-use database_tree::{Child, Database, Table};
+use database_tree::{Child, Database, Routine, Table};
 use futures::TryStreamExt;
-use sqlx::sqlite::{SqliteColumn, SqlitePoolOptions, SqliteRow};
-use sqlx::{Column as _, Row as _, TypeInfo as _};
+use sqlx::pool::PoolConnection;
+use sqlx::sqlite::{SqliteColumn, SqliteConnectOptions, SqlitePoolOptions, SqliteRow};
+use sqlx::{Column as _, Row as _, Sqlite, TypeInfo as _, ValueRef as _};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+/// the addresses of the `sqlite3` handles of the sessions running a statement by the ids their cancel tokens carry
+type Running = Arc<Mutex<HashMap<usize, usize>>>;
+
 pub struct SqlitePool {
     pool: sqlx::sqlite::SqlitePool,
+    version: ServerVersion,
+    running: Running,
+    next_session: AtomicUsize,
 }
 
 impl SqlitePool {
-    pub async fn new(database_url: &str) -> anyhow::Result<Self> {
+    /// opens the file of `database_url` with `settings` applied to every connection, in `mode=ro` when `read_only` is
+    /// set so that SQLite refuses to write to it
+    pub async fn new(
+        database_url: &str,
+        settings: &BTreeMap<String, String>,
+        read_only: bool,
+    ) -> anyhow::Result<Self> {
+        let statements = settings
+            .iter()
+            .map(|(name, value)| setting_statement("PRAGMA", name, value))
+            .collect::<anyhow::Result<Vec<String>>>()?;
+        let pool = SqlitePoolOptions::new()
+            .connect_timeout(Duration::from_millis(500))
+            .after_connect(move |conn| {
+                let statements = statements.clone();
+                Box::pin(async move {
+                    for statement in statements {
+                        sqlx::query(statement.as_str()).execute(&mut *conn).await?;
+                    }
+                    Ok(())
+                })
+            })
+            .connect_with(SqliteConnectOptions::from_str(database_url)?.read_only(read_only))
+            .await?;
+        let version = sqlx::query("SELECT sqlite_version()")
+            .fetch_one(&pool)
+            .await?
+            .try_get::<String, _>(0)?;
         Ok(Self {
-            pool: SqlitePoolOptions::new()
-                .connect_timeout(Duration::from_millis(500))
-                .connect(database_url)
-                .await?,
+            pool,
+            version: ServerVersion::parse(version.as_str()),
+            running: Arc::default(),
+            next_session: AtomicUsize::new(0),
         })
     }
+
+    async fn get_values_by_query(
+        &self,
+        query: &str,
+    ) -> anyhow::Result<(Vec<String>, Vec<Vec<Option<String>>>)> {
+        let mut rows = sqlx::query(query).fetch(&self.pool);
+        let mut headers = vec![];
+        let mut records = vec![];
+        while let Some(row) = rows.try_next().await? {
+            headers = row
+                .columns()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect();
+            let mut new_row = vec![];
+            for column in row.columns() {
+                new_row.push(convert_column_value(&row, column)?)
+            }
+            records.push(new_row)
+        }
+        Ok((headers, records))
+    }
+
+    async fn foreign_keys(
+        &self,
+        _database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<ForeignKey>> {
+        let query = format!(
+            "SELECT p.`from`, p.`to`, p.`table` FROM pragma_foreign_key_list('{}') p",
+            &table.name
+        );
+        let mut rows = sqlx::query(query.as_str())
+            .bind(&table.name)
+            .fetch(&self.pool);
+        let mut foreign_keys = vec![];
+        while let Some(row) = rows.try_next().await? {
+            foreign_keys.push(ForeignKey {
+                column_name: row.try_get("from")?,
+                ref_table: row.try_get("table")?,
+                ref_column: row.try_get("to")?,
+            })
+        }
+        Ok(foreign_keys)
+    }
 }
 
 pub struct Constraint {
     name: String,
-    column_name: String,
-    origin: String,
+    r#type: String,
+    columns: Option<String>,
+    definition: Option<String>,
 }
 
 impl TableRow for Constraint {
     fn fields(&self) -> Vec<String> {
         vec![
             "name".to_string(),
-            "column_name".to_string(),
-            "origin".to_string(),
+            "type".to_string(),
+            "columns".to_string(),
+            "definition".to_string(),
         ]
     }
 
     fn columns(&self) -> Vec<String> {
         vec![
             self.name.to_string(),
-            self.column_name.to_string(),
-            self.origin.to_string(),
+            self.r#type.to_string(),
+            self.columns
+                .as_ref()
+                .map_or(String::new(), |columns| columns.to_string()),
+            self.definition
+                .as_ref()
+                .map_or(String::new(), |definition| definition.to_string()),
         ]
     }
 }
@@ -92,6 +188,18 @@ pub struct ForeignKey {
     ref_column: Option<String>,
 }
 
+impl ForeignKey {
+    fn reference(&self) -> Option<ForeignKeyReference> {
+        Some(ForeignKeyReference {
+            column: self.column_name.clone()?,
+            ref_database: None,
+            ref_schema: None,
+            ref_table: self.ref_table.clone()?,
+            ref_column: self.ref_column.clone()?,
+        })
+    }
+}
+
 impl TableRow for ForeignKey {
     fn fields(&self) -> Vec<String> {
         vec![
@@ -117,8 +225,9 @@ impl TableRow for ForeignKey {
 }
 
 pub struct Index {
-    name: Option<String>,
-    column_name: Option<String>,
+    name: String,
+    columns: Option<String>,
+    unique: bool,
     r#type: Option<String>,
 }
 
@@ -126,19 +235,19 @@ impl TableRow for Index {
     fn fields(&self) -> Vec<String> {
         vec![
             "name".to_string(),
-            "column_name".to_string(),
+            "columns".to_string(),
+            "unique".to_string(),
             "type".to_string(),
         ]
     }
 
     fn columns(&self) -> Vec<String> {
         vec![
-            self.name
-                .as_ref()
-                .map_or(String::new(), |name| name.to_string()),
-            self.column_name
+            self.name.to_string(),
+            self.columns
                 .as_ref()
-                .map_or(String::new(), |column_name| column_name.to_string()),
+                .map_or(String::new(), |columns| columns.to_string()),
+            if self.unique { "YES" } else { "NO" }.to_string(),
             self.r#type
                 .as_ref()
                 .map_or(String::new(), |r#type| r#type.to_string()),
@@ -146,6 +255,33 @@ impl TableRow for Index {
     }
 }
 
+pub struct Trigger {
+    name: String,
+    timing: String,
+    event: String,
+    body: String,
+}
+
+impl TableRow for Trigger {
+    fn fields(&self) -> Vec<String> {
+        vec![
+            "name".to_string(),
+            "timing".to_string(),
+            "event".to_string(),
+            "body".to_string(),
+        ]
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.name.to_string(),
+            self.timing.to_string(),
+            self.event.to_string(),
+            self.body.to_string(),
+        ]
+    }
+}
+
 #[async_trait]
 impl Pool for SqlitePool {
     async fn get_databases(&self) -> anyhow::Result<Vec<Database>> {
@@ -167,7 +303,8 @@ impl Pool for SqlitePool {
 
     async fn get_tables(&self, _database: String) -> anyhow::Result<Vec<Child>> {
         let mut rows =
-            sqlx::query("SELECT name FROM sqlite_master WHERE type = 'table'").fetch(&self.pool);
+            sqlx::query("SELECT name, type FROM sqlite_master WHERE type IN ('table', 'view')")
+                .fetch(&self.pool);
         let mut tables = Vec::new();
         while let Some(row) = rows.try_next().await? {
             tables.push(Table {
@@ -176,6 +313,7 @@ impl Pool for SqlitePool {
                 update_time: None,
                 engine: None,
                 schema: None,
+                view: row.try_get::<String, _>("type")? == "view",
             })
         }
         Ok(tables.into_iter().map(|table| table.into()).collect())
@@ -185,41 +323,80 @@ impl Pool for SqlitePool {
         &self,
         _database: &Database,
         table: &Table,
-        page: u16,
+        page: usize,
         filter: Option<String>,
-    ) -> anyhow::Result<(Vec<String>, Vec<Vec<String>>)> {
-        let query = if let Some(filter) = filter {
+    ) -> anyhow::Result<(Vec<String>, Vec<Vec<Option<String>>>)> {
+        self.get_values_by_query(records_query(table, page, filter).as_str())
+            .await
+    }
+
+    async fn get_records_after(
+        &self,
+        _database: &Database,
+        table: &Table,
+        primary_key: &[String],
+        key: Option<&[String]>,
+        filter: Option<String>,
+    ) -> anyhow::Result<KeysetPage<Option<String>>> {
+        let query = records_after_query(table, primary_key, key, filter, self.text_syntax().cast);
+        let (headers, records) = self.get_values_by_query(query.as_str()).await?;
+        Ok(split_key(headers, records, primary_key.len()))
+    }
+
+    async fn get_values(
+        &self,
+        _database: &Database,
+        table: &Table,
+        page: usize,
+        primary_key: &[String],
+        key: Option<&[String]>,
+    ) -> anyhow::Result<KeysetPage<Option<String>>> {
+        // offsets only page a table in an order which stays the same, which the row ids give
+        let query = if primary_key.is_empty() {
             format!(
-                "SELECT * FROM `{table}` WHERE {filter} LIMIT {page}, {limit}",
-                table = table.name,
-                filter = filter,
-                page = page,
-                limit = RECORDS_LIMIT_PER_PAGE
+                "SELECT * FROM `{}` ORDER BY rowid LIMIT {}, {}",
+                table.name, page, RECORDS_LIMIT_PER_PAGE
             )
         } else {
-            format!(
-                "SELECT * FROM `{}` LIMIT {page}, {limit}",
-                table.name,
-                page = page,
-                limit = RECORDS_LIMIT_PER_PAGE
-            )
+            records_after_query(table, primary_key, key, None, self.text_syntax().cast)
         };
+        let (headers, records) = self.get_values_by_query(query.as_str()).await?;
+        Ok(split_key(headers, records, primary_key.len()))
+    }
+
+    async fn get_primary_key(
+        &self,
+        _database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<String>> {
+        let query = format!(
+            "SELECT name FROM pragma_table_info('{}') WHERE pk > 0 ORDER BY pk",
+            table.name
+        );
         let mut rows = sqlx::query(query.as_str()).fetch(&self.pool);
-        let mut headers = vec![];
-        let mut records = vec![];
+        let mut primary_key = vec![];
         while let Some(row) = rows.try_next().await? {
-            headers = row
-                .columns()
-                .iter()
-                .map(|column| column.name().to_string())
-                .collect();
-            let mut new_row = vec![];
-            for column in row.columns() {
-                new_row.push(convert_column_value_to_string(&row, column)?)
-            }
-            records.push(new_row)
+            primary_key.push(row.try_get("name")?)
         }
-        Ok((headers, records))
+        Ok(primary_key)
+    }
+
+    async fn get_generated_columns(
+        &self,
+        _database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<String>> {
+        // the hidden columns 2 and 3 are the virtual and the stored generated ones
+        let query = format!(
+            "SELECT name FROM pragma_table_xinfo('{}') WHERE hidden IN (2, 3) ORDER BY cid",
+            table.name
+        );
+        let mut rows = sqlx::query(query.as_str()).fetch(&self.pool);
+        let mut columns = vec![];
+        while let Some(row) = rows.try_next().await? {
+            columns.push(row.try_get("name")?)
+        }
+        Ok(columns)
     }
 
     async fn get_columns(
@@ -252,30 +429,92 @@ impl Pool for SqlitePool {
         _database: &Database,
         table: &Table,
     ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        let mut constraints: Vec<Box<dyn TableRow>> = vec![];
+        let primary_key: Option<String> = sqlx::query(
+            "SELECT group_concat(name, ', ') AS columns FROM (SELECT name FROM pragma_table_info(?) WHERE pk > 0 ORDER BY pk)",
+        )
+        .bind(&table.name)
+        .fetch_one(&self.pool)
+        .await?
+        .try_get("columns")?;
+        if let Some(columns) = primary_key {
+            constraints.push(Box::new(Constraint {
+                name: String::new(),
+                r#type: "PRIMARY KEY".to_string(),
+                columns: Some(columns),
+                definition: None,
+            }))
+        }
         let mut rows = sqlx::query(
             "
             SELECT
-                p.origin,
-                s.name AS index_name,
-                i.name AS column_name
+                l.name,
+                group_concat(i.name, ', ') AS columns
             FROM
-                sqlite_master s
-                JOIN pragma_index_list(s.tbl_name) p ON s.name = p.name,
-                pragma_index_info(s.name) i
+                pragma_index_list(?) l,
+                pragma_index_info(l.name) i
             WHERE
-                s.type = 'index'
-                AND tbl_name = ?
-                AND NOT p.origin = 'c'
+                l.origin = 'u'
+            GROUP BY
+                l.name
+            ORDER BY
+                l.name
+            ",
+        )
+        .bind(&table.name)
+        .fetch(&self.pool);
+        while let Some(row) = rows.try_next().await? {
+            constraints.push(Box::new(Constraint {
+                name: row.try_get("name")?,
+                r#type: "UNIQUE".to_string(),
+                columns: row.try_get("columns")?,
+                definition: None,
+            }))
+        }
+        let mut rows = sqlx::query(
+            "
+            SELECT
+                `table`,
+                group_concat(`from`, ', ') AS columns,
+                group_concat(`to`, ', ') AS ref_columns
+            FROM
+                pragma_foreign_key_list(?)
+            GROUP BY
+                id
+            ORDER BY
+                id
             ",
         )
         .bind(&table.name)
         .fetch(&self.pool);
-        let mut constraints: Vec<Box<dyn TableRow>> = vec![];
         while let Some(row) = rows.try_next().await? {
+            let ref_table: String = row.try_get("table")?;
+            let ref_columns: Option<String> = row.try_get("ref_columns")?;
             constraints.push(Box::new(Constraint {
-                name: row.try_get("index_name")?,
-                column_name: row.try_get("column_name")?,
-                origin: row.try_get("origin")?,
+                name: String::new(),
+                r#type: "FOREIGN KEY".to_string(),
+                columns: row.try_get("columns")?,
+                definition: Some(match ref_columns {
+                    Some(ref_columns) => format!("REFERENCES {} ({})", ref_table, ref_columns),
+                    None => format!("REFERENCES {}", ref_table),
+                }),
+            }))
+        }
+        // SQLite keeps no catalog of the CHECK constraints, so they are read from the statement creating the table
+        let sql: Option<String> =
+            sqlx::query("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?")
+                .bind(&table.name)
+                .fetch_optional(&self.pool)
+                .await?
+                .map(|row| row.try_get("sql"))
+                .transpose()?
+                .flatten();
+        for (name, expression) in check_constraints(sql.as_deref().unwrap_or_default()) {
+            constraints.push(Box::new(Constraint {
+                name,
+                r#type: "CHECK".to_string(),
+                columns: None,
+                definition: Some(format!("CHECK ({})", expression)),
             }))
         }
         Ok(constraints)
@@ -283,25 +522,73 @@ impl Pool for SqlitePool {
 
     async fn get_foreign_keys(
         &self,
-        _database: &Database,
+        database: &Database,
         table: &Table,
     ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
-        let query = format!(
-            "SELECT p.`from`, p.`to`, p.`table` FROM pragma_foreign_key_list('{}') p",
-            &table.name
-        );
-        let mut rows = sqlx::query(query.as_str())
-            .bind(&table.name)
-            .fetch(&self.pool);
-        let mut foreign_keys: Vec<Box<dyn TableRow>> = vec![];
+        Ok(self
+            .foreign_keys(database, table)
+            .await?
+            .into_iter()
+            .map(|foreign_key| Box::new(foreign_key) as Box<dyn TableRow>)
+            .collect())
+    }
+
+    async fn get_foreign_key_references(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<ForeignKeyReference>> {
+        Ok(self
+            .foreign_keys(database, table)
+            .await?
+            .iter()
+            .filter_map(ForeignKey::reference)
+            .collect())
+    }
+
+    async fn get_child_references(
+        &self,
+        _database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<ChildReference>> {
+        // a foreign key without its columns references the primary key
+        let mut rows = sqlx::query(
+            "
+            SELECT
+                m.name AS table_name,
+                p.`from`,
+                COALESCE(p.`to`, (SELECT name FROM pragma_table_info(?) WHERE pk = 1)) AS `to`
+            FROM
+                sqlite_master m,
+                pragma_foreign_key_list(m.name) p
+            WHERE
+                m.type = 'table'
+                AND p.`table` = ?
+            ORDER BY
+                m.name
+            ",
+        )
+        .bind(&table.name)
+        .bind(&table.name)
+        .fetch(&self.pool);
+        let mut references = vec![];
         while let Some(row) = rows.try_next().await? {
-            foreign_keys.push(Box::new(ForeignKey {
-                column_name: row.try_get("from")?,
-                ref_table: row.try_get("table")?,
-                ref_column: row.try_get("to")?,
-            }))
+            if let Some(ref_column) = row.try_get::<Option<String>, _>("to")? {
+                references.push(ChildReference {
+                    table: Table {
+                        name: row.try_get("table_name")?,
+                        create_time: None,
+                        update_time: None,
+                        engine: None,
+                        schema: None,
+                        view: false,
+                    },
+                    column: row.try_get("from")?,
+                    ref_column,
+                })
+            }
         }
-        Ok(foreign_keys)
+        Ok(references)
     }
 
     async fn get_indexes(
@@ -312,32 +599,409 @@ impl Pool for SqlitePool {
         let mut rows = sqlx::query(
             "
             SELECT
-                m.name AS index_name,
-                p.*
+                l.name,
+                group_concat(i.name, ', ') AS columns,
+                l.`unique` AS is_unique
             FROM
-                sqlite_master m,
-                pragma_index_info(m.name) p
-            WHERE
-                m.type = 'index'
-                AND m.tbl_name = ?
+                pragma_index_list(?) l,
+                pragma_index_info(l.name) i
+            GROUP BY
+                l.name
+            ORDER BY
+                l.origin = 'pk' DESC,
+                l.name
             ",
         )
         .bind(&table.name)
         .fetch(&self.pool);
-        let mut foreign_keys: Vec<Box<dyn TableRow>> = vec![];
+        let mut indexes: Vec<Box<dyn TableRow>> = vec![];
         while let Some(row) = rows.try_next().await? {
-            foreign_keys.push(Box::new(Index {
-                name: row.try_get("index_name")?,
-                column_name: row.try_get("name")?,
-                r#type: Some(String::new()),
+            indexes.push(Box::new(Index {
+                name: row.try_get("name")?,
+                columns: row.try_get("columns")?,
+                unique: row.try_get("is_unique")?,
+                r#type: Some("btree".to_string()),
             }))
         }
-        Ok(foreign_keys)
+        Ok(indexes)
+    }
+
+    async fn get_triggers(
+        &self,
+        _database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<Vec<Box<dyn TableRow>>> {
+        let mut rows = sqlx::query(
+            "SELECT name, sql FROM sqlite_master WHERE type = 'trigger' AND tbl_name = ? ORDER BY name",
+        )
+        .bind(&table.name)
+        .fetch(&self.pool);
+        let mut triggers: Vec<Box<dyn TableRow>> = vec![];
+        while let Some(row) = rows.try_next().await? {
+            let sql: String = row.try_get("sql")?;
+            let (timing, event, body) = trigger_parts(sql.as_str());
+            triggers.push(Box::new(Trigger {
+                name: row.try_get("name")?,
+                timing,
+                event,
+                body,
+            }))
+        }
+        Ok(triggers)
+    }
+
+    async fn get_definition(&self, _database: &Database, table: &Table) -> anyhow::Result<String> {
+        let rows = sqlx::query(
+            "SELECT sql FROM sqlite_master WHERE tbl_name = ? AND sql IS NOT NULL ORDER BY type = 'table' DESC, name",
+        )
+        .bind(&table.name)
+        .fetch_all(&self.pool)
+        .await?;
+        let mut definitions = vec![];
+        for row in rows {
+            definitions.push(format!("{};", row.try_get::<String, _>("sql")?));
+        }
+        Ok(definitions.join("\n\n"))
+    }
+
+    async fn get_dump_definition(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<DumpDefinition> {
+        // the checks of the foreign keys are off while a dump is restored, so the definition keeps them
+        Ok(DumpDefinition {
+            definition: self.get_definition(database, table).await?,
+            ..DumpDefinition::default()
+        })
+    }
+
+    async fn get_table_status(
+        &self,
+        database: &Database,
+        table: &Table,
+    ) -> anyhow::Result<TableStatus> {
+        let query = format!(
+            "SELECT COUNT(*) AS count FROM {}",
+            self.table_name(database, table)
+        );
+        let rows = sqlx::query(query.as_str())
+            .fetch_one(&self.pool)
+            .await?
+            .try_get::<i64, _>("count")?;
+        // the sizes come from the dbstat table, which SQLite only has when it is built with it
+        let sizes = sqlx::query(
+            "
+        SELECT
+            SUM(CASE WHEN m.type = 'table' THEN s.pgsize END) AS data_size,
+            SUM(CASE WHEN m.type = 'index' THEN s.pgsize END) AS index_size
+        FROM
+            dbstat s
+            JOIN sqlite_master m ON m.name = s.name
+        WHERE
+            m.tbl_name = ?
+        ",
+        )
+        .bind(&table.name)
+        .fetch_one(&self.pool)
+        .await
+        .ok();
+        let size = |column: &str| {
+            sizes
+                .as_ref()
+                .and_then(|row| row.try_get::<Option<i64>, _>(column).ok().flatten())
+                .and_then(|size| u64::try_from(size).ok())
+        };
+        Ok(TableStatus {
+            rows: u64::try_from(rows).ok(),
+            estimated: false,
+            data_size: size("data_size"),
+            index_size: size("index_size"),
+            engine: None,
+            collation: None,
+        })
+    }
+
+    async fn get_routine_definition(
+        &self,
+        _database: &Database,
+        _routine: &Routine,
+    ) -> anyhow::Result<String> {
+        // SQLite has no stored procedures or functions, so none are listed
+        Ok(String::new())
+    }
+
+    async fn get_scheduled_jobs(&self) -> anyhow::Result<Vec<ScheduledJob>> {
+        Ok(vec![])
+    }
+
+    async fn insert_records(
+        &self,
+        database: &Database,
+        table: &Table,
+        headers: &[String],
+        rows: &[Vec<String>],
+    ) -> anyhow::Result<u64> {
+        let query = insert_statement(
+            Engine::Sqlite,
+            self.table_name(database, table).as_str(),
+            '`',
+            headers,
+            rows,
+        );
+        let result = sqlx::query(query.as_str()).execute(&self.pool).await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn session(&self) -> anyhow::Result<Box<dyn Session>> {
+        Ok(Box::new(SqliteSession {
+            conn: self.pool.acquire().await?,
+            id: self.next_session.fetch_add(1, Ordering::Relaxed),
+            running: Arc::clone(&self.running),
+        }))
+    }
+
+    async fn cancel(&self, token: CancelToken) -> anyhow::Result<()> {
+        if let CancelToken::Sqlite(id) = token {
+            if let Ok(running) = self.running.lock() {
+                if let Some(handle) = running.get(&id) {
+                    // SAFETY: the handle is only listed while its session runs a statement, and the session takes it
+                    // out of the list, which waits for this lock, before it may release the connection. so the
+                    // connection is open while it is interrupted, which sqlite3_interrupt allows from any thread
+                    unsafe {
+                        libsqlite3_sys::sqlite3_interrupt(*handle as *mut libsqlite3_sys::sqlite3)
+                    };
+                }
+            }
+        }
+        Ok(())
     }
 
     async fn close(&self) {
         self.pool.close().await;
     }
+
+    fn identifier_quote(&self) -> char {
+        '`'
+    }
+
+    fn table_name(&self, _database: &Database, table: &Table) -> String {
+        format!("`{}`", table.name)
+    }
+
+    fn call_statement(
+        &self,
+        _database: &Database,
+        _routine: &Routine,
+        _arguments: &[Option<String>],
+    ) -> String {
+        String::new()
+    }
+
+    fn text_syntax(&self) -> TextSyntax {
+        // REGEXP is only defined once an extension is loaded
+        TextSyntax {
+            cast: "CAST({text} AS TEXT)",
+            length: "LENGTH({text})",
+            regex: None,
+        }
+    }
+
+    fn analyze_statement(&self, database: &Database, table: &Table) -> String {
+        format!("ANALYZE {}", self.table_name(database, table))
+    }
+
+    fn enable_job_statement(&self, _job: &ScheduledJob, _enable: bool) -> Option<String> {
+        None
+    }
+
+    fn server_version(&self) -> &ServerVersion {
+        &self.version
+    }
+
+    fn engine(&self) -> Engine {
+        Engine::Sqlite
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        match feature {
+            Feature::ExplainAnalyze | Feature::MaterializedViews | Feature::LockNowait => false,
+            Feature::CommonTableExpressions => self.version.at_least(&[3, 8, 3]),
+            Feature::Returning => self.version.at_least(&[3, 35]),
+        }
+    }
+}
+
+/// a connection of the pool running statements one after another
+pub struct SqliteSession {
+    conn: PoolConnection<Sqlite>,
+    id: usize,
+    running: Running,
+}
+
+/// lists the handle of a session while it runs a statement, taking it out of the list however the statement ends,
+/// including when the task running it is aborted
+struct RunningGuard {
+    id: usize,
+    running: Running,
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        if let Ok(mut running) = self.running.lock() {
+            running.remove(&self.id);
+        }
+    }
+}
+
+#[async_trait]
+impl Session for SqliteSession {
+    fn cancel_token(&mut self) -> CancelToken {
+        CancelToken::Sqlite(self.id)
+    }
+
+    async fn run(&mut self, statement: &str, mode: StatementMode) -> anyhow::Result<ExecuteResult> {
+        let _running = self.start_running();
+        match mode {
+            StatementMode::Execute => self.execute(statement).await,
+            StatementMode::Explain { analyze } => {
+                Ok(ExecuteResult::Plan(self.explain(statement, analyze).await?))
+            }
+        }
+    }
+
+    async fn apply_settings(
+        &mut self,
+        settings: &[(String, String)],
+    ) -> anyhow::Result<Vec<(String, String)>> {
+        let mut previous = vec![];
+        for (name, value) in settings {
+            let statement = setting_statement("PRAGMA", name, value)?;
+            let row = sqlx::query(format!("PRAGMA {}", name).as_str())
+                .fetch_one(&mut *self.conn)
+                .await?;
+            previous.push((
+                name.clone(),
+                convert_column_value_to_string(&row, &row.columns()[0])?,
+            ));
+            sqlx::query(statement.as_str())
+                .execute(&mut *self.conn)
+                .await?;
+        }
+        Ok(previous)
+    }
+
+    async fn lock_rows(&mut self, _statement: &str) -> anyhow::Result<bool> {
+        Err(anyhow::anyhow!(
+            "{} is not supported by SQLite",
+            Feature::LockNowait.name()
+        ))
+    }
+
+    async fn warnings(&mut self) -> anyhow::Result<Vec<String>> {
+        Ok(vec![])
+    }
+}
+
+impl SqliteSession {
+    /// lists the handle of the connection until the returned guard is dropped, which happens before the session and
+    /// so the connection can be released
+    fn start_running(&mut self) -> RunningGuard {
+        let handle = self.conn.as_raw_handle() as usize;
+        if let Ok(mut running) = self.running.lock() {
+            running.insert(self.id, handle);
+        }
+        RunningGuard {
+            id: self.id,
+            running: Arc::clone(&self.running),
+        }
+    }
+
+    async fn execute(&mut self, query: &str) -> anyhow::Result<ExecuteResult> {
+        if !is_read_query(query) {
+            let result = sqlx::query(query).execute(&mut *self.conn).await?;
+            return Ok(ExecuteResult::Write {
+                updated_rows: result.rows_affected(),
+            });
+        }
+        let mut rows = sqlx::query(query).fetch(&mut *self.conn);
+        let mut headers = vec![];
+        let mut records = vec![];
+        while let Some(row) = rows.try_next().await? {
+            headers = row
+                .columns()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect();
+            let mut new_row = vec![];
+            for column in row.columns() {
+                new_row.push(convert_column_value(&row, column)?)
+            }
+            records.push(new_row)
+        }
+        Ok(ExecuteResult::Read {
+            headers,
+            rows: records,
+        })
+    }
+
+    async fn explain(&mut self, query: &str, _analyze: bool) -> anyhow::Result<Vec<PlanNode>> {
+        let query = format!("EXPLAIN QUERY PLAN {}", query);
+        let mut rows = sqlx::query(query.as_str()).fetch(&mut *self.conn);
+        let mut steps = vec![];
+        while let Some(row) = rows.try_next().await? {
+            steps.push((
+                row.try_get::<i64, _>("id")?,
+                row.try_get::<i64, _>("parent")?,
+                row.try_get::<String, _>("detail")?,
+            ));
+        }
+        Ok(PlanNode::from_parent_ids(&steps))
+    }
+}
+
+/// builds the SELECT of the page of records of `table` at the offset `page`
+fn records_query(table: &Table, page: usize, filter: Option<String>) -> String {
+    if let Some(filter) = filter {
+        format!(
+            "SELECT * FROM `{table}` WHERE {filter} LIMIT {page}, {limit}",
+            table = table.name,
+            filter = filter,
+            page = page,
+            limit = RECORDS_LIMIT_PER_PAGE
+        )
+    } else {
+        format!(
+            "SELECT * FROM `{}` LIMIT {page}, {limit}",
+            table.name,
+            page = page,
+            limit = RECORDS_LIMIT_PER_PAGE
+        )
+    }
+}
+
+/// builds the SELECT of the page of records of `table` following `key` in primary key order
+fn records_after_query(
+    table: &Table,
+    primary_key: &[String],
+    key: Option<&[String]>,
+    filter: Option<String>,
+    cast: &str,
+) -> String {
+    format!(
+        "SELECT *{} FROM `{}` {}",
+        key_columns(primary_key, '`', cast),
+        table.name,
+        keyset_clause(Engine::Sqlite, primary_key, key, filter, '`')
+    )
+}
+
+/// reads the value of `column` of `row` as text, with `None` for NULL
+fn convert_column_value(row: &SqliteRow, column: &SqliteColumn) -> anyhow::Result<Option<String>> {
+    if row.try_get_raw(column.ordinal())?.is_null() {
+        return Ok(None);
+    }
+    convert_column_value_to_string(row, column).map(Some)
 }
 
 fn convert_column_value_to_string(
@@ -389,9 +1053,314 @@ fn convert_column_value_to_string(
         let value: Option<bool> = value;
         return Ok(value.map_or("NULL".to_string(), |v| v.to_string()));
     }
+    if let Ok(value) = row.try_get(column_name) {
+        let value: Option<Vec<u8>> = value;
+        return Ok(value.map_or("NULL".to_string(), |v| encode_binary(&v)));
+    }
     Err(anyhow::anyhow!(
         "column type not implemented: `{}` {}",
         column_name,
         column.type_info().clone().name()
     ))
 }
+
+/// returns the names and the expressions of the CHECK constraints written in a `CREATE TABLE` statement
+fn check_constraints(sql: &str) -> Vec<(String, String)> {
+    let chars = sql.chars().collect::<Vec<char>>();
+    let mut checks = vec![];
+    let mut words: Vec<String> = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if let Some(close) = closing_quote(c) {
+            let start = i + 1;
+            i = start;
+            while i < chars.len() && chars[i] != close {
+                i += 1;
+            }
+            words.push(chars[start..i.min(chars.len())].iter().collect());
+            i += 1;
+        } else if c.is_alphanumeric() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word = chars[start..i].iter().collect::<String>();
+            if !word.eq_ignore_ascii_case("CHECK") {
+                words.push(word);
+                continue;
+            }
+            let open = match (i..chars.len()).find(|&j| !chars[j].is_whitespace()) {
+                Some(open) if chars[open] == '(' => open,
+                _ => continue,
+            };
+            let close = match closing_paren(&chars, open) {
+                Some(close) => close,
+                None => break,
+            };
+            let name = match words.as_slice() {
+                [.., constraint, name] if constraint.eq_ignore_ascii_case("CONSTRAINT") => {
+                    name.clone()
+                }
+                _ => String::new(),
+            };
+            let expression = chars[open + 1..close].iter().collect::<String>();
+            checks.push((name, expression.trim().to_string()));
+            words.clear();
+            i = close + 1;
+        } else {
+            i += 1;
+        }
+    }
+    checks
+}
+
+fn closing_quote(c: char) -> Option<char> {
+    match c {
+        '\'' | '"' | '`' => Some(c),
+        '[' => Some(']'),
+        _ => None,
+    }
+}
+
+/// returns the position of the parenthesis closing the one at `open`, skipping quoted text
+fn closing_paren(chars: &[char], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    for (i, &c) in chars.iter().enumerate().skip(open) {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => (),
+            None => match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => quote = closing_quote(c),
+            },
+        }
+    }
+    None
+}
+
+/// reads the timing, the event and the body of a trigger out of the statement which created it, which are the only
+/// place SQLite keeps them, the timing being BEFORE when it is left out
+fn trigger_parts(sql: &str) -> (String, String, String) {
+    let upper = sql.to_ascii_uppercase();
+    let begin = upper
+        .match_indices("BEGIN")
+        .map(|(index, _)| index)
+        .find(|index| {
+            let before = upper[..*index].chars().next_back();
+            let after = upper[index + 5..].chars().next();
+            before.map_or(true, char::is_whitespace) && after.map_or(true, char::is_whitespace)
+        })
+        .unwrap_or(sql.len());
+    // the words are matched in upper case but kept as written, such as the columns of UPDATE OF
+    let words = sql[..begin]
+        .split_whitespace()
+        .zip(upper[..begin].split_whitespace())
+        .collect::<Vec<(&str, &str)>>();
+    let keyword = |index: usize| words.get(index).map_or("", |(_, upper)| *upper);
+    let mut index = words
+        .iter()
+        .position(|(_, upper)| *upper == "TRIGGER")
+        .map_or(0, |index| index + 1);
+    if keyword(index) == "IF" && keyword(index + 1) == "NOT" && keyword(index + 2) == "EXISTS" {
+        index += 3;
+    }
+    // skips the name of the trigger
+    index += 1;
+    let timing = match keyword(index) {
+        "INSTEAD" if keyword(index + 1) == "OF" => {
+            index += 2;
+            "INSTEAD OF"
+        }
+        "BEFORE" | "AFTER" => {
+            index += 1;
+            keyword(index - 1)
+        }
+        _ => "BEFORE",
+    };
+    let event = words
+        .iter()
+        .skip(index)
+        .take_while(|(_, upper)| *upper != "ON")
+        .map(|(word, upper)| {
+            if ["DELETE", "INSERT", "UPDATE", "OF"].contains(upper) {
+                *upper
+            } else {
+                *word
+            }
+        })
+        .collect::<Vec<&str>>()
+        .join(" ");
+    (timing.to_string(), event, sql[begin..].trim().to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_constraints, trigger_parts, SqlitePool};
+    use crate::database::{Pool as _, StatementMode};
+    use database_tree::{Database, Table};
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_read_only() {
+        let path = std::env::temp_dir().join(format!("gobang_read_only_{}.db", std::process::id()));
+        let url = format!("sqlite://{}", path.display());
+        let pool = SqlitePool::new(
+            format!("{}?mode=rwc", url).as_str(),
+            &BTreeMap::new(),
+            false,
+        )
+        .await
+        .unwrap();
+        pool.session()
+            .await
+            .unwrap()
+            .run("CREATE TABLE items (id INTEGER)", StatementMode::Execute)
+            .await
+            .unwrap();
+        pool.close().await;
+
+        let pool = SqlitePool::new(url.as_str(), &BTreeMap::new(), true)
+            .await
+            .unwrap();
+        let mut session = pool.session().await.unwrap();
+        assert!(session
+            .run("SELECT * FROM items", StatementMode::Execute)
+            .await
+            .is_ok());
+        assert!(session
+            .run("INSERT INTO items VALUES (1)", StatementMode::Execute)
+            .await
+            .is_err());
+        drop(session);
+        pool.close().await;
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_values_without_primary_key() {
+        let path = std::env::temp_dir().join(format!("gobang_values_{}.db", std::process::id()));
+        let pool = SqlitePool::new(
+            format!("sqlite://{}?mode=rwc", path.display()).as_str(),
+            &BTreeMap::new(),
+            false,
+        )
+        .await
+        .unwrap();
+        pool.session()
+            .await
+            .unwrap()
+            .run(
+                "CREATE TABLE events (name TEXT); INSERT INTO events VALUES ('b'), ('a'), (NULL)",
+                StatementMode::Execute,
+            )
+            .await
+            .unwrap();
+        let table = Table {
+            name: "events".to_string(),
+            create_time: None,
+            update_time: None,
+            engine: None,
+            schema: None,
+            view: false,
+        };
+        let database = Database::new("main".to_string(), vec![]);
+        let (headers, records, key) = pool
+            .get_values(&database, &table, 1, &[], None)
+            .await
+            .unwrap();
+        assert_eq!(headers, vec!["name".to_string()]);
+        assert_eq!(records, vec![vec![Some("a".to_string())], vec![None]]);
+        assert_eq!(key, None);
+        pool.close().await;
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cancel() {
+        let pool = SqlitePool::new("sqlite::memory:", &BTreeMap::new(), false)
+            .await
+            .unwrap();
+        let mut session = pool.session().await.unwrap();
+        let token = session.cancel_token();
+        // nothing is interrupted while the session runs no statement
+        pool.cancel(token).await.unwrap();
+        assert!(pool.running.lock().unwrap().is_empty());
+
+        let running = tokio::spawn(async move {
+            session
+                .run(
+                    "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c) SELECT count(*) FROM c",
+                    StatementMode::Execute,
+                )
+                .await
+        });
+        while pool.running.lock().unwrap().is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        pool.cancel(token).await.unwrap();
+        assert!(tokio::time::timeout(Duration::from_secs(10), running)
+            .await
+            .unwrap()
+            .unwrap()
+            .is_err());
+        assert!(pool.running.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_trigger_parts() {
+        let sql = "CREATE TRIGGER IF NOT EXISTS log_price AFTER UPDATE OF price ON items
+            BEGIN
+                INSERT INTO price_log VALUES (new.id, new.price);
+            END";
+        assert_eq!(
+            trigger_parts(sql),
+            (
+                "AFTER".to_string(),
+                "UPDATE OF price".to_string(),
+                "BEGIN
+                INSERT INTO price_log VALUES (new.id, new.price);
+            END"
+                .to_string()
+            )
+        );
+        let (timing, event, _) =
+            trigger_parts("create temp trigger began delete on items begin select 1; end");
+        assert_eq!((timing.as_str(), event.as_str()), ("BEFORE", "DELETE"));
+        let (timing, event, _) =
+            trigger_parts("CREATE TRIGGER v_insert INSTEAD OF INSERT ON v BEGIN SELECT 1; END");
+        assert_eq!((timing.as_str(), event.as_str()), ("INSTEAD OF", "INSERT"));
+    }
+
+    #[test]
+    fn test_check_constraints() {
+        let sql = "CREATE TABLE items (
+            id INTEGER PRIMARY KEY,
+            price REAL CHECK (price > 0),
+            note TEXT DEFAULT 'check (no)',
+            \"from\" TEXT,
+            CONSTRAINT valid_note CHECK(length(note) < 10 AND note <> ')'),
+            CONSTRAINT [checked] CHECK (\"from\" IN ('a', 'b'))
+        )";
+        assert_eq!(
+            check_constraints(sql),
+            vec![
+                ("".to_string(), "price > 0".to_string()),
+                (
+                    "valid_note".to_string(),
+                    "length(note) < 10 AND note <> ')'".to_string()
+                ),
+                ("checked".to_string(), "\"from\" IN ('a', 'b')".to_string()),
+            ]
+        );
+        assert!(check_constraints("CREATE TABLE t (a INT)").is_empty());
+    }
+}
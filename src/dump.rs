@@ -0,0 +1,234 @@
+use crate::database::{
+    insert_batches, type_badge, ColumnKind, Engine, Pool, RECORDS_LIMIT_PER_PAGE,
+};
+use database_tree::{Child, Database, Table};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::mpsc;
+use tokio::process::Command;
+
+/// how many rows one INSERT statement of a dump inserts
+const DUMP_BATCH_SIZE: usize = 100;
+
+/// a program of the server, such as `pg_dump`, which dumps a database or one of its tables into a file
+#[derive(Debug, Clone, PartialEq)]
+pub struct DumpCommand {
+    pub program: &'static str,
+    pub args: Vec<String>,
+    /// the environment variable the program reads the password from, with the password
+    pub envs: Vec<(&'static str, String)>,
+}
+
+/// how many of the tables of a dump are written, and how many rows of them
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct DumpProgress {
+    pub tables: usize,
+    pub total: usize,
+    pub rows: usize,
+}
+
+/// returns the path of `program` when one of the directories of `PATH` has it
+pub fn find_program(program: &str) -> Option<PathBuf> {
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths)
+        .flat_map(|dir| vec![dir.join(program), dir.join(format!("{}.exe", program))])
+        .find(|path| path.is_file())
+}
+
+/// runs the program of `command`, which is killed when the task running it is aborted
+pub async fn run_dump_command(command: DumpCommand) -> anyhow::Result<()> {
+    let DumpCommand {
+        program,
+        args,
+        envs,
+    } = command;
+    let output = Command::new(program)
+        .args(&args)
+        .envs(envs)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| anyhow::anyhow!("`{}`: {}", program, e))?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "`{}`: {} {}",
+            program,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// returns the tables of `database` in the order they are dumped, the views last as they may read the tables
+pub fn dump_order(database: &Database) -> Vec<Table> {
+    let mut tables = database
+        .children
+        .iter()
+        .flat_map(|child| match child {
+            Child::Table(table) => vec![table.clone()],
+            Child::Schema(schema) => schema.tables.clone(),
+            Child::Routine(_) => vec![],
+        })
+        .collect::<Vec<Table>>();
+    tables.sort_by_key(|table| table.view);
+    tables
+}
+
+/// returns the statement turning the checks of the foreign keys off or on again, so that the rows of a table can be
+/// inserted before the rows they reference
+fn foreign_key_checks(engine: Engine, on: bool) -> Option<&'static str> {
+    match (engine, on) {
+        (Engine::MySql, false) => Some("SET FOREIGN_KEY_CHECKS = 0;"),
+        (Engine::MySql, true) => Some("SET FOREIGN_KEY_CHECKS = 1;"),
+        (Engine::Sqlite, false) => Some("PRAGMA foreign_keys = OFF;"),
+        (Engine::Sqlite, true) => Some("PRAGMA foreign_keys = ON;"),
+        _ => None,
+    }
+}
+
+/// writes the definitions of `tables` and their rows as INSERT statements into `out`, reading the rows page by page
+/// with SELECT, and sends how far it has got after each page. what a definition adds once the rows are inserted, such
+/// as the foreign keys of Postgres, is written after every table
+pub async fn write_dump<W: Write + Send>(
+    pool: &dyn Pool,
+    database: &Database,
+    tables: &[Table],
+    out: &mut W,
+    progress: mpsc::Sender<DumpProgress>,
+) -> anyhow::Result<()> {
+    let mut dumped = DumpProgress {
+        total: tables.len(),
+        ..DumpProgress::default()
+    };
+    let _ = progress.send(dumped);
+    writeln!(out, "-- {}", database.name)?;
+    if let Some(statement) = foreign_key_checks(pool.engine(), false) {
+        writeln!(out, "{}", statement)?;
+    }
+    let mut after = vec![];
+    for table in tables {
+        let name = pool.table_name(database, table);
+        writeln!(out, "\n-- {}", name)?;
+        let definition = pool.get_dump_definition(database, table).await?;
+        for statement in definition.before {
+            writeln!(out, "{}", statement)?;
+        }
+        writeln!(out, "{}", definition.definition.trim_end())?;
+        after.extend(definition.after);
+        if !table.view {
+            // the types of the columns rather than their values tell numbers and bytes apart from text
+            let column_kinds = pool
+                .get_columns(database, table)
+                .await?
+                .iter()
+                .filter_map(|row| {
+                    let value = |header: &str| {
+                        row.fields()
+                            .iter()
+                            .position(|field| field == header)
+                            .and_then(|index| row.columns().get(index).cloned())
+                    };
+                    let kind =
+                        ColumnKind::from_badge(type_badge(&value("type").unwrap_or_default()));
+                    value("name").map(|name| (name, kind))
+                })
+                .collect::<HashMap<String, ColumnKind>>();
+            let primary_key = pool.get_primary_key(database, table).await?;
+            let mut fetched = 0;
+            let mut key = None;
+            loop {
+                let (headers, records, next_key) = pool
+                    .get_values(database, table, fetched, &primary_key, key.as_deref())
+                    .await?;
+                let kinds = headers
+                    .iter()
+                    .map(|header| {
+                        column_kinds
+                            .get(header)
+                            .copied()
+                            .unwrap_or(ColumnKind::Text)
+                    })
+                    .collect::<Vec<ColumnKind>>();
+                for statement in insert_batches(
+                    pool.engine(),
+                    name.as_str(),
+                    pool.identifier_quote(),
+                    &headers,
+                    &kinds,
+                    &records,
+                    DUMP_BATCH_SIZE,
+                ) {
+                    writeln!(out, "{};", statement)?;
+                }
+                fetched += records.len();
+                dumped.rows += records.len();
+                let _ = progress.send(dumped);
+                if records.len() < RECORDS_LIMIT_PER_PAGE as usize {
+                    break;
+                }
+                // the next page cannot start after a key which was not read, and offsets would page in another order
+                if !primary_key.is_empty() && next_key.is_none() {
+                    return Err(anyhow::anyhow!(
+                        "the primary key of the last record of {} could not be read",
+                        name
+                    ));
+                }
+                key = next_key;
+            }
+        }
+        dumped.tables += 1;
+        let _ = progress.send(dumped);
+    }
+    if !after.is_empty() {
+        writeln!(out)?;
+    }
+    for statement in after {
+        writeln!(out, "{}", statement)?;
+    }
+    if let Some(statement) = foreign_key_checks(pool.engine(), true) {
+        writeln!(out, "\n{}", statement)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::dump_order;
+    use database_tree::{Child, Database, Schema, Table};
+
+    #[test]
+    fn test_dump_order() {
+        let table = |name: &str, view: bool| Table {
+            name: name.to_string(),
+            create_time: None,
+            update_time: None,
+            engine: None,
+            schema: None,
+            view,
+        };
+        let database = Database {
+            name: "shop".to_string(),
+            children: vec![
+                Child::Table(table("active_users", true)),
+                Child::Schema(Schema {
+                    name: "public".to_string(),
+                    tables: vec![table("orders", false), table("totals", true)],
+                }),
+                Child::Table(table("users", false)),
+            ],
+        };
+        assert_eq!(
+            dump_order(&database)
+                .iter()
+                .map(|table| table.name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["orders", "users", "active_users", "totals"]
+        );
+    }
+}
@@ -0,0 +1,603 @@
+use crate::database::{ForeignKeyReference, Pool};
+use database_tree::{Child, Database, Table};
+use itertools::Itertools;
+
+const UP: u8 = 1;
+const DOWN: u8 = 2;
+const LEFT: u8 = 4;
+const RIGHT: u8 = 8;
+
+/// a table of the diagram with its columns and keys
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entity {
+    pub schema: Option<String>,
+    pub name: String,
+    /// the names of the columns with their types
+    pub columns: Vec<(String, String)>,
+    pub primary_key: Vec<String>,
+    pub foreign_keys: Vec<ForeignKeyReference>,
+}
+
+impl Entity {
+    /// the name of the table, qualified with its schema
+    pub fn title(&self) -> String {
+        match self.schema.as_ref() {
+            Some(schema) => format!("{}.{}", schema, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    fn column_type(&self, column: &str) -> &str {
+        self.columns
+            .iter()
+            .find(|(name, _)| name == column)
+            .map_or("", |(_, r#type)| r#type.as_str())
+    }
+
+    /// the columns of the keys of the table, those of the primary key first
+    fn key_columns(&self) -> Vec<&str> {
+        self.primary_key
+            .iter()
+            .map(String::as_str)
+            .chain(self.foreign_keys.iter().map(|key| key.column.as_str()))
+            .unique()
+            .collect()
+    }
+}
+
+/// the foreign keys of a table referencing another table of the diagram, or the same table
+#[derive(Debug, Clone, PartialEq)]
+pub struct Relation {
+    pub from: usize,
+    pub to: usize,
+    pub columns: Vec<String>,
+}
+
+/// which part of the diagram a character belongs to, to highlight the selected table and its relations
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Mark {
+    Plain,
+    Related,
+    Selected,
+}
+
+/// the diagram drawn as lines of characters
+pub struct Diagram {
+    pub lines: Vec<Vec<(char, Mark)>>,
+    /// the first line and the number of lines of the box of each table
+    pub boxes: Vec<(usize, usize)>,
+}
+
+/// a character of the diagram, with the directions of the lines of the relations meeting at it
+#[derive(Clone, Copy)]
+struct Cell {
+    c: char,
+    lines: u8,
+    mark: Mark,
+}
+
+/// the tables of a database with their keys, drawn as boxes joined by their foreign keys
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Erd {
+    pub database: String,
+    pub entities: Vec<Entity>,
+}
+
+impl Erd {
+    /// reads the columns and the keys of the tables of `database` from the server
+    pub async fn capture(pool: &dyn Pool, database: &Database) -> anyhow::Result<Self> {
+        let tables = database
+            .children
+            .iter()
+            .flat_map(|child| match child {
+                Child::Table(table) => vec![table.clone()],
+                Child::Schema(schema) => schema.tables.clone(),
+                Child::Routine(_) => vec![],
+            })
+            .filter(|table| !table.view)
+            .collect::<Vec<Table>>();
+        let mut entities = vec![];
+        for table in tables {
+            let columns = pool
+                .get_columns(database, &table)
+                .await?
+                .iter()
+                .map(|row| {
+                    let value = |header: &str| {
+                        row.fields()
+                            .iter()
+                            .position(|field| field == header)
+                            .and_then(|index| row.columns().get(index).cloned())
+                            .unwrap_or_default()
+                    };
+                    (value("name"), value("type"))
+                })
+                .collect();
+            entities.push(Entity {
+                primary_key: pool.get_primary_key(database, &table).await?,
+                foreign_keys: pool.get_foreign_key_references(database, &table).await?,
+                schema: table.schema,
+                name: table.name,
+                columns,
+            });
+        }
+        Ok(Self {
+            database: database.name.clone(),
+            entities,
+        })
+    }
+
+    /// returns the table of the diagram which a foreign key references
+    fn target(&self, reference: &ForeignKeyReference) -> Option<usize> {
+        if reference
+            .ref_database
+            .as_ref()
+            .map_or(false, |database| *database != self.database)
+        {
+            return None;
+        }
+        self.entities.iter().position(|entity| {
+            entity.name == reference.ref_table
+                && (reference.ref_schema.is_none() || entity.schema == reference.ref_schema)
+        })
+    }
+
+    /// returns the foreign keys between the tables of the diagram, with the columns of a composite key together
+    pub fn relations(&self) -> Vec<Relation> {
+        let mut relations: Vec<Relation> = vec![];
+        for (from, entity) in self.entities.iter().enumerate() {
+            for reference in &entity.foreign_keys {
+                let to = match self.target(reference) {
+                    Some(to) => to,
+                    None => continue,
+                };
+                match relations
+                    .iter_mut()
+                    .find(|relation| relation.from == from && relation.to == to)
+                {
+                    Some(relation) => relation.columns.push(reference.column.clone()),
+                    None => relations.push(Relation {
+                        from,
+                        to,
+                        columns: vec![reference.column.clone()],
+                    }),
+                }
+            }
+        }
+        relations
+    }
+
+    /// returns the key columns of a table with the lines showing them in its box, which name the table a foreign
+    /// key references when it is not in the diagram
+    fn rows(&self, index: usize) -> Vec<(String, String)> {
+        let entity = &self.entities[index];
+        entity
+            .key_columns()
+            .into_iter()
+            .map(|column| {
+                let reference = entity.foreign_keys.iter().find(|key| key.column == column);
+                let marks = match (
+                    entity.primary_key.iter().any(|key| key == column),
+                    reference,
+                ) {
+                    (true, Some(_)) => "PK FK",
+                    (true, None) => "PK",
+                    (false, _) => "FK",
+                };
+                let mut label = format!("{:<5} {} {}", marks, column, entity.column_type(column))
+                    .trim_end()
+                    .to_string();
+                if let Some(reference) = reference.filter(|key| self.target(key).is_none()) {
+                    let table = reference
+                        .ref_schema
+                        .as_ref()
+                        .or(reference.ref_database.as_ref())
+                        .map_or_else(
+                            || reference.ref_table.clone(),
+                            |qualifier| format!("{}.{}", qualifier, reference.ref_table),
+                        );
+                    label = format!("{} → {}", label, table);
+                }
+                (column.to_string(), label)
+            })
+            .collect()
+    }
+
+    /// draws the tables as boxes of the same width one under another, and each relation as a line from the row
+    /// of its column to an arrow at the name of the table it references, running down a lane of its own on the
+    /// right of the boxes unless it does not overlap the relations already in the lane
+    pub fn diagram(&self, selected: Option<usize>) -> Diagram {
+        let rows = (0..self.entities.len())
+            .map(|index| self.rows(index))
+            .collect::<Vec<_>>();
+        let inner = self
+            .entities
+            .iter()
+            .zip(&rows)
+            .flat_map(|(entity, rows)| {
+                std::iter::once(entity.title().chars().count())
+                    .chain(rows.iter().map(|(_, label)| label.chars().count()))
+            })
+            .max()
+            .unwrap_or(0);
+        let width = inner + 4;
+        let mut boxes = vec![];
+        let mut top = 0;
+        for rows in &rows {
+            let height = if rows.is_empty() { 3 } else { 4 + rows.len() };
+            boxes.push((top, height));
+            top += height + 1;
+        }
+
+        let relations = self.relations();
+        let endpoints = relations
+            .iter()
+            .map(|relation| {
+                let row = rows[relation.from]
+                    .iter()
+                    .position(|(column, _)| *column == relation.columns[0])
+                    .unwrap_or(0);
+                (boxes[relation.from].0 + 3 + row, boxes[relation.to].0 + 1)
+            })
+            .collect::<Vec<_>>();
+        // the last line taken in each lane
+        let mut lanes: Vec<usize> = vec![];
+        let mut lane_of = vec![0; relations.len()];
+        for index in (0..relations.len()).sorted_by_key(|index| {
+            let (from, to) = endpoints[*index];
+            from.min(to)
+        }) {
+            let (from, to) = endpoints[index];
+            match lanes.iter().position(|end| *end < from.min(to)) {
+                Some(lane) => {
+                    lanes[lane] = from.max(to);
+                    lane_of[index] = lane;
+                }
+                None => {
+                    lanes.push(from.max(to));
+                    lane_of[index] = lanes.len() - 1;
+                }
+            }
+        }
+
+        let blank = Cell {
+            c: ' ',
+            lines: 0,
+            mark: Mark::Plain,
+        };
+        let mut cells = vec![vec![blank; width + 1 + 2 * lanes.len()]; top.saturating_sub(1)];
+        let mut text = |x: usize, y: usize, text: &str, mark: Mark| {
+            for (offset, c) in text.chars().enumerate() {
+                cells[y][x + offset] = Cell { c, lines: 0, mark };
+            }
+        };
+        for (index, (entity, rows)) in self.entities.iter().zip(&rows).enumerate() {
+            let mark = if selected == Some(index) {
+                Mark::Selected
+            } else {
+                Mark::Plain
+            };
+            let (top, height) = boxes[index];
+            let border = "─".repeat(width - 2);
+            text(0, top, format!("┌{}┐", border).as_str(), mark);
+            text(
+                0,
+                top + 1,
+                format!("│ {:<inner$} │", entity.title(), inner = inner).as_str(),
+                mark,
+            );
+            if !rows.is_empty() {
+                text(0, top + 2, format!("├{}┤", border).as_str(), mark);
+                for (row, (_, label)) in rows.iter().enumerate() {
+                    text(
+                        0,
+                        top + 3 + row,
+                        format!("│ {:<inner$} │", label, inner = inner).as_str(),
+                        mark,
+                    );
+                }
+            }
+            text(0, top + height - 1, format!("└{}┘", border).as_str(), mark);
+        }
+        for (index, relation) in relations.iter().enumerate() {
+            let mark = if selected.map_or(false, |selected| {
+                relation.from == selected || relation.to == selected
+            }) {
+                Mark::Related
+            } else {
+                Mark::Plain
+            };
+            let mut line = |x: usize, y: usize, lines: u8| {
+                let cell: &mut Cell = &mut cells[y][x];
+                cell.lines |= lines;
+                if mark > cell.mark {
+                    cell.mark = mark;
+                }
+            };
+            let (from, to) = endpoints[index];
+            let lane = width + 2 + 2 * lane_of[index];
+            let (towards, backwards) = if to > from { (DOWN, UP) } else { (UP, DOWN) };
+            for x in width..lane {
+                line(x, from, LEFT | RIGHT);
+                line(x, to, LEFT | RIGHT);
+            }
+            line(lane, from, LEFT | towards);
+            for y in from.min(to) + 1..from.max(to) {
+                line(lane, y, UP | DOWN);
+            }
+            line(lane, to, LEFT | backwards);
+            cells[to][width].c = '◀';
+        }
+
+        Diagram {
+            lines: cells
+                .into_iter()
+                .map(|line| {
+                    line.into_iter()
+                        .map(|cell| match cell.c {
+                            ' ' => (line_char(cell.lines), cell.mark),
+                            c => (c, cell.mark),
+                        })
+                        .collect()
+                })
+                .collect(),
+            boxes,
+        }
+    }
+
+    /// writes the diagram in the DOT language of Graphviz, with the key columns of each table
+    pub fn to_dot(&self) -> String {
+        let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut dot = vec![
+            format!("digraph {} {{", quote(self.database.as_str())),
+            "  rankdir=LR;".to_string(),
+            "  node [shape=box];".to_string(),
+        ];
+        for (index, entity) in self.entities.iter().enumerate() {
+            let label = std::iter::once(format!("{}\n", entity.title()))
+                .chain(self.rows(index).into_iter().map(|(_, label)| label))
+                .map(|line| {
+                    line.replace('\\', "\\\\")
+                        .replace('"', "\\\"")
+                        .replace('\n', "\\n")
+                })
+                .join("\\l");
+            dot.push(format!(
+                "  {} [label=\"{}\\l\"];",
+                quote(entity.title().as_str()),
+                label
+            ));
+        }
+        for relation in self.relations() {
+            dot.push(format!(
+                "  {} -> {} [label={}];",
+                quote(self.entities[relation.from].title().as_str()),
+                quote(self.entities[relation.to].title().as_str()),
+                quote(relation.columns.join(", ").as_str())
+            ));
+        }
+        dot.push("}".to_string());
+        dot.join("\n")
+    }
+
+    /// writes the diagram as a Mermaid entity relationship diagram, with every column of each table
+    pub fn to_mermaid(&self) -> String {
+        let mut mermaid = vec!["erDiagram".to_string()];
+        for entity in &self.entities {
+            mermaid.push(format!("    {} {{", mermaid_word(entity.title().as_str())));
+            for (column, r#type) in &entity.columns {
+                let mut keys = vec![];
+                if entity.primary_key.contains(column) {
+                    keys.push("PK");
+                }
+                if entity.foreign_keys.iter().any(|key| key.column == *column) {
+                    keys.push("FK");
+                }
+                mermaid.push(
+                    format!(
+                        "        {} {} {}",
+                        mermaid_word(r#type),
+                        mermaid_word(column),
+                        keys.join(", ")
+                    )
+                    .trim_end()
+                    .to_string(),
+                );
+            }
+            mermaid.push("    }".to_string());
+        }
+        for relation in self.relations() {
+            mermaid.push(format!(
+                "    {} }}o--|| {} : \"{}\"",
+                mermaid_word(self.entities[relation.from].title().as_str()),
+                mermaid_word(self.entities[relation.to].title().as_str()),
+                relation.columns.join(", ").replace('"', "'")
+            ));
+        }
+        mermaid.join("\n")
+    }
+}
+
+/// returns the character drawing the lines going from a cell in the directions
+fn line_char(lines: u8) -> char {
+    match lines {
+        0 => ' ',
+        l if l == UP | DOWN | LEFT | RIGHT => '┼',
+        l if l == UP | DOWN | LEFT => '┤',
+        l if l == UP | DOWN | RIGHT => '├',
+        l if l == LEFT | RIGHT | DOWN => '┬',
+        l if l == LEFT | RIGHT | UP => '┴',
+        l if l == DOWN | RIGHT => '┌',
+        l if l == DOWN | LEFT => '┐',
+        l if l == UP | RIGHT => '└',
+        l if l == UP | LEFT => '┘',
+        l if l & (UP | DOWN) != 0 => '│',
+        _ => '─',
+    }
+}
+
+/// turns a name into a word Mermaid accepts as the name of an entity, an attribute or a type
+fn mermaid_word(name: &str) -> String {
+    let word = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "_-()[]".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    match word.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => word,
+        _ => format!("_{}", word),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Diagram, Entity, Erd, Mark, Relation};
+    use crate::database::ForeignKeyReference;
+
+    fn text(diagram: &Diagram) -> Vec<String> {
+        diagram
+            .lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|(c, _)| c)
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn entity(name: &str, primary_key: &[&str], foreign_keys: &[(&str, &str)]) -> Entity {
+        Entity {
+            schema: None,
+            name: name.to_string(),
+            columns: primary_key
+                .iter()
+                .chain(foreign_keys.iter().map(|(column, _)| column))
+                .map(|column| (column.to_string(), "int".to_string()))
+                .collect(),
+            primary_key: primary_key.iter().map(|key| key.to_string()).collect(),
+            foreign_keys: foreign_keys
+                .iter()
+                .map(|(column, table)| ForeignKeyReference {
+                    column: column.to_string(),
+                    ref_database: None,
+                    ref_schema: None,
+                    ref_table: table.to_string(),
+                    ref_column: "id".to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    fn erd() -> Erd {
+        Erd {
+            database: "shop".to_string(),
+            entities: vec![
+                entity(
+                    "orders",
+                    &["id"],
+                    &[("user_id", "users"), ("team_id", "teams")],
+                ),
+                entity("users", &["id"], &[("manager_id", "users")]),
+                entity("logs", &[], &[]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_relations() {
+        assert_eq!(
+            erd().relations(),
+            vec![
+                Relation {
+                    from: 0,
+                    to: 1,
+                    columns: vec!["user_id".to_string()]
+                },
+                Relation {
+                    from: 1,
+                    to: 1,
+                    columns: vec!["manager_id".to_string()]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diagram() {
+        let diagram = erd().diagram(Some(1));
+        assert_eq!(
+            text(&diagram),
+            vec![
+                "┌───────────────────────────┐",
+                "│ orders                    │",
+                "├───────────────────────────┤",
+                "│ PK    id int              │",
+                "│ FK    user_id int         │──┐",
+                "│ FK    team_id int → teams │  │",
+                "└───────────────────────────┘  │",
+                "                               │",
+                "┌───────────────────────────┐  │",
+                "│ users                     │◀─┴─┐",
+                "├───────────────────────────┤    │",
+                "│ PK    id int              │    │",
+                "│ FK    manager_id int      │────┘",
+                "└───────────────────────────┘",
+                "",
+                "┌───────────────────────────┐",
+                "│ logs                      │",
+                "└───────────────────────────┘",
+            ]
+        );
+        assert_eq!(diagram.boxes, vec![(0, 7), (8, 6), (15, 3)]);
+        assert_eq!(diagram.lines[9][0].1, Mark::Selected);
+        assert_eq!(diagram.lines[4][31].1, Mark::Related);
+        assert_eq!(diagram.lines[0][0].1, Mark::Plain);
+    }
+
+    #[test]
+    fn test_export() {
+        assert_eq!(
+            erd().to_dot(),
+            [
+                "digraph \"shop\" {",
+                "  rankdir=LR;",
+                "  node [shape=box];",
+                "  \"orders\" [label=\"orders\\n\\lPK    id int\\lFK    user_id int\\lFK    team_id int → teams\\l\"];",
+                "  \"users\" [label=\"users\\n\\lPK    id int\\lFK    manager_id int\\l\"];",
+                "  \"logs\" [label=\"logs\\n\\l\"];",
+                "  \"orders\" -> \"users\" [label=\"user_id\"];",
+                "  \"users\" -> \"users\" [label=\"manager_id\"];",
+                "}",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            erd().to_mermaid(),
+            [
+                "erDiagram",
+                "    orders {",
+                "        int id PK",
+                "        int user_id FK",
+                "        int team_id FK",
+                "    }",
+                "    users {",
+                "        int id PK",
+                "        int manager_id FK",
+                "    }",
+                "    logs {",
+                "    }",
+                "    orders }o--|| users : \"user_id\"",
+                "    users }o--|| users : \"manager_id\"",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::event::Key;
+use crate::event::{Key, Mouse};
 use crossterm::event;
 use std::{sync::mpsc, thread, time::Duration};
 
@@ -20,6 +20,7 @@ impl Default for EventConfig {
 #[derive(Copy, Clone)]
 pub enum Event<I> {
     Input(I),
+    Mouse(Mouse),
     Tick,
 }
 
@@ -42,10 +43,18 @@ impl Events {
         let event_tx = tx.clone();
         thread::spawn(move || loop {
             if event::poll(config.tick_rate).unwrap() {
-                if let event::Event::Key(key) = event::read().unwrap() {
-                    let key = Key::from(key);
+                match event::read().unwrap() {
+                    event::Event::Key(key) => {
+                        let key = Key::from(key);
 
-                    event_tx.send(Event::Input(key)).unwrap();
+                        event_tx.send(Event::Input(key)).unwrap();
+                    }
+                    event::Event::Mouse(mouse) => {
+                        if let Some(mouse) = Mouse::from_event(mouse) {
+                            event_tx.send(Event::Mouse(mouse)).unwrap();
+                        }
+                    }
+                    _ => (),
                 }
             }
 
@@ -9,6 +9,8 @@ pub enum Key {
     Enter,
     /// Tabulation key
     Tab,
+    /// Shift + Tab
+    BackTab,
     /// Backspace key
     Backspace,
     /// Escape key
@@ -111,6 +113,7 @@ impl fmt::Display for Key {
             Key::Down => write!(f, "\u{2193}"),  //↓
             Key::Enter
             | Key::Tab
+            | Key::BackTab
             | Key::Backspace
             | Key::Esc
             | Key::Ins
@@ -187,6 +190,10 @@ impl From<event::KeyEvent> for Key {
                 code: event::KeyCode::Tab,
                 ..
             } => Key::Tab,
+            event::KeyEvent {
+                code: event::KeyCode::BackTab,
+                ..
+            } => Key::BackTab,
 
             // First check for char + modifier
             event::KeyEvent {
@@ -0,0 +1,82 @@
+use crossterm::event;
+use tui::layout::Rect;
+
+/// how many rows or lines a turn of the wheel moves
+pub const WHEEL_LINES: usize = 3;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum MouseKind {
+    /// the left button was pressed
+    Click,
+    ScrollUp,
+    ScrollDown,
+}
+
+/// what the mouse did and on which cell of the screen
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Mouse {
+    pub kind: MouseKind,
+    pub column: u16,
+    pub row: u16,
+}
+
+impl Mouse {
+    /// returns the mouse event, or nothing for the moves, drags and buttons which are not handled
+    pub fn from_event(mouse_event: event::MouseEvent) -> Option<Self> {
+        let kind = match mouse_event.kind {
+            event::MouseEventKind::Down(event::MouseButton::Left) => MouseKind::Click,
+            event::MouseEventKind::ScrollUp => MouseKind::ScrollUp,
+            event::MouseEventKind::ScrollDown => MouseKind::ScrollDown,
+            _ => return None,
+        };
+        Some(Self {
+            kind,
+            column: mouse_event.column,
+            row: mouse_event.row,
+        })
+    }
+
+    pub fn is_in(&self, area: Rect) -> bool {
+        (area.left()..area.right()).contains(&self.column)
+            && (area.top()..area.bottom()).contains(&self.row)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{event, Mouse, MouseKind, Rect};
+
+    #[test]
+    fn test_from_event() {
+        let mouse_event = |kind| event::MouseEvent {
+            kind,
+            column: 3,
+            row: 4,
+            modifiers: event::KeyModifiers::NONE,
+        };
+        assert_eq!(
+            Mouse::from_event(mouse_event(event::MouseEventKind::Down(
+                event::MouseButton::Left
+            ))),
+            Some(Mouse {
+                kind: MouseKind::Click,
+                column: 3,
+                row: 4
+            })
+        );
+        assert_eq!(
+            Mouse::from_event(mouse_event(event::MouseEventKind::Down(
+                event::MouseButton::Right
+            ))),
+            None
+        );
+        assert_eq!(
+            Mouse::from_event(mouse_event(event::MouseEventKind::Moved)),
+            None
+        );
+
+        let mouse = Mouse::from_event(mouse_event(event::MouseEventKind::ScrollDown)).unwrap();
+        assert!(mouse.is_in(Rect::new(3, 4, 1, 1)));
+        assert!(!mouse.is_in(Rect::new(0, 0, 3, 10)));
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+const FIRST_NAMES: &[&str] = &[
+    "James", "Mary", "John", "Patricia", "Robert", "Jennifer", "Michael", "Linda", "Takayuki",
+    "Yuki", "Haruto", "Sakura", "Liam", "Emma", "Noah", "Olivia",
+];
+const LAST_NAMES: &[&str] = &[
+    "Smith",
+    "Johnson",
+    "Williams",
+    "Brown",
+    "Jones",
+    "Garcia",
+    "Miller",
+    "Davis",
+    "Sato",
+    "Suzuki",
+    "Takahashi",
+    "Tanaka",
+    "Wilson",
+    "Moore",
+    "Taylor",
+    "Anderson",
+];
+const DOMAINS: &[&str] = &["example.com", "example.net", "example.org"];
+const CITIES: &[&str] = &[
+    "Tokyo", "Osaka", "New York", "London", "Paris", "Berlin", "Sydney", "Toronto",
+];
+const WORDS: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+];
+
+/// generates plausible values from a column name and type
+pub struct FakeDataGenerator {
+    state: u64,
+}
+
+impl Default for FakeDataGenerator {
+    fn default() -> Self {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(0);
+        Self::new(hasher.finish())
+    }
+}
+
+impl FakeDataGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed.max(1) }
+    }
+
+    fn next(&mut self) -> u64 {
+        // xorshift64
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    fn range(&mut self, min: u64, max: u64) -> u64 {
+        min + self.next() % (max - min + 1)
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.next() as usize % items.len()]
+    }
+
+    /// returns `None` for columns that should be left to the database, such as auto-incremented ids
+    pub fn value(&mut self, name: &str, r#type: &str, default: Option<&str>) -> Option<String> {
+        let name = name.to_lowercase();
+        let r#type = r#type.to_lowercase();
+        if default.map_or(false, |default| default.starts_with("nextval("))
+            || (name == "id" && is_integer_type(&r#type))
+            || r#type.contains("serial")
+        {
+            return None;
+        }
+
+        if r#type.contains("uuid") || name == "uuid" || name.ends_with("_uuid") {
+            return Some(self.uuid());
+        }
+        if name.contains("email") {
+            return Some(format!(
+                "{}.{}{}@{}",
+                self.pick(FIRST_NAMES).to_lowercase(),
+                self.pick(LAST_NAMES).to_lowercase(),
+                self.range(1, 999),
+                self.pick(DOMAINS)
+            ));
+        }
+        if r#type.contains("char") || r#type.contains("text") {
+            let value = if name.contains("first_name") {
+                self.pick(FIRST_NAMES).to_string()
+            } else if name.contains("last_name") {
+                self.pick(LAST_NAMES).to_string()
+            } else if name.contains("name") {
+                format!("{} {}", self.pick(FIRST_NAMES), self.pick(LAST_NAMES))
+            } else if name.contains("city") {
+                self.pick(CITIES).to_string()
+            } else if name.contains("phone") {
+                format!(
+                    "{:03}-{:04}-{:04}",
+                    self.range(10, 999),
+                    self.range(0, 9999),
+                    self.range(0, 9999)
+                )
+            } else {
+                let count = self.range(2, 6);
+                (0..count)
+                    .map(|_| self.pick(WORDS))
+                    .collect::<Vec<&str>>()
+                    .join(" ")
+            };
+            return Some(truncate(value, char_length(&r#type)));
+        }
+        if r#type.starts_with("bool") || r#type == "tinyint(1)" {
+            return Some(if self.range(0, 1) == 1 { "1" } else { "0" }.to_string());
+        }
+        if is_integer_type(&r#type) {
+            let max = if r#type.contains("tiny") {
+                127
+            } else if r#type.contains("small") {
+                32767
+            } else {
+                100_000
+            };
+            return Some(self.range(0, max).to_string());
+        }
+        if ["decimal", "numeric", "float", "double", "real"]
+            .iter()
+            .any(|t| r#type.contains(t))
+        {
+            return Some(format!(
+                "{}.{:02}",
+                self.range(0, 10_000),
+                self.range(0, 99)
+            ));
+        }
+        if r#type.contains("timestamp") || r#type.contains("datetime") {
+            return Some(format!("{} {}", self.date(), self.time()));
+        }
+        if r#type.contains("date") {
+            return Some(self.date());
+        }
+        if r#type.contains("time") {
+            return Some(self.time());
+        }
+        if r#type.contains("json") {
+            return Some(format!(
+                r#"{{"{}": {}}}"#,
+                self.pick(WORDS),
+                self.range(0, 100)
+            ));
+        }
+        Some(self.pick(WORDS).to_string())
+    }
+
+    fn uuid(&mut self) -> String {
+        let (a, b) = (self.next(), self.next());
+        format!(
+            "{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}",
+            a >> 32,
+            (a >> 16) & 0xffff,
+            a & 0x0fff,
+            ((b >> 48) & 0x3fff) | 0x8000,
+            b & 0xffff_ffff_ffff
+        )
+    }
+
+    fn date(&mut self) -> String {
+        format!(
+            "{:04}-{:02}-{:02}",
+            self.range(2000, 2030),
+            self.range(1, 12),
+            self.range(1, 28)
+        )
+    }
+
+    fn time(&mut self) -> String {
+        format!(
+            "{:02}:{:02}:{:02}",
+            self.range(0, 23),
+            self.range(0, 59),
+            self.range(0, 59)
+        )
+    }
+}
+
+fn is_integer_type(r#type: &str) -> bool {
+    r#type.contains("int")
+}
+
+fn char_length(r#type: &str) -> Option<usize> {
+    let start = r#type.find('(')?;
+    let end = r#type.find(')')?;
+    r#type.get(start + 1..end)?.trim().parse().ok()
+}
+
+fn truncate(value: String, length: Option<usize>) -> String {
+    match length {
+        Some(length) => value.chars().take(length).collect(),
+        None => value,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FakeDataGenerator;
+
+    #[test]
+    fn test_value_skips_auto_increment_columns() {
+        let mut generator = FakeDataGenerator::new(42);
+        assert_eq!(generator.value("id", "int(11)", None), None);
+        assert_eq!(
+            generator.value("id", "bigint", Some("nextval('a_id_seq')")),
+            None
+        );
+        assert_eq!(generator.value("user_id", "serial", None), None);
+    }
+
+    #[test]
+    fn test_value() {
+        let mut generator = FakeDataGenerator::new(42);
+        assert!(generator
+            .value("email", "varchar(255)", None)
+            .unwrap()
+            .contains('@'));
+        assert_eq!(generator.value("uuid", "uuid", None).unwrap().len(), 36);
+        assert!(generator.value("name", "varchar(3)", None).unwrap().len() <= 3);
+        assert!(generator
+            .value("age", "int", None)
+            .unwrap()
+            .parse::<u64>()
+            .is_ok());
+        assert_eq!(
+            generator
+                .value("created_at", "timestamp", None)
+                .unwrap()
+                .len(),
+            19
+        );
+        assert_eq!(generator.value("birthday", "date", None).unwrap().len(), 10);
+    }
+}
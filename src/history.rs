@@ -0,0 +1,207 @@
+use crate::config::{get_app_config_path, Connection};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+const MAX_ENTRIES: usize = 1000;
+
+/// opens the file of a history so that only the user can read it, as the queries may hold passwords, also narrowing
+/// the permissions of a file written before
+fn open_private(path: &Path, options: &mut OpenOptions) -> std::io::Result<File> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(file)
+}
+
+/// queries executed in the SQL editor, persisted per connection with one query per line as a JSON string, so that a
+/// query keeps its line breaks and the comments running to the end of its lines
+#[derive(Default)]
+pub struct QueryHistory {
+    path: Option<PathBuf>,
+    entries: Vec<String>,
+    /// how many lines the file has, which is rewritten with only the last `MAX_ENTRIES` once it has twice as many
+    lines: usize,
+}
+
+impl QueryHistory {
+    pub fn load(conn: &Connection) -> anyhow::Result<Self> {
+        let dir = get_app_config_path()?.join("history");
+        std::fs::create_dir_all(&dir)?;
+        let mut history = Self {
+            path: Some(dir.join(conn.history_file_name())),
+            ..Self::default()
+        };
+        history.read()?;
+        Ok(history)
+    }
+
+    fn read(&mut self) -> anyhow::Result<()> {
+        let path = match self.path.as_ref() {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let mut entries = vec![];
+        let mut rewrite = false;
+        if let Ok(file) = File::open(path) {
+            for line in BufReader::new(file).lines() {
+                let line = line?;
+                entries.push(match serde_json::from_str::<String>(line.as_str()) {
+                    Ok(entry) => entry,
+                    // written before the entries were JSON, with the lines of each query joined by spaces
+                    Err(_) => {
+                        rewrite = true;
+                        line
+                    }
+                });
+            }
+        }
+        self.lines = entries.len();
+        let start = entries.len().saturating_sub(MAX_ENTRIES);
+        self.entries = entries.split_off(start);
+        if rewrite || start > 0 {
+            self.rewrite()?;
+        }
+        Ok(())
+    }
+
+    /// replaces the file with the entries kept in memory
+    fn rewrite(&mut self) -> anyhow::Result<()> {
+        if let Some(path) = self.path.as_ref() {
+            let mut file = BufWriter::new(open_private(
+                path,
+                OpenOptions::new().write(true).create(true).truncate(true),
+            )?);
+            for entry in &self.entries {
+                writeln!(file, "{}", serde_json::to_string(entry)?)?;
+            }
+            file.flush()?;
+            self.lines = self.entries.len();
+        }
+        Ok(())
+    }
+
+    pub fn push(&mut self, query: &str) -> anyhow::Result<()> {
+        let query = query.trim().to_string();
+        if query.is_empty() || self.entries.last() == Some(&query) {
+            return Ok(());
+        }
+        self.entries.push(query);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        if self.lines >= MAX_ENTRIES * 2 {
+            return self.rewrite();
+        }
+        if let Some(path) = self.path.as_ref() {
+            let mut file = open_private(path, OpenOptions::new().create(true).append(true))?;
+            writeln!(
+                file,
+                "{}",
+                serde_json::to_string(&self.entries[self.entries.len() - 1])?
+            )?;
+            self.lines += 1;
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, index: usize) -> Option<&String> {
+        self.entries.get(index)
+    }
+
+    /// returns the index of the newest entry older than `before` which contains `pattern`
+    pub fn search(&self, pattern: &str, before: Option<usize>) -> Option<usize> {
+        self.entries
+            .iter()
+            .take(before.unwrap_or(self.entries.len()))
+            .rposition(|entry| entry.contains(pattern))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{QueryHistory, MAX_ENTRIES};
+
+    #[test]
+    fn test_push() {
+        let mut history = QueryHistory::default();
+        history.push("SELECT 1").unwrap();
+        history.push("SELECT 1").unwrap();
+        history.push("  ").unwrap();
+        history.push("SELECT -- the second\n  2\n").unwrap();
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(
+            history.get(1),
+            Some(&"SELECT -- the second\n  2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_file() {
+        let dir = std::env::temp_dir().join(format!("gobang-history-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sqlite_a.db");
+        std::fs::write(&path, "SELECT 1\n\"SELECT -- one\\n  1\"\n").unwrap();
+        let read = |path: &std::path::PathBuf| {
+            let mut history = QueryHistory {
+                path: Some(path.clone()),
+                ..QueryHistory::default()
+            };
+            history.read().unwrap();
+            history
+        };
+
+        // the entries written before they were JSON are read as they are and written back as JSON
+        let mut history = read(&path);
+        assert_eq!(history.entries, vec!["SELECT 1", "SELECT -- one\n  1"]);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "\"SELECT 1\"\n\"SELECT -- one\\n  1\"\n"
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        for i in 0..MAX_ENTRIES * 2 {
+            history.push(format!("SELECT {}\n", i).as_str()).unwrap();
+        }
+        assert_eq!(history.entries.len(), MAX_ENTRIES);
+        let lines = std::fs::read_to_string(&path).unwrap().lines().count();
+        assert!((MAX_ENTRIES..MAX_ENTRIES * 2).contains(&lines));
+        let history = read(&path);
+        assert_eq!(history.entries.len(), MAX_ENTRIES);
+        assert_eq!(
+            history.get(MAX_ENTRIES - 1),
+            Some(&format!("SELECT {}", MAX_ENTRIES * 2 - 1))
+        );
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap().lines().count(),
+            MAX_ENTRIES
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_search() {
+        let mut history = QueryHistory::default();
+        history.push("SELECT * FROM users").unwrap();
+        history.push("DELETE FROM posts").unwrap();
+        history.push("SELECT * FROM posts").unwrap();
+        assert_eq!(history.search("FROM", None), Some(2));
+        assert_eq!(history.search("FROM", Some(2)), Some(1));
+        assert_eq!(history.search("users", None), Some(0));
+        assert_eq!(history.search("users", Some(0)), None);
+        assert_eq!(history.search("comments", None), None);
+    }
+}
@@ -0,0 +1,795 @@
+use serde::Deserialize;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static LOCALE: AtomicU8 = AtomicU8::new(Locale::En as u8);
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum Locale {
+    #[serde(rename = "en")]
+    En,
+    #[serde(rename = "ja")]
+    Ja,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::En
+    }
+}
+
+impl Locale {
+    /// set the locale used to look up UI labels
+    pub fn init(self) {
+        LOCALE.store(self as u8, Ordering::Relaxed);
+    }
+
+    pub fn current() -> Self {
+        match LOCALE.load(Ordering::Relaxed) {
+            1 => Self::Ja,
+            _ => Self::En,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Msg {
+    GroupGeneral,
+    GroupTable,
+    GroupDatabases,
+    Records,
+    Columns,
+    Constraints,
+    ForeignKeys,
+    Indexes,
+    Definition,
+    Error,
+    Help,
+    Connections,
+    Databases,
+    FilterTables,
+    FilterRecordsPlaceholder,
+    Rows,
+    ColumnCount,
+    Engine,
+    DataSize,
+    IndexSize,
+    Collation,
+    Scroll,
+    ScrollMultipleLines,
+    ScrollToTopBottom,
+    ExpandCollapse,
+    RecentDatabases,
+    Filter,
+    MoveFocus,
+    ExtendSelection,
+    Tab,
+    ToggleWrap,
+    ScrollValue,
+    SearchInTable,
+    JumpToMatch,
+    Matches,
+    Row,
+    RecordDetail,
+    OpenRecordDetail,
+    GenerateFakeData,
+    PipeToCommand,
+    PipeCommandPlaceholder,
+    CommandOutput,
+    FollowForeignKey,
+    ReferencedTableNotFound,
+    NotSupportedByServer,
+    FreezeRows,
+    NumberOfRows,
+    Preview,
+    Insert,
+    Cancel,
+    GroupSql,
+    Sql,
+    SqlEditorPlaceholder,
+    ExecuteQuery,
+    FocusSqlEditor,
+    SwitchResultTab,
+    Result,
+    RowsAffected,
+    SearchHistory,
+    ExplainQuery,
+    FormatQuery,
+    ExplainAnalyzeQuery,
+    ConfirmExplainAnalyze,
+    QueryPlan,
+    RunningQuery,
+    CancelQuery,
+    QueryCancelled,
+    RunFile,
+    RunFilePlaceholder,
+    StopOnError,
+    ContinueOnError,
+    Run,
+    Succeeded,
+    Failed,
+    Skipped,
+    Remaining,
+    HistorySearch,
+    HistorySearchFailed,
+    AddConnection,
+    EditConnection,
+    DeleteConnection,
+    TestConnection,
+    ConfirmDeleteConnection,
+    ConnectionSucceeded,
+    ConnectionFailed,
+    Save,
+    ChooseSqliteFile,
+    SchemaSnapshot,
+    SchemaSnapshotPlaceholder,
+    SaveSnapshot,
+    CompareSnapshot,
+    SnapshotSaved,
+    NoSchemaChanges,
+    Erd,
+    CopyDiagram,
+    ImportCsv,
+    ImportCsvPlaceholder,
+    FirstRowIsHeader,
+    NoHeaderRow,
+    EmptyIsNull,
+    EmptyIsEmptyString,
+    CsvColumn,
+    SkipColumn,
+    DoesNotFit,
+    InvalidValues,
+    NoColumnMapped,
+    EmptyCsv,
+    Validate,
+    RowsFit,
+    BatchSize,
+    Import,
+    RowsImported,
+    Tables,
+    RunWithSettings,
+    SessionSettingsPlaceholder,
+    ConnectionSettings,
+    ExportView,
+    ExportViewPlaceholder,
+    VisibleRows,
+    AllLoadedRows,
+    ViewExported,
+    Lines,
+    InsertRow,
+    Back,
+    DeleteRows,
+    RowsLocked,
+    Retry,
+    More,
+    ReadOnly,
+    ReadOnlyConnection,
+    ColumnStats,
+    StatsRows,
+    StatsNonNull,
+    StatsDistinct,
+    StatsMin,
+    StatsMax,
+    StatsAverage,
+    RetriedTransientErrors,
+    ConfirmWrite,
+    ConfirmExport,
+    Statements,
+    Snippets,
+    SaveSnippet,
+    InsertSnippet,
+    SnippetNamePlaceholder,
+    SearchSnippetsPlaceholder,
+    CellValue,
+    OpenCellValue,
+    RecalculateWidths,
+    Characters,
+    Bytes,
+    Queue,
+    QueueQuery,
+    QueueAnalyze,
+    OpenQueue,
+    CancelQueued,
+    Pending,
+    Cancelled,
+    Close,
+    NormalMode,
+    InsertMode,
+    VisualMode,
+    GoTo,
+    ReferencedTables,
+    OpenReferencedTable,
+    NoReferencedTables,
+    FindTable,
+    FindTablePlaceholder,
+    ScheduledJobs,
+    OpenScheduledJobs,
+    NoScheduledJobs,
+    LastRun,
+    ToggleJob,
+    EnterPassword,
+    SaveToKeyring,
+    CopyAllRecords,
+    CopyAsInsert,
+    NoInsertTable,
+    Dump,
+    DumpPlaceholder,
+    Written,
+    DumpFinished,
+    RecordsFetched,
+    TransactionOpen,
+    Commit,
+    Rollback,
+    EndTransaction,
+    UncommittedTransaction,
+    Watching,
+    ToggleWatch,
+    WatchReadOnly,
+    PinColumns,
+    ImportConnections,
+    NoConnectionsToImport,
+    ToggleConnection,
+    AddCheckedConnections,
+    ShowHideColumns,
+    ToggleColumn,
+    ApplyColumns,
+    SaveToFile,
+    SavedTo,
+    ExecuteOtherFetch,
+    ToggleFold,
+    CopyPretty,
+    CopyMinified,
+    ChildRows,
+    NoChildRows,
+    OpenChildRows,
+    DeleteTemplate,
+    UpdateTemplate,
+    ExecuteRoutine,
+    OutputParameter,
+    NoParameters,
+    ColumnQuality,
+    QualityNull,
+    QualityEmpty,
+    QualityDuplicates,
+    QualityLength,
+    QualityMatches,
+    Triggers,
+    NotSupportedBy,
+    ConfirmDialect,
+    SearchMatches,
+    JumpToCell,
+    JoinOn,
+    PinRow,
+    BulkEdit,
+    FillDown,
+    SetNull,
+    IncrementCells,
+    UndoChange,
+    NothingToUndo,
+    Warnings,
+    ExecutionLog,
+    OpenExecutionLog,
+}
+
+impl Msg {
+    pub fn text(self) -> &'static str {
+        self.text_for(Locale::current())
+    }
+
+    pub fn text_for(self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::En => self.en(),
+            Locale::Ja => self.ja(),
+        }
+    }
+
+    fn en(self) -> &'static str {
+        match self {
+            Self::GroupGeneral => "-- General --",
+            Self::GroupTable => "-- Table --",
+            Self::GroupDatabases => "-- Databases --",
+            Self::Records => "Records",
+            Self::Columns => "Columns",
+            Self::Constraints => "Constraints",
+            Self::ForeignKeys => "Foreign keys",
+            Self::Indexes => "Indexes",
+            Self::Definition => "Definition",
+            Self::Error => "Error",
+            Self::Help => "Help",
+            Self::Connections => "Connections",
+            Self::Databases => "Databases",
+            Self::FilterTables => "Filter tables",
+            Self::FilterRecordsPlaceholder => {
+                "Enter a SQL expression in WHERE clause to filter records"
+            }
+            Self::Rows => "rows",
+            Self::ColumnCount => "columns",
+            Self::Engine => "engine",
+            Self::DataSize => "data",
+            Self::IndexSize => "indexes",
+            Self::Collation => "collation",
+            Self::Scroll => "Scroll up/down/left/right",
+            Self::ScrollMultipleLines => "Scroll up/down multiple lines",
+            Self::ScrollToTopBottom => "Scroll to top/bottom",
+            Self::ExpandCollapse => "Expand/Collapse",
+            Self::RecentDatabases => "Recent databases",
+            Self::Filter => "Filter",
+            Self::MoveFocus => "Move focus to left/right",
+            Self::ExtendSelection => "Extend selection by one cell up/down/left/right",
+            Self::Tab => "Tab",
+            Self::ToggleWrap => "Toggle text wrap of a column",
+            Self::ScrollValue => "Scroll the value of the selected cell left/right",
+            Self::SearchInTable => "Search the loaded rows",
+            Self::JumpToMatch => "Jump to the next/previous match",
+            Self::Matches => "matches",
+            Self::Row => "row",
+            Self::RecordDetail => "Record",
+            Self::OpenRecordDetail => "Show the selected record vertically",
+            Self::GenerateFakeData => "Generate fake data",
+            Self::PipeToCommand => "Pipe the selected cells to a command",
+            Self::PipeCommandPlaceholder => "Enter a shell command which reads TSV from stdin",
+            Self::CommandOutput => "Output",
+            Self::FollowForeignKey => "Open the record referenced by the selected cell",
+            Self::ReferencedTableNotFound => "Referenced table not found",
+            Self::NotSupportedByServer => "Not supported by this server",
+            Self::FreezeRows => "Freeze rows up to the selected one",
+            Self::NumberOfRows => "Number of rows",
+            Self::Preview => "Preview",
+            Self::Insert => "Insert",
+            Self::Cancel => "Cancel",
+            Self::GroupSql => "-- SQL --",
+            Self::Sql => "SQL",
+            Self::SqlEditorPlaceholder => "Enter SQL statements separated by semicolons",
+            Self::ExecuteQuery => "Execute query",
+            Self::FocusSqlEditor => "Focus SQL editor",
+            Self::SwitchResultTab => "Switch result tab",
+            Self::Result => "Result",
+            Self::RowsAffected => "rows affected",
+            Self::SearchHistory => "Search query history",
+            Self::ExplainQuery => "Explain query",
+            Self::FormatQuery => "Format query",
+            Self::ExplainAnalyzeQuery => "Explain and run query",
+            Self::ConfirmExplainAnalyze => "Run the statements with EXPLAIN ANALYZE",
+            Self::QueryPlan => "Query plan",
+            Self::RunningQuery => "Running query",
+            Self::CancelQuery => "Cancel running query",
+            Self::QueryCancelled => "Query cancelled",
+            Self::RunFile => "Run a SQL file",
+            Self::RunFilePlaceholder => "Enter the path of a .sql file",
+            Self::StopOnError => "Stop on error",
+            Self::ContinueOnError => "Continue on error",
+            Self::Run => "Run",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+            Self::Skipped => "skipped",
+            Self::Remaining => "remaining",
+            Self::HistorySearch => "reverse-i-search",
+            Self::HistorySearchFailed => "failing reverse-i-search",
+            Self::AddConnection => "Add",
+            Self::EditConnection => "Edit",
+            Self::DeleteConnection => "Delete",
+            Self::TestConnection => "Test",
+            Self::ConfirmDeleteConnection => "Press again to delete the connection",
+            Self::ConnectionSucceeded => "connected successfully",
+            Self::ConnectionFailed => "failed to connect",
+            Self::Save => "Save",
+            Self::ChooseSqliteFile => "Choose a SQLite or DuckDB file",
+            Self::SchemaSnapshot => "Schema snapshot",
+            Self::SchemaSnapshotPlaceholder => "Enter the path of a .json snapshot",
+            Self::SaveSnapshot => "save the schema to it",
+            Self::CompareSnapshot => "compare the schema with it",
+            Self::SnapshotSaved => "saved the snapshot",
+            Self::NoSchemaChanges => "no changes since the snapshot",
+            Self::Erd => "Relationship diagram",
+            Self::CopyDiagram => "Copy",
+            Self::ImportCsv => "Import CSV",
+            Self::ImportCsvPlaceholder => "Enter the path of a .csv file",
+            Self::FirstRowIsHeader => "First row names the columns",
+            Self::NoHeaderRow => "No header row",
+            Self::EmptyIsNull => "Empty values are NULL",
+            Self::EmptyIsEmptyString => "Empty values are empty strings",
+            Self::CsvColumn => "CSV column",
+            Self::SkipColumn => "(skip)",
+            Self::DoesNotFit => "does not fit",
+            Self::InvalidValues => "values do not fit their columns",
+            Self::NoColumnMapped => "map at least one column to a column of the file",
+            Self::EmptyCsv => "the file has no rows to import",
+            Self::Validate => "Validate",
+            Self::RowsFit => "rows fit their columns, nothing was run",
+            Self::BatchSize => "Rows per INSERT",
+            Self::Import => "Import",
+            Self::RowsImported => "rows imported",
+            Self::Tables => "tables",
+            Self::RunWithSettings => "Run with session settings",
+            Self::SessionSettingsPlaceholder => "Enter name=value pairs such as work_mem=256MB",
+            Self::ConnectionSettings => "connection settings",
+            Self::ExportView => "Export the view",
+            Self::ExportViewPlaceholder => {
+                "Enter the path of a .txt file, .ansi to keep colors or .sql for INSERT statements"
+            }
+            Self::VisibleRows => "visible rows",
+            Self::AllLoadedRows => "all loaded rows",
+            Self::ViewExported => "exported the view",
+            Self::Lines => "lines",
+            Self::InsertRow => "Insert a row",
+            Self::Back => "Back",
+            Self::DeleteRows => "Delete the selected rows",
+            Self::RowsLocked => "The rows are locked by another transaction",
+            Self::Retry => "Retry",
+            Self::More => "More",
+            Self::ReadOnly => "read-only",
+            Self::ReadOnlyConnection => {
+                "The connection is read-only and only runs SELECT, SHOW and EXPLAIN"
+            }
+            Self::ColumnStats => "Column statistics",
+            Self::StatsRows => "Rows",
+            Self::StatsNonNull => "Non-null",
+            Self::StatsDistinct => "Distinct",
+            Self::StatsMin => "Min",
+            Self::StatsMax => "Max",
+            Self::StatsAverage => "Average",
+            Self::RetriedTransientErrors => "Retried after transient errors",
+            Self::ConfirmWrite => "Change the data of the connection",
+            Self::ConfirmExport => "Write to the file",
+            Self::Statements => "statements",
+            Self::Snippets => "Snippets",
+            Self::SaveSnippet => "Save the query as a snippet",
+            Self::InsertSnippet => "Insert a saved snippet",
+            Self::SnippetNamePlaceholder => "Enter the name of the snippet",
+            Self::SearchSnippetsPlaceholder => "Search the snippets by name or query",
+            Self::CellValue => "Value",
+            Self::OpenCellValue => "Show the whole value of the selected cell",
+            Self::RecalculateWidths => "Measure the widths of the columns from every loaded row",
+            Self::Characters => "chars",
+            Self::Bytes => "bytes",
+            Self::Queue => "Queue",
+            Self::QueueQuery => "Queue the statements to run after the queued ones",
+            Self::QueueAnalyze => "Queue ANALYZE of the selected table",
+            Self::OpenQueue => "Show the queue",
+            Self::CancelQueued => "Cancel or remove",
+            Self::Pending => "pending",
+            Self::Cancelled => "cancelled",
+            Self::Close => "Close",
+            Self::NormalMode => "NORMAL",
+            Self::InsertMode => "INSERT",
+            Self::VisualMode => "VISUAL",
+            Self::GoTo => "Go to a row number or a column",
+            Self::ReferencedTables => "Tables in the query",
+            Self::OpenReferencedTable => "Open a table the query uses",
+            Self::NoReferencedTables => "The query uses no table of the connection",
+            Self::FindTable => "Find a table",
+            Self::FindTablePlaceholder => {
+                "Type some letters of the database, the schema or the table"
+            }
+            Self::ScheduledJobs => "Scheduled jobs",
+            Self::OpenScheduledJobs => "Show the scheduled events or pg_cron jobs",
+            Self::NoScheduledJobs => "No job is scheduled, or the server has no scheduler",
+            Self::LastRun => "last run",
+            Self::ToggleJob => "Enable/disable",
+            Self::EnterPassword => "Password",
+            Self::SaveToKeyring => "Saved to the system keyring once connected",
+            Self::CopyAllRecords => "Copy all records",
+            Self::CopyAsInsert => "Copy as INSERT statements",
+            Self::NoInsertTable => {
+                "the statement of the result reads no table to insert the rows into"
+            }
+            Self::Dump => "Dump",
+            Self::DumpPlaceholder => "Enter the path of the .sql file to dump into",
+            Self::Written => "written",
+            Self::DumpFinished => "Dumped",
+            Self::RecordsFetched => "records fetched",
+            Self::TransactionOpen => "Transaction open",
+            Self::Watching => "Watching every",
+            Self::ToggleWatch => "Watch",
+            Self::WatchReadOnly => "Only statements which read can be watched",
+            Self::PinColumns => "Pin columns up to the selected one",
+            Self::ImportConnections => "Import connections",
+            Self::NoConnectionsToImport => "No new connections found in the files of other tools",
+            Self::ToggleConnection => "Check/uncheck",
+            Self::AddCheckedConnections => "Add the checked connections",
+            Self::ShowHideColumns => "Show/hide columns",
+            Self::ToggleColumn => "Show/hide",
+            Self::ApplyColumns => "Apply",
+            Self::SaveToFile => "Save to a file",
+            Self::SavedTo => "Saved to",
+            Self::ExecuteOtherFetch => "Execute query fetching rows the other way",
+            Self::ToggleFold => "Fold",
+            Self::CopyPretty => "Copy pretty",
+            Self::CopyMinified => "Copy minified",
+            Self::ChildRows => "Child rows",
+            Self::NoChildRows => "No table references this table",
+            Self::OpenChildRows => "Show child rows",
+            Self::DeleteTemplate => "Write a DELETE of the selected rows",
+            Self::UpdateTemplate => "Write an UPDATE of the selected rows",
+            Self::ExecuteRoutine => "Execute procedure/function",
+            Self::OutputParameter => "(output)",
+            Self::NoParameters => "No parameters",
+            Self::ColumnQuality => "Data quality checks",
+            Self::QualityNull => "NULL",
+            Self::QualityEmpty => "Empty strings",
+            Self::QualityDuplicates => "Duplicates",
+            Self::QualityLength => "Length",
+            Self::QualityMatches => "Matches",
+            Self::Triggers => "Triggers",
+            Self::NotSupportedBy => "Not supported by",
+            Self::ConfirmDialect => "Run SQL the engine does not support",
+            Self::SearchMatches => "List the matches of the search",
+            Self::JumpToCell => "Jump to the cell",
+            Self::JoinOn => "Join on",
+            Self::PinRow => "Pin the selected row above the others",
+            Self::BulkEdit => "Edit the selected cells",
+            Self::FillDown => "Fill down",
+            Self::SetNull => "Set to NULL",
+            Self::IncrementCells => "Count up",
+            Self::UndoChange => "Undo the last change",
+            Self::NothingToUndo => "No change was made recently enough to be undone",
+            Self::Warnings => "warnings",
+            Self::ExecutionLog => "Executions",
+            Self::OpenExecutionLog => "Show the statements run so far",
+            Self::Commit => "Commit",
+            Self::Rollback => "Rollback",
+            Self::EndTransaction => "Commit/rollback the transaction",
+            Self::UncommittedTransaction => {
+                "A transaction is open: commit or roll it back, or quit again to roll it back"
+            }
+        }
+    }
+
+    fn ja(self) -> &'static str {
+        match self {
+            Self::GroupGeneral => "-- 全般 --",
+            Self::GroupTable => "-- テーブル --",
+            Self::GroupDatabases => "-- データベース --",
+            Self::Records => "レコード",
+            Self::Columns => "カラム",
+            Self::Constraints => "制約",
+            Self::ForeignKeys => "外部キー",
+            Self::Indexes => "インデックス",
+            Self::Definition => "定義",
+            Self::Error => "エラー",
+            Self::Help => "ヘルプ",
+            Self::Connections => "接続",
+            Self::Databases => "データベース",
+            Self::FilterTables => "テーブルを絞り込む",
+            Self::FilterRecordsPlaceholder => {
+                "レコードを絞り込む WHERE 句の SQL 式を入力してください"
+            }
+            Self::Rows => "行",
+            Self::ColumnCount => "列",
+            Self::Engine => "エンジン",
+            Self::DataSize => "データ",
+            Self::IndexSize => "インデックス",
+            Self::Collation => "照合順序",
+            Self::Scroll => "上下左右にスクロール",
+            Self::ScrollMultipleLines => "複数行ずつ上下にスクロール",
+            Self::ScrollToTopBottom => "先頭/末尾へスクロール",
+            Self::ExpandCollapse => "展開/折りたたみ",
+            Self::RecentDatabases => "最近使ったデータベース",
+            Self::Filter => "絞り込み",
+            Self::MoveFocus => "フォーカスを左右に移動",
+            Self::ExtendSelection => "選択範囲を上下左右に1セル拡張",
+            Self::Tab => "タブ",
+            Self::ToggleWrap => "カラムの折り返し表示を切り替え",
+            Self::ScrollValue => "選択中のセルの値を左右にスクロール",
+            Self::SearchInTable => "読み込み済みの行を検索",
+            Self::JumpToMatch => "次/前の一致に移動",
+            Self::Matches => "件一致",
+            Self::Row => "行",
+            Self::RecordDetail => "レコード",
+            Self::OpenRecordDetail => "選択中のレコードを縦に表示",
+            Self::GenerateFakeData => "ダミーデータを生成",
+            Self::PipeToCommand => "選択中のセルをコマンドに渡す",
+            Self::PipeCommandPlaceholder => "標準入力からTSVを読むシェルコマンドを入力",
+            Self::CommandOutput => "出力",
+            Self::FollowForeignKey => "選択中のセルが参照するレコードを開く",
+            Self::ReferencedTableNotFound => "参照先のテーブルが見つかりません",
+            Self::NotSupportedByServer => "このサーバーではサポートされていません",
+            Self::FreezeRows => "選択中の行までを固定",
+            Self::NumberOfRows => "行数",
+            Self::Preview => "プレビュー",
+            Self::Insert => "挿入",
+            Self::Cancel => "キャンセル",
+            Self::GroupSql => "-- SQL --",
+            Self::Sql => "SQL",
+            Self::SqlEditorPlaceholder => "セミコロン区切りで SQL 文を入力してください",
+            Self::ExecuteQuery => "クエリを実行",
+            Self::FocusSqlEditor => "SQL エディタにフォーカス",
+            Self::SwitchResultTab => "結果タブを切り替え",
+            Self::Result => "結果",
+            Self::RowsAffected => "行が更新されました",
+            Self::SearchHistory => "クエリ履歴を検索",
+            Self::ExplainQuery => "クエリの実行計画を表示",
+            Self::FormatQuery => "クエリを整形",
+            Self::ExplainAnalyzeQuery => "クエリを実行して実行計画を表示",
+            Self::ConfirmExplainAnalyze => "EXPLAIN ANALYZE で文を実行",
+            Self::QueryPlan => "実行計画",
+            Self::RunningQuery => "クエリを実行中",
+            Self::CancelQuery => "実行中のクエリをキャンセル",
+            Self::QueryCancelled => "クエリをキャンセルしました",
+            Self::RunFile => "SQL ファイルを実行",
+            Self::RunFilePlaceholder => ".sql ファイルのパスを入力",
+            Self::StopOnError => "エラーで停止",
+            Self::ContinueOnError => "エラーでも続行",
+            Self::Run => "実行",
+            Self::Succeeded => "成功",
+            Self::Failed => "失敗",
+            Self::Skipped => "スキップ",
+            Self::Remaining => "残り",
+            Self::HistorySearch => "履歴を逆方向に検索",
+            Self::HistorySearchFailed => "履歴に一致なし",
+            Self::AddConnection => "追加",
+            Self::EditConnection => "編集",
+            Self::DeleteConnection => "削除",
+            Self::TestConnection => "接続テスト",
+            Self::ConfirmDeleteConnection => "もう一度押すと接続を削除します",
+            Self::ConnectionSucceeded => "接続に成功しました",
+            Self::ConnectionFailed => "接続に失敗しました",
+            Self::Save => "保存",
+            Self::ChooseSqliteFile => "SQLite または DuckDB ファイルを選択",
+            Self::SchemaSnapshot => "スキーマのスナップショット",
+            Self::SchemaSnapshotPlaceholder => ".json スナップショットのパスを入力",
+            Self::SaveSnapshot => "スキーマを保存",
+            Self::CompareSnapshot => "スキーマと比較",
+            Self::SnapshotSaved => "スナップショットを保存しました",
+            Self::NoSchemaChanges => "スナップショットからの変更はありません",
+            Self::Erd => "リレーション図",
+            Self::CopyDiagram => "コピー",
+            Self::ImportCsv => "CSV をインポート",
+            Self::ImportCsvPlaceholder => ".csv ファイルのパスを入力",
+            Self::FirstRowIsHeader => "1 行目はカラム名",
+            Self::NoHeaderRow => "ヘッダー行なし",
+            Self::EmptyIsNull => "空の値は NULL",
+            Self::EmptyIsEmptyString => "空の値は空文字列",
+            Self::CsvColumn => "CSV の列",
+            Self::SkipColumn => "(スキップ)",
+            Self::DoesNotFit => "は次の型に合いません:",
+            Self::InvalidValues => "個の値がカラムに合いません",
+            Self::NoColumnMapped => "少なくとも 1 つのカラムをファイルの列に対応付けてください",
+            Self::EmptyCsv => "ファイルにインポートする行がありません",
+            Self::Validate => "検証",
+            Self::RowsFit => "行がカラムに合います (何も実行していません)",
+            Self::BatchSize => "INSERT 1 文あたりの行数",
+            Self::Import => "インポート",
+            Self::RowsImported => "行をインポート済み",
+            Self::Tables => "テーブル",
+            Self::RunWithSettings => "セッション設定を指定して実行",
+            Self::SessionSettingsPlaceholder => "work_mem=256MB のように name=value を入力",
+            Self::ConnectionSettings => "接続の設定",
+            Self::ExportView => "表示をエクスポート",
+            Self::ExportViewPlaceholder => ".txt ファイルのパスを入力 (.ansi で色を保持、.sql で INSERT 文)",
+            Self::VisibleRows => "表示中の行",
+            Self::AllLoadedRows => "読み込んだ全行",
+            Self::ViewExported => "表示をエクスポートしました",
+            Self::Lines => "行",
+            Self::InsertRow => "行を挿入",
+            Self::Back => "戻る",
+            Self::DeleteRows => "選択中の行を削除",
+            Self::RowsLocked => "行が他のトランザクションにロックされています",
+            Self::Retry => "再試行",
+            Self::More => "その他",
+            Self::ReadOnly => "読み取り専用",
+            Self::ReadOnlyConnection => {
+                "読み取り専用の接続では SELECT, SHOW, EXPLAIN のみ実行できます"
+            }
+            Self::ColumnStats => "列の統計",
+            Self::StatsRows => "行数",
+            Self::StatsNonNull => "非NULL",
+            Self::StatsDistinct => "種類",
+            Self::StatsMin => "最小",
+            Self::StatsMax => "最大",
+            Self::StatsAverage => "平均",
+            Self::RetriedTransientErrors => "一時的なエラーのため再試行しました",
+            Self::ConfirmWrite => "接続先のデータを変更",
+            Self::ConfirmExport => "ファイルに書き込み",
+            Self::Statements => "文",
+            Self::Snippets => "スニペット",
+            Self::SaveSnippet => "クエリをスニペットとして保存",
+            Self::InsertSnippet => "保存したスニペットを挿入",
+            Self::SnippetNamePlaceholder => "スニペットの名前を入力",
+            Self::SearchSnippetsPlaceholder => "名前かクエリでスニペットを検索",
+            Self::CellValue => "値",
+            Self::OpenCellValue => "選択中のセルの値をすべて表示",
+            Self::RecalculateWidths => "読み込んだすべての行からカラム幅を計算し直す",
+            Self::Characters => "文字",
+            Self::Bytes => "バイト",
+            Self::Queue => "キュー",
+            Self::QueueQuery => "ステートメントをキューに追加して順に実行",
+            Self::QueueAnalyze => "選択中のテーブルの ANALYZE をキューに追加",
+            Self::OpenQueue => "キューを表示",
+            Self::CancelQueued => "キャンセルまたは削除",
+            Self::Pending => "待機中",
+            Self::Cancelled => "キャンセル済み",
+            Self::Close => "閉じる",
+            Self::NormalMode => "ノーマル",
+            Self::InsertMode => "入力",
+            Self::VisualMode => "選択",
+            Self::GoTo => "行番号かカラムへ移動",
+            Self::ReferencedTables => "クエリ内のテーブル",
+            Self::OpenReferencedTable => "クエリが使うテーブルを開く",
+            Self::NoReferencedTables => "クエリは接続先のテーブルを使っていません",
+            Self::FindTable => "テーブルを探す",
+            Self::FindTablePlaceholder => "データベース、スキーマ、テーブルの名前の一部を入力",
+            Self::ScheduledJobs => "スケジュールされたジョブ",
+            Self::OpenScheduledJobs => "スケジュールされたイベントや pg_cron のジョブを表示",
+            Self::NoScheduledJobs => {
+                "スケジュールされたジョブがないか、サーバーにスケジューラがありません"
+            }
+            Self::LastRun => "前回の実行",
+            Self::ToggleJob => "有効化/無効化",
+            Self::EnterPassword => "パスワード",
+            Self::SaveToKeyring => "接続できたらシステムのキーリングに保存されます",
+            Self::CopyAllRecords => "全レコードをコピー",
+            Self::CopyAsInsert => "INSERT 文としてコピー",
+            Self::NoInsertTable => "結果の文が行を挿入するテーブルを読んでいません",
+            Self::Dump => "ダンプ",
+            Self::DumpPlaceholder => "ダンプ先の .sql ファイルのパスを入力",
+            Self::Written => "書き込み済み",
+            Self::DumpFinished => "ダンプが完了しました",
+            Self::RecordsFetched => "件のレコードを取得済み",
+            Self::TransactionOpen => "トランザクション実行中",
+            Self::Watching => "監視中 間隔",
+            Self::ToggleWatch => "監視",
+            Self::WatchReadOnly => "監視できるのは読み取りのみのステートメントです",
+            Self::PinColumns => "選択中の列までを左に固定",
+            Self::ImportConnections => "接続をインポート",
+            Self::NoConnectionsToImport => "他のツールのファイルに新しい接続は見つかりませんでした",
+            Self::ToggleConnection => "チェック/チェック解除",
+            Self::AddCheckedConnections => "チェックした接続を追加",
+            Self::ShowHideColumns => "列の表示/非表示",
+            Self::ToggleColumn => "表示/非表示",
+            Self::ApplyColumns => "適用",
+            Self::SaveToFile => "ファイルに保存",
+            Self::SavedTo => "保存先:",
+            Self::ExecuteOtherFetch => "行の取得方法を切り替えてクエリを実行",
+            Self::ToggleFold => "折りたたむ",
+            Self::CopyPretty => "整形してコピー",
+            Self::CopyMinified => "圧縮してコピー",
+            Self::ChildRows => "子の行",
+            Self::NoChildRows => "このテーブルを参照するテーブルはありません",
+            Self::OpenChildRows => "子の行を表示",
+            Self::DeleteTemplate => "選択中の行の DELETE を書く",
+            Self::UpdateTemplate => "選択中の行の UPDATE を書く",
+            Self::ExecuteRoutine => "プロシージャ/関数を実行",
+            Self::OutputParameter => "(出力)",
+            Self::NoParameters => "パラメータなし",
+            Self::ColumnQuality => "データ品質チェック",
+            Self::QualityNull => "NULL",
+            Self::QualityEmpty => "空文字列",
+            Self::QualityDuplicates => "重複",
+            Self::QualityLength => "長さ",
+            Self::QualityMatches => "形式一致",
+            Self::Triggers => "トリガー",
+            Self::NotSupportedBy => "非対応のエンジン",
+            Self::ConfirmDialect => "エンジンが対応していない SQL を実行",
+            Self::SearchMatches => "検索の一致を一覧表示",
+            Self::JumpToCell => "セルに移動",
+            Self::JoinOn => "結合条件",
+            Self::PinRow => "選択中の行を上に固定して比較",
+            Self::BulkEdit => "選択中のセルを編集",
+            Self::FillDown => "下方向にコピー",
+            Self::SetNull => "NULL にする",
+            Self::IncrementCells => "連番にする",
+            Self::UndoChange => "直前の変更を取り消す",
+            Self::NothingToUndo => "取り消せる最近の変更がありません",
+            Self::Warnings => "件の警告",
+            Self::ExecutionLog => "実行履歴",
+            Self::OpenExecutionLog => "これまでに実行した文を表示",
+            Self::Commit => "コミット",
+            Self::Rollback => "ロールバック",
+            Self::EndTransaction => "トランザクションをコミット/ロールバック",
+            Self::UncommittedTransaction => {
+                "トランザクションが開いています。コミットかロールバックするか、もう一度終了するとロールバックされます"
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Locale, Msg};
+
+    #[test]
+    fn test_text_for() {
+        assert_eq!(Msg::Records.text_for(Locale::En), "Records");
+        assert_eq!(Msg::Records.text_for(Locale::Ja), "レコード");
+    }
+}
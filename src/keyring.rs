@@ -0,0 +1,169 @@
+use anyhow::{anyhow, Result};
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// the service the passwords are stored under
+const SERVICE: &str = "gobang";
+
+/// the exit code of the commands below when the keyring has no password for the account
+const NOT_FOUND: i32 = 44;
+
+/// the environment variable passing the account to the PowerShell scripts, which keeps it out of the script
+const ACCOUNT_VAR: &str = "GOBANG_KEYRING_ACCOUNT";
+
+const VAULT: &str = "[void][Windows.Security.Credentials.PasswordVault,Windows.Security.Credentials,ContentType=WindowsRuntime]; \
+    $vault = New-Object Windows.Security.Credentials.PasswordVault";
+
+/// reads the password of `account` from the Secret Service, the macOS Keychain or the Windows Credential Manager
+pub fn get_password(account: &str) -> Result<Option<String>> {
+    let output = if cfg!(target_os = "macos") {
+        run(
+            Command::new("security").args([
+                "find-generic-password",
+                "-s",
+                SERVICE,
+                "-a",
+                account,
+                "-w",
+            ]),
+            None,
+        )?
+    } else if cfg!(windows) {
+        run(
+            powershell(
+                "try { $credential = $vault.Retrieve('gobang', $env:GOBANG_KEYRING_ACCOUNT) } catch { exit 44 }; \
+                 $credential.RetrievePassword(); [Console]::Out.Write($credential.Password)",
+            )
+            .env(ACCOUNT_VAR, account),
+            None,
+        )?
+    } else {
+        let output = run(
+            Command::new("secret-tool").args(["lookup", "service", SERVICE, "account", account]),
+            None,
+        )?;
+        // secret-tool fails without a message when nothing is stored
+        if !output.status.success() && output.stderr.is_empty() {
+            return Ok(None);
+        }
+        output
+    };
+    if output.status.code() == Some(NOT_FOUND) {
+        return Ok(None);
+    }
+    check(&output)?;
+    let password = String::from_utf8(output.stdout)?;
+    Ok(Some(
+        password
+            .strip_suffix('\n')
+            .unwrap_or(password.as_str())
+            .to_string(),
+    ))
+}
+
+/// stores the password of `account`, replacing the one already stored
+pub fn set_password(account: &str, password: &str) -> Result<()> {
+    let output = if cfg!(target_os = "macos") {
+        // the commands are read from stdin so that the password does not show in the list of processes
+        run(
+            Command::new("security").arg("-i"),
+            Some(&format!(
+                "add-generic-password -U -s {} -a {} -w {}\n",
+                quote(SERVICE),
+                quote(account),
+                quote(password)
+            )),
+        )?
+    } else if cfg!(windows) {
+        run(
+            powershell(
+                "$vault.Add((New-Object Windows.Security.Credentials.PasswordCredential('gobang', \
+                 $env:GOBANG_KEYRING_ACCOUNT, [Console]::In.ReadToEnd())))",
+            )
+            .env(ACCOUNT_VAR, account),
+            Some(password),
+        )?
+    } else {
+        run(
+            Command::new("secret-tool").args([
+                "store",
+                "--label",
+                &format!("{} {}", SERVICE, account),
+                "service",
+                SERVICE,
+                "account",
+                account,
+            ]),
+            Some(password),
+        )?
+    };
+    check(&output)
+}
+
+fn powershell(script: &str) -> Command {
+    let mut command = Command::new("powershell");
+    command.args([
+        "-NoProfile",
+        "-NonInteractive",
+        "-Command",
+        &format!("{}; {}", VAULT, script),
+    ]);
+    command
+}
+
+/// quotes an argument of the commands `security -i` reads
+fn quote(argument: &str) -> String {
+    format!(
+        "\"{}\"",
+        argument.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+fn run(command: &mut Command, input: Option<&str>) -> Result<Output> {
+    let mut process = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => anyhow!(
+                "`{}` is not installed, which is needed to use the system keyring",
+                command.get_program().to_string_lossy()
+            ),
+            _ => anyhow!("`{:?}`: {}", command, e),
+        })?;
+    let mut stdin = process
+        .stdin
+        .take()
+        .ok_or_else(|| anyhow!("`{:?}`", command))?;
+    if let Some(input) = input {
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|e| anyhow!("`{:?}`: {}", command, e))?;
+    }
+    drop(stdin);
+    process
+        .wait_with_output()
+        .map_err(|e| anyhow!("`{:?}`: {}", command, e))
+}
+
+fn check(output: &Output) -> Result<()> {
+    if !output.status.success() {
+        return Err(anyhow!(
+            "failed to use the system keyring: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::quote;
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("gobang"), r#""gobang""#);
+        assert_eq!(quote(r#"pa"ss\word"#), r#""pa\"ss\\word""#);
+    }
+}
@@ -3,8 +3,22 @@ mod cli;
 mod clipboard;
 mod components;
 mod config;
+mod csv;
 mod database;
+mod dump;
+mod erd;
 mod event;
+mod fake_data;
+mod history;
+mod i18n;
+mod import;
+mod keyring;
+mod metadata;
+mod pipe;
+mod snapshot;
+mod snippets;
+mod spill;
+mod theme;
 mod ui;
 mod version;
 
@@ -15,16 +29,26 @@ use crate::app::App;
 use crate::event::{Event, Key};
 use anyhow::Result;
 use crossterm::{
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    event::{DisableMouseCapture, EnableMouseCapture},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
+    },
     ExecutableCommand,
 };
-use std::io;
+use std::io::{self, Write};
 use tui::{backend::CrosstermBackend, Terminal};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let value = crate::cli::parse();
-    let config = config::Config::new(&value.config)?;
+    let mut config = config::Config::new(&value.config)?;
+    if let Some(url) = value.url.as_ref() {
+        let mut conn = config::Connection::from_url(url)?;
+        conn.temporary = true;
+        config.conn.insert(0, conn);
+    }
+    config.locale.init();
+    config.theme.init();
 
     setup_terminal()?;
 
@@ -34,8 +58,18 @@ async fn main() -> anyhow::Result<()> {
     let mut app = App::new(config.clone());
 
     terminal.clear()?;
+    if value.url.is_some() {
+        if let Err(err) = app.open_selected_connection().await {
+            app.error.set(err.to_string())?
+        }
+    }
 
+    let mut title = String::new();
     loop {
+        if app.terminal_title() != title {
+            title = app.terminal_title();
+            set_title(title.as_str())?;
+        }
         terminal.draw(|f| {
             if let Err(err) = app.draw(f) {
                 outln!(config#Error, "error: {}", err.to_string());
@@ -53,7 +87,16 @@ async fn main() -> anyhow::Result<()> {
                 }
                 Err(err) => app.error.set(err.to_string())?,
             },
-            Event::Tick => (),
+            Event::Mouse(mouse) => {
+                if let Err(err) = app.mouse_event(mouse).await {
+                    app.error.set(err.to_string())?
+                }
+            }
+            Event::Tick => {
+                if let Err(err) = app.tick() {
+                    app.error.set(err.to_string())?
+                }
+            }
         }
     }
 
@@ -63,19 +106,48 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// names the terminal, and the window running gobang when it runs in tmux, so that several sessions can be told apart
+fn set_title(title: &str) -> Result<()> {
+    // a name with an escape or a bell in it would end the sequence and write the rest to the terminal
+    let title = title
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect::<String>();
+    let mut stdout = io::stdout();
+    stdout.execute(SetTitle(title.as_str()))?;
+    // tmux names the window with this sequence unless allow-rename is off, and the pane with the one above
+    if std::env::var_os("TMUX").is_some() {
+        write!(stdout, "\x1bk{}\x1b\\", title)?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
 fn setup_terminal() -> Result<()> {
+    // saves the title the terminal had, which is restored on exit
+    write!(io::stdout(), "\x1b[22;0t")?;
     enable_raw_mode()?;
     io::stdout().execute(EnterAlternateScreen)?;
+    io::stdout().execute(EnableMouseCapture)?;
     Ok(())
 }
 
 fn shutdown_terminal() {
+    if let Err(e) = io::stdout().execute(DisableMouseCapture) {
+        eprintln!("disable_mouse_capture failed:\n{}", e);
+    }
+
     let leave_screen = io::stdout().execute(LeaveAlternateScreen).map(|_f| ());
 
     if let Err(e) = leave_screen {
         eprintln!("leave_screen failed:\n{}", e);
     }
 
+    // restores the title saved on start, which terminals without a stack of titles ignore
+    if let Err(e) = write!(io::stdout(), "\x1b[23;0t").and_then(|_| io::stdout().flush()) {
+        eprintln!("restore_title failed:\n{}", e);
+    }
+
     let leave_raw_mode = disable_raw_mode();
 
     if let Err(e) = leave_raw_mode {
@@ -0,0 +1,188 @@
+use crate::database::ForeignKeyReference;
+use database_tree::{Child, Database, Table};
+use std::collections::{BTreeMap, HashMap};
+
+/// the tables and columns seen on the connection, which complete qualified names without asking the server each time
+#[derive(Default)]
+pub struct MetadataCache {
+    /// the database and the table of each table, by the lowercased name of its schema, or of its database when the server has no schemas
+    tables: BTreeMap<String, Vec<(String, Table)>>,
+    /// the column names of the tables fetched so far, by the lowercased name of the table
+    columns: HashMap<String, Vec<String>>,
+    /// the foreign keys of the tables fetched so far, by the lowercased name of the table
+    references: HashMap<String, Vec<ForeignKeyReference>>,
+}
+
+impl MetadataCache {
+    pub fn clear(&mut self) {
+        self.tables.clear();
+        self.columns.clear();
+        self.references.clear();
+    }
+
+    pub fn add_database(&mut self, database: &Database) {
+        for child in &database.children {
+            match child {
+                Child::Table(table) => self.add_table(database.name.as_str(), database, table),
+                Child::Schema(schema) => {
+                    for table in &schema.tables {
+                        self.add_table(schema.name.as_str(), database, table)
+                    }
+                }
+                Child::Routine(_) => (),
+            }
+        }
+    }
+
+    fn add_table(&mut self, qualifier: &str, database: &Database, table: &Table) {
+        let tables = self.tables.entry(qualifier.to_lowercase()).or_default();
+        if !tables.iter().any(|(_, t)| t.name == table.name) {
+            tables.push((database.name.clone(), table.clone()));
+        }
+    }
+
+    pub fn set_columns(&mut self, table: &Table, columns: Vec<String>) {
+        self.columns.insert(table.name.to_lowercase(), columns);
+    }
+
+    pub fn set_references(&mut self, table: &Table, references: Vec<ForeignKeyReference>) {
+        self.references
+            .insert(table.name.to_lowercase(), references);
+    }
+
+    /// returns the names which can follow `qualifier` and a dot, which are the tables of a schema or the columns of a table
+    pub fn names(&self, qualifier: &str) -> Vec<&String> {
+        let qualifier = qualifier.to_lowercase();
+        match self.tables.get(&qualifier) {
+            Some(tables) => tables.iter().map(|(_, table)| &table.name).collect(),
+            None => self
+                .columns
+                .get(&qualifier)
+                .map_or_else(Vec::new, |columns| columns.iter().collect()),
+        }
+    }
+
+    /// tells whether the schema or the database `qualifier`, or any of them when it is not given, has a table named
+    /// `name`, or returns None when the tables of `qualifier` are not known
+    pub fn has_table(&self, qualifier: Option<&str>, name: &str) -> Option<bool> {
+        let name = name.to_lowercase();
+        let is_named = |(_, table): &(String, Table)| table.name.to_lowercase() == name;
+        match qualifier {
+            Some(qualifier) => self
+                .tables
+                .get(&qualifier.to_lowercase())
+                .map(|tables| tables.iter().any(is_named)),
+            None if self.tables.is_empty() => None,
+            None => Some(self.tables.values().flatten().any(is_named)),
+        }
+    }
+
+    /// returns the column names of the table named `table` once they are fetched
+    pub fn columns(&self, table: &str) -> Option<&[String]> {
+        self.columns.get(&table.to_lowercase()).map(Vec::as_slice)
+    }
+
+    /// returns the foreign keys of the table named `table` once they are fetched
+    pub fn references(&self, table: &str) -> Option<&[ForeignKeyReference]> {
+        self.references
+            .get(&table.to_lowercase())
+            .map(Vec::as_slice)
+    }
+
+    /// returns the table named `qualifier` with its database when its columns have not been fetched yet
+    pub fn uncached_table(&self, qualifier: &str) -> Option<(Database, Table)> {
+        let qualifier = qualifier.to_lowercase();
+        if self.tables.contains_key(&qualifier) || self.columns.contains_key(&qualifier) {
+            return None;
+        }
+        self.find_table(qualifier.as_str())
+    }
+
+    /// returns the table named `name` with its database when its foreign keys have not been fetched yet
+    pub fn unreferenced_table(&self, name: &str) -> Option<(Database, Table)> {
+        let name = name.to_lowercase();
+        if self.references.contains_key(&name) {
+            return None;
+        }
+        self.find_table(name.as_str())
+    }
+
+    fn find_table(&self, name: &str) -> Option<(Database, Table)> {
+        self.tables
+            .values()
+            .flatten()
+            .find(|(_, table)| table.name.to_lowercase() == name)
+            .map(|(database, table)| (Database::new(database.clone(), vec![]), table.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MetadataCache;
+    use database_tree::{Child, Database, Schema, Table};
+
+    fn table(name: &str, schema: Option<&str>) -> Table {
+        Table {
+            name: name.to_string(),
+            create_time: None,
+            update_time: None,
+            engine: None,
+            schema: schema.map(|schema| schema.to_string()),
+            view: false,
+        }
+    }
+
+    #[test]
+    fn test_names() {
+        let mut cache = MetadataCache::default();
+        cache.add_database(&Database::new(
+            "shop".to_string(),
+            vec![Child::Schema(Schema {
+                name: "public".to_string(),
+                tables: vec![
+                    table("orders", Some("public")),
+                    table("users", Some("public")),
+                ],
+            })],
+        ));
+        cache.add_database(&Database::new(
+            "blog".to_string(),
+            vec![Child::Table(table("posts", None))],
+        ));
+        assert_eq!(cache.names("PUBLIC"), vec!["orders", "users"]);
+        assert_eq!(cache.names("blog"), vec!["posts"]);
+        assert!(cache.names("orders").is_empty());
+
+        let (database, orders) = cache.uncached_table("Orders").unwrap();
+        assert_eq!(database.name, "shop");
+        cache.set_columns(&orders, vec!["id".to_string(), "status".to_string()]);
+        assert_eq!(cache.names("orders"), vec!["id", "status"]);
+        assert_eq!(cache.uncached_table("orders"), None);
+        assert_eq!(cache.uncached_table("public"), None);
+        assert_eq!(cache.uncached_table("missing"), None);
+
+        let (_, orders) = cache.unreferenced_table("orders").unwrap();
+        assert_eq!(cache.references("orders"), None);
+        cache.set_references(&orders, vec![]);
+        assert_eq!(cache.unreferenced_table("ORDERS"), None);
+        assert_eq!(cache.references("orders"), Some(&[][..]));
+    }
+
+    #[test]
+    fn test_has_table() {
+        let mut cache = MetadataCache::default();
+        assert_eq!(cache.has_table(None, "orders"), None);
+        cache.add_database(&Database::new(
+            "shop".to_string(),
+            vec![Child::Schema(Schema {
+                name: "public".to_string(),
+                tables: vec![table("orders", Some("public"))],
+            })],
+        ));
+        assert_eq!(cache.has_table(None, "Orders"), Some(true));
+        assert_eq!(cache.has_table(Some("public"), "orders"), Some(true));
+        assert_eq!(cache.has_table(Some("public"), "ordrs"), Some(false));
+        assert_eq!(cache.has_table(None, "ordrs"), Some(false));
+        assert_eq!(cache.has_table(Some("pg_catalog"), "pg_tables"), None);
+    }
+}
@@ -0,0 +1,63 @@
+use anyhow::{anyhow, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> Command {
+    let mut c = Command::new("sh");
+    c.arg("-c").arg(command);
+    c
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut c = Command::new("cmd");
+    c.arg("/C").arg(command);
+    c
+}
+
+/// runs `command` in the shell with `text` as its stdin and returns what it printed
+pub fn pipe_to_command(command: &str, text: &str) -> Result<String> {
+    let mut process = shell_command(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("`{}`: {}", command, e))?;
+
+    let mut stdin = process
+        .stdin
+        .take()
+        .ok_or_else(|| anyhow!("`{}`", command))?;
+    let text = text.to_string();
+    // writes from another thread so that a command printing a lot before reading all of its input does not block
+    let writer = std::thread::spawn(move || stdin.write_all(text.as_bytes()));
+
+    let output = process
+        .wait_with_output()
+        .map_err(|e| anyhow!("`{}`: {}", command, e))?;
+    // a command which exits without reading its input closes the pipe, which is not an error
+    let _ = writer.join();
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "`{}`: {} {}",
+            command,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(all(test, not(windows)))]
+mod test {
+    use super::pipe_to_command;
+
+    #[test]
+    fn test_pipe_to_command() {
+        assert_eq!(pipe_to_command("cut -f 2", "1\ta\n2\tb").unwrap(), "a\nb\n");
+        assert_eq!(pipe_to_command("true", "ignored").unwrap(), "");
+        assert!(pipe_to_command("exit 3", "").is_err());
+    }
+}
@@ -0,0 +1,271 @@
+use crate::database::{Pool, TableRow};
+use database_tree::{Child, Database, Table};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// the rows of a part of the structure of a table, such as its columns
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Section {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Section {
+    fn new(rows: Vec<Box<dyn TableRow>>) -> Self {
+        Self {
+            headers: rows.first().map_or_else(Vec::new, |row| row.fields()),
+            rows: rows.iter().map(|row| row.columns()).collect(),
+        }
+    }
+
+    /// identifies a row by its name, with its column for indexes and constraints spanning several columns
+    fn key(&self, row: &[String]) -> String {
+        let key = self
+            .headers
+            .iter()
+            .zip(row)
+            .filter(|(header, _)| *header == "name" || *header == "column_name")
+            .map(|(_, value)| value.as_str())
+            .join(" ");
+        if key.is_empty() {
+            row.join(" ")
+        } else {
+            key
+        }
+    }
+
+    fn diff(&self, live: &Self, table: &str, kind: &str) -> Vec<String> {
+        let baseline = self
+            .rows
+            .iter()
+            .map(|row| (self.key(row), row))
+            .collect::<BTreeMap<String, &Vec<String>>>();
+        let current = live
+            .rows
+            .iter()
+            .map(|row| (live.key(row), row))
+            .collect::<BTreeMap<String, &Vec<String>>>();
+        let mut changes = vec![];
+        for (key, row) in &baseline {
+            match current.get(key) {
+                None => changes.push(format!("- {} {} {}", table, kind, key)),
+                Some(live_row) if live_row != row => changes.push(format!(
+                    "~ {} {} {}: {}",
+                    table,
+                    kind,
+                    key,
+                    live.headers
+                        .iter()
+                        .zip(row.iter().zip(live_row.iter()))
+                        .filter(|(_, (before, after))| before != after)
+                        .map(|(header, (before, after))| format!(
+                            "{} {} -> {}",
+                            header, before, after
+                        ))
+                        .join(", ")
+                )),
+                Some(_) => (),
+            }
+        }
+        for key in current.keys().filter(|key| !baseline.contains_key(*key)) {
+            changes.push(format!("+ {} {} {}", table, kind, key));
+        }
+        changes
+    }
+}
+
+/// the structure of a table
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableSchema {
+    pub columns: Section,
+    pub constraints: Section,
+    pub foreign_keys: Section,
+    pub indexes: Section,
+}
+
+impl TableSchema {
+    fn sections(&self) -> [(&'static str, &Section); 4] {
+        [
+            ("column", &self.columns),
+            ("constraint", &self.constraints),
+            ("foreign key", &self.foreign_keys),
+            ("index", &self.indexes),
+        ]
+    }
+}
+
+/// the structure of every table of a database, saved as JSON to detect changes made to it later
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaSnapshot {
+    pub database: String,
+    pub tables: BTreeMap<String, TableSchema>,
+}
+
+impl SchemaSnapshot {
+    /// reads the structure of the tables of `database` from the server
+    pub async fn capture(pool: &dyn Pool, database: &Database) -> anyhow::Result<Self> {
+        let tables = database
+            .children
+            .iter()
+            .flat_map(|child| match child {
+                Child::Table(table) => vec![table.clone()],
+                Child::Schema(schema) => schema.tables.clone(),
+                Child::Routine(_) => vec![],
+            })
+            .collect::<Vec<Table>>();
+        let mut snapshot = Self {
+            database: database.name.clone(),
+            tables: BTreeMap::new(),
+        };
+        for table in tables {
+            let schema = TableSchema {
+                columns: Section::new(pool.get_columns(database, &table).await?),
+                constraints: Section::new(pool.get_constraints(database, &table).await?),
+                foreign_keys: Section::new(pool.get_foreign_keys(database, &table).await?),
+                indexes: Section::new(pool.get_indexes(database, &table).await?),
+            };
+            let name = match table.schema.as_ref() {
+                Some(schema) => format!("{}.{}", schema, table.name),
+                None => table.name,
+            };
+            snapshot.tables.insert(name, schema);
+        }
+        Ok(snapshot)
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// describes what changed from this snapshot to `live`, one change per line starting with `+`, `-` or `~`
+    pub fn diff(&self, live: &Self) -> Vec<String> {
+        let mut changes = vec![];
+        for (name, table) in &self.tables {
+            match live.tables.get(name) {
+                Some(live_table) => {
+                    for ((kind, section), (_, live_section)) in
+                        table.sections().iter().zip(live_table.sections().iter())
+                    {
+                        changes.extend(section.diff(live_section, name, kind));
+                    }
+                }
+                None => changes.push(format!("- table {}", name)),
+            }
+        }
+        for name in live
+            .tables
+            .keys()
+            .filter(|name| !self.tables.contains_key(*name))
+        {
+            changes.push(format!("+ table {}", name));
+        }
+        changes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SchemaSnapshot, Section, TableSchema};
+
+    fn section(headers: &[&str], rows: &[&[&str]]) -> Section {
+        Section {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            rows: rows
+                .iter()
+                .map(|row| row.iter().map(|value| value.to_string()).collect())
+                .collect(),
+        }
+    }
+
+    fn snapshot(tables: Vec<(&str, TableSchema)>) -> SchemaSnapshot {
+        SchemaSnapshot {
+            database: "shop".to_string(),
+            tables: tables
+                .into_iter()
+                .map(|(name, table)| (name.to_string(), table))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let headers = ["name", "type", "null"];
+        let baseline = snapshot(vec![
+            (
+                "users",
+                TableSchema {
+                    columns: section(
+                        &headers,
+                        &[&["id", "int", "NO"], &["email", "varchar(100)", "YES"]],
+                    ),
+                    indexes: section(
+                        &["name", "column_name", "type"],
+                        &[&["PRIMARY", "id", "BTREE"]],
+                    ),
+                    ..TableSchema::default()
+                },
+            ),
+            ("logs", TableSchema::default()),
+        ]);
+        let live = snapshot(vec![
+            (
+                "users",
+                TableSchema {
+                    columns: section(
+                        &headers,
+                        &[
+                            &["id", "int", "NO"],
+                            &["email", "varchar(255)", "NO"],
+                            &["name", "text", "YES"],
+                        ],
+                    ),
+                    indexes: section(
+                        &["name", "column_name", "type"],
+                        &[
+                            &["PRIMARY", "id", "BTREE"],
+                            &["users_email", "email", "BTREE"],
+                        ],
+                    ),
+                    ..TableSchema::default()
+                },
+            ),
+            ("orders", TableSchema::default()),
+        ]);
+
+        assert_eq!(baseline.diff(&baseline), Vec::<String>::new());
+        assert_eq!(
+            baseline.diff(&live),
+            vec![
+                "- table logs",
+                "~ users column email: type varchar(100) -> varchar(255), null YES -> NO",
+                "+ users column name",
+                "+ users index users_email email",
+                "+ table orders",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path =
+            std::env::temp_dir().join(format!("gobang-snapshot-{}.json", std::process::id()));
+        let baseline = snapshot(vec![(
+            "users",
+            TableSchema {
+                columns: section(&["name", "type"], &[&["id", "int"]]),
+                ..TableSchema::default()
+            },
+        )]);
+        baseline.save(&path).unwrap();
+        let loaded = SchemaSnapshot::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, baseline);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::config::{get_app_config_path, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// a query saved under a name to be inserted into the SQL editor later
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Snippet {
+    pub name: String,
+    pub query: String,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+struct SnippetFile {
+    #[serde(default)]
+    snippet: Vec<Snippet>,
+}
+
+/// the snippets of a connection sorted by name, persisted per connection in a TOML file
+#[derive(Default)]
+pub struct SnippetLibrary {
+    path: Option<PathBuf>,
+    snippets: Vec<Snippet>,
+}
+
+impl SnippetLibrary {
+    pub fn load(conn: &Connection) -> anyhow::Result<Self> {
+        let dir = get_app_config_path()?.join("snippets");
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{}.toml", conn.history_file_name()));
+        let snippets = match std::fs::read_to_string(&path) {
+            Ok(contents) => {
+                toml::from_str::<SnippetFile>(&contents)
+                    .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?
+                    .snippet
+            }
+            Err(_) => vec![],
+        };
+        Ok(Self {
+            path: Some(path),
+            snippets,
+        })
+    }
+
+    /// saves `query` under `name`, replacing the snippet which had that name
+    pub fn save(&mut self, name: &str, query: &str) -> anyhow::Result<()> {
+        let snippet = Snippet {
+            name: name.trim().to_string(),
+            query: query.trim().to_string(),
+        };
+        match self
+            .snippets
+            .iter_mut()
+            .find(|saved| saved.name == snippet.name)
+        {
+            Some(saved) => *saved = snippet,
+            None => self.snippets.push(snippet),
+        }
+        self.snippets.sort_by(|a, b| a.name.cmp(&b.name));
+        if let Some(path) = self.path.as_ref() {
+            let file = SnippetFile {
+                snippet: self.snippets.clone(),
+            };
+            std::fs::write(path, toml::to_string(&file)?)?;
+        }
+        Ok(())
+    }
+
+    /// returns the snippets whose name or query contains `pattern`, ignoring case
+    pub fn search(&self, pattern: &str) -> Vec<&Snippet> {
+        let pattern = pattern.to_lowercase();
+        self.snippets
+            .iter()
+            .filter(|snippet| {
+                snippet.name.to_lowercase().contains(&pattern)
+                    || snippet.query.to_lowercase().contains(&pattern)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SnippetLibrary;
+
+    #[test]
+    fn test_save() {
+        let mut library = SnippetLibrary::default();
+        library.save("users", "SELECT * FROM users").unwrap();
+        library
+            .save(" active ", "SELECT * FROM users WHERE active\n")
+            .unwrap();
+        library.save("users", "SELECT id FROM users").unwrap();
+        let names = library
+            .search("")
+            .iter()
+            .map(|snippet| snippet.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["active", "users"]);
+        assert_eq!(library.search("users")[1].query, "SELECT id FROM users");
+        assert_eq!(
+            library.search("active")[0].query,
+            "SELECT * FROM users WHERE active"
+        );
+    }
+
+    #[test]
+    fn test_search() {
+        let mut library = SnippetLibrary::default();
+        library.save("Active users", "SELECT * FROM users").unwrap();
+        library.save("posts", "SELECT * FROM posts").unwrap();
+        assert_eq!(library.search("ACTIVE").len(), 1);
+        assert_eq!(library.search("from").len(), 2);
+        assert_eq!(library.search("posts")[0].name, "posts");
+        assert!(library.search("comments").is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static SPILL_FILE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+struct Page {
+    offset: u64,
+    len: u64,
+    rows: usize,
+}
+
+/// a temporary file holding rows which have been moved out of memory,
+/// as pages above and below the rows kept in memory
+pub struct SpillFile {
+    path: PathBuf,
+    file: File,
+    above: Vec<Page>,
+    below: Vec<Page>,
+}
+
+/// how many times a name is tried before giving up when the files of the names tried already exist
+const SPILL_FILE_ATTEMPTS: usize = 16;
+
+/// creates a new file only the user can read, failing rather than opening a file or a link which already exists at
+/// `path`
+fn create_private(path: &Path) -> std::io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.read(true).write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+impl SpillFile {
+    pub fn new() -> anyhow::Result<Self> {
+        let mut attempt = 0;
+        let (path, file) = loop {
+            // the time makes the name hard to guess for others who could create it first
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.subsec_nanos())
+                .unwrap_or_default();
+            let path = std::env::temp_dir().join(format!(
+                "gobang-{}-{}-{:08x}.spill",
+                std::process::id(),
+                SPILL_FILE_COUNT.fetch_add(1, Ordering::Relaxed),
+                nanos
+            ));
+            match create_private(&path) {
+                Ok(file) => break (path, file),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists && attempt < SPILL_FILE_ATTEMPTS => {
+                    attempt += 1
+                }
+                Err(e) => return Err(e.into()),
+            }
+        };
+        Ok(Self {
+            path,
+            file,
+            above: Vec::new(),
+            below: Vec::new(),
+        })
+    }
+
+    pub fn has_below(&self) -> bool {
+        !self.below.is_empty()
+    }
+
+    /// returns the number of rows moved out of memory below the rows kept in it
+    pub fn below_row_count(&self) -> usize {
+        self.below.iter().map(|page| page.rows).sum()
+    }
+
+    pub fn push_above(&mut self, rows: &[Vec<Option<String>>]) -> anyhow::Result<()> {
+        let page = self.write(rows)?;
+        self.above.push(page);
+        Ok(())
+    }
+
+    /// returns the rows right above the rows kept in memory
+    pub fn pop_above(&mut self) -> anyhow::Result<Option<Vec<Vec<Option<String>>>>> {
+        match self.above.pop() {
+            Some(page) => Ok(Some(self.read(page)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn push_below(&mut self, rows: &[Vec<Option<String>>]) -> anyhow::Result<()> {
+        let page = self.write(rows)?;
+        self.below.push(page);
+        Ok(())
+    }
+
+    /// returns the rows right below the rows kept in memory
+    pub fn pop_below(&mut self) -> anyhow::Result<Option<Vec<Vec<Option<String>>>>> {
+        match self.below.pop() {
+            Some(page) => Ok(Some(self.read(page)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn write(&mut self, rows: &[Vec<Option<String>>]) -> anyhow::Result<Page> {
+        let bytes = serde_json::to_vec(rows)?;
+        let offset = self.file.seek(SeekFrom::End(0))?;
+        self.file.write_all(&bytes)?;
+        Ok(Page {
+            offset,
+            len: bytes.len() as u64,
+            rows: rows.len(),
+        })
+    }
+
+    fn read(&mut self, page: Page) -> anyhow::Result<Vec<Vec<Option<String>>>> {
+        let mut bytes = vec![0; page.len as usize];
+        self.file.seek(SeekFrom::Start(page.offset))?;
+        self.file.read_exact(&mut bytes)?;
+        // gives the space back when the page was the last one written
+        if page.offset + page.len == self.file.metadata()?.len() {
+            self.file.set_len(page.offset)?;
+        }
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{create_private, SpillFile};
+
+    #[test]
+    fn test_push_and_pop() {
+        let mut spill = SpillFile::new().unwrap();
+        let path = spill.path.clone();
+        spill.push_above(&[vec![Some("a".to_string())]]).unwrap();
+        spill
+            .push_above(&[vec![Some("b".to_string())], vec![Some("c".to_string())]])
+            .unwrap();
+        spill
+            .push_below(&[vec![Some("d".to_string()), None]])
+            .unwrap();
+        assert!(spill.has_below());
+        assert_eq!(spill.below_row_count(), 1);
+        assert_eq!(
+            spill.pop_above().unwrap(),
+            Some(vec![
+                vec![Some("b".to_string())],
+                vec![Some("c".to_string())]
+            ])
+        );
+        assert_eq!(
+            spill.pop_below().unwrap(),
+            Some(vec![vec![Some("d".to_string()), None]])
+        );
+        assert_eq!(spill.pop_below().unwrap(), None);
+        assert_eq!(
+            spill.pop_above().unwrap(),
+            Some(vec![vec![Some("a".to_string())]])
+        );
+        assert_eq!(spill.pop_above().unwrap(), None);
+        drop(spill);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_create_private() {
+        let spill = SpillFile::new().unwrap();
+        // a file which already exists is never opened again
+        assert_eq!(
+            create_private(&spill.path).unwrap_err().kind(),
+            std::io::ErrorKind::AlreadyExists
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(
+                std::fs::metadata(&spill.path).unwrap().permissions().mode() & 0o777,
+                0o600
+            );
+        }
+    }
+}
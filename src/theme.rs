@@ -0,0 +1,245 @@
+use serde::{de, Deserialize, Deserializer};
+use std::sync::RwLock;
+use tui::style::{Color, Modifier, Style};
+
+static THEME: RwLock<Theme> = RwLock::new(Theme::DEFAULT);
+
+/// colors and modifiers of the UI, read from the `[theme]` section of the config
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct Theme {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub selection_bg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub focused_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub unfocused_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub accent_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub error_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub success_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub literal_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub match_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub match_bg: Color,
+    #[serde(deserialize_with = "deserialize_modifiers")]
+    pub header_modifiers: Modifier,
+    pub zebra_stripes: bool,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub stripe_bg: Color,
+    pub column_separators: bool,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub normal_mode_bg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub insert_mode_bg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub visual_mode_bg: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Theme {
+    const DEFAULT: Self = Self {
+        selection_bg: Color::Blue,
+        focused_fg: Color::Reset,
+        unfocused_fg: Color::DarkGray,
+        accent_fg: Color::Blue,
+        error_fg: Color::Red,
+        success_fg: Color::Green,
+        literal_fg: Color::Yellow,
+        match_fg: Color::Black,
+        match_bg: Color::Yellow,
+        header_modifiers: Modifier::BOLD,
+        zebra_stripes: false,
+        stripe_bg: Color::Indexed(236),
+        column_separators: false,
+        normal_mode_bg: Color::Blue,
+        insert_mode_bg: Color::Green,
+        visual_mode_bg: Color::Magenta,
+    };
+
+    /// set the theme used to draw every component
+    pub fn init(self) {
+        *THEME.write().unwrap() = self;
+    }
+
+    pub fn current() -> Self {
+        *THEME.read().unwrap()
+    }
+
+    /// returns the style of the text in a component, which is dimmed while it is not focused
+    pub fn text(self, focused: bool) -> Style {
+        if focused {
+            Style::default().fg(self.focused_fg)
+        } else {
+            self.hint()
+        }
+    }
+
+    /// returns the style of placeholders and key hints
+    pub fn hint(self) -> Style {
+        Style::default().fg(self.unfocused_fg)
+    }
+
+    pub fn selected(self) -> Style {
+        Style::default().bg(self.selection_bg)
+    }
+
+    pub fn accent(self) -> Style {
+        Style::default().fg(self.accent_fg)
+    }
+
+    pub fn error(self) -> Style {
+        Style::default().fg(self.error_fg)
+    }
+
+    pub fn success(self) -> Style {
+        Style::default().fg(self.success_fg)
+    }
+
+    /// returns the style of the numbers, booleans and nulls of a JSON value
+    pub fn literal(self) -> Style {
+        Style::default().fg(self.literal_fg)
+    }
+
+    /// returns the style of the text matching a search
+    pub fn matched(self) -> Style {
+        Style::default().fg(self.match_fg).bg(self.match_bg)
+    }
+
+    /// returns the style of the badge of the input mode in the footer
+    pub fn mode(self, bg: Color) -> Style {
+        Style::default()
+            .fg(Color::Black)
+            .bg(bg)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    pub fn header(self) -> Style {
+        Style::default().add_modifier(self.header_modifiers)
+    }
+
+    /// returns the style of a table row, shading every other one when zebra stripes are enabled
+    pub fn row(self, index: usize) -> Style {
+        if self.zebra_stripes && index % 2 == 1 {
+            Style::default().bg(self.stripe_bg)
+        } else {
+            Style::default()
+        }
+    }
+}
+
+/// parses a color name such as `dark_gray`, a hex code such as `#ff8800` or an index of the 256 colors
+fn parse_color(value: &str) -> Option<Color> {
+    let color = match value.to_lowercase().replace('-', "_").as_str() {
+        "reset" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" => Color::Gray,
+        "dark_gray" => Color::DarkGray,
+        "light_red" => Color::LightRed,
+        "light_green" => Color::LightGreen,
+        "light_yellow" => Color::LightYellow,
+        "light_blue" => Color::LightBlue,
+        "light_magenta" => Color::LightMagenta,
+        "light_cyan" => Color::LightCyan,
+        "white" => Color::White,
+        value => match value.strip_prefix('#') {
+            Some(hex) if hex.len() == 6 => Color::Rgb(
+                u8::from_str_radix(&hex[0..2], 16).ok()?,
+                u8::from_str_radix(&hex[2..4], 16).ok()?,
+                u8::from_str_radix(&hex[4..6], 16).ok()?,
+            ),
+            Some(_) => return None,
+            None => Color::Indexed(value.parse().ok()?),
+        },
+    };
+    Some(color)
+}
+
+fn parse_modifier(value: &str) -> Option<Modifier> {
+    let modifier = match value.to_lowercase().as_str() {
+        "bold" => Modifier::BOLD,
+        "dim" => Modifier::DIM,
+        "italic" => Modifier::ITALIC,
+        "underlined" => Modifier::UNDERLINED,
+        "reversed" => Modifier::REVERSED,
+        "crossed_out" => Modifier::CROSSED_OUT,
+        _ => return None,
+    };
+    Some(modifier)
+}
+
+fn deserialize_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    parse_color(value.as_str())
+        .ok_or_else(|| de::Error::custom(format!("unknown color: {}", value)))
+}
+
+fn deserialize_modifiers<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Modifier, D::Error> {
+    let mut modifiers = Modifier::empty();
+    for value in Vec::<String>::deserialize(deserializer)? {
+        modifiers |= parse_modifier(value.as_str())
+            .ok_or_else(|| de::Error::custom(format!("unknown modifier: {}", value)))?;
+    }
+    Ok(modifiers)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_color, Theme};
+    use tui::style::{Color, Modifier, Style};
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("dark_gray"), Some(Color::DarkGray));
+        assert_eq!(parse_color("Light-Blue"), Some(Color::LightBlue));
+        assert_eq!(parse_color("#ff8800"), Some(Color::Rgb(255, 136, 0)));
+        assert_eq!(parse_color("236"), Some(Color::Indexed(236)));
+        assert_eq!(parse_color("#ff88"), None);
+        assert_eq!(parse_color("purple"), None);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let theme: Theme = toml::from_str(
+            r##"
+selection_bg = "#303030"
+header_modifiers = ["bold", "underlined"]
+"##,
+        )
+        .unwrap();
+        assert_eq!(theme.selection_bg, Color::Rgb(48, 48, 48));
+        assert_eq!(
+            theme.header_modifiers,
+            Modifier::BOLD | Modifier::UNDERLINED
+        );
+        assert_eq!(theme.unfocused_fg, Theme::default().unfocused_fg);
+        assert_eq!(theme.row(1), Style::default());
+        assert!(toml::from_str::<Theme>("error_fg = \"purple\"").is_err());
+    }
+
+    #[test]
+    fn test_row() {
+        let theme = Theme {
+            zebra_stripes: true,
+            ..Theme::default()
+        };
+        assert_eq!(theme.row(0), Style::default());
+        assert_eq!(theme.row(1), Style::default().bg(Color::Indexed(236)));
+        assert_eq!(theme.row(2), Style::default());
+    }
+}
@@ -0,0 +1,58 @@
+use tui::{
+    backend::Backend, buffer::Buffer, layout::Rect, style::Style, symbols::line::VERTICAL,
+    widgets::Widget, Frame,
+};
+
+struct ColumnSeparators {
+    positions: Vec<u16>,
+    style: Style,
+}
+
+impl Widget for ColumnSeparators {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for x in self.positions {
+            if x >= area.right() {
+                break;
+            }
+            for y in area.top()..area.bottom() {
+                buf.set_string(x, y, VERTICAL, self.style);
+            }
+        }
+    }
+}
+
+/// returns the x coordinates of the gaps of `spacing` columns following each of the columns of `widths`
+fn separator_positions(left: u16, widths: &[u16], spacing: u16) -> Vec<u16> {
+    let mut x = left;
+    widths
+        .iter()
+        .map(|width| {
+            x = x.saturating_add(*width);
+            let position = x;
+            x = x.saturating_add(spacing);
+            position
+        })
+        .collect()
+}
+
+/// draws a vertical line in the gap after each of the columns of `widths`, which a table drew from the left of `r`
+pub fn draw_column_separators<B: Backend>(f: &mut Frame<B>, r: Rect, widths: &[u16], style: Style) {
+    f.render_widget(
+        ColumnSeparators {
+            positions: separator_positions(r.x, widths, 1),
+            style,
+        },
+        r,
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::separator_positions;
+
+    #[test]
+    fn test_separator_positions() {
+        assert_eq!(separator_positions(2, &[3, 5, 1], 1), vec![5, 11, 13]);
+        assert_eq!(separator_positions(0, &[], 1), Vec::<u16>::new());
+    }
+}
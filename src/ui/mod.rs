@@ -1,7 +1,10 @@
 use crate::config::KeyConfig;
 use crate::event::Key;
 use database_tree::MoveSelection;
+use unicode_width::UnicodeWidthStr;
 
+pub mod column_separators;
+pub mod screenshot;
 pub mod scrollbar;
 pub mod scrolllist;
 
@@ -28,3 +31,33 @@ pub fn common_nav(key: Key, key_config: &KeyConfig) -> Option<MoveSelection> {
         None
     }
 }
+
+/// returns the index of the tab drawn at `column` cells from the inside of the block of `Tabs`, which pads each title with a space on both sides and divides them with a line
+pub fn tab_at(titles: &[String], column: u16) -> Option<usize> {
+    let mut right = 0;
+    for (index, title) in titles.iter().enumerate() {
+        right += title.width() + 2;
+        if (column as usize) < right {
+            return Some(index);
+        }
+        // the divider
+        right += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::tab_at;
+
+    #[test]
+    fn test_tab_at() {
+        let titles = vec!["ab".to_string(), "cde".to_string()];
+        assert_eq!(tab_at(&titles, 0), Some(0));
+        assert_eq!(tab_at(&titles, 3), Some(0));
+        assert_eq!(tab_at(&titles, 4), Some(1));
+        assert_eq!(tab_at(&titles, 5), Some(1));
+        assert_eq!(tab_at(&titles, 9), Some(1));
+        assert_eq!(tab_at(&titles, 10), None);
+    }
+}
@@ -0,0 +1,137 @@
+use anyhow::Result;
+use tui::{
+    backend::TestBackend,
+    buffer::{Buffer, Cell},
+    style::{Color, Modifier},
+    Frame, Terminal,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// draws with `draw` off screen into a buffer of `width` by `height` cells and returns what was drawn as text
+pub fn render_to_text<F>(width: u16, height: u16, ansi: bool, draw: F) -> Result<String>
+where
+    F: FnOnce(&mut Frame<TestBackend>) -> Result<()>,
+{
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    let mut result = Ok(());
+    let mut draw = Some(draw);
+    terminal.draw(|f| {
+        if let Some(draw) = draw.take() {
+            result = draw(f);
+        }
+    })?;
+    result?;
+    Ok(buffer_to_text(terminal.backend().buffer(), ansi))
+}
+
+/// returns the lines of `buffer` without trailing spaces, with the colors and modifiers as ANSI escape codes if `ansi` is set
+fn buffer_to_text(buffer: &Buffer, ansi: bool) -> String {
+    let area = buffer.area();
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        let mut style = None;
+        let mut skip = 0;
+        for x in area.left()..area.right() {
+            // the cells hidden by a wide character hold a space which must not be written
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let cell = buffer.get(x, y);
+            skip = cell.symbol.width().saturating_sub(1);
+            if ansi {
+                let sgr = sgr(cell);
+                if style.as_ref() != Some(&sgr) {
+                    line.push_str(sgr.as_str());
+                    style = Some(sgr);
+                }
+            }
+            line.push_str(cell.symbol.as_str());
+        }
+        if ansi {
+            line.push_str("\x1b[0m");
+        } else {
+            line.truncate(line.trim_end().len());
+        }
+        text.push_str(line.as_str());
+        text.push('\n');
+    }
+    text
+}
+
+/// returns the Select Graphic Rendition sequence which resets the style and draws `cell`
+fn sgr(cell: &Cell) -> String {
+    let mut params = vec!["0".to_string()];
+    for (modifier, param) in &[
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::CROSSED_OUT, "9"),
+    ] {
+        if cell.modifier.contains(*modifier) {
+            params.push(param.to_string());
+        }
+    }
+    params.extend(color_param(cell.fg, false));
+    params.extend(color_param(cell.bg, true));
+    format!("\x1b[{}m", params.join(";"))
+}
+
+fn color_param(color: Color, background: bool) -> Option<String> {
+    let base = if background { 40 } else { 30 };
+    let param = match color {
+        Color::Reset => return None,
+        Color::Black => base.to_string(),
+        Color::Red => (base + 1).to_string(),
+        Color::Green => (base + 2).to_string(),
+        Color::Yellow => (base + 3).to_string(),
+        Color::Blue => (base + 4).to_string(),
+        Color::Magenta => (base + 5).to_string(),
+        Color::Cyan => (base + 6).to_string(),
+        Color::Gray => (base + 7).to_string(),
+        Color::DarkGray => (base + 60).to_string(),
+        Color::LightRed => (base + 61).to_string(),
+        Color::LightGreen => (base + 62).to_string(),
+        Color::LightYellow => (base + 63).to_string(),
+        Color::LightBlue => (base + 64).to_string(),
+        Color::LightMagenta => (base + 65).to_string(),
+        Color::LightCyan => (base + 66).to_string(),
+        Color::White => (base + 67).to_string(),
+        Color::Indexed(index) => format!("{};5;{}", base + 8, index),
+        Color::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+    };
+    Some(param)
+}
+
+#[cfg(test)]
+mod test {
+    use super::render_to_text;
+    use tui::{
+        style::{Color, Style},
+        text::Span,
+        widgets::{Block, Borders, Paragraph},
+    };
+
+    #[test]
+    fn test_render_to_text() {
+        let draw = |f: &mut tui::Frame<tui::backend::TestBackend>| {
+            f.render_widget(
+                Paragraph::new(Span::styled("名前 id", Style::default().fg(Color::Red)))
+                    .block(Block::default().borders(Borders::ALL)),
+                f.size(),
+            );
+            Ok(())
+        };
+        assert_eq!(
+            render_to_text(10, 3, false, draw).unwrap(),
+            "┌────────┐\n│名前 id │\n└────────┘\n"
+        );
+        assert_eq!(
+            render_to_text(10, 3, true, draw).unwrap().lines().nth(1),
+            Some("\x1b[0m│\x1b[0;31m名前 id\x1b[0m │\x1b[0m")
+        );
+    }
+}
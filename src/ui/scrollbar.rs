@@ -1,18 +1,30 @@
-use easy_cast::CastFloat;
+use crate::theme::Theme;
 use std::convert::TryFrom;
 use tui::{
     backend::Backend,
     buffer::Buffer,
-    layout::{Margin, Rect},
-    style::{Color, Style},
-    symbols::{block::FULL, line::DOUBLE_VERTICAL},
+    layout::Rect,
+    style::Style,
+    symbols::{
+        block::FULL,
+        line::{DOUBLE_HORIZONTAL, DOUBLE_VERTICAL},
+    },
     widgets::Widget,
     Frame,
 };
 
+#[derive(Clone, Copy)]
+enum Orientation {
+    Vertical,
+    Horizontal,
+}
+
 struct Scrollbar {
-    max: u16,
-    pos: u16,
+    max: usize,
+    pos: usize,
+    /// how many lines or columns are in view, which sizes the thumb
+    visible: usize,
+    orientation: Orientation,
     style_bar: Style,
     style_pos: Style,
     inside: bool,
@@ -20,10 +32,19 @@ struct Scrollbar {
 }
 
 impl Scrollbar {
-    fn new(max: usize, pos: usize, border: bool, inside: bool) -> Self {
+    fn new(
+        max: usize,
+        pos: usize,
+        visible: usize,
+        orientation: Orientation,
+        border: bool,
+        inside: bool,
+    ) -> Self {
         Self {
-            max: u16::try_from(max).unwrap_or_default(),
-            pos: u16::try_from(pos).unwrap_or_default(),
+            max,
+            pos,
+            visible,
+            orientation,
             style_pos: Style::default(),
             style_bar: Style::default(),
             inside,
@@ -34,56 +55,112 @@ impl Scrollbar {
 
 impl Widget for Scrollbar {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        if area.height <= 2 {
-            return;
-        }
-
         if self.max == 0 {
             return;
         }
-
-        let right = if self.inside {
-            area.right().saturating_sub(1)
-        } else {
-            area.right()
+        let margin = if self.border { 1 } else { 0 };
+        let (start, length) = match self.orientation {
+            Orientation::Vertical => (area.top() + margin, area.height),
+            Orientation::Horizontal => (area.left() + margin, area.width),
         };
-        if right <= area.left() {
+        if length <= 2 {
             return;
+        }
+        let length = length.saturating_sub(margin * 2);
+        let line = match self.orientation {
+            Orientation::Vertical => {
+                if self.inside {
+                    area.right().saturating_sub(1)
+                } else {
+                    area.right()
+                }
+            }
+            Orientation::Horizontal => {
+                if self.inside {
+                    area.bottom().saturating_sub(1)
+                } else {
+                    area.bottom()
+                }
+            }
         };
-
-        let (bar_top, bar_height) = {
-            let scrollbar_area = area.inner(&Margin {
-                horizontal: 0,
-                vertical: if self.border { 1 } else { 0 },
-            });
-
-            (scrollbar_area.top(), scrollbar_area.height)
+        let (line_start, bar) = match self.orientation {
+            Orientation::Vertical => (area.left(), DOUBLE_VERTICAL),
+            Orientation::Horizontal => (area.top(), DOUBLE_HORIZONTAL),
         };
-
-        for y in bar_top..(bar_top + bar_height) {
-            buf.set_string(right, y, DOUBLE_VERTICAL, self.style_bar);
+        if line <= line_start {
+            return;
         }
 
-        let progress = f32::from(self.pos) / f32::from(self.max);
-        let progress = if progress > 1.0 { 1.0 } else { progress };
-        let pos = f32::from(bar_height) * progress;
-
-        let pos: u16 = pos.cast_nearest();
-        let pos = pos.saturating_sub(1);
-
-        buf.set_string(right, bar_top + pos, FULL, self.style_pos);
+        let (thumb_start, thumb_length) = thumb(length, self.max, self.pos, self.visible);
+        for offset in 0..length {
+            let (symbol, style) = if (thumb_start..thumb_start + thumb_length).contains(&offset) {
+                (FULL, self.style_pos)
+            } else {
+                (bar, self.style_bar)
+            };
+            match self.orientation {
+                Orientation::Vertical => buf.set_string(line, start + offset, symbol, style),
+                Orientation::Horizontal => buf.set_string(start + offset, line, symbol, style),
+            }
+        }
     }
 }
 
+/// returns where the thumb starts on a bar of `length` cells and how long it is, its length being the share of the
+/// content in view when `visible` is known
+fn thumb(length: u16, max: usize, pos: usize, visible: usize) -> (u16, u16) {
+    let length = usize::from(length);
+    let size = (length * visible / (max + visible).max(1)).clamp(1, length);
+    let room = length - size;
+    let start = (room * pos.min(max) + max / 2) / max.max(1);
+    (
+        u16::try_from(start).unwrap_or_default(),
+        u16::try_from(size).unwrap_or_default(),
+    )
+}
+
 pub fn draw_scrollbar<B: Backend>(
     f: &mut Frame<B>,
     r: Rect,
     max: usize,
     pos: usize,
+    visible: usize,
     border: bool,
     inside: bool,
 ) {
-    let mut widget = Scrollbar::new(max, pos, border, inside);
-    widget.style_pos = Style::default().fg(Color::Blue);
+    let mut widget = Scrollbar::new(max, pos, visible, Orientation::Vertical, border, inside);
+    widget.style_pos = Theme::current().accent();
     f.render_widget(widget, r);
 }
+
+/// draws on the bottom border of `r` which columns are in view, the first of them being `pos`
+pub fn draw_horizontal_scrollbar<B: Backend>(
+    f: &mut Frame<B>,
+    r: Rect,
+    max: usize,
+    pos: usize,
+    visible: usize,
+) {
+    let mut widget = Scrollbar::new(max, pos, visible, Orientation::Horizontal, true, true);
+    widget.style_pos = Theme::current().accent();
+    f.render_widget(widget, r);
+}
+
+#[cfg(test)]
+mod test {
+    use super::thumb;
+
+    #[test]
+    fn test_thumb() {
+        // 100 lines with 10 in view on a bar of 10 cells
+        assert_eq!(thumb(10, 90, 0, 10), (0, 1));
+        assert_eq!(thumb(10, 90, 45, 10), (5, 1));
+        assert_eq!(thumb(10, 90, 90, 10), (9, 1));
+        // half of the lines in view
+        assert_eq!(thumb(10, 20, 0, 20), (0, 5));
+        assert_eq!(thumb(10, 20, 20, 20), (5, 5));
+        assert_eq!(thumb(10, 20, 30, 20), (5, 5));
+        // the size of the view is not known
+        assert_eq!(thumb(10, 20, 10, 0), (5, 1));
+    }
+}